@@ -394,6 +394,11 @@ impl CallableType {
 }
 
 impl FunctionIdentifier {
+    /// Is this a built-in function, rather than one defined by a contract?
+    pub fn is_native(&self) -> bool {
+        self.identifier.starts_with("_native_:")
+    }
+
     fn new_native_function(name: &str) -> FunctionIdentifier {
         let identifier = format!("_native_:{name}");
         FunctionIdentifier { identifier }
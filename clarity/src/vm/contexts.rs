@@ -1940,6 +1940,11 @@ impl CallStack {
         self.set.contains(function)
     }
 
+    /// The functions currently being applied, outermost first
+    pub fn functions(&self) -> &[FunctionIdentifier] {
+        &self.stack
+    }
+
     pub fn insert(&mut self, function: &FunctionIdentifier, track: bool) {
         self.stack.push(function.clone());
         if track {
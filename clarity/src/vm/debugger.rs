@@ -0,0 +1,545 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! An interactive step debugger for Clarity, built on `EvalHook`.
+//!
+//! The debugger stops before evaluating an expression whenever it is stepping, or when the
+//! expression starts a line with a breakpoint on it.  While stopped, it reads commands from its
+//! input to inspect local variables, constants, and data-vars, and to decide how to continue.
+//!
+//! Line numbers are only available when built with the `developer-mode` feature, so breakpoints
+//! are refused without it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{BufRead, Write};
+
+use super::EvalHook;
+use crate::vm::contexts::{Environment, LocalContext};
+use crate::vm::errors::Error;
+use crate::vm::types::QualifiedContractIdentifier;
+use crate::vm::{ExecutionResult, SymbolicExpression, Value};
+
+const HELP: &str = "Commands:
+  s, step                  step into the next expression
+  n, next                  step over the current expression
+  c, continue              run until the next breakpoint
+  b, break [CONTRACT:]LINE set a breakpoint (defaults to the current contract)
+  d, delete ID             delete a breakpoint
+  i, info                  list breakpoints
+  l, locals                print the local variables in scope
+  p, print NAME            print a local variable, constant, or data-var
+  bt, backtrace            print the call stack
+  q, quit                  stop debugging, and run to completion
+  h, help                  print this message";
+
+/// Whether breakpoints can be used.  They match expressions by line number, and expressions only
+/// carry line numbers when built with the `developer-mode` feature.
+pub const BREAKPOINTS_SUPPORTED: bool = cfg!(feature = "developer-mode");
+
+/// Why a breakpoint was refused, if `BREAKPOINTS_SUPPORTED` is false
+pub const BREAKPOINTS_UNSUPPORTED: &str =
+    "Breakpoints need line numbers, which are only available when built with the `developer-mode` feature";
+
+/// A place to stop execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakpoint {
+    /// The contract to stop in.  If not given, the breakpoint applies to every contract.
+    pub contract: Option<QualifiedContractIdentifier>,
+    pub line: u32,
+}
+
+impl Breakpoint {
+    /// Parse a breakpoint of the form `[CONTRACT:]LINE`
+    pub fn parse(s: &str) -> Result<Breakpoint, String> {
+        let (contract, line) = match s.rsplit_once(':') {
+            Some((contract, line)) => {
+                let contract = QualifiedContractIdentifier::parse(contract)
+                    .map_err(|e| format!("Invalid contract identifier `{contract}`: {e}"))?;
+                (Some(contract), line)
+            }
+            None => (None, s),
+        };
+        let line = line
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid line number `{line}`"))?;
+        Ok(Breakpoint { contract, line })
+    }
+
+    fn matches(&self, contract: &QualifiedContractIdentifier, line: u32) -> bool {
+        self.line == line && self.contract.as_ref().is_none_or(|c| c == contract)
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.contract {
+            Some(contract) => write!(f, "{contract}:{}", self.line),
+            None => write!(f, "*:{}", self.line),
+        }
+    }
+}
+
+/// A command read from the debugger's input while execution is stopped
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebuggerCommand {
+    StepInto,
+    StepOver,
+    Continue,
+    Break(Breakpoint),
+    Delete(usize),
+    Info,
+    Locals,
+    Print(String),
+    Backtrace,
+    Quit,
+    Help,
+}
+
+impl DebuggerCommand {
+    /// Parse a line of input.  A `break` without a contract applies to `current_contract`.
+    pub fn parse(
+        line: &str,
+        current_contract: &QualifiedContractIdentifier,
+    ) -> Result<DebuggerCommand, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let arg = words.next();
+        let cmd = match (command, arg) {
+            ("s" | "step", None) => DebuggerCommand::StepInto,
+            ("n" | "next", None) => DebuggerCommand::StepOver,
+            ("c" | "continue", None) => DebuggerCommand::Continue,
+            ("b" | "break", Some(arg)) => {
+                let mut breakpoint = Breakpoint::parse(arg)?;
+                if breakpoint.contract.is_none() {
+                    breakpoint.contract = Some(current_contract.clone());
+                }
+                DebuggerCommand::Break(breakpoint)
+            }
+            ("d" | "delete", Some(arg)) => DebuggerCommand::Delete(
+                arg.parse()
+                    .map_err(|_| format!("Invalid breakpoint ID `{arg}`"))?,
+            ),
+            ("i" | "info", None) => DebuggerCommand::Info,
+            ("l" | "locals", None) => DebuggerCommand::Locals,
+            ("p" | "print", Some(arg)) => DebuggerCommand::Print(arg.to_string()),
+            ("bt" | "backtrace", None) => DebuggerCommand::Backtrace,
+            ("q" | "quit", None) => DebuggerCommand::Quit,
+            ("h" | "help", None) => DebuggerCommand::Help,
+            _ => return Err(format!("Invalid command `{}`. Try `help`.", line.trim())),
+        };
+        if words.next().is_some() {
+            return Err(format!("Too many arguments to `{command}`"));
+        }
+        Ok(cmd)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StepMode {
+    /// Stop before the next expression
+    StepInto,
+    /// Stop before the next expression at this depth or shallower
+    StepOver(usize),
+    /// Only stop at breakpoints
+    Continue,
+    /// Never stop again
+    Detached,
+}
+
+/// Interactive debugger.  Commands are read from `input`, and everything the debugger prints goes
+/// to `output`.  If `input` runs out, then the debugger detaches and execution runs to completion.
+pub struct Debugger<R: BufRead, W: Write> {
+    input: R,
+    output: W,
+    breakpoints: BTreeMap<usize, Breakpoint>,
+    next_breakpoint_id: usize,
+    mode: StepMode,
+    /// Depth of the expression currently being evaluated
+    depth: usize,
+    /// Contract and line of the last expression evaluated, so that a breakpoint stops only once
+    /// per visit to its line
+    last_location: Option<(QualifiedContractIdentifier, u32)>,
+    /// Depth of the expression we last stopped at, so its result can be printed
+    stopped_depth: Option<usize>,
+}
+
+impl<R: BufRead, W: Write> Debugger<R, W> {
+    /// Make a debugger that stops before the first expression it sees
+    pub fn new(input: R, output: W) -> Self {
+        Self {
+            input,
+            output,
+            breakpoints: BTreeMap::new(),
+            next_breakpoint_id: 1,
+            mode: StepMode::StepInto,
+            depth: 0,
+            last_location: None,
+            stopped_depth: None,
+        }
+    }
+
+    /// Add a breakpoint, returning its ID
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> usize {
+        let id = self.next_breakpoint_id;
+        self.next_breakpoint_id += 1;
+        self.breakpoints.insert(id, breakpoint);
+        id
+    }
+
+    /// Don't stop until the first breakpoint is reached
+    pub fn run_to_breakpoint(&mut self) {
+        self.mode = StepMode::Continue;
+    }
+
+    /// Consume the debugger, returning its input and output
+    pub fn into_inner(self) -> (R, W) {
+        (self.input, self.output)
+    }
+
+    fn print(&mut self, msg: impl fmt::Display) {
+        // the debugger's output is best-effort
+        let _ = writeln!(self.output, "{msg}");
+    }
+
+    fn print_location(
+        &mut self,
+        contract: &QualifiedContractIdentifier,
+        expr: &SymbolicExpression,
+    ) {
+        let span = expr.span();
+        self.print(format_args!(
+            "{contract}:{}:{}: {expr}",
+            span.start_line, span.start_column
+        ));
+    }
+
+    fn print_locals(&mut self, context: &LocalContext) {
+        let mut lines = vec![];
+        let mut cur_context = Some(context);
+        while let Some(ctx) = cur_context {
+            let mut variables: Vec<_> = ctx.variables.iter().collect();
+            variables.sort_by_key(|(name, _)| *name);
+            for (name, value) in variables.into_iter() {
+                lines.push(format!("{name} = {value}"));
+            }
+            cur_context = ctx.parent;
+        }
+        if lines.is_empty() {
+            self.print("No local variables");
+        }
+        for line in lines.into_iter() {
+            self.print(line);
+        }
+    }
+
+    /// Look up `name` as a local variable, a constant, or a data-var, in that order
+    fn print_variable(&mut self, env: &mut Environment, context: &LocalContext, name: &str) {
+        if let Some(value) = context.lookup_variable(name) {
+            self.print(format_args!("{name} = {value}"));
+            return;
+        }
+        if let Some(value) = env.contract_context.lookup_variable(name) {
+            self.print(format_args!("{name} = {value} (constant)"));
+            return;
+        }
+        let Some(metadata) = env.contract_context.meta_data_var.get(name).cloned() else {
+            self.print(format_args!("No variable named `{name}`"));
+            return;
+        };
+        let contract = env.contract_context.contract_identifier.clone();
+        let epoch = *env.epoch();
+        match env
+            .global_context
+            .database
+            .lookup_variable(&contract, name, &metadata, &epoch)
+        {
+            Ok(value) => self.print(format_args!("{name} = {value} (data-var)")),
+            Err(e) => self.print(format_args!("Failed to load data-var `{name}`: {e}")),
+        }
+    }
+
+    /// Stop before evaluating `expr`, and process commands until told to resume
+    fn stop(&mut self, env: &mut Environment, context: &LocalContext, expr: &SymbolicExpression) {
+        let contract = env.contract_context.contract_identifier.clone();
+        self.print_location(&contract, expr);
+        self.stopped_depth = Some(self.depth);
+        loop {
+            let _ = write!(self.output, "(debug) ");
+            let _ = self.output.flush();
+            let mut line = String::new();
+            match self.input.read_line(&mut line) {
+                Ok(0) | Err(_) => {
+                    self.mode = StepMode::Detached;
+                    return;
+                }
+                Ok(_) => {}
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cmd = match DebuggerCommand::parse(&line, &contract) {
+                Ok(cmd) => cmd,
+                Err(e) => {
+                    self.print(e);
+                    continue;
+                }
+            };
+            match cmd {
+                DebuggerCommand::StepInto => {
+                    self.mode = StepMode::StepInto;
+                    return;
+                }
+                DebuggerCommand::StepOver => {
+                    self.mode = StepMode::StepOver(self.depth);
+                    return;
+                }
+                DebuggerCommand::Continue => {
+                    self.mode = StepMode::Continue;
+                    return;
+                }
+                DebuggerCommand::Quit => {
+                    self.mode = StepMode::Detached;
+                    return;
+                }
+                DebuggerCommand::Break(_) if !BREAKPOINTS_SUPPORTED => {
+                    self.print(BREAKPOINTS_UNSUPPORTED)
+                }
+                DebuggerCommand::Break(breakpoint) => {
+                    let msg = breakpoint.to_string();
+                    let id = self.add_breakpoint(breakpoint);
+                    self.print(format_args!("Breakpoint {id} at {msg}"));
+                }
+                DebuggerCommand::Delete(id) => match self.breakpoints.remove(&id) {
+                    Some(breakpoint) => {
+                        self.print(format_args!("Deleted breakpoint {id} at {breakpoint}"))
+                    }
+                    None => self.print(format_args!("No breakpoint {id}")),
+                },
+                DebuggerCommand::Info => {
+                    if self.breakpoints.is_empty() {
+                        self.print("No breakpoints");
+                    }
+                    let breakpoints: Vec<_> = self
+                        .breakpoints
+                        .iter()
+                        .map(|(id, breakpoint)| format!("{id}: {breakpoint}"))
+                        .collect();
+                    for breakpoint in breakpoints.into_iter() {
+                        self.print(breakpoint);
+                    }
+                }
+                DebuggerCommand::Locals => self.print_locals(context),
+                DebuggerCommand::Print(name) => self.print_variable(env, context, &name),
+                DebuggerCommand::Backtrace => {
+                    let stack_trace = env.call_stack.make_stack_trace();
+                    if stack_trace.is_empty() {
+                        self.print(format_args!("at {contract} (top level)"));
+                    }
+                    for function in stack_trace.iter().rev() {
+                        self.print(format_args!("at {function}"));
+                    }
+                }
+                DebuggerCommand::Help => self.print(HELP),
+            }
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> EvalHook for Debugger<R, W> {
+    fn will_begin_eval(
+        &mut self,
+        env: &mut Environment,
+        context: &LocalContext,
+        expr: &SymbolicExpression,
+    ) {
+        self.depth += 1;
+
+        let contract = &env.contract_context.contract_identifier;
+        let line = expr.span().start_line;
+        let new_line = self
+            .last_location
+            .as_ref()
+            .is_none_or(|(last_contract, last_line)| {
+                last_contract != contract || *last_line != line
+            });
+        if new_line {
+            self.last_location = Some((contract.clone(), line));
+        }
+
+        let stop = match self.mode {
+            StepMode::StepInto => true,
+            StepMode::StepOver(depth) => self.depth <= depth,
+            StepMode::Continue => {
+                new_line
+                    && self
+                        .breakpoints
+                        .values()
+                        .any(|breakpoint| breakpoint.matches(contract, line))
+            }
+            StepMode::Detached => false,
+        };
+        if stop {
+            self.stop(env, context, expr);
+        }
+    }
+
+    fn did_finish_eval(
+        &mut self,
+        _env: &mut Environment,
+        _context: &LocalContext,
+        _expr: &SymbolicExpression,
+        res: &Result<Value, Error>,
+    ) {
+        if self.stopped_depth == Some(self.depth) && self.mode != StepMode::Detached {
+            match res {
+                Ok(value) => self.print(format_args!("=> {value}")),
+                Err(e) => self.print(format_args!("=> error: {e}")),
+            }
+            self.stopped_depth = None;
+        }
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    fn did_complete(&mut self, _result: Result<&mut ExecutionResult, String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use stacks_common::types::StacksEpochId;
+
+    use super::*;
+    use crate::vm::ast::ASTRules;
+    use crate::vm::contexts::OwnedEnvironment;
+    use crate::vm::database::MemoryBackingStore;
+    use crate::vm::types::PrincipalData;
+
+    #[test]
+    fn test_parse_commands() {
+        let contract = QualifiedContractIdentifier::local("foo").unwrap();
+        let other = QualifiedContractIdentifier::local("bar").unwrap();
+        assert_eq!(
+            DebuggerCommand::parse("s\n", &contract),
+            Ok(DebuggerCommand::StepInto)
+        );
+        assert_eq!(
+            DebuggerCommand::parse("  next ", &contract),
+            Ok(DebuggerCommand::StepOver)
+        );
+        assert_eq!(
+            DebuggerCommand::parse("b 12", &contract),
+            Ok(DebuggerCommand::Break(Breakpoint {
+                contract: Some(contract.clone()),
+                line: 12
+            }))
+        );
+        assert_eq!(
+            DebuggerCommand::parse(&format!("break {other}:3"), &contract),
+            Ok(DebuggerCommand::Break(Breakpoint {
+                contract: Some(other.clone()),
+                line: 3
+            }))
+        );
+        assert_eq!(
+            DebuggerCommand::parse("p my-var", &contract),
+            Ok(DebuggerCommand::Print("my-var".into()))
+        );
+        assert!(DebuggerCommand::parse("b", &contract).is_err());
+        assert!(DebuggerCommand::parse("b x", &contract).is_err());
+        assert!(DebuggerCommand::parse("s 1", &contract).is_err());
+        assert!(DebuggerCommand::parse("jump", &contract).is_err());
+
+        assert_eq!(
+            Breakpoint::parse("7"),
+            Ok(Breakpoint {
+                contract: None,
+                line: 7
+            })
+        );
+        assert!(Breakpoint::parse("bad-contract:7").is_err());
+    }
+
+    #[test]
+    fn test_debug_session() {
+        let contract = "(define-constant scale 10)
+            (define-data-var counter int 5)
+            (define-private (add (a int) (b int)) (+ a b))
+            (define-public (run (x int)) (ok (add x (var-get counter))))";
+        let contract_id = QualifiedContractIdentifier::local("debugged").unwrap();
+        let sender = PrincipalData::parse("S1G2081040G2081040G2081040G208105NK8PE5").unwrap();
+
+        let input = "p x\np counter\np scale\np nope\nlocals\nbogus\nn\n".as_bytes();
+        let mut debugger = Debugger::new(input, vec![]);
+        let mut marf = MemoryBackingStore::new();
+        let mut owned_env = OwnedEnvironment::new(marf.as_clarity_db(), StacksEpochId::latest());
+        owned_env
+            .initialize_contract(contract_id.clone(), contract, None, ASTRules::PrecheckSize)
+            .unwrap();
+        owned_env.add_eval_hook(&mut debugger);
+        let (result, _, _) = owned_env
+            .execute_transaction(
+                sender,
+                None,
+                contract_id,
+                "run",
+                &[SymbolicExpression::atom_value(Value::Int(1))],
+            )
+            .unwrap();
+        drop(owned_env);
+        assert_eq!(result, Value::okay(Value::Int(6)).unwrap());
+
+        let (_, output) = debugger.into_inner();
+        let output = String::from_utf8(output).unwrap();
+        // stops at the function body, then steps over it
+        assert!(output.contains("( ok ( add x ( var-get counter ) ) )"));
+        assert!(output.contains("x = 1\n"));
+        assert!(output.contains("counter = 5 (data-var)\n"));
+        assert!(output.contains("scale = 10 (constant)\n"));
+        assert!(output.contains("No variable named `nope`\n"));
+        assert!(output.contains("Invalid command `bogus`"));
+        assert!(output.contains("=> (ok 6)\n"));
+        assert_eq!(output.matches("(debug) ").count(), 7);
+    }
+
+    #[test]
+    #[cfg(not(feature = "developer-mode"))]
+    fn test_breakpoints_unsupported() {
+        let contract_id = QualifiedContractIdentifier::local("debugged").unwrap();
+        let sender = PrincipalData::parse("S1G2081040G2081040G2081040G208105NK8PE5").unwrap();
+
+        let input = "b 1\ninfo\nc\n".as_bytes();
+        let mut debugger = Debugger::new(input, vec![]);
+        let mut marf = MemoryBackingStore::new();
+        let mut owned_env = OwnedEnvironment::new(marf.as_clarity_db(), StacksEpochId::latest());
+        owned_env
+            .initialize_contract(
+                contract_id.clone(),
+                "(define-public (run) (ok 1))",
+                None,
+                ASTRules::PrecheckSize,
+            )
+            .unwrap();
+        owned_env.add_eval_hook(&mut debugger);
+        owned_env
+            .execute_transaction(sender, None, contract_id, "run", &[])
+            .unwrap();
+        drop(owned_env);
+
+        let (_, output) = debugger.into_inner();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(BREAKPOINTS_UNSUPPORTED));
+        assert!(output.contains("No breakpoints\n"));
+    }
+}
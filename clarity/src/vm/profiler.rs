@@ -0,0 +1,465 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Execution cost profiler, built on `EvalHook`.
+//!
+//! The profiler snapshots the cost tracker's running total before and after every expression, so
+//! every charge made while evaluating an expression is attributed to it, less whatever was charged
+//! by the expressions nested inside of it.  Each expression's cost is recorded under the call stack
+//! active when it was evaluated, and under the innermost contract-defined function on that stack.
+//!
+//! Expression spans are only available when built with the `developer-mode` feature.  Without it,
+//! expressions are only labeled by the function they apply.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::Path;
+use std::{fs, io};
+
+use super::EvalHook;
+use crate::vm::contexts::{Environment, LocalContext};
+use crate::vm::costs::ExecutionCost;
+use crate::vm::errors::Error;
+use crate::vm::representations::Span;
+use crate::vm::{ExecutionResult, SymbolicExpression, Value};
+
+/// A single dimension of `ExecutionCost`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostMetric {
+    Runtime,
+    ReadCount,
+    ReadLength,
+    WriteCount,
+    WriteLength,
+}
+
+impl CostMetric {
+    pub const ALL: [CostMetric; 5] = [
+        CostMetric::Runtime,
+        CostMetric::ReadCount,
+        CostMetric::ReadLength,
+        CostMetric::WriteCount,
+        CostMetric::WriteLength,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CostMetric::Runtime => "runtime",
+            CostMetric::ReadCount => "read_count",
+            CostMetric::ReadLength => "read_length",
+            CostMetric::WriteCount => "write_count",
+            CostMetric::WriteLength => "write_length",
+        }
+    }
+
+    /// Get this dimension of `cost`
+    pub fn of(&self, cost: &ExecutionCost) -> u64 {
+        match self {
+            CostMetric::Runtime => cost.runtime,
+            CostMetric::ReadCount => cost.read_count,
+            CostMetric::ReadLength => cost.read_length,
+            CostMetric::WriteCount => cost.write_count,
+            CostMetric::WriteLength => cost.write_length,
+        }
+    }
+}
+
+/// Costs attributed to a single function
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionProfile {
+    /// Number of times the function was called
+    pub calls: u64,
+    /// Cost of evaluating the function's own expressions
+    pub self_cost: ExecutionCost,
+    /// Cost of evaluating the function, including the functions it calls
+    pub total_cost: ExecutionCost,
+}
+
+impl Default for FunctionProfile {
+    fn default() -> Self {
+        Self {
+            calls: 0,
+            self_cost: ExecutionCost::ZERO,
+            total_cost: ExecutionCost::ZERO,
+        }
+    }
+}
+
+/// An expression that is being evaluated
+struct Frame {
+    /// Cost tracker total when evaluation began
+    start: ExecutionCost,
+    /// Total cost of the expressions evaluated within this one
+    children: ExecutionCost,
+    /// Collapsed call stack, ending with this expression
+    path: String,
+    /// Innermost contract-defined function on the call stack
+    function: String,
+    /// Is this the body of a newly-called function?
+    new_call: bool,
+    /// Number of contract-defined functions on the call stack
+    function_depth: usize,
+}
+
+fn saturating_add(total: &mut ExecutionCost, cost: &ExecutionCost) {
+    total.runtime = total.runtime.saturating_add(cost.runtime);
+    total.read_count = total.read_count.saturating_add(cost.read_count);
+    total.read_length = total.read_length.saturating_add(cost.read_length);
+    total.write_count = total.write_count.saturating_add(cost.write_count);
+    total.write_length = total.write_length.saturating_add(cost.write_length);
+}
+
+fn saturating_sub(total: &ExecutionCost, cost: &ExecutionCost) -> ExecutionCost {
+    ExecutionCost {
+        runtime: total.runtime.saturating_sub(cost.runtime),
+        read_count: total.read_count.saturating_sub(cost.read_count),
+        read_length: total.read_length.saturating_sub(cost.read_length),
+        write_count: total.write_count.saturating_sub(cost.write_count),
+        write_length: total.write_length.saturating_sub(cost.write_length),
+    }
+}
+
+/// Label an expression with the function it applies (or the variable it names), and its span
+fn expression_label(expr: &SymbolicExpression) -> String {
+    let name = if let Some(list) = expr.match_list() {
+        list.first()
+            .and_then(|head| head.match_atom())
+            .map_or("list", |name| name.as_str())
+    } else if let Some(name) = expr.match_atom() {
+        name.as_str()
+    } else {
+        "value"
+    };
+    let span = expr.span();
+    if *span == Span::ZERO {
+        name.to_string()
+    } else {
+        format!("{name}@{}:{}", span.start_line, span.start_column)
+    }
+}
+
+/// Attributes execution costs to call stacks and functions
+#[derive(Default)]
+pub struct CostProfiler {
+    frames: Vec<Frame>,
+    /// Cost of each expression (less its children), keyed by collapsed call stack
+    stacks: HashMap<String, ExecutionCost>,
+    functions: HashMap<String, FunctionProfile>,
+}
+
+impl CostProfiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn functions(&self) -> &HashMap<String, FunctionProfile> {
+        &self.functions
+    }
+
+    /// Write the profile in the collapsed-stack format used by flamegraph tools, for one metric.
+    /// Stacks that cost nothing in this metric are omitted.
+    pub fn write_collapsed<W: Write>(&self, w: &mut W, metric: CostMetric) -> io::Result<()> {
+        let stacks: BTreeMap<_, _> = self.stacks.iter().collect();
+        for (path, cost) in stacks.into_iter() {
+            let value = metric.of(cost);
+            if value > 0 {
+                writeln!(w, "{path} {value}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the per-function cost table as CSV, most expensive (by total runtime) first
+    pub fn write_function_table<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "function,calls")?;
+        for metric in CostMetric::ALL.iter() {
+            write!(w, ",total_{0},self_{0}", metric.name())?;
+        }
+        writeln!(w)?;
+
+        let mut functions: Vec<_> = self.functions.iter().collect();
+        functions.sort_by(|(a_name, a), (b_name, b)| {
+            b.total_cost
+                .runtime
+                .cmp(&a.total_cost.runtime)
+                .then_with(|| a_name.cmp(b_name))
+        });
+        for (name, profile) in functions.into_iter() {
+            write!(w, "{name},{}", profile.calls)?;
+            for metric in CostMetric::ALL.iter() {
+                write!(
+                    w,
+                    ",{},{}",
+                    metric.of(&profile.total_cost),
+                    metric.of(&profile.self_cost)
+                )?;
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    /// Create `dir` if needed and check that a profile can be written to it, so that an unusable
+    /// directory is reported before anything is profiled
+    pub fn check_output_dir(dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::File::create(dir.join("functions.csv")).map(|_| ())
+    }
+
+    /// Write `<metric>.folded` for each metric, and `functions.csv`, to `dir`
+    pub fn write_to_dir(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        for metric in CostMetric::ALL.iter() {
+            let mut file = fs::File::create(dir.join(format!("{}.folded", metric.name())))?;
+            self.write_collapsed(&mut file, *metric)?;
+        }
+        let mut file = fs::File::create(dir.join("functions.csv"))?;
+        self.write_function_table(&mut file)
+    }
+}
+
+impl EvalHook for CostProfiler {
+    fn will_begin_eval(
+        &mut self,
+        env: &mut Environment,
+        _context: &LocalContext,
+        expr: &SymbolicExpression,
+    ) {
+        let functions = env.call_stack.functions();
+        let mut path = if functions.is_empty() {
+            env.contract_context.contract_identifier.to_string()
+        } else {
+            functions
+                .iter()
+                .map(|identifier| identifier.to_string())
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        // built-ins (e.g. `let`) stay on the call stack while their arguments are evaluated, but
+        // the table only tracks contract-defined functions
+        let function = functions
+            .iter()
+            .rev()
+            .find(|identifier| !identifier.is_native())
+            .map_or_else(
+                || env.contract_context.contract_identifier.to_string(),
+                |identifier| identifier.to_string(),
+            );
+        let function_depth = functions
+            .iter()
+            .filter(|identifier| !identifier.is_native())
+            .count();
+        path.push(';');
+        path.push_str(&expression_label(expr));
+
+        // top-level expressions count as calls to the contract itself
+        let new_call = self
+            .frames
+            .last()
+            .is_none_or(|parent| function_depth > parent.function_depth);
+        self.frames.push(Frame {
+            start: env.global_context.cost_track.get_total(),
+            children: ExecutionCost::ZERO,
+            path,
+            function,
+            new_call,
+            function_depth,
+        });
+    }
+
+    fn did_finish_eval(
+        &mut self,
+        env: &mut Environment,
+        _context: &LocalContext,
+        _expr: &SymbolicExpression,
+        _res: &Result<Value, Error>,
+    ) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        let total = saturating_sub(&env.global_context.cost_track.get_total(), &frame.start);
+        let self_cost = saturating_sub(&total, &frame.children);
+        if let Some(parent) = self.frames.last_mut() {
+            saturating_add(&mut parent.children, &total);
+        }
+
+        saturating_add(
+            self.stacks.entry(frame.path).or_insert(ExecutionCost::ZERO),
+            &self_cost,
+        );
+        let profile = self.functions.entry(frame.function).or_default();
+        saturating_add(&mut profile.self_cost, &self_cost);
+        if frame.new_call {
+            profile.calls = profile.calls.saturating_add(1);
+            saturating_add(&mut profile.total_cost, &total);
+        }
+    }
+
+    fn did_complete(&mut self, _result: Result<&mut ExecutionResult, String>) {}
+}
+
+thread_local! {
+    static THREAD_PROFILER: RefCell<Option<CostProfiler>> = const { RefCell::new(None) };
+}
+
+/// Start profiling every transaction evaluated on this thread, via `ThreadProfilerHook`
+pub fn start_thread_profiler() {
+    THREAD_PROFILER.with(|profiler| profiler.replace(Some(CostProfiler::new())));
+}
+
+/// Stop profiling this thread, and return the profile
+pub fn stop_thread_profiler() -> Option<CostProfiler> {
+    THREAD_PROFILER.with(|profiler| profiler.take())
+}
+
+/// Is this thread being profiled?
+pub fn thread_profiler_active() -> bool {
+    THREAD_PROFILER.with(|profiler| profiler.borrow().is_some())
+}
+
+/// Forwards evaluation events to this thread's profiler, if there is one.
+/// This lets tools profile code paths that construct their own `OwnedEnvironment`.
+pub struct ThreadProfilerHook;
+
+impl EvalHook for ThreadProfilerHook {
+    fn will_begin_eval(
+        &mut self,
+        env: &mut Environment,
+        context: &LocalContext,
+        expr: &SymbolicExpression,
+    ) {
+        THREAD_PROFILER.with(|profiler| {
+            if let Some(profiler) = profiler.borrow_mut().as_mut() {
+                profiler.will_begin_eval(env, context, expr);
+            }
+        })
+    }
+
+    fn did_finish_eval(
+        &mut self,
+        env: &mut Environment,
+        context: &LocalContext,
+        expr: &SymbolicExpression,
+        res: &Result<Value, Error>,
+    ) {
+        THREAD_PROFILER.with(|profiler| {
+            if let Some(profiler) = profiler.borrow_mut().as_mut() {
+                profiler.did_finish_eval(env, context, expr, res);
+            }
+        })
+    }
+
+    fn did_complete(&mut self, _result: Result<&mut ExecutionResult, String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use stacks_common::types::StacksEpochId;
+
+    use super::*;
+    use crate::vm::ast::ASTRules;
+    use crate::vm::contexts::OwnedEnvironment;
+    use crate::vm::database::MemoryBackingStore;
+    use crate::vm::types::{PrincipalData, QualifiedContractIdentifier};
+
+    const CONTRACT: &str = "(define-data-var counter int 0)
+        (define-private (bump (n int))
+            (begin (var-set counter (+ (var-get counter) n)) (var-get counter)))
+        (define-public (run (x int)) (ok (+ (bump x) (bump x))))";
+
+    fn run_profiled(hook: &mut dyn EvalHook) -> QualifiedContractIdentifier {
+        let contract_id = QualifiedContractIdentifier::local("profiled").unwrap();
+        let sender = PrincipalData::parse("S1G2081040G2081040G2081040G208105NK8PE5").unwrap();
+        let mut marf = MemoryBackingStore::new();
+        let mut owned_env = OwnedEnvironment::new(marf.as_clarity_db(), StacksEpochId::latest());
+        owned_env
+            .initialize_contract(contract_id.clone(), CONTRACT, None, ASTRules::PrecheckSize)
+            .unwrap();
+        owned_env.add_eval_hook(hook);
+        let (result, _, _) = owned_env
+            .execute_transaction(
+                sender,
+                None,
+                contract_id.clone(),
+                "run",
+                &[SymbolicExpression::atom_value(Value::Int(2))],
+            )
+            .unwrap();
+        assert_eq!(result, Value::okay(Value::Int(6)).unwrap());
+        contract_id
+    }
+
+    #[test]
+    fn test_cost_profiler_calls() {
+        // costs are not tracked here, since the cost contracts are not deployed
+        let mut profiler = CostProfiler::new();
+        let contract_id = run_profiled(&mut profiler);
+
+        let run = &profiler.functions()[&format!("{contract_id}:run")];
+        let bump = &profiler.functions()[&format!("{contract_id}:bump")];
+        assert_eq!(profiler.functions().len(), 2);
+        assert_eq!(run.calls, 1);
+        assert_eq!(bump.calls, 2);
+        assert!(profiler.frames.is_empty());
+        assert!(
+            profiler
+                .stacks
+                .keys()
+                .any(|path| path
+                    .starts_with(&format!("{contract_id}:run;{contract_id}:bump;var-set")))
+        );
+
+        let mut table = vec![];
+        profiler.write_function_table(&mut table).unwrap();
+        let table = String::from_utf8(table).unwrap();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("function,calls,total_runtime,self_runtime,"));
+        assert!(lines[1].starts_with(&format!("{contract_id}:bump,2,0,0,")));
+        assert!(lines[2].starts_with(&format!("{contract_id}:run,1,0,0,")));
+    }
+
+    #[test]
+    fn test_thread_profiler() {
+        // nothing is recorded without a profiler
+        assert!(!thread_profiler_active());
+        run_profiled(&mut ThreadProfilerHook);
+        assert!(stop_thread_profiler().is_none());
+
+        start_thread_profiler();
+        assert!(thread_profiler_active());
+        let contract_id = run_profiled(&mut ThreadProfilerHook);
+        let profiler = stop_thread_profiler().unwrap();
+        assert!(!thread_profiler_active());
+        assert_eq!(
+            profiler.functions()[&format!("{contract_id}:bump")].calls,
+            2
+        );
+    }
+
+    #[test]
+    fn test_check_output_dir() {
+        let dir = std::env::temp_dir().join(format!("profile-{}", std::process::id()));
+        CostProfiler::check_output_dir(&dir.join("nested")).unwrap();
+        assert!(dir.join("nested").join("functions.csv").is_file());
+
+        // a directory cannot be created under a file
+        let file = dir.join("file");
+        fs::File::create(&file).unwrap();
+        CostProfiler::check_output_dir(&file.join("profile")).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -225,23 +225,48 @@ pub fn run_http_request<S: Read + Write>(
     path: &str,
     content_type: Option<&str>,
     payload: &[u8],
+) -> Result<Vec<u8>, RPCError> {
+    run_authorized_http_request(sock, host, verb, path, content_type, None, payload)
+}
+
+/// Run an HTTP request, synchronously, through the given read/write handle, sending
+/// `authorization` (if given) in its `Authorization` header.
+/// Return the HTTP reply, decoded if it was chunked
+pub fn run_authorized_http_request<S: Read + Write>(
+    sock: &mut S,
+    host: &str,
+    verb: &str,
+    path: &str,
+    content_type: Option<&str>,
+    authorization: Option<&str>,
+    payload: &[u8],
 ) -> Result<Vec<u8>, RPCError> {
     let content_length_hdr = if !payload.is_empty() {
         format!("Content-Length: {}\r\n", payload.len())
     } else {
         "".to_string()
     };
+    let authorization_hdr = authorization
+        .map(|authorization| format!("Authorization: {authorization}\r\n"))
+        .unwrap_or_default();
 
     let req_txt = if let Some(content_type) = content_type {
         format!(
-            "{verb} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\nContent-Type: {content_type}\r\n{content_length_hdr}User-Agent: libsigner/0.1\r\nAccept: */*\r\n\r\n"
+            "{verb} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\nContent-Type: {content_type}\r\n{authorization_hdr}{content_length_hdr}User-Agent: libsigner/0.1\r\nAccept: */*\r\n\r\n"
         )
     } else {
         format!(
-            "{verb} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n{content_length_hdr}User-Agent: libsigner/0.1\r\nAccept: */*\r\n\r\n"
+            "{verb} {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n{authorization_hdr}{content_length_hdr}User-Agent: libsigner/0.1\r\nAccept: */*\r\n\r\n"
         )
     };
-    debug!("HTTP request\n{}", &req_txt);
+    if authorization.is_some() {
+        debug!(
+            "HTTP request\n{}",
+            req_txt.replace(&authorization_hdr, "Authorization: <redacted>\r\n")
+        );
+    } else {
+        debug!("HTTP request\n{}", &req_txt);
+    }
 
     sock.write_all(req_txt.as_bytes())?;
     sock.write_all(payload)?;
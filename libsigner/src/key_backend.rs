@@ -0,0 +1,60 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fmt::Debug;
+
+use stacks_common::types::chainstate::{StacksPrivateKey, StacksPublicKey};
+use stacks_common::types::PrivateKey;
+use stacks_common::util::secp256k1::MessageSignature;
+
+/// Errors from a [KeyBackend]
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum KeyBackendError {
+    /// The backend failed to produce a signature, e.g. because its key is held by another
+    /// process which could not be reached
+    #[error("Failed to sign digest: {0}")]
+    SigningFailed(String),
+}
+
+/// A source of signatures for a signer's messages.
+///
+/// Everything a signer signs (block responses, mock signatures, StackerDB chunks) is a
+/// signature over a 32-byte digest, so a backend only has to be able to report its public key
+/// and sign a digest. This lets the signing key live outside of the signer process.
+pub trait KeyBackend: Debug + Send + Sync {
+    /// The public key that this backend's signatures verify against
+    fn public_key(&self) -> StacksPublicKey;
+
+    /// Sign a 32-byte digest, such as a block's `signer_signature_hash` or a structured data
+    /// message hash
+    fn sign_digest(&self, digest: &[u8]) -> Result<MessageSignature, KeyBackendError>;
+
+    /// Check that the backend can sign with its public key, e.g. that a key held by another
+    /// process is reachable. Backends which hold their key in memory are always ready.
+    fn check_ready(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl KeyBackend for StacksPrivateKey {
+    fn public_key(&self) -> StacksPublicKey {
+        StacksPublicKey::from_private(self)
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<MessageSignature, KeyBackendError> {
+        self.sign(digest)
+            .map_err(|e| KeyBackendError::SigningFailed(e.to_string()))
+    }
+}
@@ -39,6 +39,7 @@ mod tests;
 mod error;
 mod events;
 mod http;
+mod key_backend;
 mod runloop;
 mod session;
 mod signer_set;
@@ -60,6 +61,7 @@ pub use crate::events::{
     BlockProposal, BlockProposalData, BurnBlockEvent, EventReceiver, EventStopSignaler,
     SignerEvent, SignerEventReceiver, SignerEventTrait, SignerStopSignaler, StacksBlockEvent,
 };
+pub use crate::key_backend::{KeyBackend, KeyBackendError};
 pub use crate::runloop::{RunningSigner, Signer, SignerRunLoop};
 pub use crate::session::{SignerSession, StackerDBSession};
 pub use crate::signer_set::{Error as ParseSignerEntriesError, SignerEntries};
@@ -14,8 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::io::{self, Read, Write};
 use std::net::TcpStream;
 use std::str;
+use std::sync::Arc;
 
 use clarity::vm::types::QualifiedContractIdentifier;
 use libstackerdb::{
@@ -23,10 +25,11 @@ use libstackerdb::{
     StackerDBChunkAckData, StackerDBChunkData, SIGNERS_STACKERDB_CHUNK_SIZE,
     STACKERDB_MAX_CHUNK_SIZE,
 };
+use rustls::{ClientConfig, ClientConnection, ServerName, StreamOwned};
 use stacks_common::codec::StacksMessageCodec;
 
 use crate::error::RPCError;
-use crate::http::run_http_request;
+use crate::http::run_authorized_http_request;
 
 /// Trait for connecting to and querying a signer Stacker DB replica
 pub trait SignerSession {
@@ -94,6 +97,38 @@ pub trait SignerSession {
     }
 }
 
+/// A connection to the replica, with or without TLS
+#[derive(Debug)]
+enum SessionSocket {
+    Plain(TcpStream),
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+impl Read for SessionSocket {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            SessionSocket::Plain(sock) => sock.read(buf),
+            SessionSocket::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for SessionSocket {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            SessionSocket::Plain(sock) => sock.write(buf),
+            SessionSocket::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            SessionSocket::Plain(sock) => sock.flush(),
+            SessionSocket::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
 /// signer session for a stackerdb instance
 #[derive(Debug)]
 pub struct StackerDBSession {
@@ -101,8 +136,12 @@ pub struct StackerDBSession {
     pub host: String,
     /// contract we're talking to
     pub stackerdb_contract_id: QualifiedContractIdentifier,
+    /// TLS configuration, if the replica is reached over HTTPS
+    tls_config: Option<Arc<ClientConfig>>,
+    /// API key sent in the `Authorization` header of each request
+    api_key: Option<String>,
     /// connection to the replica
-    sock: Option<TcpStream>,
+    sock: Option<SessionSocket>,
 }
 
 impl StackerDBSession {
@@ -111,21 +150,57 @@ impl StackerDBSession {
         StackerDBSession {
             host: host.to_owned(),
             stackerdb_contract_id,
+            tls_config: None,
+            api_key: None,
             sock: None,
         }
     }
 
+    /// Talk to the replica over HTTPS with this TLS configuration
+    pub fn with_tls(mut self, tls_config: Arc<ClientConfig>) -> StackerDBSession {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Send this API key with each request.  Nodes that set `stackerdb_write_requires_api_key`
+    /// only accept chunks carrying a key with the `stackerdb-write` scope.
+    pub fn with_api_key(mut self, api_key: String) -> StackerDBSession {
+        self.api_key = Some(api_key);
+        self
+    }
+
+    /// The name to verify the replica's certificate against: the host without its port
+    fn server_name(&self) -> Result<ServerName, RPCError> {
+        let hostname = match self.host.rsplit_once(':') {
+            Some((hostname, port)) if port.parse::<u16>().is_ok() => hostname,
+            _ => self.host.as_str(),
+        };
+        let hostname = hostname.trim_start_matches('[').trim_end_matches(']');
+        ServerName::try_from(hostname).map_err(|e| {
+            RPCError::MalformedRequest(format!("Invalid TLS server name '{hostname}': {e}"))
+        })
+    }
+
     /// connect or reconnect to the node
     fn connect_or_reconnect(&mut self) -> Result<(), RPCError> {
         debug!("connect to {}", &self.host);
-        self.sock = Some(TcpStream::connect(&self.host)?);
+        let sock = TcpStream::connect(&self.host)?;
+        let sock = match self.tls_config.as_ref() {
+            Some(tls_config) => {
+                let session = ClientConnection::new(tls_config.clone(), self.server_name()?)
+                    .map_err(io::Error::other)?;
+                SessionSocket::Tls(Box::new(StreamOwned::new(session, sock)))
+            }
+            None => SessionSocket::Plain(sock),
+        };
+        self.sock = Some(sock);
         Ok(())
     }
 
     /// Do something with the connected socket
     fn with_socket<F, R>(&mut self, todo: F) -> Result<R, RPCError>
     where
-        F: FnOnce(&mut StackerDBSession, &mut TcpStream) -> R,
+        F: FnOnce(&mut StackerDBSession, &mut SessionSocket) -> R,
     {
         // TODO: fix this so we can use persistent connection
         // See https://github.com/stacks-network/stacks-blockchain/issues/3922
@@ -154,7 +229,15 @@ impl StackerDBSession {
         payload: &[u8],
     ) -> Result<Vec<u8>, RPCError> {
         self.with_socket(|session, sock| {
-            run_http_request(sock, &session.host, verb, path, content_type, payload)
+            run_authorized_http_request(
+                sock,
+                &session.host,
+                verb,
+                path,
+                content_type,
+                session.api_key.as_deref(),
+                payload,
+            )
         })?
     }
 }
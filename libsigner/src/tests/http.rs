@@ -21,7 +21,10 @@ use hashbrown::HashMap;
 use stacks_common::util::chunked_encoding::*;
 
 use crate::error::{EventError, RPCError};
-use crate::http::{decode_http_body, decode_http_request, decode_http_response, run_http_request};
+use crate::http::{
+    decode_http_body, decode_http_request, decode_http_response, run_authorized_http_request,
+    run_http_request,
+};
 
 #[test]
 fn test_decode_http_request_ok() {
@@ -340,3 +343,23 @@ fn test_run_http_request_no_body() {
         assert!(result_plain.is_empty());
     }
 }
+
+#[test]
+fn test_run_authorized_http_request() {
+    let mut msock = MockHTTPSocket::new(
+        "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-type: text/plain\r\n\r\n".to_string(),
+    );
+    run_authorized_http_request(
+        &mut msock,
+        "127.0.0.1:20443",
+        "POST",
+        "/v2/stackerdb/SP000000000000000000002Q6VF78/signers-0-0/chunks",
+        Some("application/json"),
+        Some("signer-key"),
+        "{}".as_bytes(),
+    )
+    .unwrap();
+
+    let request = String::from_utf8(msock.request).unwrap();
+    assert!(request.contains("\r\nAuthorization: signer-key\r\n"));
+}
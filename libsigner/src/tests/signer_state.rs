@@ -27,7 +27,9 @@ use crate::v0::messages::{
     StateMachineUpdate as StateMachineUpdateMessage, StateMachineUpdateContent,
     StateMachineUpdateMinerState,
 };
-use crate::v0::signer_state::{GlobalStateEvaluator, ReplayTransactionSet, SignerStateMachine};
+use crate::v0::signer_state::{
+    GlobalStateEvaluator, MinerState, ReplayTransactionSet, SignerStateMachine, StateDivergence,
+};
 
 fn generate_global_state_evaluator(num_addresses: u32) -> GlobalStateEvaluator {
     let address_weights = generate_random_address_with_equal_weights(num_addresses);
@@ -417,3 +419,115 @@ fn determine_global_states_with_tx_replay_set() {
         tx_replay_state_machine
     );
 }
+
+#[test]
+fn determine_state_divergences() {
+    let mut global_eval = generate_global_state_evaluator(5);
+    let mut addresses: Vec<_> = global_eval.address_weights.keys().cloned().collect();
+    addresses.sort_by_key(|address| address.to_string());
+    let local_update = global_eval
+        .address_updates
+        .get(&addresses[0])
+        .unwrap()
+        .clone();
+    let StateMachineUpdateContent::V0 {
+        burn_block,
+        burn_block_height,
+        current_miner,
+    } = local_update.content.clone()
+    else {
+        panic!("Unexpected state machine update message version");
+    };
+    assert!(global_eval.determine_divergent_signers().is_empty());
+
+    // One signer is behind on the burn chain, another sees a different miner
+    let behind_update = StateMachineUpdateMessage::new(
+        0,
+        1,
+        StateMachineUpdateContent::V0 {
+            burn_block: ConsensusHash([0x01; 20]),
+            burn_block_height: burn_block_height - 1,
+            current_miner: current_miner.clone(),
+        },
+    )
+    .unwrap();
+    global_eval.insert_update(addresses[1], behind_update);
+    let other_miner_update = StateMachineUpdateMessage::new(
+        0,
+        1,
+        StateMachineUpdateContent::V0 {
+            burn_block,
+            burn_block_height,
+            current_miner: StateMachineUpdateMinerState::NoValidMiner,
+        },
+    )
+    .unwrap();
+    global_eval.insert_update(addresses[2], other_miner_update);
+
+    let majority_view = global_eval.determine_majority_view();
+    assert_eq!(
+        majority_view.burn_block,
+        Some((burn_block, burn_block_height))
+    );
+    assert_eq!(
+        majority_view.current_miner,
+        Some(MinerState::from(&current_miner))
+    );
+    assert_eq!(
+        majority_view.tx_replay_set,
+        Some(ReplayTransactionSet::none())
+    );
+
+    let divergent_signers = global_eval.determine_divergent_signers();
+    assert_eq!(divergent_signers.len(), 2);
+    for signer in divergent_signers {
+        assert_eq!(signer.weight, 10);
+        if signer.address == addresses[1] {
+            assert_eq!(signer.divergences, vec![StateDivergence::BurnTip]);
+        } else {
+            assert_eq!(signer.address, addresses[2]);
+            assert_eq!(signer.divergences, vec![StateDivergence::ActiveMiner]);
+        }
+    }
+
+    // A local state machine that agrees with the minority is divergent too
+    let local_state = SignerStateMachine {
+        burn_block,
+        burn_block_height,
+        current_miner: MinerState::NoValidMiner,
+        active_signer_protocol_version: 0,
+        tx_replay_set: ReplayTransactionSet::none(),
+    };
+    assert_eq!(
+        majority_view.state_machine_divergences(&local_state),
+        vec![StateDivergence::ActiveMiner]
+    );
+
+    // Without a majority on the miner, nobody diverges on it
+    global_eval.insert_update(
+        addresses[3],
+        global_eval.address_updates[&addresses[2]].clone(),
+    );
+    let third_miner_update = StateMachineUpdateMessage::new(
+        0,
+        1,
+        StateMachineUpdateContent::V0 {
+            burn_block,
+            burn_block_height,
+            current_miner: StateMachineUpdateMinerState::ActiveMiner {
+                current_miner_pkh: Hash160([0xcd; 20]),
+                tenure_id: ConsensusHash([0x45; 20]),
+                parent_tenure_id: ConsensusHash([0x22; 20]),
+                parent_tenure_last_block: StacksBlockId([0x33; 32]),
+                parent_tenure_last_block_height: 1,
+            },
+        },
+    )
+    .unwrap();
+    global_eval.insert_update(addresses[4], third_miner_update);
+    let majority_view = global_eval.determine_majority_view();
+    assert_eq!(majority_view.current_miner, None);
+    assert!(majority_view
+        .state_machine_divergences(&local_state)
+        .is_empty());
+}
@@ -36,10 +36,7 @@ use blockstack_lib::util_lib::signed_structured_data::{
     make_structured_data_domain, structured_data_message_hash,
 };
 use clarity::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
-use clarity::types::chainstate::{
-    BlockHeaderHash, ConsensusHash, StacksPrivateKey, StacksPublicKey,
-};
-use clarity::types::PrivateKey;
+use clarity::types::chainstate::{BlockHeaderHash, ConsensusHash, StacksPublicKey};
 use clarity::util::hash::Sha256Sum;
 use clarity::util::secp256k1::MessageSignature;
 use clarity::vm::types::{QualifiedContractIdentifier, TupleData};
@@ -54,8 +51,8 @@ use stacks_common::util::hash::{Hash160, Sha512Trunc256Sum};
 use crate::stacks_common::types::PublicKey;
 use crate::v0::signer_state::ReplayTransactionSet;
 use crate::{
-    BlockProposal, MessageSlotID as MessageSlotIDTrait, SignerMessage as SignerMessageTrait,
-    VERSION_STRING,
+    BlockProposal, KeyBackend, KeyBackendError, MessageSlotID as MessageSlotIDTrait,
+    SignerMessage as SignerMessageTrait, VERSION_STRING,
 };
 
 /// Maximum size of the [BlockResponseData] serialized bytes
@@ -339,14 +336,16 @@ pub struct MockProposal {
 
 impl MockProposal {
     /// Create a new mock proposal data struct from the provided peer info, chain id, and private key.
-    pub fn new(peer_info: PeerInfo, stacks_private_key: &StacksPrivateKey) -> Self {
+    pub fn new<K: KeyBackend + ?Sized>(
+        peer_info: PeerInfo,
+        stacks_private_key: &K,
+    ) -> Result<Self, KeyBackendError> {
         let mut sig = Self {
             signature: MessageSignature::empty(),
             peer_info,
         };
-        sig.sign(stacks_private_key)
-            .expect("Failed to sign MockProposal");
-        sig
+        sig.sign(stacks_private_key)?;
+        Ok(sig)
     }
 
     /// The signature hash for the mock proposal
@@ -404,9 +403,9 @@ impl MockProposal {
     }
 
     /// Sign the mock proposal and set the internal signature field
-    fn sign(&mut self, private_key: &StacksPrivateKey) -> Result<(), String> {
+    fn sign<K: KeyBackend + ?Sized>(&mut self, private_key: &K) -> Result<(), KeyBackendError> {
         let signature_hash = self.miner_signature_hash();
-        self.signature = private_key.sign(signature_hash.as_bytes())?;
+        self.signature = private_key.sign_digest(signature_hash.as_bytes())?;
         Ok(())
     }
     /// Verify the mock proposal against the provided miner public key
@@ -452,21 +451,23 @@ pub struct MockSignature {
 
 impl MockSignature {
     /// Create a new mock signature from the provided proposal and signer private key.
-    pub fn new(mock_proposal: MockProposal, stacks_private_key: &StacksPrivateKey) -> Self {
+    pub fn new<K: KeyBackend + ?Sized>(
+        mock_proposal: MockProposal,
+        stacks_private_key: &K,
+    ) -> Result<Self, KeyBackendError> {
         let mut sig = Self {
             signature: MessageSignature::empty(),
             mock_proposal,
             metadata: SignerMessageMetadata::default(),
         };
-        sig.sign(stacks_private_key)
-            .expect("Failed to sign MockSignature");
-        sig
+        sig.sign(stacks_private_key)?;
+        Ok(sig)
     }
 
     /// Sign the mock signature and set the internal signature field
-    fn sign(&mut self, private_key: &StacksPrivateKey) -> Result<(), String> {
+    fn sign<K: KeyBackend + ?Sized>(&mut self, private_key: &K) -> Result<(), KeyBackendError> {
         let signature_hash = self.mock_proposal.signer_signature_hash();
-        self.signature = private_key.sign(signature_hash.as_bytes())?;
+        self.signature = private_key.sign_digest(signature_hash.as_bytes())?;
         Ok(())
     }
 
@@ -901,6 +902,12 @@ impl From<&RejectReason> for RejectReasonPrefix {
             RejectReason::IrrecoverablePubkeyHash => RejectReasonPrefix::IrrecoverablePubkeyHash,
             RejectReason::NoSignerConsensus => RejectReasonPrefix::NoSignerConsensus,
             RejectReason::ConsensusHashMismatch { .. } => RejectReasonPrefix::ConsensusHashMismatch,
+            RejectReason::BlockCostLimitExceeded => RejectReasonPrefix::BlockCostLimitExceeded,
+            RejectReason::TooFewTransactionsAfterIdle => {
+                RejectReasonPrefix::TooFewTransactionsAfterIdle
+            }
+            RejectReason::BlockedContractCall => RejectReasonPrefix::BlockedContractCall,
+            RejectReason::TenureExtendTooFrequent => RejectReasonPrefix::TenureExtendTooFrequent,
             RejectReason::Unknown(_) => RejectReasonPrefix::Unknown,
             RejectReason::NotRejected => RejectReasonPrefix::NotRejected,
         }
@@ -986,6 +993,15 @@ pub enum RejectReason {
         /// The block proposal's corresponding miner's tenure id
         actual: ConsensusHash,
     },
+    /// The block uses more of the block limit than the signer's block policy allows
+    BlockCostLimitExceeded,
+    /// The block follows an idle period but has fewer transactions than the signer's block
+    /// policy requires
+    TooFewTransactionsAfterIdle,
+    /// The block calls a contract which the signer's block policy blocks
+    BlockedContractCall,
+    /// The block extends the tenure sooner than the signer's block policy allows
+    TenureExtendTooFrequent,
     /// The block was approved, no rejection details needed
     NotRejected,
     /// Handle unknown codes gracefully
@@ -1033,6 +1049,15 @@ pub enum RejectReasonPrefix {
     NoSignerConsensus = 15,
     /// The block consensus hash does not match the active miner's tenure id
     ConsensusHashMismatch = 16,
+    /// The block uses more of the block limit than the signer's block policy allows
+    BlockCostLimitExceeded = 17,
+    /// The block follows an idle period but has fewer transactions than the signer's block
+    /// policy requires
+    TooFewTransactionsAfterIdle = 18,
+    /// The block calls a contract which the signer's block policy blocks
+    BlockedContractCall = 19,
+    /// The block extends the tenure sooner than the signer's block policy allows
+    TenureExtendTooFrequent = 20,
     /// Unknown reject code, for forward compatibility
     Unknown = 254,
     /// The block was approved, no rejection details needed
@@ -1060,6 +1085,10 @@ impl RejectReasonPrefix {
             Self::IrrecoverablePubkeyHash => 14,
             Self::NoSignerConsensus => 15,
             Self::ConsensusHashMismatch => 16,
+            Self::BlockCostLimitExceeded => 17,
+            Self::TooFewTransactionsAfterIdle => 18,
+            Self::BlockedContractCall => 19,
+            Self::TenureExtendTooFrequent => 20,
             Self::Unknown => 254,
             Self::NotRejected => 255,
         }
@@ -1086,6 +1115,10 @@ impl From<u8> for RejectReasonPrefix {
             14 => Self::IrrecoverablePubkeyHash,
             15 => Self::NoSignerConsensus,
             16 => Self::ConsensusHashMismatch,
+            17 => Self::BlockCostLimitExceeded,
+            18 => Self::TooFewTransactionsAfterIdle,
+            19 => Self::BlockedContractCall,
+            20 => Self::TenureExtendTooFrequent,
             255 => Self::NotRejected,
             // For forward compatibility, all other values are unknown
             _ => Self::Unknown,
@@ -1186,20 +1219,15 @@ impl BlockResponse {
     }
 
     /// Create a new rejected BlockResponse for the provided block signer signature hash and rejection code and sign it with the provided private key
-    pub fn rejected(
+    pub fn rejected<K: KeyBackend + ?Sized>(
         hash: Sha512Trunc256Sum,
         reject_reason: RejectReason,
-        private_key: &StacksPrivateKey,
+        private_key: &K,
         mainnet: bool,
         timestamp: u64,
-    ) -> Self {
-        Self::Rejected(BlockRejection::new(
-            hash,
-            reject_reason,
-            private_key,
-            mainnet,
-            timestamp,
-        ))
+    ) -> Result<Self, KeyBackendError> {
+        BlockRejection::new(hash, reject_reason, private_key, mainnet, timestamp)
+            .map(Self::Rejected)
     }
 
     /// Get the tenure extend timestamp from the block response
@@ -1479,13 +1507,13 @@ pub struct BlockRejection {
 
 impl BlockRejection {
     /// Create a new BlockRejection for the provided block and reason code
-    pub fn new(
+    pub fn new<K: KeyBackend + ?Sized>(
         signer_signature_hash: Sha512Trunc256Sum,
         reject_reason: RejectReason,
-        private_key: &StacksPrivateKey,
+        private_key: &K,
         mainnet: bool,
         timestamp: u64,
-    ) -> Self {
+    ) -> Result<Self, KeyBackendError> {
         let chain_id = if mainnet {
             CHAIN_ID_MAINNET
         } else {
@@ -1500,19 +1528,17 @@ impl BlockRejection {
             metadata: SignerMessageMetadata::default(),
             response_data: BlockResponseData::new(timestamp, reject_reason),
         };
-        rejection
-            .sign(private_key)
-            .expect("Failed to sign BlockRejection");
-        rejection
+        rejection.sign(private_key)?;
+        Ok(rejection)
     }
 
     /// Create a new BlockRejection from a BlockValidateRejection
-    pub fn from_validate_rejection(
+    pub fn from_validate_rejection<K: KeyBackend + ?Sized>(
         reject: BlockValidateReject,
-        private_key: &StacksPrivateKey,
+        private_key: &K,
         mainnet: bool,
         timestamp: u64,
-    ) -> Self {
+    ) -> Result<Self, KeyBackendError> {
         let chain_id = if mainnet {
             CHAIN_ID_MAINNET
         } else {
@@ -1528,10 +1554,8 @@ impl BlockRejection {
             metadata: SignerMessageMetadata::default(),
             response_data: BlockResponseData::new(timestamp, (&reject_code).into()),
         };
-        rejection
-            .sign(private_key)
-            .expect("Failed to sign BlockRejection");
-        rejection
+        rejection.sign(private_key)?;
+        Ok(rejection)
     }
 
     /// The signature hash for the block rejection
@@ -1542,9 +1566,9 @@ impl BlockRejection {
     }
 
     /// Sign the block rejection and set the internal signature field
-    fn sign(&mut self, private_key: &StacksPrivateKey) -> Result<(), String> {
+    fn sign<K: KeyBackend + ?Sized>(&mut self, private_key: &K) -> Result<(), KeyBackendError> {
         let signature_hash = self.hash();
-        self.signature = private_key.sign(signature_hash.as_bytes())?;
+        self.signature = private_key.sign_digest(signature_hash.as_bytes())?;
         Ok(())
     }
 
@@ -1670,6 +1694,10 @@ impl StacksMessageCodec for RejectReason {
             | RejectReason::InvalidTenureExtend
             | RejectReason::IrrecoverablePubkeyHash
             | RejectReason::NoSignerConsensus
+            | RejectReason::BlockCostLimitExceeded
+            | RejectReason::TooFewTransactionsAfterIdle
+            | RejectReason::BlockedContractCall
+            | RejectReason::TenureExtendTooFrequent
             | RejectReason::Unknown(_)
             | RejectReason::NotRejected => {
                 // No additional data to serialize / deserialize
@@ -1714,6 +1742,12 @@ impl StacksMessageCodec for RejectReason {
                 let actual = read_next::<ConsensusHash, _>(fd)?;
                 RejectReason::ConsensusHashMismatch { expected, actual }
             }
+            RejectReasonPrefix::BlockCostLimitExceeded => RejectReason::BlockCostLimitExceeded,
+            RejectReasonPrefix::TooFewTransactionsAfterIdle => {
+                RejectReason::TooFewTransactionsAfterIdle
+            }
+            RejectReasonPrefix::BlockedContractCall => RejectReason::BlockedContractCall,
+            RejectReasonPrefix::TenureExtendTooFrequent => RejectReason::TenureExtendTooFrequent,
             RejectReasonPrefix::Unknown => RejectReason::Unknown(type_prefix_byte),
             RejectReasonPrefix::NotRejected => RejectReason::NotRejected,
         };
@@ -1829,6 +1863,30 @@ impl std::fmt::Display for RejectReason {
                     "The block's consensus hash ({expected}) does not match the active miner's tenure id ({actual})",
                 )
             }
+            RejectReason::BlockCostLimitExceeded => {
+                write!(
+                    f,
+                    "The block uses more of the block limit than the signer's block policy allows."
+                )
+            }
+            RejectReason::TooFewTransactionsAfterIdle => {
+                write!(
+                    f,
+                    "The block follows an idle period but has fewer transactions than the signer's block policy requires."
+                )
+            }
+            RejectReason::BlockedContractCall => {
+                write!(
+                    f,
+                    "The block calls a contract which is blocked by the signer's block policy."
+                )
+            }
+            RejectReason::TenureExtendTooFrequent => {
+                write!(
+                    f,
+                    "The block extends the tenure sooner than the signer's block policy allows."
+                )
+            }
             RejectReason::Unknown(code) => {
                 write!(f, "Unknown reject code: {code}")
             }
@@ -1889,6 +1947,21 @@ mod test {
         assert_eq!(code, deserialized_code);
     }
 
+    #[test]
+    fn serde_block_policy_reject_reasons() {
+        for reason in [
+            RejectReason::BlockCostLimitExceeded,
+            RejectReason::TooFewTransactionsAfterIdle,
+            RejectReason::BlockedContractCall,
+            RejectReason::TenureExtendTooFrequent,
+        ] {
+            let serialized_reason = reason.serialize_to_vec();
+            let deserialized_reason = read_next::<RejectReason, _>(&mut &serialized_reason[..])
+                .expect("Failed to deserialize RejectReason");
+            assert_eq!(reason, deserialized_reason);
+        }
+    }
+
     #[test]
     fn serde_block_rejection() {
         let rejection = BlockRejection::new(
@@ -1897,7 +1970,8 @@ mod test {
             &StacksPrivateKey::random(),
             thread_rng().gen_bool(0.5),
             thread_rng().next_u64(),
-        );
+        )
+        .expect("Failed to sign BlockRejection");
         let serialized_rejection = rejection.serialize_to_vec();
         let deserialized_rejection = read_next::<BlockRejection, _>(&mut &serialized_rejection[..])
             .expect("Failed to deserialize BlockRejection");
@@ -1909,7 +1983,8 @@ mod test {
             &StacksPrivateKey::random(),
             thread_rng().gen_bool(0.5),
             thread_rng().next_u64(),
-        );
+        )
+        .expect("Failed to sign BlockRejection");
         let serialized_rejection = rejection.serialize_to_vec();
         let deserialized_rejection = read_next::<BlockRejection, _>(&mut &serialized_rejection[..])
             .expect("Failed to deserialize BlockRejection");
@@ -1933,13 +2008,16 @@ mod test {
             .expect("Failed to deserialize BlockResponse");
         assert_eq!(response, deserialized_response);
 
-        let response = BlockResponse::Rejected(BlockRejection::new(
-            Sha512Trunc256Sum([1u8; 32]),
-            RejectReason::ValidationFailed(ValidateRejectCode::InvalidBlock),
-            &StacksPrivateKey::random(),
-            thread_rng().gen_bool(0.5),
-            thread_rng().next_u64(),
-        ));
+        let response = BlockResponse::Rejected(
+            BlockRejection::new(
+                Sha512Trunc256Sum([1u8; 32]),
+                RejectReason::ValidationFailed(ValidateRejectCode::InvalidBlock),
+                &StacksPrivateKey::random(),
+                thread_rng().gen_bool(0.5),
+                thread_rng().next_u64(),
+            )
+            .expect("Failed to sign BlockRejection"),
+        );
         let serialized_response = response.serialize_to_vec();
         let deserialized_response = read_next::<BlockResponse, _>(&mut &serialized_response[..])
             .expect("Failed to deserialize BlockResponse");
@@ -2050,6 +2128,68 @@ mod test {
             .expect("Failed to verify MockProposal"));
     }
 
+    /// A key backend whose key cannot be reached
+    #[derive(Debug)]
+    struct FailingKeyBackend(StacksPublicKey);
+
+    impl KeyBackend for FailingKeyBackend {
+        fn public_key(&self) -> StacksPublicKey {
+            self.0
+        }
+
+        fn sign_digest(&self, _digest: &[u8]) -> Result<MessageSignature, KeyBackendError> {
+            Err(KeyBackendError::SigningFailed(
+                "keyholder is unreachable".into(),
+            ))
+        }
+    }
+
+    #[test]
+    fn failing_key_backend() {
+        let key = FailingKeyBackend(StacksPublicKey::from_private(&StacksPrivateKey::random()));
+        let expected = KeyBackendError::SigningFailed("keyholder is unreachable".into());
+
+        assert_eq!(
+            BlockRejection::new(
+                Sha512Trunc256Sum([0u8; 32]),
+                RejectReason::ValidationFailed(ValidateRejectCode::InvalidBlock),
+                &key,
+                false,
+                0,
+            )
+            .unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            BlockResponse::rejected(
+                Sha512Trunc256Sum([0u8; 32]),
+                RejectReason::NoSortitionView,
+                &key,
+                false,
+                0,
+            )
+            .unwrap_err(),
+            expected
+        );
+        let reject = BlockValidateReject {
+            signer_signature_hash: Sha512Trunc256Sum([0u8; 32]),
+            reason: "invalid".into(),
+            reason_code: ValidateRejectCode::InvalidBlock,
+        };
+        assert_eq!(
+            BlockRejection::from_validate_rejection(reject, &key, false, 0).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            MockProposal::new(random_peer_data(), &key).unwrap_err(),
+            expected
+        );
+        assert_eq!(
+            MockSignature::new(random_mock_proposal(), &key).unwrap_err(),
+            expected
+        );
+    }
+
     #[test]
     fn serde_peer_data() {
         let peer_data = random_peer_data();
@@ -2089,9 +2229,11 @@ mod test {
     fn serde_mock_block() {
         let mock_proposal = random_mock_proposal();
         let mock_signature_1 =
-            MockSignature::new(mock_proposal.clone(), &StacksPrivateKey::random());
+            MockSignature::new(mock_proposal.clone(), &StacksPrivateKey::random())
+                .expect("Failed to sign MockSignature");
         let mock_signature_2 =
-            MockSignature::new(mock_proposal.clone(), &StacksPrivateKey::random());
+            MockSignature::new(mock_proposal.clone(), &StacksPrivateKey::random())
+                .expect("Failed to sign MockSignature");
         let mock_block = MockBlock {
             mock_proposal,
             mock_signatures: vec![mock_signature_1, mock_signature_2],
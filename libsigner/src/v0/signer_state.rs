@@ -169,6 +169,171 @@ impl GlobalStateEvaluator {
         let global_state = self.determine_global_state()?;
         Some(global_state.tx_replay_set)
     }
+
+    /// Determine the view of each part of the state machine that is held by a majority
+    /// (more than half) of the signer set's weight
+    pub fn determine_majority_view(&self) -> MajorityStateView {
+        MajorityStateView {
+            burn_block: self.majority_value(|update| {
+                let (burn_block, burn_block_height) = update.content.burn_block_view();
+                (*burn_block, burn_block_height)
+            }),
+            current_miner: self
+                .majority_value(|update| MinerState::from(update.content.current_miner())),
+            tx_replay_set: self.majority_value(|update| update.content.tx_replay_set()),
+        }
+    }
+
+    /// Determine which signers' latest updates diverge from the majority view, along with
+    /// their weight. Signers that agree with the majority view are omitted.
+    pub fn determine_divergent_signers(&self) -> Vec<DivergentSigner> {
+        let majority_view = self.determine_majority_view();
+        let mut divergent_signers: Vec<_> = self
+            .address_updates
+            .iter()
+            .filter_map(|(address, update)| {
+                let weight = *self.address_weights.get(address)?;
+                let divergences = majority_view.update_divergences(update);
+                if divergences.is_empty() {
+                    return None;
+                }
+                Some(DivergentSigner {
+                    address: *address,
+                    weight,
+                    divergences,
+                })
+            })
+            .collect();
+        divergent_signers.sort_by_key(|signer| signer.address.to_string());
+        divergent_signers
+    }
+
+    /// Find the value of `view` held by more than half of the signer set's weight, if any
+    fn majority_value<K, F>(&self, view: F) -> Option<K>
+    where
+        K: Eq + Hash,
+        F: Fn(&StateMachineUpdate) -> K,
+    {
+        let mut weights = HashMap::new();
+        for (address, update) in &self.address_updates {
+            let Some(weight) = self.address_weights.get(address) else {
+                continue;
+            };
+            let entry = weights.entry(view(update)).or_insert(0u32);
+            *entry = entry.saturating_add(*weight);
+        }
+        weights
+            .into_iter()
+            .find(|(_, weight)| u64::from(*weight) * 2 > u64::from(self.total_weight))
+            .map(|(value, _)| value)
+    }
+}
+
+/// A part of the signer state machine that a signer can disagree with the rest of the signer
+/// set on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum StateDivergence {
+    /// The signer has a different burn chain tip
+    BurnTip,
+    /// The signer has a different view of the active miner or the tenure it builds on
+    ActiveMiner,
+    /// The signer has a different transaction replay set
+    TxReplaySet,
+}
+
+impl StateDivergence {
+    /// Every kind of divergence
+    pub const ALL: [StateDivergence; 3] = [
+        StateDivergence::BurnTip,
+        StateDivergence::ActiveMiner,
+        StateDivergence::TxReplaySet,
+    ];
+
+    /// The name of the divergence, as used in logs and metric labels
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StateDivergence::BurnTip => "burn_tip",
+            StateDivergence::ActiveMiner => "active_miner",
+            StateDivergence::TxReplaySet => "tx_replay_set",
+        }
+    }
+}
+
+impl std::fmt::Display for StateDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The view of each part of the signer state machine that is held by a majority of the
+/// signer set's weight. A part is `None` if no single view of it has a majority.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MajorityStateView {
+    /// The burn chain tip and its height
+    pub burn_block: Option<(ConsensusHash, u64)>,
+    /// The active miner
+    pub current_miner: Option<MinerState>,
+    /// The transaction replay set
+    pub tx_replay_set: Option<ReplayTransactionSet>,
+}
+
+impl MajorityStateView {
+    /// Determine how the given view differs from the majority view. Parts without a
+    /// majority view are not compared.
+    pub fn divergences(
+        &self,
+        burn_block: (&ConsensusHash, u64),
+        current_miner: &MinerState,
+        tx_replay_set: &ReplayTransactionSet,
+    ) -> Vec<StateDivergence> {
+        let mut divergences = vec![];
+        if let Some((majority_burn_block, majority_burn_block_height)) = &self.burn_block {
+            if (majority_burn_block, *majority_burn_block_height) != burn_block {
+                divergences.push(StateDivergence::BurnTip);
+            }
+        }
+        if let Some(majority_miner) = &self.current_miner {
+            if majority_miner != current_miner {
+                divergences.push(StateDivergence::ActiveMiner);
+            }
+        }
+        if let Some(majority_tx_replay_set) = &self.tx_replay_set {
+            if majority_tx_replay_set != tx_replay_set {
+                divergences.push(StateDivergence::TxReplaySet);
+            }
+        }
+        divergences
+    }
+
+    /// Determine how a signer's state machine differs from the majority view
+    pub fn state_machine_divergences(&self, state: &SignerStateMachine) -> Vec<StateDivergence> {
+        self.divergences(
+            (&state.burn_block, state.burn_block_height),
+            &state.current_miner,
+            &state.tx_replay_set,
+        )
+    }
+
+    /// Determine how the view in a signer's state machine update differs from the majority
+    /// view
+    pub fn update_divergences(&self, update: &StateMachineUpdate) -> Vec<StateDivergence> {
+        self.divergences(
+            update.content.burn_block_view(),
+            &update.content.current_miner().into(),
+            &update.content.tx_replay_set(),
+        )
+    }
+}
+
+/// A signer whose view of the state machine diverges from the majority view
+#[derive(Debug, Clone, PartialEq)]
+pub struct DivergentSigner {
+    /// The signer's address
+    pub address: StacksAddress,
+    /// The signer's weight
+    pub weight: u32,
+    /// How the signer's view diverges
+    pub divergences: Vec<StateDivergence>,
 }
 
 /// A "wrapper" struct around Vec<StacksTransaction> that behaves like
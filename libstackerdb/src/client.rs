@@ -0,0 +1,512 @@
+// Copyright (C) 2020-2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A blocking client for a StackerDB instance, talking to a stacks-node's RPC interface.
+//!
+//! [`StackerDBClient`] lists replicas and slots, reads chunks, and writes chunks, picking the
+//! next slot version and signing each chunk itself. [`StackerDBSubscription`] receives the
+//! chunks a node reports through its `stackerdb_chunks` event observer interface.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use std::{error, fmt};
+
+use clarity::vm::types::QualifiedContractIdentifier;
+use serde::{Deserialize, Serialize};
+use stacks_common::codec::{StacksMessageCodec, MAX_MESSAGE_LEN};
+use stacks_common::deps_common::httparse;
+use stacks_common::types::chainstate::{StacksAddress, StacksPrivateKey};
+use stacks_common::types::net::PeerAddress;
+use stacks_common::util::chunked_encoding::HttpChunkedTransferReader;
+use stacks_common::util::hash::Hash160;
+
+use crate::{
+    stackerdb_get_chunk_path, stackerdb_get_metadata_path, stackerdb_post_chunk_path,
+    Error as StackerDBError, SlotMetadata, StackerDBChunkAckData, StackerDBChunkData,
+    StackerDBChunksEvent, StackerDBErrorCodes,
+};
+
+/// Default time to wait on a stacks-node before giving up on a request
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Default number of times a chunk write is attempted before giving up
+pub const DEFAULT_MAX_WRITE_ATTEMPTS: u32 = 5;
+/// The event observer path a stacks-node posts StackerDB chunks to
+pub const STACKERDB_CHUNKS_EVENT_PATH: &str = "/stackerdb_chunks";
+
+/// Most headers accepted in an HTTP message
+const MAX_HTTP_HEADERS: usize = 32;
+/// Largest HTTP preamble accepted
+const MAX_HTTP_PREAMBLE_LEN: usize = 64 * 1024;
+
+/// Errors from talking to a StackerDB replica
+#[derive(Debug)]
+pub enum ClientError {
+    /// Network error
+    Io(io::Error),
+    /// The node answered with a status code other than 200
+    HttpError(u16),
+    /// The node's reply could not be parsed as HTTP
+    MalformedResponse(String),
+    /// The node's reply could not be decoded
+    Deserialize(String),
+    /// The chunk could not be signed
+    Signing(StackerDBError),
+    /// The replica rejected the chunk for a reason that retrying will not fix
+    ChunkRejected(Box<StackerDBChunkAckData>),
+    /// The replica kept reporting newer versions of the slot than the ones written
+    RetriesExhausted(u32),
+    /// The contract has no such slot
+    NoSuchSlot(u32),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::Io(e) => write!(f, "Network error: {e}"),
+            ClientError::HttpError(code) => write!(f, "HTTP error {code}"),
+            ClientError::MalformedResponse(s) => write!(f, "Malformed response: {s}"),
+            ClientError::Deserialize(s) => write!(f, "Failed to decode response: {s}"),
+            ClientError::Signing(e) => write!(f, "Failed to sign chunk: {e}"),
+            ClientError::ChunkRejected(ack) => write!(f, "Chunk rejected: {ack}"),
+            ClientError::RetriesExhausted(attempts) => {
+                write!(f, "Chunk write not accepted after {attempts} attempts")
+            }
+            ClientError::NoSuchSlot(slot_id) => write!(f, "No such StackerDB slot {slot_id}"),
+        }
+    }
+}
+
+impl error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ClientError::Io(e) => Some(e),
+            ClientError::Signing(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+impl From<StackerDBError> for ClientError {
+    fn from(e: StackerDBError) -> Self {
+        ClientError::Signing(e)
+    }
+}
+
+/// A node that replicates a StackerDB, as listed by `/v2/stackerdb/{address}/{contract}/replicas`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StackerDBReplica {
+    /// The replica's address
+    #[serde(rename = "ip")]
+    pub addrbytes: PeerAddress,
+    /// The replica's p2p port
+    pub port: u16,
+    /// Hash of the replica's public key
+    pub public_key_hash: Hash160,
+}
+
+impl StackerDBReplica {
+    /// The replica's p2p socket address
+    pub fn to_socketaddr(&self) -> SocketAddr {
+        self.addrbytes.to_socketaddr(self.port)
+    }
+}
+
+/// A client for one StackerDB instance
+#[derive(Debug, Clone)]
+pub struct StackerDBClient {
+    /// The stacks-node RPC `host:port`
+    pub host: String,
+    /// The StackerDB contract
+    pub contract_id: QualifiedContractIdentifier,
+    /// How long to wait on the node for each request
+    pub timeout: Duration,
+    /// How many versions of a chunk to try before giving up on a write
+    pub max_write_attempts: u32,
+    /// API key sent in the `Authorization` header of each request. Nodes that set
+    /// `stackerdb_write_requires_api_key` only accept writes carrying a key with the
+    /// `stackerdb-write` scope.
+    pub api_key: Option<String>,
+}
+
+impl StackerDBClient {
+    /// Make a client for the StackerDB `contract_id`, replicated by the node at `host`
+    pub fn new(host: &str, contract_id: QualifiedContractIdentifier) -> Self {
+        Self {
+            host: host.to_string(),
+            contract_id,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_write_attempts: DEFAULT_MAX_WRITE_ATTEMPTS,
+            api_key: None,
+        }
+    }
+
+    /// List the nodes known to replicate this StackerDB
+    pub fn list_replicas(&self) -> Result<Vec<StackerDBReplica>, ClientError> {
+        let path = format!(
+            "/v2/stackerdb/{}/{}/replicas",
+            &StacksAddress::from(self.contract_id.issuer.clone()),
+            &self.contract_id.name
+        );
+        let body = self.rpc_request("GET", &path, None, &[])?;
+        serde_json::from_slice(&body).map_err(|e| ClientError::Deserialize(e.to_string()))
+    }
+
+    /// Get the metadata of every slot
+    pub fn list_slots(&self) -> Result<Vec<SlotMetadata>, ClientError> {
+        let path = stackerdb_get_metadata_path(self.contract_id.clone());
+        let body = self.rpc_request("GET", &path, None, &[])?;
+        serde_json::from_slice(&body).map_err(|e| ClientError::Deserialize(e.to_string()))
+    }
+
+    /// Get the metadata of a single slot.
+    /// Returns Ok(None) if the contract has no such slot.
+    pub fn get_slot_metadata(&self, slot_id: u32) -> Result<Option<SlotMetadata>, ClientError> {
+        Ok(self
+            .list_slots()?
+            .into_iter()
+            .find(|metadata| metadata.slot_id == slot_id))
+    }
+
+    /// Get the data in a slot, at the given version or the latest one.
+    /// Returns Ok(None) if the node does not have it.
+    pub fn get_chunk(
+        &self,
+        slot_id: u32,
+        slot_version: Option<u32>,
+    ) -> Result<Option<Vec<u8>>, ClientError> {
+        let path = stackerdb_get_chunk_path(self.contract_id.clone(), slot_id, slot_version);
+        match self.rpc_request("GET", &path, None, &[]) {
+            Ok(body) => Ok(Some(body)),
+            Err(ClientError::HttpError(404)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the latest data in a slot, decoded as a `T`.
+    /// Returns Ok(None) if the slot is empty.
+    pub fn get<T: StacksMessageCodec>(&self, slot_id: u32) -> Result<Option<T>, ClientError> {
+        let Some(data) = self.get_chunk(slot_id, None)? else {
+            return Ok(None);
+        };
+        if data.is_empty() {
+            return Ok(None);
+        }
+        T::consensus_deserialize(&mut data.as_slice())
+            .map(Some)
+            .map_err(|e| ClientError::Deserialize(e.to_string()))
+    }
+
+    /// Upload an already-signed chunk as is, and return the replica's acknowledgement
+    pub fn put_chunk(
+        &self,
+        chunk: &StackerDBChunkData,
+    ) -> Result<StackerDBChunkAckData, ClientError> {
+        let body =
+            serde_json::to_vec(chunk).map_err(|e| ClientError::Deserialize(e.to_string()))?;
+        let path = stackerdb_post_chunk_path(self.contract_id.clone());
+        let resp = self.rpc_request("POST", &path, Some("application/json"), &body)?;
+        serde_json::from_slice(&resp).map_err(|e| ClientError::Deserialize(e.to_string()))
+    }
+
+    /// Write `data` to a slot. The chunk is written at the version after the slot's current
+    /// one and signed with `private_key`. If the replica already has that version, the write
+    /// is retried at the version after the one it reports, up to `max_write_attempts` times.
+    pub fn put_data(
+        &self,
+        private_key: &StacksPrivateKey,
+        slot_id: u32,
+        data: Vec<u8>,
+    ) -> Result<StackerDBChunkAckData, ClientError> {
+        let mut slot_version = self
+            .get_slot_metadata(slot_id)?
+            .ok_or(ClientError::NoSuchSlot(slot_id))?
+            .slot_version
+            .saturating_add(1);
+        for _ in 0..self.max_write_attempts {
+            let mut chunk = StackerDBChunkData::new(slot_id, slot_version, data.clone());
+            chunk.sign(private_key)?;
+            let ack = self.put_chunk(&chunk)?;
+            if ack.accepted {
+                return Ok(ack);
+            }
+            match ack.code.and_then(StackerDBErrorCodes::from_code) {
+                Some(StackerDBErrorCodes::DataAlreadyExists) => {
+                    let latest = ack.metadata.as_ref().map_or(slot_version, |metadata| {
+                        metadata.slot_version.max(slot_version)
+                    });
+                    slot_version = latest.saturating_add(1);
+                }
+                _ => return Err(ClientError::ChunkRejected(Box::new(ack))),
+            }
+        }
+        Err(ClientError::RetriesExhausted(self.max_write_attempts))
+    }
+
+    /// Write `message` to a slot, encoded with its `StacksMessageCodec` encoding.
+    /// See [`Self::put_data`].
+    pub fn put<T: StacksMessageCodec>(
+        &self,
+        private_key: &StacksPrivateKey,
+        slot_id: u32,
+        message: &T,
+    ) -> Result<StackerDBChunkAckData, ClientError> {
+        self.put_data(private_key, slot_id, message.serialize_to_vec())
+    }
+
+    /// Listen on `bind` for the chunks of this StackerDB that a node posts to its event
+    /// observers. The node must have an event observer for `bind` with the
+    /// `stackerdb` event key.
+    pub fn subscribe<A: ToSocketAddrs>(
+        &self,
+        bind: A,
+    ) -> Result<StackerDBSubscription, ClientError> {
+        StackerDBSubscription::start(bind, self.contract_id.clone())
+    }
+
+    /// Send a request to the node and return the body of its reply
+    fn rpc_request(
+        &self,
+        verb: &str,
+        path: &str,
+        content_type: Option<&str>,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, ClientError> {
+        let addr = self.host.to_socket_addrs()?.next().ok_or_else(|| {
+            ClientError::Io(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("Could not resolve {}", &self.host),
+            ))
+        })?;
+        let mut sock = TcpStream::connect_timeout(&addr, self.timeout)?;
+        sock.set_read_timeout(Some(self.timeout))?;
+        sock.set_write_timeout(Some(self.timeout))?;
+
+        let content_type_hdr = content_type
+            .map(|content_type| format!("Content-Type: {content_type}\r\n"))
+            .unwrap_or_default();
+        let authorization_hdr = self
+            .api_key
+            .as_ref()
+            .map(|api_key| format!("Authorization: {api_key}\r\n"))
+            .unwrap_or_default();
+        let request = format!(
+            "{verb} {path} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n{content_type_hdr}{authorization_hdr}Content-Length: {}\r\nUser-Agent: libstackerdb/0.1\r\nAccept: */*\r\n\r\n",
+            &self.host,
+            payload.len()
+        );
+        sock.write_all(request.as_bytes())?;
+        sock.write_all(payload)?;
+
+        let mut buf = vec![];
+        sock.read_to_end(&mut buf)?;
+        decode_http_response(&buf)
+    }
+}
+
+/// Decode an HTTP response, returning its body if its status is 200
+fn decode_http_response(buf: &[u8]) -> Result<Vec<u8>, ClientError> {
+    let mut headers_buf = [httparse::EMPTY_HEADER; MAX_HTTP_HEADERS];
+    let mut resp = httparse::Response::new(&mut headers_buf);
+    let Ok(httparse::Status::Complete(body_offset)) = resp.parse(buf) else {
+        return Err(ClientError::MalformedResponse(
+            "Failed to decode HTTP headers".into(),
+        ));
+    };
+    let code = resp
+        .code
+        .ok_or_else(|| ClientError::MalformedResponse("No HTTP status code returned".into()))?;
+    if code != 200 {
+        return Err(ClientError::HttpError(code));
+    }
+    let chunked = resp.headers.iter().any(|header| {
+        header.name.eq_ignore_ascii_case("transfer-encoding")
+            && header.value.eq_ignore_ascii_case(b"chunked")
+    });
+    let mut body = &buf[body_offset..];
+    if !chunked {
+        return Ok(body.to_vec());
+    }
+    let mut decoded = vec![];
+    HttpChunkedTransferReader::from_reader(&mut body, MAX_MESSAGE_LEN.into())
+        .read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+/// A stream of the chunks written to one StackerDB, as reported by a node's event observer
+/// interface. The listener stops when this is dropped.
+pub struct StackerDBSubscription {
+    local_addr: SocketAddr,
+    chunks: Receiver<Vec<StackerDBChunkData>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl StackerDBSubscription {
+    fn start<A: ToSocketAddrs>(
+        bind: A,
+        contract_id: QualifiedContractIdentifier,
+    ) -> Result<Self, ClientError> {
+        let listener = TcpListener::bind(bind)?;
+        let local_addr = listener.local_addr()?;
+        let (chunks_send, chunks) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let stop = stop.clone();
+            thread::Builder::new()
+                .name(format!("stackerdb-subscription:{local_addr}"))
+                .spawn(move || {
+                    for stream in listener.incoming() {
+                        if stop.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        let Ok(mut stream) = stream else {
+                            continue;
+                        };
+                        if !handle_event(&mut stream, &contract_id, &chunks_send) {
+                            break;
+                        }
+                    }
+                })?
+        };
+        Ok(Self {
+            local_addr,
+            chunks,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// The address the subscription is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Wait for the next batch of chunks written to the StackerDB
+    pub fn recv(&self) -> Option<Vec<StackerDBChunkData>> {
+        self.chunks.recv().ok()
+    }
+
+    /// Wait up to `timeout` for the next batch of chunks written to the StackerDB.
+    /// Returns None if no chunks arrived in time.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Vec<StackerDBChunkData>> {
+        match self.chunks.recv_timeout(timeout) {
+            Ok(chunks) => Some(chunks),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+
+    /// Wait for the next chunk written to the StackerDB that decodes as a `T`, along with
+    /// its slot ID. Chunks that do not decode are skipped.
+    pub fn recv_message<T: StacksMessageCodec>(&self) -> Option<(u32, T)> {
+        loop {
+            let decoded = self.recv()?.into_iter().find_map(|chunk| {
+                T::consensus_deserialize(&mut chunk.data.as_slice())
+                    .ok()
+                    .map(|message| (chunk.slot_id, message))
+            });
+            if decoded.is_some() {
+                return decoded;
+            }
+        }
+    }
+}
+
+impl Drop for StackerDBSubscription {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // wake the listener up so it sees the stop flag
+        let _ = TcpStream::connect(self.local_addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Read one event observer request and acknowledge it. Chunks for `contract_id` are sent to
+/// `chunks_send`. Returns false once nobody is listening for chunks any more.
+fn handle_event(
+    stream: &mut TcpStream,
+    contract_id: &QualifiedContractIdentifier,
+    chunks_send: &Sender<Vec<StackerDBChunkData>>,
+) -> bool {
+    let Ok((path, body)) = read_http_request(stream) else {
+        return true;
+    };
+    // the node retries a delivery until it is acknowledged, so acknowledge everything
+    let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    if path != STACKERDB_CHUNKS_EVENT_PATH {
+        return true;
+    }
+    let Ok(event) = serde_json::from_slice::<StackerDBChunksEvent>(&body) else {
+        return true;
+    };
+    if &event.contract_id != contract_id || event.modified_slots.is_empty() {
+        return true;
+    }
+    chunks_send.send(event.modified_slots).is_ok()
+}
+
+/// Read an HTTP request, returning its path and body
+pub(crate) fn read_http_request(stream: &mut TcpStream) -> io::Result<(String, Vec<u8>)> {
+    stream.set_read_timeout(Some(DEFAULT_REQUEST_TIMEOUT))?;
+    let mut buf = vec![];
+    let mut read_buf = [0u8; 4096];
+    let (path, content_length, body_offset) = loop {
+        let nread = stream.read(&mut read_buf)?;
+        if nread == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&read_buf[..nread]);
+        let mut headers_buf = [httparse::EMPTY_HEADER; MAX_HTTP_HEADERS];
+        let mut req = httparse::Request::new(&mut headers_buf);
+        match req.parse(&buf) {
+            Ok(httparse::Status::Complete(body_offset)) => {
+                let content_length = req
+                    .headers
+                    .iter()
+                    .find(|header| header.name.eq_ignore_ascii_case("content-length"))
+                    .and_then(|header| std::str::from_utf8(header.value).ok())
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                let path = req.path.unwrap_or_default().to_string();
+                break (path, content_length, body_offset);
+            }
+            Ok(httparse::Status::Partial) if buf.len() < MAX_HTTP_PREAMBLE_LEN => {}
+            _ => return Err(io::ErrorKind::InvalidData.into()),
+        }
+    };
+    if content_length > usize::try_from(MAX_MESSAGE_LEN).unwrap_or(usize::MAX) {
+        return Err(io::ErrorKind::InvalidData.into());
+    }
+    let mut body = buf.split_off(body_offset);
+    if body.len() < content_length {
+        let mut rest = vec![0u8; content_length - body.len()];
+        stream.read_exact(&mut rest)?;
+        body.extend_from_slice(&rest);
+    }
+    body.truncate(content_length);
+    Ok((path, body))
+}
@@ -16,6 +16,7 @@
 
 extern crate clarity;
 extern crate serde;
+extern crate serde_json;
 extern crate sha2;
 extern crate stacks_common;
 
@@ -24,6 +25,7 @@ use std::{error, fmt};
 
 use clarity::vm::types::QualifiedContractIdentifier;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use sha2::{Digest, Sha512_256};
 use stacks_common::codec::{
     read_next, read_next_at_most, write_next, Error as CodecError, StacksMessageCodec,
@@ -38,6 +40,8 @@ pub const STACKERDB_MAX_CHUNK_SIZE: u32 = 16 * 1024 * 1024;
 /// CHUNK_SIZE constant for signers StackerDBs (2MB)
 pub const SIGNERS_STACKERDB_CHUNK_SIZE: usize = 2 * 1024 * 1024; // 2MB
 
+/// A client for a StackerDB instance on a stacks-node
+pub mod client;
 #[cfg(test)]
 mod tests;
 
@@ -135,6 +139,61 @@ pub struct StackerDBChunkAckData {
     pub code: Option<u32>,
 }
 
+/// Reasons a StackerDB replica gives for rejecting a chunk, as reported in the `code` of a
+/// [`StackerDBChunkAckData`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackerDBErrorCodes {
+    DataAlreadyExists,
+    NoSuchSlot,
+    BadSigner,
+}
+
+impl StackerDBErrorCodes {
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::DataAlreadyExists => 0,
+            Self::NoSuchSlot => 1,
+            Self::BadSigner => 2,
+        }
+    }
+
+    #[cfg_attr(test, mutants::skip)]
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::DataAlreadyExists => "Data for this slot and version already exist",
+            Self::NoSuchSlot => "No such StackerDB slot",
+            Self::BadSigner => "Signature does not match slot signer",
+        }
+    }
+
+    pub fn into_json(self) -> serde_json::Value {
+        json!({
+            "code": self.code(),
+            "message": format!("{:?}", &self),
+            "reason": self.reason()
+        })
+    }
+
+    #[cfg_attr(test, mutants::skip)]
+    pub fn from_code(code: u32) -> Option<Self> {
+        match code {
+            0 => Some(Self::DataAlreadyExists),
+            1 => Some(Self::NoSuchSlot),
+            2 => Some(Self::BadSigner),
+            _ => None,
+        }
+    }
+}
+
+/// Event structure for newly-arrived StackerDB data
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StackerDBChunksEvent {
+    /// The contract ID for the StackerDB instance
+    pub contract_id: QualifiedContractIdentifier,
+    /// The chunk data for newly-modified slots
+    pub modified_slots: Vec<StackerDBChunkData>,
+}
+
 impl fmt::Display for StackerDBChunkAckData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{self:?}")
@@ -157,7 +216,7 @@ impl SlotMetadata {
     }
 
     /// Get the digest to sign that authenticates this chunk data and metadata
-    fn auth_digest(&self) -> Sha512Trunc256Sum {
+    pub fn auth_digest(&self) -> Sha512Trunc256Sum {
         let mut hasher = Sha512_256::new();
         hasher.update(self.slot_id.to_be_bytes());
         hasher.update(self.slot_version.to_be_bytes());
@@ -0,0 +1,234 @@
+// Copyright (C) 2020-2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use clarity::vm::types::QualifiedContractIdentifier;
+use stacks_common::types::chainstate::{StacksAddress, StacksPrivateKey, StacksPublicKey};
+use stacks_common::util::hash::Sha512Trunc256Sum;
+use stacks_common::util::secp256k1::MessageSignature;
+
+use crate::client::{read_http_request, ClientError, StackerDBClient, STACKERDB_CHUNKS_EVENT_PATH};
+use crate::*;
+
+fn make_contract_id(name: &str) -> QualifiedContractIdentifier {
+    let addr = StacksAddress::p2pkh(
+        false,
+        &StacksPublicKey::from_private(&StacksPrivateKey::random()),
+    );
+    QualifiedContractIdentifier::new(addr.into(), name.into())
+}
+
+/// A StackerDB replica with a single slot whose version is `slot_version`. Chunks posted at
+/// or below that version are rejected with `DataAlreadyExists`. Before each write is
+/// checked, the slot version is bumped by `racing_writes`, as if another writer got there
+/// first.
+struct FakeReplica {
+    slot_version: u32,
+    racing_writes: u32,
+    data: Vec<u8>,
+    posted_versions: Vec<u32>,
+}
+
+fn spawn_replica(replica: Arc<Mutex<FakeReplica>>, num_requests: usize) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let host = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        for stream in listener.incoming().take(num_requests) {
+            let mut stream = stream.unwrap();
+            let (path, body) = read_http_request(&mut stream).unwrap();
+            let mut replica = replica.lock().unwrap();
+            let (code, body) = if path.ends_with("/chunks") {
+                let chunk: StackerDBChunkData = serde_json::from_slice(&body).unwrap();
+                replica.posted_versions.push(chunk.slot_version);
+                if replica.racing_writes > 0 {
+                    replica.racing_writes -= 1;
+                    replica.slot_version += 1;
+                }
+                let ack = if chunk.slot_version <= replica.slot_version {
+                    let metadata = SlotMetadata::new_unsigned(
+                        0,
+                        replica.slot_version,
+                        Sha512Trunc256Sum::from_data(&replica.data),
+                    );
+                    StackerDBChunkAckData {
+                        accepted: false,
+                        reason: Some("Data for this slot and version already exist".into()),
+                        metadata: Some(metadata),
+                        code: Some(StackerDBErrorCodes::DataAlreadyExists.code()),
+                    }
+                } else {
+                    replica.slot_version = chunk.slot_version;
+                    replica.data = chunk.data.clone();
+                    StackerDBChunkAckData {
+                        accepted: true,
+                        reason: None,
+                        metadata: Some(chunk.get_slot_metadata()),
+                        code: None,
+                    }
+                };
+                (200, serde_json::to_vec(&ack).unwrap())
+            } else if path.ends_with("/0") {
+                (200, replica.data.clone())
+            } else if path.ends_with("/1") {
+                (404, vec![])
+            } else {
+                let metadata = vec![SlotMetadata {
+                    slot_id: 0,
+                    slot_version: replica.slot_version,
+                    data_hash: Sha512Trunc256Sum::from_data(&replica.data),
+                    signature: MessageSignature::empty(),
+                }];
+                (200, serde_json::to_vec(&metadata).unwrap())
+            };
+            write!(
+                stream,
+                "HTTP/1.1 {code} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(&body).unwrap();
+        }
+    });
+    host
+}
+
+#[test]
+fn put_data_bumps_version_until_accepted() {
+    let replica = Arc::new(Mutex::new(FakeReplica {
+        slot_version: 3,
+        racing_writes: 2,
+        data: vec![],
+        posted_versions: vec![],
+    }));
+    // one metadata listing, three writes and one read
+    let host = spawn_replica(replica.clone(), 5);
+    let client = StackerDBClient::new(&host, make_contract_id("hello-world"));
+    let private_key = StacksPrivateKey::random();
+
+    let ack = client.put_data(&private_key, 0, vec![1, 2, 3]).unwrap();
+    assert!(ack.accepted);
+    assert_eq!(replica.lock().unwrap().posted_versions, vec![4, 5, 6]);
+    assert_eq!(ack.metadata.unwrap().slot_version, 6);
+    assert_eq!(client.get_chunk(0, None).unwrap(), Some(vec![1, 2, 3]));
+}
+
+#[test]
+fn put_data_gives_up_after_max_attempts() {
+    let replica = Arc::new(Mutex::new(FakeReplica {
+        slot_version: 0,
+        racing_writes: u32::MAX,
+        data: vec![],
+        posted_versions: vec![],
+    }));
+    let host = spawn_replica(replica.clone(), 4);
+    let mut client = StackerDBClient::new(&host, make_contract_id("hello-world"));
+    client.max_write_attempts = 2;
+
+    let err = client
+        .put_data(&StacksPrivateKey::random(), 0, vec![1])
+        .unwrap_err();
+    assert!(matches!(err, ClientError::RetriesExhausted(2)));
+    assert!(matches!(
+        client.put_data(&StacksPrivateKey::random(), 1, vec![1]),
+        Err(ClientError::NoSuchSlot(1))
+    ));
+}
+
+#[test]
+fn get_chunk_missing_slot() {
+    let replica = Arc::new(Mutex::new(FakeReplica {
+        slot_version: 0,
+        racing_writes: 0,
+        data: vec![],
+        posted_versions: vec![],
+    }));
+    let host = spawn_replica(replica, 1);
+    let client = StackerDBClient::new(&host, make_contract_id("hello-world"));
+    assert_eq!(client.get_chunk(1, None).unwrap(), None);
+}
+
+#[test]
+fn requests_carry_api_key() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let host = listener.local_addr().unwrap().to_string();
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut headers = vec![];
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                break;
+            }
+            headers.push(line.trim().to_string());
+        }
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]")
+            .unwrap();
+        headers
+    });
+
+    let mut client = StackerDBClient::new(&host, make_contract_id("hello-world"));
+    client.api_key = Some("signer-key".into());
+    assert!(client.list_slots().unwrap().is_empty());
+    let headers = server.join().unwrap();
+    assert!(headers.contains(&"Authorization: signer-key".to_string()));
+}
+
+#[test]
+fn subscription_filters_by_contract() {
+    let contract_id = make_contract_id("hello-world");
+    let other_contract_id = make_contract_id("other");
+    let client = StackerDBClient::new("127.0.0.1:20443", contract_id.clone());
+    let subscription = client.subscribe("127.0.0.1:0").unwrap();
+
+    let post_event = |event: &StackerDBChunksEvent| {
+        let body = serde_json::to_vec(event).unwrap();
+        let mut stream = TcpStream::connect(subscription.local_addr()).unwrap();
+        write!(
+            stream,
+            "POST {STACKERDB_CHUNKS_EVENT_PATH} HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(&body).unwrap();
+        let mut response = String::new();
+        std::io::Read::read_to_string(&mut stream, &mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+    };
+
+    let chunk = StackerDBChunkData::new(2, 7, vec![0xab; 16]);
+    post_event(&StackerDBChunksEvent {
+        contract_id: other_contract_id,
+        modified_slots: vec![chunk.clone()],
+    });
+    post_event(&StackerDBChunksEvent {
+        contract_id,
+        modified_slots: vec![chunk.clone()],
+    });
+
+    assert_eq!(
+        subscription.recv_timeout(Duration::from_secs(10)),
+        Some(vec![chunk])
+    );
+    assert_eq!(subscription.recv_timeout(Duration::from_millis(100)), None);
+}
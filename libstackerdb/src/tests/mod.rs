@@ -22,6 +22,8 @@ use stacks_common::util::secp256k1::MessageSignature;
 
 use crate::*;
 
+mod client;
+
 #[test]
 fn test_stackerdb_slot_metadata_sign_verify() {
     let pk = StacksPrivateKey::random();
@@ -39,6 +39,8 @@ pub mod deps_common {
 
 pub mod bitvec;
 
+pub mod marf_proof;
+
 pub mod consts {
     use crate::types::chainstate::{BlockHeaderHash, ConsensusHash};
     pub use crate::types::MINING_COMMITMENT_WINDOW;
@@ -0,0 +1,569 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Standalone verification of MARF inclusion proofs.
+//!
+//! The node's RPC interface can return a Merkle proof alongside an account, data-var, or
+//! map-entry lookup (see `docs/rpc-endpoints.md`).  This module decodes and checks those proofs
+//! against a block's index root hash without depending on the MARF implementation in
+//! `stackslib`, so that light clients can verify RPC responses.  It only relies on `core`,
+//! `alloc`, and SHA-512/256.
+//!
+//! A proof is made of one or more _segments_, each of which proves a path from a node up to
+//! the root of one block's trie, followed by _shunt_ proofs, which link that root to the root of a
+//! descendant block's trie through its skip-list of ancestor root hashes.  If the value was last
+//! written before the block the proof was made at, the later segments start at a back-pointer to
+//! the earlier block.  The verifier must then confirm that the earlier block's trie has the root
+//! hash the proof computed for it -- otherwise, the proof could be for a stale value.  This is
+//! done with the caller-supplied `block_root` lookup, which is expected to be answered from
+//! block headers the caller already trusts (i.e. their index root hashes).
+
+use core::fmt;
+
+use sha2::{Digest, Sha512_256};
+
+use crate::types::chainstate::{StacksBlockId, TrieHash};
+use crate::util::hash::hex_bytes;
+
+/// Node ID of an empty child pointer
+const NODE_ID_EMPTY: u8 = 0;
+/// A node ID encodes a back-pointer if its high bit is set
+const NODE_ID_BACKPTR_BIT: u8 = 0x80;
+/// Length of a MARF leaf's value: a 32-byte value hash followed by 8 zero bytes
+pub const MARF_VALUE_ENCODED_SIZE: usize = 40;
+/// Maximum number of items in any length-prefixed list of an encoded proof
+const MAX_PROOF_LIST_LEN: u32 = 1 << 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The encoded proof ended early
+    UnexpectedEof,
+    /// The encoded proof has bytes left over after the last entry
+    TrailingBytes,
+    /// The encoded proof has an unknown entry type
+    BadEntryType(u8),
+    /// A length prefix in the encoded proof is too big
+    TooLong(u32),
+    /// The proof is not valid hex
+    BadHex,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnexpectedEof => write!(f, "Unexpected end of proof"),
+            Error::TrailingBytes => write!(f, "Trailing bytes after proof"),
+            Error::BadEntryType(t) => write!(f, "Bad proof entry type {t}"),
+            Error::TooLong(len) => write!(f, "Proof list too long ({len})"),
+            Error::BadHex => write!(f, "Proof is not valid hex"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// A child pointer of a trie node in a proof.  Back-pointers carry the ID of the block whose trie
+/// the child is in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofTriePtr {
+    pub id: u8,
+    pub chr: u8,
+    pub back_block: StacksBlockId,
+}
+
+impl ProofTriePtr {
+    fn is_empty(&self) -> bool {
+        self.id == NODE_ID_EMPTY
+    }
+
+    fn is_backptr(&self) -> bool {
+        self.id & NODE_ID_BACKPTR_BIT != 0
+    }
+}
+
+/// An interior trie node in a proof
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofTrieNode {
+    pub id: u8,
+    pub path: Vec<u8>,
+    pub ptrs: Vec<ProofTriePtr>,
+}
+
+/// One entry of a MARF proof
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarfProofEntry {
+    /// An interior node, the path character of its child on the proven path, and the hashes of
+    /// all of its other children
+    Node {
+        chr: u8,
+        node: ProofTrieNode,
+        hashes: Vec<TrieHash>,
+    },
+    /// The leaf holding the proven value
+    Leaf {
+        chr: u8,
+        path: Vec<u8>,
+        value: [u8; MARF_VALUE_ENCODED_SIZE],
+    },
+    /// A link between a trie's root and its skip-list of ancestor trie root hashes.  The computed
+    /// hash is inserted among `hashes` at position `idx`.
+    Shunt { idx: i64, hashes: Vec<TrieHash> },
+}
+
+/// A MARF inclusion proof, as encoded by `TrieMerkleProof` in `stackslib`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarfProof(pub Vec<MarfProofEntry>);
+
+/// MARF key of a contract's data-var
+pub fn data_var_key(contract_identifier: &str, var_name: &str) -> String {
+    format!("vm::{contract_identifier}::1::{var_name}")
+}
+
+/// MARF key of a contract's map entry, given the hex serialization of the entry's key
+pub fn map_entry_key(contract_identifier: &str, map_name: &str, key_hex: &str) -> String {
+    format!("vm::{contract_identifier}::0::{map_name}::{key_hex}")
+}
+
+/// MARF key of an account's STX balance
+pub fn account_balance_key(principal: &str) -> String {
+    format!("vm-account::{principal}::19")
+}
+
+/// MARF key of an account's nonce
+pub fn account_nonce_key(principal: &str) -> String {
+    format!("vm-account::{principal}::18")
+}
+
+/// The MARF leaf value for a value stored under a key.  For data-vars and map entries, the
+/// stored value is the hex serialization of the Clarity value (i.e. the `data` field of the RPC
+/// response, without its `0x` prefix).
+pub fn marf_value(stored_value: &str) -> [u8; MARF_VALUE_ENCODED_SIZE] {
+    let mut value = [0u8; MARF_VALUE_ENCODED_SIZE];
+    value[..32].copy_from_slice(TrieHash::from_data(stored_value.as_bytes()).as_bytes());
+    value
+}
+
+struct ProofReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ProofReader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < len {
+            return Err(Error::UnexpectedEof);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0u8; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_len(&mut self) -> Result<u32, Error> {
+        let len = u32::from_be_bytes(self.read_array()?);
+        if len > MAX_PROOF_LIST_LEN {
+            return Err(Error::TooLong(len));
+        }
+        Ok(len)
+    }
+
+    fn read_hash(&mut self) -> Result<TrieHash, Error> {
+        Ok(TrieHash(self.read_array()?))
+    }
+
+    fn read_path(&mut self) -> Result<Vec<u8>, Error> {
+        let len = self.read_len()?;
+        Ok(self.read_bytes(len as usize)?.to_vec())
+    }
+
+    fn read_node(&mut self, num_hashes: usize) -> Result<MarfProofEntry, Error> {
+        let chr = self.read_u8()?;
+        let id = self.read_u8()?;
+        let path = self.read_path()?;
+        let num_ptrs = self.read_len()?;
+        let mut ptrs = Vec::with_capacity(num_ptrs as usize);
+        for _ in 0..num_ptrs {
+            ptrs.push(ProofTriePtr {
+                id: self.read_u8()?,
+                chr: self.read_u8()?,
+                back_block: StacksBlockId(self.read_array()?),
+            });
+        }
+        let mut hashes = Vec::with_capacity(num_hashes);
+        for _ in 0..num_hashes {
+            hashes.push(self.read_hash()?);
+        }
+        Ok(MarfProofEntry::Node {
+            chr,
+            node: ProofTrieNode { id, path, ptrs },
+            hashes,
+        })
+    }
+
+    fn read_entry(&mut self) -> Result<MarfProofEntry, Error> {
+        match self.read_u8()? {
+            0 => self.read_node(3),
+            1 => self.read_node(15),
+            2 => self.read_node(47),
+            3 => self.read_node(255),
+            4 => {
+                let chr = self.read_u8()?;
+                let path = self.read_path()?;
+                let value = self.read_array()?;
+                Ok(MarfProofEntry::Leaf { chr, path, value })
+            }
+            5 => {
+                let idx = i64::from_be_bytes(self.read_array()?);
+                let num_hashes = self.read_len()?;
+                let mut hashes = Vec::with_capacity(num_hashes as usize);
+                for _ in 0..num_hashes {
+                    hashes.push(self.read_hash()?);
+                }
+                Ok(MarfProofEntry::Shunt { idx, hashes })
+            }
+            entry_type => Err(Error::BadEntryType(entry_type)),
+        }
+    }
+}
+
+/// Hash a node's consensus bytes -- its ID, its child pointers (ID, path character, and
+/// back-pointer block), and its path -- followed by its children's hashes
+fn node_hash(node: &ProofTrieNode, child_hashes: &[TrieHash]) -> TrieHash {
+    let mut hasher = Sha512_256::new();
+    hasher.update([node.id]);
+    for ptr in node.ptrs.iter() {
+        hasher.update([ptr.id, ptr.chr]);
+        hasher.update(ptr.back_block.as_bytes());
+    }
+    hasher.update([node.path.len() as u8]);
+    hasher.update(&node.path);
+    for child_hash in child_hashes.iter() {
+        hasher.update(child_hash.as_bytes());
+    }
+    TrieHash(hasher.finalize().into())
+}
+
+fn leaf_hash(path: &[u8], value: &[u8; MARF_VALUE_ENCODED_SIZE]) -> TrieHash {
+    let mut hasher = Sha512_256::new();
+    // leaf node ID
+    hasher.update([1u8]);
+    hasher.update([path.len() as u8]);
+    hasher.update(path);
+    hasher.update(value);
+    TrieHash(hasher.finalize().into())
+}
+
+/// Hash a node, given the hash of its child at `chr` and the hashes of all its other children
+fn node_hash_with_child(
+    node: &ProofTrieNode,
+    chr: u8,
+    child_hash: &TrieHash,
+    hashes: &[TrieHash],
+) -> Option<TrieHash> {
+    if node.ptrs.len() != hashes.len() + 1 {
+        return None;
+    }
+    let mut child_hashes = Vec::with_capacity(node.ptrs.len());
+    let mut other_hashes = hashes.iter();
+    for ptr in node.ptrs.iter() {
+        if !ptr.is_empty() && ptr.chr == chr {
+            child_hashes.push(*child_hash);
+        } else {
+            child_hashes.push(*other_hashes.next()?);
+        }
+    }
+    if child_hashes.len() != node.ptrs.len() {
+        return None;
+    }
+    Some(node_hash(node, &child_hashes))
+}
+
+/// Split the proof into its segment proofs and the shunt proofs that follow each of them
+fn split_proof(proof: &[MarfProofEntry]) -> Option<Vec<(&[MarfProofEntry], &[MarfProofEntry])>> {
+    let mut parts = vec![];
+    let mut rest = proof;
+    while !rest.is_empty() {
+        let segment_len = rest
+            .iter()
+            .position(|entry| matches!(entry, MarfProofEntry::Shunt { .. }))?;
+        let (segment, tail) = rest.split_at(segment_len);
+        let shunt_len = tail
+            .iter()
+            .position(|entry| !matches!(entry, MarfProofEntry::Shunt { .. }))
+            .unwrap_or(tail.len());
+        let (shunt, tail) = tail.split_at(shunt_len);
+        if segment.is_empty() {
+            return None;
+        }
+        parts.push((segment, shunt));
+        rest = tail;
+    }
+    Some(parts)
+}
+
+/// The path a segment proof covers, from the root of its trie down to its deepest node
+fn segment_path(segment: &[MarfProofEntry]) -> Option<Vec<u8>> {
+    let mut path_parts = vec![];
+    for entry in segment.iter() {
+        match entry {
+            MarfProofEntry::Leaf { path, .. } => path_parts.push(path.as_slice()),
+            MarfProofEntry::Node { chr, node, .. } => {
+                path_parts.push(core::slice::from_ref(chr));
+                path_parts.push(node.path.as_slice());
+            }
+            MarfProofEntry::Shunt { .. } => return None,
+        }
+    }
+    Some(path_parts.into_iter().rev().flatten().copied().collect())
+}
+
+/// Hash up a segment proof, starting from the hash of its deepest node's child on the path, and
+/// return the root hash of its trie
+fn segment_root_hash(segment: &[MarfProofEntry], child_hash: TrieHash) -> Option<TrieHash> {
+    let mut hash = child_hash;
+    for entry in segment.iter() {
+        hash = match entry {
+            MarfProofEntry::Node { chr, node, hashes } => {
+                node_hash_with_child(node, *chr, &hash, hashes)?
+            }
+            // only the first segment has a leaf, and it is hashed separately
+            MarfProofEntry::Leaf { .. } | MarfProofEntry::Shunt { .. } => return None,
+        };
+    }
+    Some(hash)
+}
+
+/// Hash `hash` into position `idx - 1` of `hashes`, after the optional `first` hash
+fn shunt_hash(
+    first: Option<&TrieHash>,
+    hash: &TrieHash,
+    idx: i64,
+    hashes: &[TrieHash],
+) -> Option<TrieHash> {
+    let position = usize::try_from(idx.checked_sub(1)?).ok()?;
+    if position > hashes.len() {
+        return None;
+    }
+    let mut hasher = Sha512_256::new();
+    if let Some(first) = first {
+        hasher.update(first.as_bytes());
+    }
+    for (i, other) in hashes.iter().enumerate() {
+        if i == position {
+            hasher.update(hash.as_bytes());
+        }
+        hasher.update(other.as_bytes());
+    }
+    if position == hashes.len() {
+        hasher.update(hash.as_bytes());
+    }
+    Some(TrieHash(hasher.finalize().into()))
+}
+
+/// Hash a trie's root node hash with its ancestor trie root hashes
+fn shunt_head_hash(node_root_hash: TrieHash, shunt: &MarfProofEntry) -> Option<TrieHash> {
+    match shunt {
+        MarfProofEntry::Shunt { idx: 0, hashes } => {
+            if hashes.is_empty() {
+                // the trie of the first block has no ancestors
+                return Some(node_root_hash);
+            }
+            let mut hasher = Sha512_256::new();
+            hasher.update(node_root_hash.as_bytes());
+            for hash in hashes.iter() {
+                hasher.update(hash.as_bytes());
+            }
+            Some(TrieHash(hasher.finalize().into()))
+        }
+        _ => None,
+    }
+}
+
+impl MarfProof {
+    /// Decode a proof from its consensus serialization
+    pub fn from_bytes(bytes: &[u8]) -> Result<MarfProof, Error> {
+        let mut reader = ProofReader { bytes };
+        let num_entries = reader.read_len()?;
+        let mut entries = Vec::with_capacity(num_entries as usize);
+        for _ in 0..num_entries {
+            entries.push(reader.read_entry()?);
+        }
+        if !reader.bytes.is_empty() {
+            return Err(Error::TrailingBytes);
+        }
+        Ok(MarfProof(entries))
+    }
+
+    /// Decode a proof from its hex-encoded consensus serialization, as returned by the RPC
+    /// interface.  The `0x` prefix is optional.
+    pub fn from_hex(hex: &str) -> Result<MarfProof, Error> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        let bytes = hex_bytes(hex).map_err(|_| Error::BadHex)?;
+        MarfProof::from_bytes(&bytes)
+    }
+
+    /// The blocks whose trie root hashes must be known to verify this proof, i.e. the blocks the
+    /// proof's back-pointers refer to.
+    pub fn referenced_blocks(&self) -> Vec<StacksBlockId> {
+        split_proof(&self.0)
+            .unwrap_or_default()
+            .iter()
+            .skip(1)
+            .filter_map(|(segment, _)| match segment.first() {
+                Some(MarfProofEntry::Node { chr, node, .. }) => node
+                    .ptrs
+                    .iter()
+                    .find(|ptr| !ptr.is_empty() && ptr.chr == *chr)
+                    .map(|ptr| ptr.back_block),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Verify that `key` maps to `stored_value` in the trie with root hash `root_hash`.
+    /// `block_root` returns the trie root hash of each block in `referenced_blocks()`.
+    pub fn verify<F>(
+        &self,
+        key: &str,
+        stored_value: &str,
+        root_hash: &TrieHash,
+        block_root: F,
+    ) -> bool
+    where
+        F: FnMut(&StacksBlockId) -> Option<TrieHash>,
+    {
+        self.verify_path(
+            &TrieHash::from_key(key),
+            &marf_value(stored_value),
+            root_hash,
+            block_root,
+        )
+    }
+
+    /// Verify that the leaf at `path` holds `value` in the trie with root hash `root_hash`.
+    /// `block_root` returns the trie root hash of each block in `referenced_blocks()`.
+    pub fn verify_path<F>(
+        &self,
+        path: &TrieHash,
+        value: &[u8; MARF_VALUE_ENCODED_SIZE],
+        root_hash: &TrieHash,
+        mut block_root: F,
+    ) -> bool
+    where
+        F: FnMut(&StacksBlockId) -> Option<TrieHash>,
+    {
+        let Some(parts) = split_proof(&self.0) else {
+            return false;
+        };
+        let Some(((first_segment, first_shunt), later_parts)) = parts.split_first() else {
+            return false;
+        };
+
+        // the first segment proves the leaf, and must cover its whole path
+        let Some((
+            MarfProofEntry::Leaf {
+                path: leaf_path,
+                value: leaf_value,
+                ..
+            },
+            first_nodes,
+        )) = first_segment.split_first()
+        else {
+            return false;
+        };
+        if leaf_value != value || segment_path(first_segment).as_deref() != Some(path.as_bytes()) {
+            return false;
+        }
+        let Some(node_root_hash) = segment_root_hash(first_nodes, leaf_hash(leaf_path, value))
+        else {
+            return false;
+        };
+
+        // link the first segment's trie root to its ancestors
+        let Some((shunt_head, shunt_rest)) = first_shunt.split_first() else {
+            return false;
+        };
+        if !shunt_rest.is_empty() {
+            return false;
+        }
+        let Some(mut trie_hash) = shunt_head_hash(node_root_hash, shunt_head) else {
+            return false;
+        };
+
+        for (segment, shunt) in later_parts.iter() {
+            // each later segment is in a descendant trie, and starts at a back-pointer to the
+            // trie that was just verified
+            if segment_path(segment)
+                .is_none_or(|segment_path| !path.as_bytes().starts_with(&segment_path))
+            {
+                return false;
+            }
+            let Some(MarfProofEntry::Node { chr, node, .. }) = segment.first() else {
+                return false;
+            };
+            let Some(backptr) = node
+                .ptrs
+                .iter()
+                .find(|ptr| !ptr.is_empty() && ptr.chr == *chr)
+            else {
+                return false;
+            };
+            if !backptr.is_backptr() || block_root(&backptr.back_block).as_ref() != Some(&trie_hash)
+            {
+                return false;
+            }
+            let Some(next_node_root_hash) =
+                segment_root_hash(segment, TrieHash(backptr.back_block.0))
+            else {
+                return false;
+            };
+
+            // walk the skip-lists from the verified trie to this segment's trie.  The last
+            // shunt proof joins this segment's root node hash with its ancestors.
+            let Some((junction, tail)) = shunt.split_last() else {
+                return false;
+            };
+            let mut penultimate_hash = trie_hash;
+            for tail_entry in tail.iter() {
+                let MarfProofEntry::Shunt { idx, hashes } = tail_entry else {
+                    return false;
+                };
+                let Some(next_hash) = shunt_hash(None, &penultimate_hash, *idx, hashes) else {
+                    return false;
+                };
+                penultimate_hash = next_hash;
+            }
+            let MarfProofEntry::Shunt { idx, hashes } = junction else {
+                return false;
+            };
+            let Some(next_trie_hash) =
+                shunt_hash(Some(&next_node_root_hash), &penultimate_hash, *idx, hashes)
+            else {
+                return false;
+            };
+            trie_hash = next_trie_hash;
+        }
+
+        trie_hash == *root_hash
+    }
+}
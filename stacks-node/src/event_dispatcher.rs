@@ -56,17 +56,16 @@ use stacks::chainstate::stacks::events::{
 };
 use stacks::chainstate::stacks::miner::TransactionEvent;
 use stacks::chainstate::stacks::{
-    StacksBlock, StacksMicroblock, StacksTransaction, TransactionPayload,
+    StacksBlock, StacksMicroblock, StacksTransaction, TenureChangeCause, TransactionPayload,
 };
-use stacks::config::{EventKeyType, EventObserverConfig};
+use stacks::config::{EventKeyType, EventObserverConfig, EventObserverTransport};
 use stacks::core::mempool::{MemPoolDropReason, MemPoolEventDispatcher, ProposalCallbackReceiver};
 use stacks::libstackerdb::StackerDBChunkData;
 use stacks::net::api::postblock_proposal::{
     BlockValidateOk, BlockValidateReject, BlockValidateResponse,
 };
 use stacks::net::atlas::{Attachment, AttachmentInstance};
-use stacks::net::http::HttpRequestContents;
-use stacks::net::httpcore::{send_http_request, StacksHttpRequest};
+use stacks::net::eventstream::{EventStreamHub, StreamEvent, StreamEventKind};
 use stacks::net::stackerdb::StackerDBEventDispatcher;
 use stacks::util::hash::to_hex;
 #[cfg(any(test, feature = "testing"))]
@@ -75,13 +74,15 @@ use stacks::util_lib::db::Error as db_error;
 use stacks_common::bitvec::BitVec;
 use stacks_common::codec::StacksMessageCodec;
 use stacks_common::types::chainstate::{BlockHeaderHash, BurnchainHeaderHash, StacksBlockId};
-use stacks_common::types::net::PeerHost;
 use stacks_common::util::hash::{bytes_to_hex, Sha512Trunc256Sum};
 use stacks_common::util::secp256k1::MessageSignature;
 use stacks_common::util::serde_serializers::{
     prefix_hex, prefix_hex_codec, prefix_opt_hex, prefix_string_0x,
 };
-use url::Url;
+
+use self::transport::{make_transport, EventTransport, EventTransportError};
+
+mod transport;
 
 #[cfg(any(test, feature = "testing"))]
 lazy_static! {
@@ -101,6 +102,12 @@ pub struct EventObserver {
     /// If true, the stacks-node will not retry if event delivery fails for any reason.
     /// WARNING: This should not be set on observers that require successful delivery of all events.
     pub disable_retries: bool,
+    /// How payloads are delivered to `endpoint`
+    pub transport: EventObserverTransport,
+    /// CA certificates to pin for an HTTPS observer, instead of the web PKI roots
+    pub tls_ca_cert_path: Option<String>,
+    /// The transport instantiated from `transport`, shared by every delivery
+    transport_impl: Arc<dyn EventTransport>,
 }
 
 const STATUS_RESP_TRUE: &str = "success";
@@ -431,54 +438,38 @@ impl EventObserver {
     }
 
     fn send_payload_directly(
+        transport: &dyn EventTransport,
         payload: &serde_json::Value,
-        full_url: &str,
+        path: &str,
         timeout: Duration,
         disable_retries: bool,
     ) -> bool {
+        let target_url = transport.target_url(path);
         debug!(
-            "Event dispatcher: Sending payload"; "url" => %full_url, "payload" => ?payload
+            "Event dispatcher: Sending payload"; "url" => %target_url, "payload" => ?payload
         );
 
-        let url = Url::parse(full_url)
-            .unwrap_or_else(|_| panic!("Event dispatcher: unable to parse {full_url} as a URL"));
-
-        let host = url.host_str().expect("Invalid URL: missing host");
-        let port = url.port_or_known_default().unwrap_or(80);
-        let peerhost: PeerHost = format!("{host}:{port}")
-            .parse()
-            .unwrap_or(PeerHost::DNS(host.to_string(), port));
-
         let mut backoff = Duration::from_millis(100);
         let mut attempts: i32 = 0;
         // Cap the backoff at 3x the timeout
         let max_backoff = timeout.saturating_mul(3);
 
         loop {
-            let mut request = StacksHttpRequest::new_for_peer(
-                peerhost.clone(),
-                "POST".into(),
-                url.path().into(),
-                HttpRequestContents::new().payload_json(payload.clone()),
-            )
-            .unwrap_or_else(|_| panic!("FATAL: failed to encode infallible data as HTTP request"));
-            request.add_header("Connection".into(), "close".into());
-            match send_http_request(host, port, request, timeout) {
-                Ok(response) => {
-                    if response.preamble().status_code == 200 {
-                        debug!(
-                            "Event dispatcher: Successful POST"; "url" => %url
-                        );
-                        break;
-                    } else {
-                        error!(
-                            "Event dispatcher: Failed POST"; "url" => %url, "response" => ?response.preamble()
-                        );
-                    }
+            match transport.deliver(path, payload, timeout) {
+                Ok(()) => {
+                    debug!(
+                        "Event dispatcher: Successful delivery"; "url" => %target_url
+                    );
+                    break;
+                }
+                Err(EventTransportError::Rejected(reason)) => {
+                    error!(
+                        "Event dispatcher: Failed delivery"; "url" => %target_url, "response" => %reason
+                    );
                 }
                 Err(err) => {
                     warn!(
-                        "Event dispatcher: connection or request failed to {host}:{port} - {err:?}";
+                        "Event dispatcher: connection or request failed to {target_url} - {err:?}";
                         "backoff" => ?backoff,
                         "attempts" => attempts
                     );
@@ -512,29 +503,40 @@ impl EventObserver {
         endpoint: String,
         timeout: Duration,
         disable_retries: bool,
+        transport: EventObserverTransport,
+        tls_ca_cert_path: Option<String>,
     ) -> Self {
+        let transport_impl = make_transport(transport, &endpoint, tls_ca_cert_path.as_deref());
         EventObserver {
             db_path,
             endpoint,
             timeout,
             disable_retries,
+            transport,
+            tls_ca_cert_path,
+            transport_impl,
         }
     }
 
-    /// Send the payload to the given URL.
+    /// The transport this observer delivers its payloads with
+    fn make_transport(&self) -> Arc<dyn EventTransport> {
+        self.transport_impl.clone()
+    }
+
+    /// If `target_url` was recorded for this observer in the pending payloads
+    /// database, return the path it should be delivered to.
+    fn path_for_target(&self, target_url: &str) -> Option<String> {
+        self.make_transport().path_for_target(target_url)
+    }
+
+    /// Send the payload to the given path on this observer's transport.
     /// Before sending this payload, any pending payloads in the database will be sent first.
     pub fn send_payload(&self, payload: &serde_json::Value, path: &str, id: Option<i64>) {
-        // Construct the full URL
-        let url_str = if path.starts_with('/') {
-            format!("{}{path}", &self.endpoint)
-        } else {
-            format!("{}/{path}", &self.endpoint)
-        };
-        let full_url = format!("http://{url_str}");
+        let transport = self.make_transport();
 
         // if the observer is in "disable_retries" mode quickly send the payload without checking for the db
         if self.disable_retries {
-            Self::send_payload_directly(payload, &full_url, self.timeout, true);
+            Self::send_payload_directly(transport.as_ref(), payload, path, self.timeout, true);
         } else if let Some(db_path) = &self.db_path {
             let conn =
                 Connection::open(db_path).expect("Failed to open database for event observer");
@@ -542,12 +544,14 @@ impl EventObserver {
             let id = match id {
                 Some(id) => id,
                 None => {
-                    Self::insert_payload_with_retry(&conn, &full_url, payload, self.timeout);
+                    let target_url = transport.target_url(path);
+                    Self::insert_payload_with_retry(&conn, &target_url, payload, self.timeout);
                     conn.last_insert_rowid()
                 }
             };
 
-            let success = Self::send_payload_directly(payload, &full_url, self.timeout, false);
+            let success =
+                Self::send_payload_directly(transport.as_ref(), payload, path, self.timeout, false);
             // This is only `false` when the TestFlag is set to skip retries
             if !success {
                 return;
@@ -561,7 +565,7 @@ impl EventObserver {
             }
         } else {
             // No database, just send the payload
-            Self::send_payload_directly(payload, &full_url, self.timeout, false);
+            Self::send_payload_directly(transport.as_ref(), payload, path, self.timeout, false);
         }
     }
 
@@ -895,6 +899,8 @@ pub struct EventDispatcher {
     pub stackerdb_channel: Arc<Mutex<StackerDBChannel>>,
     /// Database path for pending payloads
     db_path: Option<PathBuf>,
+    /// Subscribers to the RPC event stream endpoint.  Shared with the `PeerNetwork`.
+    event_stream: Arc<EventStreamHub>,
 }
 
 /// This struct is used specifically for receiving proposal responses.
@@ -1116,9 +1122,15 @@ impl EventDispatcher {
             stackerdb_observers_lookup: HashSet::new(),
             block_proposal_observers_lookup: HashSet::new(),
             db_path,
+            event_stream: Arc::new(EventStreamHub::new()),
         }
     }
 
+    /// Get a handle to the hub that feeds the RPC event stream endpoint
+    pub fn event_stream(&self) -> Arc<EventStreamHub> {
+        self.event_stream.clone()
+    }
+
     pub fn process_burn_block(
         &self,
         burn_block: &BurnchainHeaderHash,
@@ -1131,7 +1143,10 @@ impl EventDispatcher {
     ) {
         // lazily assemble payload only if we have observers
         let interested_observers = self.filter_observers(&self.burn_block_observers_lookup, true);
-        if interested_observers.is_empty() {
+        let stream_interested = self
+            .event_stream
+            .has_subscribers(StreamEventKind::NewBurnBlock);
+        if interested_observers.is_empty() && !stream_interested {
             return;
         }
 
@@ -1148,6 +1163,11 @@ impl EventDispatcher {
         for observer in interested_observers.iter() {
             observer.send_new_burn_block(&payload);
         }
+
+        if stream_interested {
+            self.event_stream
+                .publish(StreamEvent::new(StreamEventKind::NewBurnBlock, payload));
+        }
     }
 
     /// Iterates through tx receipts, and then the events corresponding to each receipt to
@@ -1271,6 +1291,8 @@ impl EventDispatcher {
         block_timestamp: Option<u64>,
         coinbase_height: u64,
     ) {
+        self.publish_chain_tip_to_event_stream(block, metadata, receipts, parent_index_hash);
+
         let all_receipts = receipts.to_owned();
         let (dispatch_matrix, events) = self.create_dispatch_matrix_and_event_vector(&all_receipts);
 
@@ -1340,6 +1362,65 @@ impl EventDispatcher {
         }
     }
 
+    /// Publish a summary of a newly-processed block to the RPC event stream, as well as the
+    /// start of a new tenure if the block contains a `BlockFound` tenure change.
+    fn publish_chain_tip_to_event_stream(
+        &self,
+        block: &StacksBlockEventData,
+        metadata: &StacksHeaderInfo,
+        receipts: &[StacksTransactionReceipt],
+        parent_index_hash: &StacksBlockId,
+    ) {
+        if self.event_stream.has_subscribers(StreamEventKind::NewBlock) {
+            let summary = json!({
+                "block_hash": format!("0x{}", block.block_hash),
+                "block_height": metadata.stacks_block_height,
+                "index_block_hash": format!("0x{}", metadata.index_block_hash()),
+                "parent_index_block_hash": format!("0x{parent_index_hash}"),
+                "consensus_hash": format!("0x{}", metadata.consensus_hash),
+                "burn_block_hash": format!("0x{}", metadata.burn_header_hash),
+                "burn_block_height": metadata.burn_header_height,
+                "tx_count": receipts.len(),
+            });
+            self.event_stream
+                .publish(StreamEvent::new(StreamEventKind::NewBlock, summary));
+        }
+
+        if !self
+            .event_stream
+            .has_subscribers(StreamEventKind::NewTenure)
+        {
+            return;
+        }
+        let tenure_change = receipts.iter().find_map(|receipt| {
+            let TransactionOrigin::Stacks(ref tx) = receipt.transaction else {
+                return None;
+            };
+            match &tx.payload {
+                TransactionPayload::TenureChange(tc)
+                    if tc.cause == TenureChangeCause::BlockFound =>
+                {
+                    Some(tc)
+                }
+                _ => None,
+            }
+        });
+        if let Some(tc) = tenure_change {
+            let tenure = json!({
+                "consensus_hash": format!("0x{}", tc.tenure_consensus_hash),
+                "prev_tenure_consensus_hash": format!("0x{}", tc.prev_tenure_consensus_hash),
+                "burn_view_consensus_hash": format!("0x{}", tc.burn_view_consensus_hash),
+                "previous_tenure_end": format!("0x{}", tc.previous_tenure_end),
+                "previous_tenure_blocks": tc.previous_tenure_blocks,
+                "tenure_start_block_id": format!("0x{}", metadata.index_block_hash()),
+                "block_height": metadata.stacks_block_height,
+                "burn_block_height": metadata.burn_header_height,
+            });
+            self.event_stream
+                .publish(StreamEvent::new(StreamEventKind::NewTenure, tenure));
+        }
+    }
+
     /// Creates a list of observers that are interested in the new microblocks event,
     /// creates a mapping from observers to the event ids that are relevant to each, and then
     /// sends the event to each interested observer.
@@ -1421,6 +1502,20 @@ impl EventDispatcher {
     }
 
     pub fn process_new_mempool_txs(&self, txs: Vec<StacksTransaction>) {
+        if self
+            .event_stream
+            .has_subscribers(StreamEventKind::NewMempoolTx)
+        {
+            for tx in txs.iter() {
+                let admitted = json!({
+                    "txid": format!("0x{}", tx.txid()),
+                    "raw_tx": format!("0x{}", bytes_to_hex(&tx.serialize_to_vec())),
+                });
+                self.event_stream
+                    .publish(StreamEvent::new(StreamEventKind::NewMempoolTx, admitted));
+            }
+        }
+
         // lazily assemble payload only if we have observers
         let interested_observers = self.filter_observers(&self.mempool_observers_lookup, true);
 
@@ -1648,12 +1743,17 @@ impl EventDispatcher {
     }
 
     pub fn register_observer(&mut self, conf: &EventObserverConfig) -> EventObserver {
-        info!("Registering event observer at: {}", conf.endpoint);
+        info!(
+            "Registering event observer at: {}", conf.endpoint;
+            "transport" => ?conf.transport
+        );
         let event_observer = EventObserver::new(
             self.db_path.clone(),
             conf.endpoint.clone(),
             Duration::from_millis(conf.timeout_ms),
             conf.disable_retries,
+            conf.transport,
+            conf.tls_ca_cert_path.clone(),
         );
 
         if conf.disable_retries {
@@ -1791,21 +1891,13 @@ impl EventDispatcher {
 
         for (id, url, payload, _timeout_ms) in pending_payloads {
             info!("Event dispatcher: processing pending payload: {url}");
-            let full_url = Url::parse(url.as_str())
-                .unwrap_or_else(|_| panic!("Event dispatcher: unable to parse {url} as a URL"));
             // find the right observer
-            let observer = self.registered_observers.iter().find(|observer| {
-                let endpoint_url = Url::parse(format!("http://{}", &observer.endpoint).as_str())
-                    .unwrap_or_else(|_| {
-                        panic!(
-                            "Event dispatcher: unable to parse {} as a URL",
-                            observer.endpoint
-                        )
-                    });
-                full_url.origin() == endpoint_url.origin()
-            });
+            let observer = self
+                .registered_observers
+                .iter()
+                .find_map(|observer| Some((observer, observer.path_for_target(&url)?)));
 
-            let Some(observer) = observer else {
+            let Some((observer, path)) = observer else {
                 // This observer is no longer registered, skip and delete
                 info!(
                     "Event dispatcher: observer {} no longer registered, skipping",
@@ -1820,7 +1912,7 @@ impl EventDispatcher {
                 continue;
             };
 
-            observer.send_payload(&payload, full_url.path(), Some(id));
+            observer.send_payload(&payload, &path, Some(id));
 
             #[cfg(test)]
             if TEST_EVENT_OBSERVER_SKIP_RETRY.get() {
@@ -1873,6 +1965,8 @@ mod test {
         TransactionPostConditionMode, TransactionPublicKeyEncoding, TransactionSpendingCondition,
         TransactionVersion,
     };
+    use stacks::net::http::HttpRequestContents;
+    use stacks::net::httpcore::{send_http_request, StacksHttpRequest};
     use stacks::types::chainstate::{
         BlockHeaderHash, StacksAddress, StacksPrivateKey, StacksPublicKey,
     };
@@ -1880,6 +1974,7 @@ mod test {
     use stacks::util::secp256k1::MessageSignature;
     use stacks_common::bitvec::BitVec;
     use stacks_common::types::chainstate::{BurnchainHeaderHash, StacksBlockId};
+    use stacks_common::types::net::PeerHost;
     use tempfile::tempdir;
     use tiny_http::{Method, Response, Server, StatusCode};
 
@@ -1887,8 +1982,14 @@ mod test {
 
     #[test]
     fn build_block_processed_event() {
-        let observer =
-            EventObserver::new(None, "nowhere".to_string(), Duration::from_secs(3), false);
+        let observer = EventObserver::new(
+            None,
+            "nowhere".to_string(),
+            Duration::from_secs(3),
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
 
         let filtered_events = vec![];
         let block = StacksBlock::genesis_block();
@@ -1948,8 +2049,14 @@ mod test {
 
     #[test]
     fn test_block_processed_event_nakamoto() {
-        let observer =
-            EventObserver::new(None, "nowhere".to_string(), Duration::from_secs(3), false);
+        let observer = EventObserver::new(
+            None,
+            "nowhere".to_string(),
+            Duration::from_secs(3),
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
 
         let filtered_events = vec![];
         let mut block_header = NakamotoBlockHeader::empty();
@@ -2171,6 +2278,8 @@ mod test {
             events_keys: vec![EventKeyType::AnyEvent],
             timeout_ms: timeout.as_millis() as u64,
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
 
         let conn = EventDispatcher::init_db(&db_path).expect("Failed to initialize the database");
@@ -2220,6 +2329,8 @@ mod test {
             events_keys: vec![EventKeyType::AnyEvent],
             timeout_ms: timeout.as_millis() as u64,
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
 
         let conn = EventDispatcher::init_db(&db_path).expect("Failed to initialize the database");
@@ -2285,7 +2396,14 @@ mod test {
         let endpoint = "http://example.com".to_string();
         let timeout = Duration::from_secs(5);
 
-        let observer = EventObserver::new(None, endpoint.clone(), timeout, false);
+        let observer = EventObserver::new(
+            None,
+            endpoint.clone(),
+            timeout,
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
 
         // Verify fields
         assert_eq!(observer.endpoint, endpoint);
@@ -2318,7 +2436,14 @@ mod test {
         let endpoint = server.url().strip_prefix("http://").unwrap().to_string();
         let timeout = Duration::from_secs(5);
 
-        let observer = EventObserver::new(Some(db_path.clone()), endpoint, timeout, false);
+        let observer = EventObserver::new(
+            Some(db_path.clone()),
+            endpoint,
+            timeout,
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
 
         TEST_EVENT_OBSERVER_SKIP_RETRY.set(false);
 
@@ -2355,7 +2480,14 @@ mod test {
 
         let endpoint = server.url().strip_prefix("http://").unwrap().to_string();
 
-        let observer = EventObserver::new(None, endpoint, timeout, false);
+        let observer = EventObserver::new(
+            None,
+            endpoint,
+            timeout,
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
 
         // Call send_payload
         observer.send_payload(&payload, "/test", None);
@@ -2391,6 +2523,8 @@ mod test {
             format!("127.0.0.1:{port}"),
             Duration::from_secs(3),
             false,
+            EventObserverTransport::Http,
+            None,
         );
 
         let payload = json!({"key": "value"});
@@ -2444,6 +2578,8 @@ mod test {
             format!("127.0.0.1:{port}"),
             Duration::from_secs(3),
             false,
+            EventObserverTransport::Http,
+            None,
         );
 
         let payload = json!({"key": "value"});
@@ -2491,7 +2627,14 @@ mod test {
             }
         });
 
-        let observer = EventObserver::new(None, format!("127.0.0.1:{port}"), timeout, false);
+        let observer = EventObserver::new(
+            None,
+            format!("127.0.0.1:{port}"),
+            timeout,
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
 
         let payload = json!({"key": "value"});
 
@@ -2591,6 +2734,8 @@ mod test {
             timeout_ms: timeout.as_millis() as u64,
             events_keys: vec![EventKeyType::AnyEvent],
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
 
         EventDispatcher::init_db(&dispatcher.clone().db_path.unwrap()).unwrap();
@@ -2633,7 +2778,14 @@ mod test {
 
         let endpoint = server.url().strip_prefix("http://").unwrap().to_string();
 
-        let observer = EventObserver::new(None, endpoint, timeout, true);
+        let observer = EventObserver::new(
+            None,
+            endpoint,
+            timeout,
+            true,
+            EventObserverTransport::Http,
+            None,
+        );
 
         // in non "disable_retries" mode this will run forever
         observer.send_payload(&payload, "/test", None);
@@ -2649,12 +2801,202 @@ mod test {
 
         let endpoint = String::from("255.255.255.255");
 
-        let observer = EventObserver::new(None, endpoint, timeout, true);
+        let observer = EventObserver::new(
+            None,
+            endpoint,
+            timeout,
+            true,
+            EventObserverTransport::Http,
+            None,
+        );
 
         // in non "disable_retries" mode this will run forever
         observer.send_payload(&payload, "/test", None);
     }
 
+    #[test]
+    fn test_send_payload_file_transport() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("events.ndjson");
+        let endpoint = file_path.to_str().unwrap().to_string();
+        let timeout = Duration::from_secs(5);
+
+        let observer = EventObserver::new(
+            None,
+            endpoint,
+            timeout,
+            false,
+            EventObserverTransport::File,
+            None,
+        );
+
+        observer.send_payload(&json!({"key": "value"}), "/new_block", None);
+        observer.send_payload(&json!({"key": "value2"}), "new_burn_block", None);
+
+        let contents = std::fs::read_to_string(&file_path).expect("Failed to read events file");
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("Failed to parse NDJSON line"))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                json!({"path": "/new_block", "payload": {"key": "value"}}),
+                json!({"path": "/new_burn_block", "payload": {"key": "value2"}}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_https_transport_target_urls() {
+        let observer = EventObserver::new(
+            None,
+            "localhost:3700".into(),
+            Duration::from_secs(5),
+            false,
+            EventObserverTransport::Https,
+            None,
+        );
+        assert_eq!(
+            observer.make_transport().target_url("new_block"),
+            "https://localhost:3700/new_block"
+        );
+        assert_eq!(
+            observer.path_for_target("https://localhost:3700/new_block"),
+            Some("/new_block".to_string())
+        );
+        // payloads recorded for a plaintext observer at the same endpoint are not ours
+        assert_eq!(
+            observer.path_for_target("http://localhost:3700/new_block"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_process_pending_payloads_file_transport() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("event_observers.sqlite");
+        let file_path = dir.path().join("events.ndjson");
+        let endpoint = file_path.to_str().unwrap().to_string();
+        let timeout = Duration::from_secs(5);
+
+        let mut dispatcher = EventDispatcher::new(Some(dir.path().to_path_buf()));
+        let observer = dispatcher.register_observer(&EventObserverConfig {
+            endpoint: endpoint.clone(),
+            events_keys: vec![EventKeyType::AnyEvent],
+            timeout_ms: timeout.as_millis() as u64,
+            disable_retries: false,
+            transport: EventObserverTransport::File,
+            tls_ca_cert_path: None,
+        });
+
+        let conn = EventDispatcher::init_db(&db_path).expect("Failed to initialize the database");
+        let payload = json!({"key": "value"});
+        let target_url = format!("file://{endpoint}/new_block");
+        assert_eq!(
+            observer.path_for_target(&target_url),
+            Some("/new_block".to_string())
+        );
+
+        EventObserver::insert_payload(&conn, &target_url, &payload, timeout)
+            .expect("Failed to insert payload");
+
+        dispatcher.process_pending_payloads();
+
+        let pending_payloads =
+            EventDispatcher::get_pending_payloads(&conn).expect("Failed to get pending payloads");
+        assert_eq!(pending_payloads.len(), 0, "Expected no pending payloads");
+
+        let contents = std::fs::read_to_string(&file_path).expect("Failed to read events file");
+        let line: serde_json::Value =
+            serde_json::from_str(contents.trim_end()).expect("Failed to parse NDJSON line");
+        assert_eq!(line, json!({"path": "/new_block", "payload": payload}));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_send_payload_unix_socket_transport() {
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempdir().unwrap();
+        let socket_path = dir.path().join("events.sock");
+        let listener = UnixListener::bind(&socket_path).expect("Failed to bind socket");
+
+        let observer = EventObserver::new(
+            None,
+            socket_path.to_str().unwrap().to_string(),
+            Duration::from_secs(5),
+            false,
+            EventObserverTransport::UnixSocket,
+            None,
+        );
+
+        let payload = json!({"key": "value"});
+        let receiver = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("Failed to accept connection");
+            let mut line = String::new();
+            BufReader::new(stream)
+                .read_line(&mut line)
+                .expect("Failed to read line");
+            line
+        });
+
+        observer.send_payload(&payload, "/new_mempool_tx", None);
+
+        let line: serde_json::Value =
+            serde_json::from_str(&receiver.join().unwrap()).expect("Failed to parse NDJSON line");
+        assert_eq!(line, json!({"path": "/new_mempool_tx", "payload": payload}));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_named_pipe_transport_times_out_without_reader() {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = tempdir().unwrap();
+        let pipe_path = dir.path().join("events.pipe");
+        let c_path = CString::new(pipe_path.as_os_str().as_bytes()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+
+        let observer = EventObserver::new(
+            None,
+            pipe_path.to_str().unwrap().to_string(),
+            Duration::from_millis(100),
+            false,
+            EventObserverTransport::NamedPipe,
+            None,
+        );
+
+        let start = Instant::now();
+        let err = observer
+            .make_transport()
+            .deliver("/new_block", &json!({"key": "value"}), observer.timeout)
+            .expect_err("Delivery should fail without a reader");
+        assert!(start.elapsed() < Duration::from_secs(5));
+        match err {
+            EventTransportError::Io(e) => assert_eq!(e.kind(), std::io::ErrorKind::TimedOut),
+            e => panic!("Unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn test_http_transport_unparseable_endpoint() {
+        let observer = EventObserver::new(
+            None,
+            "not a host:port".into(),
+            Duration::from_secs(5),
+            false,
+            EventObserverTransport::Http,
+            None,
+        );
+        assert_eq!(
+            observer.path_for_target("http://localhost:3700/new_block"),
+            None
+        );
+    }
+
     #[test]
     #[ignore]
     /// This test generates a new block and ensures the "disable_retries" events_observer will not block.
@@ -2668,6 +3010,8 @@ mod test {
             events_keys: vec![EventKeyType::MinedBlocks],
             timeout_ms: 1000,
             disable_retries: true,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         };
         event_dispatcher.register_observer(&config);
 
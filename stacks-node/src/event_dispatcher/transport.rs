@@ -0,0 +1,422 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transports used by an `EventObserver` to deliver its payloads.
+//!
+//! The default transport POSTs each payload over HTTP, or HTTPS. The other transports
+//! write each payload as a single NDJSON line of the form
+//! `{"path": "/new_block", "payload": {...}}` to a Unix domain socket, an
+//! append-only file, or a named pipe.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use std::{fmt, thread};
+
+use rustls::ClientConfig;
+use serde_json::json;
+use stacks::config::EventObserverTransport;
+use stacks::net::http::tls::make_client_config;
+use stacks::net::http::HttpRequestContents;
+use stacks::net::httpcore::{send_http_request, send_https_request, StacksHttpRequest};
+use stacks_common::types::net::PeerHost;
+use url::Url;
+
+/// One lock per file or named pipe, which serializes writes to it so that concurrent
+/// observers writing to the same path never interleave their lines.
+static FILE_WRITE_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// How long to wait between attempts to open or write to a named pipe that isn't ready
+const PIPE_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(thiserror::Error, Debug)]
+pub enum EventTransportError {
+    /// The transport could not reach the observer
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// The observer was reached, but did not accept the payload
+    #[error("Observer rejected payload: {0}")]
+    Rejected(String),
+    /// The transport is not available on this platform
+    #[cfg(not(unix))]
+    #[error("Transport is not supported on this platform")]
+    Unsupported,
+}
+
+/// A means of delivering event payloads to a single observer
+pub trait EventTransport: fmt::Debug + Send + Sync {
+    /// The URL under which a payload for `path` is recorded in the pending payloads database
+    fn target_url(&self, path: &str) -> String;
+
+    /// If `target_url` was produced by this transport, return the path it was produced for
+    fn path_for_target(&self, target_url: &str) -> Option<String>;
+
+    /// Make a single attempt to deliver `payload` for `path`.
+    /// Retries, if any, are the caller's responsibility.
+    fn deliver(
+        &self,
+        path: &str,
+        payload: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), EventTransportError>;
+}
+
+/// Instantiate the transport of the given kind for `endpoint`.
+/// `tls_ca_cert_path` is only used by the HTTPS transport, which loads it once, here.
+pub fn make_transport(
+    kind: EventObserverTransport,
+    endpoint: &str,
+    tls_ca_cert_path: Option<&str>,
+) -> Arc<dyn EventTransport> {
+    match kind {
+        EventObserverTransport::Http => Arc::new(HttpTransport {
+            endpoint: endpoint.to_string(),
+            tls: false,
+            tls_config: None,
+        }),
+        EventObserverTransport::Https => {
+            let tls_config = make_client_config(tls_ca_cert_path)
+                .inspect_err(|e| {
+                    error!("Event dispatcher: failed to load TLS configuration for {endpoint}: {e}")
+                })
+                .ok();
+            Arc::new(HttpTransport {
+                endpoint: endpoint.to_string(),
+                tls: true,
+                tls_config,
+            })
+        }
+        EventObserverTransport::UnixSocket => Arc::new(UnixSocketTransport {
+            socket_path: PathBuf::from(endpoint),
+        }),
+        EventObserverTransport::File => Arc::new(NdjsonFileTransport {
+            file_path: PathBuf::from(endpoint),
+        }),
+        EventObserverTransport::NamedPipe => Arc::new(NamedPipeTransport {
+            pipe_path: PathBuf::from(endpoint),
+        }),
+    }
+}
+
+/// Get the lock that serializes writes to `file_path`
+fn file_write_lock(file_path: &Path) -> Arc<Mutex<()>> {
+    let mut locks = FILE_WRITE_LOCKS.lock().unwrap_or_else(|e| e.into_inner());
+    locks.entry(file_path.to_path_buf()).or_default().clone()
+}
+
+/// Make sure `path` starts with a `/`
+fn normalize_path(path: &str) -> String {
+    if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{path}")
+    }
+}
+
+/// Encode a payload as a single newline-terminated JSON line
+fn ndjson_line(path: &str, payload: &serde_json::Value) -> Vec<u8> {
+    let mut line = json!({
+        "path": normalize_path(path),
+        "payload": payload,
+    })
+    .to_string()
+    .into_bytes();
+    line.push(b'\n');
+    line
+}
+
+/// Strip `prefix` from `target_url`, returning the remaining path
+fn strip_target_prefix(prefix: &str, target_url: &str) -> Option<String> {
+    let path = target_url.strip_prefix(prefix)?;
+    path.starts_with('/').then(|| path.to_string())
+}
+
+/// Delivers payloads as HTTP POST requests to `http://{endpoint}{path}`, or to
+/// `https://{endpoint}{path}` if `tls` is set
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    endpoint: String,
+    tls: bool,
+    /// The client configuration used to verify the observer, if `tls` is set.
+    /// `None` if it failed to load, in which case nothing can be delivered.
+    tls_config: Option<Arc<ClientConfig>>,
+}
+
+impl HttpTransport {
+    fn origin(&self) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{scheme}://{}", self.endpoint)
+    }
+}
+
+impl EventTransport for HttpTransport {
+    fn target_url(&self, path: &str) -> String {
+        format!("{}{}", self.origin(), normalize_path(path))
+    }
+
+    fn path_for_target(&self, target_url: &str) -> Option<String> {
+        let target_url = Url::parse(target_url).ok()?;
+        let endpoint_url = Url::parse(&self.origin()).ok()?;
+        (target_url.origin() == endpoint_url.origin()).then(|| target_url.path().to_string())
+    }
+
+    fn deliver(
+        &self,
+        path: &str,
+        payload: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), EventTransportError> {
+        let full_url = self.target_url(path);
+        let url = Url::parse(&full_url).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unable to parse {full_url} as a URL: {e}"),
+            )
+        })?;
+
+        let host = url.host_str().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{full_url} has no host"),
+            )
+        })?;
+        let port = url.port_or_known_default().unwrap_or(80);
+        let peerhost: PeerHost = format!("{host}:{port}")
+            .parse()
+            .unwrap_or(PeerHost::DNS(host.to_string(), port));
+
+        let mut request = StacksHttpRequest::new_for_peer(
+            peerhost,
+            "POST".into(),
+            url.path().into(),
+            HttpRequestContents::new().payload_json(payload.clone()),
+        )
+        .unwrap_or_else(|_| panic!("FATAL: failed to encode infallible data as HTTP request"));
+        request.add_header("Connection".into(), "close".into());
+
+        let response = if self.tls {
+            let tls_config = self.tls_config.clone().ok_or_else(|| {
+                io::Error::other("TLS client configuration failed to load".to_string())
+            })?;
+            send_https_request(host, port, request, timeout, tls_config)?
+        } else {
+            send_http_request(host, port, request, timeout)?
+        };
+        if response.preamble().status_code != 200 {
+            return Err(EventTransportError::Rejected(format!(
+                "HTTP status {}",
+                response.preamble().status_code
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Writes each payload as an NDJSON line to a fresh connection on a Unix domain socket
+#[derive(Debug, Clone)]
+pub struct UnixSocketTransport {
+    socket_path: PathBuf,
+}
+
+impl EventTransport for UnixSocketTransport {
+    fn target_url(&self, path: &str) -> String {
+        format!(
+            "unix://{}{}",
+            self.socket_path.display(),
+            normalize_path(path)
+        )
+    }
+
+    fn path_for_target(&self, target_url: &str) -> Option<String> {
+        strip_target_prefix(
+            &format!("unix://{}", self.socket_path.display()),
+            target_url,
+        )
+    }
+
+    #[cfg(unix)]
+    fn deliver(
+        &self,
+        path: &str,
+        payload: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), EventTransportError> {
+        use std::os::fd::OwnedFd;
+        use std::os::unix::net::UnixStream;
+
+        use socket2::{Domain, SockAddr, Socket, Type};
+
+        let socket = Socket::new(Domain::UNIX, Type::STREAM, None)?;
+        socket.connect_timeout(&SockAddr::unix(&self.socket_path)?, timeout)?;
+        let mut stream = UnixStream::from(OwnedFd::from(socket));
+        stream.set_write_timeout(Some(timeout))?;
+        stream.write_all(&ndjson_line(path, payload))?;
+        stream.flush()?;
+        stream.shutdown(std::net::Shutdown::Both)?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn deliver(
+        &self,
+        _path: &str,
+        _payload: &serde_json::Value,
+        _timeout: Duration,
+    ) -> Result<(), EventTransportError> {
+        Err(EventTransportError::Unsupported)
+    }
+}
+
+/// Appends each payload as an NDJSON line to a file, creating it if needed
+#[derive(Debug, Clone)]
+pub struct NdjsonFileTransport {
+    file_path: PathBuf,
+}
+
+impl EventTransport for NdjsonFileTransport {
+    fn target_url(&self, path: &str) -> String {
+        format!(
+            "file://{}{}",
+            self.file_path.display(),
+            normalize_path(path)
+        )
+    }
+
+    fn path_for_target(&self, target_url: &str) -> Option<String> {
+        strip_target_prefix(&format!("file://{}", self.file_path.display()), target_url)
+    }
+
+    fn deliver(
+        &self,
+        path: &str,
+        payload: &serde_json::Value,
+        _timeout: Duration,
+    ) -> Result<(), EventTransportError> {
+        let line = ndjson_line(path, payload);
+        let lock = file_write_lock(&self.file_path);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)?;
+        file.write_all(&line)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+/// Writes each payload as an NDJSON line to an existing named pipe (FIFO).
+/// Delivery waits up to the observer's timeout for a reader to attach and start draining the
+/// pipe, and fails with `ErrorKind::TimedOut` otherwise.  Once part of a line has been written,
+/// the rest of it is always written too, so that the reader never sees a truncated line
+/// followed by the next one.
+#[derive(Debug, Clone)]
+pub struct NamedPipeTransport {
+    pipe_path: PathBuf,
+}
+
+impl EventTransport for NamedPipeTransport {
+    fn target_url(&self, path: &str) -> String {
+        format!(
+            "pipe://{}{}",
+            self.pipe_path.display(),
+            normalize_path(path)
+        )
+    }
+
+    fn path_for_target(&self, target_url: &str) -> Option<String> {
+        strip_target_prefix(&format!("pipe://{}", self.pipe_path.display()), target_url)
+    }
+
+    #[cfg(unix)]
+    fn deliver(
+        &self,
+        path: &str,
+        payload: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<(), EventTransportError> {
+        use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+
+        let file_type = std::fs::metadata(&self.pipe_path)?.file_type();
+        if !file_type.is_fifo() {
+            return Err(EventTransportError::Rejected(format!(
+                "{} is not a named pipe",
+                self.pipe_path.display()
+            )));
+        }
+        let line = ndjson_line(path, payload);
+        let deadline = Instant::now() + timeout;
+        let timed_out = |what: &str| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out {what} {}", self.pipe_path.display()),
+            )
+        };
+
+        // Opening a FIFO for writing with O_NONBLOCK fails with ENXIO while no reader is
+        // attached, instead of blocking indefinitely.
+        let mut pipe = loop {
+            match OpenOptions::new()
+                .write(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(&self.pipe_path)
+            {
+                Ok(pipe) => break pipe,
+                Err(e) if e.raw_os_error() == Some(libc::ENXIO) => {
+                    if Instant::now() >= deadline {
+                        return Err(timed_out("waiting for a reader on").into());
+                    }
+                    thread::sleep(PIPE_RETRY_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        };
+
+        let lock = file_write_lock(&self.pipe_path);
+        let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut written = 0;
+        while written < line.len() {
+            match pipe.write(&line[written..]) {
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero).into()),
+                Ok(n) => written += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    // the deadline only applies until the line is started; a reader that has
+                    // taken part of it must get the rest
+                    if written == 0 && Instant::now() >= deadline {
+                        return Err(timed_out("writing to").into());
+                    }
+                    thread::sleep(PIPE_RETRY_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn deliver(
+        &self,
+        _path: &str,
+        _payload: &serde_json::Value,
+        _timeout: Duration,
+    ) -> Result<(), EventTransportError> {
+        Err(EventTransportError::Unsupported)
+    }
+}
@@ -250,6 +250,44 @@ fn cli_get_miner_spend(
     spend_amount
 }
 
+/// Implementation of `replay-events` CLI option
+fn cli_replay_events(
+    config_path: &str,
+    observer_endpoint: &str,
+    start_height: u64,
+    end_height: u64,
+) {
+    info!("Loading config at path {config_path}");
+    let config = match ConfigFile::from_path(config_path) {
+        Ok(config_file) => Config::from_config_file(config_file, true).unwrap(),
+        Err(e) => {
+            warn!("Invalid config file: {e}");
+            process::exit(1);
+        }
+    };
+    let Some(observer_config) = config
+        .events_observers
+        .iter()
+        .find(|observer| observer.endpoint == observer_endpoint)
+    else {
+        warn!("No [[events_observer]] with endpoint {observer_endpoint} in the config file");
+        process::exit(1);
+    };
+
+    // Only the selected observer is registered, so that its `events_keys` filter applies and the
+    // other observers do not receive duplicate events. No pending-payload DB is used, so events
+    // which cannot be delivered are not persisted.
+    let mut event_dispatcher = EventDispatcher::new(None);
+    event_dispatcher.register_observer(observer_config);
+
+    if let Err(e) =
+        stacks::cli::replay_events_nakamoto(&config, start_height, end_height, &event_dispatcher)
+    {
+        warn!("Failed to replay events: {e:?}");
+        process::exit(1);
+    }
+}
+
 fn main() {
     panic::set_hook(Box::new(|panic_info| {
         error!("Process abort due to thread panic: {panic_info}");
@@ -394,6 +432,16 @@ fn main() {
             println!("Will spend {spend_amount}");
             process::exit(0);
         }
+        "replay-events" => {
+            let config_path: String = args.value_from_str("--config").unwrap();
+            let observer_endpoint: String = args.value_from_str("--observer").unwrap();
+            let start_height: u64 = args.value_from_str("--start-height").unwrap();
+            let end_height: u64 = args.value_from_str("--end-height").unwrap();
+            args.finish();
+
+            cli_replay_events(&config_path, &observer_endpoint, start_height, end_height);
+            process::exit(0);
+        }
         _ => {
             print_help();
             return;
@@ -483,6 +531,17 @@ replay-mock-mining\tReplay mock mined blocks from <dir>
 \t\t  --path: path to directory of mock mined blocks
 \t\t  --config: path to the config file
 
+replay-events\tRe-send the `/new_burn_block` and `/new_block` events of a range of burnchain blocks, and of
+\t\tthe Nakamoto tenures they started, to one of the config's event observers. Stacks blocks are
+\t\tre-evaluated to re-derive their receipts; the chainstate is not modified.
+\t\tArguments:
+\t\t  --config: path to the config file
+\t\t  --observer: endpoint of the [[events_observer]] entry to send events to
+\t\t  --start-height: first burnchain block height to replay (must be in epoch 3.0 or later)
+\t\t  --end-height: last burnchain block height to replay (inclusive)
+\t\tExample:
+\t\t  stacks-node replay-events --config /path/to/config.toml --observer localhost:3700 --start-height 900000 --end-height 900100
+
 help\t\tDisplay this help.
 
 OPTIONAL ARGUMENTS:
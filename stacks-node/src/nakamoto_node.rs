@@ -210,6 +210,7 @@ impl StacksNode {
         let mut p2p_net = data_from_neon
             .peer_network
             .unwrap_or_else(|| NeonNode::setup_peer_network(&config, &atlas_config, burnchain));
+        p2p_net.set_event_stream(runloop.get_event_dispatcher().event_stream());
 
         let stackerdbs = StackerDBs::connect(&config.get_stacker_db_file_path(), true)
             .expect("FATAL: failed to connect to stacker DB");
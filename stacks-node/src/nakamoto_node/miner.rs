@@ -580,8 +580,7 @@ impl BlockMinerThread {
         // reset the caches, because the blocks we mine are not actually
         // processed, so the mempool caches are not valid.
         if self.reset_mempool_caches
-            || self.config.miner.mempool_walk_strategy
-                == MemPoolWalkStrategy::NextNonceWithHighestFeeRate
+            || self.config.miner.mempool_walk_strategy != MemPoolWalkStrategy::GlobalFeeRate
             || self.config.node.mock_mining
         {
             let mut mem_pool = self
@@ -592,8 +591,8 @@ impl BlockMinerThread {
             if self.reset_mempool_caches || self.config.node.mock_mining {
                 mem_pool.reset_mempool_caches()?;
             } else {
-                // Even if the nonce cache is still valid, NextNonceWithHighestFeeRate strategy
-                // needs to reset this cache after each block. This prevents skipping transactions
+                // Even if the nonce cache is still valid, the nonce-ordered strategies
+                // need to reset this cache after each block. This prevents skipping transactions
                 // that were previously considered, but not included in previous blocks.
                 mem_pool.reset_considered_txs_cache()?;
             }
@@ -1107,6 +1106,9 @@ impl BlockMinerThread {
         })?;
         let miners_contract_id = boot_code_id(MINERS_NAME, chain_state.mainnet);
         let mut miners_session = StackerDBSession::new(&rpc_socket.to_string(), miners_contract_id);
+        if let Some(api_key) = self.config.stackerdb_write_api_key() {
+            miners_session = miners_session.with_api_key(api_key);
+        }
 
         if Self::fault_injection_skip_block_push() {
             warn!(
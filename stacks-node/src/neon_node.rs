@@ -2372,7 +2372,7 @@ impl BlockMinerThread {
             return Ok(());
         }
         let election_sortition = last_winner_snapshot.consensus_hash;
-        let mock_proposal = MockProposal::new(peer_info, &mining_key);
+        let mock_proposal = MockProposal::new(peer_info, &mining_key).map_err(|e| e.to_string())?;
 
         info!("Sending mock proposal to stackerdb: {mock_proposal:?}");
 
@@ -2381,6 +2381,9 @@ impl BlockMinerThread {
         let miner_contract_id = boot_code_id(MINERS_NAME, self.config.is_mainnet());
         let mut miners_stackerdb =
             StackerDBSession::new(&self.config.node.rpc_bind, miner_contract_id);
+        if let Some(api_key) = self.config.stackerdb_write_api_key() {
+            miners_stackerdb = miners_stackerdb.with_api_key(api_key);
+        }
         let miner_db = MinerDB::open_with_config(&self.config).map_err(|e| e.to_string())?;
 
         SignerCoordinator::send_miners_message(
@@ -2833,7 +2836,7 @@ impl RelayerThread {
             .make_cost_metric()
             .unwrap_or_else(|| Box::new(UnitMetric));
 
-        let mempool = MemPoolDB::open(
+        let mut mempool = MemPoolDB::open(
             is_mainnet,
             chain_id,
             &stacks_chainstate_path,
@@ -2841,6 +2844,7 @@ impl RelayerThread {
             metric,
         )
         .expect("Database failure opening mempool");
+        mempool.replace_policy = config.node.mempool_replace_policy();
 
         let keychain = Keychain::default(config.node.seed.clone());
         let bitcoin_controller = BitcoinRegtestController::new_dummy(config.clone());
@@ -4370,14 +4374,16 @@ impl PeerThread {
             .make_cost_metric()
             .unwrap_or_else(|| Box::new(UnitMetric));
 
-        MemPoolDB::open(
+        let mut mempool = MemPoolDB::open(
             config.is_mainnet(),
             config.burnchain.chain_id,
             &config.get_chainstate_path_str(),
             cost_estimator,
             metric,
         )
-        .expect("Database failure opening mempool")
+        .expect("Database failure opening mempool");
+        mempool.replace_policy = config.node.mempool_replace_policy();
+        mempool
     }
 
     /// Instantiate the p2p thread.
@@ -4784,16 +4790,17 @@ impl StacksNode {
             tx.commit().unwrap();
         }
 
-        // update services to indicate we can support mempool sync and stackerdb
+        // update services to indicate we can support mempool sync and stackerdb (and, if
+        // configured, encrypted sessions)
         {
+            let mut services = (ServiceFlags::RPC as u16)
+                | (ServiceFlags::RELAY as u16)
+                | (ServiceFlags::STACKERDB as u16);
+            if config.connection_options.p2p_encryption {
+                services |= ServiceFlags::ENCRYPTED as u16;
+            }
             let tx = peerdb.tx_begin().unwrap();
-            PeerDB::set_local_services(
-                &tx,
-                (ServiceFlags::RPC as u16)
-                    | (ServiceFlags::RELAY as u16)
-                    | (ServiceFlags::STACKERDB as u16),
-            )
-            .unwrap();
+            PeerDB::set_local_services(&tx, services).unwrap();
             tx.commit().unwrap();
         }
 
@@ -5026,6 +5033,7 @@ impl StacksNode {
         let _ = Self::setup_mempool_db(&config);
 
         let mut p2p_net = Self::setup_peer_network(&config, &atlas_config, burnchain);
+        p2p_net.set_event_stream(runloop.get_event_dispatcher().event_stream());
 
         let stackerdbs = StackerDBs::connect(&config.get_stacker_db_file_path(), true)
             .expect("FATAL: failed to connect to stacker DB");
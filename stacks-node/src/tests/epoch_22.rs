@@ -8,7 +8,7 @@ use stacks::chainstate::stacks::address::PoxAddress;
 use stacks::chainstate::stacks::db::StacksChainState;
 use stacks::chainstate::stacks::miner::{signal_mining_blocked, signal_mining_ready};
 use stacks::clarity_cli::vm_execute as execute;
-use stacks::config::{EventKeyType, EventObserverConfig, InitialBalance};
+use stacks::config::{EventKeyType, EventObserverConfig, EventObserverTransport, InitialBalance};
 use stacks::core::test_util::{make_contract_call, make_stacks_transfer_serialized};
 use stacks::core::{self, EpochList, STACKS_EPOCH_MAX};
 use stacks::util_lib::boot::boot_code_id;
@@ -646,6 +646,8 @@ fn pox_2_unlock_all() {
         events_keys: vec![EventKeyType::AnyEvent],
         timeout_ms: 1000,
         disable_retries: false,
+        transport: EventObserverTransport::Http,
+        tls_ca_cert_path: None,
     });
     conf.initial_balances.append(&mut initial_balances);
 
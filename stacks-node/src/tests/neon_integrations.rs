@@ -40,7 +40,9 @@ use stacks::chainstate::stacks::{
 use stacks::clarity_cli::vm_execute as execute;
 use stacks::cli;
 use stacks::codec::StacksMessageCodec;
-use stacks::config::{EventKeyType, EventObserverConfig, FeeEstimatorName, InitialBalance};
+use stacks::config::{
+    EventKeyType, EventObserverConfig, EventObserverTransport, FeeEstimatorName, InitialBalance,
+};
 use stacks::core::mempool::{MemPoolWalkStrategy, MemPoolWalkTxTypes};
 use stacks::core::test_util::{
     make_contract_call, make_contract_publish, make_contract_publish_microblock_only,
@@ -284,7 +286,7 @@ pub mod test_observer {
     use stacks::chainstate::stacks::events::StackerDBChunksEvent;
     use stacks::chainstate::stacks::StacksTransaction;
     use stacks::codec::StacksMessageCodec;
-    use stacks::config::{EventKeyType, EventObserverConfig};
+    use stacks::config::{EventKeyType, EventObserverConfig, EventObserverTransport};
     use stacks::net::api::postblock_proposal::BlockValidateResponse;
     use stacks::util::hash::hex_bytes;
     use stacks_common::types::chainstate::StacksBlockId;
@@ -745,6 +747,8 @@ pub mod test_observer {
             events_keys: event_keys.to_vec(),
             timeout_ms: 1000,
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
     }
 
@@ -5701,6 +5705,8 @@ fn atlas_integration_test() {
             events_keys: vec![EventKeyType::AnyEvent],
             timeout_ms: 1000,
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
 
     conf_follower_node.node.always_use_affirmation_maps = false;
@@ -6235,6 +6241,8 @@ fn antientropy_integration_test() {
             events_keys: vec![EventKeyType::AnyEvent],
             timeout_ms: 1000,
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
 
     conf_follower_node.node.mine_microblocks = true;
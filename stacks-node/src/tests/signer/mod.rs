@@ -40,7 +40,9 @@ use stacks::chainstate::nakamoto::signer_set::NakamotoSigners;
 use stacks::chainstate::nakamoto::NakamotoBlock;
 use stacks::chainstate::stacks::boot::{NakamotoSignerEntry, SIGNERS_NAME};
 use stacks::chainstate::stacks::StacksPrivateKey;
-use stacks::config::{Config as NeonConfig, EventKeyType, EventObserverConfig, InitialBalance};
+use stacks::config::{
+    Config as NeonConfig, EventKeyType, EventObserverConfig, EventObserverTransport, InitialBalance,
+};
 use stacks::core::test_util::{
     make_contract_call, make_contract_publish, make_stacks_transfer_serialized,
 };
@@ -1576,6 +1578,8 @@ fn setup_stx_btc_node<G: FnMut(&mut NeonConfig)>(
             ],
             timeout_ms: 1000,
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         });
     }
 
@@ -1592,6 +1596,8 @@ fn setup_stx_btc_node<G: FnMut(&mut NeonConfig)>(
         ],
         timeout_ms: 1000,
         disable_retries: false,
+        transport: EventObserverTransport::Http,
+        tls_ca_cert_path: None,
     });
 
     // The signers need some initial balances in order to pay for epoch 2.5 transaction votes
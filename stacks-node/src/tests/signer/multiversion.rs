@@ -18,15 +18,18 @@ use std::time::{Duration, SystemTime};
 
 use libsigner::v0::messages::{SignerMessage, StateMachineUpdate};
 use libsigner::v0::signer_state::{MinerState, ReplayTransactionSet, SignerStateMachine};
+use libsigner_v3_1_0_0_13;
+use signer_v3_1_0_0_13;
 use stacks::chainstate::stacks::StacksTransaction;
 use stacks::util::hash::Hash160;
 use stacks::util::secp256k1::Secp256k1PrivateKey;
 use stacks_common::types::chainstate::{ConsensusHash, StacksBlockId};
+use stacks_common_v3_1_00_13;
 use stacks_common_v3_1_00_13::codec::StacksMessageCodec as OldStacksMessageCodec;
 use stacks_signer::runloop::{RewardCycleInfo, State, StateInfo};
 use stacks_signer::v0::signer_state::LocalStateMachine;
 use stacks_signer::v0::SpawnedSigner;
-use {libsigner_v3_1_0_0_13, signer_v3_1_0_0_13, stacks_common_v3_1_00_13, stacks_v3_1_00_13};
+use stacks_v3_1_00_13;
 
 use super::SpawnedSignerTrait;
 use crate::stacks_common::codec::StacksMessageCodec;
@@ -139,7 +142,11 @@ impl SpawnedSignerTrait for MultiverSpawnedSigner {
                 node_host: c.node_host,
                 endpoint: c.endpoint,
                 stacks_private_key: serde_json::from_value(
-                    serde_json::to_value(&c.stacks_private_key).unwrap(),
+                    serde_json::to_value(
+                        c.stacks_private_key
+                            .expect("Older signers need a locally configured private key"),
+                    )
+                    .unwrap(),
                 )
                 .unwrap(),
                 stacks_address: serde_json::from_value(
@@ -256,6 +263,7 @@ impl SpawnedSignerTrait for MultiverSpawnedSigner {
                             )
                         })
                         .collect(),
+                    additional_identities: vec![],
                 })
             }
             ReceiveResult::Current(signer_result) => {
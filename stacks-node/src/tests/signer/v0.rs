@@ -53,7 +53,9 @@ use stacks::chainstate::stacks::{
     StacksTransaction, TenureChangeCause, TenureChangePayload, TransactionPayload,
 };
 use stacks::codec::StacksMessageCodec;
-use stacks::config::{Config as NeonConfig, EventKeyType, EventObserverConfig};
+use stacks::config::{
+    Config as NeonConfig, EventKeyType, EventObserverConfig, EventObserverTransport,
+};
 use stacks::core::mempool::MemPoolWalkStrategy;
 use stacks::core::test_util::{
     insert_tx_in_mempool, make_big_read_count_contract, make_contract_call, make_contract_publish,
@@ -2303,6 +2305,8 @@ fn regr_use_block_header_pk() {
             db_path: None,
             timeout: Duration::from_secs(120),
             disable_retries: false,
+            transport: EventObserverTransport::Http,
+            tls_ca_cert_path: None,
         })
         .collect();
 
@@ -7788,6 +7792,8 @@ fn signer_set_rollover() {
                     ],
                     timeout_ms: 1000,
                     disable_retries: false,
+                    transport: EventObserverTransport::Http,
+                    tls_ca_cert_path: None,
                 });
             }
             naka_conf.node.rpc_bind = rpc_bind.clone();
@@ -8305,6 +8311,8 @@ fn signer_multinode_rollover() {
                     ],
                     timeout_ms: 1000,
                     disable_retries: false,
+                    transport: EventObserverTransport::Http,
+                    tls_ca_cert_path: None,
                 });
             }
         },
@@ -12574,6 +12582,8 @@ fn injected_signatures_are_ignored_across_boundaries() {
                 ],
                 timeout_ms: 1000,
                 disable_retries: false,
+                transport: EventObserverTransport::Http,
+                tls_ca_cert_path: None,
             });
             naka_conf.node.rpc_bind = rpc_bind.clone();
         },
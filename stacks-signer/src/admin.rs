@@ -0,0 +1,651 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The signer admin API.
+//!
+//! When `admin_auth_token` is set in the signer config, the monitoring server also answers
+//! the following requests. Each must carry the token in its `Authorization` header.
+//!
+//! - `GET /admin/signers`: the Stacks addresses of the signing identities, starting with the
+//!   one configured at the top level
+//! - `GET /admin/blocks?limit=<n>`: the most recently proposed blocks, newest first
+//! - `GET /admin/blocks/<signer_signature_hash>`: a single block
+//! - `POST /admin/blocks/<signer_signature_hash>/reject`: override the signer's decision on a
+//!   pending block
+//! - `GET /admin/state-machine`: the signer's local state machine for each reward cycle
+//! - `GET /admin/state-machine-updates?reward_cycle=<n>`: the latest state machine update from
+//!   each signer in the reward cycle (by default, the latest one with a running signer)
+//! - `GET /admin/signing`: whether signing is paused
+//! - `POST /admin/signing/pause`: stop responding to new block proposals. Blocks which were
+//!   already submitted for validation are still answered.
+//! - `POST /admin/signing/resume`: resume responding to block proposals
+//!
+//! Blocks are listed with their state, the signatures collected for them and the signers
+//! which rejected them. Rejecting a block marks it as locally rejected in the signer's
+//! database so that the signer will never sign it; no rejection is sent to the miner.
+//!
+//! If the signer runs several identities, the block and state machine routes take a
+//! `signer=<stacks address>` query parameter that selects the identity to describe; without
+//! it they describe the identity configured at the top level. Pausing applies to all of them.
+//!
+//! There is no route that creates a block override (as `create_block_override` does in the
+//! signer db tests). That helper fabricates a `BlockInfo` row for a proposal the signer never
+//! received, which the signer would then treat as a real proposal; an operator can only
+//! override decisions on blocks the signer has actually seen, with the reject route.
+//!
+//! The admin API is served over plain HTTP on the monitoring server's `metrics_endpoint`, so
+//! the token is sent in the clear. That endpoint must only be reachable from the signer's
+//! host or a trusted network, and must never be exposed publicly.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde_json::json;
+use stacks_common::types::chainstate::StacksAddress;
+use stacks_common::types::Address;
+use stacks_common::util::hash::Sha512Trunc256Sum;
+use stacks_common::{info, warn};
+
+use crate::signerdb::{BlockInfo, SignerDb};
+use crate::v0::signer_state::LocalStateMachine;
+
+/// How many blocks `/admin/blocks` returns if no limit is given
+const DEFAULT_BLOCK_LIST_LIMIT: u64 = 20;
+/// The most blocks `/admin/blocks` will return
+const MAX_BLOCK_LIST_LIMIT: u64 = 500;
+
+/// Signer state that the admin API can inspect and change while the signer runs
+#[derive(Debug, Default)]
+pub struct SignerAdminState {
+    /// Whether the operator has paused signing
+    paused: AtomicBool,
+    /// The latest local state machine of each running signer, keyed by identity and reward
+    /// cycle
+    local_state_machines: Mutex<HashMap<StacksAddress, BTreeMap<u64, LocalStateMachine>>>,
+}
+
+impl SignerAdminState {
+    /// Whether the operator has paused signing
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Pause or resume signing
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    /// Record the local state machine of the identity's signer for `reward_cycle`
+    pub fn record_local_state_machine(
+        &self,
+        signer_address: &StacksAddress,
+        reward_cycle: u64,
+        state: &LocalStateMachine,
+    ) {
+        let mut all_state_machines = self
+            .local_state_machines
+            .lock()
+            .expect("FATAL: admin state lock poisoned");
+        let local_state_machines = all_state_machines.entry(*signer_address).or_default();
+        if local_state_machines.get(&reward_cycle) != Some(state) {
+            local_state_machines.insert(reward_cycle, state.clone());
+        }
+        // Only the current and next reward cycles have running signers
+        while local_state_machines.len() > 2 {
+            local_state_machines.pop_first();
+        }
+    }
+
+    /// The latest local state machine of each of the identity's running signers, keyed by
+    /// reward cycle
+    pub fn local_state_machines(
+        &self,
+        signer_address: &StacksAddress,
+    ) -> BTreeMap<u64, LocalStateMachine> {
+        self.local_state_machines
+            .lock()
+            .expect("FATAL: admin state lock poisoned")
+            .get(signer_address)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// A response to an admin API request
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdminResponse {
+    /// The HTTP status code
+    pub status: u16,
+    /// The JSON body
+    pub body: String,
+}
+
+impl AdminResponse {
+    fn ok(body: serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            body: body.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message }).to_string(),
+        }
+    }
+}
+
+/// Handles admin API requests for a signer and each of its signing identities
+#[derive(Debug, Clone)]
+pub struct AdminApi {
+    auth_token: String,
+    /// The address and database path of each identity, starting with the top-level one
+    identities: Vec<(StacksAddress, PathBuf)>,
+    state: Arc<SignerAdminState>,
+}
+
+impl AdminApi {
+    /// Create a handler which accepts requests carrying `auth_token`. `identities` holds the
+    /// address and database path of each signing identity, starting with the top-level one.
+    pub fn new(
+        auth_token: String,
+        identities: Vec<(StacksAddress, PathBuf)>,
+        state: Arc<SignerAdminState>,
+    ) -> Self {
+        assert!(
+            !identities.is_empty(),
+            "The admin API needs at least one signing identity"
+        );
+        Self {
+            auth_token,
+            identities,
+            state,
+        }
+    }
+
+    /// Whether `url` is an admin API path
+    pub fn is_admin_url(url: &str) -> bool {
+        url == "/admin" || url.starts_with("/admin/") || url.starts_with("/admin?")
+    }
+
+    /// Handle a request. `method` is the upper-case HTTP method and `authorization` is the
+    /// value of the request's `Authorization` header, if any.
+    pub fn handle(&self, method: &str, url: &str, authorization: Option<&str>) -> AdminResponse {
+        if !authorization.is_some_and(|token| tokens_match(&self.auth_token, token)) {
+            return AdminResponse::error(401, "Unauthorized");
+        }
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let segments: Vec<&str> = path
+            .trim_start_matches("/admin")
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+        let result = match (method, segments.as_slice()) {
+            ("GET", ["signers"]) => Ok(json!(self
+                .identities
+                .iter()
+                .map(|(address, _)| address.to_string())
+                .collect::<Vec<_>>())),
+            ("GET", ["blocks"]) => self.list_blocks(query),
+            ("GET", ["blocks", hash]) => self.get_block(hash, query),
+            ("POST", ["blocks", hash, "reject"]) => self.reject_block(hash, query),
+            ("GET", ["state-machine"]) => self
+                .identity(query)
+                .map(|(address, _)| json!(self.state.local_state_machines(address))),
+            ("GET", ["state-machine-updates"]) => self.list_state_machine_updates(query),
+            ("GET", ["signing"]) => Ok(self.signing_status()),
+            ("POST", ["signing", "pause"]) => {
+                info!("Admin: signing paused by the operator");
+                self.state.set_paused(true);
+                Ok(self.signing_status())
+            }
+            ("POST", ["signing", "resume"]) => {
+                info!("Admin: signing resumed by the operator");
+                self.state.set_paused(false);
+                Ok(self.signing_status())
+            }
+            _ => Err(AdminResponse::error(404, "Not Found")),
+        };
+        result.map_or_else(|e| e, AdminResponse::ok)
+    }
+
+    fn signing_status(&self) -> serde_json::Value {
+        json!({ "paused": self.state.is_paused() })
+    }
+
+    /// The identity selected by the request's `signer` parameter, or the top-level one
+    fn identity(&self, query: &str) -> Result<&(StacksAddress, PathBuf), AdminResponse> {
+        let Some(signer) = query_param(query, "signer") else {
+            return Ok(&self.identities[0]);
+        };
+        let address = StacksAddress::from_string(signer)
+            .ok_or_else(|| AdminResponse::error(400, "Invalid signer address"))?;
+        self.identities
+            .iter()
+            .find(|(identity_address, _)| *identity_address == address)
+            .ok_or_else(|| AdminResponse::error(404, "Unknown signer"))
+    }
+
+    /// Open the database of the identity selected by the request
+    fn open_db(&self, query: &str) -> Result<SignerDb, AdminResponse> {
+        let (_, db_path) = self.identity(query)?;
+        SignerDb::new(db_path).map_err(|e| {
+            warn!("Admin: failed to open signer db: {e:?}");
+            AdminResponse::error(500, "Failed to open signer db")
+        })
+    }
+
+    fn list_blocks(&self, query: &str) -> Result<serde_json::Value, AdminResponse> {
+        let limit = match query_param(query, "limit") {
+            Some(limit) => limit
+                .parse::<u64>()
+                .map_err(|_| AdminResponse::error(400, "Invalid limit"))?
+                .min(MAX_BLOCK_LIST_LIMIT),
+            None => DEFAULT_BLOCK_LIST_LIMIT,
+        };
+        let db = self.open_db(query)?;
+        let blocks = db.get_recent_blocks(limit).map_err(db_error)?;
+        let blocks = blocks
+            .iter()
+            .map(|block_info| block_to_json(&db, block_info))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(json!(blocks))
+    }
+
+    fn get_block(&self, hash: &str, query: &str) -> Result<serde_json::Value, AdminResponse> {
+        let hash = parse_signer_signature_hash(hash)?;
+        let db = self.open_db(query)?;
+        let block_info = db
+            .block_lookup(&hash)
+            .map_err(db_error)?
+            .ok_or_else(|| AdminResponse::error(404, "Block not found"))?;
+        block_to_json(&db, &block_info)
+    }
+
+    fn reject_block(&self, hash: &str, query: &str) -> Result<serde_json::Value, AdminResponse> {
+        let hash = parse_signer_signature_hash(hash)?;
+        let (signer_address, _) = self.identity(query)?;
+        let mut db = self.open_db(query)?;
+        let mut block_info = db
+            .block_lookup(&hash)
+            .map_err(db_error)?
+            .ok_or_else(|| AdminResponse::error(404, "Block not found"))?;
+        if block_info.is_locally_finalized() {
+            return Err(AdminResponse::error(
+                409,
+                &format!("Block is already {}", block_info.state),
+            ));
+        }
+        block_info
+            .mark_locally_rejected()
+            .map_err(|e| AdminResponse::error(409, &e))?;
+        db.insert_block(&block_info).map_err(db_error)?;
+        info!("Admin: block rejected by the operator";
+            "signer_address" => %signer_address,
+            "signer_signature_hash" => %hash,
+            "block_id" => %block_info.block.block_id(),
+        );
+        block_to_json(&db, &block_info)
+    }
+
+    fn list_state_machine_updates(&self, query: &str) -> Result<serde_json::Value, AdminResponse> {
+        let reward_cycle = match query_param(query, "reward_cycle") {
+            Some(reward_cycle) => reward_cycle
+                .parse::<u64>()
+                .map_err(|_| AdminResponse::error(400, "Invalid reward_cycle"))?,
+            None => *self
+                .state
+                .local_state_machines(&self.identity(query)?.0)
+                .keys()
+                .next_back()
+                .ok_or_else(|| AdminResponse::error(400, "No running signer; set reward_cycle"))?,
+        };
+        let mut db = self.open_db(query)?;
+        let updates = db
+            .get_signer_state_machine_updates(reward_cycle)
+            .map_err(db_error)?;
+        let updates: BTreeMap<String, _> = updates
+            .into_iter()
+            .map(|(address, update)| (address.to_string(), update))
+            .collect();
+        Ok(json!({
+            "reward_cycle": reward_cycle,
+            "updates": updates,
+        }))
+    }
+}
+
+/// Compare two tokens in time that depends only on their lengths, so that response times
+/// don't reveal how much of a guessed token is correct
+pub(crate) fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn db_error(e: blockstack_lib::util_lib::db::Error) -> AdminResponse {
+    warn!("Admin: signer db error: {e:?}");
+    AdminResponse::error(500, "Signer db error")
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find_map(|(key, value)| (key == name).then_some(value))
+}
+
+fn parse_signer_signature_hash(hash: &str) -> Result<Sha512Trunc256Sum, AdminResponse> {
+    Sha512Trunc256Sum::from_hex(hash)
+        .map_err(|_| AdminResponse::error(400, "Invalid signer signature hash"))
+}
+
+fn block_to_json(
+    db: &SignerDb,
+    block_info: &BlockInfo,
+) -> Result<serde_json::Value, AdminResponse> {
+    let hash = block_info.signer_signature_hash();
+    let signatures = db.get_block_signatures(&hash).map_err(db_error)?;
+    let rejections = db
+        .get_block_rejection_signer_addrs(&hash)
+        .map_err(db_error)?
+        .into_iter()
+        .map(|(address, reason)| json!({ "signer": address.to_string(), "reason": reason }))
+        .collect::<Vec<_>>();
+    Ok(json!({
+        "signer_signature_hash": hash.to_hex(),
+        "block_id": block_info.block.block_id().to_hex(),
+        "consensus_hash": block_info.block.header.consensus_hash.to_hex(),
+        "stacks_height": block_info.block.header.chain_length,
+        "burn_block_height": block_info.burn_block_height,
+        "reward_cycle": block_info.reward_cycle,
+        "state": block_info.state.to_string(),
+        "valid": block_info.valid,
+        "proposed_time": block_info.proposed_time,
+        "signed_self": block_info.signed_self,
+        "signed_group": block_info.signed_group,
+        "reject_reason": block_info.reject_reason.as_ref().map(|r| r.to_string()),
+        "signatures": signatures.iter().map(|s| s.to_hex()).collect::<Vec<_>>(),
+        "rejections": rejections,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use clarity::types::chainstate::{
+        ConsensusHash, StacksAddress, StacksPrivateKey, StacksPublicKey,
+    };
+    use libsigner::v0::messages::RejectReason;
+
+    use super::*;
+    use crate::signerdb::tests::{create_block_override, tmp_db_path};
+    use crate::signerdb::BlockState;
+
+    const TOKEN: &str = "admin-token";
+
+    fn setup_api() -> (AdminApi, SignerDb) {
+        let db_path = tmp_db_path();
+        let db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        let api = AdminApi::new(
+            TOKEN.to_string(),
+            vec![(StacksAddress::burn_address(false), db_path)],
+            Arc::new(SignerAdminState::default()),
+        );
+        (api, db)
+    }
+
+    fn body(response: &AdminResponse) -> serde_json::Value {
+        serde_json::from_str(&response.body).expect("Response body is not JSON")
+    }
+
+    #[test]
+    fn admin_urls() {
+        assert!(AdminApi::is_admin_url("/admin"));
+        assert!(AdminApi::is_admin_url("/admin/blocks?limit=1"));
+        assert!(!AdminApi::is_admin_url("/administrator"));
+        assert!(!AdminApi::is_admin_url("/metrics"));
+    }
+
+    #[test]
+    fn requires_auth_token() {
+        let (api, _db) = setup_api();
+        assert_eq!(api.handle("GET", "/admin/signing", None).status, 401);
+        assert_eq!(
+            api.handle("GET", "/admin/signing", Some("wrong-token"))
+                .status,
+            401
+        );
+        // Same length as the token, differing only in the last byte
+        assert_eq!(
+            api.handle("GET", "/admin/signing", Some("admin-tokem"))
+                .status,
+            401
+        );
+        // The token is checked before routing, so unknown paths don't leak
+        assert_eq!(api.handle("GET", "/admin/unknown", None).status, 401);
+        assert_eq!(api.handle("GET", "/admin/unknown", Some(TOKEN)).status, 404);
+    }
+
+    #[test]
+    fn pause_and_resume_signing() {
+        let (api, _db) = setup_api();
+        let response = api.handle("GET", "/admin/signing", Some(TOKEN));
+        assert_eq!(response.status, 200);
+        assert_eq!(body(&response), json!({ "paused": false }));
+
+        // Only POST changes the state
+        assert_eq!(
+            api.handle("GET", "/admin/signing/pause", Some(TOKEN))
+                .status,
+            404
+        );
+        assert!(!api.state.is_paused());
+
+        let response = api.handle("POST", "/admin/signing/pause", Some(TOKEN));
+        assert_eq!(body(&response), json!({ "paused": true }));
+        assert!(api.state.is_paused());
+
+        let response = api.handle("POST", "/admin/signing/resume", Some(TOKEN));
+        assert_eq!(body(&response), json!({ "paused": false }));
+        assert!(!api.state.is_paused());
+    }
+
+    #[test]
+    fn list_and_get_blocks() {
+        let (api, mut db) = setup_api();
+        let (mut block_info_1, _) = create_block_override(|b| {
+            b.block.header.consensus_hash = ConsensusHash([0x01; 20]);
+        });
+        block_info_1.proposed_time = 1;
+        let (mut block_info_2, _) = create_block_override(|b| {
+            b.block.header.consensus_hash = ConsensusHash([0x02; 20]);
+        });
+        block_info_2.proposed_time = 2;
+        db.insert_block(&block_info_1).unwrap();
+        db.insert_block(&block_info_2).unwrap();
+
+        let hash_1 = block_info_1.signer_signature_hash();
+        let signer = StacksAddress::burn_address(false);
+        db.add_block_rejection_signer_addr(&hash_1, &signer, &RejectReason::DuplicateBlockFound)
+            .unwrap();
+
+        let response = api.handle("GET", "/admin/blocks", Some(TOKEN));
+        assert_eq!(response.status, 200);
+        let blocks = body(&response);
+        let blocks = blocks.as_array().unwrap();
+        assert_eq!(blocks.len(), 2);
+        // Newest first
+        assert_eq!(
+            blocks[0]["signer_signature_hash"],
+            block_info_2.signer_signature_hash().to_hex()
+        );
+        assert_eq!(blocks[1]["signer_signature_hash"], hash_1.to_hex());
+
+        let response = api.handle("GET", "/admin/blocks?limit=1", Some(TOKEN));
+        assert_eq!(body(&response).as_array().unwrap().len(), 1);
+        assert_eq!(
+            api.handle("GET", "/admin/blocks?limit=abc", Some(TOKEN))
+                .status,
+            400
+        );
+
+        let response = api.handle(
+            "GET",
+            &format!("/admin/blocks/{}", hash_1.to_hex()),
+            Some(TOKEN),
+        );
+        assert_eq!(response.status, 200);
+        let block = body(&response);
+        assert_eq!(block["state"], BlockState::Unprocessed.to_string());
+        assert_eq!(block["rejections"][0]["signer"], signer.to_string());
+        assert_eq!(block["rejections"][0]["reason"], "DuplicateBlockFound");
+
+        assert_eq!(
+            api.handle("GET", "/admin/blocks/not-a-hash", Some(TOKEN))
+                .status,
+            400
+        );
+        let unknown = Sha512Trunc256Sum([0xff; 32]).to_hex();
+        assert_eq!(
+            api.handle("GET", &format!("/admin/blocks/{unknown}"), Some(TOKEN))
+                .status,
+            404
+        );
+    }
+
+    #[test]
+    fn reject_block() {
+        let (api, mut db) = setup_api();
+        let (block_info, _) = create_block_override(|_| {});
+        db.insert_block(&block_info).unwrap();
+        let hash = block_info.signer_signature_hash();
+        let url = format!("/admin/blocks/{}/reject", hash.to_hex());
+
+        let response = api.handle("POST", &url, Some(TOKEN));
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            body(&response)["state"],
+            BlockState::LocallyRejected.to_string()
+        );
+        let block_info = db.block_lookup(&hash).unwrap().unwrap();
+        assert_eq!(block_info.state, BlockState::LocallyRejected);
+
+        // A block the signer already decided on cannot be overridden
+        assert_eq!(api.handle("POST", &url, Some(TOKEN)).status, 409);
+    }
+
+    #[test]
+    fn state_machine_updates_need_a_reward_cycle() {
+        let (api, _db) = setup_api();
+        assert_eq!(
+            api.handle("GET", "/admin/state-machine-updates", Some(TOKEN))
+                .status,
+            400
+        );
+        let response = api.handle(
+            "GET",
+            "/admin/state-machine-updates?reward_cycle=42",
+            Some(TOKEN),
+        );
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            body(&response),
+            json!({ "reward_cycle": 42, "updates": {} })
+        );
+        let response = api.handle("GET", "/admin/state-machine", Some(TOKEN));
+        assert_eq!(body(&response), json!({}));
+    }
+
+    #[test]
+    fn selects_identity() {
+        let first_address = StacksAddress::burn_address(false);
+        let second_address = StacksAddress::burn_address(true);
+        let second_db_path = tmp_db_path();
+        let mut second_db = SignerDb::new(&second_db_path).expect("Failed to create signer db");
+        let api = AdminApi::new(
+            TOKEN.to_string(),
+            vec![
+                (first_address, tmp_db_path()),
+                (second_address, second_db_path),
+            ],
+            Arc::new(SignerAdminState::default()),
+        );
+        let (block_info, _) = create_block_override(|_| {});
+        second_db.insert_block(&block_info).unwrap();
+        api.state
+            .record_local_state_machine(&second_address, 7, &LocalStateMachine::Uninitialized);
+
+        let response = api.handle("GET", "/admin/signers", Some(TOKEN));
+        assert_eq!(
+            body(&response),
+            json!([first_address.to_string(), second_address.to_string()])
+        );
+
+        // Without a signer, the routes describe the top-level identity
+        let response = api.handle("GET", "/admin/blocks", Some(TOKEN));
+        assert_eq!(body(&response), json!([]));
+        let response = api.handle("GET", "/admin/state-machine", Some(TOKEN));
+        assert_eq!(body(&response), json!({}));
+
+        let response = api.handle(
+            "GET",
+            &format!("/admin/blocks?signer={second_address}"),
+            Some(TOKEN),
+        );
+        assert_eq!(body(&response).as_array().unwrap().len(), 1);
+        let response = api.handle(
+            "GET",
+            &format!("/admin/state-machine?signer={second_address}"),
+            Some(TOKEN),
+        );
+        assert!(body(&response).get("7").is_some());
+        let response = api.handle(
+            "GET",
+            &format!("/admin/state-machine-updates?signer={second_address}"),
+            Some(TOKEN),
+        );
+        assert_eq!(body(&response)["reward_cycle"], 7);
+        let url = format!(
+            "/admin/blocks/{}/reject?signer={second_address}",
+            block_info.signer_signature_hash().to_hex()
+        );
+        assert_eq!(api.handle("POST", &url, Some(TOKEN)).status, 200);
+
+        assert_eq!(
+            api.handle("GET", "/admin/blocks?signer=not-an-address", Some(TOKEN))
+                .status,
+            400
+        );
+        let unknown = StacksAddress::p2pkh(
+            false,
+            &StacksPublicKey::from_private(&StacksPrivateKey::random()),
+        );
+        assert_eq!(
+            api.handle(
+                "GET",
+                &format!("/admin/blocks?signer={unknown}"),
+                Some(TOKEN)
+            )
+            .status,
+            404
+        );
+    }
+}
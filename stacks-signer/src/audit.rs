@@ -0,0 +1,354 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! An audit log of the signer's block decisions.
+//!
+//! Every time the signer decides whether to accept a block proposal, it appends a
+//! [BlockAuditRecord] to its database. Records are never updated: a proposal which is
+//! rejected by the signer's own checks and later reconsidered gets a second record.
+//!
+//! `stacks-signer export-audit` renders the records for a range of burn block heights as
+//! JSON Lines or CSV. The fields are part of the export format. Their names, meanings and
+//! (in CSV) their order do not change within an [AUDIT_SCHEMA_VERSION]; new fields are only
+//! added at the end, along with a new schema version.
+
+use std::borrow::Cow;
+use std::io::Write;
+
+use clap::ValueEnum;
+use clarity::types::chainstate::StacksAddress;
+use libsigner::v0::messages::{BlockResponse, RejectReasonPrefix};
+use serde::{Deserialize, Serialize};
+use stacks_common::util::get_epoch_time_secs;
+
+use crate::signerdb::BlockInfo;
+
+/// The version of the [BlockAuditRecord] fields
+pub const AUDIT_SCHEMA_VERSION: u32 = 1;
+
+/// The point at which the signer made its decision on a proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditStage {
+    /// The signer's own checks rejected the proposal before it was sent to the stacks-node
+    ProposalCheck,
+    /// The signer decided once the stacks-node answered its `/v3/block_proposal` request
+    NodeValidation,
+    /// The stacks-node did not answer the `/v3/block_proposal` request in time
+    ValidationTimeout,
+}
+
+impl AuditStage {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AuditStage::ProposalCheck => "proposal_check",
+            AuditStage::NodeValidation => "node_validation",
+            AuditStage::ValidationTimeout => "validation_timeout",
+        }
+    }
+}
+
+/// The signer's decision on a proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditDecision {
+    /// The signer signed the block
+    Accept,
+    /// The signer rejected the block
+    Reject,
+}
+
+impl AuditDecision {
+    fn as_str(&self) -> &'static str {
+        match self {
+            AuditDecision::Accept => "accept",
+            AuditDecision::Reject => "reject",
+        }
+    }
+}
+
+/// A record of one decision on a block proposal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockAuditRecord {
+    /// The [AUDIT_SCHEMA_VERSION] the record was written with
+    pub schema_version: u32,
+    /// The address of the signer which made the decision
+    pub signer_address: String,
+    /// The reward cycle the proposal belongs to
+    pub reward_cycle: u64,
+    /// The burn block height at which the block was proposed
+    pub burn_block_height: u64,
+    /// The block's signer signature hash, which identifies the proposal
+    pub signer_signature_hash: String,
+    /// The block's ID
+    pub block_id: String,
+    /// The block's height in the Stacks chain
+    pub block_height: u64,
+    /// The consensus hash of the block's tenure
+    pub consensus_hash: String,
+    /// The public key recovered from the miner's signature, if it could be recovered
+    pub miner_pubkey: Option<String>,
+    /// The tip burn block in the signer's sortition view
+    pub view_burn_block: Option<String>,
+    /// The height of the tip burn block in the signer's sortition view, if known
+    pub view_burn_block_height: Option<u64>,
+    /// The public key hash of the miner the signer's sortition view expects
+    pub view_miner_pkh: Option<String>,
+    /// The tenure the signer's sortition view expects blocks to be built in
+    pub view_tenure_id: Option<String>,
+    /// When the decision was made
+    pub stage: AuditStage,
+    /// `ok`, or the reject code returned by the stacks-node's block validation
+    pub validation_result: Option<String>,
+    /// The reason the stacks-node gave for failing the block's validation
+    pub validation_reason: Option<String>,
+    /// How long the stacks-node took to validate the block
+    pub validation_time_ms: Option<u64>,
+    /// The signer's decision
+    pub decision: AuditDecision,
+    /// The reject reason code, if the signer rejected the block
+    pub reject_code: Option<String>,
+    /// The full reject reason, if the signer rejected the block
+    pub reject_reason: Option<String>,
+    /// When the signer received the proposal (epoch time in seconds)
+    pub proposal_received_time: u64,
+    /// When the signer made the decision (epoch time in seconds)
+    pub decision_time: u64,
+}
+
+/// The CSV columns, in the order of the [BlockAuditRecord] fields
+pub const AUDIT_CSV_COLUMNS: [&str; 22] = [
+    "schema_version",
+    "signer_address",
+    "reward_cycle",
+    "burn_block_height",
+    "signer_signature_hash",
+    "block_id",
+    "block_height",
+    "consensus_hash",
+    "miner_pubkey",
+    "view_burn_block",
+    "view_burn_block_height",
+    "view_miner_pkh",
+    "view_tenure_id",
+    "stage",
+    "validation_result",
+    "validation_reason",
+    "validation_time_ms",
+    "decision",
+    "reject_code",
+    "reject_reason",
+    "proposal_received_time",
+    "decision_time",
+];
+
+impl BlockAuditRecord {
+    /// Record `response` as the decision on `block_info`. The sortition view and validation
+    /// fields are left empty for the caller to fill in.
+    pub fn new(
+        signer_address: &StacksAddress,
+        block_info: &BlockInfo,
+        stage: AuditStage,
+        response: &BlockResponse,
+    ) -> Self {
+        let (decision, reject_code, reject_reason) = match response {
+            BlockResponse::Accepted(_) => (AuditDecision::Accept, None, None),
+            BlockResponse::Rejected(rejection) => {
+                let reason = &rejection.response_data.reject_reason;
+                (
+                    AuditDecision::Reject,
+                    Some(format!("{:?}", RejectReasonPrefix::from(reason))),
+                    Some(reason.to_string()),
+                )
+            }
+        };
+        Self {
+            schema_version: AUDIT_SCHEMA_VERSION,
+            signer_address: signer_address.to_string(),
+            reward_cycle: block_info.reward_cycle,
+            burn_block_height: block_info.burn_block_height,
+            signer_signature_hash: block_info.signer_signature_hash().to_string(),
+            block_id: block_info.block.block_id().to_string(),
+            block_height: block_info.block.header.chain_length,
+            consensus_hash: block_info.block.header.consensus_hash.to_string(),
+            miner_pubkey: block_info
+                .block
+                .header
+                .recover_miner_pk()
+                .map(|pk| pk.to_hex()),
+            view_burn_block: None,
+            view_burn_block_height: None,
+            view_miner_pkh: None,
+            view_tenure_id: None,
+            stage,
+            validation_result: None,
+            validation_reason: None,
+            validation_time_ms: None,
+            decision,
+            reject_code,
+            reject_reason,
+            proposal_received_time: block_info.proposed_time,
+            decision_time: get_epoch_time_secs(),
+        }
+    }
+
+    /// The record's fields as CSV values, in the order of [AUDIT_CSV_COLUMNS]
+    fn csv_values(&self) -> [String; AUDIT_CSV_COLUMNS.len()] {
+        fn opt<T: ToString>(value: &Option<T>) -> String {
+            value.as_ref().map(T::to_string).unwrap_or_default()
+        }
+        [
+            self.schema_version.to_string(),
+            self.signer_address.clone(),
+            self.reward_cycle.to_string(),
+            self.burn_block_height.to_string(),
+            self.signer_signature_hash.clone(),
+            self.block_id.clone(),
+            self.block_height.to_string(),
+            self.consensus_hash.clone(),
+            opt(&self.miner_pubkey),
+            opt(&self.view_burn_block),
+            opt(&self.view_burn_block_height),
+            opt(&self.view_miner_pkh),
+            opt(&self.view_tenure_id),
+            self.stage.as_str().to_string(),
+            opt(&self.validation_result),
+            opt(&self.validation_reason),
+            opt(&self.validation_time_ms),
+            self.decision.as_str().to_string(),
+            opt(&self.reject_code),
+            opt(&self.reject_reason),
+            self.proposal_received_time.to_string(),
+            self.decision_time.to_string(),
+        ]
+    }
+}
+
+/// The formats `export-audit` can render records in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AuditFormat {
+    /// One JSON object per line
+    JsonLines,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+/// Quote a CSV value if it contains a separator, quote or line break
+fn csv_escape(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Write `records` to `out` in `format`
+pub fn write_audit_records(
+    records: &[BlockAuditRecord],
+    format: AuditFormat,
+    mut out: impl Write,
+) -> std::io::Result<()> {
+    match format {
+        AuditFormat::JsonLines => {
+            for record in records {
+                serde_json::to_writer(&mut out, record)?;
+                writeln!(out)?;
+            }
+        }
+        AuditFormat::Csv => {
+            writeln!(out, "{}", AUDIT_CSV_COLUMNS.join(","))?;
+            for record in records {
+                let values = record.csv_values();
+                let row: Vec<_> = values.iter().map(|value| csv_escape(value)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockHeader};
+    use libsigner::v0::messages::{BlockRejection, RejectReason};
+    use libsigner::{BlockProposal, BlockProposalData};
+
+    use super::*;
+
+    fn rejected_record() -> BlockAuditRecord {
+        let block = NakamotoBlock {
+            header: NakamotoBlockHeader::empty(),
+            txs: vec![],
+        };
+        let block_info = BlockInfo::from(BlockProposal {
+            block: block.clone(),
+            burn_height: 7,
+            reward_cycle: 42,
+            block_proposal_data: BlockProposalData::empty(),
+        });
+        let rejection = BlockRejection::new(
+            block.header.signer_signature_hash(),
+            RejectReason::ConnectivityIssues("timed out, \"twice\"".into()),
+            &clarity::types::chainstate::StacksPrivateKey::random(),
+            false,
+            0,
+        )
+        .unwrap();
+        BlockAuditRecord::new(
+            &StacksAddress::burn_address(false),
+            &block_info,
+            AuditStage::ValidationTimeout,
+            &BlockResponse::Rejected(rejection),
+        )
+    }
+
+    #[test]
+    fn csv_columns_match_json_fields() {
+        let record = rejected_record();
+        let json = serde_json::to_value(&record).unwrap();
+        let fields: Vec<_> = json.as_object().unwrap().keys().cloned().collect();
+        let mut columns: Vec<_> = AUDIT_CSV_COLUMNS.iter().map(|c| c.to_string()).collect();
+        columns.sort();
+        assert_eq!(fields, columns);
+        assert_eq!(json["stage"], "validation_timeout");
+        assert_eq!(json["decision"], "reject");
+        assert_eq!(json["reject_code"], "ConnectivityIssues");
+    }
+
+    #[test]
+    fn write_json_lines_and_csv() {
+        let record = rejected_record();
+        let records = vec![record.clone(), record.clone()];
+
+        let mut json_lines = vec![];
+        write_audit_records(&records, AuditFormat::JsonLines, &mut json_lines).unwrap();
+        let json_lines = String::from_utf8(json_lines).unwrap();
+        assert_eq!(json_lines.lines().count(), 2);
+        for line in json_lines.lines() {
+            let parsed: BlockAuditRecord = serde_json::from_str(line).unwrap();
+            assert_eq!(parsed, record);
+        }
+
+        let mut csv = vec![];
+        write_audit_records(&records, AuditFormat::Csv, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], AUDIT_CSV_COLUMNS.join(","));
+        assert!(lines[1].starts_with(&format!("1,{},42,7,", record.signer_address)));
+        assert!(lines[1].contains(",reject,ConnectivityIssues,\""));
+        assert!(lines[1].contains("\"\"twice\"\""));
+    }
+}
@@ -125,6 +125,8 @@ pub struct SortitionData {
     pub burn_header_timestamp: u64,
     /// the burn header hash of the burn block that performed this sortition
     pub burn_block_hash: BurnchainHeaderHash,
+    /// the height of the burn block that performed this sortition
+    pub burn_block_height: u64,
 }
 
 impl TryFrom<SortitionInfo> for SortitionData {
@@ -144,6 +146,7 @@ impl TryFrom<SortitionInfo> for SortitionData {
                 .ok_or_else(|| ClientError::UnexpectedSortitionInfo)?,
             burn_header_timestamp: value.burn_header_timestamp,
             burn_block_hash: value.burn_block_hash,
+            burn_block_height: value.burn_block_height,
         })
     }
 }
@@ -68,6 +68,7 @@ fn setup_test_environment(
         consensus_hash: ConsensusHash([1; 20]),
         burn_header_timestamp: 2,
         burn_block_hash: BurnchainHeaderHash([1; 32]),
+        burn_block_height: 2,
     };
     let cur_sortition = SortitionState {
         data,
@@ -82,6 +83,7 @@ fn setup_test_environment(
         consensus_hash: ConsensusHash([0; 20]),
         burn_header_timestamp: 1,
         burn_block_hash: BurnchainHeaderHash([0; 32]),
+        burn_block_height: 1,
     };
     let last_sortition = Some(SortitionState {
         data,
@@ -449,6 +451,7 @@ fn check_sortition_timeout() {
         consensus_hash: ConsensusHash([1; 20]),
         burn_header_timestamp: 2,
         burn_block_hash: BurnchainHeaderHash([1; 32]),
+        burn_block_height: 2,
     };
     let sortition = SortitionState {
         data,
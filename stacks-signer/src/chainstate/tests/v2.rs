@@ -73,6 +73,7 @@ fn setup_test_environment(
         consensus_hash: ConsensusHash([1; 20]),
         burn_header_timestamp: 2,
         burn_block_hash: BurnchainHeaderHash([1; 32]),
+        burn_block_height: 2,
     };
     let cur_sortition = SortitionState { data };
 
@@ -84,6 +85,7 @@ fn setup_test_environment(
         consensus_hash: ConsensusHash([0; 20]),
         burn_header_timestamp: 1,
         burn_block_hash: BurnchainHeaderHash([0; 32]),
+        burn_block_height: 1,
     };
     let last_sortition = SortitionState { data };
     let config = ProposalEvalConfig {
@@ -415,6 +417,7 @@ fn check_sortition_timeout() {
         consensus_hash: ConsensusHash([1; 20]),
         burn_header_timestamp: 2,
         burn_block_hash: BurnchainHeaderHash([1; 32]),
+        burn_block_height: 2,
     };
     let sortition = SortitionState { data };
     // Ensure we have a burn height to compare against
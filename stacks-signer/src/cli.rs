@@ -15,6 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 use std::io::{self, Read};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use blockstack_lib::chainstate::stacks::address::PoxAddress;
 use blockstack_lib::util_lib::signed_structured_data::pox4::Pox4SignatureTopic;
@@ -24,12 +25,12 @@ use blockstack_lib::util_lib::signed_structured_data::{
 use clap::{ArgAction, Parser, ValueEnum};
 use clarity::consts::CHAIN_ID_MAINNET;
 use clarity::types::chainstate::StacksPublicKey;
-use clarity::types::{PrivateKey, PublicKey};
+use clarity::types::PublicKey;
 use clarity::util::hash::Sha256Sum;
 use clarity::util::secp256k1::MessageSignature;
 use clarity::vm::types::{QualifiedContractIdentifier, TupleData};
 use clarity::vm::Value;
-use libsigner::VERSION_STRING;
+use libsigner::{KeyBackend, VERSION_STRING};
 use serde::{Deserialize, Serialize};
 use stacks_common::address::{
     b58, AddressHashMode, C32_ADDRESS_VERSION_MAINNET_MULTISIG,
@@ -39,6 +40,11 @@ use stacks_common::address::{
 use stacks_common::define_u8_enum;
 use stacks_common::types::chainstate::StacksPrivateKey;
 
+use crate::audit::AuditFormat;
+use crate::config::DEFAULT_TENURE_IDLE_TIMEOUT_BUFFER_SECS;
+use crate::signerdb::DbRetention;
+use crate::tenure_extend_sim::ExtendTimeouts;
+
 extern crate alloc;
 
 /// The CLI arguments for the stacks signer
@@ -73,6 +79,13 @@ pub enum Command {
     VerifyVote(VerifyVoteArgs),
     /// Verify signer signatures by checking stackerdb slots contain the correct data
     MonitorSigners(MonitorSignersArgs),
+    /// Remove old history from a signer database and shrink the file. Stop the signer which
+    /// uses the database first.
+    PruneDb(PruneDbArgs),
+    /// Export the audit log of a signer's block decisions as JSON Lines or CSV
+    ExportAudit(ExportAuditArgs),
+    /// Replay the tenure extends recorded in signer databases under alternative timeout settings
+    SimulateTenureExtend(SimulateTenureExtendArgs),
 }
 
 /// Basic arguments for all cyrptographic and stacker-db functionality
@@ -191,9 +204,9 @@ impl VoteInfo {
     }
 
     /// Sign the vote data and return the signature
-    pub fn sign(&self, private_key: &StacksPrivateKey) -> Result<MessageSignature, &'static str> {
+    pub fn sign<K: KeyBackend + ?Sized>(&self, key: &K) -> Result<MessageSignature, String> {
         let digest = self.digest();
-        private_key.sign(digest.as_bytes())
+        key.sign_digest(digest.as_bytes()).map_err(|e| e.to_string())
     }
 
     /// Verify the vote data against the provided public key and signature
@@ -246,6 +259,105 @@ pub struct MonitorSignersArgs {
     /// Max age in seconds before a signer message is considered stale.
     #[arg(long, short, default_value = "1200")]
     pub max_age: u64,
+    /// Instead of checking for block responses, show a live table of each signer's state
+    /// machine view and how it diverges from the signer set's majority view.
+    #[arg(long)]
+    pub state_divergence: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the PruneDb command
+pub struct PruneDbArgs {
+    /// Path to the signer database file
+    #[arg(long, value_name = "FILE")]
+    pub db_path: PathBuf,
+    /// Keep this many reward cycles before the latest one
+    #[arg(
+        long,
+        required_unless_present = "burn_blocks",
+        conflicts_with = "burn_blocks"
+    )]
+    pub reward_cycles: Option<u64>,
+    /// Keep this many burn blocks below the latest one
+    #[arg(long)]
+    pub burn_blocks: Option<u64>,
+    /// Only remove rows, without rebuilding the database file to shrink it
+    #[arg(long)]
+    pub no_compact: bool,
+}
+
+impl PruneDbArgs {
+    /// The retention window given on the command line
+    pub fn retention(&self) -> DbRetention {
+        match (self.reward_cycles, self.burn_blocks) {
+            (Some(reward_cycles), _) => DbRetention::RewardCycles(reward_cycles),
+            (None, burn_blocks) => DbRetention::BurnBlocks(burn_blocks.unwrap_or_default()),
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the ExportAudit command
+pub struct ExportAuditArgs {
+    /// Path to the signer database file
+    #[arg(long, value_name = "FILE")]
+    pub db_path: PathBuf,
+    /// Only export decisions on blocks proposed at or above this burn block height
+    #[arg(long, default_value_t = 0)]
+    pub start_burn_height: u64,
+    /// Only export decisions on blocks proposed at or below this burn block height
+    #[arg(long)]
+    pub end_burn_height: Option<u64>,
+    /// The output format
+    #[arg(long, value_enum, default_value_t = AuditFormat::JsonLines)]
+    pub format: AuditFormat,
+}
+
+#[derive(Parser, Debug, Clone)]
+/// Arguments for the SimulateTenureExtend command
+pub struct SimulateTenureExtendArgs {
+    /// Path to a signer database file. Repeat to replay the decisions of several signers.
+    #[arg(long = "db-path", value_name = "FILE", required = true)]
+    pub db_paths: Vec<PathBuf>,
+    /// A signer `tenure_idle_timeout` (in secs) to replay under. Repeat to compare several.
+    #[arg(long = "tenure-idle-timeout", value_name = "SECS", required = true)]
+    pub tenure_idle_timeouts: Vec<u64>,
+    /// A signer `tenure_idle_timeout_buffer` (in secs) to replay under. Repeat to compare several.
+    #[arg(
+        long = "tenure-idle-timeout-buffer",
+        value_name = "SECS",
+        default_values_t = [DEFAULT_TENURE_IDLE_TIMEOUT_BUFFER_SECS]
+    )]
+    pub tenure_idle_timeout_buffers: Vec<u64>,
+    /// The miner's `tenure_timeout` (in secs)
+    #[arg(long, value_name = "SECS")]
+    pub miner_tenure_timeout: Option<u64>,
+    /// The miner's stacks-node log, to compare its tenure extend attempts
+    #[arg(long, value_name = "FILE")]
+    pub miner_log: Option<PathBuf>,
+    /// Only replay blocks proposed at or above this burn block height
+    #[arg(long, default_value_t = 0)]
+    pub start_burn_height: u64,
+    /// Only replay blocks proposed at or below this burn block height
+    #[arg(long)]
+    pub end_burn_height: Option<u64>,
+}
+
+impl SimulateTenureExtendArgs {
+    /// Every combination of the timeouts given on the command line
+    pub fn timeouts(&self) -> Vec<ExtendTimeouts> {
+        let mut timeouts = vec![];
+        for tenure_idle_timeout in &self.tenure_idle_timeouts {
+            for tenure_idle_timeout_buffer in &self.tenure_idle_timeout_buffers {
+                timeouts.push(ExtendTimeouts {
+                    tenure_idle_timeout: Duration::from_secs(*tenure_idle_timeout),
+                    tenure_idle_timeout_buffer: Duration::from_secs(*tenure_idle_timeout_buffer),
+                    miner_tenure_timeout: self.miner_tenure_timeout.map(Duration::from_secs),
+                });
+            }
+        }
+        timeouts
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
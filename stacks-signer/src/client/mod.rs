@@ -134,6 +134,7 @@ pub(crate) mod tests {
     use std::collections::{BTreeMap, HashMap};
     use std::io::{Read, Write};
     use std::net::{SocketAddr, TcpListener};
+    use std::sync::Arc;
 
     use blockstack_lib::chainstate::stacks::boot::POX_4_NAME;
     use blockstack_lib::chainstate::stacks::db::StacksBlockHeaderTypes;
@@ -386,12 +387,11 @@ pub(crate) mod tests {
         let mut signer_addresses = Vec::new();
 
         for signer_id in 0..num_signers {
-            let private_key = if signer_id == 0 {
-                config.stacks_private_key
+            let public_key = if signer_id == 0 {
+                config.signing_key.public_key()
             } else {
-                StacksPrivateKey::random()
+                StacksPublicKey::from_private(&StacksPrivateKey::random())
             };
-            let public_key = StacksPublicKey::from_private(&private_key);
 
             signer_id_to_pk.insert(signer_id, public_key);
             signer_pk_to_id.insert(public_key, signer_id);
@@ -420,8 +420,10 @@ pub(crate) mod tests {
                 signer_addresses,
             },
             signer_slot_ids,
-            stacks_private_key: config.stacks_private_key,
+            signing_key: config.signing_key.clone(),
             node_host: config.node_host.to_string(),
+            node_stackerdb_tls_config: config.node_stackerdb_tls_config.clone(),
+            auth_password: config.auth_password.clone(),
             mainnet: config.network.is_mainnet(),
             db_path: config.db_path.clone(),
             first_proposal_burn_block_timing: config.first_proposal_burn_block_timing,
@@ -435,6 +437,9 @@ pub(crate) mod tests {
             proposal_wait_for_parent_time: config.proposal_wait_for_parent_time,
             validate_with_replay_tx: config.validate_with_replay_tx,
             capitulate_miner_view_timeout: config.capitulate_miner_view_timeout,
+            block_policy: config.block_policy.clone(),
+            admin_state: config.admin_state.clone(),
+            block_validations: Arc::default(),
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version: SUPPORTED_SIGNER_PROTOCOL_VERSION,
         }
@@ -14,13 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
+use std::sync::Arc;
+
 use blockstack_lib::net::api::poststackerdbchunk::StackerDBErrorCodes;
 use clarity::codec::read_next;
-use clarity::types::chainstate::StacksPublicKey;
 use hashbrown::HashMap;
-use libsigner::{MessageSlotID, SignerMessage, SignerSession, StackerDBSession};
-use libstackerdb::{StackerDBChunkAckData, StackerDBChunkData};
-use stacks_common::types::chainstate::StacksPrivateKey;
+use libsigner::{KeyBackend, MessageSlotID, SignerMessage, SignerSession, StackerDBSession};
+use libstackerdb::{Error as StackerDBError, StackerDBChunkAckData, StackerDBChunkData};
 use stacks_common::util::hash::to_hex;
 use stacks_common::{debug, info, warn};
 
@@ -50,8 +50,8 @@ pub struct StackerDB<M: MessageSlotID + std::cmp::Eq> {
     /// The stacker-db sessions for each signer set and message type.
     /// Maps message ID to the DB session.
     signers_message_stackerdb_sessions: HashMap<M, StackerDBSession>,
-    /// The private key used in all stacks node communications
-    stacks_private_key: StacksPrivateKey,
+    /// The key used to sign all stacks node communications
+    signing_key: Arc<dyn KeyBackend>,
     /// The running mode of the stackerdb (whether the signer is running in dry-run or
     ///  normal operation)
     mode: StackerDBMode,
@@ -75,7 +75,9 @@ impl<M: MessageSlotID + 'static> From<&SignerConfig> for StackerDB<M> {
 
         Self::new(
             &config.node_host,
-            config.stacks_private_key,
+            config.node_stackerdb_tls_config.clone(),
+            Some(config.auth_password.clone()),
+            config.signing_key.clone(),
             config.mainnet,
             config.reward_cycle,
             signer_db,
@@ -87,9 +89,9 @@ impl<M: MessageSlotID + 'static> From<&SignerConfig> for StackerDB<M> {
 impl<M: MessageSlotID + 'static> StackerDB<M> {
     #[cfg(any(test, feature = "testing"))]
     /// Create a StackerDB client in normal operation (i.e., not a dry-run signer)
-    pub fn new_normal(
+    pub fn new_normal<K: KeyBackend + 'static>(
         host: &str,
-        stacks_private_key: StacksPrivateKey,
+        signing_key: K,
         is_mainnet: bool,
         reward_cycle: u64,
         signer_slot_id: SignerSlotID,
@@ -97,7 +99,9 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
     ) -> Self {
         Self::new(
             host,
-            stacks_private_key,
+            None,
+            None,
+            Arc::new(signing_key),
             is_mainnet,
             reward_cycle,
             signer_db,
@@ -108,7 +112,9 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
     /// Create a new StackerDB client
     fn new(
         host: &str,
-        stacks_private_key: StacksPrivateKey,
+        tls_config: Option<Arc<rustls::ClientConfig>>,
+        api_key: Option<String>,
+        signing_key: Arc<dyn KeyBackend>,
         is_mainnet: bool,
         reward_cycle: u64,
         signer_db: SignerDb,
@@ -116,14 +122,20 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
     ) -> Self {
         let mut signers_message_stackerdb_sessions = HashMap::new();
         for msg_id in M::all() {
-            let session =
+            let mut session =
                 StackerDBSession::new(host, msg_id.stacker_db_contract(is_mainnet, reward_cycle));
+            if let Some(tls_config) = tls_config.as_ref() {
+                session = session.with_tls(tls_config.clone());
+            }
+            if let Some(api_key) = api_key.as_ref() {
+                session = session.with_api_key(api_key.clone());
+            }
             signers_message_stackerdb_sessions.insert(*msg_id, session);
         }
 
         Self {
             signers_message_stackerdb_sessions,
-            stacks_private_key,
+            signing_key,
             mode: signer_mode,
             reward_cycle,
             signer_db,
@@ -167,7 +179,7 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
                 code: None,
             });
         };
-        let signer_pk = StacksPublicKey::from_private(&self.stacks_private_key);
+        let signer_pk = self.signing_key.public_key();
         loop {
             let slot_version = self
                 .signer_db
@@ -180,7 +192,10 @@ impl<M: MessageSlotID + 'static> StackerDB<M> {
             };
 
             let mut chunk = StackerDBChunkData::new(slot_id.0, slot_version, message_bytes.clone());
-            chunk.sign(&self.stacks_private_key)?;
+            chunk.sig = self
+                .signing_key
+                .sign_digest(chunk.get_slot_metadata().auth_digest().as_bytes())
+                .map_err(|e| StackerDBError::SigningError(e.to_string()))?;
 
             debug!(
                 "Sending a chunk to stackerdb slot ID {slot_id} with version {slot_version} and message ID {msg_id:?} to contract {:?}!\n{chunk:?}",
@@ -282,6 +297,7 @@ mod tests {
         SignerMessageMetadata,
     };
     use rand::{thread_rng, RngCore};
+    use stacks_common::types::chainstate::StacksPrivateKey;
 
     use super::*;
     use crate::client::tests::{generate_signer_config, mock_server_from_config, write_response};
@@ -31,6 +31,7 @@ use blockstack_lib::net::api::postblock::StacksBlockAcceptedData;
 use blockstack_lib::net::api::postblock_proposal::NakamotoBlockProposal;
 use blockstack_lib::net::api::postblock_v3;
 use blockstack_lib::util_lib::boot::boot_code_id;
+use clarity::vm::costs::ExecutionCost;
 use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier};
 use clarity::vm::{ClarityName, ContractName, Value as ClarityValue};
 use libsigner::v0::messages::PeerInfo;
@@ -47,7 +48,7 @@ use stacks_common::{debug, warn};
 
 use super::SignerSlotID;
 use crate::client::{retry_with_exponential_backoff, ClientError};
-use crate::config::GlobalConfig;
+use crate::config::{GlobalConfig, SignerIdentity};
 use crate::runloop::RewardCycleInfo;
 
 /// The Stacks signer client used to communicate with the stacks node
@@ -87,10 +88,10 @@ impl From<&GlobalConfig> for StacksClient {
     fn from(config: &GlobalConfig) -> Self {
         Self {
             stacks_address: config.stacks_address,
-            http_origin: format!("http://{}", config.node_host),
+            http_origin: config.node_origin(),
             tx_version: config.network.to_transaction_version(),
             chain_id: config.to_chain_id(),
-            stacks_node_client: reqwest::blocking::Client::new(),
+            stacks_node_client: config.node_http_client(),
             mainnet: config.network.is_mainnet(),
             auth_password: config.auth_password.clone(),
         }
@@ -152,6 +153,16 @@ impl StacksClient {
         Ok(stacks_client)
     }
 
+    /// Create a client which acts as a different signer identity, sharing this client's
+    /// connection pool to the stacks node
+    pub fn for_identity(&self, identity: &SignerIdentity) -> Self {
+        Self {
+            stacks_address: identity.stacks_address,
+            auth_password: identity.auth_password.clone(),
+            ..self.clone()
+        }
+    }
+
     /// Get our signer address
     pub const fn get_signer_address(&self) -> &StacksAddress {
         &self.stacks_address
@@ -301,6 +312,25 @@ impl StacksClient {
         }
     }
 
+    /// Get the block limit of the epoch that `burn_block_height` falls in, according to the
+    /// stacks node's network and epoch configuration
+    pub fn get_block_limit(&self, burn_block_height: u64) -> Result<ExecutionCost, ClientError> {
+        debug!("StacksClient: Getting block limit"; "burn_block_height" => burn_block_height);
+        let pox_info = self.get_pox_data()?;
+        pox_info
+            .epochs
+            .into_iter()
+            .find(|epoch| {
+                epoch.start_height <= burn_block_height && burn_block_height < epoch.end_height
+            })
+            .map(|epoch| epoch.block_limit)
+            .ok_or_else(|| {
+                ClientError::InvalidResponse(format!(
+                    "/v2/pox reports no epoch for burn block height {burn_block_height}"
+                ))
+            })
+    }
+
     /// Submit the block proposal to the stacks node. The block will be validated and returned via the HTTP endpoint for Block events.
     pub fn submit_block_for_validation(
         &self,
@@ -1079,6 +1109,25 @@ mod tests {
         assert_eq!(epoch, StacksEpochId::Epoch30);
     }
 
+    #[test]
+    fn get_block_limit_should_succeed() {
+        let mock = MockServerClient::new();
+        let (pox_response, pox_data) =
+            build_get_pox_data_response(None, None, Some(100), Some(200));
+        let h = spawn(move || mock.client.get_block_limit(250));
+        write_response(mock.server, pox_response.as_bytes());
+        let block_limit = h.join().unwrap().expect("Failed to get block limit");
+        assert_eq!(block_limit, pox_data.epochs[1].block_limit);
+
+        let mock = MockServerClient::from_config(mock.config);
+        let h = spawn(move || mock.client.get_block_limit(50));
+        write_response(mock.server, pox_response.as_bytes());
+        assert!(matches!(
+            h.join().unwrap(),
+            Err(ClientError::InvalidResponse(_))
+        ));
+    }
+
     #[test]
     fn get_node_epoch_should_fail() {
         let mock = MockServerClient::new();
@@ -18,12 +18,14 @@ use std::fmt::{Debug, Display};
 use std::fs;
 use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use blockstack_lib::chainstate::stacks::TransactionVersion;
 use blockstack_lib::net::connection::DEFAULT_BLOCK_PROPOSAL_MAX_AGE_SECS;
+use blockstack_lib::net::http::tls::make_client_config;
 use clarity::util::hash::to_hex;
-use libsigner::SignerEntries;
+use libsigner::{KeyBackend, SignerEntries};
 use serde::Deserialize;
 use stacks_common::address::{
     C32_ADDRESS_VERSION_MAINNET_SINGLESIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
@@ -32,7 +34,12 @@ use stacks_common::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
 use stacks_common::types::chainstate::{StacksAddress, StacksPrivateKey, StacksPublicKey};
 use stacks_common::util::hash::Hash160;
 
+use crate::admin::SignerAdminState;
 use crate::client::SignerSlotID;
+use crate::keyholder::{KeyholderEndpoint, RemoteKeyBackend};
+use crate::policy::{BlockPolicy, BlockPolicyRule};
+use crate::runloop::SharedBlockValidations;
+use crate::signerdb::DbRetention;
 #[cfg(any(test, feature = "testing"))]
 use crate::v0::signer_state::SUPPORTED_SIGNER_PROTOCOL_VERSION;
 
@@ -46,7 +53,7 @@ const TENURE_IDLE_TIMEOUT_SECS: u64 = 120;
 const DEFAULT_REORG_ATTEMPTS_ACTIVITY_TIMEOUT_MS: u64 = 200_000;
 /// Default number of seconds to add to the tenure extend time, after computing the idle timeout,
 /// to allow for clock skew between the signer and the miner
-const DEFAULT_TENURE_IDLE_TIMEOUT_BUFFER_SECS: u64 = 2;
+pub const DEFAULT_TENURE_IDLE_TIMEOUT_BUFFER_SECS: u64 = 2;
 /// Default time (in ms) to wait before submitting a proposal if we
 ///  cannot determine that our stacks-node has processed the parent
 ///  block
@@ -54,6 +61,15 @@ const DEFAULT_PROPOSAL_WAIT_TIME_FOR_PARENT_SECS: u64 = 15;
 /// Default time (in secs) to wait between updating our local state
 /// machine view point and capitulating to other signers tenure view
 const DEFAULT_CAPITULATE_MINER_VIEW_SECS: u64 = 20;
+/// Default time (in ms) to wait for a keyholder to answer a single request
+const DEFAULT_KEYHOLDER_TIMEOUT_MS: u64 = 5_000;
+/// Default time (in secs) to keep retrying a signing request to an unreachable keyholder
+const DEFAULT_KEYHOLDER_RETRY_TIMEOUT_SECS: u64 = 10;
+/// Signing requests block the signer's event loop, so a keyholder request and its retries may
+/// take at most this fraction of the block proposal timeout
+const KEYHOLDER_RETRY_BLOCK_PROPOSAL_TIMEOUT_DIVISOR: u32 = 4;
+/// Default time (in secs) to wait between prunes of the signer database
+const DEFAULT_DB_PRUNE_INTERVAL_SECS: u64 = 3600;
 
 #[derive(thiserror::Error, Debug)]
 /// An error occurred parsing the provided configuration
@@ -154,10 +170,14 @@ pub struct SignerConfig {
     pub signer_entries: SignerEntries,
     /// The signer slot ids of all signers registered for this reward cycle
     pub signer_slot_ids: Vec<SignerSlotID>,
-    /// The private key for this signer
-    pub stacks_private_key: StacksPrivateKey,
+    /// The key that this signer signs its messages with
+    pub signing_key: Arc<dyn KeyBackend>,
     /// The node host for this signer
     pub node_host: String,
+    /// The TLS configuration for StackerDB sessions with the node, if it is reached over HTTPS
+    pub node_stackerdb_tls_config: Option<Arc<rustls::ClientConfig>>,
+    /// The authorization password sent to the node with StackerDB chunk writes
+    pub auth_password: String,
     /// Whether this signer is running on mainnet or not
     pub mainnet: bool,
     /// The path to the signer's database file
@@ -191,20 +211,50 @@ pub struct SignerConfig {
     pub validate_with_replay_tx: bool,
     /// Time to wait between updating our local state machine view point and capitulating to other signers miner view
     pub capitulate_miner_view_timeout: Duration,
+    /// Operator-defined rules that block proposals must satisfy
+    pub block_policy: BlockPolicy,
+    /// Signer state shared with the admin API
+    pub admin_state: Arc<SignerAdminState>,
+    /// The block validations submitted by any of the runloop's signing identities
+    pub block_validations: Arc<SharedBlockValidations>,
     #[cfg(any(test, feature = "testing"))]
     /// Only used for testing purposes to enable overriding the signer version
     pub supported_signer_protocol_version: u64,
 }
 
+/// A signing identity run by the signer process. Each identity signs with its own key and
+/// keeps its own database, while sharing the process's connection to the stacks node.
+#[derive(Debug, Clone)]
+pub struct SignerIdentity {
+    /// The key that the identity signs its messages with
+    pub signing_key: Arc<dyn KeyBackend>,
+    /// The identity's Stacks address
+    pub stacks_address: StacksAddress,
+    /// The path to the identity's database file
+    pub db_path: PathBuf,
+    /// The authorization password for the block proposal endpoint
+    pub auth_password: String,
+}
+
 /// The parsed configuration for the signer
 #[derive(Clone)]
 pub struct GlobalConfig {
     /// endpoint to the stacks node
     pub node_host: String,
+    /// Whether to talk to the stacks node's RPC endpoints over HTTPS
+    pub node_tls: bool,
+    /// The CA certificates that the stacks node's certificate must be issued by. If empty, the
+    /// built-in web PKI roots are trusted instead.
+    pub node_tls_ca_certs: Vec<reqwest::Certificate>,
+    /// The TLS configuration for StackerDB sessions with the stacks node, if `node_tls` is set
+    pub node_stackerdb_tls_config: Option<Arc<rustls::ClientConfig>>,
     /// endpoint to the event receiver
     pub endpoint: SocketAddr,
-    /// The signer's Stacks private key
-    pub stacks_private_key: StacksPrivateKey,
+    /// The signer's Stacks private key, if it is configured locally rather than held by a
+    /// keyholder process. All signing goes through `signing_key`.
+    pub stacks_private_key: Option<StacksPrivateKey>,
+    /// The key that the signer signs its messages with
+    pub signing_key: Arc<dyn KeyBackend>,
     /// The signer's Stacks address
     pub stacks_address: StacksAddress,
     /// The network to use. One of "mainnet" or "testnet".
@@ -249,26 +299,86 @@ pub struct GlobalConfig {
     pub validate_with_replay_tx: bool,
     /// Time to wait between updating our local state machine view point and capitulating to other signers miner view
     pub capitulate_miner_view_timeout: Duration,
+    /// Operator-defined rules that block proposals must satisfy
+    pub block_policy: BlockPolicy,
+    /// The token that admin API requests must carry. The admin API is disabled if unset.
+    pub admin_auth_token: Option<String>,
+    /// Signer state shared with the admin API
+    pub admin_state: Arc<SignerAdminState>,
+    /// Signing identities run alongside the one configured at the top level
+    pub additional_identities: Vec<SignerIdentity>,
+    /// How much history to keep in the signer database. The database is never pruned if unset.
+    pub db_retention: Option<DbRetention>,
+    /// How often to prune the signer database
+    pub db_prune_interval: Duration,
     #[cfg(any(test, feature = "testing"))]
     /// Only used for testing to enable specific signer protocol versions
     pub supported_signer_protocol_version: u64,
 }
 
+/// Internal struct for loading up an additional signing identity
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct RawSignerIdentity {
+    /// The hex representation of the identity's Stacks private key.
+    /// Exactly one of this and `keyholder_endpoint` must be set.
+    pub stacks_private_key: Option<String>,
+    /// The endpoint of a keyholder process which holds the identity's private key.
+    /// Exactly one of this and `stacks_private_key` must be set.
+    pub keyholder_endpoint: Option<String>,
+    /// The hex-encoded public key of the identity's keyholder. Required with
+    /// `keyholder_endpoint`.
+    pub keyholder_public_key: Option<String>,
+    /// The token that authenticates the signer to the identity's keyholder. Required with
+    /// `keyholder_endpoint`.
+    pub keyholder_auth_token: Option<String>,
+    /// The path to the identity's database file
+    pub db_path: String,
+    /// The authorization password for the block proposal endpoint. Defaults to the top-level
+    /// `auth_password`.
+    pub auth_password: Option<String>,
+}
+
 /// Internal struct for loading up the config file
 #[derive(Deserialize, Debug)]
 struct RawConfigFile {
     /// endpoint to stacks node
     pub node_host: String,
+    /// Whether to talk to the stacks node's RPC endpoints (including StackerDB) over HTTPS.
+    /// Implied by `node_tls_ca_cert_path`.
+    pub node_tls: Option<bool>,
+    /// Path to a PEM file with the CA certificates that the stacks node's certificate must be
+    /// issued by. If unset, the built-in web PKI roots are trusted.
+    pub node_tls_ca_cert_path: Option<String>,
     /// endpoint to event receiver
     pub endpoint: String,
     /// The hex representation of the signer's Stacks private key used for communicating
     /// with the Stacks Node, including writing to the Stacker DB instance.
-    pub stacks_private_key: String,
+    /// Exactly one of this and `keyholder_endpoint` must be set.
+    pub stacks_private_key: Option<String>,
+    /// The endpoint of a keyholder process which holds the signer's private key and signs on
+    /// its behalf, either `unix:<path>` or a loopback `<ip>:<port>`.
+    /// Exactly one of this and `stacks_private_key` must be set.
+    pub keyholder_endpoint: Option<String>,
+    /// The hex-encoded public key that the keyholder signs with. Required with
+    /// `keyholder_endpoint`, so that the config can be loaded without reaching the keyholder.
+    pub keyholder_public_key: Option<String>,
+    /// The token that authenticates the signer to the keyholder, which must be started with the
+    /// same token. Required with `keyholder_endpoint`.
+    pub keyholder_auth_token: Option<String>,
+    /// How long (in millisecs) to wait for the keyholder to answer a single request
+    pub keyholder_timeout_ms: Option<u64>,
+    /// How long (in secs) to keep retrying a signing request to an unreachable keyholder.
+    /// Signing blocks the signer's event loop, so this is capped at a quarter of the block
+    /// proposal timeout, less `keyholder_timeout_ms`.
+    pub keyholder_retry_timeout_secs: Option<u64>,
     /// The network to use. One of "mainnet" or "testnet".
     pub network: Network,
     /// The time to wait (in millisecs) for a response from the stacker-db instance
     pub event_timeout_ms: Option<u64>,
-    /// The authorization password for the block proposal endpoint
+    /// The authorization password for the block proposal endpoint. It is also sent with
+    /// StackerDB chunk writes, so a node that sets `stackerdb_write_requires_api_key` must
+    /// give it the `stackerdb-write` scope as well as `block-proposal`.
     pub auth_password: String,
     /// The path to the signer's database file or :memory: for an in-memory database
     pub db_path: String,
@@ -305,6 +415,23 @@ struct RawConfigFile {
     pub validate_with_replay_tx: Option<bool>,
     /// Time to wait (in secs) between updating our local state machine view point and capitulating to other signers miner view
     pub capitulate_miner_view_timeout_secs: Option<u64>,
+    /// Operator-defined rules that block proposals must satisfy, given as `[[block_policy]]` tables
+    pub block_policy: Option<Vec<BlockPolicyRule>>,
+    /// The token that admin API requests must carry in their `Authorization` header. The admin
+    /// API is served by the metrics server, and is disabled if this is unset. The metrics server
+    /// speaks plain HTTP, so `metrics_endpoint` must not be exposed beyond a trusted network
+    /// when this is set.
+    pub admin_auth_token: Option<String>,
+    /// Signing identities to run alongside the top-level one, given as `[[identities]]` tables
+    pub identities: Option<Vec<RawSignerIdentity>>,
+    /// Prune signer database history older than this many reward cycles before the current one.
+    /// At most one of this and `db_retention_burn_blocks` may be set.
+    pub db_retention_reward_cycles: Option<u64>,
+    /// Prune signer database history older than this many burn blocks below the current one.
+    /// At most one of this and `db_retention_reward_cycles` may be set.
+    pub db_retention_burn_blocks: Option<u64>,
+    /// How often (in secs) to prune the signer database
+    pub db_prune_interval_secs: Option<u64>,
     #[cfg(any(test, feature = "testing"))]
     /// Only used for testing to enable specific signer protocol versions
     pub supported_signer_protocol_version: Option<u64>,
@@ -334,6 +461,66 @@ impl TryFrom<&PathBuf> for RawConfigFile {
     }
 }
 
+/// Load a signing key from either a hex-encoded private key or a keyholder endpoint, exactly
+/// one of which must be given. The keyholder is not contacted.
+fn load_signing_key(
+    stacks_private_key: Option<&str>,
+    keyholder_endpoint: Option<&str>,
+    keyholder_public_key: Option<&str>,
+    keyholder_auth_token: Option<&str>,
+    keyholder_timeout: Duration,
+    keyholder_retry_timeout: Duration,
+) -> Result<(Option<StacksPrivateKey>, Arc<dyn KeyBackend>), ConfigError> {
+    match (stacks_private_key, keyholder_endpoint) {
+        (Some(stacks_private_key), None) => {
+            let stacks_private_key = StacksPrivateKey::from_hex(stacks_private_key)
+                .map_err(|e| ConfigError::BadField("stacks_private_key".to_string(), e.into()))?;
+            Ok((Some(stacks_private_key), Arc::new(stacks_private_key)))
+        }
+        (None, Some(keyholder_endpoint)) => {
+            let endpoint: KeyholderEndpoint =
+                keyholder_endpoint
+                    .parse()
+                    .map_err(|e: crate::keyholder::KeyholderError| {
+                        ConfigError::BadField("keyholder_endpoint".to_string(), e.to_string())
+                    })?;
+            let public_key = keyholder_public_key.ok_or_else(|| {
+                ConfigError::InvalidConfig(
+                    "keyholder_public_key must be set with keyholder_endpoint".into(),
+                )
+            })?;
+            let public_key = StacksPublicKey::from_hex(public_key).map_err(|e| {
+                ConfigError::BadField("keyholder_public_key".to_string(), e.to_string())
+            })?;
+            let auth_token = keyholder_auth_token
+                .filter(|token| !token.is_empty())
+                .ok_or_else(|| {
+                    ConfigError::InvalidConfig(
+                        "keyholder_auth_token must be set with keyholder_endpoint".into(),
+                    )
+                })?;
+            let backend = RemoteKeyBackend::new(
+                endpoint,
+                public_key,
+                auth_token.to_string(),
+                keyholder_timeout,
+                keyholder_retry_timeout,
+            );
+            Ok((None, Arc::new(backend)))
+        }
+        _ => Err(ConfigError::InvalidConfig(
+            "exactly one of stacks_private_key and keyholder_endpoint must be set".into(),
+        )),
+    }
+}
+
+/// The Stacks address that signatures from `signing_key` belong to
+fn signer_address(signing_key: &dyn KeyBackend, network: &Network) -> StacksAddress {
+    let stacks_public_key = signing_key.public_key();
+    let signer_hash = Hash160::from_data(stacks_public_key.to_bytes_compressed().as_slice());
+    StacksAddress::p2pkh_from_hash(network.is_mainnet(), signer_hash)
+}
+
 impl TryFrom<RawConfigFile> for GlobalConfig {
     type Error = ConfigError;
 
@@ -353,12 +540,34 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
                 ConfigError::BadField("endpoint".to_string(), raw_data.endpoint.clone())
             })?;
 
-        let stacks_private_key = StacksPrivateKey::from_hex(&raw_data.stacks_private_key)
-            .map_err(|e| ConfigError::BadField("stacks_private_key".to_string(), e.into()))?;
-        let stacks_public_key = StacksPublicKey::from_private(&stacks_private_key);
-        let signer_hash = Hash160::from_data(stacks_public_key.to_bytes_compressed().as_slice());
-        let stacks_address =
-            StacksAddress::p2pkh_from_hash(raw_data.network.is_mainnet(), signer_hash);
+        let keyholder_timeout = Duration::from_millis(
+            raw_data
+                .keyholder_timeout_ms
+                .unwrap_or(DEFAULT_KEYHOLDER_TIMEOUT_MS),
+        );
+        let block_proposal_timeout = Duration::from_millis(
+            raw_data
+                .block_proposal_timeout_ms
+                .unwrap_or(BLOCK_PROPOSAL_TIMEOUT_MS),
+        );
+        let keyholder_retry_timeout = Duration::from_secs(
+            raw_data
+                .keyholder_retry_timeout_secs
+                .unwrap_or(DEFAULT_KEYHOLDER_RETRY_TIMEOUT_SECS),
+        )
+        .min(
+            (block_proposal_timeout / KEYHOLDER_RETRY_BLOCK_PROPOSAL_TIMEOUT_DIVISOR)
+                .saturating_sub(keyholder_timeout),
+        );
+        let (stacks_private_key, signing_key) = load_signing_key(
+            raw_data.stacks_private_key.as_deref(),
+            raw_data.keyholder_endpoint.as_deref(),
+            raw_data.keyholder_public_key.as_deref(),
+            raw_data.keyholder_auth_token.as_deref(),
+            keyholder_timeout,
+            keyholder_retry_timeout,
+        )?;
+        let stacks_address = signer_address(signing_key.as_ref(), &raw_data.network);
         let event_timeout =
             Duration::from_millis(raw_data.event_timeout_ms.unwrap_or(EVENT_TIMEOUT_MS));
         let first_proposal_burn_block_timing = Duration::from_secs(
@@ -366,7 +575,55 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
                 .first_proposal_burn_block_timing_secs
                 .unwrap_or(DEFAULT_FIRST_PROPOSAL_BURN_BLOCK_TIMING_SECS),
         );
-        let db_path = raw_data.db_path.into();
+        let db_path: PathBuf = raw_data.db_path.into();
+
+        let mut additional_identities = vec![];
+        for raw_identity in raw_data.identities.unwrap_or_default() {
+            let (_, signing_key) = load_signing_key(
+                raw_identity.stacks_private_key.as_deref(),
+                raw_identity.keyholder_endpoint.as_deref(),
+                raw_identity.keyholder_public_key.as_deref(),
+                raw_identity.keyholder_auth_token.as_deref(),
+                keyholder_timeout,
+                keyholder_retry_timeout,
+            )?;
+            let stacks_address = signer_address(signing_key.as_ref(), &raw_data.network);
+            additional_identities.push(SignerIdentity {
+                signing_key,
+                stacks_address,
+                db_path: raw_identity.db_path.into(),
+                auth_password: raw_identity
+                    .auth_password
+                    .unwrap_or_else(|| raw_data.auth_password.clone()),
+            });
+        }
+        for (i, identity) in additional_identities.iter().enumerate() {
+            let earlier_identities = additional_identities[..i]
+                .iter()
+                .map(|identity| (&identity.stacks_address, &identity.db_path));
+            for (other_address, other_db_path) in
+                std::iter::once((&stacks_address, &db_path)).chain(earlier_identities)
+            {
+                if &identity.stacks_address == other_address {
+                    return Err(ConfigError::BadField(
+                        "identities".to_string(),
+                        format!(
+                            "signer {} is configured more than once",
+                            identity.stacks_address
+                        ),
+                    ));
+                }
+                if &identity.db_path == other_db_path {
+                    return Err(ConfigError::BadField(
+                        "identities".to_string(),
+                        format!(
+                            "db_path {} is used by more than one identity",
+                            identity.db_path.display()
+                        ),
+                    ));
+                }
+            }
+        }
 
         let metrics_endpoint = match raw_data.metrics_endpoint {
             Some(endpoint) => Some(
@@ -381,12 +638,6 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
             None => None,
         };
 
-        let block_proposal_timeout = Duration::from_millis(
-            raw_data
-                .block_proposal_timeout_ms
-                .unwrap_or(BLOCK_PROPOSAL_TIMEOUT_MS),
-        );
-
         let tenure_last_block_proposal_timeout = Duration::from_secs(
             raw_data
                 .tenure_last_block_proposal_timeout_secs
@@ -439,6 +690,71 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
                 .unwrap_or(DEFAULT_CAPITULATE_MINER_VIEW_SECS),
         );
 
+        if raw_data
+            .admin_auth_token
+            .as_ref()
+            .is_some_and(|token| token.is_empty())
+        {
+            return Err(ConfigError::InvalidConfig(
+                "admin_auth_token must not be empty".into(),
+            ));
+        }
+
+        let block_policy = BlockPolicy::new(raw_data.block_policy.unwrap_or_default())
+            .map_err(|e| ConfigError::BadField("block_policy".to_string(), e))?;
+
+        let db_retention = match (
+            raw_data.db_retention_reward_cycles,
+            raw_data.db_retention_burn_blocks,
+        ) {
+            (Some(reward_cycles), None) => Some(DbRetention::RewardCycles(reward_cycles)),
+            (None, Some(burn_blocks)) => Some(DbRetention::BurnBlocks(burn_blocks)),
+            (None, None) => None,
+            (Some(_), Some(_)) => return Err(ConfigError::InvalidConfig(
+                "at most one of db_retention_reward_cycles and db_retention_burn_blocks may be set"
+                    .into(),
+            )),
+        };
+
+        let db_prune_interval = Duration::from_secs(
+            raw_data
+                .db_prune_interval_secs
+                .unwrap_or(DEFAULT_DB_PRUNE_INTERVAL_SECS),
+        );
+
+        let node_tls_ca_certs = match raw_data.node_tls_ca_cert_path.as_ref() {
+            Some(path) => {
+                let pem = fs::read(path).map_err(|e| {
+                    ConfigError::BadField(
+                        "node_tls_ca_cert_path".to_string(),
+                        format!("{path}: {e}"),
+                    )
+                })?;
+                let certs = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| {
+                    ConfigError::BadField(
+                        "node_tls_ca_cert_path".to_string(),
+                        format!("{path}: {e}"),
+                    )
+                })?;
+                if certs.is_empty() {
+                    return Err(ConfigError::BadField(
+                        "node_tls_ca_cert_path".to_string(),
+                        format!("{path}: no certificates found"),
+                    ));
+                }
+                certs
+            }
+            None => vec![],
+        };
+        let node_tls = raw_data.node_tls.unwrap_or(false) || !node_tls_ca_certs.is_empty();
+        let node_stackerdb_tls_config = if node_tls {
+            let tls_config = make_client_config(raw_data.node_tls_ca_cert_path.as_deref())
+                .map_err(|e| ConfigError::BadField("node_tls_ca_cert_path".to_string(), e))?;
+            Some(tls_config)
+        } else {
+            None
+        };
+
         #[cfg(any(test, feature = "testing"))]
         let supported_signer_protocol_version = raw_data
             .supported_signer_protocol_version
@@ -446,8 +762,12 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
 
         Ok(Self {
             node_host: raw_data.node_host,
+            node_tls,
+            node_tls_ca_certs,
+            node_stackerdb_tls_config,
             endpoint,
             stacks_private_key,
+            signing_key,
             stacks_address,
             network: raw_data.network,
             event_timeout,
@@ -467,6 +787,12 @@ impl TryFrom<RawConfigFile> for GlobalConfig {
             proposal_wait_for_parent_time,
             validate_with_replay_tx,
             capitulate_miner_view_timeout,
+            block_policy,
+            admin_auth_token: raw_data.admin_auth_token,
+            admin_state: Arc::new(SignerAdminState::default()),
+            additional_identities,
+            db_retention,
+            db_prune_interval,
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version,
         })
@@ -492,6 +818,41 @@ impl GlobalConfig {
         Self::try_from(&PathBuf::from(path))
     }
 
+    /// All of the signing identities run by this signer, starting with the one configured at
+    /// the top level
+    pub fn identities(&self) -> Vec<SignerIdentity> {
+        let primary = SignerIdentity {
+            signing_key: self.signing_key.clone(),
+            stacks_address: self.stacks_address,
+            db_path: self.db_path.clone(),
+            auth_password: self.auth_password.clone(),
+        };
+        std::iter::once(primary)
+            .chain(self.additional_identities.iter().cloned())
+            .collect()
+    }
+
+    /// The origin of the stacks node's RPC endpoints, e.g. `http://localhost:20443`
+    pub fn node_origin(&self) -> String {
+        let scheme = if self.node_tls { "https" } else { "http" };
+        format!("{scheme}://{}", self.node_host)
+    }
+
+    /// An HTTP client for the stacks node's RPC endpoints, which trusts only
+    /// `node_tls_ca_certs` if any are configured
+    pub fn node_http_client(&self) -> reqwest::blocking::Client {
+        let mut builder = reqwest::blocking::Client::builder();
+        if !self.node_tls_ca_certs.is_empty() {
+            builder = builder.tls_built_in_root_certs(false);
+            for cert in self.node_tls_ca_certs.iter() {
+                builder = builder.add_root_certificate(cert.clone());
+            }
+        }
+        builder
+            .build()
+            .unwrap_or_else(|e| panic!("FATAL: failed to build stacks node HTTP client: {e}"))
+    }
+
     /// Return a string with non-sensitive configuration
     /// information for logging purposes
     pub fn config_to_log_string(&self) -> String {
@@ -500,9 +861,10 @@ impl GlobalConfig {
             None => "None".to_string(),
         };
         let chain_id = format!("{:x}", self.to_chain_id());
-        format!(
+        let mut log_string = format!(
             r#"
 Stacks node host: {node_host}
+Stacks node TLS: {node_tls}
 Signer endpoint: {endpoint}
 Stacks address: {stacks_address}
 Public key: {public_key}
@@ -513,16 +875,23 @@ Metrics endpoint: {metrics_endpoint}
 Dry run: {dry_run}
 "#,
             node_host = self.node_host,
+            node_tls = self.node_tls,
             endpoint = self.endpoint,
             stacks_address = self.stacks_address,
-            public_key = to_hex(
-                &StacksPublicKey::from_private(&self.stacks_private_key).to_bytes_compressed()
-            ),
+            public_key = to_hex(&self.signing_key.public_key().to_bytes_compressed()),
             network = self.network,
             db_path = self.db_path.to_str().unwrap_or_default(),
             metrics_endpoint = metrics_endpoint,
             dry_run = self.dry_run,
-        )
+        );
+        for identity in &self.additional_identities {
+            log_string.push_str(&format!(
+                "Additional identity: {stacks_address} (database path: {db_path})\n",
+                stacks_address = identity.stacks_address,
+                db_path = identity.db_path.to_str().unwrap_or_default(),
+            ));
+        }
+        log_string
     }
 
     /// Get the chain ID for the network
@@ -642,6 +1011,8 @@ chain_id = {chain_id}
 
 #[cfg(test)]
 mod tests {
+    use clarity::vm::types::QualifiedContractIdentifier;
+
     use super::*;
 
     #[test]
@@ -685,6 +1056,7 @@ mod tests {
 
         let expected_str_v4 = r#"
 Stacks node host: 127.0.0.1:20443
+Stacks node TLS: false
 Signer endpoint: 127.0.0.1:30000
 Stacks address: ST3FPN8KBZ3YPBP0ZJGAAHTVFMQDTJCR5QPS7VTNJ
 Public key: 03bc489f27da3701d9f9e577c88de5567cf4023111b7577042d55cde4d823a3505
@@ -697,6 +1069,7 @@ Dry run: false
 
         let expected_str_v6: &'static str = r#"
 Stacks node host: 127.0.0.1:20443
+Stacks node TLS: false
 Signer endpoint: [::1]:30000
 Stacks address: ST3FPN8KBZ3YPBP0ZJGAAHTVFMQDTJCR5QPS7VTNJ
 Public key: 03bc489f27da3701d9f9e577c88de5567cf4023111b7577042d55cde4d823a3505
@@ -794,4 +1167,227 @@ capitulate_miner_view_timeout_secs = 1000
         let global_config = GlobalConfig::try_from(config).unwrap();
         assert_eq!(global_config.to_chain_id(), 0x80000100);
     }
+
+    #[test]
+    fn test_keyholder_config() {
+        let sk = StacksPrivateKey::random();
+        let pk_hex = StacksPublicKey::from_private(&sk).to_hex();
+        // nothing listens here: loading the config must not contact the keyholder
+        let keyholder_endpoint = "127.0.0.1:1";
+
+        let config_toml = format!(
+            r#"
+keyholder_endpoint = "{keyholder_endpoint}"
+keyholder_public_key = "{pk_hex}"
+keyholder_auth_token = "keyholder-token"
+node_host = "localhost"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "abcd"
+db_path = ":memory:"
+            "#
+        );
+        let config = GlobalConfig::load_from_str(&config_toml).unwrap();
+        assert!(config.stacks_private_key.is_none());
+        assert_eq!(
+            config.signing_key.public_key().to_bytes_compressed(),
+            StacksPublicKey::from_private(&sk).to_bytes_compressed()
+        );
+        assert_eq!(
+            config.stacks_address,
+            StacksAddress::p2pkh(false, &StacksPublicKey::from_private(&sk))
+        );
+        assert!(config.signing_key.check_ready().is_err());
+        assert!(!format!("{:?}", config.signing_key).contains("keyholder-token"));
+
+        // the keyholder's public key and auth token are required
+        let no_public_key =
+            config_toml.replace(&format!("keyholder_public_key = \"{pk_hex}\""), "");
+        assert!(GlobalConfig::load_from_str(&no_public_key).is_err());
+        let bad_public_key = config_toml.replace(&pk_hex, "00");
+        assert!(GlobalConfig::load_from_str(&bad_public_key).is_err());
+        let no_auth_token = config_toml.replace("keyholder_auth_token = \"keyholder-token\"", "");
+        assert!(GlobalConfig::load_from_str(&no_auth_token).is_err());
+        let empty_auth_token = config_toml.replace("\"keyholder-token\"", "\"\"");
+        assert!(GlobalConfig::load_from_str(&empty_auth_token).is_err());
+
+        // exactly one key source must be configured
+        let sk_hex = sk.to_hex();
+        let both = format!("stacks_private_key = \"{sk_hex}\"\n{config_toml}");
+        assert!(GlobalConfig::load_from_str(&both).is_err());
+        let neither = config_toml.replace(
+            &format!("keyholder_endpoint = \"{keyholder_endpoint}\""),
+            "",
+        );
+        assert!(GlobalConfig::load_from_str(&neither).is_err());
+
+        // the keyholder must be on a loopback address
+        let remote = config_toml.replace(keyholder_endpoint, "10.0.0.1:30001");
+        assert!(GlobalConfig::load_from_str(&remote).is_err());
+    }
+
+    #[test]
+    fn test_block_policy_config() {
+        let config_toml = r#"
+stacks_private_key = "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01"
+node_host = "localhost"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "abcd"
+db_path = ":memory:"
+
+[[block_policy]]
+rule = "max_block_cost"
+max_percentage = 80
+
+[[block_policy]]
+rule = "blocked_contract_call"
+contract = "ST000000000000000000002AMW42H.pox-4"
+function = "stack-stx"
+            "#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(
+            config.block_policy.rules(),
+            &[
+                BlockPolicyRule::MaxBlockCost { max_percentage: 80 },
+                BlockPolicyRule::BlockedContractCall {
+                    contract: QualifiedContractIdentifier::parse(
+                        "ST000000000000000000002AMW42H.pox-4"
+                    )
+                    .unwrap(),
+                    function: Some("stack-stx".into()),
+                },
+            ]
+        );
+
+        // no rules by default
+        let no_policy = config_toml.split("[[block_policy]]").next().unwrap();
+        let config = GlobalConfig::load_from_str(no_policy).unwrap();
+        assert!(config.block_policy.is_empty());
+
+        // rules are checked when the config is loaded
+        let bad_percentage = config_toml.replace("max_percentage = 80", "max_percentage = 0");
+        assert!(GlobalConfig::load_from_str(&bad_percentage).is_err());
+        let bad_contract = config_toml.replace("AMW42H.pox-4", "AMW42H");
+        assert!(GlobalConfig::load_from_str(&bad_contract).is_err());
+        let unknown_rule = config_toml.replace("\"max_block_cost\"", "\"max_block_size\"");
+        assert!(GlobalConfig::load_from_str(&unknown_rule).is_err());
+    }
+
+    #[test]
+    fn test_additional_identities_config() {
+        let config_toml = r#"
+stacks_private_key = "2de4e77aab89c0c2570bb8bb90824f5cf2a5204a975905fee450ff9dad0fcf28"
+node_host = "localhost"
+endpoint = "localhost:30000"
+network = "mainnet"
+auth_password = "abcd"
+db_path = "/tmp/signer-0.sqlite"
+
+[[identities]]
+stacks_private_key = "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01"
+db_path = "/tmp/signer-1.sqlite"
+
+[[identities]]
+stacks_private_key = "4bf4a1c89f3c9fe3b1e6fa0a4e3f9d8ea1d3f0b8db7a7b4b7e0cf1f1a0e5c06f01"
+db_path = "/tmp/signer-2.sqlite"
+auth_password = "efgh"
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        let identities = config.identities();
+        assert_eq!(identities.len(), 3);
+        assert_eq!(identities[0].stacks_address, config.stacks_address);
+        assert_eq!(identities[0].db_path, PathBuf::from("/tmp/signer-0.sqlite"));
+        let pk = StacksPrivateKey::from_hex(
+            "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+        )
+        .unwrap();
+        assert_eq!(
+            identities[1].stacks_address,
+            StacksAddress::p2pkh(true, &StacksPublicKey::from_private(&pk))
+        );
+        assert_eq!(identities[1].auth_password, "abcd");
+        assert_eq!(identities[2].auth_password, "efgh");
+        assert!(config
+            .config_to_log_string()
+            .contains("(database path: /tmp/signer-2.sqlite)"));
+
+        // Identities need their own keys and databases
+        let duplicate_db = config_toml.replace("signer-2.sqlite", "signer-1.sqlite");
+        assert!(GlobalConfig::load_from_str(&duplicate_db).is_err());
+        let duplicate_key = config_toml.replace(
+            "4bf4a1c89f3c9fe3b1e6fa0a4e3f9d8ea1d3f0b8db7a7b4b7e0cf1f1a0e5c06f01",
+            "2de4e77aab89c0c2570bb8bb90824f5cf2a5204a975905fee450ff9dad0fcf28",
+        );
+        assert!(GlobalConfig::load_from_str(&duplicate_key).is_err());
+        let unknown_field = config_toml.replace("auth_password = \"efgh\"", "dry_run = true");
+        assert!(GlobalConfig::load_from_str(&unknown_field).is_err());
+    }
+
+    #[test]
+    fn test_db_retention_config() {
+        let config_toml = r#"
+stacks_private_key = "2de4e77aab89c0c2570bb8bb90824f5cf2a5204a975905fee450ff9dad0fcf28"
+node_host = "localhost"
+endpoint = "localhost:30000"
+network = "mainnet"
+auth_password = "abcd"
+db_path = ":memory:"
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert_eq!(config.db_retention, None);
+        assert_eq!(
+            config.db_prune_interval,
+            Duration::from_secs(DEFAULT_DB_PRUNE_INTERVAL_SECS)
+        );
+
+        let reward_cycles =
+            format!("{config_toml}db_retention_reward_cycles = 6\ndb_prune_interval_secs = 60\n");
+        let config = GlobalConfig::load_from_str(&reward_cycles).unwrap();
+        assert_eq!(config.db_retention, Some(DbRetention::RewardCycles(6)));
+        assert_eq!(config.db_prune_interval, Duration::from_secs(60));
+
+        let burn_blocks = format!("{config_toml}db_retention_burn_blocks = 2100\n");
+        let config = GlobalConfig::load_from_str(&burn_blocks).unwrap();
+        assert_eq!(config.db_retention, Some(DbRetention::BurnBlocks(2100)));
+
+        let both = format!("{reward_cycles}db_retention_burn_blocks = 2100\n");
+        assert!(GlobalConfig::load_from_str(&both).is_err());
+    }
+
+    #[test]
+    fn test_node_tls_config() {
+        let config_toml = r#"
+stacks_private_key = "2de4e77aab89c0c2570bb8bb90824f5cf2a5204a975905fee450ff9dad0fcf28"
+node_host = "localhost:20443"
+endpoint = "localhost:30000"
+network = "mainnet"
+auth_password = "abcd"
+db_path = ":memory:"
+"#;
+        let config = GlobalConfig::load_from_str(config_toml).unwrap();
+        assert!(!config.node_tls);
+        assert!(config.node_stackerdb_tls_config.is_none());
+        assert_eq!(config.node_origin(), "http://localhost:20443");
+
+        let tls = format!("{config_toml}node_tls = true\n");
+        let config = GlobalConfig::load_from_str(&tls).unwrap();
+        assert_eq!(config.node_origin(), "https://localhost:20443");
+        assert!(config.node_tls_ca_certs.is_empty());
+        assert!(config.node_stackerdb_tls_config.is_some());
+
+        // pinning a CA implies TLS
+        let ca_cert_path = format!(
+            "{}/../stackslib/src/net/tests/tls/ca-1.pem",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let pinned = format!("{config_toml}node_tls_ca_cert_path = \"{ca_cert_path}\"\n");
+        let config = GlobalConfig::load_from_str(&pinned).unwrap();
+        assert_eq!(config.node_origin(), "https://localhost:20443");
+        assert_eq!(config.node_tls_ca_certs.len(), 1);
+        config.node_http_client();
+
+        let missing = format!("{config_toml}node_tls_ca_cert_path = \"/nonexistent/ca.pem\"\n");
+        assert!(GlobalConfig::load_from_str(&missing).is_err());
+    }
 }
@@ -0,0 +1,757 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Out-of-process signing for the signer.
+//!
+//! A keyholder is a separate process which holds the signer's private key and signs digests on
+//! its behalf, so that the signer host never needs the raw key. The signer talks to it over a
+//! Unix socket or a loopback TCP connection using newline-delimited JSON: each request is a
+//! single JSON object on its own line, and is answered by a single JSON object on its own line.
+//!
+//! Every connection must start with `{"method":"authenticate","token":"<token>"}`, carrying
+//! a token shared by the signer and the keyholder. If the token is wrong, the keyholder
+//! answers `{"error":"Unauthorized"}` and closes the connection; otherwise it answers nothing
+//! and serves the requests that follow. Without the token, a local process which can reach
+//! the endpoint could have the keyholder sign arbitrary digests.
+//!
+//! The protocol only exposes what the signer needs:
+//!
+//! * `{"method":"public_key"}` is answered with `{"public_key":"<hex>"}`
+//! * `{"method":"sign_digest","digest":"<32 bytes of hex>"}` is answered with
+//!   `{"signature":"<65 bytes of hex>"}`
+//!
+//! Failures are answered with `{"error":"<message>"}`.
+//!
+//! The keyholder serves each connection on its own thread, and closes a connection that stays
+//! idle for longer than its timeout.
+
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+use libsigner::{KeyBackend, KeyBackendError};
+use serde::{Deserialize, Serialize};
+use stacks_common::types::chainstate::{StacksPrivateKey, StacksPublicKey};
+use stacks_common::util::hash::{hex_bytes, to_hex};
+use stacks_common::util::secp256k1::MessageSignature;
+use stacks_common::{debug, info, warn};
+
+use crate::admin::tokens_match;
+
+/// The size of the digests that the keyholder will sign
+pub const DIGEST_LENGTH: usize = 32;
+
+/// The longest request or response line that will be read, in bytes
+const MAX_LINE_LENGTH: u64 = 4096;
+
+/// Keyholder errors
+#[derive(thiserror::Error, Debug)]
+pub enum KeyholderError {
+    /// The endpoint string could not be parsed or is not allowed
+    #[error("Invalid keyholder endpoint: {0}")]
+    InvalidEndpoint(String),
+    /// An I/O error occurred talking to the keyholder
+    #[error("Keyholder I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The peer sent a message that does not follow the protocol
+    #[error("Keyholder protocol error: {0}")]
+    Protocol(String),
+    /// The keyholder refused or failed to handle the request
+    #[error("Keyholder returned an error: {0}")]
+    Remote(String),
+}
+
+/// Where a keyholder listens
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyholderEndpoint {
+    /// A Unix domain socket, written as `unix:<path>`
+    Unix(PathBuf),
+    /// A TCP socket, written as `<ip>:<port>`. Only loopback addresses are allowed, since the
+    /// connection is not encrypted.
+    Tcp(SocketAddr),
+}
+
+impl FromStr for KeyholderEndpoint {
+    type Err = KeyholderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix:") {
+            if path.is_empty() {
+                return Err(KeyholderError::InvalidEndpoint(s.into()));
+            }
+            return Ok(Self::Unix(path.into()));
+        }
+        let addr: SocketAddr = s
+            .parse()
+            .map_err(|_| KeyholderError::InvalidEndpoint(s.into()))?;
+        if !addr.ip().is_loopback() {
+            return Err(KeyholderError::InvalidEndpoint(format!(
+                "{s} is not a loopback address"
+            )));
+        }
+        Ok(Self::Tcp(addr))
+    }
+}
+
+impl Display for KeyholderEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+            Self::Tcp(addr) => write!(f, "{addr}"),
+        }
+    }
+}
+
+/// A request to the keyholder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum KeyholderRequest {
+    /// Authenticate the connection. Must be the first request on every connection.
+    Authenticate {
+        /// The token shared by the signer and the keyholder
+        token: String,
+    },
+    /// Look up the keyholder's public key
+    PublicKey,
+    /// Sign a digest
+    SignDigest {
+        /// The hex-encoded digest
+        digest: String,
+    },
+}
+
+/// A response from the keyholder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyholderResponse {
+    /// The hex-encoded compressed public key
+    PublicKey(String),
+    /// The signature over the requested digest
+    Signature(MessageSignature),
+    /// The request failed
+    Error(String),
+}
+
+/// A connection to or from a keyholder
+enum KeyholderStream {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl KeyholderStream {
+    fn connect(endpoint: &KeyholderEndpoint, timeout: Duration) -> Result<Self, KeyholderError> {
+        let stream = match endpoint {
+            KeyholderEndpoint::Tcp(addr) => {
+                let stream = TcpStream::connect_timeout(addr, timeout)?;
+                stream.set_nodelay(true)?;
+                Self::Tcp(stream)
+            }
+            #[cfg(unix)]
+            KeyholderEndpoint::Unix(path) => Self::Unix(UnixStream::connect(path)?),
+            #[cfg(not(unix))]
+            KeyholderEndpoint::Unix(_) => {
+                return Err(KeyholderError::InvalidEndpoint(
+                    "Unix sockets are not supported on this platform".into(),
+                ))
+            }
+        };
+        stream.set_timeouts(Some(timeout))?;
+        Ok(stream)
+    }
+
+    fn set_timeouts(&self, timeout: Option<Duration>) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(s) => {
+                s.set_read_timeout(timeout)?;
+                s.set_write_timeout(timeout)
+            }
+            #[cfg(unix)]
+            Self::Unix(s) => {
+                s.set_read_timeout(timeout)?;
+                s.set_write_timeout(timeout)
+            }
+        }
+    }
+}
+
+impl Read for KeyholderStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.read(buf),
+            #[cfg(unix)]
+            Self::Unix(s) => s.read(buf),
+        }
+    }
+}
+
+impl Write for KeyholderStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Tcp(s) => s.write(buf),
+            #[cfg(unix)]
+            Self::Unix(s) => s.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Tcp(s) => s.flush(),
+            #[cfg(unix)]
+            Self::Unix(s) => s.flush(),
+        }
+    }
+}
+
+/// Write a message as a single line of JSON
+fn write_message<W: Write, T: Serialize>(fd: &mut W, msg: &T) -> Result<(), KeyholderError> {
+    let mut line =
+        serde_json::to_vec(msg).map_err(|e| KeyholderError::Protocol(format!("{e:?}")))?;
+    line.push(b'\n');
+    fd.write_all(&line)?;
+    fd.flush()?;
+    Ok(())
+}
+
+/// Read a single line of JSON. Returns `Ok(None)` if the peer closed the connection.
+fn read_message<R: BufRead, T: for<'de> Deserialize<'de>>(
+    fd: &mut R,
+) -> Result<Option<T>, KeyholderError> {
+    let mut line = String::new();
+    let read = fd.take(MAX_LINE_LENGTH).read_line(&mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') {
+        return Err(KeyholderError::Protocol(
+            "Message is too long or truncated".into(),
+        ));
+    }
+    serde_json::from_str(&line)
+        .map(Some)
+        .map_err(|e| KeyholderError::Protocol(format!("{e:?}")))
+}
+
+/// A [KeyBackend] whose key is held by a keyholder process
+pub struct RemoteKeyBackend {
+    /// Where the keyholder listens
+    endpoint: KeyholderEndpoint,
+    /// The public key that the keyholder is expected to sign with
+    public_key: StacksPublicKey,
+    /// The token that authenticates the signer to the keyholder
+    auth_token: String,
+    /// How long to wait for the keyholder to answer a single request
+    timeout: Duration,
+    /// How long to keep retrying a signing request before giving up
+    retry_timeout: Duration,
+}
+
+impl std::fmt::Debug for RemoteKeyBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // never log the auth token
+        f.debug_struct("RemoteKeyBackend")
+            .field("endpoint", &self.endpoint)
+            .field("public_key", &self.public_key)
+            .field("timeout", &self.timeout)
+            .field("retry_timeout", &self.retry_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RemoteKeyBackend {
+    /// A backend for the keyholder at `endpoint`, which should sign with `public_key`. This
+    /// does not contact the keyholder: each signing request connects to it on its own.
+    pub fn new(
+        endpoint: KeyholderEndpoint,
+        public_key: StacksPublicKey,
+        auth_token: String,
+        timeout: Duration,
+        retry_timeout: Duration,
+    ) -> Self {
+        Self {
+            endpoint,
+            public_key,
+            auth_token,
+            timeout,
+            retry_timeout,
+        }
+    }
+
+    /// Send a single request to the keyholder over a new, authenticated connection
+    fn request(&self, request: &KeyholderRequest) -> Result<KeyholderResponse, KeyholderError> {
+        let mut stream = BufReader::new(KeyholderStream::connect(&self.endpoint, self.timeout)?);
+        let authenticate = KeyholderRequest::Authenticate {
+            token: self.auth_token.clone(),
+        };
+        write_message(stream.get_mut(), &authenticate)?;
+        write_message(stream.get_mut(), request)?;
+        match read_message(&mut stream)? {
+            Some(KeyholderResponse::Error(e)) => Err(KeyholderError::Remote(e)),
+            Some(response) => Ok(response),
+            None => Err(KeyholderError::Protocol(
+                "Keyholder closed the connection without responding".into(),
+            )),
+        }
+    }
+
+    /// Ask the keyholder to sign `digest`, and check the signature against its public key
+    fn try_sign_digest(&self, digest: &[u8]) -> Result<MessageSignature, KeyholderError> {
+        let request = KeyholderRequest::SignDigest {
+            digest: to_hex(digest),
+        };
+        let response = self.request(&request)?;
+        let KeyholderResponse::Signature(signature) = response else {
+            return Err(KeyholderError::Protocol(format!(
+                "Expected a signature, got {response:?}"
+            )));
+        };
+        let signer = StacksPublicKey::recover_to_pubkey(digest, &signature)
+            .map_err(|e| KeyholderError::Protocol(e.to_string()))?;
+        if signer.to_bytes_compressed() != self.public_key.to_bytes_compressed() {
+            return Err(KeyholderError::Protocol(
+                "Keyholder signed with an unexpected key".into(),
+            ));
+        }
+        Ok(signature)
+    }
+}
+
+impl KeyBackend for RemoteKeyBackend {
+    fn public_key(&self) -> StacksPublicKey {
+        self.public_key
+    }
+
+    fn check_ready(&self) -> Result<(), String> {
+        let response = self
+            .request(&KeyholderRequest::PublicKey)
+            .map_err(|e| format!("Failed to reach keyholder at {}: {e}", self.endpoint))?;
+        let KeyholderResponse::PublicKey(public_key_hex) = response else {
+            return Err(format!("Expected a public key, got {response:?}"));
+        };
+        if public_key_hex != self.public_key.to_hex() {
+            return Err(format!(
+                "Keyholder at {} holds the key for {public_key_hex}, expected {}",
+                self.endpoint,
+                self.public_key.to_hex()
+            ));
+        }
+        Ok(())
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<MessageSignature, KeyBackendError> {
+        if digest.len() != DIGEST_LENGTH {
+            return Err(KeyBackendError::SigningFailed(format!(
+                "Cannot sign a {}-byte digest",
+                digest.len()
+            )));
+        }
+        let backoff_timer = backoff::ExponentialBackoffBuilder::new()
+            .with_initial_interval(Duration::from_millis(50))
+            .with_max_interval(Duration::from_secs(1))
+            .with_max_elapsed_time(Some(self.retry_timeout))
+            .build();
+        let notify = |e, dur| {
+            warn!(
+                "Failed to get a signature from keyholder at {}: {e}. Next attempt in {dur:?}",
+                self.endpoint
+            );
+        };
+        let sign = || {
+            self.try_sign_digest(digest).map_err(|e| match e {
+                // the keyholder refused the request, so retrying won't help
+                KeyholderError::Remote(_) => backoff::Error::permanent(e),
+                _ => backoff::Error::transient(e),
+            })
+        };
+        backoff::retry_notify(backoff_timer, sign, notify).map_err(|e| match e {
+            backoff::Error::Permanent(e) | backoff::Error::Transient { err: e, .. } => {
+                KeyBackendError::SigningFailed(e.to_string())
+            }
+        })
+    }
+}
+
+/// Answer a single request on an authenticated connection with `private_key`
+pub fn handle_request(
+    private_key: &StacksPrivateKey,
+    request: KeyholderRequest,
+) -> KeyholderResponse {
+    match request {
+        KeyholderRequest::Authenticate { .. } => {
+            KeyholderResponse::Error("Connection is already authenticated".into())
+        }
+        KeyholderRequest::PublicKey => {
+            KeyholderResponse::PublicKey(private_key.public_key().to_hex())
+        }
+        KeyholderRequest::SignDigest { digest } => {
+            let digest = match hex_bytes(&digest) {
+                Ok(digest) if digest.len() == DIGEST_LENGTH => digest,
+                _ => {
+                    return KeyholderResponse::Error(format!(
+                        "Digest must be {DIGEST_LENGTH} bytes of hex"
+                    ))
+                }
+            };
+            match private_key.sign_digest(&digest) {
+                Ok(signature) => KeyholderResponse::Signature(signature),
+                Err(e) => KeyholderResponse::Error(e.to_string()),
+            }
+        }
+    }
+}
+
+/// Serve requests on a single connection until the peer closes it. The first request must
+/// authenticate the peer with `auth_token`.
+fn serve_connection(
+    private_key: &StacksPrivateKey,
+    auth_token: &str,
+    stream: KeyholderStream,
+) -> Result<(), KeyholderError> {
+    let mut stream = BufReader::new(stream);
+    let authenticated = match read_message(&mut stream)? {
+        Some(KeyholderRequest::Authenticate { token }) => tokens_match(auth_token, &token),
+        Some(_) => false,
+        None => return Ok(()),
+    };
+    if !authenticated {
+        write_message(
+            stream.get_mut(),
+            &KeyholderResponse::Error("Unauthorized".into()),
+        )?;
+        return Err(KeyholderError::Protocol(
+            "Peer failed to authenticate".into(),
+        ));
+    }
+    while let Some(request) = read_message(&mut stream)? {
+        debug!("Keyholder: handling request {request:?}");
+        let response = handle_request(private_key, request);
+        write_message(stream.get_mut(), &response)?;
+    }
+    Ok(())
+}
+
+/// A bound keyholder socket
+pub enum KeyholderListener {
+    /// A loopback TCP listener
+    Tcp(TcpListener),
+    /// A Unix domain socket listener
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+impl KeyholderListener {
+    /// Bind to `endpoint`. A stale Unix socket left over at the endpoint's path is replaced.
+    pub fn bind(endpoint: &KeyholderEndpoint) -> Result<Self, KeyholderError> {
+        match endpoint {
+            KeyholderEndpoint::Tcp(addr) => Ok(Self::Tcp(TcpListener::bind(addr)?)),
+            #[cfg(unix)]
+            KeyholderEndpoint::Unix(path) => {
+                use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+                if let Ok(metadata) = std::fs::symlink_metadata(path) {
+                    if !metadata.file_type().is_socket() {
+                        return Err(KeyholderError::InvalidEndpoint(format!(
+                            "{} exists and is not a socket",
+                            path.display()
+                        )));
+                    }
+                    std::fs::remove_file(path)?;
+                }
+                let listener = UnixListener::bind(path)?;
+                // only the keyholder's user and group may connect
+                std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o660))?;
+                Ok(Self::Unix(listener))
+            }
+            #[cfg(not(unix))]
+            KeyholderEndpoint::Unix(_) => Err(KeyholderError::InvalidEndpoint(
+                "Unix sockets are not supported on this platform".into(),
+            )),
+        }
+    }
+
+    /// The TCP address that this listener is bound to, if it is a TCP listener
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        match self {
+            Self::Tcp(listener) => listener.local_addr().ok(),
+            #[cfg(unix)]
+            Self::Unix(_) => None,
+        }
+    }
+
+    fn accept(&self) -> std::io::Result<KeyholderStream> {
+        match self {
+            Self::Tcp(listener) => {
+                let (stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                Ok(KeyholderStream::Tcp(stream))
+            }
+            #[cfg(unix)]
+            Self::Unix(listener) => Ok(KeyholderStream::Unix(listener.accept()?.0)),
+        }
+    }
+
+    /// Serve keyholder requests with `private_key` forever, from peers which authenticate with
+    /// `auth_token`. Each connection is served on its own thread, and is closed once it has
+    /// been idle for `timeout`.
+    pub fn serve(&self, private_key: &StacksPrivateKey, auth_token: &str, timeout: Duration) -> ! {
+        info!(
+            "Keyholder: serving requests for public key {}",
+            private_key.public_key().to_hex()
+        );
+        loop {
+            let stream = match self.accept() {
+                Ok(stream) => stream,
+                Err(e) => {
+                    warn!("Keyholder: failed to accept a connection: {e}");
+                    continue;
+                }
+            };
+            if let Err(e) = stream.set_timeouts(Some(timeout)) {
+                warn!("Keyholder: failed to set connection timeouts: {e}");
+                continue;
+            }
+            let private_key = *private_key;
+            let auth_token = auth_token.to_string();
+            let spawned = thread::Builder::new()
+                .name("keyholder-connection".into())
+                .spawn(move || {
+                    if let Err(e) = serve_connection(&private_key, &auth_token, stream) {
+                        warn!("Keyholder: connection failed: {e}");
+                    }
+                });
+            if let Err(e) = spawned {
+                warn!("Keyholder: failed to spawn a connection thread: {e}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use stacks_common::types::PublicKey;
+    use stacks_common::util::hash::Sha256Sum;
+
+    use super::*;
+
+    const AUTH_TOKEN: &str = "keyholder-token";
+
+    fn spawn_keyholder(endpoint: &KeyholderEndpoint) -> (KeyholderEndpoint, StacksPrivateKey) {
+        let private_key = StacksPrivateKey::random();
+        let listener = KeyholderListener::bind(endpoint).unwrap();
+        let endpoint = match listener.local_addr() {
+            Some(addr) => KeyholderEndpoint::Tcp(addr),
+            None => endpoint.clone(),
+        };
+        thread::spawn(move || listener.serve(&private_key, AUTH_TOKEN, Duration::from_secs(5)));
+        (endpoint, private_key)
+    }
+
+    fn remote_backend(
+        endpoint: KeyholderEndpoint,
+        private_key: &StacksPrivateKey,
+        auth_token: &str,
+    ) -> RemoteKeyBackend {
+        RemoteKeyBackend::new(
+            endpoint,
+            private_key.public_key(),
+            auth_token.into(),
+            Duration::from_secs(5),
+            Duration::from_secs(1),
+        )
+    }
+
+    fn check_backend(endpoint: KeyholderEndpoint, private_key: &StacksPrivateKey) {
+        let backend = remote_backend(endpoint, private_key, AUTH_TOKEN);
+        backend.check_ready().unwrap();
+
+        let digest = Sha256Sum::from_data(b"block");
+        let signature = backend.sign_digest(digest.as_bytes()).unwrap();
+        assert_eq!(
+            signature,
+            private_key.sign_digest(digest.as_bytes()).unwrap()
+        );
+        assert!(backend
+            .public_key()
+            .verify(digest.as_bytes(), &signature)
+            .unwrap());
+
+        // only digests can be signed
+        assert!(backend.sign_digest(b"not a digest").is_err());
+    }
+
+    #[test]
+    fn parse_endpoints() {
+        assert_eq!(
+            "unix:/run/keyholder.sock"
+                .parse::<KeyholderEndpoint>()
+                .unwrap(),
+            KeyholderEndpoint::Unix("/run/keyholder.sock".into())
+        );
+        assert_eq!(
+            "127.0.0.1:30001".parse::<KeyholderEndpoint>().unwrap(),
+            KeyholderEndpoint::Tcp("127.0.0.1:30001".parse().unwrap())
+        );
+        assert!("[::1]:30001".parse::<KeyholderEndpoint>().is_ok());
+        assert!("0.0.0.0:30001".parse::<KeyholderEndpoint>().is_err());
+        assert!("10.0.0.1:30001".parse::<KeyholderEndpoint>().is_err());
+        assert!("unix:".parse::<KeyholderEndpoint>().is_err());
+        assert!("localhost".parse::<KeyholderEndpoint>().is_err());
+    }
+
+    #[test]
+    fn handle_requests() {
+        let private_key = StacksPrivateKey::random();
+        assert_eq!(
+            handle_request(&private_key, KeyholderRequest::PublicKey),
+            KeyholderResponse::PublicKey(private_key.public_key().to_hex())
+        );
+        let KeyholderResponse::Error(_) = handle_request(
+            &private_key,
+            KeyholderRequest::SignDigest {
+                digest: "00".into(),
+            },
+        ) else {
+            panic!("Signed a short digest");
+        };
+        let KeyholderResponse::Error(_) = handle_request(
+            &private_key,
+            KeyholderRequest::SignDigest {
+                digest: "not hex".into(),
+            },
+        ) else {
+            panic!("Signed a malformed digest");
+        };
+
+        // the wire format is part of the protocol
+        assert_eq!(
+            serde_json::to_string(&KeyholderRequest::SignDigest {
+                digest: "00".into()
+            })
+            .unwrap(),
+            r#"{"method":"sign_digest","digest":"00"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&KeyholderRequest::PublicKey).unwrap(),
+            r#"{"method":"public_key"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&KeyholderResponse::Error("oops".into())).unwrap(),
+            r#"{"error":"oops"}"#
+        );
+    }
+
+    #[test]
+    fn remote_signing_over_tcp() {
+        let (endpoint, private_key) =
+            spawn_keyholder(&KeyholderEndpoint::Tcp("127.0.0.1:0".parse().unwrap()));
+        check_backend(endpoint, &private_key);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn remote_signing_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!(
+            "stacks-signer-keyholder-{}.sock",
+            rand::random::<u64>()
+        ));
+        let (endpoint, private_key) = spawn_keyholder(&KeyholderEndpoint::Unix(path.clone()));
+        check_backend(endpoint, &private_key);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn requires_auth_token() {
+        let (endpoint, private_key) =
+            spawn_keyholder(&KeyholderEndpoint::Tcp("127.0.0.1:0".parse().unwrap()));
+        let backend = remote_backend(endpoint.clone(), &private_key, "wrong-token");
+        assert!(backend.check_ready().is_err());
+        let digest = Sha256Sum::from_data(b"block");
+        assert!(backend.sign_digest(digest.as_bytes()).is_err());
+
+        // a connection which skips authentication is refused
+        let mut stream =
+            BufReader::new(KeyholderStream::connect(&endpoint, Duration::from_secs(5)).unwrap());
+        write_message(stream.get_mut(), &KeyholderRequest::PublicKey).unwrap();
+        assert_eq!(
+            read_message::<_, KeyholderResponse>(&mut stream).unwrap(),
+            Some(KeyholderResponse::Error("Unauthorized".into()))
+        );
+        assert_eq!(
+            read_message::<_, KeyholderResponse>(&mut stream).unwrap(),
+            None
+        );
+
+        // the keyholder must hold the configured key
+        let other_key = StacksPrivateKey::random();
+        let backend = RemoteKeyBackend::new(
+            endpoint,
+            other_key.public_key(),
+            AUTH_TOKEN.into(),
+            Duration::from_secs(5),
+            Duration::from_secs(1),
+        );
+        assert!(backend.check_ready().is_err());
+        assert!(backend.sign_digest(digest.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn idle_connection_does_not_block_signing() {
+        let (endpoint, private_key) =
+            spawn_keyholder(&KeyholderEndpoint::Tcp("127.0.0.1:0".parse().unwrap()));
+        // authenticate, then stay idle for longer than the backend's timeout
+        let mut idle =
+            BufReader::new(KeyholderStream::connect(&endpoint, Duration::from_secs(5)).unwrap());
+        write_message(
+            idle.get_mut(),
+            &KeyholderRequest::Authenticate {
+                token: AUTH_TOKEN.into(),
+            },
+        )
+        .unwrap();
+        let backend = RemoteKeyBackend::new(
+            endpoint,
+            private_key.public_key(),
+            AUTH_TOKEN.into(),
+            Duration::from_millis(500),
+            Duration::from_millis(500),
+        );
+        let digest = Sha256Sum::from_data(b"block");
+        backend.sign_digest(digest.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn unreachable_keyholder() {
+        // nothing listens on port 1, but creating the backend doesn't connect
+        let endpoint = KeyholderEndpoint::Tcp("127.0.0.1:1".parse().unwrap());
+        let private_key = StacksPrivateKey::random();
+        let backend = RemoteKeyBackend::new(
+            endpoint,
+            private_key.public_key(),
+            AUTH_TOKEN.into(),
+            Duration::from_millis(100),
+            Duration::from_millis(100),
+        );
+        assert!(backend.check_ready().is_err());
+        let digest = Sha256Sum::from_data(b"block");
+        assert!(backend.sign_digest(digest.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! # stacks-signer-keyholder: reference keyholder for stacks-signer.
+//!
+//! Holds a signer's private key and signs digests on the signer's behalf, so that the host
+//! running `stacks-signer` never needs the raw key. See `stacks_signer::keyholder` for the
+//! protocol.
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use stacks_common::types::chainstate::StacksPrivateKey;
+use stacks_signer::keyholder::{KeyholderEndpoint, KeyholderListener};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+/// Hold a stacks-signer's private key and sign on its behalf
+struct Args {
+    /// Where to listen for the signer: `unix:<path>` or a loopback `<ip>:<port>`
+    #[arg(long)]
+    endpoint: KeyholderEndpoint,
+    /// A file containing the hex-encoded private key. It should only be readable by the
+    /// keyholder's user.
+    #[arg(long)]
+    private_key_file: PathBuf,
+    /// A file containing the token that the signer must authenticate with, which is its
+    /// `keyholder_auth_token`. It should only be readable by the keyholder's user.
+    #[arg(long)]
+    auth_token_file: PathBuf,
+    /// How long (in millisecs) to wait on an idle connection before closing it
+    #[arg(long, default_value_t = 30_000)]
+    timeout_ms: u64,
+}
+
+fn main() {
+    let args = Args::parse();
+    let private_key_hex = fs::read_to_string(&args.private_key_file).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read private key file {}: {e}",
+            args.private_key_file.display()
+        )
+    });
+    let private_key = StacksPrivateKey::from_hex(private_key_hex.trim())
+        .expect("Private key file does not contain a hex-encoded private key");
+    let auth_token = fs::read_to_string(&args.auth_token_file).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read auth token file {}: {e}",
+            args.auth_token_file.display()
+        )
+    });
+    let auth_token = auth_token.trim();
+    assert!(!auth_token.is_empty(), "Auth token file is empty");
+    let listener = KeyholderListener::bind(&args.endpoint)
+        .unwrap_or_else(|e| panic!("Failed to listen on {}: {e}", args.endpoint));
+    println!("Keyholder listening on {}", args.endpoint);
+    listener.serve(
+        &private_key,
+        auth_token,
+        Duration::from_millis(args.timeout_ms),
+    );
+}
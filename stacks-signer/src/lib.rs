@@ -21,6 +21,10 @@ Usage documentation can be found in the [README](https://github.com/Trust-Machin
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+/// The signer admin API
+pub mod admin;
+/// The audit log of the signer's block decisions
+pub mod audit;
 /// This module stores chainstate information about Stacks, SortitionDB for
 /// tracking by the signer.
 pub mod chainstate;
@@ -30,14 +34,23 @@ pub mod cli;
 pub mod client;
 /// The configuration module for the signer
 pub mod config;
+/// The keyholder protocol for signing with a key held outside of the signer process
+pub mod keyholder;
 /// The signer monitor for observing signer behaviours in the network
 pub mod monitor_signers;
 /// The monitoring server for the signer
 pub mod monitoring;
+/// Operator-defined block acceptance rules
+pub mod policy;
 /// The primary runloop for the signer
 pub mod runloop;
 /// The signer state module
 pub mod signerdb;
+/// An in-process simulation of a signer set, a miner and a stacks-node
+#[cfg(any(test, feature = "testing"))]
+pub mod sim;
+/// Offline replay of tenure-extend decisions under alternative timeout settings
+pub mod tenure_extend_sim;
 /// The util module for the signer
 pub mod utils;
 /// The v0 implementation of the signer.
@@ -134,6 +147,20 @@ impl<S: Signer<T> + Send + 'static, T: SignerEventTrait + 'static> SpawnedSigner
         let (res_send, res_recv) = channel();
         let ev = SignerEventReceiver::new(config.network.is_mainnet());
         crate::monitoring::actions::start_serving_monitoring_metrics(config.clone()).ok();
+        if let Some(retention) = config.db_retention {
+            for identity in config.identities() {
+                if identity.db_path.to_str() == Some(":memory:") {
+                    continue;
+                }
+                if let Err(e) = crate::signerdb::spawn_db_pruner(
+                    identity.db_path,
+                    retention,
+                    config.db_prune_interval,
+                ) {
+                    warn!("Failed to start signer db pruning: {e}");
+                }
+            }
+        }
         let runloop = RunLoop::new(config.clone());
         let mut signer: RunLoopSigner<S, T> = libsigner::Signer::new(runloop, ev, res_send);
         let running_signer = signer.spawn(endpoint).expect("Failed to spawn signer");
@@ -26,24 +26,29 @@ extern crate serde;
 extern crate serde_json;
 extern crate toml;
 
-use std::io::{self, Write};
+use std::fs::File;
+use std::io::{self, BufReader, Write};
 
-use blockstack_lib::util_lib::signed_structured_data::pox4::make_pox_4_signer_key_signature;
+use blockstack_lib::util_lib::signed_structured_data::pox4::make_pox_4_signer_key_message_hash;
 use clap::Parser;
-use clarity::types::chainstate::StacksPublicKey;
 use clarity::util::sleep_ms;
 use libsigner::{SignerSession, VERSION_STRING};
 use libstackerdb::StackerDBChunkData;
 use stacks_common::util::hash::to_hex;
 use stacks_common::util::secp256k1::MessageSignature;
 use stacks_common::{debug, error};
+use stacks_signer::audit::write_audit_records;
 use stacks_signer::cli::{
-    Cli, Command, GenerateStackingSignatureArgs, GenerateVoteArgs, GetChunkArgs,
-    GetLatestChunkArgs, MonitorSignersArgs, PutChunkArgs, RunSignerArgs, StackerDBArgs,
-    VerifyVoteArgs,
+    Cli, Command, ExportAuditArgs, GenerateStackingSignatureArgs, GenerateVoteArgs, GetChunkArgs,
+    GetLatestChunkArgs, MonitorSignersArgs, PruneDbArgs, PutChunkArgs, RunSignerArgs,
+    SimulateTenureExtendArgs, StackerDBArgs, VerifyVoteArgs,
 };
 use stacks_signer::config::GlobalConfig;
 use stacks_signer::monitor_signers::SignerMonitor;
+use stacks_signer::signerdb::SignerDb;
+use stacks_signer::tenure_extend_sim::{
+    parse_miner_extend_log, replay_tenure_extends, write_replay_report, SignerHistory,
+};
 use stacks_signer::utils::stackerdb_session;
 use stacks_signer::v0::SpawnedSigner;
 use tracing_subscriber::prelude::*;
@@ -99,6 +104,14 @@ fn handle_put_chunk(args: PutChunkArgs) {
 fn handle_run(args: RunSignerArgs) {
     debug!("Running signer...");
     let config = GlobalConfig::try_from(&args.config).unwrap();
+    for identity in config.identities() {
+        if let Err(e) = identity.signing_key.check_ready() {
+            panic!(
+                "Signing key for {} is not ready: {e}",
+                identity.stacks_address
+            );
+        }
+    }
     let spawned_signer = SpawnedSigner::new(config);
     println!("Signer spawned successfully. Waiting for messages to process...");
     // Wait for the spawned signer to stop (will only occur if an error occurs)
@@ -111,21 +124,22 @@ fn handle_generate_stacking_signature(
 ) -> MessageSignature {
     let config = GlobalConfig::try_from(&args.config).unwrap();
 
-    let private_key = config.stacks_private_key;
-    let public_key = StacksPublicKey::from_private(&private_key);
+    let public_key = config.signing_key.public_key();
     let pk_hex = to_hex(&public_key.to_bytes_compressed());
 
-    let signature = make_pox_4_signer_key_signature(
+    let message_hash = make_pox_4_signer_key_message_hash(
         &args.pox_address,
-        &private_key, //
         args.reward_cycle.into(),
         args.method.topic(),
         config.to_chain_id(),
         args.period.into(),
         args.max_amount,
         args.auth_id,
-    )
-    .expect("Failed to generate signature");
+    );
+    let signature = config
+        .signing_key
+        .sign_digest(message_hash.as_bytes())
+        .expect("Failed to generate signature");
 
     let output_str = if args.json {
         serde_json::to_string(&serde_json::json!({
@@ -161,7 +175,7 @@ fn handle_check_config(args: RunSignerArgs) {
 
 fn handle_generate_vote(args: GenerateVoteArgs, do_print: bool) -> MessageSignature {
     let config = GlobalConfig::try_from(&args.config).unwrap();
-    let message_signature = args.vote_info.sign(&config.stacks_private_key).unwrap();
+    let message_signature = args.vote_info.sign(config.signing_key.as_ref()).unwrap();
     if do_print {
         println!("{}", to_hex(message_signature.as_bytes()));
     }
@@ -197,6 +211,76 @@ fn handle_monitor_signers(args: MonitorSignersArgs) {
     }
 }
 
+fn handle_prune_db(args: PruneDbArgs) {
+    match prune_db(&args) {
+        Ok(stats) => println!("{stats}"),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prune the signer db (and compact it, unless disabled), and return the prune stats as JSON
+fn prune_db(args: &PruneDbArgs) -> Result<String, String> {
+    let mut signer_db = SignerDb::new(&args.db_path)
+        .map_err(|e| format!("Failed to open signer db {}: {e:?}", args.db_path.display()))?;
+    let stats = signer_db
+        .prune(args.retention())
+        .map_err(|e| format!("Failed to prune signer db: {e:?}"))?;
+    if !args.no_compact {
+        signer_db
+            .compact()
+            .map_err(|e| format!("Failed to compact signer db: {e:?}"))?;
+    }
+    serde_json::to_string_pretty(&stats)
+        .map_err(|e| format!("Failed to serialize prune stats: {e}"))
+}
+
+fn handle_export_audit(args: ExportAuditArgs) {
+    let signer_db = SignerDb::new(&args.db_path)
+        .unwrap_or_else(|e| panic!("Failed to open signer db {}: {e:?}", args.db_path.display()));
+    let records = signer_db
+        .get_block_audit_records(args.start_burn_height, args.end_burn_height)
+        .expect("Failed to read audit records from signer db");
+    write_audit_records(&records, args.format, std::io::stdout().lock())
+        .expect("Failed to write audit records");
+}
+
+fn handle_simulate_tenure_extend(args: SimulateTenureExtendArgs) {
+    let histories: Vec<_> = args
+        .db_paths
+        .iter()
+        .map(|db_path| {
+            let signer_db = SignerDb::new(db_path).unwrap_or_else(|e| {
+                panic!("Failed to open signer db {}: {e:?}", db_path.display())
+            });
+            SignerHistory::load(
+                db_path.display().to_string(),
+                &signer_db,
+                args.start_burn_height,
+                args.end_burn_height,
+            )
+            .expect("Failed to read blocks from signer db")
+        })
+        .collect();
+    let miner_attempts = args
+        .miner_log
+        .as_ref()
+        .map(|miner_log| {
+            let file = File::open(miner_log).unwrap_or_else(|e| {
+                panic!("Failed to open miner log {}: {e:?}", miner_log.display())
+            });
+            parse_miner_extend_log(BufReader::new(file)).expect("Failed to read miner log")
+        })
+        .unwrap_or_default();
+    for timeouts in args.timeouts() {
+        let replays = replay_tenure_extends(&histories, &miner_attempts, &timeouts);
+        write_replay_report(&timeouts, &replays, std::io::stdout().lock())
+            .expect("Failed to write tenure extend report");
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -236,6 +320,15 @@ fn main() {
         Command::MonitorSigners(args) => {
             handle_monitor_signers(args);
         }
+        Command::PruneDb(args) => {
+            handle_prune_db(args);
+        }
+        Command::ExportAudit(args) => {
+            handle_export_audit(args);
+        }
+        Command::SimulateTenureExtend(args) => {
+            handle_simulate_tenure_extend(args);
+        }
     }
 }
 
@@ -246,6 +339,7 @@ pub mod tests {
     use blockstack_lib::util_lib::signed_structured_data::pox4::{
         make_pox_4_signer_key_message_hash, Pox4SignatureTopic,
     };
+    use clarity::types::chainstate::StacksPublicKey;
     use clarity::util::secp256k1::Secp256k1PrivateKey;
     use clarity::vm::{execute_v2, Value};
     use rand::{Rng, RngCore};
@@ -310,7 +404,7 @@ pub mod tests {
         };
 
         let signature = handle_generate_stacking_signature(args.clone(), false);
-        let public_key = Secp256k1PublicKey::from_private(&config.stacks_private_key);
+        let public_key = config.signing_key.public_key();
 
         let valid = call_verify_signer_sig(
             &args.pox_address,
@@ -333,7 +427,7 @@ pub mod tests {
         args.max_amount = 100;
 
         let signature = handle_generate_stacking_signature(args.clone(), false);
-        let public_key = Secp256k1PublicKey::from_private(&config.stacks_private_key);
+        let public_key = config.signing_key.public_key();
 
         let valid = call_verify_signer_sig(
             &args.pox_address,
@@ -366,7 +460,7 @@ pub mod tests {
 
         let signature = handle_generate_stacking_signature(args.clone(), false);
 
-        let public_key = Secp256k1PublicKey::from_private(&config.stacks_private_key);
+        let public_key = config.signing_key.public_key();
 
         let message_hash = make_pox_4_signer_key_message_hash(
             &args.pox_address,
@@ -392,8 +486,7 @@ pub mod tests {
         };
         let config_file = "./src/tests/conf/signer-0.toml";
         let config = GlobalConfig::load_from_file(config_file).unwrap();
-        let private_key = config.stacks_private_key;
-        let public_key = StacksPublicKey::from_private(&private_key);
+        let public_key = config.signing_key.public_key();
         let args = GenerateVoteArgs {
             config: config_file.into(),
             vote_info,
@@ -14,12 +14,15 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::IsTerminal;
 
 use clarity::codec::read_next;
 use clarity::types::chainstate::{StacksAddress, StacksPrivateKey, StacksPublicKey};
 use clarity::types::StacksEpochId;
 use clarity::util::sleep_ms;
-use libsigner::v0::messages::{MessageSlotID, SignerMessage};
+use libsigner::v0::messages::{MessageSlotID, SignerMessage, StateMachineUpdate};
+use libsigner::v0::signer_state::{GlobalStateEvaluator, MinerState, StateDivergence};
 use libsigner::SignerSession;
 use stacks_common::{info, warn};
 
@@ -218,8 +221,71 @@ impl SignerMonitor {
         );
     }
 
+    /// Show a live table of each signer's latest state machine view and how it diverges
+    /// from the signer set's majority view, refreshed every polling interval
+    fn monitor_state_divergence(&mut self) -> Result<(), ClientError> {
+        let interval_ms = self.args.interval * 1000;
+        let mut session = None;
+        loop {
+            let reward_cycle_changed = self.refresh_state()?;
+            let reward_cycle = self
+                .cycle_state
+                .reward_cycle
+                .expect("BUG: reward cycle not set");
+            if reward_cycle_changed || session.is_none() {
+                let contract = MessageSlotID::StateMachineUpdate
+                    .stacker_db_contract(self.stacks_client.mainnet, reward_cycle);
+                info!("Monitoring signer state machine updates in StackerDB contract {contract}");
+                session = Some(stackerdb_session(&self.args.host, contract));
+            }
+            let session = session.as_mut().expect("BUG: session not set");
+
+            let mut signers = vec![];
+            let mut address_weights = HashMap::new();
+            let mut address_updates = HashMap::new();
+            let chunks = session.get_latest_chunks(&self.cycle_state.slot_ids)?;
+            for (chunk_opt, slot_id) in chunks.into_iter().zip(&self.cycle_state.slot_ids) {
+                let Some(address) = self
+                    .cycle_state
+                    .signers_addresses
+                    .get(&SignerSlotID(*slot_id))
+                    .copied()
+                else {
+                    continue;
+                };
+                if signers.contains(&address) {
+                    continue;
+                }
+                signers.push(address);
+                let weight = self
+                    .cycle_state
+                    .signers_weights
+                    .get(&address)
+                    .copied()
+                    .unwrap_or(0);
+                address_weights.insert(address, weight);
+                let update =
+                    chunk_opt.and_then(|data| read_next::<SignerMessage, _>(&mut &data[..]).ok());
+                if let Some(SignerMessage::StateMachineUpdate(update)) = update {
+                    address_updates.insert(address, update);
+                }
+            }
+            let evaluator = GlobalStateEvaluator::new(address_updates, address_weights);
+            let table = format_divergence_table(&evaluator, &signers, reward_cycle);
+            if std::io::stdout().is_terminal() {
+                // Clear the screen so that the table is redrawn in place
+                print!("\x1b[2J\x1b[H");
+            }
+            println!("{table}");
+            sleep_ms(interval_ms);
+        }
+    }
+
     /// Start monitoring the signers stackerdb slots for expected new messages
     pub fn start(&mut self) -> Result<(), ClientError> {
+        if self.args.state_divergence {
+            return self.monitor_state_divergence();
+        }
         self.refresh_state()?;
         let nmb_signers = self.cycle_state.signers_keys.len();
         let interval_ms = self.args.interval * 1000;
@@ -328,3 +394,135 @@ impl SignerMonitor {
         }
     }
 }
+
+/// Abbreviate a hex string to its first few characters
+fn short_hex(hex: &str) -> &str {
+    hex.get(..8).unwrap_or(hex)
+}
+
+/// Describe the active miner in a state machine view
+fn describe_miner(miner: &MinerState) -> String {
+    match miner {
+        MinerState::ActiveMiner {
+            current_miner_pkh,
+            tenure_id,
+            ..
+        } => format!(
+            "{} (tenure {})",
+            short_hex(&current_miner_pkh.to_hex()),
+            short_hex(&tenure_id.to_hex())
+        ),
+        MinerState::NoValidMiner => "none".to_string(),
+    }
+}
+
+/// Render each signer's latest state machine view, the signer set's majority view, and how
+/// much weight diverges from it, as a table
+fn format_divergence_table(
+    evaluator: &GlobalStateEvaluator,
+    signers: &[StacksAddress],
+    reward_cycle: u64,
+) -> String {
+    let majority_view = evaluator.determine_majority_view();
+    let mut table = String::new();
+    let _ = writeln!(
+        table,
+        "Signer state machine views in reward cycle {reward_cycle}: {} signer(s), total weight {}",
+        signers.len(),
+        evaluator.total_weight
+    );
+    let majority_burn_block = majority_view
+        .burn_block
+        .map(|(burn_block, height)| format!("{height} ({})", short_hex(&burn_block.to_hex())))
+        .unwrap_or_else(|| "no majority".to_string());
+    let majority_miner = majority_view
+        .current_miner
+        .as_ref()
+        .map(describe_miner)
+        .unwrap_or_else(|| "no majority".to_string());
+    let majority_replay_set = majority_view
+        .tx_replay_set
+        .as_ref()
+        .map(|replay_set| {
+            format!(
+                "{} tx(s)",
+                replay_set.clone_as_optional().unwrap_or_default().len()
+            )
+        })
+        .unwrap_or_else(|| "no majority".to_string());
+    let _ = writeln!(
+        table,
+        "Majority view: burn tip {majority_burn_block}, miner {majority_miner}, replay set {majority_replay_set}"
+    );
+    let _ = writeln!(
+        table,
+        "{:<41} {:>6} {:>11} {:<10} {:<30} {:>10}  DIVERGENCE",
+        "SIGNER", "WEIGHT", "BURN HEIGHT", "BURN TIP", "MINER", "REPLAY TXS"
+    );
+    let mut divergent_weights: HashMap<StateDivergence, u32> = HashMap::new();
+    let mut sorted_signers = signers.to_vec();
+    sorted_signers.sort_by_key(|address| address.to_string());
+    for address in sorted_signers {
+        let weight = evaluator
+            .address_weights
+            .get(&address)
+            .copied()
+            .unwrap_or(0);
+        let Some(update) = evaluator.address_updates.get(&address) else {
+            let _ = writeln!(
+                table,
+                "{:<41} {weight:>6} {:>11} {:<10} {:<30} {:>10}  no update",
+                address.to_string(),
+                "-",
+                "-",
+                "-",
+                "-"
+            );
+            continue;
+        };
+        let divergences = majority_view.update_divergences(update);
+        for divergence in divergences.iter() {
+            let entry = divergent_weights.entry(*divergence).or_insert(0);
+            *entry = entry.saturating_add(weight);
+        }
+        let _ = writeln!(
+            table,
+            "{:<41} {weight:>6} {:>11} {:<10} {:<30} {:>10}  {}",
+            address.to_string(),
+            update.content.burn_block_view().1,
+            short_hex(&update.content.burn_block_view().0.to_hex()),
+            describe_miner(&update.content.current_miner().into()),
+            update_replay_set_len(update),
+            if divergences.is_empty() {
+                "-".to_string()
+            } else {
+                divergences
+                    .iter()
+                    .map(|divergence| divergence.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }
+        );
+    }
+    let summary = StateDivergence::ALL
+        .iter()
+        .map(|kind| {
+            format!(
+                "{kind}: {}",
+                divergent_weights.get(kind).copied().unwrap_or(0)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let _ = write!(table, "Divergent weight: {summary}");
+    table
+}
+
+/// The number of transactions in a state machine update's replay set
+fn update_replay_set_len(update: &StateMachineUpdate) -> usize {
+    update
+        .content
+        .tx_replay_set()
+        .clone_as_optional()
+        .map_or(0, |txs| txs.len())
+}
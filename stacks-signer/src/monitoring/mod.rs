@@ -55,7 +55,9 @@ SignerAgreementStateConflict {
 pub mod actions {
     use ::prometheus::HistogramTimer;
     use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+    use libsigner::v0::signer_state::{DivergentSigner, StateDivergence};
     use stacks_common::error;
+    use stacks_common::types::chainstate::StacksAddress;
 
     use crate::config::GlobalConfig;
     use crate::monitoring::prometheus::*;
@@ -73,32 +75,40 @@ pub mod actions {
     }
 
     /// Increment the block validation responses counter
-    pub fn increment_block_validation_responses(accepted: bool) {
+    pub fn increment_block_validation_responses(signer: &StacksAddress, accepted: bool) {
         let label_value = if accepted { "accepted" } else { "rejected" };
         BLOCK_VALIDATION_RESPONSES
-            .with_label_values(&[label_value])
+            .with_label_values(&[&signer.to_string(), label_value])
             .inc();
     }
 
     /// Increment the block responses sent counter
-    pub fn increment_block_responses_sent(accepted: bool) {
+    pub fn increment_block_responses_sent(signer: &StacksAddress, accepted: bool) {
         let label_value = if accepted { "accepted" } else { "rejected" };
-        BLOCK_RESPONSES_SENT.with_label_values(&[label_value]).inc();
+        BLOCK_RESPONSES_SENT
+            .with_label_values(&[&signer.to_string(), label_value])
+            .inc();
     }
 
     /// Increment the number of block proposals received
-    pub fn increment_block_proposals_received() {
-        BLOCK_PROPOSALS_RECEIVED.inc();
+    pub fn increment_block_proposals_received(signer: &StacksAddress) {
+        BLOCK_PROPOSALS_RECEIVED
+            .with_label_values(&[&signer.to_string()])
+            .inc();
     }
 
     /// Update the stx balance of the signer
-    pub fn update_signer_stx_balance(balance: i64) {
-        SIGNER_STX_BALANCE.set(balance);
+    pub fn update_signer_stx_balance(signer: &StacksAddress, balance: i64) {
+        SIGNER_STX_BALANCE
+            .with_label_values(&[&signer.to_string()])
+            .set(balance);
     }
 
     /// Update the signer nonce metric
-    pub fn update_signer_nonce(nonce: u64) {
-        SIGNER_NONCE.set(nonce as i64);
+    pub fn update_signer_nonce(signer: &StacksAddress, nonce: u64) {
+        SIGNER_NONCE
+            .with_label_values(&[&signer.to_string()])
+            .set(nonce as i64);
     }
 
     /// Start a new RPC call timer.
@@ -164,6 +174,32 @@ pub mod actions {
             .observe(latency_s as f64);
     }
 
+    /// Record how the signer's local state machine, and the latest updates of the rest of
+    /// the signer set, diverge from the signer set's majority view
+    pub fn record_state_divergence(
+        signer: &StacksAddress,
+        reward_cycle: u64,
+        local_divergences: &[StateDivergence],
+        divergent_signers: &[DivergentSigner],
+    ) {
+        let signer = signer.to_string();
+        let reward_cycle = reward_cycle.to_string();
+        for kind in StateDivergence::ALL {
+            let diverges = local_divergences.contains(&kind);
+            SIGNER_STATE_DIVERGENCE
+                .with_label_values(&[&signer, &reward_cycle, kind.as_str()])
+                .set(i64::from(diverges));
+            let divergent_weight: u32 = divergent_signers
+                .iter()
+                .filter(|divergent| divergent.divergences.contains(&kind))
+                .map(|divergent| divergent.weight)
+                .sum();
+            SIGNER_SET_DIVERGENT_WEIGHT
+                .with_label_values(&[&signer, &reward_cycle, kind.as_str()])
+                .set(i64::from(divergent_weight));
+        }
+    }
+
     /// Start serving monitoring metrics.
     /// This will only serve the metrics if the `monitoring_prom` feature is enabled.
     pub fn start_serving_monitoring_metrics(config: GlobalConfig) -> Result<(), String> {
@@ -185,7 +221,9 @@ pub mod actions {
 #[cfg(not(feature = "monitoring_prom"))]
 pub mod actions {
     use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+    use libsigner::v0::signer_state::{DivergentSigner, StateDivergence};
     use stacks_common::info;
+    use stacks_common::types::chainstate::StacksAddress;
 
     use crate::monitoring::{SignerAgreementStateChangeReason, SignerAgreementStateConflict};
     use crate::v0::signer_state::LocalStateMachine;
@@ -198,19 +236,19 @@ pub mod actions {
     pub fn update_reward_cycle(_reward_cycle: i64) {}
 
     /// Increment the block validation responses counter
-    pub fn increment_block_validation_responses(_accepted: bool) {}
+    pub fn increment_block_validation_responses(_signer: &StacksAddress, _accepted: bool) {}
 
     /// Increment the block responses sent counter
-    pub fn increment_block_responses_sent(_accepted: bool) {}
+    pub fn increment_block_responses_sent(_signer: &StacksAddress, _accepted: bool) {}
 
     /// Increment the number of block proposals received
-    pub fn increment_block_proposals_received() {}
+    pub fn increment_block_proposals_received(_signer: &StacksAddress) {}
 
     /// Update the stx balance of the signer
-    pub fn update_signer_stx_balance(_balance: i64) {}
+    pub fn update_signer_stx_balance(_signer: &StacksAddress, _balance: i64) {}
 
     /// Update the signer nonce metric
-    pub fn update_signer_nonce(_nonce: u64) {}
+    pub fn update_signer_nonce(_signer: &StacksAddress, _nonce: u64) {}
 
     /// NoOp timer uses for monitoring when the monitoring feature is not enabled.
     pub struct NoOpTimer;
@@ -248,6 +286,16 @@ pub mod actions {
     /// Record the time (seconds) taken for a signer to agree with the signer set
     pub fn record_signer_agreement_capitulation_latency(_latency_s: u64) {}
 
+    /// Record how the signer's local state machine, and the latest updates of the rest of
+    /// the signer set, diverge from the signer set's majority view
+    pub fn record_state_divergence(
+        _signer: &StacksAddress,
+        _reward_cycle: u64,
+        _local_divergences: &[StateDivergence],
+        _divergent_signers: &[DivergentSigner],
+    ) {
+    }
+
     /// Start serving monitoring metrics.
     /// This will only serve the metrics if the `monitoring_prom` feature is enabled.
     pub fn start_serving_monitoring_metrics(config: GlobalConfig) -> Result<(), String> {
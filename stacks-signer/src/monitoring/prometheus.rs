@@ -18,9 +18,9 @@ use std::sync::Mutex;
 
 use lazy_static::lazy_static;
 use prometheus::{
-    gather, histogram_opts, opts, register_histogram_vec, register_int_counter,
-    register_int_counter_vec, register_int_gauge, Encoder, HistogramVec, IntCounter, IntCounterVec,
-    IntGauge, TextEncoder,
+    gather, histogram_opts, opts, register_histogram_vec, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, Encoder, HistogramVec, IntCounterVec, IntGauge,
+    IntGaugeVec, TextEncoder,
 };
 
 use crate::v0::signer_state::LocalStateMachine;
@@ -34,32 +34,33 @@ lazy_static! {
     pub static ref BLOCK_VALIDATION_RESPONSES: IntCounterVec = register_int_counter_vec!(
         "stacks_signer_block_validation_responses",
         "The number of block validation responses. `response_type` is either 'accepted' or 'rejected'",
-        &["response_type"]
+        &["signer", "response_type"]
     )
     .unwrap();
     pub static ref BLOCK_RESPONSES_SENT: IntCounterVec = register_int_counter_vec!(
         "stacks_signer_block_responses_sent",
         "The number of block responses sent. `response_type` is either 'accepted' or 'rejected'",
-        &["response_type"]
+        &["signer", "response_type"]
     )
     .unwrap();
-    pub static ref BLOCK_PROPOSALS_RECEIVED: IntCounter = register_int_counter!(opts!(
+    pub static ref BLOCK_PROPOSALS_RECEIVED: IntCounterVec = register_int_counter_vec!(
         "stacks_signer_block_proposals_received",
-        "The number of block proposals received by the signer"
-    ))
+        "The number of block proposals received by the signer",
+        &["signer"]
+    )
     .unwrap();
     pub static ref CURRENT_REWARD_CYCLE: IntGauge = register_int_gauge!(opts!(
         "stacks_signer_current_reward_cycle",
         "The current reward cycle"
     )).unwrap();
-    pub static ref SIGNER_STX_BALANCE: IntGauge = register_int_gauge!(opts!(
+    pub static ref SIGNER_STX_BALANCE: IntGaugeVec = register_int_gauge_vec!(opts!(
         "stacks_signer_stx_balance",
         "The current STX balance of the signer"
-    )).unwrap();
-    pub static ref SIGNER_NONCE: IntGauge = register_int_gauge!(opts!(
+    ), &["signer"]).unwrap();
+    pub static ref SIGNER_NONCE: IntGaugeVec = register_int_gauge_vec!(opts!(
         "stacks_signer_nonce",
         "The current nonce of the signer"
-    )).unwrap();
+    ), &["signer"]).unwrap();
 
     pub static ref SIGNER_RPC_CALL_LATENCIES_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
         "stacks_signer_node_rpc_call_latencies_histogram",
@@ -97,6 +98,16 @@ lazy_static! {
         vec![0.0, 1.0, 3.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0]
     ), &[]).unwrap();
 
+    pub static ref SIGNER_STATE_DIVERGENCE: IntGaugeVec = register_int_gauge_vec!(opts!(
+        "stacks_signer_state_divergence",
+        "Whether the signer's local state machine diverges (1) or not (0) from the view held by a majority of the signer set's weight. `kind` can be one of: 'burn_tip', 'active_miner', 'tx_replay_set'"
+    ), &["signer", "reward_cycle", "kind"]).unwrap();
+
+    pub static ref SIGNER_SET_DIVERGENT_WEIGHT: IntGaugeVec = register_int_gauge_vec!(opts!(
+        "stacks_signer_set_divergent_weight",
+        "The signer weight whose latest state machine update diverges from the view held by a majority of the signer set's weight. `kind` can be one of: 'burn_tip', 'active_miner', 'tx_replay_set'"
+    ), &["signer", "reward_cycle", "kind"]).unwrap();
+
     pub static ref SIGNER_LOCAL_STATE_MACHINE: Mutex<Option<LocalStateMachine>> = Mutex::new(None);
 }
 
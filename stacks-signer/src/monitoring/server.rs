@@ -17,13 +17,15 @@
 use std::net::SocketAddr;
 use std::time::Instant;
 
+use clarity::types::chainstate::StacksAddress;
 use clarity::util::hash::to_hex;
 use clarity::util::secp256k1::Secp256k1PublicKey;
 use libsigner::VERSION_STRING;
 use stacks_common::{debug, error, info, warn};
-use tiny_http::{Response as HttpResponse, Server as HttpServer};
+use tiny_http::{Header as HttpHeader, Response as HttpResponse, Server as HttpServer};
 
 use super::actions::{update_reward_cycle, update_signer_stx_balance};
+use crate::admin::AdminApi;
 use crate::client::{ClientError, StacksClient};
 use crate::config::{GlobalConfig, Network};
 use crate::monitoring::actions::{update_signer_nonce, update_stacks_tip_height};
@@ -57,16 +59,22 @@ pub struct MonitoringServer {
     public_key: Secp256k1PublicKey,
     stacks_node_client: reqwest::blocking::Client,
     stacks_node_origin: String,
+    admin_api: Option<AdminApi>,
+    signer_addresses: Vec<StacksAddress>,
 }
 
 impl MonitoringServer {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         http_server: HttpServer,
         local_addr: SocketAddr,
         stacks_client: StacksClient,
         network: Network,
         public_key: Secp256k1PublicKey,
+        stacks_node_client: reqwest::blocking::Client,
         stacks_node_origin: String,
+        admin_api: Option<AdminApi>,
+        signer_addresses: Vec<StacksAddress>,
     ) -> Self {
         Self {
             http_server,
@@ -75,8 +83,10 @@ impl MonitoringServer {
             last_metrics_poll: Instant::now(),
             network,
             public_key,
-            stacks_node_client: reqwest::blocking::Client::new(),
+            stacks_node_client,
             stacks_node_origin,
+            admin_api,
+            signer_addresses,
         }
     }
 
@@ -87,14 +97,35 @@ impl MonitoringServer {
         };
         let stacks_client = StacksClient::from(config);
         let http_server = HttpServer::http(endpoint).map_err(|_| MonitoringError::AlreadyBound)?;
-        let public_key = Secp256k1PublicKey::from_private(&config.stacks_private_key);
+        let public_key = config.signing_key.public_key();
+        let admin_api = config.admin_auth_token.as_ref().map(|auth_token| {
+            let identities: Vec<_> = config
+                .identities()
+                .into_iter()
+                .map(|identity| (identity.stacks_address, identity.db_path))
+                .collect();
+            if identities
+                .iter()
+                .any(|(_, db_path)| db_path.to_str() == Some(":memory:"))
+            {
+                warn!("Monitoring: the admin API cannot read an in-memory signer db");
+            }
+            AdminApi::new(auth_token.clone(), identities, config.admin_state.clone())
+        });
         let mut server = MonitoringServer::new(
             http_server,
             endpoint,
             stacks_client,
             config.network.clone(),
             public_key,
-            format!("http://{}", config.node_host),
+            config.node_http_client(),
+            config.node_origin(),
+            admin_api,
+            config
+                .identities()
+                .iter()
+                .map(|identity| identity.stacks_address)
+                .collect(),
         );
         if let Err(e) = server.update_metrics() {
             warn!(
@@ -163,6 +194,34 @@ impl MonitoringServer {
                 continue;
             }
 
+            if let Some(admin_api) = self
+                .admin_api
+                .as_ref()
+                .filter(|_| AdminApi::is_admin_url(request.url()))
+            {
+                let authorization = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Authorization"))
+                    .map(|header| header.value.to_string());
+                let response = admin_api.handle(
+                    request.method().as_str(),
+                    request.url(),
+                    authorization.as_deref(),
+                );
+                let content_type =
+                    HttpHeader::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+                        .expect("Failed to build header");
+                request
+                    .respond(
+                        HttpResponse::from_string(response.body)
+                            .with_status_code(response.status)
+                            .with_header(content_type),
+                    )
+                    .expect("Failed to respond to request");
+                continue;
+            }
+
             // unknown request, return 404
             request
                 .respond(HttpResponse::from_string("Not Found").with_status_code(404))
@@ -196,16 +255,17 @@ impl MonitoringServer {
         if let Ok(reward_cycle) = i64::try_from(pox_info.reward_cycle_id) {
             update_reward_cycle(reward_cycle);
         }
-        let signer_stx_addr = self.stacks_client.get_signer_address();
-        let account_entry = self.stacks_client.get_account_entry(signer_stx_addr)?;
-        let balance = i64::from_str_radix(&account_entry.balance[2..], 16).map_err(|e| {
-            MonitoringError::FetchError(ClientError::MalformedClarityValue(format!(
-                "Failed to parse balance: {} with err: {}",
-                &account_entry.balance, e,
-            )))
-        })?;
-        update_signer_nonce(account_entry.nonce);
-        update_signer_stx_balance(balance);
+        for signer_stx_addr in &self.signer_addresses {
+            let account_entry = self.stacks_client.get_account_entry(signer_stx_addr)?;
+            let balance = i64::from_str_radix(&account_entry.balance[2..], 16).map_err(|e| {
+                MonitoringError::FetchError(ClientError::MalformedClarityValue(format!(
+                    "Failed to parse balance: {} with err: {}",
+                    &account_entry.balance, e,
+                )))
+            })?;
+            update_signer_nonce(signer_stx_addr, account_entry.nonce);
+            update_signer_stx_balance(signer_stx_addr, balance);
+        }
         Ok(())
     }
 
@@ -216,6 +276,11 @@ impl MonitoringServer {
             "signerPublicKey": to_hex(&self.public_key.to_bytes_compressed()),
             "network": self.network.to_string(),
             "stxAddress": self.stacks_client.get_signer_address().to_string(),
+            "stxAddresses": self
+                .signer_addresses
+                .iter()
+                .map(|address| address.to_string())
+                .collect::<Vec<_>>(),
             "version": VERSION_STRING.to_string(),
         }))
         .expect("Failed to serialize JSON")
@@ -0,0 +1,540 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Operator-defined block acceptance rules.
+//!
+//! On top of the signer's built-in checks, an operator can list rules in the signer config
+//! which every block proposal must satisfy. Each rule is a `[[block_policy]]` table whose
+//! `rule` key picks the kind of rule:
+//!
+//! ```toml
+//! [[block_policy]]
+//! rule = "max_block_cost"
+//! max_percentage = 80
+//!
+//! [[block_policy]]
+//! rule = "min_transactions_after_idle"
+//! idle_secs = 60
+//! min_transactions = 1
+//!
+//! [[block_policy]]
+//! rule = "blocked_contract_call"
+//! contract = "SP000000000000000000002Q6VF78.pox-4"
+//! function = "stack-stx"
+//!
+//! [[block_policy]]
+//! rule = "tenure_extend_interval"
+//! min_interval_secs = 300
+//! ```
+//!
+//! A block which breaks a rule is rejected with a [RejectReason] specific to that rule.
+//! All rules except `max_block_cost` are evaluated when the proposal arrives, before it is
+//! submitted to the stacks-node for validation. The block's cost is only known once the
+//! node has validated it, so `max_block_cost` is evaluated against the validation result.
+
+use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+use blockstack_lib::chainstate::stacks::TransactionPayload;
+use clarity::types::chainstate::ConsensusHash;
+use clarity::vm::costs::ExecutionCost;
+use clarity::vm::types::QualifiedContractIdentifier;
+use libsigner::v0::messages::RejectReason;
+use serde::{Deserialize, Deserializer};
+use stacks_common::warn;
+
+use crate::signerdb::{BlockInfo, SignerDb};
+
+/// A single operator-defined rule that block proposals must satisfy
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BlockPolicyRule {
+    /// Reject blocks whose cost, in any dimension, exceeds a percentage of the block limit.
+    /// The block limit is that of the block's epoch, as configured on the stacks-node.
+    MaxBlockCost {
+        /// The largest share of the block limit (from 1 to 100) that a block may use
+        max_percentage: u64,
+    },
+    /// Reject blocks which follow an idle period but carry too few transactions. The idle
+    /// period is measured from the timestamp of the last accepted block in the tenure.
+    MinTransactionsAfterIdle {
+        /// How long (in secs) since the last block in the tenure counts as idle
+        idle_secs: u64,
+        /// How many transactions (not counting tenure changes and coinbases) a block must
+        /// carry after an idle period
+        min_transactions: u64,
+    },
+    /// Reject blocks which call a contract, or a single function of a contract
+    BlockedContractCall {
+        /// The contract which may not be called
+        #[serde(deserialize_with = "deserialize_contract_id")]
+        contract: QualifiedContractIdentifier,
+        /// If set, only calls to this function of the contract are blocked
+        function: Option<String>,
+    },
+    /// Reject tenure extends which come too soon after the start of the tenure or its
+    /// previous extend
+    TenureExtendInterval {
+        /// How long (in secs) must pass between tenure changes and extends in a tenure
+        min_interval_secs: u64,
+    },
+}
+
+fn deserialize_contract_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<QualifiedContractIdentifier, D::Error> {
+    let contract = String::deserialize(deserializer)?;
+    QualifiedContractIdentifier::parse(&contract).map_err(serde::de::Error::custom)
+}
+
+/// The set of operator-defined rules that the signer applies to block proposals
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlockPolicy {
+    rules: Vec<BlockPolicyRule>,
+}
+
+impl BlockPolicy {
+    /// Build a policy from its rules, checking that each rule is well-formed
+    pub fn new(rules: Vec<BlockPolicyRule>) -> Result<Self, String> {
+        for rule in &rules {
+            match rule {
+                BlockPolicyRule::MaxBlockCost { max_percentage } => {
+                    if !(1..=100).contains(max_percentage) {
+                        return Err(format!(
+                            "max_block_cost: max_percentage must be between 1 and 100, got {max_percentage}"
+                        ));
+                    }
+                }
+                BlockPolicyRule::MinTransactionsAfterIdle { idle_secs, .. } => {
+                    if *idle_secs == 0 {
+                        return Err(
+                            "min_transactions_after_idle: idle_secs must be greater than 0".into(),
+                        );
+                    }
+                }
+                BlockPolicyRule::BlockedContractCall { .. }
+                | BlockPolicyRule::TenureExtendInterval { .. } => {}
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether no rules are configured
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The configured rules
+    pub fn rules(&self) -> &[BlockPolicyRule] {
+        &self.rules
+    }
+
+    /// Check a block proposal against every rule which can be evaluated before validation.
+    /// Returns the reason for the first rule that the block breaks.
+    pub fn check_proposal(
+        &self,
+        block: &NakamotoBlock,
+        signer_db: &SignerDb,
+    ) -> Result<(), RejectReason> {
+        for rule in &self.rules {
+            match rule {
+                BlockPolicyRule::MaxBlockCost { .. } => {}
+                BlockPolicyRule::MinTransactionsAfterIdle {
+                    idle_secs,
+                    min_transactions,
+                } => {
+                    let Some(last_block) = last_block_in_tenure(signer_db, block, |db, ch| {
+                        db.get_last_accepted_block(ch)
+                    })?
+                    else {
+                        continue;
+                    };
+                    let idle_until = last_block.block.header.timestamp.saturating_add(*idle_secs);
+                    let num_transactions = block
+                        .txs
+                        .iter()
+                        .filter(|tx| {
+                            !matches!(
+                                tx.payload,
+                                TransactionPayload::TenureChange(_)
+                                    | TransactionPayload::Coinbase(..)
+                            )
+                        })
+                        .count() as u64;
+                    if block.header.timestamp >= idle_until && num_transactions < *min_transactions
+                    {
+                        warn!("Block proposal follows an idle period but has too few transactions";
+                            "signer_signature_hash" => %block.header.signer_signature_hash(),
+                            "num_transactions" => num_transactions,
+                            "min_transactions" => min_transactions,
+                            "last_block_timestamp" => last_block.block.header.timestamp,
+                            "block_timestamp" => block.header.timestamp,
+                        );
+                        return Err(RejectReason::TooFewTransactionsAfterIdle);
+                    }
+                }
+                BlockPolicyRule::BlockedContractCall { contract, function } => {
+                    let blocked_call = block.txs.iter().find_map(|tx| match &tx.payload {
+                        TransactionPayload::ContractCall(call)
+                            if call.to_clarity_contract_id() == *contract
+                                && function
+                                    .as_ref()
+                                    .is_none_or(|f| f.as_str() == call.function_name.as_str()) =>
+                        {
+                            Some((tx.txid(), call.function_name.clone()))
+                        }
+                        _ => None,
+                    });
+                    if let Some((txid, function_name)) = blocked_call {
+                        warn!("Block proposal contains a blocked contract call";
+                            "signer_signature_hash" => %block.header.signer_signature_hash(),
+                            "txid" => %txid,
+                            "contract" => %contract,
+                            "function" => %function_name,
+                        );
+                        return Err(RejectReason::BlockedContractCall);
+                    }
+                }
+                BlockPolicyRule::TenureExtendInterval { min_interval_secs } => {
+                    if block.get_tenure_extend_tx_payload().is_none() {
+                        continue;
+                    }
+                    let Some(last_change) = last_block_in_tenure(signer_db, block, |db, ch| {
+                        db.get_last_tenure_change_block(ch)
+                    })?
+                    else {
+                        continue;
+                    };
+                    let allowed_at = last_change
+                        .block
+                        .header
+                        .timestamp
+                        .saturating_add(*min_interval_secs);
+                    if block.header.timestamp < allowed_at {
+                        warn!("Block proposal extends the tenure too soon after its last tenure change";
+                            "signer_signature_hash" => %block.header.signer_signature_hash(),
+                            "last_tenure_change_timestamp" => last_change.block.header.timestamp,
+                            "block_timestamp" => block.header.timestamp,
+                            "min_interval_secs" => min_interval_secs,
+                        );
+                        return Err(RejectReason::TenureExtendTooFrequent);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Does the policy limit the cost of blocks?  If not, `check_block_cost` always passes.
+    pub fn limits_block_cost(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| matches!(rule, BlockPolicyRule::MaxBlockCost { .. }))
+    }
+
+    /// Check a validated block's cost against the policy.
+    /// `block_limit` is the block limit of the block's network and epoch.
+    pub fn check_block_cost(
+        &self,
+        block: &NakamotoBlock,
+        cost: &ExecutionCost,
+        block_limit: &ExecutionCost,
+    ) -> Result<(), RejectReason> {
+        for rule in &self.rules {
+            let BlockPolicyRule::MaxBlockCost { max_percentage } = rule else {
+                continue;
+            };
+            let cost_percentage = block_limit.proportion_largest_dimension(cost);
+            if cost_percentage > *max_percentage {
+                warn!("Block proposal uses too much of the block limit";
+                    "signer_signature_hash" => %block.header.signer_signature_hash(),
+                    "cost_percentage" => cost_percentage,
+                    "max_percentage" => max_percentage,
+                    "cost" => %cost,
+                );
+                return Err(RejectReason::BlockCostLimitExceeded);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Look up a block in the proposed block's tenure, treating a database failure as a rejection
+/// so that the rule is never silently skipped.
+fn last_block_in_tenure<F>(
+    signer_db: &SignerDb,
+    block: &NakamotoBlock,
+    lookup: F,
+) -> Result<Option<BlockInfo>, RejectReason>
+where
+    F: FnOnce(
+        &SignerDb,
+        &ConsensusHash,
+    ) -> Result<Option<BlockInfo>, blockstack_lib::util_lib::db::Error>,
+{
+    lookup(signer_db, &block.header.consensus_hash).map_err(|e| {
+        warn!("Failed to check block proposal against the block policy: {e:?}";
+            "signer_signature_hash" => %block.header.signer_signature_hash(),
+        );
+        RejectReason::ConnectivityIssues("failed to read tenure from signer db".to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+    use blockstack_lib::chainstate::stacks::{
+        StacksTransaction, TenureChangeCause, TenureChangePayload, TokenTransferMemo,
+        TransactionAuth, TransactionVersion,
+    };
+    use blockstack_lib::core::{BLOCK_LIMIT_MAINNET_21, HELIUM_BLOCK_LIMIT_20};
+    use clarity::types::chainstate::{StacksAddress, StacksBlockId, StacksPrivateKey};
+    use clarity::util::hash::Hash160;
+    use clarity::vm::types::PrincipalData;
+    use libsigner::{BlockProposal, BlockProposalData};
+
+    use super::*;
+    use crate::signerdb::BlockState;
+
+    fn make_tx(payload: TransactionPayload) -> StacksTransaction {
+        StacksTransaction::new(
+            TransactionVersion::Testnet,
+            TransactionAuth::from_p2pkh(&StacksPrivateKey::random()).unwrap(),
+            payload,
+        )
+    }
+
+    fn make_transfer_tx() -> StacksTransaction {
+        make_tx(TransactionPayload::TokenTransfer(
+            PrincipalData::from(StacksAddress::burn_address(false)),
+            1,
+            TokenTransferMemo([0; 34]),
+        ))
+    }
+
+    fn make_tenure_change_tx(cause: TenureChangeCause) -> StacksTransaction {
+        make_tx(TransactionPayload::TenureChange(TenureChangePayload {
+            tenure_consensus_hash: ConsensusHash([0x01; 20]),
+            prev_tenure_consensus_hash: ConsensusHash([0x01; 20]),
+            burn_view_consensus_hash: ConsensusHash([0x02; 20]),
+            previous_tenure_end: StacksBlockId([0; 32]),
+            previous_tenure_blocks: 1,
+            cause,
+            pubkey_hash: Hash160([0; 20]),
+        }))
+    }
+
+    fn make_block(timestamp: u64, chain_length: u64, txs: Vec<StacksTransaction>) -> NakamotoBlock {
+        let mut header = NakamotoBlockHeader::empty();
+        header.consensus_hash = ConsensusHash([0x01; 20]);
+        header.timestamp = timestamp;
+        header.chain_length = chain_length;
+        NakamotoBlock { header, txs }
+    }
+
+    fn insert_accepted_block(signer_db: &mut SignerDb, block: NakamotoBlock) {
+        let mut block_info = BlockInfo::from(BlockProposal {
+            block,
+            burn_height: 1,
+            reward_cycle: 1,
+            block_proposal_data: BlockProposalData::empty(),
+        });
+        block_info.state = BlockState::GloballyAccepted;
+        signer_db.insert_block(&block_info).unwrap();
+    }
+
+    #[test]
+    fn blocked_contract_call() {
+        let signer_db = SignerDb::new(":memory:").unwrap();
+        let boot_address = StacksAddress::burn_address(false);
+        let contract =
+            QualifiedContractIdentifier::parse(&format!("{boot_address}.pox-4")).unwrap();
+        let block = make_block(
+            100,
+            1,
+            vec![
+                make_transfer_tx(),
+                make_tx(
+                    TransactionPayload::new_contract_call(
+                        boot_address,
+                        "pox-4",
+                        "stack-stx",
+                        vec![],
+                    )
+                    .unwrap(),
+                ),
+            ],
+        );
+
+        let policy = BlockPolicy::new(vec![BlockPolicyRule::BlockedContractCall {
+            contract: contract.clone(),
+            function: None,
+        }])
+        .unwrap();
+        assert_eq!(
+            policy.check_proposal(&block, &signer_db),
+            Err(RejectReason::BlockedContractCall)
+        );
+
+        let policy = BlockPolicy::new(vec![BlockPolicyRule::BlockedContractCall {
+            contract: contract.clone(),
+            function: Some("stack-stx".into()),
+        }])
+        .unwrap();
+        assert_eq!(
+            policy.check_proposal(&block, &signer_db),
+            Err(RejectReason::BlockedContractCall)
+        );
+
+        // other functions of the contract may still be called
+        let policy = BlockPolicy::new(vec![BlockPolicyRule::BlockedContractCall {
+            contract,
+            function: Some("delegate-stx".into()),
+        }])
+        .unwrap();
+        assert_eq!(policy.check_proposal(&block, &signer_db), Ok(()));
+    }
+
+    #[test]
+    fn min_transactions_after_idle() {
+        let mut signer_db = SignerDb::new(":memory:").unwrap();
+        let policy = BlockPolicy::new(vec![BlockPolicyRule::MinTransactionsAfterIdle {
+            idle_secs: 60,
+            min_transactions: 1,
+        }])
+        .unwrap();
+
+        // the first block in a tenure does not follow an idle period
+        let empty_block = make_block(1000, 2, vec![]);
+        assert_eq!(policy.check_proposal(&empty_block, &signer_db), Ok(()));
+
+        insert_accepted_block(
+            &mut signer_db,
+            make_block(
+                1000,
+                1,
+                vec![make_tenure_change_tx(TenureChangeCause::BlockFound)],
+            ),
+        );
+        let empty_block = make_block(1059, 2, vec![]);
+        assert_eq!(policy.check_proposal(&empty_block, &signer_db), Ok(()));
+        let empty_block = make_block(1060, 2, vec![]);
+        assert_eq!(
+            policy.check_proposal(&empty_block, &signer_db),
+            Err(RejectReason::TooFewTransactionsAfterIdle)
+        );
+        // tenure changes do not count towards the minimum
+        let extend_block = make_block(
+            1060,
+            2,
+            vec![make_tenure_change_tx(TenureChangeCause::Extended)],
+        );
+        assert_eq!(
+            policy.check_proposal(&extend_block, &signer_db),
+            Err(RejectReason::TooFewTransactionsAfterIdle)
+        );
+        let block = make_block(1060, 2, vec![make_transfer_tx()]);
+        assert_eq!(policy.check_proposal(&block, &signer_db), Ok(()));
+    }
+
+    #[test]
+    fn tenure_extend_interval() {
+        let mut signer_db = SignerDb::new(":memory:").unwrap();
+        let policy = BlockPolicy::new(vec![BlockPolicyRule::TenureExtendInterval {
+            min_interval_secs: 300,
+        }])
+        .unwrap();
+        insert_accepted_block(
+            &mut signer_db,
+            make_block(
+                1000,
+                1,
+                vec![make_tenure_change_tx(TenureChangeCause::BlockFound)],
+            ),
+        );
+        insert_accepted_block(
+            &mut signer_db,
+            make_block(1200, 2, vec![make_transfer_tx()]),
+        );
+
+        let extend_block = make_block(
+            1299,
+            3,
+            vec![make_tenure_change_tx(TenureChangeCause::Extended)],
+        );
+        assert_eq!(
+            policy.check_proposal(&extend_block, &signer_db),
+            Err(RejectReason::TenureExtendTooFrequent)
+        );
+        // blocks which do not extend the tenure are unaffected
+        let block = make_block(1299, 3, vec![make_transfer_tx()]);
+        assert_eq!(policy.check_proposal(&block, &signer_db), Ok(()));
+
+        let extend_block = make_block(
+            1300,
+            3,
+            vec![make_tenure_change_tx(TenureChangeCause::Extended)],
+        );
+        assert_eq!(policy.check_proposal(&extend_block, &signer_db), Ok(()));
+
+        // the interval is measured from the most recent extend
+        insert_accepted_block(&mut signer_db, extend_block);
+        let extend_block = make_block(
+            1400,
+            4,
+            vec![make_tenure_change_tx(TenureChangeCause::Extended)],
+        );
+        assert_eq!(
+            policy.check_proposal(&extend_block, &signer_db),
+            Err(RejectReason::TenureExtendTooFrequent)
+        );
+    }
+
+    #[test]
+    fn max_block_cost() {
+        assert!(
+            BlockPolicy::new(vec![BlockPolicyRule::MaxBlockCost { max_percentage: 0 }]).is_err()
+        );
+        assert!(BlockPolicy::new(vec![BlockPolicyRule::MaxBlockCost {
+            max_percentage: 101
+        }])
+        .is_err());
+
+        let policy =
+            BlockPolicy::new(vec![BlockPolicyRule::MaxBlockCost { max_percentage: 50 }]).unwrap();
+        let block = make_block(1000, 1, vec![]);
+        // the cost rule is only applied once the block's cost is known
+        let signer_db = SignerDb::new(":memory:").unwrap();
+        assert_eq!(policy.check_proposal(&block, &signer_db), Ok(()));
+
+        assert!(policy.limits_block_cost());
+        assert!(!BlockPolicy::default().limits_block_cost());
+
+        let mut cost = ExecutionCost::ZERO;
+        cost.runtime = BLOCK_LIMIT_MAINNET_21.runtime / 2;
+        assert_eq!(
+            policy.check_block_cost(&block, &cost, &BLOCK_LIMIT_MAINNET_21),
+            Ok(())
+        );
+        cost.write_length = BLOCK_LIMIT_MAINNET_21.write_length * 3 / 4;
+        assert_eq!(
+            policy.check_block_cost(&block, &cost, &BLOCK_LIMIT_MAINNET_21),
+            Err(RejectReason::BlockCostLimitExceeded)
+        );
+        // the same cost is within a larger limit, such as a testnet's
+        assert_eq!(
+            policy.check_block_cost(&block, &cost, &HELIUM_BLOCK_LIMIT_20),
+            Ok(())
+        );
+    }
+}
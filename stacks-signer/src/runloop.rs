@@ -15,16 +15,19 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 use std::fmt::Debug;
 use std::sync::mpsc::Sender;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use clarity::codec::StacksMessageCodec;
 use hashbrown::HashMap;
 use libsigner::{SignerEntries, SignerEvent, SignerRunLoop};
+use stacks_common::types::chainstate::StacksAddress;
+use stacks_common::util::hash::Sha512Trunc256Sum;
 use stacks_common::{debug, error, info, warn};
 
 use crate::chainstate::v1::SortitionsView;
 use crate::client::{retry_with_exponential_backoff, ClientError, StacksClient};
-use crate::config::{GlobalConfig, SignerConfig, SignerConfigMode};
+use crate::config::{GlobalConfig, SignerConfig, SignerConfigMode, SignerIdentity};
 use crate::signerdb::BlockInfo;
 use crate::v0::signer_state::LocalStateMachine;
 #[cfg(any(test, feature = "testing"))]
@@ -63,6 +66,69 @@ pub struct StateInfo {
     /// The canonical tip block info according to the running signers
     /// as a pair of (reward-cycle, block-info)
     pub signer_canonical_tips: Vec<(u64, Option<BlockInfo>)>,
+    /// The state of each signing identity run alongside the one configured at the top
+    /// level, whose state is reported above
+    pub additional_identities: Vec<IdentityStateInfo>,
+}
+
+/// The state of the signers run for a single signing identity
+#[derive(PartialEq, Clone, Debug)]
+pub struct IdentityStateInfo {
+    /// The identity's Stacks address
+    pub signer_address: StacksAddress,
+    /// The current running signers reward cycles
+    pub running_signers: Vec<u64>,
+    /// The local state machines for the running signers
+    ///  as a pair of (reward-cycle, state-machine)
+    pub signer_state_machines: Vec<(u64, Option<LocalStateMachine>)>,
+    /// The number of pending block proposals for this identity
+    pub pending_proposals_count: u64,
+    /// The canonical tip block info according to the running signers
+    /// as a pair of (reward-cycle, block-info)
+    pub signer_canonical_tips: Vec<(u64, Option<BlockInfo>)>,
+}
+
+impl IdentityStateInfo {
+    fn new<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>(
+        identity: &IdentitySigners<Signer, T>,
+    ) -> Self {
+        let stacks_signers = &identity.stacks_signers;
+        Self {
+            signer_address: identity.identity.stacks_address,
+            running_signers: stacks_signers.values().map(|s| s.reward_cycle()).collect(),
+            signer_state_machines: stacks_signers
+                .iter()
+                .map(|(reward_cycle, signer)| {
+                    let ConfiguredSigner::RegisteredSigner(ref signer) = signer else {
+                        return (*reward_cycle, None);
+                    };
+                    (
+                        *reward_cycle,
+                        Some(signer.get_local_state_machine().clone()),
+                    )
+                })
+                .collect(),
+            pending_proposals_count: stacks_signers
+                .values()
+                .find_map(|signer| {
+                    if let ConfiguredSigner::RegisteredSigner(signer) = signer {
+                        Some(signer.get_pending_proposals_count())
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(0),
+            signer_canonical_tips: stacks_signers
+                .iter()
+                .map(|(reward_cycle, signer)| {
+                    let ConfiguredSigner::RegisteredSigner(ref signer) = signer else {
+                        return (*reward_cycle, None);
+                    };
+                    (*reward_cycle, signer.get_canonical_tip())
+                })
+                .collect(),
+        }
+    }
 }
 
 /// The signer result that can be sent across threads
@@ -179,6 +245,62 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
     }
 }
 
+/// The block proposals which the runloop's signing identities have submitted to the stacks
+/// node for validation. The node reports each validation result to every identity, so an
+/// identity which receives a proposal that another identity already submitted waits for that
+/// result instead of having the node validate the block again.
+#[derive(Debug, Default)]
+pub struct SharedBlockValidations {
+    /// When each pending validation was submitted, keyed by signer signature hash
+    submitted: Mutex<HashMap<Sha512Trunc256Sum, Instant>>,
+}
+
+impl SharedBlockValidations {
+    /// Whether a validation of the block was submitted less than `timeout` ago and has not
+    /// returned a result yet
+    pub fn is_pending(&self, signer_signature_hash: &Sha512Trunc256Sum, timeout: Duration) -> bool {
+        self.submitted
+            .lock()
+            .expect("FATAL: block validations lock poisoned")
+            .get(signer_signature_hash)
+            .is_some_and(|submitted_at| submitted_at.elapsed() < timeout)
+    }
+
+    /// Record that the block was submitted for validation. Submissions older than `timeout`
+    /// are forgotten, since no identity waits for them anymore.
+    pub fn submitted(&self, signer_signature_hash: Sha512Trunc256Sum, timeout: Duration) {
+        let mut submitted = self
+            .submitted
+            .lock()
+            .expect("FATAL: block validations lock poisoned");
+        submitted.retain(|_, submitted_at| submitted_at.elapsed() < timeout);
+        submitted.insert(signer_signature_hash, Instant::now());
+    }
+
+    /// Record that the stacks node returned the result of the block's validation
+    pub fn finished(&self, signer_signature_hash: &Sha512Trunc256Sum) {
+        self.submitted
+            .lock()
+            .expect("FATAL: block validations lock poisoned")
+            .remove(signer_signature_hash);
+    }
+}
+
+/// The signers run for a single signing identity
+pub struct IdentitySigners<Signer, T>
+where
+    Signer: SignerTrait<T>,
+    T: StacksMessageCodec + Clone + Send + Debug,
+{
+    /// The signing identity
+    pub identity: SignerIdentity,
+    /// The stacks node client, acting as this identity
+    pub stacks_client: StacksClient,
+    /// The internal signer for an odd or even reward cycle
+    /// Keyed by reward cycle % 2
+    pub stacks_signers: HashMap<u64, ConfiguredSigner<Signer, T>>,
+}
+
 /// The runloop for the stacks signer
 pub struct RunLoop<Signer, T>
 where
@@ -189,28 +311,41 @@ where
     pub config: GlobalConfig,
     /// The stacks node client
     pub stacks_client: StacksClient,
-    /// The internal signer for an odd or even reward cycle
-    /// Keyed by reward cycle % 2
-    pub stacks_signers: HashMap<u64, ConfiguredSigner<Signer, T>>,
+    /// The signers for each signing identity, starting with the one configured at the top
+    /// level. The identities share the stacks node client, the event receiver, the
+    /// sortition view and the block validations, but each keeps its own signer database.
+    pub identities: Vec<IdentitySigners<Signer, T>>,
     /// The state of the runloop
     pub state: State,
     /// The current reward cycle info. Only None if the runloop is uninitialized
     pub current_reward_cycle_info: Option<RewardCycleInfo>,
     /// Cache sortitin data from `stacks-node`
     pub sortition_state: Option<SortitionsView>,
+    /// The block validations submitted by any of the identities
+    pub block_validations: Arc<SharedBlockValidations>,
 }
 
 impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLoop<Signer, T> {
     /// Create a new signer runloop from the provided configuration
     pub fn new(config: GlobalConfig) -> Self {
         let stacks_client = StacksClient::from(&config);
+        let identities = config
+            .identities()
+            .into_iter()
+            .map(|identity| IdentitySigners {
+                stacks_client: stacks_client.for_identity(&identity),
+                identity,
+                stacks_signers: HashMap::with_capacity(2),
+            })
+            .collect();
         Self {
             config,
             stacks_client,
-            stacks_signers: HashMap::with_capacity(2),
+            identities,
             state: State::Uninitialized,
             current_reward_cycle_info: None,
             sortition_state: None,
+            block_validations: Arc::new(SharedBlockValidations::default()),
         }
     }
     /// Get the registered signers for a specific reward cycle
@@ -232,9 +367,10 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
         Ok(Some(entries))
     }
 
-    /// Get a signer configuration for a specific reward cycle from the stacks node
+    /// Get the signer configuration of an identity for a specific reward cycle from the stacks node
     fn get_signer_config(
-        &mut self,
+        &self,
+        identity: &SignerIdentity,
         reward_cycle: u64,
     ) -> Result<Option<SignerConfig>, ConfigurationError> {
         // We can only register for a reward cycle if a reward set exists.
@@ -273,7 +409,7 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             })?;
 
         let dry_run = self.config.dry_run;
-        let current_addr = self.stacks_client.get_signer_address();
+        let current_addr = &identity.stacks_address;
 
         let signer_config_mode = if !dry_run {
             let Some(signer_slot_id) = signer_slot_ids.get(current_addr) else {
@@ -316,10 +452,12 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             signer_entries,
             signer_slot_ids: signer_slot_ids.into_values().collect(),
             first_proposal_burn_block_timing: self.config.first_proposal_burn_block_timing,
-            stacks_private_key: self.config.stacks_private_key,
+            signing_key: identity.signing_key.clone(),
             node_host: self.config.node_host.to_string(),
+            node_stackerdb_tls_config: self.config.node_stackerdb_tls_config.clone(),
+            auth_password: identity.auth_password.clone(),
             mainnet: self.config.network.is_mainnet(),
-            db_path: self.config.db_path.clone(),
+            db_path: identity.db_path.clone(),
             block_proposal_timeout: self.config.block_proposal_timeout,
             tenure_last_block_proposal_timeout: self.config.tenure_last_block_proposal_timeout,
             block_proposal_validation_timeout: self.config.block_proposal_validation_timeout,
@@ -330,23 +468,28 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             proposal_wait_for_parent_time: self.config.proposal_wait_for_parent_time,
             validate_with_replay_tx: self.config.validate_with_replay_tx,
             capitulate_miner_view_timeout: self.config.capitulate_miner_view_timeout,
+            block_policy: self.config.block_policy.clone(),
+            admin_state: self.config.admin_state.clone(),
+            block_validations: self.block_validations.clone(),
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version: self.config.supported_signer_protocol_version,
         }))
     }
 
-    /// Refresh signer configuration for a specific reward cycle
-    fn refresh_signer_config(&mut self, reward_cycle: u64) {
+    /// Refresh the signer configuration of an identity for a specific reward cycle
+    fn refresh_signer_config(&mut self, identity_index: usize, reward_cycle: u64) {
         let reward_index = reward_cycle % 2;
-        let new_signer_config = match self.get_signer_config(reward_cycle) {
+        let identity = &self.identities[identity_index];
+        let signer_address = identity.identity.stacks_address;
+        let new_signer_config = match self.get_signer_config(&identity.identity, reward_cycle) {
             Ok(Some(new_signer_config)) => {
                 let signer_mode = new_signer_config.signer_mode.clone();
-                let new_signer = Signer::new(&self.stacks_client, new_signer_config);
+                let new_signer = Signer::new(&identity.stacks_client, new_signer_config);
                 info!("{new_signer} Signer is registered for reward cycle {reward_cycle} as {signer_mode}. Initialized signer state.");
                 ConfiguredSigner::RegisteredSigner(new_signer)
             }
             Ok(None) => {
-                warn!("Signer is not registered for reward cycle {reward_cycle}");
+                warn!("Signer {signer_address} is not registered for reward cycle {reward_cycle}");
                 ConfiguredSigner::not_registered(reward_cycle)
             }
             Err(e) => {
@@ -355,7 +498,22 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             }
         };
 
-        self.stacks_signers.insert(reward_index, new_signer_config);
+        self.identities[identity_index]
+            .stacks_signers
+            .insert(reward_index, new_signer_config);
+    }
+
+    /// Update the runloop state from the configured signers of every identity
+    fn update_state(&mut self) {
+        if self
+            .identities
+            .iter()
+            .all(|identity| identity.stacks_signers.is_empty())
+        {
+            self.state = State::NoRegisteredSigners;
+        } else {
+            self.state = State::RegisteredSigners;
+        }
     }
 
     fn initialize_runloop(&mut self) -> Result<(), ClientError> {
@@ -366,18 +524,17 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
                 .map_err(backoff::Error::transient)
         })?;
         let current_reward_cycle = reward_cycle_info.reward_cycle;
-        self.refresh_signer_config(current_reward_cycle);
-        // We should only attempt to initialize the next reward cycle signer if we are in the prepare phase of the next reward cycle
-        if reward_cycle_info.is_in_next_prepare_phase(reward_cycle_info.last_burnchain_block_height)
-        {
-            self.refresh_signer_config(current_reward_cycle.saturating_add(1));
+        for identity_index in 0..self.identities.len() {
+            self.refresh_signer_config(identity_index, current_reward_cycle);
+            // We should only attempt to initialize the next reward cycle signer if we are in the prepare phase of the next reward cycle
+            if reward_cycle_info
+                .is_in_next_prepare_phase(reward_cycle_info.last_burnchain_block_height)
+            {
+                self.refresh_signer_config(identity_index, current_reward_cycle.saturating_add(1));
+            }
         }
         self.current_reward_cycle_info = Some(reward_cycle_info);
-        if self.stacks_signers.is_empty() {
-            self.state = State::NoRegisteredSigners;
-        } else {
-            self.state = State::RegisteredSigners;
-        }
+        self.update_state();
         Ok(())
     }
 
@@ -410,37 +567,39 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             reward_cycle_info.is_in_next_prepare_phase(current_burn_block_height);
         let next_reward_cycle = current_reward_cycle.saturating_add(1);
 
-        info!(
-            "Refreshing runloop with new burn block event";
-            "latest_node_burn_ht" => current_burn_block_height,
-            "event_ht" =>  ev_burn_block_height,
-            "reward_cycle_before_refresh" => reward_cycle_before_refresh,
-            "current_reward_cycle" => current_reward_cycle,
-            "configured_for_current" => Self::is_configured_for_cycle(&self.stacks_signers, current_reward_cycle),
-            "registered_for_current" => Self::is_registered_for_cycle(&self.stacks_signers, current_reward_cycle),
-            "configured_for_next" => Self::is_configured_for_cycle(&self.stacks_signers, next_reward_cycle),
-            "registered_for_next" => Self::is_registered_for_cycle(&self.stacks_signers, next_reward_cycle),
-            "is_in_next_prepare_phase" => is_in_next_prepare_phase,
-        );
+        for identity_index in 0..self.identities.len() {
+            let stacks_signers = &self.identities[identity_index].stacks_signers;
+            let configured_for_current =
+                Self::is_configured_for_cycle(stacks_signers, current_reward_cycle);
+            let configured_for_next =
+                Self::is_configured_for_cycle(stacks_signers, next_reward_cycle);
+            info!(
+                "Refreshing runloop with new burn block event";
+                "signer_address" => %self.identities[identity_index].identity.stacks_address,
+                "latest_node_burn_ht" => current_burn_block_height,
+                "event_ht" =>  ev_burn_block_height,
+                "reward_cycle_before_refresh" => reward_cycle_before_refresh,
+                "current_reward_cycle" => current_reward_cycle,
+                "configured_for_current" => configured_for_current,
+                "registered_for_current" => Self::is_registered_for_cycle(stacks_signers, current_reward_cycle),
+                "configured_for_next" => configured_for_next,
+                "registered_for_next" => Self::is_registered_for_cycle(stacks_signers, next_reward_cycle),
+                "is_in_next_prepare_phase" => is_in_next_prepare_phase,
+            );
 
-        // Check if we need to refresh the signers:
-        //   need to refresh the current signer if we are not configured for the current reward cycle
-        //   need to refresh the next signer if we're not configured for the next reward cycle, and we're in the prepare phase
-        if !Self::is_configured_for_cycle(&self.stacks_signers, current_reward_cycle) {
-            self.refresh_signer_config(current_reward_cycle);
-        }
-        if is_in_next_prepare_phase
-            && !Self::is_configured_for_cycle(&self.stacks_signers, next_reward_cycle)
-        {
-            self.refresh_signer_config(next_reward_cycle);
+            // Check if we need to refresh the signers:
+            //   need to refresh the current signer if we are not configured for the current reward cycle
+            //   need to refresh the next signer if we're not configured for the next reward cycle, and we're in the prepare phase
+            if !configured_for_current {
+                self.refresh_signer_config(identity_index, current_reward_cycle);
+            }
+            if is_in_next_prepare_phase && !configured_for_next {
+                self.refresh_signer_config(identity_index, next_reward_cycle);
+            }
         }
 
         self.cleanup_stale_signers(current_reward_cycle);
-        if self.stacks_signers.is_empty() {
-            self.state = State::NoRegisteredSigners;
-        } else {
-            self.state = State::RegisteredSigners;
-        }
+        self.update_state();
         Ok(())
     }
 
@@ -471,28 +630,30 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug> RunLo
             warn!("Skipping signer cleanup due to testing directive.");
             return;
         }
-        let mut to_delete = Vec::new();
-        for (idx, signer) in &mut self.stacks_signers {
-            let reward_cycle = signer.reward_cycle();
-            if reward_cycle >= current_reward_cycle {
-                // We are either the current or a future reward cycle, so we are not stale.
-                continue;
-            }
-            match signer {
-                ConfiguredSigner::RegisteredSigner(signer) => {
-                    if !signer.has_unprocessed_blocks() {
-                        debug!("{signer}: Signer's tenure has completed.");
+        for identity in &mut self.identities {
+            let mut to_delete = Vec::new();
+            for (idx, signer) in &mut identity.stacks_signers {
+                let reward_cycle = signer.reward_cycle();
+                if reward_cycle >= current_reward_cycle {
+                    // We are either the current or a future reward cycle, so we are not stale.
+                    continue;
+                }
+                match signer {
+                    ConfiguredSigner::RegisteredSigner(signer) => {
+                        if !signer.has_unprocessed_blocks() {
+                            debug!("{signer}: Signer's tenure has completed.");
+                            to_delete.push(*idx);
+                        }
+                    }
+                    ConfiguredSigner::NotRegistered { .. } => {
+                        debug!("{signer}: Unregistered signer's tenure has completed.");
                         to_delete.push(*idx);
                     }
                 }
-                ConfiguredSigner::NotRegistered { .. } => {
-                    debug!("{signer}: Unregistered signer's tenure has completed.");
-                    to_delete.push(*idx);
-                }
             }
-        }
-        for idx in to_delete {
-            self.stacks_signers.remove(&idx);
+            for idx in to_delete {
+                identity.stacks_signers.remove(&idx);
+            }
         }
     }
 }
@@ -520,48 +681,24 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
 
         // This is the only event that we respond to from the outer signer runloop
         if let Some(SignerEvent::StatusCheck) = event {
+            let mut identity_states = self.identities.iter().map(IdentityStateInfo::new);
+            let IdentityStateInfo {
+                running_signers,
+                signer_state_machines,
+                pending_proposals_count,
+                signer_canonical_tips,
+                ..
+            } = identity_states
+                .next()
+                .expect("FATAL: the signer runs at least the top-level identity");
             let state_info = StateInfo {
                 runloop_state: self.state,
                 reward_cycle_info: self.current_reward_cycle_info,
-                running_signers: self
-                    .stacks_signers
-                    .values()
-                    .map(|s| s.reward_cycle())
-                    .collect(),
-                signer_state_machines: self
-                    .stacks_signers
-                    .iter()
-                    .map(|(reward_cycle, signer)| {
-                        let ConfiguredSigner::RegisteredSigner(ref signer) = signer else {
-                            return (*reward_cycle, None);
-                        };
-                        (
-                            *reward_cycle,
-                            Some(signer.get_local_state_machine().clone()),
-                        )
-                    })
-                    .collect(),
-                pending_proposals_count: self
-                    .stacks_signers
-                    .values()
-                    .find_map(|signer| {
-                        if let ConfiguredSigner::RegisteredSigner(signer) = signer {
-                            Some(signer.get_pending_proposals_count())
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(0),
-                signer_canonical_tips: self
-                    .stacks_signers
-                    .iter()
-                    .map(|(reward_cycle, signer)| {
-                        let ConfiguredSigner::RegisteredSigner(ref signer) = signer else {
-                            return (*reward_cycle, None);
-                        };
-                        (*reward_cycle, signer.get_canonical_tip())
-                    })
-                    .collect(),
+                running_signers,
+                signer_state_machines,
+                pending_proposals_count,
+                signer_canonical_tips,
+                additional_identities: identity_states.collect(),
             };
             info!("Signer status check requested: {state_info:?}");
 
@@ -590,19 +727,28 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
             .as_ref()
             .expect("FATAL: cannot be an initialized signer with no reward cycle info.")
             .reward_cycle;
-        for configured_signer in self.stacks_signers.values_mut() {
-            let ConfiguredSigner::RegisteredSigner(ref mut signer) = configured_signer else {
-                debug!("{configured_signer}: Not configured for cycle, ignoring events for cycle");
-                continue;
-            };
+        for identity in self.identities.iter_mut() {
+            for configured_signer in identity.stacks_signers.values_mut() {
+                let ConfiguredSigner::RegisteredSigner(ref mut signer) = configured_signer else {
+                    debug!(
+                        "{configured_signer}: Not configured for cycle, ignoring events for cycle"
+                    );
+                    continue;
+                };
 
-            signer.process_event(
-                &self.stacks_client,
-                &mut self.sortition_state,
-                event.as_ref(),
-                res,
-                current_reward_cycle,
-            );
+                signer.process_event(
+                    &identity.stacks_client,
+                    &mut self.sortition_state,
+                    event.as_ref(),
+                    res,
+                    current_reward_cycle,
+                );
+                self.config.admin_state.record_local_state_machine(
+                    &identity.identity.stacks_address,
+                    signer.reward_cycle(),
+                    signer.get_local_state_machine(),
+                );
+            }
         }
 
         if self.state == State::NoRegisteredSigners && event.is_some() {
@@ -615,12 +761,70 @@ impl<Signer: SignerTrait<T>, T: StacksMessageCodec + Clone + Send + Debug>
 
 #[cfg(test)]
 mod tests {
+    use std::sync::mpsc::channel;
+
     use blockstack_lib::chainstate::stacks::boot::NakamotoSignerEntry;
+    use libsigner::v0::messages::SignerMessage;
     use libsigner::SignerEntries;
     use rand::{thread_rng, Rng, RngCore};
     use stacks_common::types::chainstate::StacksPublicKey;
 
-    use super::RewardCycleInfo;
+    use super::*;
+    use crate::signerdb::tests::tmp_db_path;
+
+    /// A signer which only reports a fixed number of pending proposals
+    #[derive(Debug)]
+    struct TestSigner {
+        reward_cycle: u64,
+        pending_proposals_count: u64,
+        local_state_machine: LocalStateMachine,
+    }
+
+    impl std::fmt::Display for TestSigner {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "TestSigner for cycle #{}", self.reward_cycle)
+        }
+    }
+
+    impl SignerTrait<SignerMessage> for TestSigner {
+        fn new(_stacks_client: &StacksClient, signer_config: SignerConfig) -> Self {
+            Self {
+                reward_cycle: signer_config.reward_cycle,
+                pending_proposals_count: 0,
+                local_state_machine: LocalStateMachine::Uninitialized,
+            }
+        }
+
+        fn reward_cycle(&self) -> u64 {
+            self.reward_cycle
+        }
+
+        fn process_event(
+            &mut self,
+            _stacks_client: &StacksClient,
+            _sortition_state: &mut Option<SortitionsView>,
+            _event: Option<&SignerEvent<SignerMessage>>,
+            _res: &Sender<SignerResult>,
+            _current_reward_cycle: u64,
+        ) {
+        }
+
+        fn has_unprocessed_blocks(&self) -> bool {
+            false
+        }
+
+        fn get_local_state_machine(&self) -> &LocalStateMachine {
+            &self.local_state_machine
+        }
+
+        fn get_pending_proposals_count(&self) -> u64 {
+            self.pending_proposals_count
+        }
+
+        fn get_canonical_tip(&self) -> Option<BlockInfo> {
+            None
+        }
+    }
 
     #[test]
     fn parse_nakamoto_signer_entries_test() {
@@ -765,4 +969,85 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn status_check_and_admin_state_cover_every_identity() {
+        let config_toml = format!(
+            r#"
+stacks_private_key = "2de4e77aab89c0c2570bb8bb90824f5cf2a5204a975905fee450ff9dad0fcf28"
+node_host = "localhost:20443"
+endpoint = "localhost:30000"
+network = "testnet"
+auth_password = "abcd"
+db_path = "{}"
+
+[[identities]]
+stacks_private_key = "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01"
+db_path = "{}"
+"#,
+            tmp_db_path().display(),
+            tmp_db_path().display()
+        );
+        let config = GlobalConfig::load_from_str(&config_toml).unwrap();
+        let admin_state = config.admin_state.clone();
+        let mut runloop: RunLoop<TestSigner, SignerMessage> = RunLoop::new(config);
+        assert_eq!(runloop.identities.len(), 2);
+
+        let reward_cycle = 7;
+        for (i, identity) in runloop.identities.iter_mut().enumerate() {
+            identity.stacks_signers.insert(
+                reward_cycle % 2,
+                ConfiguredSigner::RegisteredSigner(TestSigner {
+                    reward_cycle,
+                    pending_proposals_count: i as u64 + 1,
+                    local_state_machine: LocalStateMachine::Uninitialized,
+                }),
+            );
+        }
+        runloop.state = State::RegisteredSigners;
+        runloop.current_reward_cycle_info = Some(RewardCycleInfo {
+            reward_cycle,
+            reward_cycle_length: 20,
+            prepare_phase_block_length: 5,
+            first_burnchain_block_height: 0,
+            last_burnchain_block_height: 140,
+        });
+
+        let (res_send, res_recv) = channel();
+        runloop.run_one_pass(Some(SignerEvent::StatusCheck), &res_send);
+        let SignerResult::StatusCheck(state_info) = res_recv.try_recv().unwrap();
+        assert_eq!(state_info.running_signers, vec![reward_cycle]);
+        assert_eq!(state_info.pending_proposals_count, 1);
+        assert_eq!(state_info.additional_identities.len(), 1);
+        let second_identity = &state_info.additional_identities[0];
+        assert_eq!(
+            second_identity.signer_address,
+            runloop.identities[1].identity.stacks_address
+        );
+        assert_eq!(second_identity.running_signers, vec![reward_cycle]);
+        assert_eq!(second_identity.pending_proposals_count, 2);
+
+        // The admin API sees the state machines of both identities
+        for identity in &runloop.identities {
+            assert!(admin_state
+                .local_state_machines(&identity.identity.stacks_address)
+                .contains_key(&reward_cycle));
+        }
+    }
+
+    #[test]
+    fn shared_block_validations() {
+        let validations = SharedBlockValidations::default();
+        let hash = Sha512Trunc256Sum([0x01; 32]);
+        let timeout = Duration::from_secs(60);
+        assert!(!validations.is_pending(&hash, timeout));
+
+        validations.submitted(hash, timeout);
+        assert!(validations.is_pending(&hash, timeout));
+        // A submission older than the timeout no longer holds back other identities
+        assert!(!validations.is_pending(&hash, Duration::ZERO));
+
+        validations.finished(&hash);
+        assert!(!validations.is_pending(&hash, timeout));
+    }
 }
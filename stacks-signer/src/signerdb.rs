@@ -16,7 +16,8 @@
 
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
@@ -40,7 +41,9 @@ use stacks_common::types::chainstate::ConsensusHash;
 use stacks_common::util::get_epoch_time_secs;
 use stacks_common::util::hash::Sha512Trunc256Sum;
 use stacks_common::util::secp256k1::MessageSignature;
-use stacks_common::{debug, define_u8_enum, error, warn};
+use stacks_common::{debug, define_u8_enum, error, info, warn};
+
+use crate::audit::BlockAuditRecord;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 /// A vote across the signer set for a block
@@ -316,6 +319,98 @@ impl BlockInfo {
     }
 }
 
+/// How much history `SignerDb::prune` keeps. The current and previous reward cycles are
+/// always kept, as are the globally accepted blocks in the canonical tip's tenure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbRetention {
+    /// Keep this many reward cycles before the latest one
+    RewardCycles(u64),
+    /// Keep this many burn blocks below the latest one
+    BurnBlocks(u64),
+}
+
+/// The number of rows removed from each table by `SignerDb::prune`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PruneStats {
+    /// Rows removed from `blocks`
+    pub blocks: usize,
+    /// Rows removed from `block_signatures`
+    pub block_signatures: usize,
+    /// Rows removed from `block_rejection_signer_addrs`
+    pub block_rejection_signer_addrs: usize,
+    /// Rows removed from `block_validations_pending`
+    pub block_validations_pending: usize,
+    /// Rows removed from `block_validated_by_replay_txs`
+    pub block_validated_by_replay_txs: usize,
+    /// Rows removed from `signer_state_machine_updates`
+    pub signer_state_machine_updates: usize,
+    /// Rows removed from `burn_blocks`
+    pub burn_blocks: usize,
+    /// Rows removed from `burn_block_updates_received_times`
+    pub burn_block_updates_received_times: usize,
+    /// Rows removed from `tenure_activity`
+    pub tenure_activity: usize,
+    /// Rows removed from `block_audit_log`
+    pub block_audit_log: usize,
+}
+
+impl PruneStats {
+    /// The total number of rows removed
+    pub fn total(&self) -> usize {
+        self.blocks
+            + self.block_signatures
+            + self.block_rejection_signer_addrs
+            + self.block_validations_pending
+            + self.block_validated_by_replay_txs
+            + self.signer_state_machine_updates
+            + self.burn_blocks
+            + self.burn_block_updates_received_times
+            + self.tenure_activity
+            + self.block_audit_log
+    }
+}
+
+/// A tenure's start time and processing time, accumulated from its globally accepted blocks
+/// from the highest one down to its latest tenure change block
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TenureTimes {
+    /// When the lowest block added so far was proposed (epoch time in seconds)
+    pub start_time: Option<u64>,
+    /// The total validation time of the blocks added so far, in milliseconds
+    pub process_time_ms: u64,
+}
+
+impl TenureTimes {
+    /// Add the next lower globally accepted block of the tenure. Returns `false` once the
+    /// tenure's latest tenure change block has been added, as lower blocks do not count.
+    pub fn add_block(
+        &mut self,
+        tenure_change: bool,
+        proposed_time: u64,
+        validation_time_ms: Option<u64>,
+    ) -> bool {
+        self.process_time_ms = self
+            .process_time_ms
+            .saturating_add(validation_time_ms.unwrap_or(0));
+        self.start_time = Some(proposed_time);
+        !tenure_change
+    }
+}
+
+/// The earliest time (epoch time in seconds) at which a signer accepts a time-based tenure
+/// extend, for a tenure which started at `tenure_start_time` and whose blocks took
+/// `tenure_process_time_ms` to validate
+pub fn tenure_idle_timestamp(
+    tenure_start_time: u64,
+    tenure_process_time_ms: u64,
+    tenure_idle_timeout: Duration,
+) -> u64 {
+    // Plus (ms + 999)/1000 to round up to the nearest second
+    tenure_start_time
+        .saturating_add(tenure_idle_timeout.as_secs())
+        .saturating_add(tenure_process_time_ms.div_ceil(1000))
+}
+
 /// This struct manages a SQLite database connection
 /// for the signer.
 #[derive(Debug)]
@@ -673,6 +768,20 @@ CREATE TABLE IF NOT EXISTS block_rejection_signer_addrs (
     PRIMARY KEY (signer_signature_hash, signer_addr)
 ) STRICT;"#;
 
+static CREATE_BLOCK_AUDIT_LOG_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS block_audit_log (
+    -- Rows are only ever appended, so the id orders the records
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    signer_signature_hash TEXT NOT NULL,
+    reward_cycle INTEGER NOT NULL,
+    burn_block_height INTEGER NOT NULL,
+    -- The serialized BlockAuditRecord
+    audit_record TEXT NOT NULL
+) STRICT;
+
+CREATE INDEX IF NOT EXISTS block_audit_log_burn_block_height ON block_audit_log(burn_block_height);
+"#;
+
 static SCHEMA_1: &[&str] = &[
     DROP_SCHEMA_0,
     CREATE_DB_CONFIG,
@@ -786,6 +895,11 @@ static SCHEMA_16: &[&str] = &[
     "INSERT INTO db_config (version) VALUES (16);",
 ];
 
+static SCHEMA_17: &[&str] = &[
+    CREATE_BLOCK_AUDIT_LOG_TABLE,
+    "INSERT INTO db_config (version) VALUES (17);",
+];
+
 struct Migration {
     version: u32,
     statements: &'static [&'static str],
@@ -856,11 +970,15 @@ static MIGRATIONS: &[Migration] = &[
         version: 16,
         statements: SCHEMA_16,
     },
+    Migration {
+        version: 17,
+        statements: SCHEMA_17,
+    },
 ];
 
 impl SignerDb {
     /// The current schema version used in this build of the signer binary.
-    pub const SCHEMA_VERSION: u32 = 16;
+    pub const SCHEMA_VERSION: u32 = 17;
 
     /// Create a new `SignerState` instance.
     /// This will create a new SQLite database at the given path
@@ -1146,6 +1264,22 @@ impl SignerDb {
         try_deserialize(result)
     }
 
+    /// Return the last accepted tenure change or tenure extend block in a tenure (identified by its consensus hash).
+    pub fn get_last_tenure_change_block(
+        &self,
+        tenure: &ConsensusHash,
+    ) -> Result<Option<BlockInfo>, DBError> {
+        let query = "SELECT block_info FROM blocks WHERE consensus_hash = ?1 AND tenure_change = 1 AND state IN (?2, ?3) ORDER BY stacks_height DESC LIMIT 1";
+        let args = params![
+            tenure,
+            &BlockState::GloballyAccepted.to_string(),
+            &BlockState::LocallyAccepted.to_string()
+        ];
+        let result: Option<String> = query_row(&self.db, query, args)?;
+
+        try_deserialize(result)
+    }
+
     /// Return the last globally accepted block in a tenure (identified by its consensus hash).
     pub fn get_last_globally_accepted_block(
         &self,
@@ -1177,6 +1311,34 @@ impl SignerDb {
         Ok(result.map(|signed_self| UNIX_EPOCH + Duration::from_secs(signed_self)))
     }
 
+    /// Return the most recently proposed blocks, newest first.
+    pub fn get_recent_blocks(&self, limit: u64) -> Result<Vec<BlockInfo>, DBError> {
+        let query = "SELECT block_info FROM blocks ORDER BY proposed_time DESC, stacks_height DESC LIMIT ?1";
+        let args = params![u64_to_sql(limit)?];
+        let block_infos: Vec<String> = query_rows(&self.db, query, args)?;
+        block_infos
+            .iter()
+            .map(|block_info| serde_json::from_str(block_info).map_err(DBError::from))
+            .collect()
+    }
+
+    /// Return the blocks proposed at burn block heights from `start_burn_height` up to and
+    /// including `end_burn_height` (or the latest one, if `None`), oldest first.
+    pub fn get_blocks_in_burn_range(
+        &self,
+        start_burn_height: u64,
+        end_burn_height: Option<u64>,
+    ) -> Result<Vec<BlockInfo>, DBError> {
+        let end_burn_height = end_burn_height.map(u64_to_sql).transpose()?;
+        let query = "SELECT block_info FROM blocks WHERE burn_block_height >= ?1 AND (?2 IS NULL OR burn_block_height <= ?2) ORDER BY proposed_time ASC, stacks_height ASC";
+        let args = params![u64_to_sql(start_burn_height)?, end_burn_height];
+        let block_infos: Vec<String> = query_rows(&self.db, query, args)?;
+        block_infos
+            .iter()
+            .map(|block_info| serde_json::from_str(block_info).map_err(DBError::from))
+            .collect()
+    }
+
     /// Return the canonical tip -- the last globally accepted block.
     pub fn get_canonical_tip(&self) -> Result<Option<BlockInfo>, DBError> {
         let query = "SELECT block_info FROM blocks WHERE state = ?1 ORDER BY stacks_height DESC, signed_group DESC LIMIT 1";
@@ -1567,24 +1729,20 @@ impl SignerDb {
             let validation_time_ms: Option<u64> = row.get(2)?;
             Ok((tenure_change_block, proposed_time, validation_time_ms))
         })?;
-        let mut tenure_processing_time_ms = 0_u64;
-        let mut tenure_start_time = None;
+        let mut tenure_times = TenureTimes::default();
         let mut nmb_rows = 0;
         for (i, row) in rows.enumerate() {
             nmb_rows += 1;
             let (tenure_change_block, proposed_time, validation_time_ms) = row?;
-            tenure_processing_time_ms =
-                tenure_processing_time_ms.saturating_add(validation_time_ms.unwrap_or(0));
-            tenure_start_time = Some(proposed_time);
-            if tenure_change_block {
+            if !tenure_times.add_block(tenure_change_block, proposed_time, validation_time_ms) {
                 debug!("Found tenure change block {i} blocks ago in tenure {tenure}");
                 break;
             }
         }
         debug!("Calculated tenure extend timestamp from {nmb_rows} blocks in tenure {tenure}");
         Ok((
-            tenure_start_time.unwrap_or(get_epoch_time_secs()),
-            tenure_processing_time_ms,
+            tenure_times.start_time.unwrap_or(get_epoch_time_secs()),
+            tenure_times.process_time_ms,
         ))
     }
 
@@ -1604,10 +1762,11 @@ impl SignerDb {
         }
         let tenure_idle_timeout_secs = tenure_idle_timeout.as_secs();
         let (tenure_start_time, tenure_process_time_ms) = self.get_tenure_times(&block.header.consensus_hash).inspect_err(|e| error!("Error occurred calculating tenure extend timestamp: {e:?}. Defaulting to {tenure_idle_timeout_secs} from now.")).unwrap_or((get_epoch_time_secs(), 0));
-        // Plus (ms + 999)/1000 to round up to the nearest second
-        let tenure_extend_timestamp = tenure_start_time
-            .saturating_add(tenure_idle_timeout_secs)
-            .saturating_add(tenure_process_time_ms.div_ceil(1000));
+        let tenure_extend_timestamp = tenure_idle_timestamp(
+            tenure_start_time,
+            tenure_process_time_ms,
+            tenure_idle_timeout,
+        );
         debug!("Calculated tenure extend timestamp";
             "tenure_extend_timestamp" => tenure_extend_timestamp,
             "tenure_start_time" => tenure_start_time,
@@ -1764,6 +1923,46 @@ impl SignerDb {
         query_row(&self.db, query, args)
     }
 
+    /// Append a record of a block decision to the audit log
+    pub fn insert_block_audit_record(&self, record: &BlockAuditRecord) -> Result<(), DBError> {
+        let record_json = serde_json::to_string(record).expect("Unable to serialize audit record");
+        self.db.execute(
+            "INSERT INTO block_audit_log (signer_signature_hash, reward_cycle, burn_block_height, audit_record) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                record.signer_signature_hash,
+                u64_to_sql(record.reward_cycle)?,
+                u64_to_sql(record.burn_block_height)?,
+                record_json,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the audit records for blocks proposed at burn block heights from `start_burn_height`
+    /// up to and including `end_burn_height` (or the latest one, if `None`), oldest first.
+    pub fn get_block_audit_records(
+        &self,
+        start_burn_height: u64,
+        end_burn_height: Option<u64>,
+    ) -> Result<Vec<BlockAuditRecord>, DBError> {
+        let records: Vec<String> = match end_burn_height {
+            Some(end_burn_height) => query_rows(
+                &self.db,
+                "SELECT audit_record FROM block_audit_log WHERE burn_block_height >= ?1 AND burn_block_height <= ?2 ORDER BY id ASC",
+                params![u64_to_sql(start_burn_height)?, u64_to_sql(end_burn_height)?],
+            )?,
+            None => query_rows(
+                &self.db,
+                "SELECT audit_record FROM block_audit_log WHERE burn_block_height >= ?1 ORDER BY id ASC",
+                params![u64_to_sql(start_burn_height)?],
+            )?,
+        };
+        records
+            .iter()
+            .map(|record| serde_json::from_str(record).map_err(DBError::from))
+            .collect()
+    }
+
     /// Get the earliest received time at which the signer state update achieved
     /// a global burn view identified by the provided ConsensusHash
     pub fn get_burn_block_received_time_from_signers(
@@ -1873,6 +2072,161 @@ impl FromRow<BlockValidatedByReplaySet> for BlockValidatedByReplaySet {
     }
 }
 
+impl SignerDb {
+    /// Remove history older than `retention` allows. The latest reward cycle is the highest one
+    /// that this signer has recorded a block for, and the latest burn block is the highest one
+    /// it has recorded. Data for the latest and previous reward cycles is always kept, as are
+    /// the canonical tip and the other globally accepted blocks in its tenure, which the tenure
+    /// extend timestamp is calculated from.
+    pub fn prune(&mut self, retention: DbRetention) -> Result<PruneStats, DBError> {
+        let mut stats = PruneStats::default();
+        let latest_reward_cycle: Option<u64> = query_row(
+            &self.db,
+            "SELECT reward_cycle FROM blocks ORDER BY reward_cycle DESC LIMIT 1",
+            params![],
+        )?;
+        let latest_burn_height: Option<u64> = query_row(
+            &self.db,
+            "SELECT block_height FROM burn_blocks ORDER BY block_height DESC LIMIT 1",
+            params![],
+        )?;
+        let (Some(latest_reward_cycle), Some(latest_burn_height)) =
+            (latest_reward_cycle, latest_burn_height)
+        else {
+            return Ok(stats);
+        };
+        let protected_reward_cycle = latest_reward_cycle.saturating_sub(1);
+        let (prune_before_reward_cycle, retained_burn_height) = match retention {
+            DbRetention::RewardCycles(reward_cycles) => (
+                latest_reward_cycle
+                    .saturating_sub(reward_cycles)
+                    .min(protected_reward_cycle),
+                None,
+            ),
+            DbRetention::BurnBlocks(burn_blocks) => (
+                protected_reward_cycle,
+                Some(latest_burn_height.saturating_sub(burn_blocks)),
+            ),
+        };
+        // Burn blocks are kept from the first block of the oldest reward cycle that is kept
+        let first_retained_burn_height: Option<u64> = query_row(
+            &self.db,
+            "SELECT burn_block_height FROM blocks WHERE reward_cycle >= ?1 ORDER BY burn_block_height ASC LIMIT 1",
+            params![u64_to_sql(prune_before_reward_cycle)?],
+        )?;
+        let prune_below_burn_height = match (retained_burn_height, first_retained_burn_height) {
+            (Some(retained), Some(first_retained)) => retained.min(first_retained),
+            (Some(height), None) | (None, Some(height)) => height,
+            (None, None) => return Ok(stats),
+        };
+
+        let canonical_tenure = self
+            .get_canonical_tip()?
+            .map(|tip| tip.block.header.consensus_hash);
+
+        let tx = tx_begin_immediate(&mut self.db)?;
+        let pruned_blocks = "SELECT signer_signature_hash FROM blocks WHERE reward_cycle < ?1 AND burn_block_height < ?2 AND NOT (state = ?3 AND consensus_hash IS ?4)";
+        let block_args = params![
+            u64_to_sql(prune_before_reward_cycle)?,
+            u64_to_sql(prune_below_burn_height)?,
+            &BlockState::GloballyAccepted.to_string(),
+            canonical_tenure,
+        ];
+        stats.block_signatures = tx.execute(
+            &format!(
+                "DELETE FROM block_signatures WHERE signer_signature_hash IN ({pruned_blocks})"
+            ),
+            block_args,
+        )?;
+        stats.block_rejection_signer_addrs = tx.execute(
+            &format!("DELETE FROM block_rejection_signer_addrs WHERE signer_signature_hash IN ({pruned_blocks})"),
+            block_args,
+        )?;
+        stats.block_validations_pending = tx.execute(
+            &format!("DELETE FROM block_validations_pending WHERE signer_signature_hash IN ({pruned_blocks})"),
+            block_args,
+        )?;
+        stats.block_validated_by_replay_txs = tx.execute(
+            &format!("DELETE FROM block_validated_by_replay_txs WHERE signer_signature_hash IN ({pruned_blocks})"),
+            block_args,
+        )?;
+        stats.blocks = tx.execute(
+            "DELETE FROM blocks WHERE reward_cycle < ?1 AND burn_block_height < ?2 AND NOT (state = ?3 AND consensus_hash IS ?4)",
+            block_args,
+        )?;
+        stats.block_audit_log = tx.execute(
+            "DELETE FROM block_audit_log WHERE reward_cycle < ?1 AND burn_block_height < ?2",
+            params![
+                u64_to_sql(prune_before_reward_cycle)?,
+                u64_to_sql(prune_below_burn_height)?,
+            ],
+        )?;
+        stats.signer_state_machine_updates = tx.execute(
+            "DELETE FROM signer_state_machine_updates WHERE reward_cycle < ?1",
+            params![u64_to_sql(prune_before_reward_cycle)?],
+        )?;
+
+        let pruned_burn_blocks = "SELECT consensus_hash FROM burn_blocks WHERE block_height < ?1";
+        let burn_block_args = params![u64_to_sql(prune_below_burn_height)?];
+        stats.burn_block_updates_received_times = tx.execute(
+            &format!("DELETE FROM burn_block_updates_received_times WHERE burn_block_consensus_hash IN ({pruned_burn_blocks})"),
+            burn_block_args,
+        )?;
+        stats.tenure_activity = tx.execute(
+            &format!("DELETE FROM tenure_activity WHERE consensus_hash IN ({pruned_burn_blocks})"),
+            burn_block_args,
+        )?;
+        stats.burn_blocks = tx.execute(
+            "DELETE FROM burn_blocks WHERE block_height < ?1",
+            burn_block_args,
+        )?;
+        tx.commit()?;
+
+        debug!("Pruned signer db";
+            "prune_before_reward_cycle" => prune_before_reward_cycle,
+            "prune_below_burn_height" => prune_below_burn_height,
+            "stats" => ?stats,
+        );
+        Ok(stats)
+    }
+
+    /// Rebuild the database file so that it no longer takes up the space freed by pruning.
+    /// This needs exclusive access to the database, so it blocks the signer while it runs.
+    pub fn compact(&self) -> Result<(), DBError> {
+        self.db.execute_batch("VACUUM;")?;
+        // Leave the write-ahead log empty as well
+        self.db
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE);", [], |_| Ok(()))?;
+        Ok(())
+    }
+}
+
+/// Periodically prune the signer database at `db_path` in a background thread. Space freed by
+/// pruning is reused by the database rather than returned to the file system; run the
+/// `prune-db` command with the signer stopped to shrink the file.
+pub fn spawn_db_pruner(
+    db_path: PathBuf,
+    retention: DbRetention,
+    interval: Duration,
+) -> std::io::Result<JoinHandle<()>> {
+    std::thread::Builder::new()
+        .name("signer_db_pruner".to_string())
+        .spawn(move || loop {
+            std::thread::sleep(interval);
+            let result = SignerDb::new(&db_path).and_then(|mut db| db.prune(retention));
+            match result {
+                Ok(stats) => info!("Pruned signer db";
+                    "db_path" => %db_path.display(),
+                    "rows_removed" => stats.total(),
+                ),
+                Err(e) => warn!("Failed to prune signer db";
+                    "db_path" => %db_path.display(),
+                    "err" => ?e,
+                ),
+            }
+        })
+}
+
 #[cfg(any(test, feature = "testing"))]
 impl SignerDb {
     /// For tests, fetch all pending block validations
@@ -1909,10 +2263,13 @@ pub mod tests {
     use clarity::types::chainstate::{StacksBlockId, StacksPrivateKey, StacksPublicKey};
     use clarity::util::hash::Hash160;
     use clarity::util::secp256k1::MessageSignature;
-    use libsigner::v0::messages::{StateMachineUpdateContent, StateMachineUpdateMinerState};
+    use libsigner::v0::messages::{
+        BlockResponse, StateMachineUpdateContent, StateMachineUpdateMinerState,
+    };
     use libsigner::{BlockProposal, BlockProposalData};
 
     use super::*;
+    use crate::audit::AuditStage;
     use crate::signerdb::NakamotoBlockVote;
 
     fn _wipe_db(db_path: &PathBuf) {
@@ -3381,4 +3738,167 @@ pub mod tests {
 
         assert!(result_3.is_none());
     }
+
+    /// Fill a db with burn blocks at heights 10, 20, 30, 40 and 50 and blocks in reward cycles
+    /// 1, 4 and 5. Returns the db along with the signer signature hashes of an old block, an old
+    /// globally accepted block, and an old globally accepted block in the canonical tip's tenure.
+    fn setup_prune_db(
+        db_path: impl AsRef<Path>,
+    ) -> (
+        SignerDb,
+        Sha512Trunc256Sum,
+        Sha512Trunc256Sum,
+        Sha512Trunc256Sum,
+    ) {
+        let mut db = SignerDb::new(db_path).expect("Failed to create signer db");
+        for i in 1..=5u8 {
+            db.insert_burn_block(
+                &BurnchainHeaderHash([i; 32]),
+                &ConsensusHash([i; 20]),
+                u64::from(i) * 10,
+                &SystemTime::now(),
+                &BurnchainHeaderHash([i - 1; 32]),
+            )
+            .unwrap();
+        }
+        let mut insert_block =
+            |chain_length: u64, reward_cycle: u64, burn_height: u64, tenure: u8, accepted: bool| {
+                let (mut block_info, _) = create_block_override(|b| {
+                    b.block.header.chain_length = chain_length;
+                    b.block.header.consensus_hash = ConsensusHash([tenure; 20]);
+                    b.reward_cycle = reward_cycle;
+                    b.burn_height = burn_height;
+                });
+                if accepted {
+                    block_info.mark_globally_accepted().unwrap();
+                }
+                db.insert_block(&block_info).unwrap();
+                block_info
+            };
+        let old_block = insert_block(1, 1, 10, 1, false);
+        let old_accepted_block = insert_block(2, 1, 10, 1, true);
+        // A long-running tenure whose latest block is the canonical tip
+        let old_tip_tenure_block = insert_block(3, 1, 10, 4, true);
+        insert_block(4, 4, 40, 4, false);
+        insert_block(5, 5, 50, 4, true);
+
+        let old_hash = old_block.signer_signature_hash();
+        let old_accepted_hash = old_accepted_block.signer_signature_hash();
+        let old_tip_tenure_hash = old_tip_tenure_block.signer_signature_hash();
+        let address = StacksAddress::burn_address(false);
+        db.add_block_signature(&old_accepted_hash, &address, &MessageSignature([0x11; 65]))
+            .unwrap();
+        db.add_block_signature(
+            &old_tip_tenure_hash,
+            &address,
+            &MessageSignature([0x22; 65]),
+        )
+        .unwrap();
+        db.add_block_rejection_signer_addr(&old_hash, &address, &RejectReason::InvalidParentBlock)
+            .unwrap();
+        (db, old_hash, old_accepted_hash, old_tip_tenure_hash)
+    }
+
+    #[test]
+    fn prune_by_reward_cycles() {
+        let (mut db, old_hash, old_accepted_hash, old_tip_tenure_hash) =
+            setup_prune_db(tmp_db_path());
+
+        // The previous reward cycle is kept even when no history is asked for
+        let stats = db.prune(DbRetention::RewardCycles(0)).unwrap();
+        assert_eq!(
+            stats,
+            PruneStats {
+                blocks: 2,
+                block_signatures: 1,
+                block_rejection_signer_addrs: 1,
+                burn_blocks: 3,
+                ..PruneStats::default()
+            }
+        );
+        assert!(db.block_lookup(&old_hash).unwrap().is_none());
+        assert!(db
+            .get_block_rejection_signer_addrs(&old_hash)
+            .unwrap()
+            .is_empty());
+        assert!(db.block_lookup(&old_accepted_hash).unwrap().is_none());
+        assert!(db
+            .get_block_signatures(&old_accepted_hash)
+            .unwrap()
+            .is_empty());
+        // Globally accepted blocks in the canonical tip's tenure are kept
+        assert!(db.block_lookup(&old_tip_tenure_hash).unwrap().is_some());
+        assert_eq!(
+            db.get_block_signatures(&old_tip_tenure_hash).unwrap().len(),
+            1
+        );
+        assert!(db.get_burn_block_by_ch(&ConsensusHash([3; 20])).is_err());
+        assert!(db.get_burn_block_by_ch(&ConsensusHash([4; 20])).is_ok());
+
+        // Nothing is left to prune
+        assert_eq!(db.prune(DbRetention::RewardCycles(0)).unwrap().total(), 0);
+    }
+
+    #[test]
+    fn prune_by_burn_blocks() {
+        let (mut db, old_hash, _, _) = setup_prune_db(tmp_db_path());
+
+        // Blocks inside the burn block window are kept
+        let stats = db.prune(DbRetention::BurnBlocks(45)).unwrap();
+        assert_eq!(stats.total(), 0);
+        assert!(db.block_lookup(&old_hash).unwrap().is_some());
+
+        let stats = db.prune(DbRetention::BurnBlocks(25)).unwrap();
+        assert_eq!(stats.blocks, 2);
+        assert_eq!(stats.burn_blocks, 2);
+        assert!(db.block_lookup(&old_hash).unwrap().is_none());
+        assert!(db.get_burn_block_by_ch(&ConsensusHash([3; 20])).is_ok());
+    }
+
+    #[test]
+    fn prune_empty_db_and_compact() {
+        let db_path = tmp_db_path();
+        let mut db = SignerDb::new(&db_path).expect("Failed to create signer db");
+        assert_eq!(
+            db.prune(DbRetention::RewardCycles(0)).unwrap(),
+            PruneStats::default()
+        );
+
+        let (mut db, _, _, old_tip_tenure_hash) = setup_prune_db(&db_path);
+        db.prune(DbRetention::RewardCycles(0)).unwrap();
+        db.compact().unwrap();
+        assert!(db.block_lookup(&old_tip_tenure_hash).unwrap().is_some());
+    }
+
+    #[test]
+    fn insert_and_get_block_audit_records() {
+        let db_path = tmp_db_path();
+        let db = SignerDb::new(db_path).expect("Failed to create signer db");
+        let address = StacksAddress::burn_address(false);
+        let records: Vec<_> = [5, 7, 7, 9]
+            .into_iter()
+            .map(|burn_height| {
+                let (block_info, _) = create_block_override(|b| {
+                    b.burn_height = burn_height;
+                });
+                let response = BlockResponse::accepted(
+                    block_info.signer_signature_hash(),
+                    MessageSignature([0x11; 65]),
+                    0,
+                );
+                BlockAuditRecord::new(&address, &block_info, AuditStage::NodeValidation, &response)
+            })
+            .collect();
+        for record in records.iter() {
+            db.insert_block_audit_record(record).unwrap();
+        }
+
+        assert_eq!(db.get_block_audit_records(0, None).unwrap(), records);
+        assert_eq!(
+            db.get_block_audit_records(6, Some(8)).unwrap(),
+            records[1..3]
+        );
+        assert_eq!(db.get_block_audit_records(8, None).unwrap(), records[3..]);
+        assert!(db.get_block_audit_records(10, None).unwrap().is_empty());
+    }
 }
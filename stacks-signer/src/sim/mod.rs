@@ -0,0 +1,646 @@
+// Copyright (C) 2020-2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! An in-process simulation of a signer set. [`SignerSimulation`] runs N v0 signers, each with
+//! an in-memory database, against a [`MockNode`] and routes everything they send through a
+//! simulated StackerDB bus. Delivery happens on a logical clock, one event at a time and in a
+//! fixed order, so network partitions, delayed messages, node validation results and faulty
+//! miners can be scripted deterministically.
+
+/// The scripted stacks-node the simulated signers talk to
+pub mod node;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockHeader};
+use blockstack_lib::chainstate::stacks::{
+    StacksTransaction, StacksTransactionSigner, TenureChangeCause, TenureChangePayload,
+    TransactionAuth, TransactionPayload, TransactionVersion,
+};
+use blockstack_lib::net::api::getsortition::SortitionInfo;
+use blockstack_lib::net::api::postblock_proposal::{
+    BlockValidateOk, BlockValidateReject, BlockValidateResponse, ValidateRejectCode,
+};
+use clarity::vm::costs::ExecutionCost;
+use libsigner::v0::messages::{BlockResponse, SignerMessage};
+use libsigner::{
+    BlockProposal, BlockProposalData, KeyBackend, KeyBackendError, SignerEntries, SignerEvent,
+};
+use stacks_common::bitvec::BitVec;
+use stacks_common::codec::StacksMessageCodec;
+use stacks_common::types::chainstate::{
+    ConsensusHash, StacksAddress, StacksPrivateKey, StacksPublicKey,
+};
+use stacks_common::util::get_epoch_time_secs;
+use stacks_common::util::hash::{Hash160, MerkleTree, Sha512Trunc256Sum};
+use stacks_common::util::secp256k1::MessageSignature;
+
+pub use self::node::{MockNode, NodeEvent};
+use crate::chainstate::v1::SortitionsView;
+use crate::client::{SignerSlotID, StacksClient};
+use crate::config::{GlobalConfig, SignerConfig, SignerConfigMode};
+use crate::runloop::SignerResult;
+use crate::signerdb::BlockState;
+use crate::v0::signer::Signer;
+use crate::v0::signer_state::SUPPORTED_SIGNER_PROTOCOL_VERSION;
+use crate::Signer as SignerTrait;
+
+/// The reward cycle that simulated signers sign for
+pub const SIM_REWARD_CYCLE: u64 = 10;
+
+/// The number of ticks after which `run_until_quiet` gives up on a simulation settling
+const MAX_QUIET_TICKS: u64 = 10_000;
+
+/// A miner taking part in a simulation
+#[derive(Debug, Clone)]
+pub struct SimMiner {
+    private_key: StacksPrivateKey,
+}
+
+impl Default for SimMiner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimMiner {
+    /// A miner with a random key
+    pub fn new() -> Self {
+        Self {
+            private_key: StacksPrivateKey::random(),
+        }
+    }
+
+    /// The hash of the miner's public key, as recorded in the sortitions it wins
+    pub fn public_key_hash(&self) -> Hash160 {
+        Hash160::from_node_public_key(&StacksPublicKey::from_private(&self.private_key))
+    }
+
+    /// Sign the block header with the miner's key. Call this again after changing a block.
+    pub fn sign_block(&self, block: &mut NakamotoBlock) {
+        block
+            .header
+            .sign_miner(&self.private_key)
+            .expect("Failed to sign block as miner");
+    }
+
+    /// Make a signed tenure change transaction
+    fn make_tenure_change_tx(&self, payload: TenureChangePayload) -> StacksTransaction {
+        let auth = TransactionAuth::from_p2pkh(&self.private_key)
+            .expect("Failed to make p2pkh auth for the miner");
+        let tx = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth,
+            TransactionPayload::TenureChange(payload),
+        );
+        let mut tx_signer = StacksTransactionSigner::new(&tx);
+        tx_signer
+            .sign_origin(&self.private_key)
+            .expect("Failed to sign tenure change");
+        tx_signer
+            .get_tx()
+            .expect("Failed to get signed tenure change")
+    }
+}
+
+/// How the mock node answers a signer's request to validate a block
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationOutcome {
+    /// The block is valid
+    Accept,
+    /// The block is invalid for the given reason
+    Reject(ValidateRejectCode),
+    /// The node never answers
+    Drop,
+}
+
+/// A signer's key, which can be made to fail like an unreachable keyholder
+#[derive(Debug)]
+struct SimKeyBackend {
+    private_key: StacksPrivateKey,
+    failing: AtomicBool,
+}
+
+impl KeyBackend for SimKeyBackend {
+    fn public_key(&self) -> StacksPublicKey {
+        StacksPublicKey::from_private(&self.private_key)
+    }
+
+    fn sign_digest(&self, digest: &[u8]) -> Result<MessageSignature, KeyBackendError> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(KeyBackendError::SigningFailed(
+                "Simulated keyholder is unreachable".into(),
+            ));
+        }
+        self.private_key.sign_digest(digest)
+    }
+}
+
+/// A signer taking part in a simulation
+struct SimSigner {
+    signer: Signer,
+    client: StacksClient,
+    public_key: StacksPublicKey,
+    key_backend: Arc<SimKeyBackend>,
+    sortition_state: Option<SortitionsView>,
+}
+
+/// An event waiting on the logical clock to be delivered to a signer
+struct ScheduledEvent {
+    signer: usize,
+    event: SignerEvent<SignerMessage>,
+}
+
+/// A set of signers, a miner and a mock node, run in-process on a logical clock
+pub struct SignerSimulation {
+    node: MockNode,
+    signers: Vec<SimSigner>,
+    miner: SimMiner,
+    reward_cycle: u64,
+    /// The current logical time
+    tick: u64,
+    /// Orders events scheduled for the same tick
+    next_seq: u64,
+    queue: BTreeMap<(u64, u64), ScheduledEvent>,
+    /// The group each signer is in, if the signers are partitioned
+    partition: Option<Vec<usize>>,
+    /// Extra ticks a message takes from one signer to another
+    link_delays: HashMap<(usize, usize), u64>,
+    validation: HashMap<Sha512Trunc256Sum, ValidationOutcome>,
+    /// Every block response a signer wrote to StackerDB, in the order they were written
+    responses: Vec<(usize, BlockResponse)>,
+    res_send: Sender<SignerResult>,
+    _res_recv: Receiver<SignerResult>,
+}
+
+impl SignerSimulation {
+    /// Start `num_signers` equally weighted signers and a miner. The miner wins the first
+    /// tenure, and the signers have exchanged their state machine updates for it by the time
+    /// this returns, so the set is ready to evaluate the miner's first block.
+    pub fn new(num_signers: u32) -> Self {
+        assert!(num_signers > 0, "A simulation needs at least one signer");
+        let miner = SimMiner::new();
+        let node = MockNode::start(SIM_REWARD_CYCLE, miner.public_key_hash())
+            .expect("Failed to start mock node");
+        let reward_cycle = node.reward_cycle();
+
+        let private_keys: Vec<_> = (0..num_signers)
+            .map(|_| StacksPrivateKey::random())
+            .collect();
+        let signer_entries = Self::make_signer_entries(&private_keys);
+        let (res_send, res_recv) = channel();
+        let mut sim = Self {
+            node,
+            signers: vec![],
+            miner,
+            reward_cycle,
+            tick: 0,
+            next_seq: 0,
+            queue: BTreeMap::new(),
+            partition: None,
+            link_delays: HashMap::new(),
+            validation: HashMap::new(),
+            responses: vec![],
+            res_send,
+            _res_recv: res_recv,
+        };
+        for (signer_id, private_key) in (0..num_signers).zip(private_keys) {
+            let signer = sim.make_signer(signer_id, private_key, &signer_entries);
+            sim.signers.push(signer);
+        }
+        sim.start_tenure();
+        sim.run_until_quiet();
+        sim
+    }
+
+    fn make_signer_entries(private_keys: &[StacksPrivateKey]) -> SignerEntries {
+        let num_signers = u32::try_from(private_keys.len()).expect("Too many signers");
+        let weight = 100 / num_signers;
+        let mut entries = SignerEntries {
+            signer_addr_to_id: HashMap::new(),
+            signer_id_to_addr: BTreeMap::new(),
+            signer_id_to_pk: HashMap::new(),
+            signer_pk_to_id: HashMap::new(),
+            signer_pks: vec![],
+            signer_addresses: vec![],
+            signer_addr_to_weight: HashMap::new(),
+        };
+        for (signer_id, private_key) in (0..num_signers).zip(private_keys) {
+            let public_key = StacksPublicKey::from_private(private_key);
+            let address = StacksAddress::p2pkh(false, &public_key);
+            entries.signer_addr_to_id.insert(address, signer_id);
+            entries.signer_id_to_addr.insert(signer_id, address);
+            entries.signer_id_to_pk.insert(signer_id, public_key);
+            entries.signer_pk_to_id.insert(public_key, signer_id);
+            entries.signer_pks.push(public_key);
+            entries.signer_addresses.push(address);
+            entries.signer_addr_to_weight.insert(address, weight);
+        }
+        entries
+    }
+
+    fn make_signer(
+        &self,
+        signer_id: u32,
+        private_key: StacksPrivateKey,
+        signer_entries: &SignerEntries,
+    ) -> SimSigner {
+        let config = GlobalConfig::load_from_str(&format!(
+            r#"
+stacks_private_key = "{}"
+node_host = "{}"
+endpoint = "127.0.0.1:0"
+network = "testnet"
+auth_password = "sim-signer-{signer_id}"
+db_path = ":memory:"
+"#,
+            private_key.to_hex(),
+            self.node.host(),
+        ))
+        .expect("Failed to load simulated signer config");
+        let client = StacksClient::from(&config);
+        let key_backend = Arc::new(SimKeyBackend {
+            private_key,
+            failing: AtomicBool::new(false),
+        });
+        let signer_config = SignerConfig {
+            reward_cycle: self.reward_cycle,
+            signer_entries: signer_entries.clone(),
+            signer_slot_ids: signer_entries
+                .signer_id_to_addr
+                .keys()
+                .map(|id| SignerSlotID(*id))
+                .collect(),
+            signing_key: key_backend.clone(),
+            node_host: config.node_host.clone(),
+            node_stackerdb_tls_config: None,
+            auth_password: config.auth_password.clone(),
+            mainnet: false,
+            db_path: config.db_path.clone(),
+            first_proposal_burn_block_timing: config.first_proposal_burn_block_timing,
+            block_proposal_timeout: config.block_proposal_timeout,
+            tenure_last_block_proposal_timeout: config.tenure_last_block_proposal_timeout,
+            block_proposal_validation_timeout: config.block_proposal_validation_timeout,
+            tenure_idle_timeout: config.tenure_idle_timeout,
+            tenure_idle_timeout_buffer: config.tenure_idle_timeout_buffer,
+            block_proposal_max_age_secs: config.block_proposal_max_age_secs,
+            reorg_attempts_activity_timeout: config.reorg_attempts_activity_timeout,
+            signer_mode: SignerConfigMode::Normal {
+                signer_id,
+                signer_slot_id: SignerSlotID(signer_id),
+            },
+            proposal_wait_for_parent_time: config.proposal_wait_for_parent_time,
+            validate_with_replay_tx: config.validate_with_replay_tx,
+            capitulate_miner_view_timeout: config.capitulate_miner_view_timeout,
+            block_policy: config.block_policy.clone(),
+            admin_state: config.admin_state.clone(),
+            block_validations: Arc::default(),
+            supported_signer_protocol_version: SUPPORTED_SIGNER_PROTOCOL_VERSION,
+        };
+        SimSigner {
+            signer: Signer::new(&client, signer_config),
+            client,
+            public_key: StacksPublicKey::from_private(&private_key),
+            key_backend,
+            sortition_state: None,
+        }
+    }
+
+    /// The mock node the signers are connected to
+    pub fn node(&self) -> &MockNode {
+        &self.node
+    }
+
+    /// The number of signers in the set
+    pub fn num_signers(&self) -> usize {
+        self.signers.len()
+    }
+
+    /// The signer at `index`
+    pub fn signer(&self, index: usize) -> &Signer {
+        &self.signers[index].signer
+    }
+
+    /// The miner that wins the sortitions started with [`Self::start_tenure`]
+    pub fn miner(&self) -> &SimMiner {
+        &self.miner
+    }
+
+    /// Replace the miner that wins subsequent sortitions
+    pub fn set_miner(&mut self, miner: SimMiner) {
+        self.miner = miner;
+    }
+
+    /// Mine a burn block won by the current miner, and notify every signer of it
+    pub fn start_tenure(&mut self) -> SortitionInfo {
+        let winner = self.miner.public_key_hash();
+        self.mine_burn_block(Some(winner))
+    }
+
+    /// Mine a burn block, won by the miner with public key hash `winner` if set, and notify
+    /// every signer of it
+    pub fn mine_burn_block(&mut self, winner: Option<Hash160>) -> SortitionInfo {
+        let parent_burn_block_hash = self.node.latest_sortition().burn_block_hash;
+        let sortition = self.node.advance_burn_block(winner);
+        for signer in 0..self.signers.len() {
+            let event = SignerEvent::NewBurnBlock {
+                burn_height: sortition.burn_block_height,
+                burn_header_hash: sortition.burn_block_hash,
+                consensus_hash: sortition.consensus_hash,
+                received_time: SystemTime::now(),
+                parent_burn_block_hash,
+            };
+            self.schedule(signer, 1, event);
+        }
+        sortition
+    }
+
+    /// Build the next block on the node's Stacks tip, in the tenure of the latest winning
+    /// sortition, signed by the current miner. The first block of a tenure carries its
+    /// tenure change.
+    pub fn build_block(&self) -> NakamotoBlock {
+        let tip = self.node.stacks_tip();
+        let tenure_id = self.current_tenure_id();
+        let mut txs = vec![];
+        if tip.consensus_hash != tenure_id {
+            txs.push(self.miner.make_tenure_change_tx(TenureChangePayload {
+                tenure_consensus_hash: tenure_id,
+                prev_tenure_consensus_hash: tip.consensus_hash,
+                burn_view_consensus_hash: tenure_id,
+                previous_tenure_end: tip.block_id(),
+                previous_tenure_blocks: self.node.tenure_block_count(&tip.consensus_hash),
+                cause: TenureChangeCause::BlockFound,
+                pubkey_hash: self.miner.public_key_hash(),
+            }));
+        }
+        let txids: Vec<_> = txs.iter().map(|tx| tx.txid().as_bytes().to_vec()).collect();
+        let header = NakamotoBlockHeader {
+            version: 1,
+            chain_length: tip.chain_length + 1,
+            consensus_hash: tenure_id,
+            parent_block_id: tip.block_id(),
+            tx_merkle_root: MerkleTree::<Sha512Trunc256Sum>::new(&txids).root(),
+            timestamp: get_epoch_time_secs(),
+            pox_treatment: BitVec::ones(1).expect("BUG: bitvec of length-1 failed to construct"),
+            ..NakamotoBlockHeader::empty()
+        };
+        let mut block = NakamotoBlock { header, txs };
+        self.miner.sign_block(&mut block);
+        block
+    }
+
+    /// The consensus hash of the latest winning sortition
+    fn current_tenure_id(&self) -> ConsensusHash {
+        let latest = self.node.latest_sortition();
+        if latest.was_sortition {
+            latest.consensus_hash
+        } else {
+            latest
+                .last_sortition_ch
+                .expect("No winning sortition to build a block in")
+        }
+    }
+
+    /// Send the block proposal to every signer
+    pub fn propose_block(&mut self, block: &NakamotoBlock) {
+        let signers: Vec<_> = (0..self.signers.len()).collect();
+        self.propose_block_to(block, &signers);
+    }
+
+    /// Send the block proposal to the given signers only
+    pub fn propose_block_to(&mut self, block: &NakamotoBlock, signers: &[usize]) {
+        let proposal = BlockProposal {
+            block: block.clone(),
+            burn_height: self.node.latest_sortition().burn_block_height,
+            reward_cycle: self.reward_cycle,
+            block_proposal_data: BlockProposalData::empty(),
+        };
+        for signer in signers {
+            let event =
+                SignerEvent::MinerMessages(vec![SignerMessage::BlockProposal(proposal.clone())]);
+            self.schedule(*signer, 1, event);
+        }
+    }
+
+    /// Script the node's answer when a signer asks it to validate the block with the given
+    /// signer signature hash. Blocks are valid unless scripted otherwise.
+    pub fn set_validation(
+        &mut self,
+        signer_signature_hash: Sha512Trunc256Sum,
+        outcome: ValidationOutcome,
+    ) {
+        self.validation.insert(signer_signature_hash, outcome);
+    }
+
+    /// Split the signers into groups that cannot exchange StackerDB messages. Signers not
+    /// listed are cut off from everybody. Messages sent across the partition are lost.
+    pub fn partition(&mut self, groups: &[&[usize]]) {
+        let mut membership: Vec<_> = (0..self.signers.len())
+            .map(|signer| groups.len() + signer)
+            .collect();
+        for (group, members) in groups.iter().enumerate() {
+            for signer in members.iter() {
+                membership[*signer] = group;
+            }
+        }
+        self.partition = Some(membership);
+    }
+
+    /// Remove any partition between the signers
+    pub fn heal(&mut self) {
+        self.partition = None;
+    }
+
+    /// Make every signature the given signer asks for fail, or succeed again
+    pub fn set_signing_fails(&mut self, signer: usize, fails: bool) {
+        self.signers[signer]
+            .key_backend
+            .failing
+            .store(fails, Ordering::SeqCst);
+    }
+
+    /// Delay StackerDB messages from signer `from` to signer `to` by `ticks`
+    pub fn set_link_delay(&mut self, from: usize, to: usize, ticks: u64) {
+        self.link_delays.insert((from, to), ticks);
+    }
+
+    fn can_reach(&self, from: usize, to: usize) -> bool {
+        self.partition
+            .as_ref()
+            .is_none_or(|membership| membership[from] == membership[to])
+    }
+
+    fn schedule(&mut self, signer: usize, delay: u64, event: SignerEvent<SignerMessage>) {
+        let key = (self.tick + delay, self.next_seq);
+        self.next_seq += 1;
+        self.queue.insert(key, ScheduledEvent { signer, event });
+    }
+
+    /// Route what the signers sent to the node onto the logical clock
+    fn route_node_events(&mut self) {
+        for node_event in self.node.take_events() {
+            match node_event {
+                NodeEvent::StackerDBMessage {
+                    signer_public_key,
+                    message,
+                } => {
+                    let Some(from) = self
+                        .signers
+                        .iter()
+                        .position(|signer| signer.public_key == signer_public_key)
+                    else {
+                        continue;
+                    };
+                    if let SignerMessage::BlockResponse(response) = &message {
+                        self.responses.push((from, response.clone()));
+                    }
+                    for to in 0..self.signers.len() {
+                        if !self.can_reach(from, to) {
+                            continue;
+                        }
+                        let delay = 1 + self.link_delays.get(&(from, to)).copied().unwrap_or(0);
+                        let event = SignerEvent::SignerMessages {
+                            signer_set: u32::try_from(self.reward_cycle % 2).expect("infallible"),
+                            messages: vec![(signer_public_key, message.clone())],
+                            received_time: SystemTime::now(),
+                        };
+                        self.schedule(to, delay, event);
+                    }
+                }
+                NodeEvent::BlockProposal { auth, block } => {
+                    let Some(signer) = auth
+                        .strip_prefix("sim-signer-")
+                        .and_then(|id| id.parse::<usize>().ok())
+                    else {
+                        continue;
+                    };
+                    let signer_signature_hash = block.header.signer_signature_hash();
+                    let response = match self
+                        .validation
+                        .get(&signer_signature_hash)
+                        .unwrap_or(&ValidationOutcome::Accept)
+                    {
+                        ValidationOutcome::Accept => BlockValidateResponse::Ok(BlockValidateOk {
+                            signer_signature_hash,
+                            cost: ExecutionCost::ZERO,
+                            size: u64::try_from(block.serialize_to_vec().len())
+                                .expect("infallible"),
+                            validation_time_ms: 0,
+                            replay_tx_hash: None,
+                            replay_tx_exhausted: false,
+                        }),
+                        ValidationOutcome::Reject(reason_code) => {
+                            BlockValidateResponse::Reject(BlockValidateReject {
+                                signer_signature_hash,
+                                reason: "Rejected by the simulation".into(),
+                                reason_code: *reason_code,
+                            })
+                        }
+                        ValidationOutcome::Drop => continue,
+                    };
+                    self.schedule(signer, 1, SignerEvent::BlockValidationResponse(response));
+                }
+                NodeEvent::BlockPosted(block) => {
+                    for signer in 0..self.signers.len() {
+                        let event = SignerEvent::NewBlock {
+                            block_id: block.block_id(),
+                            consensus_hash: block.header.consensus_hash,
+                            signer_sighash: Some(block.header.signer_signature_hash()),
+                            block_height: block.header.chain_length,
+                            transactions: block.txs.clone(),
+                        };
+                        self.schedule(signer, 1, event);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Advance the logical clock to the next tick with scheduled events and deliver them.
+    /// Returns false if there was nothing left to deliver.
+    pub fn step(&mut self) -> bool {
+        self.route_node_events();
+        let Some((&(tick, _), _)) = self.queue.first_key_value() else {
+            return false;
+        };
+        self.tick = tick;
+        while let Some(entry) = self.queue.first_entry() {
+            if entry.key().0 != tick {
+                break;
+            }
+            let ScheduledEvent { signer, event } = entry.remove();
+            let sim_signer = &mut self.signers[signer];
+            sim_signer.signer.process_event(
+                &sim_signer.client,
+                &mut sim_signer.sortition_state,
+                Some(&event),
+                &self.res_send,
+                self.reward_cycle,
+            );
+            self.route_node_events();
+        }
+        true
+    }
+
+    /// Deliver events until the signers stop sending messages
+    pub fn run_until_quiet(&mut self) {
+        let start = self.tick;
+        while self.step() {
+            assert!(
+                self.tick - start < MAX_QUIET_TICKS,
+                "Simulation did not settle after {MAX_QUIET_TICKS} ticks"
+            );
+        }
+    }
+
+    /// The current logical time
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// The state of the block in the given signer's database, if it stored the block
+    pub fn block_state(
+        &self,
+        signer: usize,
+        signer_signature_hash: &Sha512Trunc256Sum,
+    ) -> Option<BlockState> {
+        self.signer(signer)
+            .signer_db
+            .block_lookup(signer_signature_hash)
+            .expect("Failed to look up block")
+            .map(|block_info| block_info.state)
+    }
+
+    /// The responses signers wrote for the block, along with the index of each signer
+    pub fn block_responses(
+        &self,
+        signer_signature_hash: &Sha512Trunc256Sum,
+    ) -> Vec<(usize, &BlockResponse)> {
+        self.responses
+            .iter()
+            .filter(|(_, response)| &response.get_signer_signature_hash() == signer_signature_hash)
+            .map(|(signer, response)| (*signer, response))
+            .collect()
+    }
+
+    /// Every block the signers posted to the node
+    pub fn posted_blocks(&self) -> Vec<NakamotoBlock> {
+        self.node.posted_blocks()
+    }
+}
@@ -0,0 +1,676 @@
+// Copyright (C) 2020-2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A scripted stacks-node that serves the RPC endpoints a signer talks to. The node keeps a
+//! simple burnchain of sortitions and a single Stacks fork, stores the signers' StackerDB
+//! chunks, and queues everything the signers send it as [`NodeEvent`]s so that a simulation
+//! can decide when (and whether) each one is delivered.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+
+use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockHeader};
+use blockstack_lib::chainstate::stacks::boot::POX_4_NAME;
+use blockstack_lib::chainstate::stacks::db::StacksBlockHeaderTypes;
+use blockstack_lib::net::api::get_tenures_fork_info::TenureForkingInfo;
+use blockstack_lib::net::api::getpoxinfo::{
+    RPCPoxCurrentCycleInfo, RPCPoxEpoch, RPCPoxInfoData, RPCPoxNextCycleInfo,
+};
+use blockstack_lib::net::api::getsortition::SortitionInfo;
+use blockstack_lib::net::api::postblock::StacksBlockAcceptedData;
+use blockstack_lib::net::api::postblock_proposal::NakamotoBlockProposal;
+use blockstack_lib::net::api::poststackerdbchunk::StackerDBErrorCodes;
+use blockstack_lib::util_lib::boot::boot_code_id;
+use clarity::codec::read_next;
+use clarity::vm::costs::ExecutionCost;
+use libsigner::v0::messages::{PeerInfo, SignerMessage};
+use libstackerdb::{StackerDBChunkAckData, StackerDBChunkData};
+use stacks_common::deps_common::httparse;
+use stacks_common::types::chainstate::{
+    BurnchainHeaderHash, ConsensusHash, SortitionId, StacksBlockId, StacksPublicKey,
+};
+use stacks_common::types::StacksEpochId;
+use stacks_common::util::get_epoch_time_secs;
+use stacks_common::util::hash::{Hash160, Sha256Sum, Sha512Trunc256Sum};
+use stacks_common::{debug, warn};
+
+/// The number of burn blocks in a simulated reward cycle
+pub const SIM_REWARD_CYCLE_LENGTH: u64 = 20;
+/// The number of burn blocks in a simulated prepare phase
+pub const SIM_PREPARE_PHASE_LENGTH: u64 = 5;
+
+/// Something a signer sent to the mock node, waiting to be routed by the simulation
+#[derive(Debug, Clone)]
+pub enum NodeEvent {
+    /// A signer wrote a message to the signers' StackerDB
+    StackerDBMessage {
+        /// The public key that signed the chunk
+        signer_public_key: StacksPublicKey,
+        /// The message stored in the chunk
+        message: SignerMessage,
+    },
+    /// A signer submitted a block proposal for validation
+    BlockProposal {
+        /// The authorization header the proposal was submitted with
+        auth: String,
+        /// The proposed block
+        block: NakamotoBlock,
+    },
+    /// A signer posted a block the node had not seen before
+    BlockPosted(NakamotoBlock),
+}
+
+/// The chain and StackerDB state served by the mock node
+#[derive(Debug)]
+struct NodeState {
+    /// The reward cycle the burnchain is in
+    reward_cycle: u64,
+    /// Every burn block, in height order
+    sortitions: Vec<SortitionInfo>,
+    /// The highest known block header of each tenure
+    tenure_tips: HashMap<ConsensusHash, NakamotoBlockHeader>,
+    /// The canonical Stacks tip
+    stacks_tip: NakamotoBlockHeader,
+    /// Every block posted to the node, in the order it was received
+    posted_blocks: Vec<NakamotoBlock>,
+    /// The latest chunk in each (contract name, slot ID)
+    chunks: HashMap<(String, u32), StackerDBChunkData>,
+    /// Events waiting to be taken by the simulation
+    events: VecDeque<NodeEvent>,
+}
+
+/// An HTTP server that plays the part of the stacks-node a signer is connected to
+#[derive(Debug)]
+pub struct MockNode {
+    addr: SocketAddr,
+    state: Arc<Mutex<NodeState>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockNode {
+    /// Start a node whose burnchain begins with a genesis sortition at the start of
+    /// `reward_cycle`, won by the miner with public key hash `miner_pkh`. The genesis tenure
+    /// holds the genesis Stacks block.
+    pub fn start(reward_cycle: u64, miner_pkh: Hash160) -> io::Result<Self> {
+        let height = reward_cycle * SIM_REWARD_CYCLE_LENGTH + 1;
+        let consensus_hash = sim_consensus_hash(height);
+        let genesis = SortitionInfo {
+            burn_block_hash: sim_burn_block_hash(height),
+            burn_block_height: height,
+            burn_header_timestamp: get_epoch_time_secs(),
+            sortition_id: sim_sortition_id(height),
+            parent_sortition_id: sim_sortition_id(height - 1),
+            consensus_hash,
+            was_sortition: true,
+            miner_pk_hash160: Some(miner_pkh),
+            stacks_parent_ch: Some(consensus_hash),
+            last_sortition_ch: Some(consensus_hash),
+            committed_block_hash: None,
+            vrf_seed: None,
+        };
+        let mut genesis_header = NakamotoBlockHeader::empty();
+        genesis_header.consensus_hash = consensus_hash;
+        let state = NodeState {
+            reward_cycle,
+            sortitions: vec![genesis],
+            tenure_tips: HashMap::from([(consensus_hash, genesis_header.clone())]),
+            stacks_tip: genesis_header,
+            posted_blocks: vec![],
+            chunks: HashMap::new(),
+            events: VecDeque::new(),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(state));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let state = state.clone();
+            let stop = stop.clone();
+            thread::Builder::new()
+                .name("sim-mock-node".into())
+                .spawn(move || {
+                    for stream in listener.incoming() {
+                        if stop.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        let Ok(mut stream) = stream else {
+                            continue;
+                        };
+                        if let Err(e) = handle_connection(&state, &mut stream) {
+                            warn!("Mock node: failed to handle request: {e}");
+                        }
+                    }
+                })?
+        };
+        Ok(Self {
+            addr,
+            state,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// The `host:port` the node is listening on
+    pub fn host(&self) -> String {
+        self.addr.to_string()
+    }
+
+    fn state(&self) -> MutexGuard<'_, NodeState> {
+        self.state.lock().expect("Mock node state poisoned")
+    }
+
+    /// The reward cycle the burnchain is in
+    pub fn reward_cycle(&self) -> u64 {
+        self.state().reward_cycle
+    }
+
+    /// Mine a burn block. If `winner` is set, the sortition is won by the miner with that
+    /// public key hash, committing to the current Stacks tip's tenure.
+    pub fn advance_burn_block(&self, winner: Option<Hash160>) -> SortitionInfo {
+        let mut state = self.state();
+        let parent = state
+            .sortitions
+            .last()
+            .cloned()
+            .expect("Mock node has no genesis sortition");
+        let last_winner = state
+            .sortitions
+            .iter()
+            .rev()
+            .find(|sortition| sortition.was_sortition)
+            .map(|sortition| sortition.consensus_hash);
+        let height = parent.burn_block_height + 1;
+        let sortition = SortitionInfo {
+            burn_block_hash: sim_burn_block_hash(height),
+            burn_block_height: height,
+            burn_header_timestamp: get_epoch_time_secs(),
+            sortition_id: sim_sortition_id(height),
+            parent_sortition_id: parent.sortition_id,
+            consensus_hash: sim_consensus_hash(height),
+            was_sortition: winner.is_some(),
+            miner_pk_hash160: winner,
+            stacks_parent_ch: winner.map(|_| state.stacks_tip.consensus_hash),
+            last_sortition_ch: last_winner,
+            committed_block_hash: None,
+            vrf_seed: None,
+        };
+        state.sortitions.push(sortition.clone());
+        state.reward_cycle = height / SIM_REWARD_CYCLE_LENGTH;
+        sortition
+    }
+
+    /// The most recent burn block
+    pub fn latest_sortition(&self) -> SortitionInfo {
+        self.state()
+            .sortitions
+            .last()
+            .cloned()
+            .expect("Mock node has no genesis sortition")
+    }
+
+    /// The header of the canonical Stacks tip
+    pub fn stacks_tip(&self) -> NakamotoBlockHeader {
+        self.state().stacks_tip.clone()
+    }
+
+    /// The number of posted blocks in the given tenure
+    pub fn tenure_block_count(&self, tenure_id: &ConsensusHash) -> u32 {
+        let count = self
+            .state()
+            .posted_blocks
+            .iter()
+            .filter(|block| &block.header.consensus_hash == tenure_id)
+            .count();
+        u32::try_from(count).unwrap_or(u32::MAX)
+    }
+
+    /// Every block posted to the node, in the order it was received
+    pub fn posted_blocks(&self) -> Vec<NakamotoBlock> {
+        self.state().posted_blocks.clone()
+    }
+
+    /// Take the events the signers have sent to the node since the last call
+    pub fn take_events(&self) -> Vec<NodeEvent> {
+        self.state().events.drain(..).collect()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the listener up so it can see the stop flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// A simulated consensus hash for the burn block at `height`
+fn sim_consensus_hash(height: u64) -> ConsensusHash {
+    ConsensusHash(Hash160::from_data(&height.to_be_bytes()).0)
+}
+
+/// A simulated burn header hash for the burn block at `height`
+fn sim_burn_block_hash(height: u64) -> BurnchainHeaderHash {
+    BurnchainHeaderHash(Sha256Sum::from_data(&height.to_be_bytes()).0)
+}
+
+/// A simulated sortition ID for the burn block at `height`
+fn sim_sortition_id(height: u64) -> SortitionId {
+    SortitionId(Sha512Trunc256Sum::from_data(&height.to_be_bytes()).0)
+}
+
+/// An HTTP response from the mock node
+struct Response {
+    status: u16,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json<T: serde::Serialize>(status: u16, value: &T) -> Self {
+        Self {
+            status,
+            body: serde_json::to_vec(value).expect("Failed to serialize mock node response"),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: 404,
+            body: vec![],
+        }
+    }
+
+    fn bad_request(reason: &str) -> Self {
+        Self {
+            status: 400,
+            body: reason.as_bytes().to_vec(),
+        }
+    }
+}
+
+/// Read one request from the stream, answer it, and close the connection
+fn handle_connection(state: &Mutex<NodeState>, stream: &mut TcpStream) -> io::Result<()> {
+    let mut buf = vec![];
+    let mut read_buf = [0u8; 4096];
+    let (verb, path, auth, body_offset, content_length) = loop {
+        let n = stream.read(&mut read_buf)?;
+        if n == 0 {
+            // The connection was closed before a full request arrived (e.g. the stop wakeup)
+            return Ok(());
+        }
+        buf.extend_from_slice(&read_buf[..n]);
+        let mut headers = [httparse::EMPTY_HEADER; 32];
+        let mut req = httparse::Request::new(&mut headers);
+        let Ok(httparse::Status::Complete(body_offset)) = req.parse(&buf) else {
+            continue;
+        };
+        let header_value = |name: &str| {
+            req.headers
+                .iter()
+                .find(|header| header.name.eq_ignore_ascii_case(name))
+                .map(|header| String::from_utf8_lossy(header.value).to_string())
+        };
+        let content_length = header_value("content-length")
+            .and_then(|len| len.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        let auth = header_value("authorization").unwrap_or_default();
+        break (
+            req.method.unwrap_or_default().to_string(),
+            req.path.unwrap_or_default().to_string(),
+            auth,
+            body_offset,
+            content_length,
+        );
+    };
+    while buf.len() < body_offset + content_length {
+        let n = stream.read(&mut read_buf)?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&read_buf[..n]);
+    }
+    let body = &buf[body_offset..];
+
+    debug!("Mock node: {verb} {path}");
+    let response = {
+        let mut state = state.lock().expect("Mock node state poisoned");
+        route(&mut state, &verb, &path, &auth, body)
+    };
+
+    let reason = match response.status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    let header = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.body.len()
+    );
+    stream.write_all(header.as_bytes())?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// Dispatch a request to the handler for its endpoint
+fn route(state: &mut NodeState, verb: &str, path: &str, auth: &str, body: &[u8]) -> Response {
+    let path = path.split('?').next().unwrap_or_default();
+    let parts: Vec<_> = path.trim_matches('/').split('/').collect();
+    match (verb, parts.as_slice()) {
+        ("GET", ["v2", "info"]) => get_info(state),
+        ("GET", ["v2", "pox"]) => get_pox(state),
+        ("GET", ["v3", "sortitions", "latest_and_last"]) => get_latest_and_last(state),
+        ("GET", ["v3", "sortitions", "consensus", ch]) => get_sortition(state, ch),
+        ("GET", ["v3", "tenures", "tip", ch]) => get_tenure_tip(state, ch),
+        ("GET", ["v3", "tenures", "fork_info", start, stop]) => {
+            get_tenure_fork_info(state, start, stop)
+        }
+        ("GET", ["v2", "stackerdb", _, contract, slot_id, ..]) => {
+            get_stackerdb_chunk(state, contract, slot_id)
+        }
+        ("POST", ["v2", "stackerdb", _, contract, "chunks"]) => {
+            post_stackerdb_chunk(state, contract, body)
+        }
+        ("POST", ["v3", "block_proposal"]) => post_block_proposal(state, auth, body),
+        ("POST", ["v3", "blocks", "upload"]) => post_block(state, body),
+        _ => Response::not_found(),
+    }
+}
+
+fn get_info(state: &NodeState) -> Response {
+    let latest = state
+        .sortitions
+        .last()
+        .expect("Mock node has no genesis sortition");
+    Response::json(
+        200,
+        &PeerInfo {
+            burn_block_height: latest.burn_block_height,
+            stacks_tip_consensus_hash: state.stacks_tip.consensus_hash,
+            stacks_tip: state.stacks_tip.block_hash(),
+            stacks_tip_height: state.stacks_tip.chain_length,
+            pox_consensus: latest.consensus_hash,
+            server_version: "stacks-signer simulation".into(),
+            network_id: 0,
+        },
+    )
+}
+
+fn get_pox(state: &NodeState) -> Response {
+    let height = state
+        .sortitions
+        .last()
+        .expect("Mock node has no genesis sortition")
+        .burn_block_height;
+    let next_cycle_start = (state.reward_cycle + 1) * SIM_REWARD_CYCLE_LENGTH;
+    let prepare_phase_start = next_cycle_start - SIM_PREPARE_PHASE_LENGTH;
+    Response::json(
+        200,
+        &RPCPoxInfoData {
+            contract_id: boot_code_id(POX_4_NAME, false).to_string(),
+            pox_activation_threshold_ustx: 0,
+            first_burnchain_block_height: 0,
+            current_burnchain_block_height: height,
+            prepare_phase_block_length: SIM_PREPARE_PHASE_LENGTH,
+            reward_phase_block_length: SIM_REWARD_CYCLE_LENGTH - SIM_PREPARE_PHASE_LENGTH,
+            reward_slots: 0,
+            rejection_fraction: None,
+            total_liquid_supply_ustx: 0,
+            current_cycle: RPCPoxCurrentCycleInfo {
+                id: state.reward_cycle,
+                min_threshold_ustx: 0,
+                stacked_ustx: 0,
+                is_pox_active: true,
+            },
+            next_cycle: RPCPoxNextCycleInfo {
+                id: state.reward_cycle + 1,
+                min_threshold_ustx: 0,
+                min_increment_ustx: 0,
+                stacked_ustx: 0,
+                prepare_phase_start_block_height: prepare_phase_start,
+                blocks_until_prepare_phase: i64::try_from(prepare_phase_start)
+                    .unwrap_or(i64::MAX)
+                    .saturating_sub(i64::try_from(height).unwrap_or(i64::MAX)),
+                reward_phase_start_block_height: next_cycle_start,
+                blocks_until_reward_phase: next_cycle_start.saturating_sub(height),
+                ustx_until_pox_rejection: None,
+            },
+            min_amount_ustx: 0,
+            prepare_cycle_length: SIM_PREPARE_PHASE_LENGTH,
+            reward_cycle_id: state.reward_cycle,
+            epochs: vec![RPCPoxEpoch {
+                start_height: 0,
+                end_height: u64::MAX,
+                block_limit: ExecutionCost::max_value(),
+                epoch_id: StacksEpochId::Epoch30,
+                network_epoch: 0,
+            }],
+            reward_cycle_length: SIM_REWARD_CYCLE_LENGTH,
+            rejection_votes_left_required: None,
+            next_reward_cycle_in: next_cycle_start.saturating_sub(height),
+            contract_versions: vec![],
+        },
+    )
+}
+
+fn find_sortition<'a>(state: &'a NodeState, ch: &ConsensusHash) -> Option<&'a SortitionInfo> {
+    state
+        .sortitions
+        .iter()
+        .find(|sortition| &sortition.consensus_hash == ch)
+}
+
+fn get_latest_and_last(state: &NodeState) -> Response {
+    let latest = state
+        .sortitions
+        .last()
+        .expect("Mock node has no genesis sortition");
+    let mut sortitions = vec![latest.clone()];
+    if let Some(last) = latest
+        .last_sortition_ch
+        .as_ref()
+        .and_then(|ch| find_sortition(state, ch))
+    {
+        sortitions.push(last.clone());
+    }
+    Response::json(200, &sortitions)
+}
+
+fn get_sortition(state: &NodeState, ch: &str) -> Response {
+    let Ok(ch) = ConsensusHash::from_hex(ch) else {
+        return Response::bad_request("Invalid consensus hash");
+    };
+    match find_sortition(state, &ch) {
+        Some(sortition) => Response::json(200, &vec![sortition.clone()]),
+        None => Response::not_found(),
+    }
+}
+
+fn get_tenure_tip(state: &NodeState, ch: &str) -> Response {
+    let Ok(ch) = ConsensusHash::from_hex(ch) else {
+        return Response::bad_request("Invalid consensus hash");
+    };
+    match state.tenure_tips.get(&ch) {
+        Some(header) => Response::json(200, &StacksBlockHeaderTypes::Nakamoto(header.clone())),
+        None => Response::not_found(),
+    }
+}
+
+/// Every burn block from `stop` back to `start`, newest first
+fn get_tenure_fork_info(state: &NodeState, start: &str, stop: &str) -> Response {
+    let (Ok(start), Ok(stop)) = (
+        ConsensusHash::from_hex(start),
+        ConsensusHash::from_hex(stop),
+    ) else {
+        return Response::bad_request("Invalid consensus hash");
+    };
+    let Some(stop_index) = state
+        .sortitions
+        .iter()
+        .position(|sortition| sortition.consensus_hash == stop)
+    else {
+        return Response::not_found();
+    };
+    let mut tenures = vec![];
+    for sortition in state.sortitions[..=stop_index].iter().rev() {
+        let first_block_mined = state
+            .posted_blocks
+            .iter()
+            .filter(|block| block.header.consensus_hash == sortition.consensus_hash)
+            .min_by_key(|block| block.header.chain_length)
+            .map(|block| block.block_id());
+        tenures.push(TenureForkingInfo {
+            burn_block_hash: sortition.burn_block_hash,
+            burn_block_height: sortition.burn_block_height,
+            sortition_id: sortition.sortition_id,
+            parent_sortition_id: sortition.parent_sortition_id,
+            consensus_hash: sortition.consensus_hash,
+            was_sortition: sortition.was_sortition,
+            first_block_mined,
+            nakamoto_blocks: None,
+        });
+        if sortition.consensus_hash == start {
+            break;
+        }
+    }
+    Response::json(200, &tenures)
+}
+
+fn get_stackerdb_chunk(state: &NodeState, contract: &str, slot_id: &str) -> Response {
+    let Ok(slot_id) = slot_id.parse::<u32>() else {
+        return Response::bad_request("Invalid slot ID");
+    };
+    match state.chunks.get(&(contract.to_string(), slot_id)) {
+        Some(chunk) => Response {
+            status: 200,
+            body: chunk.data.clone(),
+        },
+        None => Response::not_found(),
+    }
+}
+
+fn post_stackerdb_chunk(state: &mut NodeState, contract: &str, body: &[u8]) -> Response {
+    let Ok(chunk) = serde_json::from_slice::<StackerDBChunkData>(body) else {
+        return Response::bad_request("Invalid StackerDB chunk");
+    };
+    let Ok(signer_public_key) = chunk.recover_pk() else {
+        let code = StackerDBErrorCodes::BadSigner;
+        return Response::json(
+            200,
+            &StackerDBChunkAckData {
+                accepted: false,
+                reason: Some(code.reason().to_string()),
+                metadata: None,
+                code: Some(code.code()),
+            },
+        );
+    };
+    let key = (contract.to_string(), chunk.slot_id);
+    if let Some(existing) = state.chunks.get(&key) {
+        if existing.slot_version >= chunk.slot_version {
+            let code = StackerDBErrorCodes::DataAlreadyExists;
+            return Response::json(
+                200,
+                &StackerDBChunkAckData {
+                    accepted: false,
+                    reason: Some(code.reason().to_string()),
+                    metadata: Some(existing.get_slot_metadata()),
+                    code: Some(code.code()),
+                },
+            );
+        }
+    }
+    match read_next::<SignerMessage, _>(&mut &chunk.data[..]) {
+        Ok(message) => state.events.push_back(NodeEvent::StackerDBMessage {
+            signer_public_key,
+            message,
+        }),
+        Err(e) => warn!("Mock node: stored a chunk that is not a signer message: {e}"),
+    }
+    let metadata = chunk.get_slot_metadata();
+    state.chunks.insert(key, chunk);
+    Response::json(
+        200,
+        &StackerDBChunkAckData {
+            accepted: true,
+            reason: None,
+            metadata: Some(metadata),
+            code: None,
+        },
+    )
+}
+
+fn post_block_proposal(state: &mut NodeState, auth: &str, body: &[u8]) -> Response {
+    let Ok(proposal) = serde_json::from_slice::<NakamotoBlockProposal>(body) else {
+        return Response::bad_request("Invalid block proposal");
+    };
+    let signer_signature_hash = proposal.block.header.signer_signature_hash();
+    state.events.push_back(NodeEvent::BlockProposal {
+        auth: auth.to_string(),
+        block: proposal.block,
+    });
+    Response::json(
+        202,
+        &serde_json::json!({
+            "result": "Accepted",
+            "message": "Block proposal is processing",
+            "signer_signature_hash": signer_signature_hash,
+        }),
+    )
+}
+
+fn post_block(state: &mut NodeState, body: &[u8]) -> Response {
+    let Ok(block) = read_next::<NakamotoBlock, _>(&mut &body[..]) else {
+        return Response::bad_request("Invalid block");
+    };
+    let block_id: StacksBlockId = block.block_id();
+    let known = state
+        .posted_blocks
+        .iter()
+        .any(|posted| posted.block_id() == block_id);
+    if !known {
+        let header = &block.header;
+        let tenure_tip_height = state
+            .tenure_tips
+            .get(&header.consensus_hash)
+            .map(|tip| tip.chain_length);
+        if tenure_tip_height.is_none_or(|height| height < header.chain_length) {
+            state
+                .tenure_tips
+                .insert(header.consensus_hash, header.clone());
+        }
+        if state.stacks_tip.chain_length < header.chain_length {
+            state.stacks_tip = header.clone();
+        }
+        state.posted_blocks.push(block.clone());
+        state.events.push_back(NodeEvent::BlockPosted(block));
+    }
+    Response::json(
+        200,
+        &StacksBlockAcceptedData {
+            stacks_block_id: block_id,
+            accepted: !known,
+        },
+    )
+}
@@ -0,0 +1,782 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline replay of tenure-extend decisions under alternative timeout settings.
+//!
+//! A signer accepts a time-based tenure extend once `tenure_idle_timeout` has passed since
+//! the proposal of the tenure's last tenure change (or extend) block, plus the time its
+//! stacks-node spent validating the tenure's blocks since then. In every block response it
+//! tells the miner when that will be, padded by `tenure_idle_timeout_buffer`. The miner
+//! attempts a time-based extend once 70% of the signers have advertised a time in the past,
+//! or once its own `tenure_timeout` has elapsed since the last tenure change.
+//!
+//! `stacks-signer simulate-tenure-extend` replays the block proposals recorded in one or
+//! more signer databases under alternative settings. For every tenure extend that was
+//! proposed, it reports when each signer would have accepted it, whether it would have done
+//! so when the extend was proposed, and when the miner would have attempted it. If the
+//! miner's log is given, the tenure extend timestamp the miner logged for each attempt is
+//! shown alongside.
+//!
+//! The replay is an approximation of what the signers and the miner do:
+//! * every signer database carries the same weight
+//! * a block counts as globally accepted from the time the signer saw the signer set's
+//!   signature on it, or from its proposal if that time was not recorded
+//! * an extend changes the burn view if its burn view consensus hash differs from the
+//!   consensus hash of its tenure
+//! * the miner's cost-based heuristics (`tenure_extend_cost_threshold`) are not replayed
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+use blockstack_lib::util_lib::db::Error as DBError;
+use clarity::types::chainstate::ConsensusHash;
+use clarity::util::hash::Sha512Trunc256Sum;
+use libsigner::v0::messages::RejectReasonPrefix;
+
+use crate::signerdb::{tenure_idle_timestamp, BlockInfo, BlockState, SignerDb, TenureTimes};
+
+/// The share (in percent) of the signers which must accept a tenure extend
+pub const EXTEND_THRESHOLD_PCT: usize = 70;
+
+/// How long (in secs) before a proposal a logged miner extend attempt may be, and still be
+/// matched to it
+pub const MINER_LOG_MATCH_WINDOW_SECS: u64 = 30;
+
+/// The message the miner logs when it attempts a time-based tenure extend
+const MINER_EXTEND_LOG_MESSAGE: &str = "Miner: Time-based tenure extend";
+
+/// The signer and miner settings to replay the recorded proposals under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtendTimeouts {
+    /// The signers' `tenure_idle_timeout`
+    pub tenure_idle_timeout: Duration,
+    /// The signers' `tenure_idle_timeout_buffer`
+    pub tenure_idle_timeout_buffer: Duration,
+    /// The miner's `tenure_timeout`, if it should be replayed
+    pub miner_tenure_timeout: Option<Duration>,
+}
+
+impl fmt::Display for ExtendTimeouts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tenure_idle_timeout: {}s, tenure_idle_timeout_buffer: {}s",
+            self.tenure_idle_timeout.as_secs(),
+            self.tenure_idle_timeout_buffer.as_secs()
+        )?;
+        match self.miner_tenure_timeout {
+            Some(timeout) => write!(f, ", miner tenure_timeout: {}s", timeout.as_secs()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A signer's decision on a proposal, as recorded in its database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordedDecision {
+    /// The signer signed the block
+    Accepted,
+    /// The signer rejected the block
+    Rejected(RejectReasonPrefix),
+}
+
+impl fmt::Display for RecordedDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordedDecision::Accepted => write!(f, "accepted"),
+            RecordedDecision::Rejected(reason) => write!(f, "rejected ({reason:?})"),
+        }
+    }
+}
+
+/// A block proposal as recorded in a signer's database
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedProposal {
+    /// The block's signer signature hash
+    pub signer_signature_hash: Sha512Trunc256Sum,
+    /// The consensus hash of the block's tenure
+    pub consensus_hash: ConsensusHash,
+    /// The block's height in the Stacks chain
+    pub stacks_height: u64,
+    /// When the signer received the proposal (epoch time in seconds)
+    pub proposed_time: u64,
+    /// How long the stacks-node took to validate the block
+    pub validation_time_ms: Option<u64>,
+    /// Whether the block starts or extends a tenure
+    pub tenure_change: bool,
+    /// For a tenure extend, whether it changes the tenure's burn view
+    pub extend_burn_view_changed: Option<bool>,
+    /// When the signer signed the block, if it did (epoch time in seconds)
+    pub signed_time: Option<u64>,
+    /// From when the block counts as globally accepted, if it was (epoch time in seconds)
+    pub globally_accepted_time: Option<u64>,
+    /// The signer's decision, if it made one
+    pub decision: Option<RecordedDecision>,
+}
+
+impl From<&BlockInfo> for RecordedProposal {
+    fn from(block_info: &BlockInfo) -> Self {
+        let block = &block_info.block;
+        let decision = match (&block_info.reject_reason, block_info.signed_self) {
+            (Some(reason), _) => Some(RecordedDecision::Rejected(reason.into())),
+            (None, Some(_)) => Some(RecordedDecision::Accepted),
+            (None, None) => None,
+        };
+        let globally_accepted_time = (block_info.state == BlockState::GloballyAccepted)
+            .then(|| block_info.signed_group.unwrap_or(block_info.proposed_time));
+        Self {
+            signer_signature_hash: block.header.signer_signature_hash(),
+            consensus_hash: block.header.consensus_hash,
+            stacks_height: block.header.chain_length,
+            proposed_time: block_info.proposed_time,
+            validation_time_ms: block_info.validation_time_ms,
+            tenure_change: block_info.is_tenure_change(),
+            extend_burn_view_changed: block
+                .get_tenure_extend_tx_payload()
+                .map(|payload| payload.burn_view_consensus_hash != block.header.consensus_hash),
+            signed_time: block_info.signed_self,
+            globally_accepted_time,
+            decision,
+        }
+    }
+}
+
+/// The proposals recorded by one signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignerHistory {
+    /// A name for the signer in the report
+    pub signer: String,
+    /// The recorded proposals
+    pub proposals: Vec<RecordedProposal>,
+}
+
+impl SignerHistory {
+    /// Read the proposals made at burn block heights from `start_burn_height` up to and
+    /// including `end_burn_height` (or the latest one, if `None`) from a signer's database
+    pub fn load(
+        signer: String,
+        signer_db: &SignerDb,
+        start_burn_height: u64,
+        end_burn_height: Option<u64>,
+    ) -> Result<Self, DBError> {
+        let proposals = signer_db
+            .get_blocks_in_burn_range(start_burn_height, end_burn_height)?
+            .iter()
+            .map(RecordedProposal::from)
+            .collect();
+        Ok(Self { signer, proposals })
+    }
+
+    /// The blocks in a tenure below `stacks_height` which were globally accepted before
+    /// `time`, highest first
+    fn accepted_before(
+        &self,
+        tenure: &ConsensusHash,
+        stacks_height: u64,
+        time: u64,
+    ) -> Vec<&RecordedProposal> {
+        let mut accepted: Vec<_> = self
+            .proposals
+            .iter()
+            .filter(|proposal| {
+                proposal.consensus_hash == *tenure
+                    && proposal.stacks_height < stacks_height
+                    && proposal
+                        .globally_accepted_time
+                        .is_some_and(|accepted_time| accepted_time < time)
+            })
+            .collect();
+        accepted.sort_by_key(|proposal| std::cmp::Reverse(proposal.stacks_height));
+        accepted
+    }
+
+    /// The earliest time at which the signer accepts a time-based extend, given the
+    /// accepted blocks in the tenure (highest first)
+    fn idle_timestamp(accepted: &[&RecordedProposal], time: u64, timeout: Duration) -> u64 {
+        let mut tenure_times = TenureTimes::default();
+        for proposal in accepted {
+            if !tenure_times.add_block(
+                proposal.tenure_change,
+                proposal.proposed_time,
+                proposal.validation_time_ms,
+            ) {
+                break;
+            }
+        }
+        tenure_idle_timestamp(
+            tenure_times.start_time.unwrap_or(time),
+            tenure_times.process_time_ms,
+            timeout,
+        )
+    }
+
+    /// The tenure extend timestamp in the signer's response to the latest accepted block of
+    /// the tenure. A response to a tenure change block restarts the idle timeout.
+    fn advertised_timestamp(
+        accepted: &[&RecordedProposal],
+        time: u64,
+        timeouts: &ExtendTimeouts,
+    ) -> u64 {
+        let padded_timeout = timeouts
+            .tenure_idle_timeout
+            .saturating_add(timeouts.tenure_idle_timeout_buffer);
+        match accepted.first() {
+            Some(latest) if latest.tenure_change => latest
+                .signed_time
+                .unwrap_or(latest.proposed_time)
+                .saturating_add(padded_timeout.as_secs()),
+            _ => Self::idle_timestamp(accepted, time, padded_timeout),
+        }
+    }
+}
+
+/// A tenure extend attempt logged by the miner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinerExtendAttempt {
+    /// When the miner decided to extend its tenure (epoch time in seconds)
+    pub time: u64,
+    /// The time from which the miner expected the signers to accept the extend
+    pub tenure_extend_timestamp: u64,
+}
+
+/// Read a `key: value` field with an integer value from a log line
+fn log_field(line: &str, key: &str) -> Option<u64> {
+    let start = line.find(&format!("{key}: "))? + key.len() + 2;
+    let value = &line[start..];
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+/// Read the miner's time-based tenure extend attempts from a stacks-node log
+pub fn parse_miner_extend_log(log: impl BufRead) -> std::io::Result<Vec<MinerExtendAttempt>> {
+    let mut attempts = vec![];
+    for line in log.lines() {
+        let line = line?;
+        if !line.contains(MINER_EXTEND_LOG_MESSAGE) {
+            continue;
+        }
+        let (Some(time), Some(tenure_extend_timestamp)) = (
+            log_field(&line, "current_timestamp"),
+            log_field(&line, "tenure_extend_timestamp"),
+        ) else {
+            continue;
+        };
+        attempts.push(MinerExtendAttempt {
+            time,
+            tenure_extend_timestamp,
+        });
+    }
+    Ok(attempts)
+}
+
+/// A signer's replayed decision on a tenure extend
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignerExtendReplay {
+    /// The signer's name
+    pub signer: String,
+    /// The decision the signer recorded, if it saw the proposal
+    pub recorded: Option<RecordedDecision>,
+    /// The earliest time the signer would accept the extend, or `None` if the extend changes
+    /// the burn view and is accepted at any time
+    pub accept_from: Option<u64>,
+    /// The tenure extend timestamp the signer advertised to the miner before the extend
+    pub advertised: u64,
+}
+
+impl SignerExtendReplay {
+    /// Would the signer accept the extend at `time`?
+    pub fn accepts_at(&self, time: u64) -> bool {
+        self.accept_from
+            .is_none_or(|accept_from| time >= accept_from)
+    }
+}
+
+/// The replay of one proposed tenure extend
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendReplay {
+    /// The extend block's signer signature hash
+    pub signer_signature_hash: Sha512Trunc256Sum,
+    /// The tenure being extended
+    pub consensus_hash: ConsensusHash,
+    /// The extend block's height in the Stacks chain
+    pub stacks_height: u64,
+    /// When the extend was first proposed to a signer (epoch time in seconds)
+    pub proposed_time: u64,
+    /// Whether the extend changes the tenure's burn view
+    pub burn_view_changed: bool,
+    /// The signers' replayed decisions
+    pub signers: Vec<SignerExtendReplay>,
+    /// When the miner would have attempted a time-based extend, if it would have
+    pub miner_attempt_time: Option<u64>,
+    /// The tenure extend timestamp the miner logged for the attempt, if it was found
+    pub miner_logged_timestamp: Option<u64>,
+}
+
+impl ExtendReplay {
+    /// How many signers would accept the extend at `time`
+    pub fn accepting_signers(&self, time: u64) -> usize {
+        self.signers
+            .iter()
+            .filter(|signer| signer.accepts_at(time))
+            .count()
+    }
+
+    /// How many signers recorded an acceptance of the extend
+    pub fn recorded_acceptances(&self) -> usize {
+        self.signers
+            .iter()
+            .filter(|signer| signer.recorded == Some(RecordedDecision::Accepted))
+            .count()
+    }
+
+    /// Would the extend have reached the threshold when it was proposed?
+    pub fn accepted_when_proposed(&self) -> bool {
+        self.accepting_signers(self.proposed_time) >= extend_threshold(self.signers.len())
+    }
+
+    /// Would the miner's attempt have reached the threshold?
+    pub fn miner_attempt_accepted(&self) -> Option<bool> {
+        self.miner_attempt_time
+            .map(|time| self.accepting_signers(time) >= extend_threshold(self.signers.len()))
+    }
+}
+
+/// How many of `num_signers` equally weighted signers must accept a tenure extend
+pub fn extend_threshold(num_signers: usize) -> usize {
+    num_signers
+        .saturating_mul(EXTEND_THRESHOLD_PCT)
+        .div_ceil(100)
+        .max(1)
+}
+
+/// Replay every tenure extend proposed to the signers in `histories` under `timeouts`
+pub fn replay_tenure_extends(
+    histories: &[SignerHistory],
+    miner_attempts: &[MinerExtendAttempt],
+    timeouts: &ExtendTimeouts,
+) -> Vec<ExtendReplay> {
+    // The same extend is proposed to every signer, at slightly different times
+    let mut extends: HashMap<Sha512Trunc256Sum, &RecordedProposal> = HashMap::new();
+    for proposal in histories.iter().flat_map(|history| &history.proposals) {
+        if proposal.extend_burn_view_changed.is_none() {
+            continue;
+        }
+        extends
+            .entry(proposal.signer_signature_hash)
+            .and_modify(|first| {
+                if proposal.proposed_time < first.proposed_time {
+                    *first = proposal;
+                }
+            })
+            .or_insert(proposal);
+    }
+    let mut extends: Vec<_> = extends.into_values().collect();
+    extends.sort_by_key(|extend| (extend.proposed_time, extend.stacks_height));
+
+    extends
+        .into_iter()
+        .map(|extend| replay_tenure_extend(histories, miner_attempts, timeouts, extend))
+        .collect()
+}
+
+fn replay_tenure_extend(
+    histories: &[SignerHistory],
+    miner_attempts: &[MinerExtendAttempt],
+    timeouts: &ExtendTimeouts,
+    extend: &RecordedProposal,
+) -> ExtendReplay {
+    let burn_view_changed = extend.extend_burn_view_changed.unwrap_or(false);
+    let mut last_tenure_change_time: Option<u64> = None;
+    let signers: Vec<_> = histories
+        .iter()
+        .map(|history| {
+            let accepted = history.accepted_before(
+                &extend.consensus_hash,
+                extend.stacks_height,
+                extend.proposed_time,
+            );
+            if let Some(tenure_change) = accepted.iter().find(|proposal| proposal.tenure_change) {
+                last_tenure_change_time = Some(
+                    last_tenure_change_time.map_or(tenure_change.proposed_time, |time| {
+                        time.min(tenure_change.proposed_time)
+                    }),
+                );
+            }
+            let recorded = history
+                .proposals
+                .iter()
+                .find(|proposal| proposal.signer_signature_hash == extend.signer_signature_hash)
+                .and_then(|proposal| proposal.decision);
+            let accept_from = (!burn_view_changed).then(|| {
+                SignerHistory::idle_timestamp(
+                    &accepted,
+                    extend.proposed_time,
+                    timeouts.tenure_idle_timeout,
+                )
+            });
+            SignerExtendReplay {
+                signer: history.signer.clone(),
+                recorded,
+                accept_from,
+                advertised: SignerHistory::advertised_timestamp(
+                    &accepted,
+                    extend.proposed_time,
+                    timeouts,
+                ),
+            }
+        })
+        .collect();
+
+    // A burn view change is not a time-based extend, so the miner's timeouts do not apply
+    let miner_attempt_time = if burn_view_changed || signers.is_empty() {
+        None
+    } else {
+        let mut advertised: Vec<_> = signers.iter().map(|signer| signer.advertised).collect();
+        advertised.sort();
+        // The miner extends once the time is past the threshold's timestamp
+        let signers_time = advertised[extend_threshold(signers.len()) - 1].saturating_add(1);
+        let miner_time = timeouts
+            .miner_tenure_timeout
+            .zip(last_tenure_change_time)
+            .map(|(timeout, tenure_change_time)| {
+                tenure_change_time
+                    .saturating_add(timeout.as_secs())
+                    .saturating_add(1)
+            });
+        Some(miner_time.map_or(signers_time, |time| time.min(signers_time)))
+    };
+
+    let miner_logged_timestamp = miner_attempts
+        .iter()
+        .filter(|attempt| {
+            attempt.time <= extend.proposed_time
+                && extend.proposed_time - attempt.time <= MINER_LOG_MATCH_WINDOW_SECS
+        })
+        .max_by_key(|attempt| attempt.time)
+        .map(|attempt| attempt.tenure_extend_timestamp);
+
+    ExtendReplay {
+        signer_signature_hash: extend.signer_signature_hash,
+        consensus_hash: extend.consensus_hash,
+        stacks_height: extend.stacks_height,
+        proposed_time: extend.proposed_time,
+        burn_view_changed,
+        signers,
+        miner_attempt_time,
+        miner_logged_timestamp,
+    }
+}
+
+/// Render a time relative to `base` as `+Ns` or `-Ns`
+fn relative_time(time: u64, base: u64) -> String {
+    if time >= base {
+        format!("+{}s", time - base)
+    } else {
+        format!("-{}s", base - time)
+    }
+}
+
+/// Write a report of the `replays` of the recorded tenure extends under `timeouts` to `out`.
+/// Times are shown relative to each extend's proposal.
+pub fn write_replay_report(
+    timeouts: &ExtendTimeouts,
+    replays: &[ExtendReplay],
+    mut out: impl Write,
+) -> std::io::Result<()> {
+    writeln!(out, "== {timeouts} ==")?;
+    for replay in replays {
+        let threshold = extend_threshold(replay.signers.len());
+        writeln!(
+            out,
+            "extend {} of tenure {} at height {}, proposed at {}{}",
+            replay.signer_signature_hash,
+            replay.consensus_hash,
+            replay.stacks_height,
+            replay.proposed_time,
+            if replay.burn_view_changed {
+                " (burn view changed)"
+            } else {
+                ""
+            }
+        )?;
+        writeln!(
+            out,
+            "  recorded: {}/{} signers accepted; replayed: {}/{} signers accept when proposed",
+            replay.recorded_acceptances(),
+            replay.signers.len(),
+            replay.accepting_signers(replay.proposed_time),
+            replay.signers.len(),
+        )?;
+        if let Some(attempt_time) = replay.miner_attempt_time {
+            writeln!(
+                out,
+                "  miner would attempt at {}: {}/{} signers accept (threshold {threshold})",
+                relative_time(attempt_time, replay.proposed_time),
+                replay.accepting_signers(attempt_time),
+                replay.signers.len(),
+            )?;
+        }
+        if let Some(logged) = replay.miner_logged_timestamp {
+            writeln!(
+                out,
+                "  miner logged a tenure extend timestamp of {}",
+                relative_time(logged, replay.proposed_time)
+            )?;
+        }
+        for signer in &replay.signers {
+            let recorded = signer
+                .recorded
+                .map_or("not seen".to_string(), |decision| decision.to_string());
+            let accept_from = signer.accept_from.map_or("any time".to_string(), |time| {
+                relative_time(time, replay.proposed_time)
+            });
+            writeln!(
+                out,
+                "  {:<44} recorded: {recorded:<32} accepts from: {accept_from:<8} advertised: {}",
+                signer.signer,
+                relative_time(signer.advertised, replay.proposed_time),
+            )?;
+        }
+    }
+    let accepted_when_proposed = replays
+        .iter()
+        .filter(|replay| replay.accepted_when_proposed())
+        .count();
+    let miner_attempts: Vec<_> = replays
+        .iter()
+        .filter_map(ExtendReplay::miner_attempt_accepted)
+        .collect();
+    writeln!(
+        out,
+        "summary: {} extends, {accepted_when_proposed} accepted when proposed, {} of {} miner attempts accepted",
+        replays.len(),
+        miner_attempts.iter().filter(|accepted| **accepted).count(),
+        miner_attempts.len(),
+    )?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use blockstack_lib::chainstate::nakamoto::{NakamotoBlock, NakamotoBlockHeader};
+    use blockstack_lib::chainstate::stacks::{
+        StacksTransaction, TenureChangeCause, TenureChangePayload, TokenTransferMemo,
+        TransactionAuth, TransactionPayload, TransactionVersion,
+    };
+    use clarity::types::chainstate::{StacksAddress, StacksBlockId, StacksPrivateKey};
+    use clarity::util::hash::Hash160;
+    use clarity::vm::types::PrincipalData;
+    use libsigner::v0::messages::RejectReason;
+    use libsigner::{BlockProposal, BlockProposalData};
+
+    use super::*;
+
+    const TENURE: ConsensusHash = ConsensusHash([0x01; 20]);
+
+    fn make_tx(payload: TransactionPayload) -> StacksTransaction {
+        StacksTransaction::new(
+            TransactionVersion::Testnet,
+            TransactionAuth::from_p2pkh(&StacksPrivateKey::from_seed(&[0, 1])).unwrap(),
+            payload,
+        )
+    }
+
+    fn make_tenure_change_tx(cause: TenureChangeCause, parent: StacksBlockId) -> StacksTransaction {
+        make_tx(TransactionPayload::TenureChange(TenureChangePayload {
+            tenure_consensus_hash: TENURE,
+            prev_tenure_consensus_hash: TENURE,
+            burn_view_consensus_hash: TENURE,
+            previous_tenure_end: parent,
+            previous_tenure_blocks: 1,
+            cause,
+            pubkey_hash: Hash160([0; 20]),
+        }))
+    }
+
+    fn make_block(chain_length: u64, tenure_change: Option<TenureChangeCause>) -> NakamotoBlock {
+        let mut header = NakamotoBlockHeader::empty();
+        header.consensus_hash = TENURE;
+        header.chain_length = chain_length;
+        header.parent_block_id = StacksBlockId([chain_length as u8; 32]);
+        let tx = match tenure_change {
+            Some(cause) => make_tenure_change_tx(cause, header.parent_block_id),
+            None => make_tx(TransactionPayload::TokenTransfer(
+                PrincipalData::from(StacksAddress::burn_address(false)),
+                1,
+                TokenTransferMemo([0; 34]),
+            )),
+        };
+        NakamotoBlock {
+            header,
+            txs: vec![tx],
+        }
+    }
+
+    fn make_block_info(block: NakamotoBlock, proposed_time: u64) -> BlockInfo {
+        let mut block_info = BlockInfo::from(BlockProposal {
+            block,
+            burn_height: 1,
+            reward_cycle: 1,
+            block_proposal_data: BlockProposalData::empty(),
+        });
+        block_info.proposed_time = proposed_time;
+        block_info
+    }
+
+    fn accepted_block_info(
+        block: NakamotoBlock,
+        proposed_time: u64,
+        validation_time_ms: u64,
+    ) -> BlockInfo {
+        let mut block_info = make_block_info(block, proposed_time);
+        block_info.validation_time_ms = Some(validation_time_ms);
+        block_info.signed_self = Some(proposed_time + 1);
+        block_info.signed_group = Some(proposed_time + 1);
+        block_info.state = BlockState::GloballyAccepted;
+        block_info
+    }
+
+    /// A signer which saw a tenure start at `start`, a block 10s later, and then an
+    /// extend proposed at 1150.
+    fn signer_history(
+        signer: &str,
+        start: u64,
+        extend_decision: Option<RejectReason>,
+    ) -> SignerHistory {
+        let mut signer_db = SignerDb::new(":memory:").unwrap();
+        signer_db
+            .insert_block(&accepted_block_info(
+                make_block(1, Some(TenureChangeCause::BlockFound)),
+                start,
+                1500,
+            ))
+            .unwrap();
+        signer_db
+            .insert_block(&accepted_block_info(make_block(2, None), start + 10, 2500))
+            .unwrap();
+        let mut extend = make_block_info(make_block(3, Some(TenureChangeCause::Extended)), 1150);
+        match extend_decision {
+            Some(reason) => extend.reject_reason = Some(reason),
+            None => extend.signed_self = Some(1151),
+        }
+        signer_db.insert_block(&extend).unwrap();
+        SignerHistory::load(signer.into(), &signer_db, 0, None).unwrap()
+    }
+
+    #[test]
+    fn replay_extend_under_alternative_timeouts() {
+        // Signers 0 to 2 started the tenure at 1000 and signer 3 at 1040.
+        let histories = vec![
+            signer_history("signer-0", 1000, None),
+            signer_history("signer-1", 1000, None),
+            signer_history("signer-2", 1000, None),
+            signer_history("signer-3", 1040, Some(RejectReason::InvalidTenureExtend)),
+        ];
+        assert_eq!(histories[0].proposals.len(), 3);
+        assert_eq!(
+            histories[0].proposals[2].extend_burn_view_changed,
+            Some(false)
+        );
+
+        let mut timeouts = ExtendTimeouts {
+            tenure_idle_timeout: Duration::from_secs(120),
+            tenure_idle_timeout_buffer: Duration::from_secs(2),
+            miner_tenure_timeout: None,
+        };
+        let replays = replay_tenure_extends(&histories, &[], &timeouts);
+        assert_eq!(replays.len(), 1);
+        let replay = &replays[0];
+        assert_eq!(replay.stacks_height, 3);
+        assert_eq!(replay.recorded_acceptances(), 3);
+        // 1000 + 120 + 4 secs of validation
+        assert_eq!(replay.signers[0].accept_from, Some(1124));
+        assert_eq!(replay.signers[0].advertised, 1126);
+        assert_eq!(replay.signers[3].accept_from, Some(1164));
+        assert_eq!(
+            replay.signers[3].recorded,
+            Some(RecordedDecision::Rejected(
+                RejectReasonPrefix::InvalidTenureExtend
+            ))
+        );
+        assert_eq!(replay.accepting_signers(1150), 3);
+        assert!(replay.accepted_when_proposed());
+        // 3 of 4 signers make the threshold, so the miner attempts after their timestamp
+        assert_eq!(replay.miner_attempt_time, Some(1127));
+        assert_eq!(replay.miner_attempt_accepted(), Some(true));
+
+        // A longer timeout would have rejected the extend when it was proposed
+        timeouts.tenure_idle_timeout = Duration::from_secs(180);
+        let replay = &replay_tenure_extends(&histories, &[], &timeouts)[0];
+        assert_eq!(replay.accepting_signers(1150), 0);
+        assert!(!replay.accepted_when_proposed());
+        assert_eq!(replay.miner_attempt_time, Some(1187));
+        assert_eq!(replay.miner_attempt_accepted(), Some(true));
+
+        // A miner tenure_timeout shorter than the signers' idle timeout makes the miner
+        // attempt extends the signers reject
+        timeouts.miner_tenure_timeout = Some(Duration::from_secs(60));
+        let replay = &replay_tenure_extends(&histories, &[], &timeouts)[0];
+        assert_eq!(replay.miner_attempt_time, Some(1061));
+        assert_eq!(replay.miner_attempt_accepted(), Some(false));
+
+        let mut report = vec![];
+        write_replay_report(&timeouts, std::slice::from_ref(replay), &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        assert!(report.starts_with(
+            "== tenure_idle_timeout: 180s, tenure_idle_timeout_buffer: 2s, miner tenure_timeout: 60s =="
+        ));
+        assert!(report.contains("miner would attempt at -89s: 0/4 signers accept (threshold 3)"));
+        assert!(report.ends_with(
+            "summary: 1 extends, 0 accepted when proposed, 0 of 1 miner attempts accepted\n"
+        ));
+    }
+
+    #[test]
+    fn parse_miner_log() {
+        let log = "\
+INFO [1700000000.1] [stacks-node/src/nakamoto_node/miner.rs:1671] [miner-block-http://127.0.0.1:20443] Miner: Time-based tenure extend, current_timestamp: 1149, tenure_extend_timestamp: 1126, tenure_change_time_elapsed: 149, tenure_timeout_secs: 180
+INFO [1700000000.2] [stacks-node/src/nakamoto_node/miner.rs:1700] [miner-block-http://127.0.0.1:20443] Miner: some other message, current_timestamp: 1149
+INFO [1700000000.3] [stacks-node/src/nakamoto_node/miner.rs:1671] [miner-block-http://127.0.0.1:20443] Miner: Time-based tenure extend, current_timestamp: 900, tenure_extend_timestamp: 800, tenure_change_time_elapsed: 149, tenure_timeout_secs: 180
+";
+        let attempts = parse_miner_extend_log(log.as_bytes()).unwrap();
+        assert_eq!(
+            attempts,
+            vec![
+                MinerExtendAttempt {
+                    time: 1149,
+                    tenure_extend_timestamp: 1126
+                },
+                MinerExtendAttempt {
+                    time: 900,
+                    tenure_extend_timestamp: 800
+                },
+            ]
+        );
+
+        let histories = vec![signer_history("signer-0", 1000, None)];
+        let timeouts = ExtendTimeouts {
+            tenure_idle_timeout: Duration::from_secs(120),
+            tenure_idle_timeout_buffer: Duration::from_secs(2),
+            miner_tenure_timeout: None,
+        };
+        // Only the attempt right before the proposal is matched to it
+        let replays = replay_tenure_extends(&histories, &attempts, &timeouts);
+        assert_eq!(replays[0].miner_logged_timestamp, Some(1126));
+    }
+}
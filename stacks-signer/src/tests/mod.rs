@@ -1 +1,2 @@
 mod signer_state;
+mod sim;
@@ -298,6 +298,7 @@ fn check_miner_inactivity_timeout() {
         consensus_hash: ConsensusHash([1; 20]),
         burn_header_timestamp: 2,
         burn_block_hash: BurnchainHeaderHash([1; 32]),
+        burn_block_height: 2,
     };
     let last_sortition = SortitionData {
         miner_pkh: block_pkh,
@@ -307,6 +308,7 @@ fn check_miner_inactivity_timeout() {
         consensus_hash: ConsensusHash([0; 20]),
         burn_header_timestamp: 1,
         burn_block_hash: BurnchainHeaderHash([0; 32]),
+        burn_block_height: 1,
     };
 
     // Ensure we have a burn height to compare against
@@ -0,0 +1,164 @@
+// Copyright (C) 2020-2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+use blockstack_lib::net::api::postblock_proposal::ValidateRejectCode;
+use clarity::util::hash::Sha512Trunc256Sum;
+use libsigner::v0::messages::{BlockResponse, RejectReason};
+
+use crate::signerdb::BlockState;
+use crate::sim::{SignerSimulation, SimMiner, ValidationOutcome};
+
+const NUM_SIGNERS: u32 = 4;
+
+fn assert_all_rejected(sim: &SignerSimulation, block_hash: &Sha512Trunc256Sum) {
+    let responses = sim.block_responses(block_hash);
+    assert_eq!(responses.len(), sim.num_signers());
+    assert!(responses
+        .iter()
+        .all(|(_, response)| matches!(response, BlockResponse::Rejected(_))));
+}
+
+#[test]
+fn signers_accept_and_post_valid_block() {
+    let mut sim = SignerSimulation::new(NUM_SIGNERS);
+    // Messages from signer 0 to signer 3 arrive late, which must not change the outcome
+    sim.set_link_delay(0, 3, 5);
+    let block = sim.build_block();
+    let block_hash = block.header.signer_signature_hash();
+    sim.propose_block(&block);
+    sim.run_until_quiet();
+
+    let accepted = sim.block_responses(&block_hash);
+    assert_eq!(accepted.len(), sim.num_signers());
+    assert!(accepted
+        .iter()
+        .all(|(_, response)| matches!(response, BlockResponse::Accepted(_))));
+    let posted = sim.posted_blocks();
+    assert_eq!(posted.len(), 1);
+    assert_eq!(posted[0].header.signer_signature_hash(), block_hash);
+    for signer in 0..sim.num_signers() {
+        assert_eq!(
+            sim.block_state(signer, &block_hash),
+            Some(BlockState::GloballyAccepted)
+        );
+    }
+}
+
+#[test]
+fn partitioned_signers_cannot_reach_threshold() {
+    let mut sim = SignerSimulation::new(NUM_SIGNERS);
+    sim.partition(&[&[0, 1], &[2, 3]]);
+    let block = sim.build_block();
+    let block_hash = block.header.signer_signature_hash();
+    sim.propose_block(&block);
+    sim.run_until_quiet();
+
+    assert_eq!(sim.block_responses(&block_hash).len(), sim.num_signers());
+    assert!(sim.posted_blocks().is_empty());
+    for signer in 0..sim.num_signers() {
+        assert_eq!(
+            sim.block_state(signer, &block_hash),
+            Some(BlockState::LocallyAccepted)
+        );
+    }
+
+    // Once the partition heals, the miner re-proposing the block makes each signer resend
+    // its response, and the block reaches the threshold.
+    sim.heal();
+    sim.propose_block(&block);
+    sim.run_until_quiet();
+
+    let posted = sim.posted_blocks();
+    assert_eq!(posted.len(), 1);
+    assert_eq!(posted[0].header.signer_signature_hash(), block_hash);
+}
+
+#[test]
+fn signer_skips_responses_it_cannot_sign() {
+    let mut sim = SignerSimulation::new(NUM_SIGNERS);
+    sim.set_signing_fails(0, true);
+    let block = sim.build_block();
+    let block_hash = block.header.signer_signature_hash();
+    sim.propose_block(&block);
+    sim.run_until_quiet();
+
+    // Signer 0 could not sign its acceptance, so it sent nothing, but the others reach the
+    // threshold without it
+    let responses = sim.block_responses(&block_hash);
+    assert_eq!(responses.len(), sim.num_signers() - 1);
+    assert!(responses.iter().all(|(signer, _)| *signer != 0));
+    assert_eq!(sim.posted_blocks().len(), 1);
+
+    // A block signer 0 cannot sign a rejection for gets no response from it either
+    let mut bad_block = sim.build_block();
+    SimMiner::new().sign_block(&mut bad_block);
+    let bad_block_hash = bad_block.header.signer_signature_hash();
+    sim.propose_block_to(&bad_block, &[0]);
+    sim.run_until_quiet();
+    assert!(sim.block_responses(&bad_block_hash).is_empty());
+
+    // Once signing works again, a re-proposal gets signer 0's response
+    sim.set_signing_fails(0, false);
+    sim.propose_block_to(&bad_block, &[0]);
+    sim.run_until_quiet();
+    let responses = sim.block_responses(&bad_block_hash);
+    assert_eq!(responses.len(), 1);
+    assert!(matches!(responses[0], (0, BlockResponse::Rejected(_))));
+}
+
+#[test]
+fn signers_reject_block_from_wrong_miner() {
+    let mut sim = SignerSimulation::new(NUM_SIGNERS);
+    let mut block = sim.build_block();
+    SimMiner::new().sign_block(&mut block);
+    let block_hash = block.header.signer_signature_hash();
+    sim.propose_block(&block);
+    sim.run_until_quiet();
+
+    assert_all_rejected(&sim, &block_hash);
+    assert!(sim
+        .block_responses(&block_hash)
+        .iter()
+        .all(|(_, response)| {
+            response
+                .as_block_rejection()
+                .unwrap()
+                .response_data
+                .reject_reason
+                == RejectReason::PubkeyHashMismatch
+        }));
+    assert!(sim.posted_blocks().is_empty());
+}
+
+#[test]
+fn signers_reject_block_the_node_rejects() {
+    let mut sim = SignerSimulation::new(NUM_SIGNERS);
+    let block = sim.build_block();
+    let block_hash = block.header.signer_signature_hash();
+    sim.set_validation(
+        block_hash,
+        ValidationOutcome::Reject(ValidateRejectCode::InvalidBlock),
+    );
+    sim.propose_block(&block);
+    sim.run_until_quiet();
+
+    assert_all_rejected(&sim, &block_hash);
+    assert!(sim.posted_blocks().is_empty());
+    for signer in 0..sim.num_signers() {
+        assert_eq!(
+            sim.block_state(signer, &block_hash),
+            Some(BlockState::GloballyRejected)
+        );
+    }
+}
@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 #[cfg(any(test, feature = "testing"))]
 use std::sync::LazyLock;
 use std::time::{Duration, Instant, SystemTime};
@@ -26,8 +27,8 @@ use blockstack_lib::net::api::postblock_proposal::{
 };
 use blockstack_lib::util_lib::db::Error as DBError;
 use clarity::codec::read_next;
-use clarity::types::chainstate::{StacksBlockId, StacksPrivateKey};
-use clarity::types::{PrivateKey, StacksEpochId};
+use clarity::types::chainstate::StacksBlockId;
+use clarity::types::StacksEpochId;
 use clarity::util::hash::{MerkleHashFunc, Sha512Trunc256Sum};
 use clarity::util::secp256k1::Secp256k1PublicKey;
 #[cfg(any(test, feature = "testing"))]
@@ -38,20 +39,27 @@ use libsigner::v0::messages::{
     BlockAccepted, BlockRejection, BlockResponse, MessageSlotID, MockProposal, MockSignature,
     RejectReason, RejectReasonPrefix, SignerMessage, StateMachineUpdate,
 };
-use libsigner::v0::signer_state::GlobalStateEvaluator;
-use libsigner::{BlockProposal, SignerEvent, SignerSession};
-use stacks_common::types::chainstate::{StacksAddress, StacksPublicKey};
+use libsigner::v0::signer_state::{
+    DivergentSigner, GlobalStateEvaluator, MinerState, StateDivergence,
+};
+use libsigner::{BlockProposal, KeyBackend, KeyBackendError, SignerEvent, SignerSession};
+use stacks_common::types::chainstate::StacksAddress;
+#[cfg(any(test, feature = "testing"))]
+use stacks_common::types::chainstate::StacksPublicKey;
 use stacks_common::util::get_epoch_time_secs;
 use stacks_common::util::secp256k1::MessageSignature;
 use stacks_common::{debug, error, info, warn};
 
 use super::signer_state::LocalStateMachine;
+use crate::admin::SignerAdminState;
+use crate::audit::{AuditStage, BlockAuditRecord};
 use crate::chainstate::v1::{SortitionMinerStatus, SortitionsView};
 use crate::chainstate::v2::GlobalStateView;
 use crate::chainstate::{ProposalEvalConfig, SortitionData, SortitionStateVersion};
 use crate::client::{ClientError, SignerSlotID, StackerDB, StacksClient};
 use crate::config::{SignerConfig, SignerConfigMode};
-use crate::runloop::SignerResult;
+use crate::policy::BlockPolicy;
+use crate::runloop::{SharedBlockValidations, SignerResult};
 use crate::signerdb::{BlockInfo, BlockState, SignerDb};
 #[cfg(not(any(test, feature = "testing")))]
 use crate::v0::signer_state::SUPPORTED_SIGNER_PROTOCOL_VERSION;
@@ -87,12 +95,12 @@ pub struct RecentlyProcessedBlocks<const N: usize> {
 /// The stacks signer registered for the reward cycle
 #[derive(Debug)]
 pub struct Signer {
-    /// The private key of the signer
+    /// The key that the signer signs its messages with
     #[cfg(any(test, feature = "testing"))]
-    pub private_key: StacksPrivateKey,
+    pub signing_key: Arc<dyn KeyBackend>,
     #[cfg(not(any(test, feature = "testing")))]
-    /// The private key of the signer
-    private_key: StacksPrivateKey,
+    /// The key that the signer signs its messages with
+    signing_key: Arc<dyn KeyBackend>,
     /// The signer address
     pub stacks_address: StacksAddress,
     /// The stackerdb client
@@ -134,6 +142,16 @@ pub struct Signer {
     pub capitulate_miner_view_timeout: Duration,
     /// The last time we capitulated our miner viewpoint
     pub last_capitulate_miner_view: SystemTime,
+    /// How the local state machine last diverged from the signer set's majority view
+    pub state_divergence: Vec<StateDivergence>,
+    /// The signers whose latest updates last diverged from the signer set's majority view
+    pub divergent_signers: Vec<DivergentSigner>,
+    /// Operator-defined rules that block proposals must satisfy
+    pub block_policy: BlockPolicy,
+    /// Signer state shared with the admin API
+    pub admin_state: Arc<SignerAdminState>,
+    /// The block validations submitted by any of the runloop's signing identities
+    pub block_validations: Arc<SharedBlockValidations>,
     /// The signer supported protocol version. used only in testing
     #[cfg(any(test, feature = "testing"))]
     pub supported_signer_protocol_version: u64,
@@ -216,7 +234,7 @@ impl SignerTrait<SignerMessage> for Signer {
 
         let stacks_address = StacksAddress::p2pkh(
             signer_config.mainnet,
-            &StacksPublicKey::from_private(&signer_config.stacks_private_key),
+            &signer_config.signing_key.public_key(),
         );
 
         let session = stackerdb
@@ -291,7 +309,7 @@ impl SignerTrait<SignerMessage> for Signer {
             LocalStateMachine::Uninitialized
         });
         Self {
-            private_key: signer_config.stacks_private_key,
+            signing_key: signer_config.signing_key,
             stacks_address,
             stackerdb,
             mainnet: signer_config.mainnet,
@@ -312,6 +330,11 @@ impl SignerTrait<SignerMessage> for Signer {
             tx_replay_scope: None,
             capitulate_miner_view_timeout: signer_config.capitulate_miner_view_timeout,
             last_capitulate_miner_view: SystemTime::now(),
+            state_divergence: vec![],
+            divergent_signers: vec![],
+            block_policy: signer_config.block_policy,
+            admin_state: signer_config.admin_state,
+            block_validations: signer_config.block_validations,
             #[cfg(any(test, feature = "testing"))]
             supported_signer_protocol_version: signer_config.supported_signer_protocol_version,
         }
@@ -406,6 +429,7 @@ impl SignerTrait<SignerMessage> for Signer {
             self.local_state_machine
                 .send_signer_update_message(&mut self.stackerdb, version);
         }
+        self.check_state_divergence();
     }
 
     fn has_unprocessed_blocks(&self) -> bool {
@@ -448,7 +472,10 @@ impl Signer {
     /// Determine this signers response to a proposed block
     /// Returns a BlockResponse if we have already validated the block
     /// Returns None otherwise
-    fn determine_response(&mut self, block_info: &BlockInfo) -> Option<BlockResponse> {
+    fn determine_response(
+        &mut self,
+        block_info: &BlockInfo,
+    ) -> Option<Result<BlockResponse, KeyBackendError>> {
         let valid = block_info.valid?;
         let response = if valid {
             debug!("{self}: Accepting block {}", block_info.block.block_id());
@@ -461,12 +488,14 @@ impl Signer {
     }
 
     /// Create a block acceptance response for a block
-    pub fn create_block_acceptance(&self, block: &NakamotoBlock) -> BlockResponse {
+    pub fn create_block_acceptance(
+        &self,
+        block: &NakamotoBlock,
+    ) -> Result<BlockResponse, KeyBackendError> {
         let signature = self
-            .private_key
-            .sign(block.header.signer_signature_hash().bits())
-            .expect("Failed to sign block");
-        BlockResponse::accepted(
+            .signing_key
+            .sign_digest(block.header.signer_signature_hash().bits())?;
+        Ok(BlockResponse::accepted(
             block.header.signer_signature_hash(),
             signature,
             self.signer_db.calculate_tenure_extend_timestamp(
@@ -476,7 +505,7 @@ impl Signer {
                 block,
                 true,
             ),
-        )
+        ))
     }
 
     /// The actual switch-on-event processing of an event.
@@ -667,11 +696,11 @@ impl Signer {
         &self,
         reject_reason: RejectReason,
         block: &NakamotoBlock,
-    ) -> BlockResponse {
+    ) -> Result<BlockResponse, KeyBackendError> {
         BlockResponse::rejected(
             block.header.signer_signature_hash(),
             reject_reason,
-            &self.private_key,
+            self.signing_key.as_ref(),
             self.mainnet,
             self.signer_db.calculate_tenure_extend_timestamp(
                 self.proposal_config
@@ -683,6 +712,45 @@ impl Signer {
         )
     }
 
+    /// Append our decision on a block proposal to the audit log, along with the sortition view
+    /// that the decision was checked against.
+    fn record_block_decision(
+        &mut self,
+        sortition_state: Option<&SortitionsView>,
+        mut record: BlockAuditRecord,
+    ) {
+        let uses_global_state = self
+            .global_state_evaluator
+            .determine_latest_supported_signer_protocol_version()
+            .is_some_and(|version| {
+                SortitionStateVersion::from_protocol_version(version).uses_global_state()
+            });
+        if uses_global_state {
+            if let Some(global_state) = self.global_state_evaluator.determine_global_state() {
+                record.view_burn_block = Some(global_state.burn_block.to_string());
+                record.view_burn_block_height = Some(global_state.burn_block_height);
+                if let MinerState::ActiveMiner {
+                    current_miner_pkh,
+                    tenure_id,
+                    ..
+                } = global_state.current_miner
+                {
+                    record.view_miner_pkh = Some(current_miner_pkh.to_string());
+                    record.view_tenure_id = Some(tenure_id.to_string());
+                }
+            }
+        } else if let Some(sortition_state) = sortition_state {
+            let cur_sortition = &sortition_state.cur_sortition.data;
+            record.view_burn_block = Some(cur_sortition.burn_block_hash.to_string());
+            record.view_burn_block_height = Some(cur_sortition.burn_block_height);
+            record.view_miner_pkh = Some(cur_sortition.miner_pkh.to_string());
+            record.view_tenure_id = Some(cur_sortition.consensus_hash.to_string());
+        }
+        self.signer_db
+            .insert_block_audit_record(&record)
+            .unwrap_or_else(|e| warn!("{self}: Failed to insert block audit record: {e:?}"));
+    }
+
     /// Check some heuristics to see if our stacks-node has processed the parent of `block`.
     ///  Note: this can be wrong in both directions. It may return false for some blocks that
     ///  have been processed, and it may return true for some blocks that have not been processed.
@@ -707,13 +775,13 @@ impl Signer {
     }
 
     /// Check if block should be rejected based on the appropriate state (either local or global)
-    /// Will return a BlockResponse::Rejection if the block is invalid, none otherwise.
+    /// Will return the reason to reject the block if the block is invalid, none otherwise.
     fn check_block_against_state(
         &mut self,
         stacks_client: &StacksClient,
         sortition_state: &mut Option<SortitionsView>,
         block: &NakamotoBlock,
-    ) -> Option<BlockResponse> {
+    ) -> Option<RejectReason> {
         let Some(latest_version) = self
             .global_state_evaluator
             .determine_latest_supported_signer_protocol_version()
@@ -723,7 +791,7 @@ impl Signer {
                 "signer_signature_hash" => %block.header.signer_signature_hash(),
                 "block_id" => %block.block_id(),
             );
-            return Some(self.create_block_rejection(RejectReason::NoSignerConsensus, block));
+            return Some(RejectReason::NoSignerConsensus);
         };
         let state_version = SortitionStateVersion::from_protocol_version(latest_version);
         if state_version.uses_global_state() {
@@ -733,15 +801,23 @@ impl Signer {
         }
     }
 
+    /// Check if block should be rejected by the operator-defined block policy.
+    /// Will return the reason to reject the block if the block breaks a rule, none otherwise.
+    fn check_block_against_policy(&self, block: &NakamotoBlock) -> Option<RejectReason> {
+        self.block_policy
+            .check_proposal(block, &self.signer_db)
+            .err()
+    }
+
     /// Check if block should be rejected based on the local view of the sortition state
-    /// Will return a BlockResponse::Rejection if the block is invalid, none otherwise.
+    /// Will return the reason to reject the block if the block is invalid, none otherwise.
     /// This is the pre-global signer state activation path.
     fn check_block_against_local_state(
         &mut self,
         stacks_client: &StacksClient,
         sortition_state: &mut Option<SortitionsView>,
         block: &NakamotoBlock,
-    ) -> Option<BlockResponse> {
+    ) -> Option<RejectReason> {
         let signer_signature_hash = block.header.signer_signature_hash();
         let block_id = block.block_id();
         // Get sortition view if we don't have it
@@ -768,7 +844,7 @@ impl Signer {
                         "signer_signature_hash" => %signer_signature_hash,
                         "block_id" => %block_id,
                     );
-                    Some(self.create_block_rejection(RejectReason::ConnectivityIssues(e), block))
+                    Some(RejectReason::ConnectivityIssues(e))
                 }
                 // Block proposal is bad
                 Err(reject_code) => {
@@ -779,7 +855,7 @@ impl Signer {
                         "reject_reason" => %reject_code,
                         "reject_code" => ?reject_code,
                     );
-                    Some(self.create_block_rejection(reject_code, block))
+                    Some(reject_code)
                 }
                 // Block proposal passed check, still don't know if valid
                 Ok(_) => None,
@@ -790,18 +866,18 @@ impl Signer {
                 "signer_signature_hash" => %signer_signature_hash,
                 "block_id" => %block_id,
             );
-            Some(self.create_block_rejection(RejectReason::NoSortitionView, block))
+            Some(RejectReason::NoSortitionView)
         }
     }
 
     /// Check if block should be rejected based on global signer state
-    /// Will return a BlockResponse::Rejection if the block is invalid, none otherwise.
+    /// Will return the reason to reject the block if the block is invalid, none otherwise.
     /// This is the Post-global signer state activation path
     fn check_block_against_global_state(
         &mut self,
         stacks_client: &StacksClient,
         block: &NakamotoBlock,
-    ) -> Option<BlockResponse> {
+    ) -> Option<RejectReason> {
         let signer_signature_hash = block.header.signer_signature_hash();
         let block_id = block.block_id();
         // First update our global state evaluator with our local state if we have one
@@ -821,7 +897,7 @@ impl Signer {
                 "block_id" => %block_id,
                 "local_signer_state" => ?self.local_state_machine
             );
-            return Some(self.create_block_rejection(RejectReason::NoSignerConsensus, block));
+            return Some(RejectReason::NoSignerConsensus);
         };
 
         let global_state_view = GlobalStateView {
@@ -846,7 +922,7 @@ impl Signer {
                     "signer_signature_hash" => %signer_signature_hash,
                     "block_id" => %block_id,
                 );
-                Some(self.create_block_rejection(RejectReason::ConnectivityIssues(e), block))
+                Some(RejectReason::ConnectivityIssues(e))
             }
             // Block proposal is bad
             Err(reject_code) => {
@@ -857,7 +933,7 @@ impl Signer {
                     "reject_reason" => %reject_code,
                     "reject_code" => ?reject_code,
                 );
-                Some(self.create_block_rejection(reject_code, block))
+                Some(reject_code)
             }
             // Block proposal passed check, still don't know if valid
             Ok(_) => None,
@@ -886,7 +962,10 @@ impl Signer {
                         ack.reason
                     );
                 }
-                crate::monitoring::actions::increment_block_responses_sent(accepted);
+                crate::monitoring::actions::increment_block_responses_sent(
+                    &self.stacks_address,
+                    accepted,
+                );
                 if let Some(block) = block {
                     crate::monitoring::actions::record_block_response_latency(block);
                 }
@@ -906,9 +985,7 @@ impl Signer {
         const NUM_REPEATS: usize = 1;
         let mut count = 0;
         let public_keys = TEST_REPEAT_PROPOSAL_RESPONSE.get();
-        if !public_keys.contains(
-            &stacks_common::types::chainstate::StacksPublicKey::from_private(&self.private_key),
-        ) {
+        if !public_keys.contains(&self.signing_key.public_key()) {
             count = NUM_REPEATS;
         }
         while count <= NUM_REPEATS {
@@ -950,6 +1027,63 @@ impl Signer {
             .insert_update(address, update.clone());
     }
 
+    /// Compare the local state machine and the latest updates from the rest of the signer set
+    /// with the signer set's majority view, and report any change in how they diverge
+    fn check_state_divergence(&mut self) {
+        let majority_view = self.global_state_evaluator.determine_majority_view();
+        let local_divergence = match &self.local_state_machine {
+            LocalStateMachine::Initialized(state) => majority_view.state_machine_divergences(state),
+            LocalStateMachine::Pending { prior, .. } => {
+                majority_view.state_machine_divergences(prior)
+            }
+            LocalStateMachine::Uninitialized => vec![],
+        };
+        let divergent_signers = self.global_state_evaluator.determine_divergent_signers();
+        if local_divergence == self.state_divergence && divergent_signers == self.divergent_signers
+        {
+            return;
+        }
+        crate::monitoring::actions::record_state_divergence(
+            &self.stacks_address,
+            self.reward_cycle,
+            &local_divergence,
+            &divergent_signers,
+        );
+        if local_divergence != self.state_divergence {
+            if local_divergence.is_empty() {
+                info!(
+                    "{self}: Local state machine agrees with the signer set's majority view again"
+                );
+            } else {
+                warn!(
+                    "{self}: Local state machine diverges from the signer set's majority view";
+                    "divergence" => local_divergence.iter().map(|d| d.as_str()).collect::<Vec<_>>().join(","),
+                    "local_state" => ?self.local_state_machine,
+                    "majority_view" => ?majority_view,
+                );
+            }
+        }
+        if divergent_signers != self.divergent_signers {
+            let divergent_weight: u32 = divergent_signers.iter().map(|signer| signer.weight).sum();
+            info!(
+                "{self}: {} signer(s) diverge from the signer set's majority view", divergent_signers.len();
+                "divergent_weight" => divergent_weight,
+                "total_weight" => self.global_state_evaluator.total_weight,
+                "divergent_signers" => divergent_signers
+                    .iter()
+                    .map(|signer| format!(
+                        "{}:{}",
+                        signer.address,
+                        signer.divergences.iter().map(|d| d.as_str()).collect::<Vec<_>>().join("+")
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+        self.state_divergence = local_divergence;
+        self.divergent_signers = divergent_signers;
+    }
+
     /// Handle block proposal messages submitted to signers stackerdb
     fn handle_block_proposal(
         &mut self,
@@ -967,6 +1101,15 @@ impl Signer {
             return;
         }
 
+        if self.admin_state.is_paused() {
+            info!(
+                "{self}: Signing is paused by the operator. Ignoring block proposal.";
+                "signer_signature_hash" => %block_proposal.block.header.signer_signature_hash(),
+                "block_id" => %block_proposal.block.block_id(),
+            );
+            return;
+        }
+
         if block_proposal
             .block
             .header
@@ -1017,7 +1160,7 @@ impl Signer {
             "burn_height" => block_proposal.burn_height,
             "consensus_hash" => %block_proposal.block.header.consensus_hash,
         );
-        crate::monitoring::actions::increment_block_proposals_received();
+        crate::monitoring::actions::increment_block_proposals_received(&self.stacks_address);
         #[cfg(any(test, feature = "testing"))]
         let mut block_info = BlockInfo::from(block_proposal.clone());
         #[cfg(not(any(test, feature = "testing")))]
@@ -1037,16 +1180,36 @@ impl Signer {
                     .ok();
         }
 
-        // Check if proposal can be rejected now if not valid against sortition view
-        let block_response =
-            self.check_block_against_state(stacks_client, sortition_state, &block_proposal.block);
+        // Check if proposal can be rejected now if not valid against sortition view or our block policy
+        let reject_reason = self
+            .check_block_against_state(stacks_client, sortition_state, &block_proposal.block)
+            .or_else(|| self.check_block_against_policy(&block_proposal.block));
 
         #[cfg(any(test, feature = "testing"))]
-        let block_response =
-            self.test_reject_block_proposal(block_proposal, &mut block_info, block_response);
+        let reject_reason =
+            self.test_reject_block_proposal(block_proposal, &mut block_info, reject_reason);
 
-        if let Some(block_response) = block_response {
+        if let Some(reject_reason) = reject_reason {
             // We know proposal is invalid. Send rejection message, do not do further validation and do not store it.
+            let block_response =
+                match self.create_block_rejection(reject_reason, &block_proposal.block) {
+                    Ok(block_response) => block_response,
+                    Err(e) => {
+                        warn!(
+                            "{self}: Failed to sign block rejection: {e}";
+                            "signer_signature_hash" => %signer_signature_hash,
+                            "block_id" => %block_proposal.block.block_id(),
+                        );
+                        return;
+                    }
+                };
+            let record = BlockAuditRecord::new(
+                &self.stacks_address,
+                &block_info,
+                AuditStage::ProposalCheck,
+                &block_response,
+            );
+            self.record_block_decision(sortition_state.as_ref(), record);
             self.send_block_response(Some(&block_info.block), block_response);
         } else {
             // Just in case check if the last block validation submission timed out.
@@ -1091,14 +1254,26 @@ impl Signer {
     }
 
     fn handle_prior_proposal_eval(&mut self, block_info: &BlockInfo) {
-        let Some(block_response) = self.determine_response(block_info) else {
-            // We are still waiting for a response for this block. Do nothing.
-            debug!(
-                "{self}: Received a block proposal for a block we are already validating.";
-                "signer_signature_hash" => %block_info.signer_signature_hash(),
-                "block_id" => %block_info.block.block_id()
-            );
-            return;
+        let block_response = match self.determine_response(block_info) {
+            Some(Ok(block_response)) => block_response,
+            Some(Err(e)) => {
+                // The miner will re-propose the block if it still needs our response
+                warn!(
+                    "{self}: Failed to sign block response: {e}";
+                    "signer_signature_hash" => %block_info.signer_signature_hash(),
+                    "block_id" => %block_info.block.block_id()
+                );
+                return;
+            }
+            None => {
+                // We are still waiting for a response for this block. Do nothing.
+                debug!(
+                    "{self}: Received a block proposal for a block we are already validating.";
+                    "signer_signature_hash" => %block_info.signer_signature_hash(),
+                    "block_id" => %block_info.block.block_id()
+                );
+                return;
+            }
         };
 
         self.impl_send_block_response(Some(&block_info.block), block_response);
@@ -1129,7 +1304,7 @@ impl Signer {
         &mut self,
         stacks_client: &StacksClient,
         proposed_block: &NakamotoBlock,
-    ) -> Option<BlockResponse> {
+    ) -> Option<RejectReason> {
         let signer_signature_hash = proposed_block.header.signer_signature_hash();
         // If this is a tenure change block, ensure that it confirms the correct number of blocks from the parent tenure.
         if let Some(tenure_change) = proposed_block.get_tenure_change_tx_payload() {
@@ -1143,22 +1318,14 @@ impl Signer {
                 self.proposal_config.reorg_attempts_activity_timeout,
             ) {
                 Ok(true) => return None,
-                Ok(false) => {
-                    return Some(self.create_block_rejection(
-                        RejectReason::SortitionViewMismatch,
-                        proposed_block,
-                    ))
-                }
+                Ok(false) => return Some(RejectReason::SortitionViewMismatch),
                 Err(e) => {
                     warn!("{self}: Error checking block proposal: {e}";
                         "signer_signature_hash" => %signer_signature_hash,
                         "block_id" => %proposed_block.block_id()
                     );
-                    return Some(self.create_block_rejection(
-                        RejectReason::ConnectivityIssues(
-                            "error checking block proposal".to_string(),
-                        ),
-                        proposed_block,
+                    return Some(RejectReason::ConnectivityIssues(
+                        "error checking block proposal".to_string(),
                     ));
                 }
             }
@@ -1181,10 +1348,7 @@ impl Signer {
                         "proposed_block_signer_signature_hash" => %signer_signature_hash,
                         "proposed_chain_length" => proposed_block.header.chain_length,
                     );
-                    Some(self.create_block_rejection(
-                        RejectReason::SortitionViewMismatch,
-                        proposed_block,
-                    ))
+                    Some(RejectReason::SortitionViewMismatch)
                 } else {
                     None
                 }
@@ -1194,11 +1358,8 @@ impl Signer {
                     "signer_signature_hash" => %signer_signature_hash,
                     "block_id" => %proposed_block.block_id()
                 );
-                Some(self.create_block_rejection(
-                    RejectReason::ConnectivityIssues(
-                        "failed to check block against signer db".to_string(),
-                    ),
-                    proposed_block,
+                Some(RejectReason::ConnectivityIssues(
+                    "failed to check block against signer db".to_string(),
                 ))
             }
         }
@@ -1211,7 +1372,10 @@ impl Signer {
         block_validate_ok: &BlockValidateOk,
         sortition_state: &mut Option<SortitionsView>,
     ) -> Option<BlockResponse> {
-        crate::monitoring::actions::increment_block_validation_responses(true);
+        crate::monitoring::actions::increment_block_validation_responses(
+            &self.stacks_address,
+            true,
+        );
         let signer_signature_hash = block_validate_ok.signer_signature_hash;
         if self
             .submitted_block_proposal
@@ -1246,11 +1410,34 @@ impl Signer {
             return None;
         }
 
-        if let Some(block_response) =
-            self.check_block_against_signer_db_state(stacks_client, &block_info.block)
-        {
-            let block_rejection = block_response.as_block_rejection()?;
-            // The signer db state has changed. We no longer view this block as valid. Override the validation response.
+        let block_response = if let Some(reject_reason) = self
+            .check_block_against_signer_db_state(stacks_client, &block_info.block)
+            .or_else(|| {
+                if !self.block_policy.limits_block_cost() {
+                    return None;
+                }
+                let reject_reason = match stacks_client
+                    .get_block_limit(block_info.burn_block_height)
+                {
+                    Ok(block_limit) => self
+                        .block_policy
+                        .check_block_cost(
+                            &block_info.block,
+                            &block_validate_ok.cost,
+                            &block_limit,
+                        )
+                        .err()?,
+                    Err(e) => {
+                        // never skip the rule silently
+                        warn!("{self}: Failed to get the block limit to check the block's cost: {e:?}");
+                        RejectReason::ConnectivityIssues(
+                            "failed to get the block limit from the stacks node".to_string(),
+                        )
+                    }
+                };
+                Some(reject_reason)
+            }) {
+            // The signer db state has changed or the block breaks our block policy. We no longer view this block as valid. Override the validation response.
             if let Err(e) = block_info.mark_locally_rejected() {
                 if !block_info.has_reached_consensus() {
                     warn!("{self}: Failed to mark block as locally rejected: {e:?}");
@@ -1259,8 +1446,18 @@ impl Signer {
             self.signer_db
                 .insert_block(&block_info)
                 .unwrap_or_else(|e| self.handle_insert_block_error(e));
+            // If we cannot sign, the rejection is resent when the miner re-proposes the block
+            let block_response = self
+                .create_block_rejection(reject_reason, &block_info.block)
+                .inspect_err(|e| {
+                    warn!("{self}: Failed to sign block rejection: {e}";
+                        "signer_signature_hash" => %signer_signature_hash,
+                    )
+                })
+                .ok()?;
+            let block_rejection = block_response.as_block_rejection()?;
             self.handle_block_rejection(block_rejection, sortition_state);
-            Some(block_response)
+            block_response
         } else {
             if let Err(e) = block_info.mark_locally_accepted(false) {
                 if !block_info.has_reached_consensus() {
@@ -1279,11 +1476,29 @@ impl Signer {
             self.signer_db
                 .insert_block(&block_info)
                 .unwrap_or_else(|e| self.handle_insert_block_error(e));
-            let block_response = self.create_block_acceptance(&block_info.block);
+            // If we cannot sign, the acceptance is resent when the miner re-proposes the block
+            let block_response = self
+                .create_block_acceptance(&block_info.block)
+                .inspect_err(|e| {
+                    warn!("{self}: Failed to sign block acceptance: {e}";
+                        "signer_signature_hash" => %signer_signature_hash,
+                    )
+                })
+                .ok()?;
             // have to save the signature _after_ the block info
             self.handle_block_signature(stacks_client, block_response.as_block_accepted()?);
-            Some(block_response)
-        }
+            block_response
+        };
+        let mut record = BlockAuditRecord::new(
+            &self.stacks_address,
+            &block_info,
+            AuditStage::NodeValidation,
+            &block_response,
+        );
+        record.validation_result = Some("ok".to_string());
+        record.validation_time_ms = Some(block_validate_ok.validation_time_ms);
+        self.record_block_decision(sortition_state.as_ref(), record);
+        Some(block_response)
     }
 
     /// Handle the block validate reject response. Returns our block response if we have one
@@ -1292,7 +1507,10 @@ impl Signer {
         block_validate_reject: &BlockValidateReject,
         sortition_state: &mut Option<SortitionsView>,
     ) -> Option<BlockResponse> {
-        crate::monitoring::actions::increment_block_validation_responses(false);
+        crate::monitoring::actions::increment_block_validation_responses(
+            &self.stacks_address,
+            false,
+        );
         let signer_signature_hash = block_validate_reject.signer_signature_hash;
         if self
             .submitted_block_proposal
@@ -1318,7 +1536,7 @@ impl Signer {
         }
         let block_rejection = BlockRejection::from_validate_rejection(
             block_validate_reject.clone(),
-            &self.private_key,
+            self.signing_key.as_ref(),
             self.mainnet,
             self.signer_db.calculate_tenure_extend_timestamp(
                 self.proposal_config
@@ -1327,14 +1545,30 @@ impl Signer {
                 &block_info.block,
                 false,
             ),
-        );
+        )
+        .inspect_err(|e| {
+            warn!("{self}: Failed to sign block rejection: {e}";
+                "signer_signature_hash" => %signer_signature_hash,
+            )
+        })
+        .ok()?;
 
         block_info.reject_reason = Some(block_rejection.response_data.reject_reason.clone());
         self.signer_db
             .insert_block(&block_info)
             .unwrap_or_else(|e| self.handle_insert_block_error(e));
         self.handle_block_rejection(&block_rejection, sortition_state);
-        Some(BlockResponse::Rejected(block_rejection))
+        let block_response = BlockResponse::Rejected(block_rejection);
+        let mut record = BlockAuditRecord::new(
+            &self.stacks_address,
+            &block_info,
+            AuditStage::NodeValidation,
+            &block_response,
+        );
+        record.validation_result = Some(format!("{:?}", block_validate_reject.reason_code));
+        record.validation_reason = Some(block_validate_reject.reason.clone());
+        self.record_block_decision(sortition_state.as_ref(), record);
+        Some(block_response)
     }
 
     /// Handle the block validate response returned from our prior calls to submit a block for validation
@@ -1358,6 +1592,7 @@ impl Signer {
         };
         // Remove this block validation from the pending table
         let signer_sig_hash = block_validate_response.signer_signature_hash();
+        self.block_validations.finished(&signer_sig_hash);
         self.signer_db
             .remove_pending_block_validation(&signer_sig_hash)
             .unwrap_or_else(|e| warn!("{self}: Failed to remove pending block validation: {e:?}"));
@@ -1449,18 +1684,33 @@ impl Signer {
             "{self}: Failed to receive block validation response within {} ms. Rejecting block.", self.block_proposal_validation_timeout.as_millis();
             "signer_signature_hash" => %proposal_signer_sighash,
         );
-        let rejection = self.create_block_rejection(
+        let rejection = match self.create_block_rejection(
             RejectReason::ConnectivityIssues(
                 "failed to receive block validation response in time".to_string(),
             ),
             &block_info.block,
-        );
+        ) {
+            Ok(rejection) => rejection,
+            Err(e) => {
+                warn!("{self}: Failed to sign block rejection: {e}";
+                    "signer_signature_hash" => %proposal_signer_sighash,
+                );
+                return;
+            }
+        };
         block_info.reject_reason = Some(rejection.get_response_data().reject_reason.clone());
         if let Err(e) = block_info.mark_locally_rejected() {
             if !block_info.has_reached_consensus() {
                 warn!("{self}: Failed to mark block as locally rejected: {e:?}");
             }
         };
+        let record = BlockAuditRecord::new(
+            &self.stacks_address,
+            &block_info,
+            AuditStage::ValidationTimeout,
+            &rejection,
+        );
+        self.record_block_decision(None, record);
         self.impl_send_block_response(Some(&block_info.block), rejection);
 
         self.signer_db
@@ -1847,6 +2097,16 @@ impl Signer {
                 debug!("{self}: Cannot confirm that we have processed parent, but we've waited proposal_wait_for_parent_time, will submit proposal");
             }
         }
+        if self.block_validations.is_pending(
+            &signer_signature_hash,
+            self.block_proposal_validation_timeout,
+        ) {
+            info!("{self}: Block proposal was already submitted for validation by another signing identity, waiting for its result";
+                "signer_signature_hash" => %signer_signature_hash,
+            );
+            self.submitted_block_proposal = Some((signer_signature_hash, Instant::now()));
+            return;
+        }
         match stacks_client.submit_block_for_validation(
             block.clone(),
             if self.validate_with_replay_tx {
@@ -1860,6 +2120,10 @@ impl Signer {
         ) {
             Ok(_) => {
                 self.submitted_block_proposal = Some((signer_signature_hash, Instant::now()));
+                self.block_validations.submitted(
+                    signer_signature_hash,
+                    self.block_proposal_validation_timeout,
+                );
             }
             Err(ClientError::RequestFailure(status)) => {
                 if status.as_u16() == TOO_MANY_REQUESTS_STATUS {
@@ -1884,7 +2148,13 @@ impl Signer {
     /// Send a mock signature to stackerdb to prove we are still alive
     fn mock_sign(&mut self, mock_proposal: MockProposal) {
         info!("{self}: Mock signing mock proposal: {mock_proposal:?}");
-        let mock_signature = MockSignature::new(mock_proposal, &self.private_key);
+        let mock_signature = match MockSignature::new(mock_proposal, self.signing_key.as_ref()) {
+            Ok(mock_signature) => mock_signature,
+            Err(e) => {
+                warn!("{self}: Failed to sign mock proposal: {e}");
+                return;
+            }
+        };
         let message = SignerMessage::MockSignature(mock_signature);
         if let Err(e) = self
             .stackerdb
@@ -1954,7 +2224,11 @@ fn should_reevaluate_block(block_info: &BlockInfo) -> bool {
             | RejectReason::NotLatestSortitionWinner
             | RejectReason::InvalidParentBlock
             | RejectReason::DuplicateBlockFound
-            | RejectReason::IrrecoverablePubkeyHash => {
+            | RejectReason::IrrecoverablePubkeyHash
+            | RejectReason::BlockCostLimitExceeded
+            | RejectReason::TooFewTransactionsAfterIdle
+            | RejectReason::BlockedContractCall
+            | RejectReason::TenureExtendTooFrequent => {
                 // No need to re-validate these types of rejections.
                 false
             }
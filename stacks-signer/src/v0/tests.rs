@@ -17,7 +17,7 @@ use std::collections::HashMap;
 use std::sync::LazyLock;
 
 use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
-use libsigner::v0::messages::{BlockResponse, RejectReason};
+use libsigner::v0::messages::RejectReason;
 use libsigner::BlockProposal;
 use stacks_common::types::chainstate::StacksPublicKey;
 use stacks_common::util::get_epoch_time_secs;
@@ -81,12 +81,10 @@ impl Signer {
         &mut self,
         block_proposal: &BlockProposal,
         block_info: &mut BlockInfo,
-        block_response: Option<BlockResponse>,
-    ) -> Option<BlockResponse> {
+        reject_reason: Option<RejectReason>,
+    ) -> Option<RejectReason> {
         let public_keys = TEST_REJECT_ALL_BLOCK_PROPOSAL.get();
-        if public_keys.contains(
-            &stacks_common::types::chainstate::StacksPublicKey::from_private(&self.private_key),
-        ) {
+        if public_keys.contains(&self.signing_key.public_key()) {
             warn!("{self}: Rejecting block proposal automatically due to testing directive";
                 "block_id" => %block_proposal.block.block_id(),
                 "height" => block_proposal.block.header.chain_length,
@@ -106,9 +104,9 @@ impl Signer {
             self.signer_db
                 .insert_block(block_info)
                 .unwrap_or_else(|e| self.handle_insert_block_error(e));
-            Some(self.create_block_rejection(RejectReason::TestingDirective, &block_proposal.block))
+            Some(RejectReason::TestingDirective)
         } else {
-            block_response
+            reject_reason
         }
     }
 
@@ -133,9 +131,7 @@ impl Signer {
     /// Ignore block proposals if the TEST_IGNORE_ALL_BLOCK_PROPOSALS flag is set for the signer's public key
     pub fn test_ignore_all_block_proposals(&self, block_proposal: &BlockProposal) -> bool {
         let public_keys = TEST_IGNORE_ALL_BLOCK_PROPOSALS.get();
-        if public_keys.contains(
-            &stacks_common::types::chainstate::StacksPublicKey::from_private(&self.private_key),
-        ) {
+        if public_keys.contains(&self.signing_key.public_key()) {
             warn!("{self}: Ignoring block proposal due to testing directive";
                 "block_id" => %block_proposal.block.block_id(),
                 "height" => block_proposal.block.header.chain_length,
@@ -161,9 +157,7 @@ impl Signer {
     /// Get the pinned signer version for the signer
     pub fn test_get_signer_protocol_version(&self) -> u64 {
         let public_keys = TEST_PIN_SUPPORTED_SIGNER_PROTOCOL_VERSION.get();
-        if let Some(version) = public_keys.get(
-            &stacks_common::types::chainstate::StacksPublicKey::from_private(&self.private_key),
-        ) {
+        if let Some(version) = public_keys.get(&self.signing_key.public_key()) {
             warn!("{self}: signer version is pinned to {version}");
             return *version;
         }
@@ -5261,7 +5261,7 @@ impl NakamotoChainState {
     /// Generate a "phantom" transaction to include STXMintEvents for
     /// lockups that could not be attached to a Coinbase transaction
     /// (because the block doesn't have a Coinbase transaction).
-    fn generate_phantom_unlock_tx(
+    pub(crate) fn generate_phantom_unlock_tx(
         events: Vec<StacksTransactionEvent>,
         config: &ChainstateConfig,
         stacks_block_height: u64,
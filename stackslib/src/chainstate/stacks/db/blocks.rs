@@ -122,6 +122,11 @@ pub enum MemPoolRejection {
     NoTenureChangeViaMempool,
     NoSuchChainTip(ConsensusHash, BlockHeaderHash),
     ConflictingNonceInMempool,
+    /// The replacement pays a higher fee than the conflicting transaction, but not enough to
+    /// replace it: (actual, expected)
+    ReplacementFeeTooLow(u64, u64),
+    /// The conflicting transaction has already been replaced by fee this many times
+    TooManyReplacements(u64),
     TooMuchChaining {
         max_nonce: u64,
         actual_nonce: u64,
@@ -269,6 +274,16 @@ impl MemPoolRejection {
                 Some(json!({"message": e.to_string()})),
             ),
             ConflictingNonceInMempool => ("ConflictingNonceInMempool", None),
+            ReplacementFeeTooLow(actual, expected) => (
+                "ReplacementFeeTooLow",
+                Some(json!({
+                    "expected": expected,
+                    "actual": actual})),
+            ),
+            TooManyReplacements(replacements) => (
+                "TooManyReplacements",
+                Some(json!({ "replacements": replacements })),
+            ),
             ContractAlreadyExists(id) => (
                 "ContractAlreadyExists",
                 Some(json!({ "contract_identifier": id.to_string() })),
@@ -1,8 +1,8 @@
 use clarity::vm::analysis::ContractAnalysis;
 use clarity::vm::costs::ExecutionCost;
 pub use clarity::vm::events::StacksTransactionEvent;
-use clarity::vm::types::{QualifiedContractIdentifier, Value};
-use libstackerdb::StackerDBChunkData;
+use clarity::vm::types::Value;
+pub use libstackerdb::StackerDBChunksEvent;
 use stacks_common::codec::StacksMessageCodec;
 use stacks_common::types::chainstate::BlockHeaderHash;
 use stacks_common::util::hash::to_hex;
@@ -85,12 +85,3 @@ impl From<(NakamotoBlock, BlockHeaderHash)> for StacksBlockEventData {
         }
     }
 }
-
-/// Event structure for newly-arrived StackerDB data
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-pub struct StackerDBChunksEvent {
-    /// The contract ID for the StackerDB instance
-    pub contract_id: QualifiedContractIdentifier,
-    /// The chunk data for newly-modified slots
-    pub modified_slots: Vec<StackerDBChunkData>,
-}
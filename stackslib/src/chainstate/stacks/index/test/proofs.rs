@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use stacks_common::marf_proof::MarfProof;
+use stacks_common::util::hash::hex_bytes;
+
 use super::*;
 use crate::chainstate::stacks::index::*;
 
@@ -195,3 +198,95 @@ fn ncc_verifier_catches_stale_proof() {
     println!("DEBUG: verify(old_v)");
     assert!(!proof_5.verify(&triepath_4, &marf_value_4, &root_hash_5, &root_to_block));
 }
+
+#[test]
+fn standalone_verifier_checks_proofs() {
+    let marf_opts = MARFOpenOpts::default();
+    let mut m = MARF::from_path(":memory:", marf_opts).unwrap();
+
+    let mut blocks = vec![StacksBlockId::sentinel()];
+    for i in 0..20u8 {
+        let block = StacksBlockId([i; 32]);
+        m.begin(blocks.last().unwrap(), &block).unwrap();
+        m.insert(&format!("K{i}"), MARFValue::from_value(&format!("V{i}")))
+            .unwrap();
+        if i > 0 && i % 7 == 0 {
+            m.insert("K0", MARFValue::from_value(&format!("V0-{i}")))
+                .unwrap();
+        }
+        m.commit().unwrap();
+        blocks.push(block);
+    }
+
+    let tip = blocks.last().unwrap().clone();
+    let root_hash = m.get_root_hash_at(&tip).unwrap();
+    let block_roots: HashMap<_, _> = blocks[1..]
+        .iter()
+        .map(|block| (block.clone(), m.get_root_hash_at(block).unwrap()))
+        .collect();
+    let root_to_block = m
+        .borrow_storage_backend()
+        .read_root_to_block_table()
+        .unwrap();
+
+    // written at the tip, two blocks back, and long ago (and rewritten since)
+    for (key, value, has_backptrs) in [
+        ("K19", "V19", false),
+        ("K17", "V17", true),
+        ("K3", "V3", true),
+        ("K0", "V0-14", true),
+    ] {
+        let proof =
+            TrieMerkleProof::from_entry(&mut m.borrow_storage_backend(), key, value, &tip).unwrap();
+        assert!(proof.verify(
+            &TrieHash::from_key(key),
+            &MARFValue::from_value(value),
+            &root_hash,
+            &root_to_block
+        ));
+
+        let proof_hex = proof.to_hex();
+        let standalone_proof = MarfProof::from_hex(&proof_hex).unwrap();
+        assert_eq!(
+            MarfProof::from_hex(&format!("0x{proof_hex}")).unwrap(),
+            standalone_proof
+        );
+        assert_eq!(
+            !standalone_proof.referenced_blocks().is_empty(),
+            has_backptrs
+        );
+        assert!(
+            standalone_proof.verify(key, value, &root_hash, |block| block_roots
+                .get(block)
+                .cloned())
+        );
+
+        // wrong value, key, or root hash
+        assert!(
+            !standalone_proof.verify(key, "V1", &root_hash, |block| block_roots
+                .get(block)
+                .cloned())
+        );
+        assert!(
+            !standalone_proof.verify("K1", value, &root_hash, |block| block_roots
+                .get(block)
+                .cloned())
+        );
+        assert!(
+            !standalone_proof.verify(key, value, &block_roots[&blocks[5]], |block| {
+                block_roots.get(block).cloned()
+            })
+        );
+
+        // the roots of the blocks that back-pointers refer to must be known
+        if has_backptrs {
+            assert!(!standalone_proof.verify(key, value, &root_hash, |_| None));
+            assert!(!standalone_proof.verify(key, value, &root_hash, |_| Some(root_hash.clone())));
+        }
+
+        // truncated or padded encodings are rejected
+        let proof_bytes = hex_bytes(&proof_hex).unwrap();
+        assert!(MarfProof::from_bytes(&proof_bytes[..proof_bytes.len() - 1]).is_err());
+        assert!(MarfProof::from_bytes(&[proof_bytes.as_slice(), &[0]].concat()).is_err());
+    }
+}
@@ -16,10 +16,13 @@
 
 use std::ffi::OsStr;
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{fs, io};
 
 use clarity::vm::coverage::CoverageReporter;
+use clarity::vm::debugger::{Breakpoint, Debugger, BREAKPOINTS_SUPPORTED, BREAKPOINTS_UNSUPPORTED};
+use clarity::vm::profiler::CostProfiler;
+use clarity::vm::EvalHook;
 use lazy_static::lazy_static;
 use rand::Rng;
 use rusqlite::{Connection, OpenFlags};
@@ -46,7 +49,9 @@ use crate::clarity::vm::analysis::contract_interface_builder::build_contract_int
 use crate::clarity::vm::analysis::errors::CheckError;
 use crate::clarity::vm::analysis::{AnalysisDatabase, ContractAnalysis};
 use crate::clarity::vm::ast::{build_ast_with_rules, ASTRules};
-use crate::clarity::vm::contexts::{AssetMap, GlobalContext, OwnedEnvironment};
+use crate::clarity::vm::contexts::{
+    AssetMap, Environment, GlobalContext, LocalContext, OwnedEnvironment,
+};
 use crate::clarity::vm::costs::{ExecutionCost, LimitedCostTracker};
 use crate::clarity::vm::database::{
     BurnStateDB, ClarityDatabase, HeadersDB, STXBalance, NULL_BURN_STATE_DB,
@@ -54,8 +59,8 @@ use crate::clarity::vm::database::{
 use crate::clarity::vm::errors::{Error, InterpreterResult, RuntimeErrorType};
 use crate::clarity::vm::types::{PrincipalData, QualifiedContractIdentifier};
 use crate::clarity::vm::{
-    analysis, ast, eval_all, ClarityVersion, ContractContext, ContractName, SymbolicExpression,
-    Value,
+    analysis, ast, eval_all, ClarityVersion, ContractContext, ContractName, ExecutionResult,
+    SymbolicExpression, Value,
 };
 use crate::clarity_vm::database::marf::{MarfedKV, WritableMarfStore};
 use crate::clarity_vm::database::MemoryBackingStore;
@@ -116,6 +121,8 @@ where command is one of:
   eval_raw           to typecheck and evaluate an expression without a contract or database context.
   repl               to typecheck and evaluate expressions in a stdin/stdout loop.
   execute            to execute a public function of a defined contract.
+  debug              to run `eval` or `execute` under an interactive step debugger.
+  test               to deploy a directory of contracts and run their `test-` functions.
   generate_address   to generate a random Stacks public address for testing purposes.
 ",
         invoked_by
@@ -407,6 +414,7 @@ fn with_env_costs<F, R>(
     header_db: &CLIHeadersDB,
     marf: &mut WritableMarfStore,
     coverage: Option<&mut CoverageReporter>,
+    eval_hooks: Vec<&mut dyn EvalHook>,
     f: F,
 ) -> (R, ExecutionCost)
 where
@@ -435,6 +443,9 @@ where
     if let Some(coverage) = coverage {
         vm_env.add_eval_hook(coverage);
     }
+    for eval_hook in eval_hooks.into_iter() {
+        vm_env.add_eval_hook(eval_hook);
+    }
     let result = f(&mut vm_env);
     let cost = vm_env.get_cost_total();
     (result, cost)
@@ -489,6 +500,109 @@ fn save_coverage(
     }
 }
 
+fn save_profile(profile_folder: Option<String>, profiler: Option<CostProfiler>) {
+    if let (Some(profile_folder), Some(profiler)) = (profile_folder, profiler) {
+        friendly_expect(
+            profiler.write_to_dir(&PathBuf::from(profile_folder)),
+            "Profile generation failure",
+        );
+    }
+}
+
+/// Address that `test` deploys contracts from and sends test transactions as.
+const TEST_DEPLOYER: &str = "S1G2081040G2081040G2081040G208105NK8PE5";
+
+/// Collects the values passed to `print` during a test. Unlike the transaction's events, these
+///  are kept when the test fails and its transaction is rolled back.
+#[derive(Default)]
+struct PrintCollector {
+    prints: Vec<Value>,
+}
+
+impl EvalHook for PrintCollector {
+    fn will_begin_eval(
+        &mut self,
+        _env: &mut Environment,
+        _context: &LocalContext,
+        _expr: &SymbolicExpression,
+    ) {
+    }
+
+    fn did_finish_eval(
+        &mut self,
+        _env: &mut Environment,
+        _context: &LocalContext,
+        expr: &SymbolicExpression,
+        res: &Result<Value, Error>,
+    ) {
+        let is_print = expr
+            .match_list()
+            .and_then(|list| list.first())
+            .and_then(|head| head.match_atom())
+            .is_some_and(|name| name.as_str() == "print");
+        if let (true, Ok(value)) = (is_print, res) {
+            self.prints.push(value.clone());
+        }
+    }
+
+    fn did_complete(&mut self, _result: Result<&mut ExecutionResult, String>) {}
+}
+
+/// Typecheck and deploy a contract for `test`, registering its source with the coverage
+///  reporter (if any).
+fn deploy_test_contract(
+    mainnet: bool,
+    marf: &mut MemoryBackingStore,
+    contract_identifier: &QualifiedContractIdentifier,
+    contract_src_file: &str,
+    contract_content: &str,
+    coverage: Option<(&mut CoverageReporter, &PathBuf)>,
+) -> Result<ContractAnalysis, String> {
+    let mut ast = parse(
+        contract_identifier,
+        contract_content,
+        ClarityVersion::Clarity2,
+    )
+    .map_err(|e| format!("Failed to parse program: {e}"))?;
+    // the analysis is only saved once the contract is initialized, so a contract that failed
+    // to deploy can be retried
+    let analysis = run_analysis_free(contract_identifier, &mut ast, marf, false)
+        .map_err(|(e, _)| format!("Type check error: {e}"))?;
+
+    let mut vm_env = OwnedEnvironment::new_free(
+        mainnet,
+        default_chain_id(mainnet),
+        marf.as_clarity_db(),
+        DEFAULT_CLI_EPOCH,
+    );
+    if let Some((coverage, coverage_folder)) = coverage {
+        let mut coverage_file = coverage_folder.clone();
+        coverage_file.push(contract_identifier.name.as_str());
+        coverage_file.set_extension("clarcovref");
+        CoverageReporter::register_src_file(
+            contract_identifier,
+            contract_src_file,
+            &ast,
+            &coverage_file,
+        )
+        .expect("Coverage reference file generation failure");
+        vm_env.add_eval_hook(coverage);
+    }
+    vm_env
+        .initialize_versioned_contract(
+            contract_identifier.clone(),
+            ClarityVersion::Clarity2,
+            contract_content,
+            None,
+            ASTRules::PrecheckSize,
+        )
+        .map_err(|e| format!("Initialization error: {e}"))?;
+    marf.as_analysis_db()
+        .execute(|db| db.insert_contract(contract_identifier, &analysis))
+        .map_err(|e| format!("Failed to save contract analysis: {e}"))?;
+    Ok(analysis)
+}
+
 struct CLIHeadersDB {
     db_path: String,
     conn: Connection,
@@ -987,6 +1101,15 @@ pub fn add_serialized_output(result: &mut serde_json::Value, value: Value) {
 
 /// Returns (process-exit-code, Option<json-output>)
 pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_json::Value>) {
+    invoke_command_with_hook(invoked_by, args, None)
+}
+
+/// Like `invoke_command`, but `eval` and `execute` run with `eval_hook` attached.
+fn invoke_command_with_hook(
+    invoked_by: &str,
+    args: &[String],
+    eval_hook: Option<&mut dyn EvalHook>,
+) -> (i32, Option<serde_json::Value>) {
     if args.is_empty() {
         print_usage(invoked_by);
         return (1, None);
@@ -1384,8 +1507,13 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
             );
 
             let (_, _, result_and_cost) = in_block(header_db, marf_kv, |header_db, mut marf| {
-                let result_and_cost =
-                    with_env_costs(mainnet, &header_db, &mut marf, None, |vm_env| {
+                let result_and_cost = with_env_costs(
+                    mainnet,
+                    &header_db,
+                    &mut marf,
+                    None,
+                    eval_hook.into_iter().collect(),
+                    |vm_env| {
                         vm_env
                             .get_exec_environment(None, None, &placeholder_context)
                             .eval_read_only_with_rules(
@@ -1393,7 +1521,8 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
                                 &evalInput.content,
                                 ASTRules::PrecheckSize,
                             )
-                    });
+                    },
+                );
                 (header_db, marf, result_and_cost)
             });
 
@@ -1454,6 +1583,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
                     &header_db,
                     &mut marf,
                     coverage.as_mut(),
+                    vec![],
                     |vm_env| {
                         vm_env
                             .get_exec_environment(None, None, &placeholder_context)
@@ -1537,7 +1667,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
             );
             let result_and_cost = at_block(chain_tip, marf_kv, |mut marf| {
                 let result_and_cost =
-                    with_env_costs(mainnet, &header_db, &mut marf, None, |vm_env| {
+                    with_env_costs(mainnet, &header_db, &mut marf, None, vec![], |vm_env| {
                         vm_env
                             .get_exec_environment(None, None, &placeholder_context)
                             .eval_read_only_with_rules(
@@ -1656,6 +1786,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
                                 &header_db,
                                 &mut marf,
                                 coverage.as_mut(),
+                                vec![],
                                 |vm_env| {
                                     vm_env.initialize_versioned_contract(
                                         contract_identifier,
@@ -1721,14 +1852,21 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
         "execute" => {
             let mut argv = args.to_vec();
             let coverage_folder = consume_arg(&mut argv, &["--c"], true).unwrap_or(None);
+            let profile_folder = consume_arg(&mut argv, &["--profile"], true).unwrap_or(None);
 
             let costs = matches!(consume_arg(&mut argv, &["--costs"], false), Ok(Some(_)));
             let assets = matches!(consume_arg(&mut argv, &["--assets"], false), Ok(Some(_)));
 
             if argv.len() < 5 {
-                eprintln!("Usage: {} {} [--costs] [--assets] [vm-state.db] [contract-identifier] [public-function-name] [sender-address] [args...]", invoked_by, argv[0]);
+                eprintln!("Usage: {} {} [--costs] [--assets] [--profile profile-dir] [vm-state.db] [contract-identifier] [public-function-name] [sender-address] [args...]", invoked_by, argv[0]);
                 panic_test!();
             }
+            if let Some(profile_folder) = profile_folder.as_ref() {
+                friendly_expect(
+                    CostProfiler::check_output_dir(Path::new(profile_folder)),
+                    &format!("Cannot write a profile to {profile_folder}"),
+                );
+            }
 
             let vm_filename = &argv[1];
             let header_db =
@@ -1776,12 +1914,21 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
             } else {
                 None
             };
+            let mut profiler = profile_folder.as_ref().map(|_| CostProfiler::new());
+            let mut eval_hooks: Vec<&mut dyn EvalHook> = vec![];
+            if let Some(eval_hook) = eval_hook {
+                eval_hooks.push(eval_hook);
+            }
+            if let Some(profiler) = profiler.as_mut() {
+                eval_hooks.push(profiler);
+            }
             let (_, _, result_and_cost) = in_block(header_db, marf_kv, |header_db, mut marf| {
                 let result_and_cost = with_env_costs(
                     mainnet,
                     &header_db,
                     &mut marf,
                     coverage.as_mut(),
+                    eval_hooks,
                     |vm_env| {
                         vm_env.execute_transaction(
                             sender,
@@ -1795,6 +1942,7 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
                 let (result, cost) = result_and_cost;
                 (header_db, marf, (result, cost))
             });
+            save_profile(profile_folder, profiler);
 
             match result_and_cost {
                 (Ok((x, asset_map, events)), cost) => {
@@ -1856,6 +2004,271 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
                 }
             }
         }
+        "debug" => {
+            let mut argv = args.to_vec();
+            let mut debugger = Debugger::new(io::stdin().lock(), io::stderr());
+            let mut has_breakpoints = false;
+            while let Ok(Some(breakpoint)) = consume_arg(&mut argv, &["--break"], true) {
+                if !BREAKPOINTS_SUPPORTED {
+                    eprintln!("{BREAKPOINTS_UNSUPPORTED}");
+                    panic_test!();
+                }
+                let breakpoint = friendly_expect(
+                    Breakpoint::parse(&breakpoint),
+                    &format!("Invalid breakpoint: {breakpoint}"),
+                );
+                debugger.add_breakpoint(breakpoint);
+                has_breakpoints = true;
+            }
+
+            if argv.len() < 2 || !matches!(argv[1].as_str(), "eval" | "execute") {
+                eprintln!(
+                    "Usage: {} {} [--break [contract-identifier:]line ...] (eval|execute) [args...]",
+                    invoked_by, argv[0]
+                );
+                panic_test!();
+            }
+
+            // the debugger reads its commands from stdin, so the program can't come from there
+            if argv[1] == "eval" && argv[2..].iter().filter(|arg| *arg != "--costs").count() < 3 {
+                eprintln!(
+                    "Usage: {} {} eval [--costs] [contract-identifier] (program.clar) [vm-state.db]",
+                    invoked_by, argv[0]
+                );
+                eprintln!("   The program must be given as a file, since debugger commands are read from stdin.");
+                panic_test!();
+            }
+
+            // without breakpoints, stop at the first expression
+            if has_breakpoints {
+                debugger.run_to_breakpoint();
+            }
+            invoke_command_with_hook(invoked_by, &argv[1..], Some(&mut debugger))
+        }
+        "test" => {
+            let mut argv = args.to_vec();
+            let lcov_output_file = consume_arg(&mut argv, &["--lcov"], true).unwrap_or(None);
+            let mainnet = !matches!(consume_arg(&mut argv, &["--testnet"], false), Ok(Some(_)));
+
+            if argv.len() < 2 {
+                eprintln!(
+                    "Usage: {} {} [--testnet] [--lcov lcov-output-file] [contracts-dir]",
+                    invoked_by, argv[0]
+                );
+                eprintln!("   Each contract-name.clar file in contracts-dir is deployed as {TEST_DEPLOYER}.contract-name.");
+                eprintln!("   Each public function whose name starts with `test-` is then called by {TEST_DEPLOYER}, in its own transaction, which is rolled back afterwards.");
+                eprintln!("   A test passes if it returns an `ok` response.");
+                panic_test!();
+            }
+
+            let contracts_dir = &argv[1];
+            let mut contract_src_files: Vec<_> = friendly_expect(
+                fs::read_dir(contracts_dir),
+                &format!("Failed to read the contracts folder: {}", contracts_dir),
+            )
+            .map(|folder_entry| {
+                friendly_expect(folder_entry, "Failed to read entry in the contracts folder").path()
+            })
+            .filter(|entry_path| {
+                entry_path.is_file() && entry_path.extension() == Some(OsStr::new("clar"))
+            })
+            .collect();
+            contract_src_files.sort();
+
+            let deployer = friendly_expect(
+                PrincipalData::parse_standard_principal(TEST_DEPLOYER),
+                "Failed to parse the test deployer address.",
+            );
+            let mut pending = vec![];
+            for contract_src_file in contract_src_files.iter() {
+                let contract_name = friendly_expect_opt(
+                    contract_src_file.file_stem().and_then(OsStr::to_str),
+                    &format!(
+                        "Invalid contract file name: {}",
+                        contract_src_file.display()
+                    ),
+                );
+                let contract_identifier = QualifiedContractIdentifier::new(
+                    deployer.clone(),
+                    friendly_expect(
+                        ContractName::try_from(contract_name.to_string()),
+                        &format!("Invalid contract name: {}", contract_name),
+                    ),
+                );
+                let contract_content = friendly_expect(
+                    fs::read_to_string(contract_src_file),
+                    &format!("Error reading file: {}", contract_src_file.display()),
+                );
+                pending.push((
+                    contract_identifier,
+                    contract_src_file.display().to_string(),
+                    contract_content,
+                ));
+            }
+
+            let coverage_folder = lcov_output_file.as_ref().map(|_| {
+                let mut coverage_folder = std::env::temp_dir();
+                coverage_folder.push(format!("clarity_test_{}", rand::thread_rng().gen::<u32>()));
+                friendly_expect(
+                    fs::create_dir_all(&coverage_folder),
+                    "Failed to create the coverage folder",
+                );
+                coverage_folder
+            });
+            let mut coverage = coverage_folder.as_ref().map(|_| CoverageReporter::new());
+            let mut marf = MemoryBackingStore::new();
+
+            // contracts may depend on each other, so keep deploying whatever can be deployed
+            // until no further progress is made.
+            let mut deployed = vec![];
+            while !pending.is_empty() {
+                let pending_count = pending.len();
+                let mut deferred = vec![];
+                let mut errors = serde_json::Map::new();
+                for (contract_identifier, contract_src_file, contract_content) in pending {
+                    let deploy_result = deploy_test_contract(
+                        mainnet,
+                        &mut marf,
+                        &contract_identifier,
+                        &contract_src_file,
+                        &contract_content,
+                        coverage.as_mut().zip(coverage_folder.as_ref()),
+                    );
+                    match deploy_result {
+                        Ok(analysis) => deployed.push((contract_identifier, analysis)),
+                        Err(error) => {
+                            errors.insert(contract_identifier.to_string(), json!(error));
+                            deferred.push((
+                                contract_identifier,
+                                contract_src_file,
+                                contract_content,
+                            ));
+                        }
+                    }
+                }
+                if deferred.len() == pending_count {
+                    if let Some(coverage_folder) = coverage_folder {
+                        let _ = fs::remove_dir_all(coverage_folder);
+                    }
+                    return (
+                        1,
+                        Some(json!({
+                            "error": {
+                                "deployment": errors
+                            },
+                            "success": false,
+                        })),
+                    );
+                }
+                pending = deferred;
+            }
+
+            let mut tests = vec![];
+            let mut failed = 0;
+            for (contract_identifier, analysis) in deployed.iter() {
+                for test_name in analysis
+                    .public_function_types
+                    .keys()
+                    .filter(|name| name.starts_with("test-"))
+                {
+                    let mut print_collector = PrintCollector::default();
+                    let result = {
+                        let mut db = marf.as_clarity_db();
+                        db.begin();
+                        let mut vm_env = OwnedEnvironment::new_free(
+                            mainnet,
+                            default_chain_id(mainnet),
+                            db,
+                            DEFAULT_CLI_EPOCH,
+                        );
+                        if let Some(coverage) = coverage.as_mut() {
+                            vm_env.add_eval_hook(coverage);
+                        }
+                        vm_env.add_eval_hook(&mut print_collector);
+                        let result = vm_env.execute_transaction(
+                            PrincipalData::Standard(deployer.clone()),
+                            None,
+                            contract_identifier.clone(),
+                            test_name,
+                            &[],
+                        );
+                        let (mut db, _) = vm_env.destruct().expect(
+                            "Failed to recover database reference after executing transaction",
+                        );
+                        // every test starts from the freshly-deployed state
+                        db.roll_back()
+                            .expect("Failed to roll back the test transaction");
+                        result
+                    };
+
+                    let prints: Vec<_> = print_collector
+                        .prints
+                        .iter()
+                        .map(|value| value.to_string())
+                        .collect();
+                    let mut test_result = json!({
+                        "contract": contract_identifier.to_string(),
+                        "test": test_name.to_string(),
+                        "prints": prints,
+                    });
+                    let success = match result {
+                        Ok((value, ..)) => {
+                            let success = matches!(&value, Value::Response(data) if data.committed);
+                            test_result["result"] = json!(value.to_string());
+                            success
+                        }
+                        Err(error) => {
+                            test_result["error"] = json!(error.to_string());
+                            false
+                        }
+                    };
+                    test_result["success"] = json!(success);
+                    if !success {
+                        failed += 1;
+                    }
+                    tests.push(test_result);
+                }
+            }
+
+            if let (Some(lcov_output_file), Some(coverage_folder), Some(coverage)) =
+                (lcov_output_file, coverage_folder, coverage)
+            {
+                let mut coverage_file = coverage_folder.clone();
+                coverage_file.push("test");
+                coverage_file.set_extension("clarcov");
+                coverage
+                    .to_file(&coverage_file)
+                    .expect("Coverage reference file generation failure");
+
+                let register_files: Vec<_> = deployed
+                    .iter()
+                    .map(|(contract_identifier, _)| {
+                        let mut register_file = coverage_folder.clone();
+                        register_file.push(contract_identifier.name.as_str());
+                        register_file.set_extension("clarcovref");
+                        register_file
+                    })
+                    .collect();
+                CoverageReporter::produce_lcov(
+                    &lcov_output_file,
+                    &register_files,
+                    &[coverage_file],
+                )
+                .expect("Failed to produce an lcov output");
+                let _ = fs::remove_dir_all(coverage_folder);
+            }
+
+            let passed = tests.len() - failed;
+            (
+                if failed == 0 { 0 } else { 1 },
+                Some(json!({
+                    "tests": tests,
+                    "passed": passed,
+                    "failed": failed,
+                    "success": failed == 0,
+                })),
+            )
+        }
         "make_lcov" => {
             let mut register_files = vec![];
             let mut coverage_files = vec![];
@@ -2358,4 +2771,155 @@ mod test {
                 })
         );
     }
+
+    #[test]
+    fn test_profile() {
+        let db_name = format!("/tmp/db_{}", rand::thread_rng().gen::<i32>());
+        let profile_dir = format!("/tmp/profile_{}", rand::thread_rng().gen::<i32>());
+        let contract_id = "S1G2081040G2081040G2081040G208105NK8PE5.tokens";
+
+        invoke_command("test", &["initialize".to_string(), db_name.clone()]);
+        let invoked = invoke_command(
+            "test",
+            &[
+                "launch".to_string(),
+                contract_id.to_string(),
+                cargo_workspace_as_string("sample/contracts/tokens.clar"),
+                db_name.clone(),
+            ],
+        );
+        assert_eq!(invoked.0, 0);
+
+        eprintln!("execute tokens with profile");
+        let invoked = invoke_command(
+            "test",
+            &[
+                "execute".to_string(),
+                "--profile".to_string(),
+                profile_dir.clone(),
+                db_name,
+                contract_id.to_string(),
+                "mint!".to_string(),
+                "SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR".to_string(),
+                "u1000".to_string(),
+            ],
+        );
+        assert_eq!(invoked.0, 0);
+
+        let table = fs::read_to_string(format!("{profile_dir}/functions.csv")).unwrap();
+        let rows: Vec<Vec<&str>> = table
+            .lines()
+            .map(|line| line.split(',').collect())
+            .collect();
+        assert_eq!(
+            rows[0][..4],
+            ["function", "calls", "total_runtime", "self_runtime"]
+        );
+        let row = |function: &str| {
+            rows.iter()
+                .find(|row| row[0] == format!("{contract_id}:{function}"))
+                .unwrap()
+                .clone()
+        };
+        // mint! calls everything else, so it is the most expensive
+        assert_eq!(rows[1][0], format!("{contract_id}:mint!"));
+        assert_eq!(row("mint!")[1], "1");
+        assert_eq!(row("get-balance")[1], "2");
+        assert_eq!(row("token-credit!")[1], "1");
+        assert!(row("mint!")[2].parse::<u64>().unwrap() > 0);
+        // total_write_count
+        assert_eq!(row("token-credit!")[8], "1");
+
+        let runtime = fs::read_to_string(format!("{profile_dir}/runtime.folded")).unwrap();
+        assert!(runtime
+            .lines()
+            .all(|line| line.starts_with(&format!("{contract_id}:mint!"))));
+        assert!(runtime.contains(&format!(
+            "{contract_id}:mint!;_native_:special_let;{contract_id}:token-credit!;"
+        )));
+        fs::remove_dir_all(profile_dir).unwrap();
+    }
+
+    #[test]
+    fn test_test_runner() {
+        let contracts_dir = format!("/tmp/contracts_{}", rand::thread_rng().gen::<i32>());
+        let lcov_file = format!("/tmp/lcov_{}.info", rand::thread_rng().gen::<i32>());
+        fs::create_dir_all(&contracts_dir).unwrap();
+        // sorts before its dependency, so it can only be deployed after a retry
+        fs::write(
+            format!("{contracts_dir}/a-counter-test.clar"),
+            r#"
+(define-public (test-increment)
+  (begin
+    (print "incrementing")
+    (try! (contract-call? .counter increment))
+    (asserts! (is-eq (contract-call? .counter get-count) u1) (err u1))
+    (ok true)))
+
+(define-public (test-increment-twice)
+  (begin
+    (try! (contract-call? .counter increment))
+    (try! (contract-call? .counter increment))
+    (print (contract-call? .counter get-count))
+    (asserts! (is-eq (contract-call? .counter get-count) u3) (err u2))
+    (ok true)))
+
+(define-read-only (get-nothing) none)
+"#,
+        )
+        .unwrap();
+        fs::write(
+            format!("{contracts_dir}/counter.clar"),
+            r#"
+(define-data-var count uint u0)
+(define-read-only (get-count) (var-get count))
+(define-public (increment)
+  (begin
+    (asserts! (< (var-get count) u10) (err u100))
+    (ok (var-set count (+ (var-get count) u1)))))
+"#,
+        )
+        .unwrap();
+
+        let invoked = invoke_command(
+            "test",
+            &[
+                "test".to_string(),
+                "--lcov".to_string(),
+                lcov_file.clone(),
+                contracts_dir.clone(),
+            ],
+        );
+        let result = invoked.1.unwrap();
+        eprintln!("{}", serde_json::to_string(&result).unwrap());
+
+        // each test starts from the deployed state, so the second test sees a count of 2
+        assert_eq!(invoked.0, 1);
+        assert_eq!(result["passed"], 1);
+        assert_eq!(result["failed"], 1);
+        let tests = result["tests"].as_array().unwrap();
+        assert_eq!(tests.len(), 2);
+
+        assert_eq!(
+            tests[0]["contract"],
+            "S1G2081040G2081040G2081040G208105NK8PE5.a-counter-test"
+        );
+        assert_eq!(tests[0]["test"], "test-increment");
+        assert_eq!(tests[0]["success"], true);
+        assert_eq!(tests[0]["result"], "(ok true)");
+        assert_eq!(tests[0]["prints"], json!(["\"incrementing\""]));
+
+        assert_eq!(tests[1]["test"], "test-increment-twice");
+        assert_eq!(tests[1]["success"], false);
+        assert_eq!(tests[1]["result"], "(err u2)");
+        assert_eq!(tests[1]["prints"], json!(["u2"]));
+
+        let lcov = fs::read_to_string(&lcov_file).unwrap();
+        assert!(lcov.contains("TN:S1G2081040G2081040G2081040G208105NK8PE5.counter"));
+        assert!(lcov.contains(&format!("SF:{contracts_dir}/counter.clar")));
+        assert_eq!(lcov.matches("end_of_record").count(), 2);
+
+        fs::remove_dir_all(contracts_dir).unwrap();
+        fs::remove_file(lcov_file).unwrap();
+    }
 }
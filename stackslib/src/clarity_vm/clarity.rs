@@ -30,6 +30,7 @@ use clarity::vm::database::{
 };
 use clarity::vm::errors::Error as InterpreterError;
 use clarity::vm::events::{STXEventType, STXMintEventData};
+use clarity::vm::profiler::{thread_profiler_active, ThreadProfilerHook};
 use clarity::vm::representations::SymbolicExpression;
 use clarity::vm::types::{PrincipalData, QualifiedContractIdentifier, Value};
 use clarity::vm::{ClarityVersion, ContractName};
@@ -1853,6 +1854,7 @@ impl TransactionConnection for ClarityTransactionConnection<'_, '_> {
                 // wrap the whole contract-call in a claritydb transaction,
                 //   so we can abort on call_back's boolean retun
                 db.begin();
+                let mut profiler_hook = ThreadProfilerHook;
                 let mut vm_env = OwnedEnvironment::new_cost_limited(
                     self.mainnet,
                     self.chain_id,
@@ -1860,6 +1862,10 @@ impl TransactionConnection for ClarityTransactionConnection<'_, '_> {
                     cost_track,
                     self.epoch,
                 );
+                // only tools like `replay-block` profile, so this is never set in the node
+                if thread_profiler_active() {
+                    vm_env.add_eval_hook(&mut profiler_hook);
+                }
                 let result = to_do(&mut vm_env);
                 let (mut db, cost_track) = vm_env
                     .destruct()
@@ -16,27 +16,30 @@
 
 //! Subcommands used by `stacks-inspect` binary
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
-use std::{fs, process};
+use std::{fs, io, process};
 
 use clarity::types::chainstate::SortitionId;
+use clarity::vm::profiler::{start_thread_profiler, stop_thread_profiler, CostProfiler};
 use db::blocks::DummyEventDispatcher;
 use db::ChainstateTx;
 use regex::Regex;
-use rusqlite::{Connection, OpenFlags};
+use rusqlite::{params, Connection, OpenFlags};
 use stacks_common::types::chainstate::{BlockHeaderHash, StacksBlockId};
 use stacks_common::types::sqlite::NO_PARAMS;
 use stacks_common::util::hash::Hash160;
 use stacks_common::util::vrf::VRFProof;
 
-use crate::burnchains::db::BurnchainDB;
+use crate::burnchains::db::{BurnchainBlockData, BurnchainDB};
 use crate::burnchains::Burnchain;
 use crate::chainstate::burn::db::sortdb::{
     get_ancestor_sort_id, SortitionDB, SortitionHandleContext,
 };
 use crate::chainstate::burn::ConsensusHash;
-use crate::chainstate::coordinator::OnChainRewardSetProvider;
+use crate::chainstate::coordinator::{
+    calculate_paid_rewards, BlockEventDispatcher, OnChainRewardSetProvider,
+};
 use crate::chainstate::nakamoto::miner::{BlockMetadata, NakamotoBlockBuilder, NakamotoTenureInfo};
 use crate::chainstate::nakamoto::{NakamotoBlock, NakamotoChainState};
 use crate::chainstate::stacks::db::{StacksBlockHeaderTypes, StacksChainState, StacksHeaderInfo};
@@ -45,10 +48,11 @@ use crate::chainstate::stacks::{Error as ChainstateError, *};
 use crate::clarity_vm::clarity::ClarityInstance;
 use crate::clarity_vm::database::GetTenureStartId;
 use crate::config::{Config, ConfigFile, DEFAULT_MAINNET_CONFIG};
+use crate::core::mempool::{MemPoolSnapshot, MEMPOOL_SNAPSHOT_VERSION};
 use crate::core::*;
 use crate::cost_estimates::metrics::UnitMetric;
 use crate::cost_estimates::UnitEstimator;
-use crate::util_lib::db::IndexDBTx;
+use crate::util_lib::db::{query_row_columns, IndexDBTx};
 
 /// Options common to many `stacks-inspect` subcommands
 /// Returned by `process_common_opts()`
@@ -115,16 +119,48 @@ pub fn drain_common_opts(argv: &mut Vec<String>, start_at: usize) -> CommonOpts
     opts
 }
 
+/// Drain `--profile <profile-dir>` from `argv`.
+/// If it was given, then start profiling the Clarity code evaluated on this thread.
+fn start_profile_from_args(argv: &mut Vec<String>) -> Option<String> {
+    let i = argv.iter().position(|arg| arg == "--profile")?;
+    if i + 1 >= argv.len() {
+        eprintln!("Expected a directory after `--profile`");
+        process::exit(1);
+    }
+    let profile_dir = argv.remove(i + 1);
+    argv.remove(i);
+    if let Err(e) = CostProfiler::check_output_dir(Path::new(&profile_dir)) {
+        eprintln!("Cannot write a profile to {profile_dir}: {e}");
+        process::exit(1);
+    }
+    start_thread_profiler();
+    Some(profile_dir)
+}
+
+/// Stop profiling, and write the profile to `profile_dir` (if given)
+fn finish_profile(profile_dir: Option<String>) {
+    let (Some(profile_dir), Some(profiler)) = (profile_dir, stop_thread_profiler()) else {
+        return;
+    };
+    if let Err(e) = profiler.write_to_dir(Path::new(&profile_dir)) {
+        eprintln!("Failed to write profile to {profile_dir}: {e}");
+        process::exit(1);
+    }
+    println!("Wrote cost profile to {profile_dir}");
+}
+
 /// Replay blocks from chainstate database
 /// Terminates on error using `process::exit()`
 ///
 /// Arguments:
 ///  - `argv`: Args in CLI format: `<command-name> [args...]`
 pub fn command_replay_block(argv: &[String], conf: Option<&Config>) {
+    let mut argv = argv.to_vec();
+    let profile_dir = start_profile_from_args(&mut argv);
     let print_help_and_exit = || -> ! {
         let n = &argv[0];
         eprintln!("Usage:");
-        eprintln!("  {n} <database-path>");
+        eprintln!("  {n} [--profile <profile-dir>] <database-path>");
         eprintln!("  {n} <database-path> prefix <index-block-hash-prefix>");
         eprintln!("  {n} <database-path> index-range <start-block> <end-block>");
         eprintln!("  {n} <database-path> range <start-block> <end-block>");
@@ -190,6 +226,7 @@ pub fn command_replay_block(argv: &[String], conf: Option<&Config>) {
         }
         replay_staging_block(db_path, index_block_hash, conf);
     }
+    finish_profile(profile_dir);
     println!("Finished. run_time_seconds = {}", start.elapsed().as_secs());
 }
 
@@ -199,10 +236,12 @@ pub fn command_replay_block(argv: &[String], conf: Option<&Config>) {
 /// Arguments:
 ///  - `argv`: Args in CLI format: `<command-name> [args...]`
 pub fn command_replay_block_nakamoto(argv: &[String], conf: Option<&Config>) {
+    let mut argv = argv.to_vec();
+    let profile_dir = start_profile_from_args(&mut argv);
     let print_help_and_exit = || -> ! {
         let n = &argv[0];
         eprintln!("Usage:");
-        eprintln!("  {n} <database-path>");
+        eprintln!("  {n} [--profile <profile-dir>] <database-path>");
         eprintln!("  {n} <database-path> prefix <index-block-hash-prefix>");
         eprintln!("  {n} <database-path> index-range <start-block> <end-block>");
         eprintln!("  {n} <database-path> range <start-block> <end-block>");
@@ -278,6 +317,7 @@ pub fn command_replay_block_nakamoto(argv: &[String], conf: Option<&Config>) {
         }
         replay_naka_staging_block(db_path, index_block_hash, conf);
     }
+    finish_profile(profile_dir);
     println!("Finished. run_time_seconds = {}", start.elapsed().as_secs());
 }
 
@@ -548,6 +588,179 @@ pub fn command_try_mine(argv: &[String], conf: Option<&Config>) {
     process::exit(code);
 }
 
+/// Export the contents of a node's mempool to a portable JSON snapshot file
+/// Terminates on error using `process::exit()`
+///
+/// Arguments:
+///  - `argv`: Args in CLI format: `<command-name> [args...]`
+///  - `conf`: Optional config for running on non-mainnet chainstate
+pub fn command_mempool_export(argv: &[String], conf: Option<&Config>) {
+    let print_help_and_exit = || -> ! {
+        let n = &argv[0];
+        eprintln!("Usage: {n} <working-dir> <snapshot-file>");
+        eprintln!();
+        eprintln!("Write every transaction in the mempool of <working-dir>, along with its accept time and");
+        eprintln!("fee rate estimate, to <snapshot-file>. Use `mempool-import` to load it into another node.");
+        process::exit(1);
+    };
+    let db_path = argv.get(1).unwrap_or_else(|| print_help_and_exit());
+    let snapshot_path = argv.get(2).unwrap_or_else(|| print_help_and_exit());
+
+    let conf = conf.unwrap_or(&DEFAULT_MAINNET_CONFIG);
+    match mempool_export(db_path, snapshot_path, conf) {
+        Ok(num_txs) => println!("Exported {num_txs} mempool transactions to {snapshot_path}"),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Write the mempool of the node in `db_path` to `snapshot_path`.
+/// Returns the number of transactions written.
+fn mempool_export(db_path: &str, snapshot_path: &str, conf: &Config) -> Result<usize, String> {
+    let chain_state_path = format!("{db_path}/chainstate/");
+
+    let mempool_db = MemPoolDB::open(
+        conf.is_mainnet(),
+        conf.burnchain.chain_id,
+        &chain_state_path,
+        Box::new(UnitEstimator),
+        Box::new(UnitMetric),
+    )
+    .map_err(|e| format!("Failed to open mempool db: {e}"))?;
+
+    let txs = MemPoolDB::export_snapshot_txs(mempool_db.conn())
+        .map_err(|e| format!("Failed to read mempool transactions: {e}"))?;
+    let snapshot = MemPoolSnapshot::new(conf.is_mainnet(), conf.burnchain.chain_id, txs);
+
+    let file = fs::File::create(snapshot_path)
+        .map_err(|e| format!("Failed to create {snapshot_path}: {e}"))?;
+    let mut writer = io::BufWriter::new(file);
+    serde_json::to_writer(&mut writer, &snapshot)
+        .map_err(|e| format!("Failed to write {snapshot_path}: {e}"))?;
+    io::Write::flush(&mut writer).map_err(|e| format!("Failed to write {snapshot_path}: {e}"))?;
+
+    Ok(snapshot.txs.len())
+}
+
+/// Import a mempool snapshot produced by `command_mempool_export()` into a node's mempool.
+/// Each transaction is re-validated against the node's canonical chain tip, and is skipped if
+/// it is no longer admissible.
+/// Terminates on error using `process::exit()`
+///
+/// Arguments:
+///  - `argv`: Args in CLI format: `<command-name> [args...]`
+///  - `conf`: Optional config for running on non-mainnet chainstate
+pub fn command_mempool_import(argv: &[String], conf: Option<&Config>) {
+    let print_help_and_exit = || -> ! {
+        let n = &argv[0];
+        eprintln!("Usage: {n} <working-dir> <snapshot-file>");
+        eprintln!();
+        eprintln!("Load the transactions in <snapshot-file> into the mempool of <working-dir>. Each transaction");
+        eprintln!("must pass the mempool's admission checks at the current chain tip.");
+        process::exit(1);
+    };
+    let db_path = argv.get(1).unwrap_or_else(|| print_help_and_exit());
+    let snapshot_path = argv.get(2).unwrap_or_else(|| print_help_and_exit());
+
+    let conf = conf.unwrap_or(&DEFAULT_MAINNET_CONFIG);
+    if let Err(e) = mempool_import(db_path, snapshot_path, conf) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+/// Load the snapshot in `snapshot_path` into the mempool of the node in `db_path`, printing
+/// each rejected transaction and a summary.
+fn mempool_import(db_path: &str, snapshot_path: &str, conf: &Config) -> Result<(), String> {
+    let file =
+        fs::File::open(snapshot_path).map_err(|e| format!("Failed to open {snapshot_path}: {e}"))?;
+    let snapshot: MemPoolSnapshot = serde_json::from_reader(io::BufReader::new(file))
+        .map_err(|e| format!("Failed to decode {snapshot_path}: {e}"))?;
+    if snapshot.version != MEMPOOL_SNAPSHOT_VERSION {
+        return Err(format!(
+            "Unsupported mempool snapshot version {} (expected {MEMPOOL_SNAPSHOT_VERSION})",
+            snapshot.version
+        ));
+    }
+    if snapshot.mainnet != conf.is_mainnet() || snapshot.chain_id != conf.burnchain.chain_id {
+        return Err(format!(
+            "Mempool snapshot is for chain ID {:08x} (mainnet: {}), but the node is on chain ID {:08x} (mainnet: {})",
+            snapshot.chain_id,
+            snapshot.mainnet,
+            conf.burnchain.chain_id,
+            conf.is_mainnet()
+        ));
+    }
+
+    let sort_db_path = format!("{db_path}/burnchain/sortition");
+    let chain_state_path = format!("{db_path}/chainstate/");
+
+    let burnchain = conf.get_burnchain();
+    let sort_db = SortitionDB::open(&sort_db_path, false, burnchain.pox_constants.clone())
+        .map_err(|e| format!("Failed to open {sort_db_path}: {e}"))?;
+    let (mut chainstate, _) = StacksChainState::open(
+        conf.is_mainnet(),
+        conf.burnchain.chain_id,
+        &chain_state_path,
+        None,
+    )
+    .map_err(|e| format!("Failed to open stacks chain state: {e}"))?;
+    let mut mempool_db = MemPoolDB::open(
+        conf.is_mainnet(),
+        conf.burnchain.chain_id,
+        &chain_state_path,
+        Box::new(UnitEstimator),
+        Box::new(UnitMetric),
+    )
+    .map_err(|e| format!("Failed to open mempool db: {e}"))?;
+    mempool_db.replace_policy = conf.node.mempool_replace_policy();
+
+    let burn_tip = SortitionDB::get_canonical_burn_chain_tip(sort_db.conn())
+        .map_err(|e| format!("Failed to get sortition chain tip: {e}"))?;
+    let stacks_epoch = SortitionDB::get_stacks_epoch(sort_db.conn(), burn_tip.block_height)
+        .map_err(|e| format!("Failed to load Stacks epoch: {e}"))?
+        .ok_or("No Stacks epoch for the canonical burn height")?;
+    let stacks_tip = NakamotoChainState::get_canonical_block_header(chainstate.db(), &sort_db)
+        .map_err(|e| format!("Error looking up chain tip: {e}"))?
+        .ok_or("No chain tip found")?;
+    let stacks_tip_block_hash = stacks_tip.anchored_header.block_hash();
+
+    let mut imported = 0;
+    let mut already_present = 0;
+    let mut rejected = 0;
+    for snapshot_tx in snapshot.txs.iter() {
+        match mempool_db.import_snapshot_tx(
+            &mut chainstate,
+            &sort_db,
+            &stacks_tip.consensus_hash,
+            &stacks_tip_block_hash,
+            snapshot_tx,
+            &stacks_epoch.block_limit,
+            &stacks_epoch.epoch_id,
+        ) {
+            Ok(true) => imported += 1,
+            Ok(false) => already_present += 1,
+            Err(e) => {
+                println!(
+                    "Rejected {}: {}",
+                    &snapshot_tx.txid,
+                    e.into_json(&snapshot_tx.txid)
+                );
+                rejected += 1;
+            }
+        }
+    }
+
+    println!(
+        "Imported {imported} of {} transactions from {snapshot_path} at {}/{stacks_tip_block_hash} ({already_present} already present, {rejected} rejected)",
+        snapshot.txs.len(),
+        &stacks_tip.consensus_hash
+    );
+    Ok(())
+}
+
 /// Fetch and process a `StagingBlock` from database and call `replay_block()` to validate
 fn replay_staging_block(db_path: &str, index_block_hash_hex: &str, conf: Option<&Config>) {
     let block_id = StacksBlockId::from_hex(index_block_hash_hex).unwrap();
@@ -876,14 +1089,162 @@ fn replay_naka_staging_block(db_path: &str, index_block_hash_hex: &str, conf: &C
         .get_nakamoto_block(&block_id)
         .unwrap()
         .unwrap();
-    replay_block_nakamoto(&mut sortdb, &mut chainstate, &block, block_size).unwrap();
+    if let Err(e) = replay_block_nakamoto::<DummyEventDispatcher>(
+        &mut sortdb,
+        &mut chainstate,
+        &block,
+        block_size,
+        None,
+    ) {
+        println!("Failed processing block! block = {block_id}, error = {e:?}");
+        process::exit(1);
+    }
 }
 
-fn replay_block_nakamoto(
+/// Re-announce the burn blocks in the burnchain height range `[start_height, end_height]` (on the
+/// canonical sortition history), and the processed Nakamoto blocks of the tenures they started, to
+/// `dispatcher`.
+///
+/// Burn block payloads are rebuilt from the burnchain DB, and each Stacks block is re-evaluated in
+/// a transaction that is rolled back afterwards to re-derive its receipts, so the dispatcher sees
+/// the same calls the chains coordinator made when these blocks were first processed.
+/// The chainstate itself is not modified.
+///
+/// Only Nakamoto blocks can be replayed, so a range starting before epoch 3.0 is rejected.
+/// Fails if a block does not evaluate to the cost it had when it was first processed.
+pub fn replay_events_nakamoto<T: BlockEventDispatcher>(
+    conf: &Config,
+    start_height: u64,
+    end_height: u64,
+    dispatcher: &T,
+) -> Result<(), ChainstateError> {
+    let burnchain = conf.get_burnchain();
+    let epochs = conf.burnchain.get_epoch_list();
+    let (mut chainstate, _) = StacksChainState::open(
+        conf.is_mainnet(),
+        conf.burnchain.chain_id,
+        &conf.get_chainstate_path_str(),
+        Some(conf.node.get_marf_opts()),
+    )?;
+    let mut sortdb = SortitionDB::connect(
+        &conf.get_burn_db_file_path(),
+        burnchain.first_block_height,
+        &burnchain.first_block_hash,
+        u64::from(burnchain.first_block_timestamp),
+        &epochs,
+        burnchain.pox_constants.clone(),
+        None,
+        true,
+    )?;
+    let burnchain_db = BurnchainDB::open(&burnchain.get_burnchaindb_path(), false)
+        .map_err(|e| ChainstateError::InvalidStacksBlock(format!("{e:?}")))?;
+
+    replay_events_nakamoto_from_dbs(
+        &mut sortdb,
+        &mut chainstate,
+        &burnchain_db,
+        start_height,
+        end_height,
+        dispatcher,
+    )
+}
+
+/// Implementation of `replay_events_nakamoto()` over already-opened databases
+fn replay_events_nakamoto_from_dbs<T: BlockEventDispatcher>(
+    sortdb: &mut SortitionDB,
+    chainstate: &mut StacksChainState,
+    burnchain_db: &BurnchainDB,
+    start_height: u64,
+    end_height: u64,
+    dispatcher: &T,
+) -> Result<(), ChainstateError> {
+    let Some(start_epoch) = SortitionDB::get_stacks_epoch(sortdb.conn(), start_height)? else {
+        return Err(ChainstateError::InvalidStacksBlock(format!(
+            "No epoch defined at burn height {start_height}"
+        )));
+    };
+    if start_epoch.epoch_id < StacksEpochId::Epoch30 {
+        return Err(ChainstateError::InvalidStacksBlock(format!(
+            "Burn height {start_height} is in {}; only heights from epoch 3.0 onwards can be replayed",
+            start_epoch.epoch_id
+        )));
+    }
+
+    let sort_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())?;
+    for burn_height in start_height..=end_height.min(sort_tip.block_height) {
+        let snapshot = if burn_height == sort_tip.block_height {
+            sort_tip.clone()
+        } else {
+            let Some(snapshot) = sortdb
+                .index_handle(&sort_tip.sortition_id)
+                .get_block_snapshot_by_height(burn_height)?
+            else {
+                return Err(ChainstateError::InvalidStacksBlock(format!(
+                    "No sortition at burn height {burn_height}"
+                )));
+            };
+            snapshot
+        };
+
+        let BurnchainBlockData { header, ops } =
+            BurnchainDB::get_burnchain_block(burnchain_db.conn(), &snapshot.burn_header_hash)
+                .map_err(|e| ChainstateError::InvalidStacksBlock(format!("{e:?}")))?;
+        let paid_rewards = calculate_paid_rewards(&ops);
+        let (recipients, _) = sortdb
+            .index_conn()
+            .get_reward_set_payouts_at(&snapshot.sortition_id)?;
+        dispatcher.announce_burn_block(
+            &header.block_hash,
+            header.block_height,
+            paid_rewards.pox,
+            paid_rewards.burns,
+            recipients,
+            &snapshot.consensus_hash,
+            &header.parent_block_hash,
+        );
+
+        let block_ids: Vec<StacksBlockId> = query_row_columns(
+            &chainstate.nakamoto_blocks_db(),
+            "SELECT index_block_hash FROM nakamoto_staging_blocks WHERE consensus_hash = ?1 AND processed = 1 AND orphaned = 0 ORDER BY height ASC",
+            params![snapshot.consensus_hash],
+            "index_block_hash",
+        )?;
+        for block_id in block_ids {
+            let Some((block, block_size)) = chainstate
+                .nakamoto_blocks_db()
+                .get_nakamoto_block(&block_id)?
+            else {
+                return Err(ChainstateError::InvalidStacksBlock(format!(
+                    "Processed block {block_id} is missing from the staging DB"
+                )));
+            };
+            replay_block_nakamoto(sortdb, chainstate, &block, block_size, Some(dispatcher))?;
+        }
+    }
+    Ok(())
+}
+
+/// `replay-block` only validates blocks, so it skips a block that cannot be replayed. A replay to
+/// a dispatcher fails instead, so that its observer does not silently miss the block.
+fn skip_unreplayable_block(has_dispatcher: bool, reason: &str) -> Result<(), ChainstateError> {
+    if has_dispatcher {
+        return Err(ChainstateError::InvalidStacksBlock(format!(
+            "Cannot replay block: {reason}"
+        )));
+    }
+    Ok(())
+}
+
+/// Re-evaluate an already-processed Nakamoto block in a transaction that is rolled back
+/// afterwards, checking that it costs the same as when it was first processed.
+/// If `dispatcher` is given, the block is announced to it with its re-derived receipts, and a
+/// block which cannot be replayed is an error rather than skipped.
+fn replay_block_nakamoto<T: BlockEventDispatcher>(
     sort_db: &mut SortitionDB,
     stacks_chain_state: &mut StacksChainState,
     block: &NakamotoBlock,
     block_size: u64,
+    dispatcher: Option<&T>,
 ) -> Result<(), ChainstateError> {
     // find corresponding snapshot
     let next_ready_block_snapshot =
@@ -909,7 +1270,7 @@ fn replay_block_nakamoto(
     )
     .unwrap() else {
         println!("Failed to find cost for block {}", block.header.block_id());
-        return Ok(());
+        return skip_unreplayable_block(dispatcher.is_some(), "missing block cost");
     };
 
     let expected_cost = if block.get_tenure_tx_payload().is_some() {
@@ -924,7 +1285,7 @@ fn replay_block_nakamoto(
                 "Failed to find cost for parent of block {}",
                 block.header.block_id()
             );
-            return Ok(());
+            return skip_unreplayable_block(dispatcher.is_some(), "missing parent block cost");
         };
         expected_total_tenure_cost.sub(&expected_parent_total_tenure_cost).expect("FATAL: failed to subtract parent total cost from self total cost in non-tenure-changing block");
         expected_total_tenure_cost
@@ -960,6 +1321,7 @@ fn replay_block_nakamoto(
             );
             ChainstateError::NoSuchBlockError
         })?;
+    let chainstate_config = stacks_chain_state.config();
     let (mut chainstate_tx, clarity_instance) = stacks_chain_state.chainstate_tx_begin()?;
 
     // find parent header
@@ -973,7 +1335,7 @@ fn replay_block_nakamoto(
                "stacks_block_id" => %block.header.block_id(),
                "parent_block_id" => %block.header.parent_block_id
         );
-        return Ok(());
+        return skip_unreplayable_block(dispatcher.is_some(), "missing parent header");
     };
 
     // sanity check -- must attach to parent
@@ -1079,7 +1441,7 @@ fn replay_block_nakamoto(
             "stacks_block_id" => %block.header.block_id(),
             "burn_view_consensus_hash" => %burnchain_view,
         );
-        return Ok(());
+        return skip_unreplayable_block(dispatcher.is_some(), "missing burn view sortition");
     };
 
     // find commit and sortition burns if this is a tenure-start block
@@ -1139,16 +1501,58 @@ fn replay_block_nakamoto(
         &active_reward_set,
         true,
     ) {
-        Ok((receipt, _, _, _)) => (Some(receipt), None),
+        Ok((receipt, _, reward_set_data, phantom_unlock_events)) => (
+            Some((receipt, reward_set_data, phantom_unlock_events)),
+            None,
+        ),
         Err(e) => (None, Some(e)),
     };
 
-    if let Some(receipt) = ok_opt {
+    if let Some((mut receipt, reward_set_data, phantom_unlock_events)) = ok_opt {
         // check the cost
         let evaluated_cost = receipt.anchored_block_cost.clone();
         if evaluated_cost != expected_cost {
-            println!("Failed processing block! block = {block_id}. Unexpected cost. expected = {expected_cost}, evaluated = {evaluated_cost}");
-            process::exit(1);
+            return Err(ChainstateError::InvalidStacksBlock(format!(
+                "Unexpected cost for block {block_id}. expected = {expected_cost}, evaluated = {evaluated_cost}"
+            )));
+        }
+
+        if let Some(dispatcher) = dispatcher {
+            // the receipt only carries a placeholder header when the chain tip is not advanced,
+            // so announce the header that was stored when the block was first processed
+            receipt.header = NakamotoChainState::get_block_header(&chainstate_tx.tx, &block_id)?
+                .ok_or(ChainstateError::NoSuchBlockError)?;
+            if let Some(unlock_receipt) = NakamotoChainState::generate_phantom_unlock_tx(
+                phantom_unlock_events,
+                &chainstate_config,
+                block.header.chain_length,
+            ) {
+                receipt.tx_receipts.push(unlock_receipt);
+            }
+            let block_event = (
+                block.clone(),
+                parent_header_info.anchored_header.block_hash(),
+            )
+                .into();
+            dispatcher.announce_block(
+                &block_event,
+                &receipt.header,
+                &receipt.tx_receipts,
+                &parent_block_id,
+                next_ready_block_snapshot.winning_block_txid,
+                &receipt.matured_rewards,
+                receipt.matured_rewards_info.as_ref(),
+                receipt.parent_burn_block_hash,
+                receipt.parent_burn_block_height,
+                receipt.parent_burn_block_timestamp,
+                &receipt.anchored_block_cost,
+                &receipt.parent_microblocks_cost,
+                &pox_constants,
+                &reward_set_data,
+                &Some(block.header.pox_treatment.clone()),
+                Some(block.header.timestamp),
+                receipt.coinbase_height,
+            );
         }
     }
 
@@ -1179,6 +1583,9 @@ fn replay_block_nakamoto(
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use crate::chainstate::nakamoto::coordinator::tests::boot_nakamoto;
+    use crate::chainstate::nakamoto::tests::node::TestStacker;
+    use crate::net::test::TestEventObserver;
 
     fn parse_cli_command(s: &str) -> Vec<String> {
         s.split(' ').map(String::from).collect()
@@ -1207,4 +1614,111 @@ pub mod test {
         assert_eq!(argv, argv_expected);
         assert!(opts.config.is_some());
     }
+
+    #[test]
+    fn test_replay_events_nakamoto() {
+        let (mut test_signers, test_stackers) = TestStacker::common_signing_set();
+        let mut peer = boot_nakamoto(
+            function_name!(),
+            vec![],
+            &mut test_signers,
+            &test_stackers,
+            None,
+        );
+
+        let (burn_ops, mut tenure_change, miner_key) =
+            peer.begin_nakamoto_tenure(TenureChangeCause::BlockFound);
+        let (burn_height, _, consensus_hash) = peer.next_burnchain_block(burn_ops);
+        let vrf_proof = peer.make_nakamoto_vrf_proof(miner_key);
+
+        tenure_change.tenure_consensus_hash = consensus_hash.clone();
+        tenure_change.burn_view_consensus_hash = consensus_hash;
+        let tenure_change_tx = peer.miner.make_nakamoto_tenure_change(tenure_change);
+        let coinbase_tx = peer.miner.make_nakamoto_coinbase(None, vrf_proof);
+        let block_ids: Vec<_> = peer
+            .make_nakamoto_tenure(
+                tenure_change_tx,
+                coinbase_tx,
+                &mut test_signers,
+                |_miner, _chainstate, _sort_dbconn, _blocks| vec![],
+            )
+            .into_iter()
+            .map(|(block, ..)| block.block_id())
+            .collect();
+        assert!(!block_ids.is_empty());
+
+        let burnchain_db = peer.get_burnchain_db(false);
+        let mut sortdb = peer.sortdb.take().unwrap();
+        let chainstate = &mut peer.stacks_node.as_mut().unwrap().chainstate;
+
+        // the tenure's blocks are replayed, with their receipts
+        let observer = TestEventObserver::new();
+        replay_events_nakamoto_from_dbs(
+            &mut sortdb,
+            chainstate,
+            &burnchain_db,
+            burn_height,
+            burn_height,
+            &observer,
+        )
+        .unwrap();
+        let replayed = observer.get_blocks();
+        assert_eq!(
+            replayed
+                .iter()
+                .map(|block| block.metadata.index_block_hash())
+                .collect::<Vec<_>>(),
+            block_ids
+        );
+        assert!(!replayed[0].receipts.is_empty());
+
+        // epoch 2.x blocks cannot be replayed
+        let observer = TestEventObserver::new();
+        replay_events_nakamoto_from_dbs(
+            &mut sortdb,
+            chainstate,
+            &burnchain_db,
+            1,
+            burn_height,
+            &observer,
+        )
+        .unwrap_err();
+        assert!(observer.get_blocks().is_empty());
+
+        // a block which cannot be replayed fails a replay to a dispatcher, but `replay-block`
+        // skips it
+        chainstate
+            .db()
+            .execute(
+                "DELETE FROM nakamoto_block_headers WHERE index_block_hash = ?1",
+                params![block_ids[0]],
+            )
+            .unwrap();
+        let observer = TestEventObserver::new();
+        replay_events_nakamoto_from_dbs(
+            &mut sortdb,
+            chainstate,
+            &burnchain_db,
+            burn_height,
+            burn_height,
+            &observer,
+        )
+        .unwrap_err();
+        assert!(observer.get_blocks().is_empty());
+        let (block, block_size) = chainstate
+            .nakamoto_blocks_db()
+            .get_nakamoto_block(&block_ids[0])
+            .unwrap()
+            .unwrap();
+        replay_block_nakamoto::<DummyEventDispatcher>(
+            &mut sortdb,
+            chainstate,
+            &block,
+            block_size,
+            None,
+        )
+        .unwrap();
+
+        peer.sortdb = Some(sortdb);
+    }
 }
@@ -46,7 +46,9 @@ use crate::chainstate::stacks::index::storage::TrieHashCalculationMode;
 use crate::chainstate::stacks::miner::{BlockBuilderSettings, MinerStatus};
 use crate::chainstate::stacks::MAX_BLOCK_LEN;
 use crate::config::chain_data::MinerStats;
-use crate::core::mempool::{MemPoolWalkSettings, MemPoolWalkStrategy, MemPoolWalkTxTypes};
+use crate::core::mempool::{
+    MemPoolReplacePolicy, MemPoolWalkSettings, MemPoolWalkStrategy, MemPoolWalkTxTypes,
+};
 use crate::core::{
     MemPoolDB, StacksEpoch, StacksEpochExtension, StacksEpochId,
     BITCOIN_TESTNET_FIRST_BLOCK_HEIGHT, BITCOIN_TESTNET_STACKS_25_BURN_HEIGHT,
@@ -60,6 +62,10 @@ use crate::cost_estimates::metrics::{CostMetric, ProportionalDotProduct, UnitMet
 use crate::cost_estimates::{CostEstimator, FeeEstimator, PessimisticEstimator, UnitEstimator};
 use crate::net::atlas::AtlasConfig;
 use crate::net::connection::{ConnectionOptions, DEFAULT_BLOCK_PROPOSAL_MAX_AGE_SECS};
+use crate::net::http::apikey::LEGACY_AUTH_TOKEN_KEY_NAME;
+use crate::net::http::ratelimit::parse_cidr;
+use crate::net::http::tls::{make_client_config, make_server_config};
+use crate::net::http::{HttpApiKey, HttpApiKeyScope};
 use crate::net::{Neighbor, NeighborAddress, NeighborKey};
 use crate::types::chainstate::BurnchainHeaderHash;
 use crate::types::EpochList;
@@ -615,13 +621,15 @@ impl Config {
             .make_cost_metric()
             .unwrap_or_else(|| Box::new(UnitMetric));
 
-        MemPoolDB::open(
+        let mut mempool = MemPoolDB::open(
             self.is_mainnet(),
             self.burnchain.chain_id,
             &self.get_chainstate_path_str(),
             cost_estimator,
             metric,
-        )
+        )?;
+        mempool.replace_policy = self.node.mempool_replace_policy();
+        Ok(mempool)
     }
 
     /// Load up a Burnchain and apply config settings to it.
@@ -951,11 +959,34 @@ impl Config {
                         .map(|e| EventKeyType::from_string(e).unwrap())
                         .collect();
 
+                    let transport = match observer.transport.as_deref() {
+                        Some(raw) => EventObserverTransport::from_string(raw)
+                            .ok_or_else(|| format!("Invalid event observer transport: {raw}"))?,
+                        None => EventObserverTransport::default(),
+                    };
+
+                    if let Some(ca_cert_path) = observer.tls_ca_cert_path.as_deref() {
+                        if transport != EventObserverTransport::Https {
+                            return Err(format!(
+                                "Event observer {}: tls_ca_cert_path requires the \"https\" transport",
+                                observer.endpoint
+                            ));
+                        }
+                        make_client_config(Some(ca_cert_path)).map_err(|e| {
+                            format!(
+                                "Event observer {}: invalid tls_ca_cert_path: {e}",
+                                observer.endpoint
+                            )
+                        })?;
+                    }
+
                     observers.insert(EventObserverConfig {
                         endpoint: observer.endpoint,
                         events_keys,
                         timeout_ms: observer.timeout_ms.unwrap_or(1_000),
                         disable_retries: observer.disable_retries.unwrap_or(false),
+                        transport,
+                        tls_ca_cert_path: observer.tls_ca_cert_path,
                     });
                 }
                 observers
@@ -970,6 +1001,8 @@ impl Config {
                 events_keys: vec![EventKeyType::AnyEvent],
                 timeout_ms: 1_000,
                 disable_retries: false,
+                transport: EventObserverTransport::Http,
+                tls_ca_cert_path: None,
             });
         };
 
@@ -1125,6 +1158,21 @@ impl Config {
         !self.events_observers.is_empty()
     }
 
+    /// The API key that this node's miner sends with the StackerDB chunks it writes through its
+    /// own RPC interface, if `stackerdb_write_requires_api_key` is set
+    pub fn stackerdb_write_api_key(&self) -> Option<String> {
+        let conn_opts = &self.connection_options;
+        if !conn_opts.stackerdb_write_requires_api_key {
+            return None;
+        }
+        conn_opts
+            .api_keys
+            .iter()
+            .find(|api_key| api_key.has_scope(HttpApiKeyScope::StackerDBWrite))
+            .map(|api_key| api_key.key.clone())
+            .or_else(|| conn_opts.auth_token.clone())
+    }
+
     pub fn make_nakamoto_block_builder_settings(
         &self,
         miner_status: Arc<Mutex<MinerStatus>>,
@@ -2321,6 +2369,38 @@ pub struct NodeConfig {
     /// ---
     /// @default: `false`
     pub txindex: bool,
+    /// The minimum amount, in microSTX, by which a transaction's fee must exceed the fee of
+    /// the mempool transaction with the same origin (or sponsor) nonce in order to replace it.
+    /// ---
+    /// @default: `1`
+    /// @units: microSTX
+    /// @notes:
+    ///   - Must be at least `1`, so that a replacement always pays more than the
+    ///     transaction it replaces.
+    ///   - Replacements across forks are not subject to this rule.
+    pub mempool_replace_min_fee_bump: u64,
+    /// The minimum percentage by which a transaction's fee must exceed the fee of the
+    /// mempool transaction with the same origin (or sponsor) nonce in order to replace it.
+    /// ---
+    /// @default: `0`
+    /// @units: percent
+    /// @notes:
+    ///   - Both this and [`NodeConfig::mempool_replace_min_fee_bump`] must be satisfied.
+    ///   - Replacements across forks are not subject to this rule.
+    pub mempool_replace_min_fee_bump_percent: u64,
+    /// The maximum number of times that the mempool transaction for a given origin address
+    /// and nonce can be replaced by fee. Once reached, further replacements are rejected
+    /// until the transaction leaves the mempool.
+    /// ---
+    /// @default: `None` (no limit)
+    pub mempool_max_replacements: Option<u64>,
+    /// If `true`, a sponsored transaction that conflicts with one mempool transaction by its
+    /// origin nonce and with another by its sponsor nonce must out-bid both of them, and
+    /// replaces both of them. If `false`, only the first conflict found is considered: the
+    /// origin nonce conflict if there is one, and otherwise the sponsor nonce conflict.
+    /// ---
+    /// @default: `false`
+    pub mempool_replace_sponsor_aware: bool,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -2585,6 +2665,10 @@ impl Default for NodeConfig {
             chain_liveness_poll_time_secs: 300,
             stacker_dbs: vec![],
             txindex: false,
+            mempool_replace_min_fee_bump: 1,
+            mempool_replace_min_fee_bump_percent: 0,
+            mempool_max_replacements: None,
+            mempool_replace_sponsor_aware: false,
         }
     }
 }
@@ -2624,6 +2708,16 @@ impl NodeConfig {
         }
     }
 
+    /// The rules for replacing mempool transactions that spend the same nonce
+    pub fn mempool_replace_policy(&self) -> MemPoolReplacePolicy {
+        MemPoolReplacePolicy {
+            min_fee_bump: self.mempool_replace_min_fee_bump,
+            min_fee_bump_percent: self.mempool_replace_min_fee_bump_percent,
+            max_replacements: self.mempool_max_replacements,
+            sponsor_aware: self.mempool_replace_sponsor_aware,
+        }
+    }
+
     pub fn add_miner_stackerdb(&mut self, is_mainnet: bool) {
         let miners_contract_id = boot_code_id(MINERS_NAME, is_mainnet);
         if !self.stacker_dbs.contains(&miners_contract_id) {
@@ -2785,6 +2879,9 @@ pub struct MinerConfig {
     /// - `"GlobalFeeRate"`: Selects the transaction with the highest fee rate globally.
     /// - `"NextNonceWithHighestFeeRate"`: Selects the highest-fee transaction among those
     ///   matching the next expected nonce for sender/sponsor accounts.
+    /// - `"NonceChainPackageFeeRate"`: Ranks each sender's chain of consecutive-nonce
+    ///   transactions by its aggregate fee rate, so a high-fee transaction can pay for the
+    ///   lower-fee transactions that precede it.
     /// ---
     /// @default: `"NextNonceWithHighestFeeRate"`
     pub mempool_walk_strategy: MemPoolWalkStrategy,
@@ -3636,6 +3733,19 @@ pub struct ConnectionOptionsFile {
     /// ---
     /// @default: `false`
     pub private_neighbors: Option<bool>,
+    /// Whether to encrypt p2p traffic with peers that support it.
+    ///
+    /// If `true`, the node advertises encrypted-session support in its handshakes. With
+    /// each peer that advertises it too, both sides switch to an encrypted and
+    /// authenticated byte stream right after the handshake, keyed from fresh ephemeral
+    /// keys and the two nodes' p2p public keys. Peers that do not advertise it are still
+    /// talked to in the clear, as before.
+    /// ---
+    /// @default: `false`
+    /// @notes:
+    ///   - A peer that advertises support but does not start its encrypted session within
+    ///     `handshake_timeout` seconds, or that keeps sending in the clear, is disconnected.
+    pub p2p_encryption: Option<bool>,
     /// HTTP auth password to use when communicating with stacks-signer binary.
     ///
     /// This token is used in the `Authorization` header for certain requests.
@@ -3650,7 +3760,45 @@ pub struct ConnectionOptionsFile {
     ///     block proposals from a configured `stacks-signer` [[events_observer]]
     ///     via the `/v3/block_proposal` endpoint.
     ///   - The value must match the token configured on the signer.
+    ///   - This token is accepted as an API key named `auth_token` with the `admin`
+    ///     scope, so it grants access to every privileged endpoint. Prefer
+    ///     [`ConnectionOptionsFile::api_keys`] to give each client only what it needs.
     pub auth_token: Option<String>,
+    /// Named API keys for privileged RPC endpoints, each limited to a set of scopes.
+    ///
+    /// A client presents a key by sending it verbatim in the `Authorization` header.
+    /// Each request made with a key is logged with the key's name. The scopes are:
+    ///   - `block-proposal`: submit blocks for validation via `/v3/block_proposal`.
+    ///   - `block-upload`: upload and broadcast blocks via `/v3/blocks/upload?broadcast=1`.
+    ///   - `stackerdb-write`: write StackerDB chunks, if
+    ///     [`ConnectionOptionsFile::stackerdb_write_requires_api_key`] is set.
+    ///   - `unlimited-read-only`: make read-only calls without cost limits via
+    ///     `/v3/contracts/fast-call-read`.
+    ///   - `rate-limit-exempt`: make requests without being subject to the RPC rate limits.
+    ///   - `admin`: all of the above.
+    /// ---
+    /// @default: `None` (only [`ConnectionOptionsFile::auth_token`], if set, is accepted)
+    /// @notes:
+    ///   - Key names and key values must each be unique, and must not be empty.
+    /// @toml_example: |
+    ///   [[connection_options.api_keys]]
+    ///   name = "signer"
+    ///   key = "a-long-random-secret"
+    ///   scopes = ["block-proposal"]
+    pub api_keys: Option<Vec<ApiKeyConfigFile>>,
+    /// If `true`, writes to `/v2/stackerdb/:principal/:contract_name/chunks` must carry
+    /// an API key with the `stackerdb-write` scope.
+    /// ---
+    /// @default: `false`
+    /// @notes:
+    ///   - Chunks are still authenticated by their slot signatures either way. This only
+    ///     restricts which clients may submit them through this node's RPC interface.
+    ///   - Requires at least one key with the `stackerdb-write` or `admin` scope.
+    ///   - This node's miner writes through the RPC interface too, and sends the first key
+    ///     with the `stackerdb-write` scope, or else [`ConnectionOptionsFile::auth_token`].
+    ///   - Signers send their `auth_password` with each chunk, so the key they use must
+    ///     have the `stackerdb-write` scope as well as `block-proposal`.
+    pub stackerdb_write_requires_api_key: Option<bool>,
     /// Minimum interval (in seconds) between attempts to run the Epoch 2.x anti-entropy
     /// data push mechanism.
     ///
@@ -3730,6 +3878,182 @@ pub struct ConnectionOptionsFile {
     /// @default: 30
     /// @units: seconds
     pub read_only_max_execution_time_secs: Option<u64>,
+
+    /// Maximum number of concurrent clients of the `/v3/events/stream` endpoint.
+    ///
+    /// Each client holds an HTTP connection open for as long as it is subscribed, so
+    /// these connections also count against [`ConnectionOptionsFile::max_http_clients`].
+    /// Requests beyond this limit are rejected with HTTP 503. Set to `0` to disable
+    /// the endpoint.
+    /// ---
+    /// @default: `16`
+    pub max_event_stream_clients: Option<u64>,
+
+    /// Maximum number of undelivered events buffered for each `/v3/events/stream`
+    /// client.
+    ///
+    /// If a client reads too slowly and its buffer fills up, the oldest buffered
+    /// events are dropped, and the client is sent a `lagged` event reporting how many
+    /// events it missed.
+    /// ---
+    /// @default: `256`
+    pub event_stream_buffer_size: Option<u64>,
+
+    /// Interval (in seconds) at which a keep-alive comment is sent to an idle
+    /// `/v3/events/stream` client.
+    /// ---
+    /// @default: `10`
+    /// @units: seconds
+    /// @notes:
+    ///   - Must be less than [`ConnectionOptionsFile::idle_timeout`], otherwise
+    ///     idle event stream connections would be closed by the node.
+    pub event_stream_keepalive_secs: Option<u64>,
+    /// Maximum number of read-only function calls (`/v2/contracts/call-read` and
+    /// `/v3/contracts/fast-call-read`) that one client may make to the RPC server per
+    /// [`ConnectionOptionsFile::rpc_rate_limit_window_secs`].
+    ///
+    /// Each client may use its whole budget in a burst, and it is then refilled
+    /// evenly over the window. Requests beyond the budget are rejected with HTTP 429,
+    /// and a `Retry-After` header saying how many seconds to wait. Requests that carry
+    /// one of the node's [`ConnectionOptionsFile::api_keys`] with the `rate-limit-exempt`
+    /// or `admin` scope (or its [`ConnectionOptionsFile::auth_token`]) are never rate-limited.
+    /// Set to `0` for no limit.
+    /// ---
+    /// @default: `0`
+    pub rpc_rate_limit_read_only_calls: Option<u64>,
+
+    /// Maximum number of transactions that one client may broadcast through the RPC
+    /// server per [`ConnectionOptionsFile::rpc_rate_limit_window_secs`].
+    /// Set to `0` for no limit.
+    /// ---
+    /// @default: `0`
+    /// @notes:
+    ///   - See [`ConnectionOptionsFile::rpc_rate_limit_read_only_calls`] for how limits
+    ///     are enforced.
+    pub rpc_rate_limit_tx_broadcasts: Option<u64>,
+
+    /// Maximum number of block, tenure, and microblock downloads that one client may
+    /// make from the RPC server per [`ConnectionOptionsFile::rpc_rate_limit_window_secs`].
+    /// Set to `0` for no limit.
+    /// ---
+    /// @default: `0`
+    /// @notes:
+    ///   - See [`ConnectionOptionsFile::rpc_rate_limit_read_only_calls`] for how limits
+    ///     are enforced.
+    pub rpc_rate_limit_block_fetches: Option<u64>,
+
+    /// Maximum number of requests to all other RPC endpoints that one client may make
+    /// per [`ConnectionOptionsFile::rpc_rate_limit_window_secs`].
+    /// Set to `0` for no limit.
+    /// ---
+    /// @default: `0`
+    /// @notes:
+    ///   - See [`ConnectionOptionsFile::rpc_rate_limit_read_only_calls`] for how limits
+    ///     are enforced.
+    pub rpc_rate_limit_other: Option<u64>,
+
+    /// Length (in seconds) of the window over which each client's RPC request budgets
+    /// are refilled.
+    /// ---
+    /// @default: `60`
+    /// @units: seconds
+    pub rpc_rate_limit_window_secs: Option<u64>,
+
+    /// Prefix length used to group IPv4 RPC clients for rate limiting. All addresses in
+    /// the same prefix share one set of budgets. `32` limits each address separately.
+    /// ---
+    /// @default: `32`
+    pub rpc_rate_limit_ipv4_prefix_len: Option<u32>,
+
+    /// Prefix length used to group IPv6 RPC clients for rate limiting. All addresses in
+    /// the same prefix share one set of budgets.
+    /// ---
+    /// @default: `64`
+    pub rpc_rate_limit_ipv6_prefix_len: Option<u32>,
+
+    /// Comma-separated list of CIDR prefixes (or single IP addresses) of RPC clients
+    /// that are never rate-limited.
+    /// ---
+    /// @default: `None` (no client is exempt)
+    /// @toml_example: |
+    ///   rpc_rate_limit_exempt = "127.0.0.1,10.0.0.0/8,fd00::/8"
+    pub rpc_rate_limit_exempt: Option<String>,
+
+    /// Path to a PEM file with the certificate chain the RPC server presents to TLS clients.
+    /// Must be set together with [`ConnectionOptionsFile::rpc_tls_key_path`].
+    /// ---
+    /// @default: `None` (TLS is not offered)
+    /// @notes:
+    ///   - TLS is served on the same port as plaintext HTTP ([`NodeConfig::rpc_bind`]).
+    ///     The node tells the two apart by the first byte a client sends, so plaintext
+    ///     clients (such as other nodes fetching blocks) keep working unless
+    ///     [`ConnectionOptionsFile::rpc_tls_required`] is set.
+    ///   - The certificate and key are re-read within a few seconds of either file
+    ///     changing, so they can be renewed without restarting the node.  If the new files
+    ///     cannot be loaded, the previous certificate stays in use.
+    /// @toml_example: |
+    ///   rpc_tls_cert_path = "/etc/stacks/tls/fullchain.pem"
+    ///   rpc_tls_key_path = "/etc/stacks/tls/privkey.pem"
+    pub rpc_tls_cert_path: Option<String>,
+
+    /// Path to a PEM file with the private key for
+    /// [`ConnectionOptionsFile::rpc_tls_cert_path`]. PKCS#8, PKCS#1 (RSA), and SEC1 (EC)
+    /// keys are supported.
+    /// ---
+    /// @default: `None` (TLS is not offered)
+    pub rpc_tls_key_path: Option<String>,
+
+    /// If set to `true`, the RPC server only serves clients that use TLS, and disconnects
+    /// remote plaintext clients as soon as they send their first byte. Requires
+    /// [`ConnectionOptionsFile::rpc_tls_cert_path`] and
+    /// [`ConnectionOptionsFile::rpc_tls_key_path`].
+    /// ---
+    /// @default: `false`
+    /// @notes:
+    ///   - Use this when clients send auth tokens or API keys over the network, so that
+    ///     they are never sent in the clear.
+    ///   - Every remote client of the RPC port must then use TLS, including signers
+    ///     (`node_host` with `https://`), event-stream consumers, and other nodes that
+    ///     fetch blocks over RPC.
+    ///   - Clients connecting from a loopback address are still served over plaintext,
+    ///     since their traffic never leaves the host. The node's own miner relies on this
+    ///     to write to StackerDB.
+    pub rpc_tls_required: Option<bool>,
+
+    /// Reputation score at which a peer is banned. Set to `0` to never ban peers for
+    /// their reputation.
+    /// ---
+    /// @default: `100`
+    /// @notes:
+    ///   - Peers accrue points for misbehavior: 100 for sending an invalid block, 25 for
+    ///     flooding the node with NACKs, 20 for advertising a tenure in their inventory
+    ///     that they then fail to serve, and 10 for sending an invalid StackerDB chunk.
+    ///   - Scores decay over time (see
+    ///     [`ConnectionOptionsFile::reputation_half_life_secs`]) and are kept in the peer
+    ///     database across restarts. They can be inspected with
+    ///     `GET /v2/neighbors/reputation`.
+    ///   - Peers with the `allowed` flag are never banned.
+    ///   - A peer that sends an invalid block is always banned, whatever this is set to.
+    pub reputation_ban_threshold: Option<u64>,
+
+    /// Reputation score at which a peer is no longer used as a starting point for
+    /// neighbor walks. Set to `0` to ignore reputation when choosing walk peers.
+    /// ---
+    /// @default: `50`
+    pub reputation_walk_threshold: Option<u64>,
+
+    /// Time it takes for a peer's reputation score to decay by half. Set to `0` to never
+    /// decay scores.
+    /// ---
+    /// @default: `3600`
+    /// @units: seconds
+    pub reputation_half_life_secs: Option<u64>,
+
+    /// Number of NACKs a peer may send within one minute before it is penalized for
+    /// flooding the node with them. Set to `0` to disable the check.
+    /// ---
+    /// @default: `100`
+    pub reputation_nack_flood_threshold: Option<u64>,
 }
 
 impl ConnectionOptionsFile {
@@ -3761,7 +4085,89 @@ impl ConnectionOptionsFile {
         if let Some(x) = self.read_only_call_limit_runtime {
             read_only_call_limit.runtime = x;
         };
+        if self
+            .rpc_rate_limit_ipv4_prefix_len
+            .is_some_and(|len| len > 32)
+        {
+            return Err(
+                "connection_options.rpc_rate_limit_ipv4_prefix_len must be at most 32".into(),
+            );
+        }
+        if self
+            .rpc_rate_limit_ipv6_prefix_len
+            .is_some_and(|len| len > 128)
+        {
+            return Err(
+                "connection_options.rpc_rate_limit_ipv6_prefix_len must be at most 128".into(),
+            );
+        }
         let default = ConnectionOptions::default();
+        let idle_timeout = self
+            .idle_timeout
+            .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.idle_timeout);
+        let event_stream_keepalive_secs = self
+            .event_stream_keepalive_secs
+            .unwrap_or(default.event_stream_keepalive_secs);
+        if event_stream_keepalive_secs >= idle_timeout {
+            return Err(format!(
+                "connection_options.event_stream_keepalive_secs ({event_stream_keepalive_secs}) must be less than connection_options.idle_timeout ({idle_timeout})"
+            ));
+        }
+        let api_keys = self
+            .api_keys
+            .unwrap_or_default()
+            .into_iter()
+            .map(ApiKeyConfigFile::into_config)
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut names = HashSet::new();
+        let mut keys = HashSet::new();
+        if let Some(auth_token) = self.auth_token.as_ref() {
+            names.insert(LEGACY_AUTH_TOKEN_KEY_NAME);
+            keys.insert(auth_token.as_str());
+        }
+        for api_key in api_keys.iter() {
+            if !names.insert(api_key.name.as_str()) {
+                return Err(format!(
+                    "connection_options.api_keys: duplicate key name '{}'",
+                    api_key.name
+                ));
+            }
+            if !keys.insert(api_key.key.as_str()) {
+                return Err(format!(
+                    "connection_options.api_keys: key '{}' reuses another key's value",
+                    api_key.name
+                ));
+            }
+        }
+        let stackerdb_write_requires_api_key =
+            self.stackerdb_write_requires_api_key.unwrap_or(false);
+        if stackerdb_write_requires_api_key
+            && self.auth_token.is_none()
+            && !api_keys
+                .iter()
+                .any(|api_key| api_key.has_scope(HttpApiKeyScope::StackerDBWrite))
+        {
+            return Err("connection_options.stackerdb_write_requires_api_key is set, but no API key has the 'stackerdb-write' scope".into());
+        }
+        match (
+            self.rpc_tls_cert_path.as_ref(),
+            self.rpc_tls_key_path.as_ref(),
+        ) {
+            (Some(cert_path), Some(key_path)) => {
+                // fail now, rather than when the RPC server starts
+                make_server_config(cert_path, key_path).map_err(|e| {
+                    format!("Invalid connection_options.rpc_tls_cert_path or rpc_tls_key_path: {e}")
+                })?;
+            }
+            (None, None) => {
+                if self.rpc_tls_required == Some(true) {
+                    return Err("connection_options.rpc_tls_required is set, but rpc_tls_cert_path and rpc_tls_key_path are not".into());
+                }
+            }
+            _ => {
+                return Err("connection_options.rpc_tls_cert_path and rpc_tls_key_path must be set together".into());
+            }
+        }
         Ok(ConnectionOptions {
             read_only_call_limit,
             inbox_maxlen: self
@@ -3773,9 +4179,7 @@ impl ConnectionOptionsFile {
             timeout: self
                 .timeout
                 .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.timeout),
-            idle_timeout: self
-                .idle_timeout
-                .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.idle_timeout),
+            idle_timeout,
             heartbeat: self
                 .heartbeat
                 .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.heartbeat),
@@ -3860,7 +4264,10 @@ impl ConnectionOptionsFile {
             max_sockets: self.max_sockets.unwrap_or(800) as usize,
             antientropy_public: self.antientropy_public.unwrap_or(true),
             private_neighbors: self.private_neighbors.unwrap_or(false),
+            p2p_encryption: self.p2p_encryption.unwrap_or(default.p2p_encryption),
             auth_token: self.auth_token,
+            api_keys,
+            stackerdb_write_requires_api_key,
             antientropy_retry: self.antientropy_retry.unwrap_or(default.antientropy_retry),
             reject_blocks_pushed: self
                 .reject_blocks_pushed
@@ -3884,11 +4291,128 @@ impl ConnectionOptionsFile {
             read_only_max_execution_time_secs: self
                 .read_only_max_execution_time_secs
                 .unwrap_or(default.read_only_max_execution_time_secs),
+            max_event_stream_clients: self
+                .max_event_stream_clients
+                .unwrap_or(default.max_event_stream_clients),
+            event_stream_buffer_size: self
+                .event_stream_buffer_size
+                .map(|size| size as usize)
+                .unwrap_or(default.event_stream_buffer_size),
+            event_stream_keepalive_secs,
+            rpc_rate_limit_read_only_calls: self
+                .rpc_rate_limit_read_only_calls
+                .unwrap_or(default.rpc_rate_limit_read_only_calls),
+            rpc_rate_limit_tx_broadcasts: self
+                .rpc_rate_limit_tx_broadcasts
+                .unwrap_or(default.rpc_rate_limit_tx_broadcasts),
+            rpc_rate_limit_block_fetches: self
+                .rpc_rate_limit_block_fetches
+                .unwrap_or(default.rpc_rate_limit_block_fetches),
+            rpc_rate_limit_other: self
+                .rpc_rate_limit_other
+                .unwrap_or(default.rpc_rate_limit_other),
+            rpc_rate_limit_window_secs: self
+                .rpc_rate_limit_window_secs
+                .unwrap_or(default.rpc_rate_limit_window_secs),
+            rpc_rate_limit_ipv4_prefix_len: self
+                .rpc_rate_limit_ipv4_prefix_len
+                .unwrap_or(default.rpc_rate_limit_ipv4_prefix_len),
+            rpc_rate_limit_ipv6_prefix_len: self
+                .rpc_rate_limit_ipv6_prefix_len
+                .unwrap_or(default.rpc_rate_limit_ipv6_prefix_len),
+            rpc_rate_limit_exempt: self
+                .rpc_rate_limit_exempt
+                .map(|exempt| {
+                    exempt
+                        .split(',')
+                        .filter(|cidr| !cidr.trim().is_empty())
+                        .map(|cidr| {
+                            parse_cidr(cidr).map_err(|e| {
+                                format!("Invalid connection_options.rpc_rate_limit_exempt: {e}")
+                            })
+                        })
+                        .collect::<Result<Vec<_>, String>>()
+                })
+                .transpose()?
+                .unwrap_or(default.rpc_rate_limit_exempt),
+            rpc_tls_cert_path: self.rpc_tls_cert_path,
+            rpc_tls_key_path: self.rpc_tls_key_path,
+            rpc_tls_required: self.rpc_tls_required.unwrap_or(default.rpc_tls_required),
+            reputation_ban_threshold: self
+                .reputation_ban_threshold
+                .unwrap_or(default.reputation_ban_threshold),
+            reputation_walk_threshold: self
+                .reputation_walk_threshold
+                .unwrap_or(default.reputation_walk_threshold),
+            reputation_half_life_secs: self
+                .reputation_half_life_secs
+                .unwrap_or(default.reputation_half_life_secs),
+            reputation_nack_flood_threshold: self
+                .reputation_nack_flood_threshold
+                .unwrap_or(default.reputation_nack_flood_threshold),
             ..default
         })
     }
 }
 
+#[derive(Clone, Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfigFile {
+    /// Name of the key. It is only used to identify the key in logs.
+    /// ---
+    /// @default: No default.
+    /// @required: true
+    pub name: String,
+    /// The secret value that clients send in the `Authorization` header.
+    /// ---
+    /// @default: No default.
+    /// @required: true
+    pub key: String,
+    /// What the key may be used for. See [`ConnectionOptionsFile::api_keys`] for the
+    /// list of scopes.
+    /// ---
+    /// @default: No default.
+    /// @required: true
+    pub scopes: Vec<String>,
+}
+
+impl ApiKeyConfigFile {
+    fn into_config(self) -> Result<HttpApiKey, String> {
+        if self.name.is_empty() {
+            return Err("connection_options.api_keys: key names must not be empty".into());
+        }
+        if self.key.is_empty() {
+            return Err(format!(
+                "connection_options.api_keys: key '{}' must not be empty",
+                self.name
+            ));
+        }
+        if self.scopes.is_empty() {
+            return Err(format!(
+                "connection_options.api_keys: key '{}' must have at least one scope",
+                self.name
+            ));
+        }
+        let scopes = self
+            .scopes
+            .iter()
+            .map(|scope| {
+                HttpApiKeyScope::lookup_by_name(scope).ok_or_else(|| {
+                    format!(
+                        "connection_options.api_keys: key '{}' has unknown scope '{scope}'",
+                        self.name
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(HttpApiKey {
+            name: self.name,
+            key: self.key,
+            scopes,
+        })
+    }
+}
+
 #[derive(Clone, Deserialize, Default, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct NodeConfigFile {
@@ -3929,6 +4453,10 @@ pub struct NodeConfigFile {
     pub fault_injection_block_push_fail_probability: Option<u8>,
     /// enable transactions indexing, note this will require additional storage (in the order of gigabytes)
     pub txindex: Option<bool>,
+    pub mempool_replace_min_fee_bump: Option<u64>,
+    pub mempool_replace_min_fee_bump_percent: Option<u64>,
+    pub mempool_max_replacements: Option<u64>,
+    pub mempool_replace_sponsor_aware: Option<bool>,
 }
 
 impl NodeConfigFile {
@@ -4029,6 +4557,22 @@ impl NodeConfigFile {
             },
 
             txindex: self.txindex.unwrap_or(default_node_config.txindex),
+            mempool_replace_min_fee_bump: match self.mempool_replace_min_fee_bump {
+                Some(0) => {
+                    return Err("node.mempool_replace_min_fee_bump must be at least 1".to_string())
+                }
+                Some(min_fee_bump) => min_fee_bump,
+                None => default_node_config.mempool_replace_min_fee_bump,
+            },
+            mempool_replace_min_fee_bump_percent: self
+                .mempool_replace_min_fee_bump_percent
+                .unwrap_or(default_node_config.mempool_replace_min_fee_bump_percent),
+            mempool_max_replacements: self
+                .mempool_max_replacements
+                .or(default_node_config.mempool_max_replacements),
+            mempool_replace_sponsor_aware: self
+                .mempool_replace_sponsor_aware
+                .unwrap_or(default_node_config.mempool_replace_sponsor_aware),
         };
         Ok(node_config)
     }
@@ -4512,6 +5056,45 @@ pub struct EventObserverConfigFile {
     ///   - **Warning:** Setting this to `true` can lead to missed events if the
     ///     observer endpoint is temporarily unavailable or experiences issues.
     pub disable_retries: Option<bool>,
+    /// The transport used to deliver event payloads to this observer.
+    ///
+    /// - `"http"` (default): Payloads are sent as HTTP POST requests to
+    ///   `http://{endpoint}/{path}`.
+    /// - `"https"`: Payloads are sent as HTTP POST requests to
+    ///   `https://{endpoint}/{path}`. See [`EventObserverConfigFile::tls_ca_cert_path`].
+    /// - `"unix_socket"`: `endpoint` is the filesystem path of a Unix domain socket.
+    ///   Each payload is written to a new connection as a single NDJSON line.
+    /// - `"file"`: `endpoint` is the path of a file to which each payload is appended
+    ///   as a single NDJSON line. The file is created if it does not exist.
+    /// - `"named_pipe"`: `endpoint` is the path of an existing named pipe (FIFO).
+    ///   Each payload is written to it as a single NDJSON line.
+    ///
+    /// For all non-HTTP transports, every line is a JSON object of the form
+    /// `{"path": "/new_block", "payload": {...}}`, where `path` is the HTTP path the
+    /// payload would have been POSTed to.
+    /// ---
+    /// @default: `"http"`
+    /// @notes:
+    ///   - `"unix_socket"` and `"named_pipe"` are only available on Unix platforms.
+    ///   - Writing to a named pipe blocks until a reader has opened it.
+    /// @toml_example: |
+    ///   transport = "file"
+    ///   endpoint = "/var/log/stacks/events.ndjson"
+    pub transport: Option<String>,
+    /// Path to a PEM file with the CA certificates that an `"https"` observer's certificate
+    /// must be issued by.
+    ///
+    /// If unset, the observer's certificate is verified against the built-in web PKI
+    /// roots. Either way, it must be valid for the host in `endpoint`.
+    /// ---
+    /// @default: `None` (trust the web PKI roots)
+    /// @notes:
+    ///   - Only relevant if [`EventObserverConfigFile::transport`] is `"https"`.
+    /// @toml_example: |
+    ///   transport = "https"
+    ///   endpoint = "events.example.com:443"
+    ///   tls_ca_cert_path = "/etc/stacks/tls/observer-ca.pem"
+    pub tls_ca_cert_path: Option<String>,
 }
 
 #[derive(Clone, Default, Debug, Hash, PartialEq, Eq, PartialOrd)]
@@ -4520,6 +5103,38 @@ pub struct EventObserverConfig {
     pub events_keys: Vec<EventKeyType>,
     pub timeout_ms: u64,
     pub disable_retries: bool,
+    pub transport: EventObserverTransport,
+    /// CA certificates to pin for an HTTPS observer, instead of the web PKI roots
+    pub tls_ca_cert_path: Option<String>,
+}
+
+/// How event payloads are delivered to an event observer
+#[derive(Clone, Copy, Default, Debug, Hash, PartialEq, Eq, PartialOrd)]
+pub enum EventObserverTransport {
+    /// HTTP POST to `http://{endpoint}/{path}`
+    #[default]
+    Http,
+    /// HTTP POST to `https://{endpoint}/{path}`
+    Https,
+    /// NDJSON lines written to the Unix domain socket at `endpoint`
+    UnixSocket,
+    /// NDJSON lines appended to the file at `endpoint`
+    File,
+    /// NDJSON lines written to the named pipe (FIFO) at `endpoint`
+    NamedPipe,
+}
+
+impl EventObserverTransport {
+    pub fn from_string(raw: &str) -> Option<EventObserverTransport> {
+        match raw {
+            "http" => Some(EventObserverTransport::Http),
+            "https" => Some(EventObserverTransport::Https),
+            "unix_socket" => Some(EventObserverTransport::UnixSocket),
+            "file" => Some(EventObserverTransport::File),
+            "named_pipe" => Some(EventObserverTransport::NamedPipe),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd)]
@@ -4633,6 +5248,7 @@ mod tests {
     use std::path::Path;
 
     use super::*;
+    use crate::net::tests::tls_test_file_path;
 
     #[test]
     fn test_config_file() {
@@ -4679,6 +5295,37 @@ mod tests {
             .unwrap_err()
         );
 
+        assert_eq!(
+            format!("node.mempool_replace_min_fee_bump must be at least 1"),
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [node]
+                    mempool_replace_min_fee_bump = 0
+                    "#,
+                )
+                .unwrap(),
+                false
+            )
+            .unwrap_err()
+        );
+
+        assert_eq!(
+            format!("connection_options.event_stream_keepalive_secs (15) must be less than connection_options.idle_timeout (15)"),
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [connection_options]
+                    event_stream_keepalive_secs = 15
+                    idle_timeout = 15
+                    "#,
+                )
+                .unwrap(),
+                false
+            )
+            .unwrap_err()
+        );
+
         let expected_err_prefix =
             "Invalid burnchain.peer_host: failed to lookup address information:";
         let actual_err_msg = Config::from_config_file(
@@ -4904,6 +5551,206 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_load_rpc_rate_limits() {
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [connection_options]
+                rpc_rate_limit_read_only_calls = 120
+                rpc_rate_limit_tx_broadcasts = 10
+                rpc_rate_limit_window_secs = 30
+                rpc_rate_limit_ipv6_prefix_len = 48
+                rpc_rate_limit_exempt = "127.0.0.1, 10.0.0.0/8,fd00::/8"
+                "#,
+            )
+            .unwrap(),
+            false,
+        )
+        .expect("Expected to be able to parse RPC rate limits from file");
+
+        let opts = &config.connection_options;
+        assert_eq!(opts.rpc_rate_limit_read_only_calls, 120);
+        assert_eq!(opts.rpc_rate_limit_tx_broadcasts, 10);
+        assert_eq!(opts.rpc_rate_limit_block_fetches, 0);
+        assert_eq!(opts.rpc_rate_limit_other, 0);
+        assert_eq!(opts.rpc_rate_limit_window_secs, 30);
+        assert_eq!(opts.rpc_rate_limit_ipv4_prefix_len, 32);
+        assert_eq!(opts.rpc_rate_limit_ipv6_prefix_len, 48);
+        assert_eq!(
+            opts.rpc_rate_limit_exempt,
+            vec![
+                (PeerAddress::from_ipv4(127, 0, 0, 1), 128),
+                (PeerAddress::from_ipv4(10, 0, 0, 0), 104),
+                (PeerAddress::from_ip(&"fd00::".parse().unwrap()), 8),
+            ]
+        );
+
+        for bad_option in [
+            "rpc_rate_limit_exempt = \"10.0.0.0/33\"",
+            "rpc_rate_limit_exempt = \"not-an-address\"",
+            "rpc_rate_limit_ipv4_prefix_len = 33",
+        ] {
+            let config_file =
+                ConfigFile::from_str(&format!("[connection_options]\n{bad_option}\n")).unwrap();
+            assert!(
+                Config::from_config_file(config_file, false).is_err(),
+                "Expected '{bad_option}' to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn should_load_api_keys() {
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [connection_options]
+                auth_token = "password"
+                stackerdb_write_requires_api_key = true
+
+                [[connection_options.api_keys]]
+                name = "signer"
+                key = "signer-key"
+                scopes = ["block-proposal", "stackerdb-write"]
+
+                [[connection_options.api_keys]]
+                name = "explorer"
+                key = "explorer-key"
+                scopes = ["unlimited-read-only"]
+                "#,
+            )
+            .unwrap(),
+            false,
+        )
+        .expect("Expected to be able to parse API keys from file");
+
+        let opts = &config.connection_options;
+        assert_eq!(opts.auth_token, Some("password".to_string()));
+        assert!(opts.stackerdb_write_requires_api_key);
+        assert_eq!(
+            opts.api_keys,
+            vec![
+                HttpApiKey {
+                    name: "signer".into(),
+                    key: "signer-key".into(),
+                    scopes: vec![
+                        HttpApiKeyScope::BlockProposal,
+                        HttpApiKeyScope::StackerDBWrite
+                    ],
+                },
+                HttpApiKey {
+                    name: "explorer".into(),
+                    key: "explorer-key".into(),
+                    scopes: vec![HttpApiKeyScope::UnlimitedReadOnly],
+                },
+            ]
+        );
+        // the miner writes to StackerDB with the first key that may do so
+        assert_eq!(
+            config.stackerdb_write_api_key(),
+            Some("signer-key".to_string())
+        );
+
+        for bad_options in [
+            // unknown scope
+            "[[connection_options.api_keys]]\nname = \"a\"\nkey = \"a\"\nscopes = [\"everything\"]\n",
+            // no scopes
+            "[[connection_options.api_keys]]\nname = \"a\"\nkey = \"a\"\nscopes = []\n",
+            // empty key
+            "[[connection_options.api_keys]]\nname = \"a\"\nkey = \"\"\nscopes = [\"admin\"]\n",
+            // duplicate names
+            "[[connection_options.api_keys]]\nname = \"a\"\nkey = \"a\"\nscopes = [\"admin\"]\n[[connection_options.api_keys]]\nname = \"a\"\nkey = \"b\"\nscopes = [\"admin\"]\n",
+            // duplicate keys
+            "[[connection_options.api_keys]]\nname = \"a\"\nkey = \"a\"\nscopes = [\"admin\"]\n[[connection_options.api_keys]]\nname = \"b\"\nkey = \"a\"\nscopes = [\"admin\"]\n",
+            // key collides with the legacy auth token
+            "[connection_options]\nauth_token = \"a\"\n[[connection_options.api_keys]]\nname = \"b\"\nkey = \"a\"\nscopes = [\"admin\"]\n",
+            // no key may write to StackerDB
+            "[connection_options]\nstackerdb_write_requires_api_key = true\n",
+        ] {
+            let config_file = ConfigFile::from_str(bad_options).unwrap();
+            assert!(
+                Config::from_config_file(config_file, false).is_err(),
+                "Expected '{bad_options}' to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn should_load_tls_options() {
+        let cert_path = tls_test_file_path("server-1.pem");
+        let key_path = tls_test_file_path("server-1.key");
+        let ca_path = tls_test_file_path("ca-1.pem");
+        let config = Config::from_config_file(
+            ConfigFile::from_str(&format!(
+                r#"
+                [connection_options]
+                rpc_tls_cert_path = "{cert_path}"
+                rpc_tls_key_path = "{key_path}"
+
+                [[events_observer]]
+                endpoint = "localhost:3700"
+                events_keys = ["*"]
+                transport = "https"
+                tls_ca_cert_path = "{ca_path}"
+                "#
+            ))
+            .unwrap(),
+            false,
+        )
+        .expect("Expected to be able to parse TLS options from file");
+
+        let opts = &config.connection_options;
+        assert_eq!(opts.rpc_tls_cert_path, Some(cert_path.clone()));
+        assert_eq!(opts.rpc_tls_key_path, Some(key_path.clone()));
+        assert!(!opts.rpc_tls_required);
+
+        let observer = config.events_observers.iter().next().unwrap();
+        assert_eq!(observer.transport, EventObserverTransport::Https);
+        assert_eq!(observer.tls_ca_cert_path, Some(ca_path.clone()));
+
+        for bad_options in [
+            // cert without a key
+            format!("[connection_options]\nrpc_tls_cert_path = \"{cert_path}\"\n"),
+            // unreadable key
+            format!("[connection_options]\nrpc_tls_cert_path = \"{cert_path}\"\nrpc_tls_key_path = \"/nonexistent.key\"\n"),
+            // pinned CA without https
+            format!("[[events_observer]]\nendpoint = \"localhost:3700\"\nevents_keys = [\"*\"]\ntls_ca_cert_path = \"{ca_path}\"\n"),
+            // TLS required, but not configured
+            "[connection_options]\nrpc_tls_required = true\n".to_string(),
+        ] {
+            let config_file = ConfigFile::from_str(&bad_options).unwrap();
+            assert!(
+                Config::from_config_file(config_file, false).is_err(),
+                "Expected '{bad_options}' to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn should_load_reputation_options() {
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [connection_options]
+                reputation_ban_threshold = 200
+                reputation_walk_threshold = 0
+                reputation_half_life_secs = 600
+                reputation_nack_flood_threshold = 30
+                "#,
+            )
+            .unwrap(),
+            false,
+        )
+        .expect("Expected to be able to parse reputation options from file");
+
+        let opts = &config.connection_options;
+        assert_eq!(opts.reputation_ban_threshold, 200);
+        assert_eq!(opts.reputation_walk_threshold, 0);
+        assert_eq!(opts.reputation_half_life_secs, 600);
+        assert_eq!(opts.reputation_nack_flood_threshold, 30);
+    }
+
     #[test]
     fn should_load_affirmation_map() {
         let affirmation_string = "nnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnnppnnnnnnnnnnnnnnnnnnnnnnnnpppppnnnnnnnnnnnnnnnnnnnnnnnpppppppppppppppnnnnnnnnnnnnnnnnnnnnnnnppppppppppnnnnnnnnnnnnnnnnnnnppppnnnnnnnnnnnnnnnnnnnnnnnppppppppnnnnnnnnnnnnnnnnnnnnnnnppnppnnnnnnnnnnnnnnnnnnnnnnnppppnnnnnnnnnnnnnnnnnnnnnnnnnppppppnnnnnnnnnnnnnnnnnnnnnnnnnppnnnnnnnnnnnnnnnnnnnnnnnnnpppppppnnnnnnnnnnnnnnnnnnnnnnnnnnpnnnnnnnnnnnnnnnnnnnnnnnnnpppnppppppppppppppnnppppnpa";
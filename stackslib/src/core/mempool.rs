@@ -36,7 +36,7 @@ use stacks_common::types::chainstate::{BlockHeaderHash, StacksAddress, StacksBlo
 use stacks_common::types::sqlite::NO_PARAMS;
 use stacks_common::types::MempoolCollectionBehavior;
 use stacks_common::util::get_epoch_time_secs;
-use stacks_common::util::hash::{to_hex, Sha512Trunc256Sum};
+use stacks_common::util::hash::{hex_bytes, to_hex, Sha512Trunc256Sum};
 use stacks_common::util::retry::{BoundReader, RetryReader};
 
 use crate::burnchains::Txid;
@@ -431,6 +431,9 @@ pub struct MemPoolTxInfo {
     pub metadata: MemPoolTxMetadata,
 }
 
+/// A transaction in a `NonceChainPackageFeeRate` nonce chain, with its fee and estimated cost.
+type NonceChainTx = (MemPoolTxInfoPartial, u64, f64);
+
 /// This class is a minimal version of `MemPoolTxInfo`. It contains
 /// just enough information to 1) filter by nonce readiness, 2) sort by fee rate.
 #[derive(Debug, Clone)]
@@ -523,6 +526,9 @@ pub enum MemPoolWalkStrategy {
     GlobalFeeRate,
     /// Select transactions with the next expected nonce for origin and sponsor addresses,
     NextNonceWithHighestFeeRate,
+    /// Select transactions in per-origin nonce chains, ranked by the chain's aggregate fee rate,
+    /// so that a high-fee transaction can pay for the lower-fee transactions ahead of it.
+    NonceChainPackageFeeRate,
 }
 
 impl FromStr for MemPoolWalkStrategy {
@@ -535,6 +541,9 @@ impl FromStr for MemPoolWalkStrategy {
             "NextNonceWithHighestFeeRate" => {
                 return Ok(Self::NextNonceWithHighestFeeRate);
             }
+            "NonceChainPackageFeeRate" => {
+                return Ok(Self::NonceChainPackageFeeRate);
+            }
             _ => {
                 return Err("Unknown mempool walk strategy");
             }
@@ -595,6 +604,123 @@ impl MemPoolWalkSettings {
     }
 }
 
+/// Rules for replacing a mempool transaction with a new transaction that spends the same origin
+/// (or sponsor) nonce.  Replacements across forks are always allowed, and are not subject to
+/// these rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemPoolReplacePolicy {
+    /// The replacement must pay at least this many more microSTX than the transaction it replaces
+    pub min_fee_bump: u64,
+    /// The replacement must pay at least this percentage more than the transaction it replaces
+    pub min_fee_bump_percent: u64,
+    /// The maximum number of times a single (origin, nonce) may be replaced by fee.
+    /// `None` means no limit.
+    pub max_replacements: Option<u64>,
+    /// If set, a sponsored transaction must out-bid both the transaction with the same origin
+    /// nonce and the transaction with the same sponsor nonce, and it replaces both of them.
+    /// Otherwise, only the first conflicting transaction found is considered: the one with the
+    /// same origin nonce if there is one, and otherwise the one with the same sponsor nonce.
+    pub sponsor_aware: bool,
+}
+
+impl Default for MemPoolReplacePolicy {
+    fn default() -> Self {
+        MemPoolReplacePolicy {
+            min_fee_bump: 1,
+            min_fee_bump_percent: 0,
+            max_replacements: None,
+            sponsor_aware: false,
+        }
+    }
+}
+
+impl MemPoolReplacePolicy {
+    /// The lowest fee that a transaction must pay in order to replace a transaction that pays
+    /// `prior_fee`.
+    pub fn min_replacement_fee(&self, prior_fee: u64) -> u64 {
+        let relative_bump =
+            (u128::from(prior_fee) * u128::from(self.min_fee_bump_percent)).div_ceil(100);
+        let bump = u64::try_from(relative_bump)
+            .unwrap_or(u64::MAX)
+            .max(self.min_fee_bump);
+        prior_fee.saturating_add(bump)
+    }
+}
+
+/// A record of one transaction replacing another in the mempool
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemPoolReplacement {
+    pub replaced_txid: Txid,
+    pub replacement_txid: Txid,
+    /// Origin address and nonce of the replacement transaction
+    pub origin_address: StacksAddress,
+    pub origin_nonce: u64,
+    pub replaced_fee: u64,
+    pub replacement_fee: u64,
+    /// Why the replacement was allowed (i.e. a `MemPoolDropReason`)
+    pub reason: String,
+    pub replace_time: u64,
+}
+
+impl FromRow<MemPoolReplacement> for MemPoolReplacement {
+    fn from_row(row: &Row) -> Result<MemPoolReplacement, db_error> {
+        Ok(MemPoolReplacement {
+            replaced_txid: Txid::from_column(row, "replaced_txid")?,
+            replacement_txid: Txid::from_column(row, "replacement_txid")?,
+            origin_address: StacksAddress::from_column(row, "origin_address")?,
+            origin_nonce: u64::from_column(row, "origin_nonce")?,
+            replaced_fee: u64::from_column(row, "replaced_fee")?,
+            replacement_fee: u64::from_column(row, "replacement_fee")?,
+            reason: row.get("reason")?,
+            replace_time: u64::from_column(row, "replace_time")?,
+        })
+    }
+}
+
+/// Version of the mempool snapshot file format
+pub const MEMPOOL_SNAPSHOT_VERSION: u32 = 1;
+
+/// A transaction in a mempool snapshot, with the metadata needed to restore it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemPoolSnapshotTx {
+    pub txid: Txid,
+    /// Hex-encoded transaction
+    pub tx: String,
+    pub tx_fee: u64,
+    pub origin_address: String,
+    pub origin_nonce: u64,
+    pub sponsor_address: String,
+    pub sponsor_nonce: u64,
+    /// When the exporting node accepted the transaction
+    pub accept_time: u64,
+    /// The exporting node's fee rate estimate, if it had one
+    pub fee_rate: Option<f64>,
+}
+
+/// Portable snapshot of the contents of a mempool, used to warm up another node's mempool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemPoolSnapshot {
+    pub version: u32,
+    pub mainnet: bool,
+    pub chain_id: u32,
+    /// When the snapshot was taken
+    pub exported_at: u64,
+    /// Transactions, in the order in which the exporting node accepted them
+    pub txs: Vec<MemPoolSnapshotTx>,
+}
+
+impl MemPoolSnapshot {
+    pub fn new(mainnet: bool, chain_id: u32, txs: Vec<MemPoolSnapshotTx>) -> Self {
+        Self {
+            version: MEMPOOL_SNAPSHOT_VERSION,
+            mainnet,
+            chain_id,
+            exported_at: get_epoch_time_secs(),
+            txs,
+        }
+    }
+}
+
 impl FromRow<Txid> for Txid {
     fn from_row(row: &Row) -> Result<Txid, db_error> {
         row.get(0).map_err(db_error::SqliteError)
@@ -857,6 +983,33 @@ const MEMPOOL_SCHEMA_8_NONCE_SORTING: &'static [&'static str] = &[
     "#,
 ];
 
+const MEMPOOL_SCHEMA_9_REPLACEMENTS: &[&str] = &[
+    r#"
+    -- History of replaced transactions.
+    -- `origin_address` and `origin_nonce` belong to the replacement transaction.
+    CREATE TABLE IF NOT EXISTS mempool_replacements(
+        replaced_txid TEXT NOT NULL,
+        replacement_txid TEXT NOT NULL,
+        origin_address TEXT NOT NULL,
+        origin_nonce INTEGER NOT NULL,
+        replaced_fee INTEGER NOT NULL,
+        replacement_fee INTEGER NOT NULL,
+        reason TEXT NOT NULL,
+        replace_time INTEGER NOT NULL,
+        PRIMARY KEY (replaced_txid, replacement_txid)
+    );
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS by_replacement_origin ON mempool_replacements(origin_address, origin_nonce);
+    "#,
+    r#"
+    CREATE INDEX IF NOT EXISTS by_replacement_txid ON mempool_replacements(replacement_txid);
+    "#,
+    r#"
+    INSERT INTO schema_version (version) VALUES (9)
+    "#,
+];
+
 const MEMPOOL_INDEXES: &[&str] = &[
     "CREATE INDEX IF NOT EXISTS by_txid ON mempool(txid);",
     "CREATE INDEX IF NOT EXISTS by_height ON mempool(height);",
@@ -881,12 +1034,14 @@ pub struct MemPoolDB {
     metric: Box<dyn CostMetric>,
     pub blacklist_timeout: u64,
     pub blacklist_max_size: u64,
+    pub replace_policy: MemPoolReplacePolicy,
 }
 
 pub struct MemPoolTx<'a> {
     tx: DBTx<'a>,
     admitter: &'a mut MemPoolAdmitter,
     bloom_counter: Option<&'a mut BloomCounter<BloomNodeHasher>>,
+    replace_policy: &'a MemPoolReplacePolicy,
 }
 
 impl<'a> Deref for MemPoolTx<'a> {
@@ -907,11 +1062,13 @@ impl<'a> MemPoolTx<'a> {
         tx: DBTx<'a>,
         admitter: &'a mut MemPoolAdmitter,
         bloom_counter: &'a mut BloomCounter<BloomNodeHasher>,
+        replace_policy: &'a MemPoolReplacePolicy,
     ) -> MemPoolTx<'a> {
         MemPoolTx {
             tx,
             admitter,
             bloom_counter: Some(bloom_counter),
+            replace_policy,
         }
     }
 
@@ -967,8 +1124,8 @@ impl<'a> MemPoolTx<'a> {
         Ok(())
     }
 
-    /// Add the txid to the bloom counter in the mempool DB, optionally replacing a prior
-    /// transaction (identified by prior_txid) if the bloom counter is full.
+    /// Add the txid to the bloom counter in the mempool DB, optionally replacing prior
+    /// transactions (identified by prior_txids) if the bloom counter is full.
     /// If this is the first txid at this coinbase height, then also garbage-collect the bloom counter to remove no-longer-recent transactions.
     /// If the bloom counter is saturated -- i.e. it represents more than MAX_BLOOM_COUNTER_TXS
     /// transactions -- then pick another transaction to evict from the bloom filter and return its txid.
@@ -978,7 +1135,7 @@ impl<'a> MemPoolTx<'a> {
         &mut self,
         coinbase_height: u64,
         txid: &Txid,
-        prior_txid: Option<Txid>,
+        prior_txids: &[Txid],
     ) -> Result<Option<Txid>, MemPoolRejection> {
         // is this the first-ever txid at this coinbase height?
         let sql = "SELECT 1 FROM mempool WHERE height = ?1";
@@ -992,8 +1149,8 @@ impl<'a> MemPoolTx<'a> {
         }
 
         MemPoolTx::with_bloom_state(self, |ref mut dbtx, ref mut bloom_counter| {
-            // remove replaced transaction
-            if let Some(prior_txid) = prior_txid {
+            // remove replaced transactions
+            for prior_txid in prior_txids.iter() {
                 bloom_counter.remove_raw(dbtx, &prior_txid.0)?;
             }
 
@@ -1229,6 +1386,9 @@ impl MemPoolDB {
                     MemPoolDB::instantiate_schema_8(tx)?;
                 }
                 8 => {
+                    MemPoolDB::instantiate_schema_9(tx)?;
+                }
+                9 => {
                     break;
                 }
                 _ => {
@@ -1325,6 +1485,16 @@ impl MemPoolDB {
         Ok(())
     }
 
+    /// Add the transaction replacement history table
+    #[cfg_attr(test, mutants::skip)]
+    fn instantiate_schema_9(tx: &DBTx) -> Result<(), db_error> {
+        for sql_exec in MEMPOOL_SCHEMA_9_REPLACEMENTS {
+            tx.execute_batch(sql_exec)?;
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(test, mutants::skip)]
     pub fn db_path(chainstate_root_path: &str) -> Result<String, db_error> {
         let mut path = PathBuf::from(chainstate_root_path);
@@ -1387,6 +1557,7 @@ impl MemPoolDB {
             metric,
             blacklist_timeout: DEFAULT_BLACKLIST_TIMEOUT,
             blacklist_max_size: DEFAULT_BLACKLIST_MAX_SIZE,
+            replace_policy: MemPoolReplacePolicy::default(),
         })
     }
 
@@ -1544,6 +1715,124 @@ impl MemPoolDB {
         }
     }
 
+    /// Estimated cost of a mempool transaction, in the units of the mempool's cost metric.
+    /// If the cost estimator has given the transaction a fee rate, then the cost is recovered
+    /// from it.  Otherwise, the cost is estimated from the transaction's length.
+    fn estimate_candidate_cost(&self, fee_rate: Option<f64>, tx_fee: u64, len: u64) -> f64 {
+        match fee_rate {
+            Some(fee_rate) if fee_rate > 0.0 && tx_fee > 0 => tx_fee as f64 / fee_rate,
+            _ => self.metric.from_len(len).max(1) as f64,
+        }
+    }
+
+    /// Load the transactions for the `NonceChainPackageFeeRate` mempool walk strategy, grouped
+    /// into one chain per origin in nonce order, each with its fee and estimated cost.
+    ///
+    /// This is done once per mempool walk, and `next_nonce_chain_candidates()` then takes the
+    /// candidates of each pass from the chains, so that a walk does not re-read the mempool on
+    /// every pass.
+    fn load_nonce_chains(&self) -> Result<Vec<VecDeque<NonceChainTx>>, db_error> {
+        let sql = "
+            SELECT txid, origin_nonce, origin_address, sponsor_nonce, sponsor_address, fee_rate, tx_fee, length
+            FROM mempool AS m
+            LEFT JOIN nonces AS no ON m.origin_address = no.address
+            WHERE (no.address IS NULL OR m.origin_nonce >= no.nonce)
+                AND m.txid NOT IN (SELECT txid FROM considered_txs)
+            ORDER BY m.origin_address ASC, m.origin_nonce ASC
+            ";
+        let mut stmt = self.db.prepare(sql)?;
+        let mut rows = stmt.query(NO_PARAMS)?;
+
+        let mut chains: Vec<VecDeque<NonceChainTx>> = vec![];
+        while let Some(row) = rows.next()? {
+            let tx = MemPoolTxInfoPartial::from_row(row)?;
+            let tx_fee = u64::from_column(row, "tx_fee")?;
+            let len = u64::from_column(row, "length")?;
+            let cost = self.estimate_candidate_cost(tx.fee_rate, tx_fee, len);
+            match chains.last_mut() {
+                Some(chain) if chain[0].0.origin_address == tx.origin_address => {
+                    chain.push_back((tx, tx_fee, cost))
+                }
+                _ => chains.push(VecDeque::from([(tx, tx_fee, cost)])),
+            }
+        }
+        Ok(chains)
+    }
+
+    /// Take the candidates of the next pass of the `NonceChainPackageFeeRate` mempool walk
+    /// strategy from the chains loaded by `load_nonce_chains()`.
+    ///
+    /// Each origin's transactions with consecutive nonces, starting at the origin's next
+    /// expected nonce, form a package of at most `MAXIMUM_MEMPOOL_TX_CHAINING` transactions.
+    /// A package is ranked by the aggregate fee rate of its best-paying prefix (total fee over
+    /// total estimated cost), and only that prefix is returned -- the rest of the chain is
+    /// re-evaluated on the next pass, once the nonces have been updated.  Returned and
+    /// already-mined transactions are removed from the chains.
+    ///
+    /// Returns the candidates of all packages, in package rank order, and in nonce order within
+    /// each package.
+    fn next_nonce_chain_candidates<C: ClarityConnection>(
+        chains: &mut Vec<VecDeque<NonceChainTx>>,
+        clarity_tx: &mut C,
+        nonce_cache: &mut NonceCache,
+        nonce_conn: &mut DBConn,
+    ) -> VecDeque<MemPoolTxInfoPartial> {
+        let mut packages = vec![];
+        for chain in chains.iter_mut() {
+            let origin_address = chain[0].0.origin_address.clone();
+            let mut next_nonce = nonce_cache.get(&origin_address, clarity_tx, nonce_conn);
+            while chain
+                .front()
+                .is_some_and(|(tx, ..)| tx.origin_nonce < next_nonce)
+            {
+                // already mined
+                chain.pop_front();
+            }
+            let mut sponsor_nonces = HashMap::new();
+            let mut total_fee = 0.0;
+            let mut total_cost = 0.0;
+            let mut best_fee_rate = None;
+            let mut best_len = 0;
+            for (package_len, (tx, tx_fee, cost)) in chain.iter().enumerate() {
+                if tx.origin_nonce > next_nonce || package_len as u64 >= MAXIMUM_MEMPOOL_TX_CHAINING
+                {
+                    break;
+                }
+                if tx.sponsor_address != tx.origin_address {
+                    let expected_sponsor_nonce = match sponsor_nonces.get(&tx.sponsor_address) {
+                        Some(nonce) => *nonce,
+                        None => nonce_cache.get(&tx.sponsor_address, clarity_tx, nonce_conn),
+                    };
+                    if tx.sponsor_nonce != expected_sponsor_nonce {
+                        break;
+                    }
+                    sponsor_nonces.insert(tx.sponsor_address.clone(), expected_sponsor_nonce + 1);
+                }
+
+                total_fee += *tx_fee as f64;
+                total_cost += cost;
+                let fee_rate = total_fee / total_cost;
+                if best_fee_rate.map_or(true, |best| fee_rate >= best) {
+                    best_fee_rate = Some(fee_rate);
+                    best_len = package_len + 1;
+                }
+                next_nonce += 1;
+            }
+            let Some(fee_rate) = best_fee_rate else {
+                continue;
+            };
+            let package: Vec<_> = chain.drain(..best_len).map(|(tx, ..)| tx).collect();
+            packages.push((fee_rate, package));
+        }
+        chains.retain(|chain| !chain.is_empty());
+
+        packages.sort_by(|(rate_a, _), (rate_b, _)| rate_b.total_cmp(rate_a));
+        packages
+            .into_iter()
+            .flat_map(|(_, package)| package.into_iter())
+            .collect()
+    }
+
     /// Iterate over candidates in the mempool
     /// `todo` will be called once for each transaction that is a valid
     /// candidate for inclusion in the next block, meaning its origin and
@@ -1626,13 +1915,32 @@ impl MemPoolDB {
         // The `GlobalFeeRate` strategy includes all transactions, so we just
         // query once and walk the full mempool in the inner loop.
         //
-        // The `NextNonceWithHighestFeeRate` strategy only selects transactions
-        // that have the next expected nonce, so we need to re-query the
+        // The `NextNonceWithHighestFeeRate` and `NonceChainPackageFeeRate`
+        // strategies only select transactions that have the next expected
+        // nonce (or that directly follow one), so we need to re-query the
         // mempool after one batch has been processed and the nonce table has
         // been updated. This is handled in the outer loop.
+        let mut nonce_chains = if settings.strategy == MemPoolWalkStrategy::NonceChainPackageFeeRate
+        {
+            self.load_nonce_chains()?
+        } else {
+            vec![]
+        };
+
         let stop_reason = loop {
             let mut state_changed = false;
 
+            // == Candidates for `NonceChainPackageFeeRate` mempool walk strategy
+            //
+            // Ranks each origin's chain of pending transactions by its aggregate fee rate, so a
+            // low-fee transaction that blocks a high-fee one is considered along with it.
+            let mut package_candidates = Self::next_nonce_chain_candidates(
+                &mut nonce_chains,
+                clarity_tx,
+                &mut nonce_cache,
+                &mut nonce_conn,
+            );
+
             // == Query for `NextNonceWithHighestFeeRate` mempool walk strategy
             //
             // Selects the next mempool transaction to consider using a heuristic that maximizes miner fee profitability and minimizes
@@ -1759,6 +2067,20 @@ impl MemPoolDB {
                             }
                         }
                     }
+                    MemPoolWalkStrategy::NonceChainPackageFeeRate => {
+                        match package_candidates.pop_front() {
+                            Some(tx) => {
+                                let update_estimate = tx.fee_rate.is_none();
+                                (tx, update_estimate)
+                            }
+                            None => {
+                                monitoring::increment_miner_stop_reason(
+                                    monitoring::MinerStopReason::NoTransactions,
+                                );
+                                break MempoolIterationStopReason::NoMoreCandidates;
+                            }
+                        }
+                    }
                 };
 
                 state_changed = true;
@@ -1923,11 +2245,11 @@ impl MemPoolDB {
 
             // If we've reached the end of the mempool, or if we've stopped
             // iterating for some other reason, break out of the loop. In the
-            // case of `NextNonceWithHighestFeeRate` we know we've reached the
-            // end of the mempool if the state has not changed. In the case of
-            // `GlobalFeeRate` we know we've reached the end of the mempool if
-            // the stop reason is `NoMoreCandidates`.
-            if settings.strategy != MemPoolWalkStrategy::NextNonceWithHighestFeeRate
+            // case of `NextNonceWithHighestFeeRate` and `NonceChainPackageFeeRate`
+            // we know we've reached the end of the mempool if the state has not
+            // changed. In the case of `GlobalFeeRate` we know we've reached the
+            // end of the mempool if the stop reason is `NoMoreCandidates`.
+            if settings.strategy == MemPoolWalkStrategy::GlobalFeeRate
                 || stop_reason != MempoolIterationStopReason::NoMoreCandidates
                 || !state_changed
             {
@@ -1976,6 +2298,7 @@ impl MemPoolDB {
             tx,
             &mut self.admitter,
             &mut self.bloom_counter,
+            &self.replace_policy,
         ))
     }
 
@@ -1996,6 +2319,33 @@ impl MemPoolDB {
         Ok(rows)
     }
 
+    /// Get every transaction in the mempool as snapshot entries, in the order in which they
+    /// were accepted
+    pub fn export_snapshot_txs(conn: &DBConn) -> Result<Vec<MemPoolSnapshotTx>, db_error> {
+        let sql =
+            "SELECT * FROM mempool ORDER BY accept_time ASC, origin_address ASC, origin_nonce ASC";
+        let mut stmt = conn.prepare(sql)?;
+        let mut rows = stmt.query(NO_PARAMS)?;
+        let mut txs = vec![];
+        while let Some(row) = rows.next()? {
+            let tx_info = MemPoolTxInfo::from_row(row)?;
+            let fee_rate: Option<f64> = row.get("fee_rate")?;
+            let md = tx_info.metadata;
+            txs.push(MemPoolSnapshotTx {
+                txid: md.txid,
+                tx: to_hex(&tx_info.tx.serialize_to_vec()),
+                tx_fee: md.tx_fee,
+                origin_address: md.origin_address.to_string(),
+                origin_nonce: md.origin_nonce,
+                sponsor_address: md.sponsor_address.to_string(),
+                sponsor_nonce: md.sponsor_nonce,
+                accept_time: md.accept_time,
+                fee_rate,
+            });
+        }
+        Ok(txs)
+    }
+
     /// Get all transactions at a specific block
     #[cfg(test)]
     pub fn get_num_tx_at_block(
@@ -2078,8 +2428,8 @@ impl MemPoolDB {
         }
     }
 
-    /// Add a transaction to the mempool.  If it already exists, then replace it if the given fee
-    /// is higher than the one that's already there.
+    /// Add a transaction to the mempool.  If it already exists, then replace it if the mempool's
+    /// replace policy allows it (see `MemPoolReplacePolicy`).
     /// Carry out the mempool admission test before adding.
     ///
     /// `tip_consensus_hash`, `tip_block_header_hash`, and `coinbase_height` describe the fork that
@@ -2143,71 +2493,44 @@ impl MemPoolDB {
         };
 
         // do we already have txs with either the same origin nonce or sponsor nonce ?
-        let prior_tx = {
-            match MemPoolDB::get_tx_metadata_by_address(tx, true, origin_address, origin_nonce)? {
-                Some(prior_tx) => Some(prior_tx),
-                None => MemPoolDB::get_tx_metadata_by_address(
-                    tx,
-                    false,
-                    sponsor_address,
-                    sponsor_nonce,
-                )?,
+        // If the replace policy is sponsor-aware, then we need both of them.
+        let mut prior_txs = vec![];
+        if let Some(prior_tx) =
+            MemPoolDB::get_tx_metadata_by_address(tx, true, origin_address, origin_nonce)?
+        {
+            prior_txs.push(prior_tx);
+        }
+        if prior_txs.is_empty() || tx.replace_policy.sponsor_aware {
+            if let Some(prior_tx) =
+                MemPoolDB::get_tx_metadata_by_address(tx, false, sponsor_address, sponsor_nonce)?
+            {
+                if !prior_txs.iter().any(|p| p.txid == prior_tx.txid) {
+                    prior_txs.push(prior_tx);
+                }
             }
-        };
-
-        let mut replace_reason = MemPoolDropReason::REPLACE_BY_FEE;
+        }
 
         // if so, is this a replace-by-fee? or a replace-in-chain-tip?
-        let add_tx = if let Some(ref prior_tx) = prior_tx {
-            if tx_fee > prior_tx.tx_fee {
-                // is this a replace-by-fee ?
-                debug!(
-                    "Can replace {} with {} for {},{} by fee ({} < {})",
-                    &prior_tx.txid, &txid, origin_address, origin_nonce, &prior_tx.tx_fee, &tx_fee
-                );
-                replace_reason = MemPoolDropReason::REPLACE_BY_FEE;
-                true
-            } else if !MemPoolDB::are_blocks_in_same_fork(
+        let mut replace_reasons = Vec::with_capacity(prior_txs.len());
+        for prior_tx in prior_txs.iter() {
+            let replace_reason = MemPoolDB::check_replace_tx(
+                tx,
                 chainstate,
-                &prior_tx.tenure_consensus_hash,
-                &prior_tx.tenure_block_header_hash,
+                prior_tx,
+                &txid,
+                tx_fee,
                 &consensus_hash,
                 &block_header_hash,
-            )? {
-                // is this a replace-across-fork ?
-                debug!(
-                    "Can replace {} with {} for {},{} across fork",
-                    &prior_tx.txid, &txid, origin_address, origin_nonce
-                );
-                replace_reason = MemPoolDropReason::REPLACE_ACROSS_FORK;
-                true
-            } else {
-                // there's a >= fee tx in this fork, cannot add
-                info!("TX conflicts with sponsor/origin nonce in same fork with >= fee";
-                      "new_txid" => %txid,
-                      "old_txid" => %prior_tx.txid,
-                      "origin_addr" => %origin_address,
-                      "origin_nonce" => origin_nonce,
-                      "sponsor_addr" => %sponsor_address,
-                      "sponsor_nonce" => sponsor_nonce,
-                      "new_fee" => tx_fee,
-                      "old_fee" => prior_tx.tx_fee);
-                false
-            }
-        } else {
-            // no conflicting TX with this origin/sponsor, go ahead and add
-            true
-        };
-
-        if !add_tx {
-            return Err(MemPoolRejection::ConflictingNonceInMempool);
+                origin_address,
+                origin_nonce,
+                sponsor_address,
+                sponsor_nonce,
+            )?;
+            replace_reasons.push(replace_reason);
         }
 
-        tx.update_bloom_counter(
-            coinbase_height,
-            &txid,
-            prior_tx.as_ref().map(|tx| tx.txid.clone()),
-        )?;
+        let prior_txids: Vec<_> = prior_txs.iter().map(|tx| tx.txid.clone()).collect();
+        tx.update_bloom_counter(coinbase_height, &txid, &prior_txids)?;
 
         let sql = "INSERT OR REPLACE INTO mempool (
             txid,
@@ -2244,14 +2567,160 @@ impl MemPoolDB {
 
         tx.update_mempool_pager(&txid)?;
 
-        // broadcast drop event if a tx is being replaced
-        if let (Some(prior_tx), Some(event_observer)) = (prior_tx, event_observer) {
-            event_observer.mempool_txs_dropped(vec![prior_tx.txid], Some(txid), replace_reason);
-        };
+        let replace_time = get_epoch_time_secs();
+        for (prior_tx, replace_reason) in prior_txs.into_iter().zip(replace_reasons.into_iter()) {
+            let sql = "INSERT OR REPLACE INTO mempool_replacements (
+                replaced_txid,
+                replacement_txid,
+                origin_address,
+                origin_nonce,
+                replaced_fee,
+                replacement_fee,
+                reason,
+                replace_time)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)";
+            let args = params![
+                prior_tx.txid,
+                txid,
+                origin_address.to_string(),
+                u64_to_sql(origin_nonce)?,
+                u64_to_sql(prior_tx.tx_fee)?,
+                u64_to_sql(tx_fee)?,
+                replace_reason.to_string(),
+                u64_to_sql(replace_time)?,
+            ];
+            tx.execute(sql, args)
+                .map_err(|e| MemPoolRejection::DBError(db_error::SqliteError(e)))?;
+
+            // broadcast drop event if a tx is being replaced
+            if let Some(event_observer) = event_observer {
+                event_observer.mempool_txs_dropped(
+                    vec![prior_tx.txid],
+                    Some(txid.clone()),
+                    replace_reason,
+                );
+            }
+        }
 
         Ok(())
     }
 
+    /// Decide whether or not a new transaction can replace `prior_tx`, which spends the same
+    /// origin or sponsor nonce, according to the mempool's replace policy.
+    /// Returns the reason for the replacement if it is allowed.
+    fn check_replace_tx(
+        tx: &MemPoolTx,
+        chainstate: &mut StacksChainState,
+        prior_tx: &MemPoolTxMetadata,
+        txid: &Txid,
+        tx_fee: u64,
+        consensus_hash: &ConsensusHash,
+        block_header_hash: &BlockHeaderHash,
+        origin_address: &StacksAddress,
+        origin_nonce: u64,
+        sponsor_address: &StacksAddress,
+        sponsor_nonce: u64,
+    ) -> Result<MemPoolDropReason, MemPoolRejection> {
+        let policy = tx.replace_policy;
+        let min_fee = policy.min_replacement_fee(prior_tx.tx_fee);
+        let fee_bumped = tx_fee >= min_fee;
+        let num_replacements = if fee_bumped && policy.max_replacements.is_some() {
+            MemPoolDB::get_num_replacements_by_fee(tx, origin_address, origin_nonce)?
+        } else {
+            0
+        };
+        let under_limit = policy
+            .max_replacements
+            .map(|max_replacements| num_replacements < max_replacements)
+            .unwrap_or(true);
+
+        if fee_bumped && under_limit {
+            // is this a replace-by-fee ?
+            debug!(
+                "Can replace {} with {} for {},{} by fee ({} < {})",
+                &prior_tx.txid, txid, origin_address, origin_nonce, &prior_tx.tx_fee, &tx_fee
+            );
+            return Ok(MemPoolDropReason::REPLACE_BY_FEE);
+        }
+
+        if !MemPoolDB::are_blocks_in_same_fork(
+            chainstate,
+            &prior_tx.tenure_consensus_hash,
+            &prior_tx.tenure_block_header_hash,
+            consensus_hash,
+            block_header_hash,
+        )? {
+            // is this a replace-across-fork ?
+            debug!(
+                "Can replace {} with {} for {},{} across fork",
+                &prior_tx.txid, txid, origin_address, origin_nonce
+            );
+            return Ok(MemPoolDropReason::REPLACE_ACROSS_FORK);
+        }
+
+        // there's a conflicting tx in this fork that we can't replace
+        info!("TX conflicts with sponsor/origin nonce in same fork";
+              "new_txid" => %txid,
+              "old_txid" => %prior_tx.txid,
+              "origin_addr" => %origin_address,
+              "origin_nonce" => origin_nonce,
+              "sponsor_addr" => %sponsor_address,
+              "sponsor_nonce" => sponsor_nonce,
+              "new_fee" => tx_fee,
+              "old_fee" => prior_tx.tx_fee,
+              "min_replacement_fee" => min_fee,
+              "num_replacements" => num_replacements);
+
+        if fee_bumped {
+            Err(MemPoolRejection::TooManyReplacements(num_replacements))
+        } else if tx_fee > prior_tx.tx_fee {
+            Err(MemPoolRejection::ReplacementFeeTooLow(tx_fee, min_fee))
+        } else {
+            Err(MemPoolRejection::ConflictingNonceInMempool)
+        }
+    }
+
+    /// How many times has the transaction with this origin address and nonce been replaced by
+    /// fee?
+    fn get_num_replacements_by_fee(
+        conn: &DBConn,
+        origin_address: &StacksAddress,
+        origin_nonce: u64,
+    ) -> Result<u64, db_error> {
+        let sql = "SELECT COUNT(*) FROM mempool_replacements WHERE origin_address = ?1 AND origin_nonce = ?2 AND reason = ?3";
+        let args = params![
+            origin_address.to_string(),
+            u64_to_sql(origin_nonce)?,
+            MemPoolDropReason::REPLACE_BY_FEE.to_string(),
+        ];
+        query_int(conn, sql, args).map(|count| u64::try_from(count).unwrap_or(0))
+    }
+
+    /// Get the replacement history of the (origin address, nonce) that the given transaction
+    /// spends, oldest first.  The transaction can be the one currently in the mempool, or any
+    /// transaction it replaced.
+    /// Returns None if the mempool knows nothing about this transaction.
+    pub fn get_tx_replacements(
+        conn: &DBConn,
+        txid: &Txid,
+    ) -> Result<Option<(StacksAddress, u64, Vec<MemPoolReplacement>)>, db_error> {
+        let origin = match MemPoolDB::get_tx(conn, txid)? {
+            Some(txinfo) => Some((txinfo.metadata.origin_address, txinfo.metadata.origin_nonce)),
+            None => {
+                let sql = "SELECT * FROM mempool_replacements WHERE replaced_txid = ?1 OR replacement_txid = ?1 LIMIT 1";
+                let replacement: Option<MemPoolReplacement> = query_row(conn, sql, params![txid])?;
+                replacement.map(|r| (r.origin_address, r.origin_nonce))
+            }
+        };
+        let Some((origin_address, origin_nonce)) = origin else {
+            return Ok(None);
+        };
+        let sql = "SELECT * FROM mempool_replacements WHERE origin_address = ?1 AND origin_nonce = ?2 ORDER BY replace_time ASC, rowid ASC";
+        let args = params![origin_address.to_string(), u64_to_sql(origin_nonce)?];
+        let replacements = query_rows(conn, sql, args)?;
+        Ok(Some((origin_address, origin_nonce, replacements)))
+    }
+
     /// Garbage-collect the mempool according to the behavior specified in `behavior`.
     pub fn garbage_collect(
         &mut self,
@@ -2298,6 +2767,7 @@ impl MemPoolDB {
         let sql = "DELETE FROM mempool WHERE accept_time < ?1";
 
         tx.execute(sql, args)?;
+        Self::prune_replacements(tx)?;
         increment_stx_mempool_gc();
         Ok(())
     }
@@ -2320,10 +2790,22 @@ impl MemPoolDB {
         let sql = "DELETE FROM mempool WHERE height < ?1";
 
         tx.execute(sql, args)?;
+        Self::prune_replacements(tx)?;
         increment_stx_mempool_gc();
         Ok(())
     }
 
+    /// Forget the replacement history of each (origin address, nonce) that no longer has a
+    /// transaction in the mempool.
+    fn prune_replacements(tx: &MemPoolTx) -> Result<(), db_error> {
+        let sql = "DELETE FROM mempool_replacements WHERE NOT EXISTS (
+            SELECT 1 FROM mempool
+            WHERE mempool.origin_address = mempool_replacements.origin_address
+            AND mempool.origin_nonce = mempool_replacements.origin_nonce)";
+        tx.execute(sql, NO_PARAMS)?;
+        Ok(())
+    }
+
     #[cfg(test)]
     pub fn clear_before_coinbase_height(
         &mut self,
@@ -2528,6 +3010,80 @@ impl MemPoolDB {
         Ok(())
     }
 
+    /// Restore a transaction from a mempool snapshot.
+    ///
+    /// The transaction goes through the same admission checks as a newly-received transaction,
+    /// evaluated against the given chain tip.  If it is admitted, then it keeps the snapshot's
+    /// accept time, as well as the snapshot's fee rate estimate (if there is one; otherwise, the
+    /// fee rate is estimated locally).
+    ///
+    /// Returns `Ok(false)` if the transaction is already in the mempool.
+    pub fn import_snapshot_tx(
+        &mut self,
+        chainstate: &mut StacksChainState,
+        sortdb: &SortitionDB,
+        consensus_hash: &ConsensusHash,
+        block_hash: &BlockHeaderHash,
+        snapshot_tx: &MemPoolSnapshotTx,
+        block_limit: &ExecutionCost,
+        stacks_epoch_id: &StacksEpochId,
+    ) -> Result<bool, MemPoolRejection> {
+        let tx_bytes = hex_bytes(&snapshot_tx.tx)
+            .map_err(|e| MemPoolRejection::Other(format!("Invalid transaction hex: {e}")))?;
+        let tx = StacksTransaction::consensus_deserialize(&mut &tx_bytes[..])
+            .map_err(MemPoolRejection::DeserializationFailure)?;
+        let txid = tx.txid();
+        if txid != snapshot_tx.txid {
+            return Err(MemPoolRejection::Other(format!(
+                "Transaction ID mismatch: snapshot has {}, but transaction is {txid}",
+                &snapshot_tx.txid
+            )));
+        }
+
+        if MemPoolDB::db_has_tx(self.conn(), &txid)? {
+            return Ok(false);
+        }
+        if self.is_tx_blacklisted(&txid)? {
+            return Err(MemPoolRejection::TemporarilyBlacklisted);
+        }
+
+        let fee_rate = match snapshot_tx.fee_rate {
+            Some(fee_rate) => Some(fee_rate),
+            None => match cost_estimates::estimate_fee_rate(
+                &tx,
+                self.cost_estimator.as_ref(),
+                self.metric.as_ref(),
+                block_limit,
+                stacks_epoch_id,
+            ) {
+                Ok(x) => Some(x),
+                Err(EstimatorError::NoEstimateAvailable) => None,
+                Err(e) => return Err(MemPoolRejection::EstimatorError(e)),
+            },
+        };
+
+        let mut mempool_tx = self.tx_begin().map_err(MemPoolRejection::DBError)?;
+        MemPoolDB::tx_submit(
+            &mut mempool_tx,
+            chainstate,
+            sortdb,
+            consensus_hash,
+            block_hash,
+            &tx,
+            true,
+            None,
+            fee_rate,
+        )?;
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET accept_time = ? WHERE txid = ?",
+                params![u64_to_sql(snapshot_tx.accept_time)?, txid],
+            )
+            .map_err(db_error::from)?;
+        mempool_tx.commit().map_err(MemPoolRejection::DBError)?;
+        Ok(true)
+    }
+
     /// Directly submit to the mempool, and don't do any admissions checks.
     #[cfg(any(test, feature = "testing"))]
     pub fn submit_raw(
@@ -54,11 +54,13 @@ use crate::chainstate::stacks::{
     TransactionSpendingCondition, TransactionVersion,
 };
 use crate::core::mempool::{
-    db_get_all_nonces, MemPoolSyncData, MemPoolWalkSettings, MemPoolWalkTxTypes, TxTag,
-    BLOOM_COUNTER_DEPTH, BLOOM_COUNTER_ERROR_RATE, MAX_BLOOM_COUNTER_TXS,
+    db_get_all_nonces, MemPoolReplacePolicy, MemPoolSnapshot, MemPoolSnapshotTx, MemPoolSyncData,
+    MemPoolTx, MemPoolWalkSettings, MemPoolWalkTxTypes, TxTag, BLOOM_COUNTER_DEPTH,
+    BLOOM_COUNTER_ERROR_RATE, MAX_BLOOM_COUNTER_TXS, MEMPOOL_SNAPSHOT_VERSION,
 };
 use crate::core::test_util::{insert_tx_in_mempool, make_stacks_transfer_serialized, to_addr};
 use crate::core::{FIRST_BURNCHAIN_CONSENSUS_HASH, FIRST_STACKS_BLOCK_HASH};
+use crate::net::test::{TestPeer, TestPeerConfig};
 use crate::util_lib::bloom::test::setup_bloom_counter;
 use crate::util_lib::bloom::*;
 use crate::util_lib::db::tx_begin_immediate;
@@ -1745,6 +1747,219 @@ fn mempool_db_test_rbf() {
     assert_eq!(tx_info.metadata.tx_fee, 124);
 }
 
+#[test]
+fn mempool_db_test_rbf_policy() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    mempool.replace_policy = MemPoolReplacePolicy {
+        min_fee_bump: 10,
+        min_fee_bump_percent: 20,
+        max_replacements: Some(1),
+        sponsor_aware: false,
+    };
+    assert_eq!(mempool.replace_policy.min_replacement_fee(10), 20);
+    assert_eq!(mempool.replace_policy.min_replacement_fee(1000), 1200);
+
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    let spending_condition = TransactionSpendingCondition::Singlesig(SinglesigSpendingCondition {
+        signer: Hash160([0x11; 20]),
+        hash_mode: SinglesigHashMode::P2PKH,
+        key_encoding: TransactionPublicKeyEncoding::Uncompressed,
+        nonce: 123,
+        tx_fee: 456,
+        signature: MessageSignature::from_raw(&[0xff; 65]),
+    });
+    let stx_address = StacksAddress::new(1, Hash160([0xff; 20])).unwrap();
+    let mut tx = StacksTransaction {
+        version: TransactionVersion::Testnet,
+        chain_id: 0x80000000,
+        auth: TransactionAuth::Standard(spending_condition),
+        anchor_mode: TransactionAnchorMode::Any,
+        post_condition_mode: TransactionPostConditionMode::Allow,
+        post_conditions: Vec::new(),
+        payload: TransactionPayload::TokenTransfer(
+            stx_address.into(),
+            123,
+            TokenTransferMemo([0u8; 34]),
+        ),
+    };
+
+    let origin_address = StacksAddress::new(22, Hash160([0x01; 20])).unwrap();
+    let origin_nonce = tx.get_origin_nonce();
+
+    let mut try_add_with_fee = |mempool_tx: &mut MemPoolTx, fee: u64| {
+        tx.set_tx_fee(fee);
+        let txid = tx.txid();
+        let res = MemPoolDB::try_add_tx(
+            mempool_tx,
+            &mut chainstate,
+            &ConsensusHash([0x1; 20]),
+            &BlockHeaderHash([0x2; 32]),
+            false, // don't resolve the above chain tip since it doesn't exist
+            txid.clone(),
+            tx.serialize_to_vec(),
+            fee,
+            100,
+            &origin_address,
+            origin_nonce,
+            &origin_address,
+            origin_nonce,
+            None,
+        );
+        (txid, res)
+    };
+
+    let (first_txid, res) = try_add_with_fee(&mut mempool_tx, 1000);
+    res.unwrap();
+    assert_eq!(
+        MemPoolDB::get_tx_replacements(&mempool_tx, &first_txid)
+            .unwrap()
+            .unwrap(),
+        (origin_address.clone(), origin_nonce, vec![])
+    );
+
+    // higher fee, but not by enough
+    let (txid, res) = try_add_with_fee(&mut mempool_tx, 1199);
+    match res.unwrap_err() {
+        MemPoolRejection::ReplacementFeeTooLow(1199, 1200) => {}
+        e => panic!("Failed: {e:?}"),
+    };
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &txid).unwrap());
+
+    // not higher at all
+    let (_, res) = try_add_with_fee(&mut mempool_tx, 999);
+    match res.unwrap_err() {
+        MemPoolRejection::ConflictingNonceInMempool => {}
+        e => panic!("Failed: {e:?}"),
+    };
+
+    // enough of a bump
+    let (second_txid, res) = try_add_with_fee(&mut mempool_tx, 1200);
+    res.unwrap();
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &first_txid).unwrap());
+    assert!(MemPoolDB::db_has_tx(&mempool_tx, &second_txid).unwrap());
+
+    // only one replacement allowed
+    let (txid, res) = try_add_with_fee(&mut mempool_tx, 5000);
+    match res.unwrap_err() {
+        MemPoolRejection::TooManyReplacements(1) => {}
+        e => panic!("Failed: {e:?}"),
+    };
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &txid).unwrap());
+
+    // the history can be found from either transaction
+    for txid in [&first_txid, &second_txid] {
+        let (address, nonce, replacements) = MemPoolDB::get_tx_replacements(&mempool_tx, txid)
+            .unwrap()
+            .unwrap();
+        assert_eq!(address, origin_address);
+        assert_eq!(nonce, origin_nonce);
+        assert_eq!(replacements.len(), 1);
+        assert_eq!(replacements[0].replaced_txid, first_txid);
+        assert_eq!(replacements[0].replacement_txid, second_txid);
+        assert_eq!(replacements[0].replaced_fee, 1000);
+        assert_eq!(replacements[0].replacement_fee, 1200);
+        assert_eq!(replacements[0].reason, "ReplaceByFee");
+    }
+    assert!(
+        MemPoolDB::get_tx_replacements(&mempool_tx, &Txid([0x00; 32]))
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[test]
+fn mempool_db_test_rbf_sponsor_aware() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    mempool.replace_policy.sponsor_aware = true;
+
+    let mut txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+        StacksEpochId::latest(),
+    );
+
+    let origin_address = StacksAddress::new(22, Hash160([0x01; 20])).unwrap();
+    let other_origin_address = StacksAddress::new(22, Hash160([0x02; 20])).unwrap();
+    let sponsor_address = StacksAddress::new(22, Hash160([0x03; 20])).unwrap();
+    let other_sponsor_address = StacksAddress::new(22, Hash160([0x04; 20])).unwrap();
+
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    let mut try_add =
+        |mempool_tx: &mut MemPoolTx, fee: u64, origin: &StacksAddress, sponsor: &StacksAddress| {
+            let mut tx = txs.pop().unwrap();
+            tx.set_tx_fee(fee);
+            let txid = tx.txid();
+            let res = MemPoolDB::try_add_tx(
+                mempool_tx,
+                &mut chainstate,
+                &ConsensusHash([0x1; 20]),
+                &BlockHeaderHash([0x2; 32]),
+                false, // don't resolve the above chain tip since it doesn't exist
+                txid.clone(),
+                tx.serialize_to_vec(),
+                fee,
+                100,
+                origin,
+                1,
+                sponsor,
+                1,
+                None,
+            );
+            (txid, res)
+        };
+
+    // one tx conflicts by origin nonce, the other by sponsor nonce
+    let (origin_txid, res) = try_add(
+        &mut mempool_tx,
+        100,
+        &origin_address,
+        &other_sponsor_address,
+    );
+    res.unwrap();
+    let (sponsor_txid, res) = try_add(
+        &mut mempool_tx,
+        200,
+        &other_origin_address,
+        &sponsor_address,
+    );
+    res.unwrap();
+
+    // out-bids the origin conflict, but not the sponsor conflict
+    let (txid, res) = try_add(&mut mempool_tx, 150, &origin_address, &sponsor_address);
+    match res.unwrap_err() {
+        MemPoolRejection::ConflictingNonceInMempool => {}
+        e => panic!("Failed: {e:?}"),
+    };
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &txid).unwrap());
+    assert!(MemPoolDB::db_has_tx(&mempool_tx, &origin_txid).unwrap());
+    assert!(MemPoolDB::db_has_tx(&mempool_tx, &sponsor_txid).unwrap());
+
+    // out-bids both, and replaces both
+    let (txid, res) = try_add(&mut mempool_tx, 201, &origin_address, &sponsor_address);
+    res.unwrap();
+    assert!(MemPoolDB::db_has_tx(&mempool_tx, &txid).unwrap());
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &origin_txid).unwrap());
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &sponsor_txid).unwrap());
+
+    let (_, _, replacements) = MemPoolDB::get_tx_replacements(&mempool_tx, &txid)
+        .unwrap()
+        .unwrap();
+    let replaced: HashSet<_> = replacements
+        .iter()
+        .map(|r| (r.replaced_txid.clone(), r.replaced_fee))
+        .collect();
+    assert_eq!(
+        replaced,
+        HashSet::from([(origin_txid, 100), (sponsor_txid, 200)])
+    );
+}
+
 #[test]
 fn test_add_txs_bloom_filter() {
     let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
@@ -2763,6 +2978,271 @@ fn test_filter_txs_by_type() {
     );
 }
 
+#[test]
+/// This test verifies that the `NonceChainPackageFeeRate` strategy lets a high-fee transaction
+/// pay for the low-fee transaction ahead of it in its origin's nonce chain.
+fn test_iterate_candidates_nonce_chain_packages() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    let b = make_block(
+        &mut chainstate,
+        ConsensusHash([0x2; 20]),
+        &(
+            FIRST_BURNCHAIN_CONSENSUS_HASH.clone(),
+            FIRST_STACKS_BLOCK_HASH.clone(),
+        ),
+        2,
+        2,
+    );
+    let recipient = PrincipalData::from(StacksAddress::burn_address(false));
+
+    // `a` has a low-fee transaction blocking a high-fee one, `b` has a single medium-fee
+    // transaction, and `c` has a medium-fee transaction followed by a low-fee one.
+    let sender_a = StacksPrivateKey::random();
+    let sender_b = StacksPrivateKey::random();
+    let sender_c = StacksPrivateKey::random();
+    let txs = [
+        (&sender_a, 0, 200),
+        (&sender_a, 1, 20_000),
+        (&sender_b, 0, 2_000),
+        (&sender_c, 0, 1_000),
+        (&sender_c, 1, 180),
+    ];
+
+    let mempool_tx = mempool.tx_begin().unwrap();
+    let mut txids = vec![];
+    for (sender_sk, nonce, fee) in txs.iter() {
+        let transfer_tx =
+            make_stacks_transfer_serialized(sender_sk, *nonce, *fee, 0x80000000, &recipient, 1);
+        let txid = StacksTransaction::consensus_deserialize(&mut &transfer_tx[..])
+            .unwrap()
+            .txid();
+        insert_tx_in_mempool(
+            &mempool_tx,
+            transfer_tx,
+            &to_addr(sender_sk),
+            *nonce,
+            *fee,
+            &ConsensusHash([0x2; 20]),
+            &FIRST_STACKS_BLOCK_HASH,
+            10,
+        );
+        txids.push(txid);
+    }
+    mempool_tx.commit().unwrap();
+
+    let mut mempool_settings = MemPoolWalkSettings::default();
+    mempool_settings.strategy = MemPoolWalkStrategy::NonceChainPackageFeeRate;
+    let mut tx_events = Vec::new();
+
+    chainstate.with_read_only_clarity_tx(
+        &TEST_BURN_STATE_DB,
+        &StacksBlockHeader::make_index_block_hash(&b.0, &b.1),
+        |clarity_conn| {
+            let mut considered_txids = vec![];
+            mempool
+                .iterate_candidates::<_, ChainstateError, _>(
+                    clarity_conn,
+                    &mut tx_events,
+                    mempool_settings.clone(),
+                    |_, available_tx, _| {
+                        considered_txids.push(available_tx.tx.tx.txid());
+                        Ok(Some(
+                            // Generate any success result
+                            TransactionResult::success(
+                                &available_tx.tx.tx,
+                                StacksTransactionReceipt::from_stx_transfer(
+                                    available_tx.tx.tx.clone(),
+                                    vec![],
+                                    Value::okay(Value::Bool(true)).unwrap(),
+                                    ExecutionCost::ZERO,
+                                ),
+                            )
+                            .convert_to_event(),
+                        ))
+                    },
+                )
+                .unwrap();
+
+            // `a`'s package goes first, and `c`'s low-fee transaction is not part of its
+            // package, so it goes last.
+            assert_eq!(considered_txids, txids);
+        },
+    );
+}
+
+#[test]
+fn test_export_snapshot_txs() {
+    let _chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    let recipient = PrincipalData::from(StacksAddress::burn_address(false));
+    let sender_sk = StacksPrivateKey::random();
+    let sender_addr = to_addr(&sender_sk);
+
+    let mempool_tx = mempool.tx_begin().unwrap();
+    let mut expected = vec![];
+    for nonce in 0..3 {
+        let fee = 1000 * (nonce + 1);
+        let transfer_tx =
+            make_stacks_transfer_serialized(&sender_sk, nonce, fee, 0x80000000, &recipient, 1);
+        let tx = StacksTransaction::consensus_deserialize(&mut &transfer_tx[..]).unwrap();
+        insert_tx_in_mempool(
+            &mempool_tx,
+            transfer_tx.clone(),
+            &sender_addr,
+            nonce,
+            fee,
+            &ConsensusHash([0x2; 20]),
+            &FIRST_STACKS_BLOCK_HASH,
+            10,
+        );
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET accept_time = ?1, fee_rate = ?2 WHERE txid = ?3",
+                params![100 - nonce, (nonce > 0).then_some(1.5), tx.txid()],
+            )
+            .unwrap();
+        expected.push(MemPoolSnapshotTx {
+            txid: tx.txid(),
+            tx: to_hex(&transfer_tx),
+            tx_fee: fee,
+            origin_address: sender_addr.to_string(),
+            origin_nonce: nonce,
+            sponsor_address: sender_addr.to_string(),
+            sponsor_nonce: nonce,
+            accept_time: 100 - nonce,
+            fee_rate: (nonce > 0).then_some(1.5),
+        });
+    }
+    mempool_tx.commit().unwrap();
+
+    // ordered by accept time
+    expected.reverse();
+    let txs = MemPoolDB::export_snapshot_txs(mempool.conn()).unwrap();
+    assert_eq!(txs, expected);
+
+    let snapshot = MemPoolSnapshot::new(false, 0x80000000, txs);
+    let decoded: MemPoolSnapshot =
+        serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+    assert_eq!(decoded, snapshot);
+    assert_eq!(decoded.version, MEMPOOL_SNAPSHOT_VERSION);
+}
+
+#[test]
+fn test_import_snapshot_txs() {
+    let sender_sk = StacksPrivateKey::random();
+    let sender_addr = to_addr(&sender_sk);
+    let unfunded_sk = StacksPrivateKey::random();
+    let recipient = PrincipalData::from(StacksAddress::burn_address(false));
+
+    let mut peer_config = TestPeerConfig::new(function_name!(), 0, 0);
+    peer_config.initial_balances = vec![(sender_addr.to_account_principal(), 1_000_000_000)];
+    let mut peer = TestPeer::new(peer_config);
+
+    // export a snapshot from another node's mempool
+    let source_name = format!("{}-source", function_name!());
+    let _source_chainstate = instantiate_chainstate(false, 0x80000000, &source_name);
+    let mut source_mempool =
+        MemPoolDB::open_test(false, 0x80000000, &chainstate_path(&source_name)).unwrap();
+    let mempool_tx = source_mempool.tx_begin().unwrap();
+    let mut unfunded_txid = None;
+    for (i, (sk, nonce)) in [
+        (&sender_sk, 0),
+        (&sender_sk, 1),
+        (&sender_sk, 2),
+        (&unfunded_sk, 0),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let fee = 1000 * (nonce + 1);
+        let transfer_tx =
+            make_stacks_transfer_serialized(sk, nonce, fee, 0x80000000, &recipient, 1);
+        let txid = StacksTransaction::consensus_deserialize(&mut &transfer_tx[..])
+            .unwrap()
+            .txid();
+        insert_tx_in_mempool(
+            &mempool_tx,
+            transfer_tx,
+            &to_addr(sk),
+            nonce,
+            fee,
+            &ConsensusHash([0x2; 20]),
+            &FIRST_STACKS_BLOCK_HASH,
+            10,
+        );
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET accept_time = ?1, fee_rate = ?2 WHERE txid = ?3",
+                params![100 + nonce, 1.5, txid],
+            )
+            .unwrap();
+        if i == 3 {
+            unfunded_txid = Some(txid);
+        }
+    }
+    mempool_tx.commit().unwrap();
+    let mut snapshot_txs = MemPoolDB::export_snapshot_txs(source_mempool.conn()).unwrap();
+    let unfunded_txid = unfunded_txid.unwrap();
+    let mut expected: Vec<_> = snapshot_txs
+        .iter()
+        .filter(|tx| tx.txid != unfunded_txid)
+        .cloned()
+        .collect();
+    expected.sort_by_key(|tx| tx.origin_nonce);
+    let mut corrupt_tx = expected[0].clone();
+    corrupt_tx.tx.truncate(20);
+    snapshot_txs.push(corrupt_tx);
+
+    // import it into an empty mempool
+    let mut mempool = peer.mempool.take().unwrap();
+    let sortdb = peer.sortdb.take().unwrap();
+    let chainstate = &mut peer.stacks_node.as_mut().unwrap().chainstate;
+    let tip = StacksChainState::get_genesis_header_info(chainstate.db()).unwrap();
+    let import = |mempool: &mut MemPoolDB,
+                  chainstate: &mut StacksChainState,
+                  snapshot_tx: &MemPoolSnapshotTx| {
+        mempool.import_snapshot_tx(
+            chainstate,
+            &sortdb,
+            &tip.consensus_hash,
+            &tip.anchored_header.block_hash(),
+            snapshot_tx,
+            &ExecutionCost::max_value(),
+            &StacksEpochId::Epoch20,
+        )
+    };
+    for snapshot_tx in snapshot_txs.iter() {
+        let result = import(&mut mempool, chainstate, snapshot_tx);
+        if snapshot_tx.txid == unfunded_txid {
+            assert!(matches!(result, Err(MemPoolRejection::NotEnoughFunds(..))));
+        } else if snapshot_tx.tx.len() == 20 {
+            assert!(matches!(
+                result,
+                Err(MemPoolRejection::DeserializationFailure(..))
+            ));
+        } else {
+            assert!(result.unwrap());
+        }
+    }
+
+    // the admitted transactions keep their nonces, accept times and fee rates
+    let mut imported = MemPoolDB::export_snapshot_txs(mempool.conn()).unwrap();
+    imported.sort_by_key(|tx| tx.origin_nonce);
+    assert_eq!(imported, expected);
+
+    // importing the snapshot again skips the transactions that are already present
+    for snapshot_tx in expected.iter() {
+        assert!(!import(&mut mempool, chainstate, snapshot_tx).unwrap());
+    }
+    assert_eq!(MemPoolDB::get_all_txs(mempool.conn()).unwrap().len(), 3);
+
+    peer.sortdb = Some(sortdb);
+    peer.mempool = Some(mempool);
+}
+
 #[test]
 fn large_mempool() {
     let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
@@ -1605,6 +1605,16 @@ check if the associated microblocks can be downloaded
         process::exit(0);
     }
 
+    if argv[1] == "mempool-export" {
+        cli::command_mempool_export(&argv[1..], common_opts.config.as_ref());
+        process::exit(0);
+    }
+
+    if argv[1] == "mempool-import" {
+        cli::command_mempool_import(&argv[1..], common_opts.config.as_ref());
+        process::exit(0);
+    }
+
     if argv[1] == "dump-consts" {
         dump_consts();
     }
@@ -24,6 +24,7 @@ use stacks_common::types::sqlite::NO_PARAMS;
 use stacks_common::util::uint::{Uint256, Uint512};
 
 use crate::burnchains::{BurnchainSigner, Txid};
+use crate::net::http::HttpRateLimitClass;
 use crate::net::httpcore::StacksHttpRequest;
 use crate::net::rpc::ConversationHttp;
 use crate::net::Error as net_error;
@@ -65,6 +66,14 @@ where
     res
 }
 
+#[allow(unused_variables)]
+pub fn increment_rpc_throttled_requests_counter(class: HttpRateLimitClass) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::RPC_THROTTLED_REQUESTS_COUNTER
+        .with_label_values(&[class.get_name_str()])
+        .inc();
+}
+
 pub fn increment_stx_blocks_received_counter() {
     #[cfg(feature = "monitoring_prom")]
     prometheus::STX_BLOCKS_RECEIVED_COUNTER.inc();
@@ -34,6 +34,12 @@ lazy_static! {
         // Will use DEFAULT_BUCKETS = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0] by default
     ), &["path"]).unwrap();
 
+    pub static ref RPC_THROTTLED_REQUESTS_COUNTER: IntCounterVec = register_int_counter_vec!(
+        "stacks_node_rpc_throttled_requests_total",
+        "Total number of RPC requests rejected by per-client rate limits, by endpoint class",
+        &["class"]
+    ).unwrap();
+
     pub static ref STX_BLOCKS_RECEIVED_COUNTER: IntCounter = register_int_counter!(opts!(
         "stacks_node_stx_blocks_received_total",
         "Total number of Stacks blocks received"
@@ -28,9 +28,9 @@ use stacks_common::types::chainstate::StacksAddress;
 use stacks_common::types::net::PeerHost;
 
 use crate::net::http::{
-    parse_json, Error, HttpContentType, HttpNotFound, HttpRequest, HttpRequestContents,
-    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
-    HttpResponsePreamble,
+    parse_json, Error, HttpContentType, HttpNotFound, HttpRateLimitClass, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble,
 };
 use crate::net::httpcore::{
     request, HttpPreambleExtensions, HttpRequestContentsExtensions, RPCRequestHandler,
@@ -170,6 +170,10 @@ impl RPCRequestHandler for RPCCallReadOnlyRequestHandler {
         self.arguments = None;
     }
 
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::ReadOnlyCall
+    }
+
     /// Make the response
     fn try_handle_request(
         &mut self,
@@ -32,9 +32,9 @@ use crate::net::api::callreadonly::{
     CallReadOnlyRequestBody, CallReadOnlyResponse, RPCCallReadOnlyRequestHandler,
 };
 use crate::net::http::{
-    parse_json, Error, HttpContentType, HttpNotFound, HttpRequest, HttpRequestContents,
-    HttpRequestPreamble, HttpRequestTimeout, HttpResponse, HttpResponseContents,
-    HttpResponsePayload, HttpResponsePreamble,
+    parse_json, Error, HttpApiKeyScope, HttpApiKeys, HttpContentType, HttpNotFound,
+    HttpRateLimitClass, HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpRequestTimeout,
+    HttpResponse, HttpResponseContents, HttpResponsePayload, HttpResponsePreamble,
 };
 use crate::net::httpcore::{
     request, HttpPreambleExtensions, HttpRequestContentsExtensions, RPCRequestHandler,
@@ -46,14 +46,14 @@ use crate::net::{Error as NetError, StacksNodeState, TipRequest};
 pub struct RPCFastCallReadOnlyRequestHandler {
     pub call_read_only_handler: RPCCallReadOnlyRequestHandler,
     read_only_max_execution_time: Duration,
-    pub auth: Option<String>,
+    pub api_keys: HttpApiKeys,
 }
 
 impl RPCFastCallReadOnlyRequestHandler {
     pub fn new(
         maximum_call_argument_size: u32,
         read_only_max_execution_time: Duration,
-        auth: Option<String>,
+        api_keys: HttpApiKeys,
     ) -> Self {
         Self {
             call_read_only_handler: RPCCallReadOnlyRequestHandler::new(
@@ -67,7 +67,7 @@ impl RPCFastCallReadOnlyRequestHandler {
                 },
             ),
             read_only_max_execution_time,
-            auth,
+            api_keys,
         }
     }
 }
@@ -98,16 +98,15 @@ impl HttpRequest for RPCFastCallReadOnlyRequestHandler {
         query: Option<&str>,
         body: &[u8],
     ) -> Result<HttpRequestContents, Error> {
-        // If no authorization is set, then the block proposal endpoint is not enabled
-        let Some(password) = &self.auth else {
+        // If no API key may make unlimited read-only calls, then this endpoint is not enabled
+        if !self
+            .api_keys
+            .any_with_scope(HttpApiKeyScope::UnlimitedReadOnly)
+        {
             return Err(Error::Http(400, "Bad Request.".into()));
-        };
-        let Some(auth_header) = preamble.headers.get("authorization") else {
-            return Err(Error::Http(401, "Unauthorized".into()));
-        };
-        if auth_header != password {
-            return Err(Error::Http(401, "Unauthorized".into()));
         }
+        self.api_keys
+            .authorize(preamble, HttpApiKeyScope::UnlimitedReadOnly)?;
 
         let content_len = preamble.get_content_length();
         if !(content_len > 0
@@ -171,6 +170,10 @@ impl RPCRequestHandler for RPCFastCallReadOnlyRequestHandler {
         self.call_read_only_handler.arguments = None;
     }
 
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::ReadOnlyCall
+    }
+
     /// Make the response
     fn try_handle_request(
         &mut self,
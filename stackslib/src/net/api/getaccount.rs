@@ -15,10 +15,11 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use clarity::vm::clarity::ClarityConnection;
-use clarity::vm::database::{ClarityDatabase, STXBalance};
+use clarity::vm::database::{ClarityDatabase, ClarityDeserializable, STXBalance};
 use clarity::vm::representations::PRINCIPAL_DATA_REGEX_STRING;
 use clarity::vm::types::PrincipalData;
 use regex::{Captures, Regex};
+use stacks_common::types::chainstate::StacksBlockId;
 use stacks_common::types::net::PeerHost;
 use stacks_common::util::hash::to_hex;
 
@@ -44,6 +45,16 @@ pub struct AccountEntryResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub nonce_proof: Option<String>,
+    /// The balance record as stored in the MARF, which is the value that `balance_proof`
+    /// commits to. Only set if a proof was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub balance_data: Option<String>,
+    /// The index block hash of the block whose MARF root the proofs were generated against.
+    /// Only set if a proof was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub index_block_hash: Option<StacksBlockId>,
 }
 
 #[derive(Clone)]
@@ -143,20 +154,28 @@ impl RPCRequestHandler for RPCGetAccountRequestHandler {
                             let v1_unlock_height = clarity_db.get_v1_unlock_height();
                             let v2_unlock_height = clarity_db.get_v2_unlock_height().ok()?;
                             let v3_unlock_height = clarity_db.get_v3_unlock_height().ok()?;
-                            let (balance, balance_proof) = if with_proof {
-                                clarity_db
-                                    .get_data_with_proof::<STXBalance>(&key)
+                            let (balance, balance_proof, balance_data) = if with_proof {
+                                // read the raw record so that the client can check it against
+                                // the proof, which commits to the stored bytes
+                                match clarity_db
+                                    .get_data_with_proof::<String>(&key)
                                     .ok()
                                     .flatten()
-                                    .map(|(a, b)| (a, Some(format!("0x{}", to_hex(&b)))))
-                                    .unwrap_or_else(|| (STXBalance::zero(), Some("".into())))
+                                {
+                                    Some((data, proof)) => (
+                                        STXBalance::deserialize(&data).ok()?,
+                                        Some(format!("0x{}", to_hex(&proof))),
+                                        Some(format!("0x{}", data)),
+                                    ),
+                                    None => (STXBalance::zero(), Some("".into()), None),
+                                }
                             } else {
                                 clarity_db
                                     .get_data::<STXBalance>(&key)
                                     .ok()
                                     .flatten()
-                                    .map(|a| (a, None))
-                                    .unwrap_or_else(|| (STXBalance::zero(), None))
+                                    .map(|a| (a, None, None))
+                                    .unwrap_or_else(|| (STXBalance::zero(), None, None))
                             };
 
                             let key = ClarityDatabase::make_key_for_account_nonce(&account);
@@ -202,6 +221,8 @@ impl RPCRequestHandler for RPCGetAccountRequestHandler {
                                 nonce,
                                 balance_proof,
                                 nonce_proof,
+                                balance_data,
+                                index_block_hash: with_proof.then_some(tip),
                             })
                         })
                     },
@@ -25,8 +25,8 @@ use stacks_common::types::net::PeerHost;
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::chainstate::stacks::{Error as ChainError, StacksBlock};
 use crate::net::http::{
-    parse_bytes, Error, HttpChunkGenerator, HttpContentType, HttpNotFound, HttpRequest,
-    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    parse_bytes, Error, HttpChunkGenerator, HttpContentType, HttpNotFound, HttpRateLimitClass,
+    HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
     HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{RPCRequestHandler, StacksHttpRequest, StacksHttpResponse};
@@ -122,6 +122,10 @@ impl RPCRequestHandler for RPCBlocksRequestHandler {
         self.block_id = None;
     }
 
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::BlockFetch
+    }
+
     /// Make the response
     fn try_handle_request(
         &mut self,
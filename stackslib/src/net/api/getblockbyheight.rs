@@ -21,9 +21,9 @@ use stacks_common::types::net::PeerHost;
 use crate::chainstate::stacks::Error as ChainError;
 use crate::net::api::getblock_v3::NakamotoBlockStream;
 use crate::net::http::{
-    parse_bytes, Error, HttpContentType, HttpNotFound, HttpRequest, HttpRequestContents,
-    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
-    HttpResponsePreamble, HttpServerError,
+    parse_bytes, Error, HttpContentType, HttpNotFound, HttpRateLimitClass, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{
     HttpRequestContentsExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
@@ -92,6 +92,10 @@ impl RPCRequestHandler for RPCNakamotoBlockByHeightRequestHandler {
         self.block_height = None;
     }
 
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::BlockFetch
+    }
+
     /// Make the response
     fn try_handle_request(
         &mut self,
@@ -21,7 +21,7 @@ use clarity::vm::representations::{CONTRACT_NAME_REGEX_STRING, STANDARD_PRINCIPA
 use clarity::vm::types::QualifiedContractIdentifier;
 use clarity::vm::{ClarityName, ContractName};
 use regex::{Captures, Regex};
-use stacks_common::types::chainstate::StacksAddress;
+use stacks_common::types::chainstate::{StacksAddress, StacksBlockId};
 use stacks_common::types::net::PeerHost;
 use stacks_common::util::hash::to_hex;
 
@@ -42,6 +42,11 @@ pub struct DataVarResponse {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub marf_proof: Option<String>,
+    /// The index block hash of the block whose MARF root the proof was generated against.
+    /// Only set if a proof was requested.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_block_hash: Option<StacksBlockId>,
 }
 
 #[derive(Clone)]
@@ -160,7 +165,11 @@ impl RPCRequestHandler for RPCGetDataVarRequestHandler {
                         };
 
                         let data = format!("0x{}", value_hex);
-                        Some(DataVarResponse { data, marf_proof })
+                        Some(DataVarResponse {
+                            data,
+                            marf_proof,
+                            index_block_hash: with_proof.then_some(tip),
+                        })
                     })
                 },
             )
@@ -0,0 +1,262 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+use regex::{Captures, Regex};
+use stacks_common::codec::MAX_MESSAGE_LEN;
+use stacks_common::types::net::PeerHost;
+use stacks_common::util::get_epoch_time_secs;
+
+use crate::net::eventstream::{EventStreamSubscription, StreamEventKind, StreamItem};
+use crate::net::http::common::parse_raw_bytes;
+use crate::net::http::{
+    Error, HttpChunkGenerator, HttpContentType, HttpRequest, HttpRequestContents,
+    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
+    HttpResponsePreamble, HttpServiceUnavailable,
+};
+use crate::net::httpcore::{RPCRequestHandler, StacksHttpRequest, StacksHttpResponse};
+use crate::net::{Error as NetError, StacksNodeState};
+
+/// Maximum number of events packed into a single chunk of the stream
+const MAX_EVENTS_PER_CHUNK: usize = 64;
+
+#[derive(Clone)]
+pub struct RPCGetEventStreamRequestHandler {
+    pub kinds: Option<HashSet<StreamEventKind>>,
+}
+
+impl RPCGetEventStreamRequestHandler {
+    pub fn new() -> Self {
+        Self { kinds: None }
+    }
+
+    /// Decode a comma-separated list of event filter keys.
+    /// An absent or empty list means all events.
+    pub fn parse_event_filter(filter: Option<&str>) -> Result<HashSet<StreamEventKind>, Error> {
+        let Some(filter) = filter.filter(|f| !f.is_empty()) else {
+            return Ok(StreamEventKind::ALL.into_iter().collect());
+        };
+        let mut kinds = HashSet::new();
+        for key in filter.split(',') {
+            let key_kinds = StreamEventKind::from_filter_key(key.trim()).ok_or_else(|| {
+                Error::DecodeError(format!("Invalid event stream filter `{key}`"))
+            })?;
+            kinds.extend(key_kinds);
+        }
+        Ok(kinds)
+    }
+}
+
+/// Server-sent event stream for a single subscriber
+pub struct EventStreamGenerator {
+    /// Where the events come from
+    subscription: EventStreamSubscription,
+    /// How often to send a keep-alive comment when there are no events
+    keepalive_secs: u64,
+    /// When we last generated a chunk
+    last_chunk_time: u64,
+    /// ID of the next event to send
+    next_event_id: u64,
+}
+
+impl EventStreamGenerator {
+    pub fn new(subscription: EventStreamSubscription, keepalive_secs: u64) -> Self {
+        Self {
+            subscription,
+            keepalive_secs,
+            // send the opening chunk right away
+            last_chunk_time: 0,
+            next_event_id: 0,
+        }
+    }
+
+    /// Encode a single server-sent event
+    fn encode_event(id: u64, event_name: &str, data: &serde_json::Value) -> String {
+        format!("id: {id}\nevent: {event_name}\ndata: {data}\n\n")
+    }
+}
+
+impl HttpChunkGenerator for EventStreamGenerator {
+    fn hint_chunk_size(&self) -> usize {
+        4096
+    }
+
+    fn is_waiting(&self) -> bool {
+        !self.subscription.has_pending()
+            && !self.subscription.is_closed()
+            && self.last_chunk_time.saturating_add(self.keepalive_secs) > get_epoch_time_secs()
+    }
+
+    /// Only returns an empty chunk (ending the stream) once the subscription is closed and
+    /// drained.  If there are no events to send, then this generates a keep-alive comment.
+    fn generate_next_chunk(&mut self) -> Result<Vec<u8>, String> {
+        if self.subscription.is_closed() && !self.subscription.has_pending() {
+            return Ok(vec![]);
+        }
+        let mut chunk = String::new();
+        for _ in 0..MAX_EVENTS_PER_CHUNK {
+            let Some(item) = self.subscription.next_item() else {
+                break;
+            };
+            let encoded = match item {
+                StreamItem::Event(event) => {
+                    Self::encode_event(self.next_event_id, event.kind.event_name(), &event.data)
+                }
+                StreamItem::Lagged(dropped) => Self::encode_event(
+                    self.next_event_id,
+                    "lagged",
+                    &serde_json::json!({ "dropped": dropped }),
+                ),
+            };
+            self.next_event_id = self.next_event_id.saturating_add(1);
+            chunk.push_str(&encoded);
+        }
+        if chunk.is_empty() {
+            chunk.push_str(": keep-alive\n\n");
+        }
+        self.last_chunk_time = get_epoch_time_secs();
+        Ok(chunk.into_bytes())
+    }
+}
+
+/// Decode the HTTP request
+impl HttpRequest for RPCGetEventStreamRequestHandler {
+    fn verb(&self) -> &'static str {
+        "GET"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(r#"^/v3/events/stream$"#).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        "/v3/events/stream"
+    }
+
+    /// Try to decode this request.
+    /// The only thing to decode is the optional `events` filter.
+    fn try_parse_request(
+        &mut self,
+        preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        _body: &[u8],
+    ) -> Result<HttpRequestContents, Error> {
+        if preamble.get_content_length() != 0 {
+            return Err(Error::DecodeError(
+                "Invalid Http request: expected 0-length body".to_string(),
+            ));
+        }
+
+        let contents = HttpRequestContents::new().query_string(query);
+        let kinds = Self::parse_event_filter(contents.get_query_arg("events").map(|s| s.as_str()))?;
+        self.kinds = Some(kinds);
+
+        Ok(contents)
+    }
+}
+
+impl RPCRequestHandler for RPCGetEventStreamRequestHandler {
+    /// Reset internal state
+    fn restart(&mut self) {
+        self.kinds = None;
+    }
+
+    /// Make the response
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        _contents: HttpRequestContents,
+        node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        let kinds = self
+            .kinds
+            .take()
+            .ok_or(NetError::SendError("Missing `kinds`".into()))?;
+
+        let stream_opt =
+            node.with_node_state(|network, _sortdb, _chainstate, _mempool, _rpc_args| {
+                let opts = &network.connection_opts;
+                let max_clients =
+                    usize::try_from(opts.max_event_stream_clients).unwrap_or(usize::MAX);
+                network
+                    .event_stream
+                    .subscribe(kinds, opts.event_stream_buffer_size, max_clients)
+                    .map(|subscription| {
+                        EventStreamGenerator::new(subscription, opts.event_stream_keepalive_secs)
+                    })
+            });
+
+        let Some(stream) = stream_opt else {
+            return StacksHttpResponse::new_error(
+                &preamble,
+                &HttpServiceUnavailable::new("Too many event stream clients\n".to_string()),
+            )
+            .try_into_contents()
+            .map_err(NetError::from);
+        };
+
+        let mut resp_preamble = HttpResponsePreamble::from_http_request_preamble(
+            &preamble,
+            200,
+            "OK",
+            None,
+            HttpContentType::EventStream,
+        );
+        resp_preamble.add_header("Cache-Control".into(), "no-cache".into());
+
+        Ok((
+            resp_preamble,
+            HttpResponseContents::from_stream(Box::new(stream)),
+        ))
+    }
+}
+
+/// Decode the HTTP response
+impl HttpResponse for RPCGetEventStreamRequestHandler {
+    /// Decode this response from a byte stream.  This is called by the client to decode this
+    /// message.  The stream never ends on its own, so this only decodes whatever was received
+    /// before the connection closed.
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, Error> {
+        let bytes = parse_raw_bytes(
+            preamble,
+            body,
+            MAX_MESSAGE_LEN.into(),
+            HttpContentType::EventStream,
+        )?;
+        Ok(HttpResponsePayload::Text(
+            String::from_utf8_lossy(&bytes).to_string(),
+        ))
+    }
+}
+
+impl StacksHttpRequest {
+    /// Make a request to subscribe to the event stream.
+    /// `events` is a list of filter keys; an empty list subscribes to all events.
+    pub fn new_get_event_stream(host: PeerHost, events: &[&str]) -> StacksHttpRequest {
+        let contents = if events.is_empty() {
+            HttpRequestContents::new()
+        } else {
+            HttpRequestContents::new().query_arg("events".into(), events.join(","))
+        };
+        StacksHttpRequest::new_for_peer(host, "GET".into(), "/v3/events/stream".into(), contents)
+            .expect("FATAL: failed to construct request from infallible data")
+    }
+}
@@ -22,8 +22,8 @@ use stacks_common::types::net::PeerHost;
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::chainstate::stacks::{Error as ChainError, StacksBlockHeader, StacksMicroblock};
 use crate::net::http::{
-    parse_bytes, Error, HttpChunkGenerator, HttpContentType, HttpNotFound, HttpRequest,
-    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    parse_bytes, Error, HttpChunkGenerator, HttpContentType, HttpNotFound, HttpRateLimitClass,
+    HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
     HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{request, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse};
@@ -135,6 +135,10 @@ impl RPCRequestHandler for RPCMicroblocksIndexedRequestHandler {
         self.tail_microblock_id = None;
     }
 
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::BlockFetch
+    }
+
     /// Make the response
     fn try_handle_request(
         &mut self,
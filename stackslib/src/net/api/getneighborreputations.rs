@@ -0,0 +1,202 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use regex::{Captures, Regex};
+use stacks_common::types::net::{PeerAddress, PeerHost};
+use stacks_common::util::get_epoch_time_secs;
+
+use crate::net::db::PeerDB;
+use crate::net::http::{
+    parse_json, Error, HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse,
+    HttpResponseContents, HttpResponsePayload, HttpResponsePreamble,
+};
+use crate::net::httpcore::{
+    HttpPreambleExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
+};
+use crate::net::neighbors::reputation::PeerReputation;
+use crate::net::p2p::PeerNetwork;
+use crate::net::{Error as NetError, StacksNodeState, MAX_NEIGHBORS_DATA_LEN};
+
+#[derive(Clone)]
+pub struct RPCNeighborReputationsRequestHandler {}
+impl RPCNeighborReputationsRequestHandler {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// A peer's reputation, as reported by `/v2/neighbors/reputation`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCPeerReputation {
+    pub network_id: u32,
+    #[serde(rename = "ip")]
+    pub addrbytes: PeerAddress,
+    pub port: u16,
+    /// The score as of the time of the request, after decay
+    pub score: f64,
+    pub last_update: u64,
+    pub invalid_blocks: u64,
+    pub nack_floods: u64,
+    pub inventory_lies: u64,
+    pub invalid_stackerdb_chunks: u64,
+}
+
+impl RPCPeerReputation {
+    pub fn from_reputation(
+        reputation: PeerReputation,
+        now: u64,
+        half_life_secs: u64,
+    ) -> RPCPeerReputation {
+        RPCPeerReputation {
+            score: reputation.score_at(now, half_life_secs),
+            network_id: reputation.network_id,
+            addrbytes: reputation.addrbytes,
+            port: reputation.port,
+            last_update: reputation.last_update,
+            invalid_blocks: reputation.invalid_blocks,
+            nack_floods: reputation.nack_floods,
+            inventory_lies: reputation.inventory_lies,
+            invalid_stackerdb_chunks: reputation.invalid_stackerdb_chunks,
+        }
+    }
+}
+
+/// Struct given back from a call to `/v2/neighbors/reputation`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCPeerReputationsInfo {
+    pub ban_threshold: u64,
+    pub walk_threshold: u64,
+    pub half_life_secs: u64,
+    /// The peers with the worst scores, worst first, up to `MAX_NEIGHBORS_DATA_LEN` of them
+    pub peers: Vec<RPCPeerReputation>,
+}
+
+impl RPCPeerReputationsInfo {
+    /// Load the worst `MAX_NEIGHBORS_DATA_LEN` peer reputations from the peer DB
+    pub fn from_p2p(network: &PeerNetwork) -> Result<RPCPeerReputationsInfo, NetError> {
+        let now = get_epoch_time_secs();
+        let conn_opts = network.get_connection_opts();
+        let mut peers: Vec<_> = PeerDB::get_all_peer_reputations(
+            network.peerdb_conn(),
+            network.get_local_peer().network_id,
+        )?
+        .into_iter()
+        .map(|reputation| {
+            RPCPeerReputation::from_reputation(reputation, now, conn_opts.reputation_half_life_secs)
+        })
+        .collect();
+
+        peers.sort_by(|p1, p2| p2.score.total_cmp(&p1.score));
+        peers.truncate(MAX_NEIGHBORS_DATA_LEN as usize);
+
+        Ok(RPCPeerReputationsInfo {
+            ban_threshold: conn_opts.reputation_ban_threshold,
+            walk_threshold: conn_opts.reputation_walk_threshold,
+            half_life_secs: conn_opts.reputation_half_life_secs,
+            peers,
+        })
+    }
+}
+
+/// Decode the HTTP request
+impl HttpRequest for RPCNeighborReputationsRequestHandler {
+    fn verb(&self) -> &'static str {
+        "GET"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(r#"^/v2/neighbors/reputation$"#).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        "/v2/neighbors/reputation"
+    }
+
+    /// Try to decode this request.
+    /// There's nothing to load here, so just make sure the request is well-formed.
+    fn try_parse_request(
+        &mut self,
+        preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        _body: &[u8],
+    ) -> Result<HttpRequestContents, Error> {
+        if preamble.get_content_length() != 0 {
+            return Err(Error::DecodeError(
+                "Invalid Http request: expected 0-length body for GetNeighborReputations"
+                    .to_string(),
+            ));
+        }
+        Ok(HttpRequestContents::new().query_string(query))
+    }
+}
+
+impl RPCRequestHandler for RPCNeighborReputationsRequestHandler {
+    /// Reset internal state
+    fn restart(&mut self) {}
+
+    /// Make the response
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        _contents: HttpRequestContents,
+        node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        let reputation_data =
+            node.with_node_state(|network, _sortdb, _chainstate, _mempool, _rpc_args| {
+                RPCPeerReputationsInfo::from_p2p(network)
+            })?;
+
+        let mut preamble = HttpResponsePreamble::ok_json(&preamble);
+        preamble.set_canonical_stacks_tip_height(Some(node.canonical_stacks_tip_height()));
+        let body = HttpResponseContents::try_from_json(&reputation_data)?;
+        Ok((preamble, body))
+    }
+}
+
+/// Decode the HTTP response
+impl HttpResponse for RPCNeighborReputationsRequestHandler {
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, Error> {
+        let reputation_info: RPCPeerReputationsInfo = parse_json(preamble, body)?;
+        Ok(HttpResponsePayload::try_from_json(reputation_info)?)
+    }
+}
+
+impl StacksHttpRequest {
+    /// Make a new request for the reputations of this node's peers
+    pub fn new_getneighborreputations(host: PeerHost) -> StacksHttpRequest {
+        StacksHttpRequest::new_for_peer(
+            host,
+            "GET".into(),
+            "/v2/neighbors/reputation".into(),
+            HttpRequestContents::new(),
+        )
+        .expect("FATAL: failed to construct request from infallible data")
+    }
+}
+
+impl StacksHttpResponse {
+    pub fn decode_neighbor_reputations(self) -> Result<RPCPeerReputationsInfo, NetError> {
+        let contents = self.get_http_payload_ok()?;
+        let response_json: serde_json::Value = contents.try_into()?;
+        let reputation_info = serde_json::from_value(response_json)
+            .map_err(|_e| Error::DecodeError("Failed to decode JSON".to_string()))?;
+        Ok(reputation_info)
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use regex::{Captures, Regex};
+use stacks_common::types::net::PeerHost;
+
+use crate::burnchains::Txid;
+use crate::core::mempool::{MemPoolDB, MemPoolReplacement};
+use crate::net::http::{
+    parse_json, Error, HttpNotFound, HttpRequest, HttpRequestContents, HttpRequestPreamble,
+    HttpResponse, HttpResponseContents, HttpResponsePayload, HttpResponsePreamble, HttpServerError,
+};
+use crate::net::httpcore::{
+    request, HttpPreambleExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
+};
+use crate::net::{Error as NetError, StacksNodeState};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReplacementEntry {
+    pub replaced_txid: Txid,
+    pub replacement_txid: Txid,
+    pub replaced_fee: u64,
+    pub replacement_fee: u64,
+    /// Why the replacement was allowed (`ReplaceByFee` or `ReplaceAcrossFork`)
+    pub reason: String,
+    pub replace_time: u64,
+}
+
+impl From<MemPoolReplacement> for TransactionReplacementEntry {
+    fn from(replacement: MemPoolReplacement) -> Self {
+        Self {
+            replaced_txid: replacement.replaced_txid,
+            replacement_txid: replacement.replacement_txid,
+            replaced_fee: replacement.replaced_fee,
+            replacement_fee: replacement.replacement_fee,
+            reason: replacement.reason,
+            replace_time: replacement.replace_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionReplacementsResponse {
+    pub origin_address: String,
+    pub origin_nonce: u64,
+    /// The transaction that currently holds this origin address and nonce in the mempool, if any
+    pub current_txid: Option<Txid>,
+    /// Replacements, oldest first
+    pub replacements: Vec<TransactionReplacementEntry>,
+}
+
+#[derive(Clone)]
+pub struct RPCGetTransactionReplacementsRequestHandler {
+    pub txid: Option<Txid>,
+}
+impl RPCGetTransactionReplacementsRequestHandler {
+    pub fn new() -> Self {
+        Self { txid: None }
+    }
+}
+
+/// Decode the HTTP request
+impl HttpRequest for RPCGetTransactionReplacementsRequestHandler {
+    fn verb(&self) -> &'static str {
+        "GET"
+    }
+
+    fn path_regex(&self) -> Regex {
+        Regex::new(r#"^/v2/transactions/(?P<txid>[0-9a-f]{64})/replacements$"#).unwrap()
+    }
+
+    fn metrics_identifier(&self) -> &str {
+        "/v2/transactions/:txid/replacements"
+    }
+
+    /// Try to decode this request.
+    /// There's nothing to load here, so just make sure the request is well-formed.
+    fn try_parse_request(
+        &mut self,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        query: Option<&str>,
+        _body: &[u8],
+    ) -> Result<HttpRequestContents, Error> {
+        if preamble.get_content_length() != 0 {
+            return Err(Error::DecodeError(
+                "Invalid Http request: expected 0-length body for GetTransactionReplacements"
+                    .to_string(),
+            ));
+        }
+
+        let txid = request::get_txid(captures, "txid")?;
+        self.txid = Some(txid);
+
+        Ok(HttpRequestContents::new().query_string(query))
+    }
+}
+
+impl RPCRequestHandler for RPCGetTransactionReplacementsRequestHandler {
+    /// Reset internal state
+    fn restart(&mut self) {
+        self.txid = None;
+    }
+
+    /// Make the response
+    fn try_handle_request(
+        &mut self,
+        preamble: HttpRequestPreamble,
+        _contents: HttpRequestContents,
+        node: &mut StacksNodeState,
+    ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError> {
+        let txid = self
+            .txid
+            .take()
+            .ok_or(NetError::SendError("`txid` no set".into()))?;
+
+        let replacements_res =
+            node.with_node_state(|_network, _sortdb, _chainstate, mempool, _rpc_args| {
+                let Some((origin_address, origin_nonce, replacements)) =
+                    MemPoolDB::get_tx_replacements(mempool.conn(), &txid)?
+                else {
+                    return Err(NetError::NotFoundError);
+                };
+                let current_txid = MemPoolDB::get_tx_metadata_by_address(
+                    mempool.conn(),
+                    true,
+                    &origin_address,
+                    origin_nonce,
+                )?
+                .map(|metadata| metadata.txid);
+
+                Ok(TransactionReplacementsResponse {
+                    origin_address: origin_address.to_string(),
+                    origin_nonce,
+                    current_txid,
+                    replacements: replacements.into_iter().map(|r| r.into()).collect(),
+                })
+            });
+
+        let replacements = match replacements_res {
+            Ok(replacements) => replacements,
+            Err(NetError::NotFoundError) => {
+                return StacksHttpResponse::new_error(
+                    &preamble,
+                    &HttpNotFound::new(format!("Transaction {} not found in mempool", &txid)),
+                )
+                .try_into_contents()
+                .map_err(NetError::from);
+            }
+            Err(e) => {
+                return StacksHttpResponse::new_error(
+                    &preamble,
+                    &HttpServerError::new(format!(
+                        "Failed to query replacements of transaction {}: {:?}",
+                        &txid, &e
+                    )),
+                )
+                .try_into_contents()
+                .map_err(NetError::from);
+            }
+        };
+
+        let mut preamble = HttpResponsePreamble::ok_json(&preamble);
+        preamble.set_canonical_stacks_tip_height(Some(node.canonical_stacks_tip_height()));
+        let body = HttpResponseContents::try_from_json(&replacements)?;
+        Ok((preamble, body))
+    }
+}
+
+/// Decode the HTTP response
+impl HttpResponse for RPCGetTransactionReplacementsRequestHandler {
+    fn try_parse_response(
+        &self,
+        preamble: &HttpResponsePreamble,
+        body: &[u8],
+    ) -> Result<HttpResponsePayload, Error> {
+        let replacements: TransactionReplacementsResponse = parse_json(preamble, body)?;
+        Ok(HttpResponsePayload::try_from_json(replacements)?)
+    }
+}
+
+impl StacksHttpRequest {
+    /// Make a new request for a transaction's replacement history
+    pub fn new_gettransaction_replacements(host: PeerHost, txid: Txid) -> StacksHttpRequest {
+        StacksHttpRequest::new_for_peer(
+            host,
+            "GET".into(),
+            format!("/v2/transactions/{}/replacements", &txid),
+            HttpRequestContents::new(),
+        )
+        .expect("FATAL: failed to construct request from infallible data")
+    }
+}
+
+impl StacksHttpResponse {
+    pub fn decode_gettransaction_replacements(
+        self,
+    ) -> Result<TransactionReplacementsResponse, NetError> {
+        let contents = self.get_http_payload_ok()?;
+        let response_json: serde_json::Value = contents.try_into()?;
+        let replacements: TransactionReplacementsResponse =
+            serde_json::from_value(response_json)
+                .map_err(|_e| Error::DecodeError("Failed to decode JSON".to_string()))?;
+        Ok(replacements)
+    }
+}
@@ -32,6 +32,7 @@ pub mod getconstantval;
 pub mod getcontractabi;
 pub mod getcontractsrc;
 pub mod getdatavar;
+pub mod geteventstream;
 pub mod getheaders;
 pub mod gethealth;
 pub mod getinfo;
@@ -40,6 +41,7 @@ pub mod getmapentry;
 pub mod getmicroblocks_confirmed;
 pub mod getmicroblocks_indexed;
 pub mod getmicroblocks_unconfirmed;
+pub mod getneighborreputations;
 pub mod getneighbors;
 pub mod getpoxinfo;
 pub mod getsigner;
@@ -52,6 +54,7 @@ pub mod gettenure;
 pub mod gettenureinfo;
 pub mod gettenuretip;
 pub mod gettransaction;
+pub mod gettransaction_replacements;
 pub mod gettransaction_unconfirmed;
 pub mod liststackerdbreplicas;
 pub mod postblock;
@@ -78,7 +81,7 @@ impl StacksHttp {
         self.register_rpc_endpoint(fastcallreadonly::RPCFastCallReadOnlyRequestHandler::new(
             self.maximum_call_argument_size,
             self.read_only_max_execution_time,
-            self.auth_token.clone(),
+            self.api_keys.clone(),
         ));
         self.register_rpc_endpoint(getaccount::RPCGetAccountRequestHandler::new());
         self.register_rpc_endpoint(getattachment::RPCGetAttachmentRequestHandler::new());
@@ -92,6 +95,7 @@ impl StacksHttp {
         self.register_rpc_endpoint(getcontractabi::RPCGetContractAbiRequestHandler::new());
         self.register_rpc_endpoint(getcontractsrc::RPCGetContractSrcRequestHandler::new());
         self.register_rpc_endpoint(getdatavar::RPCGetDataVarRequestHandler::new());
+        self.register_rpc_endpoint(geteventstream::RPCGetEventStreamRequestHandler::new());
         self.register_rpc_endpoint(getheaders::RPCHeadersRequestHandler::new());
         self.register_rpc_endpoint(getinfo::RPCPeerInfoRequestHandler::new());
         self.register_rpc_endpoint(
@@ -107,6 +111,9 @@ impl StacksHttp {
         self.register_rpc_endpoint(
             getmicroblocks_unconfirmed::RPCMicroblocksUnconfirmedRequestHandler::new(),
         );
+        self.register_rpc_endpoint(
+            getneighborreputations::RPCNeighborReputationsRequestHandler::new(),
+        );
         self.register_rpc_endpoint(getneighbors::RPCNeighborsRequestHandler::new());
         self.register_rpc_endpoint(getstxtransfercost::RPCGetStxTransferCostRequestHandler::new());
         self.register_rpc_endpoint(getstackerdbchunk::RPCGetStackerDBChunkRequestHandler::new());
@@ -123,6 +130,9 @@ impl StacksHttp {
         self.register_rpc_endpoint(
             gettransaction_unconfirmed::RPCGetTransactionUnconfirmedRequestHandler::new(),
         );
+        self.register_rpc_endpoint(
+            gettransaction_replacements::RPCGetTransactionReplacementsRequestHandler::new(),
+        );
         self.register_rpc_endpoint(gettransaction::RPCGetTransactionRequestHandler::new());
         self.register_rpc_endpoint(getsigner::GetSignerRequestHandler::default());
         self.register_rpc_endpoint(gethealth::RPCGetHealthRequestHandler::new());
@@ -131,15 +141,20 @@ impl StacksHttp {
         );
         self.register_rpc_endpoint(postblock::RPCPostBlockRequestHandler::new());
         self.register_rpc_endpoint(postblock_proposal::RPCBlockProposalRequestHandler::new(
-            self.auth_token.clone(),
+            self.api_keys.clone(),
         ));
         self.register_rpc_endpoint(postblock_v3::RPCPostBlockRequestHandler::new(
-            self.auth_token.clone(),
+            self.api_keys.clone(),
         ));
         self.register_rpc_endpoint(postfeerate::RPCPostFeeRateRequestHandler::new());
         self.register_rpc_endpoint(postmempoolquery::RPCMempoolQueryRequestHandler::new());
         self.register_rpc_endpoint(postmicroblock::RPCPostMicroblockRequestHandler::new());
-        self.register_rpc_endpoint(poststackerdbchunk::RPCPostStackerDBChunkRequestHandler::new());
+        self.register_rpc_endpoint(
+            poststackerdbchunk::RPCPostStackerDBChunkRequestHandler::new(
+                self.stackerdb_write_requires_api_key
+                    .then(|| self.api_keys.clone()),
+            ),
+        );
         self.register_rpc_endpoint(posttransaction::RPCPostTransactionRequestHandler::new());
     }
 }
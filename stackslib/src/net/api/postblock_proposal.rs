@@ -49,9 +49,9 @@ use crate::chainstate::stacks::{
 use crate::clarity_vm::clarity::Error as ClarityError;
 use crate::core::mempool::ProposalCallbackReceiver;
 use crate::net::http::{
-    http_reason, parse_json, Error, HttpContentType, HttpRequest, HttpRequestContents,
-    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
-    HttpResponsePreamble,
+    http_reason, parse_json, Error, HttpApiKeyScope, HttpApiKeys, HttpContentType, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble,
 };
 use crate::net::httpcore::{HttpPreambleExtensions, RPCRequestHandler};
 use crate::net::{Error as NetError, StacksNodeState};
@@ -859,14 +859,14 @@ impl NakamotoBlockProposal {
 #[derive(Clone, Default)]
 pub struct RPCBlockProposalRequestHandler {
     pub block_proposal: Option<NakamotoBlockProposal>,
-    pub auth: Option<String>,
+    pub api_keys: HttpApiKeys,
 }
 
 impl RPCBlockProposalRequestHandler {
-    pub fn new(auth: Option<String>) -> Self {
+    pub fn new(api_keys: HttpApiKeys) -> Self {
         Self {
             block_proposal: None,
-            auth,
+            api_keys,
         }
     }
 
@@ -900,16 +900,12 @@ impl HttpRequest for RPCBlockProposalRequestHandler {
         query: Option<&str>,
         body: &[u8],
     ) -> Result<HttpRequestContents, Error> {
-        // If no authorization is set, then the block proposal endpoint is not enabled
-        let Some(password) = &self.auth else {
+        // If no API key may propose blocks, then the block proposal endpoint is not enabled
+        if !self.api_keys.any_with_scope(HttpApiKeyScope::BlockProposal) {
             return Err(Error::Http(400, "Bad Request.".into()));
-        };
-        let Some(auth_header) = preamble.headers.get("authorization") else {
-            return Err(Error::Http(401, "Unauthorized".into()));
-        };
-        if auth_header != password {
-            return Err(Error::Http(401, "Unauthorized".into()));
         }
+        self.api_keys
+            .authorize(preamble, HttpApiKeyScope::BlockProposal)?;
         if preamble.get_content_length() == 0 {
             return Err(Error::DecodeError(
                 "Invalid Http request: expected non-zero-length body for block proposal endpoint"
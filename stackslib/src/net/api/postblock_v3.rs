@@ -22,9 +22,9 @@ use super::postblock::StacksBlockAcceptedData;
 use crate::chainstate::nakamoto::staging_blocks::NakamotoBlockObtainMethod;
 use crate::chainstate::nakamoto::NakamotoBlock;
 use crate::net::http::{
-    parse_json, Error, HttpContentType, HttpError, HttpRequest, HttpRequestContents,
-    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
-    HttpResponsePreamble,
+    parse_json, Error, HttpApiKeyScope, HttpApiKeys, HttpContentType, HttpError, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble,
 };
 use crate::net::httpcore::{
     HttpPreambleExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
@@ -37,15 +37,15 @@ pub static PATH: &str = "/v3/blocks/upload/";
 #[derive(Clone, Default)]
 pub struct RPCPostBlockRequestHandler {
     pub block: Option<NakamotoBlock>,
-    pub auth: Option<String>,
+    pub api_keys: HttpApiKeys,
     pub broadcast: Option<bool>,
 }
 
 impl RPCPostBlockRequestHandler {
-    pub fn new(auth: Option<String>) -> Self {
+    pub fn new(api_keys: HttpApiKeys) -> Self {
         Self {
             block: None,
-            auth,
+            api_keys,
             broadcast: None,
         }
     }
@@ -98,19 +98,8 @@ impl HttpRequest for RPCPostBlockRequestHandler {
             ));
         }
 
-        // if broadcast=1 is set, then the requester must be authenticated
+        // if broadcast=1 is set, then the requester must have a key that may upload blocks
         let mut broadcast = false;
-        let mut authenticated = false;
-
-        // look for authorization header
-        if let Some(password) = &self.auth {
-            if let Some(auth_header) = preamble.headers.get("authorization") {
-                if auth_header != password {
-                    return Err(Error::Http(401, "Unauthorized".into()));
-                }
-                authenticated = true;
-            }
-        }
 
         // see if broadcast=1 is set
         for (key, value) in form_urlencoded::parse(query.as_ref().unwrap_or(&"").as_bytes()) {
@@ -119,7 +108,14 @@ impl HttpRequest for RPCPostBlockRequestHandler {
             }
         }
 
-        if broadcast && !authenticated {
+        if broadcast {
+            self.api_keys
+                .authorize(preamble, HttpApiKeyScope::BlockUpload)?;
+        } else if !self.api_keys.is_empty()
+            && preamble.headers.contains_key("authorization")
+            && self.api_keys.lookup(preamble).is_none()
+        {
+            // an authorization header, if given, must still be a valid key
             return Err(Error::Http(401, "Unauthorized".into()));
         }
 
@@ -16,17 +16,18 @@
 
 use clarity::vm::representations::{CONTRACT_NAME_REGEX_STRING, STANDARD_PRINCIPAL_REGEX_STRING};
 use clarity::vm::types::QualifiedContractIdentifier;
+pub use libstackerdb::StackerDBErrorCodes;
 use libstackerdb::{StackerDBChunkAckData, StackerDBChunkData};
 use regex::{Captures, Regex};
 use serde_json;
-use serde_json::json;
 use stacks_common::codec::MAX_MESSAGE_LEN;
 use stacks_common::types::net::PeerHost;
 use stacks_common::util::secp256k1::MessageSignature;
 
 use crate::net::http::{
-    parse_json, Error, HttpNotFound, HttpRequest, HttpRequestContents, HttpRequestPreamble,
-    HttpResponse, HttpResponseContents, HttpResponsePayload, HttpResponsePreamble, HttpServerError,
+    parse_json, Error, HttpApiKeyScope, HttpApiKeys, HttpNotFound, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{
     request, HttpPreambleExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
@@ -37,12 +38,15 @@ use crate::net::{Error as NetError, StackerDBPushChunkData, StacksMessageType, S
 pub struct RPCPostStackerDBChunkRequestHandler {
     pub contract_identifier: Option<QualifiedContractIdentifier>,
     pub chunk: Option<StackerDBChunkData>,
+    /// If set, writers must present one of these keys with the `stackerdb-write` scope
+    pub api_keys: Option<HttpApiKeys>,
 }
 impl RPCPostStackerDBChunkRequestHandler {
-    pub fn new() -> Self {
+    pub fn new(api_keys: Option<HttpApiKeys>) -> Self {
         Self {
             contract_identifier: None,
             chunk: None,
+            api_keys,
         }
     }
 }
@@ -74,6 +78,10 @@ impl HttpRequest for RPCPostStackerDBChunkRequestHandler {
         query: Option<&str>,
         body: &[u8],
     ) -> Result<HttpRequestContents, Error> {
+        if let Some(api_keys) = self.api_keys.as_ref() {
+            api_keys.authorize(preamble, HttpApiKeyScope::StackerDBWrite)?;
+        }
+
         if preamble.get_content_length() == 0 {
             return Err(Error::DecodeError(
                 "Invalid Http request: expected non-empty body".to_string(),
@@ -96,50 +104,6 @@ impl HttpRequest for RPCPostStackerDBChunkRequestHandler {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum StackerDBErrorCodes {
-    DataAlreadyExists,
-    NoSuchSlot,
-    BadSigner,
-}
-
-impl StackerDBErrorCodes {
-    pub fn code(&self) -> u32 {
-        match self {
-            Self::DataAlreadyExists => 0,
-            Self::NoSuchSlot => 1,
-            Self::BadSigner => 2,
-        }
-    }
-
-    #[cfg_attr(test, mutants::skip)]
-    pub fn reason(&self) -> &'static str {
-        match self {
-            Self::DataAlreadyExists => "Data for this slot and version already exist",
-            Self::NoSuchSlot => "No such StackerDB slot",
-            Self::BadSigner => "Signature does not match slot signer",
-        }
-    }
-
-    pub fn into_json(self) -> serde_json::Value {
-        json!({
-            "code": self.code(),
-            "message": format!("{:?}", &self),
-            "reason": self.reason()
-        })
-    }
-
-    #[cfg_attr(test, mutants::skip)]
-    pub fn from_code(code: u32) -> Option<Self> {
-        match code {
-            0 => Some(Self::DataAlreadyExists),
-            1 => Some(Self::NoSuchSlot),
-            2 => Some(Self::BadSigner),
-            _ => None,
-        }
-    }
-}
-
 impl RPCRequestHandler for RPCPostStackerDBChunkRequestHandler {
     /// Reset internal state
     fn restart(&mut self) {
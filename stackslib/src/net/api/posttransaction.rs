@@ -22,9 +22,9 @@ use stacks_common::util::hash::{hex_bytes, to_hex};
 use crate::burnchains::Txid;
 use crate::chainstate::stacks::{StacksTransaction, TransactionPayload};
 use crate::net::http::{
-    parse_json, Error, HttpBadRequest, HttpContentType, HttpRequest, HttpRequestContents,
-    HttpRequestPreamble, HttpResponse, HttpResponseContents, HttpResponsePayload,
-    HttpResponsePreamble, HttpServerError,
+    parse_json, Error, HttpBadRequest, HttpContentType, HttpRateLimitClass, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::httpcore::{
     HttpPreambleExtensions, RPCRequestHandler, StacksHttpRequest, StacksHttpResponse,
@@ -167,6 +167,10 @@ impl RPCRequestHandler for RPCPostTransactionRequestHandler {
         self.attachment = None;
     }
 
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::TxBroadcast
+    }
+
     /// Make the response
     fn try_handle_request(
         &mut self,
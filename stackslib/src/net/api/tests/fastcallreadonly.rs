@@ -24,6 +24,7 @@ use stacks_common::types::Address;
 use super::{test_rpc, test_rpc_with_config};
 use crate::net::api::*;
 use crate::net::connection::ConnectionOptions;
+use crate::net::http::{HttpApiKey, HttpApiKeys};
 use crate::net::httpcore::{
     HttpPreambleExtensions, HttpRequestContentsExtensions, RPCRequestHandler, StacksHttp,
     StacksHttpRequest,
@@ -64,7 +65,7 @@ fn test_try_parse_request() {
     let mut handler = fastcallreadonly::RPCFastCallReadOnlyRequestHandler::new(
         4096,
         Duration::from_secs(30),
-        Some("password".into()),
+        HttpApiKeys::new(vec![HttpApiKey::from_auth_token("password".into())]),
     );
     let mut parsed_request = http
         .handle_try_parse_request(
@@ -17,6 +17,7 @@
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 use clarity::vm::types::{PrincipalData, StacksAddressExtensions};
+use stacks_common::marf_proof::MarfProof;
 use stacks_common::types::chainstate::StacksAddress;
 use stacks_common::types::Address;
 
@@ -142,6 +143,8 @@ fn test_try_make_response() {
     assert_eq!(resp.nonce, 2);
     assert!(resp.balance_proof.is_none());
     assert!(resp.nonce_proof.is_none());
+    assert!(resp.balance_data.is_none());
+    assert!(resp.index_block_hash.is_none());
 
     let response = responses.remove(0);
     debug!(
@@ -161,6 +164,10 @@ fn test_try_make_response() {
     assert_eq!(resp.nonce, 2);
     assert!(resp.balance_proof.is_some());
     assert!(resp.nonce_proof.is_some());
+    assert!(resp.balance_data.is_some());
+    assert!(resp.index_block_hash.is_some());
+    MarfProof::from_hex(resp.balance_proof.as_ref().unwrap()).unwrap();
+    MarfProof::from_hex(resp.nonce_proof.as_ref().unwrap()).unwrap();
 
     let response = responses.remove(0);
     debug!(
@@ -180,6 +187,7 @@ fn test_try_make_response() {
     assert_eq!(resp.nonce, 0);
     assert_eq!(resp.balance_proof, Some("".to_string()));
     assert_eq!(resp.nonce_proof, Some("".to_string()));
+    assert!(resp.balance_data.is_none());
 
     let response = responses.remove(0);
     debug!(
@@ -153,6 +153,7 @@ fn test_try_make_response() {
     let resp = response.decode_data_var_response().unwrap();
     assert_eq!(resp.data, "0x0000000000000000000000000000000000");
     assert!(resp.marf_proof.is_some());
+    assert!(resp.index_block_hash.is_some());
 
     // unconfirmed data
     let response = responses.remove(0);
@@ -0,0 +1,150 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use serde_json::json;
+
+use super::test_rpc_with_config;
+use crate::net::api::geteventstream::{EventStreamGenerator, RPCGetEventStreamRequestHandler};
+use crate::net::connection::ConnectionOptions;
+use crate::net::eventstream::{EventStreamHub, StreamEvent, StreamEventKind};
+use crate::net::http::HttpChunkGenerator;
+use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
+use crate::net::ProtocolFamily;
+
+#[test]
+fn test_try_parse_request() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let mut http = StacksHttp::new(addr.clone(), &ConnectionOptions::default());
+
+    let request = StacksHttpRequest::new_get_event_stream(addr.into(), &["blocks", "memtx"]);
+    let bytes = request.try_serialize().unwrap();
+
+    debug!("Request:\n{}\n", std::str::from_utf8(&bytes).unwrap());
+
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    let mut handler = RPCGetEventStreamRequestHandler::new();
+    let mut parsed_request = http
+        .handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        )
+        .unwrap();
+
+    assert_eq!(
+        handler.kinds,
+        Some(HashSet::from([
+            StreamEventKind::NewBlock,
+            StreamEventKind::NewMempoolTx
+        ]))
+    );
+
+    // parsed request consumes headers that would not be in a constructed reqeuest
+    parsed_request.clear_headers();
+    let (preamble, _contents) = parsed_request.destruct();
+    assert_eq!(&preamble, request.preamble());
+
+    handler.restart();
+    assert!(handler.kinds.is_none());
+
+    // no filter means everything
+    assert_eq!(
+        RPCGetEventStreamRequestHandler::parse_event_filter(None).unwrap(),
+        StreamEventKind::ALL.into_iter().collect()
+    );
+    assert_eq!(
+        RPCGetEventStreamRequestHandler::parse_event_filter(Some("*,blocks")).unwrap(),
+        StreamEventKind::ALL.into_iter().collect()
+    );
+    assert!(RPCGetEventStreamRequestHandler::parse_event_filter(Some("blocks,stx")).is_err());
+}
+
+#[test]
+fn test_try_make_response_too_many_clients() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let requests = vec![StacksHttpRequest::new_get_event_stream(addr.into(), &[])];
+
+    let mut responses = test_rpc_with_config(
+        function_name!(),
+        requests,
+        |peer_1_config| peer_1_config.connection_opts.max_event_stream_clients = 0,
+        |peer_2_config| peer_2_config.connection_opts.max_event_stream_clients = 0,
+    );
+
+    let response = responses.remove(0);
+    let (preamble, _contents) = response.destruct();
+    assert_eq!(preamble.status_code, 503);
+}
+
+#[test]
+fn test_stream_events() {
+    let hub = EventStreamHub::new();
+    let subscription = hub
+        .subscribe(
+            HashSet::from([StreamEventKind::NewBurnBlock, StreamEventKind::NewTenure]),
+            2,
+            1,
+        )
+        .unwrap();
+    let mut stream = EventStreamGenerator::new(subscription, 3600);
+
+    // the stream opens with a keep-alive, and then waits
+    assert!(!stream.is_waiting());
+    let chunk = stream.generate_next_chunk().unwrap();
+    assert_eq!(std::str::from_utf8(&chunk).unwrap(), ": keep-alive\n\n");
+    assert!(stream.is_waiting());
+
+    // not subscribed
+    hub.publish(StreamEvent::new(
+        StreamEventKind::NewMempoolTx,
+        json!({"txid": "0x00"}),
+    ));
+    assert!(stream.is_waiting());
+
+    hub.publish(StreamEvent::new(
+        StreamEventKind::NewBurnBlock,
+        json!({"burn_block_height": 1}),
+    ));
+    assert!(!stream.is_waiting());
+    let chunk = stream.generate_next_chunk().unwrap();
+    assert_eq!(
+        std::str::from_utf8(&chunk).unwrap(),
+        "id: 0\nevent: new_burn_block\ndata: {\"burn_block_height\":1}\n\n"
+    );
+    assert!(stream.is_waiting());
+
+    // overflow the buffer
+    for height in 2..6 {
+        hub.publish(StreamEvent::new(
+            StreamEventKind::NewBurnBlock,
+            json!({ "burn_block_height": height }),
+        ));
+    }
+    let chunk = stream.generate_next_chunk().unwrap();
+    assert_eq!(
+        std::str::from_utf8(&chunk).unwrap(),
+        "id: 1\nevent: lagged\ndata: {\"dropped\":2}\n\n\
+         id: 2\nevent: new_burn_block\ndata: {\"burn_block_height\":4}\n\n\
+         id: 3\nevent: new_burn_block\ndata: {\"burn_block_height\":5}\n\n"
+    );
+
+    // once the hub goes away, the stream ends
+    hub.close();
+    assert!(!stream.is_waiting());
+    assert!(stream.generate_next_chunk().unwrap().is_empty());
+}
@@ -161,6 +161,7 @@ fn test_try_make_response() {
     let resp = response.decode_map_entry_response().unwrap();
     assert_eq!(resp.data, "0x0a0100000000000000000000000000000002");
     assert!(resp.marf_proof.is_some());
+    assert!(resp.index_block_hash.is_some());
 
     // unconfirmed data
     let response = responses.remove(0);
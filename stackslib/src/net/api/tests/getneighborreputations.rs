@@ -0,0 +1,119 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use stacks_common::types::net::PeerAddress;
+use stacks_common::util::get_epoch_time_secs;
+
+use super::TestRPC;
+use crate::net::api::*;
+use crate::net::connection::ConnectionOptions;
+use crate::net::db::PeerDB;
+use crate::net::httpcore::{StacksHttp, StacksHttpRequest};
+use crate::net::neighbors::reputation::PeerReputationEvent;
+use crate::net::{NeighborKey, ProtocolFamily};
+
+#[test]
+fn test_try_parse_request() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let mut http = StacksHttp::new(addr.clone(), &ConnectionOptions::default());
+
+    let request = StacksHttpRequest::new_getneighborreputations(addr.into());
+    let bytes = request.try_serialize().unwrap();
+
+    debug!("Request:\n{}\n", std::str::from_utf8(&bytes).unwrap());
+
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    let mut handler = getneighborreputations::RPCNeighborReputationsRequestHandler::new();
+    let mut parsed_request = http
+        .handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        )
+        .unwrap();
+
+    // parsed request consumes headers that would not be in a constructed reqeuest
+    parsed_request.clear_headers();
+    let (preamble, _contents) = parsed_request.destruct();
+
+    assert_eq!(&preamble, request.preamble());
+}
+
+#[test]
+fn test_try_make_response() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+
+    let mut rpc_test = TestRPC::setup(function_name!());
+    let now = get_epoch_time_secs();
+
+    // give a couple of made-up peers a bad reputation on both nodes
+    for peer in [&mut rpc_test.peer_1, &mut rpc_test.peer_2] {
+        let network_id = peer.network.get_local_peer().network_id;
+        let mut nk = NeighborKey {
+            peer_version: 0,
+            network_id,
+            addrbytes: PeerAddress::from_ipv4(10, 0, 0, 1),
+            port: 20444,
+        };
+        let half_life = peer.network.get_connection_opts().reputation_half_life_secs;
+        let tx = peer.network.peerdb.tx_begin().unwrap();
+        PeerDB::record_peer_reputation_event(
+            &tx,
+            &nk,
+            PeerReputationEvent::InvalidStackerDBChunk,
+            now,
+            half_life,
+        )
+        .unwrap();
+        nk.port = 20445;
+        PeerDB::record_peer_reputation_event(
+            &tx,
+            &nk,
+            PeerReputationEvent::InvalidBlock,
+            now,
+            half_life,
+        )
+        .unwrap();
+        tx.commit().unwrap();
+    }
+
+    let conn_opts = rpc_test.peer_2.network.get_connection_opts().clone();
+    let request = StacksHttpRequest::new_getneighborreputations(addr.into());
+    let mut responses = rpc_test.run(vec![request]);
+
+    let response = responses.remove(0);
+    debug!(
+        "Response:\n{}\n",
+        std::str::from_utf8(&response.try_serialize().unwrap()).unwrap()
+    );
+
+    let resp = response.decode_neighbor_reputations().unwrap();
+    assert_eq!(resp.ban_threshold, conn_opts.reputation_ban_threshold);
+    assert_eq!(resp.walk_threshold, conn_opts.reputation_walk_threshold);
+    assert_eq!(resp.half_life_secs, conn_opts.reputation_half_life_secs);
+
+    // worst peer first
+    assert_eq!(resp.peers.len(), 2);
+    assert_eq!(resp.peers[0].port, 20445);
+    assert_eq!(resp.peers[0].invalid_blocks, 1);
+    assert_eq!(resp.peers[0].invalid_stackerdb_chunks, 0);
+    assert_eq!(resp.peers[1].port, 20444);
+    assert_eq!(resp.peers[1].invalid_blocks, 0);
+    assert_eq!(resp.peers[1].invalid_stackerdb_chunks, 1);
+    assert!(resp.peers[0].score > resp.peers[1].score);
+    assert!(resp.peers[1].score > 0.0);
+}
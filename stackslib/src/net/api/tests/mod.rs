@@ -45,6 +45,7 @@ use crate::chainstate::stacks::{
 };
 use crate::core::MemPoolDB;
 use crate::net::db::PeerDB;
+use crate::net::http::HttpRateLimiter;
 use crate::net::httpcore::{StacksHttpRequest, StacksHttpResponse};
 use crate::net::relay::Relayer;
 use crate::net::rpc::ConversationHttp;
@@ -71,6 +72,7 @@ mod getconstantval;
 mod getcontractabi;
 mod getcontractsrc;
 mod getdatavar;
+mod geteventstream;
 mod getheaders;
 mod gethealth;
 mod getinfo;
@@ -79,6 +81,7 @@ mod getmapentry;
 mod getmicroblocks_confirmed;
 mod getmicroblocks_indexed;
 mod getmicroblocks_unconfirmed;
+mod getneighborreputations;
 mod getneighbors;
 mod getpoxinfo;
 mod getsigner;
@@ -90,6 +93,7 @@ mod gettenure;
 mod gettenureinfo;
 mod gettenuretip;
 mod gettransaction;
+mod gettransaction_replacements;
 mod gettransaction_unconfirmed;
 mod liststackerdbreplicas;
 mod postblock;
@@ -1108,6 +1112,8 @@ impl<'a> TestRPC<'a> {
         let peer_2_indexer = self.peer_2_indexer;
         let mut convo_1 = self.convo_1;
         let mut convo_2 = self.convo_2;
+        let mut rate_limiter_1 = HttpRateLimiter::new(&peer_1.config.connection_opts);
+        let mut rate_limiter_2 = HttpRateLimiter::new(&peer_2.config.connection_opts);
         let unconfirmed_state = self.unconfirmed_state;
 
         let mut responses = vec![];
@@ -1157,7 +1163,7 @@ impl<'a> TestRPC<'a> {
                     false,
                     peer_1.config.txindex,
                 );
-                convo_1.chat(&mut node_state).unwrap();
+                convo_1.chat(&mut node_state, &mut rate_limiter_1).unwrap();
             }
 
             peer_1.sortdb = Some(peer_1_sortdb);
@@ -1206,7 +1212,7 @@ impl<'a> TestRPC<'a> {
                     false,
                     peer_2.config.txindex,
                 );
-                convo_2.chat(&mut node_state).unwrap();
+                convo_2.chat(&mut node_state, &mut rate_limiter_2).unwrap();
             }
 
             peer_2.sortdb = Some(peer_2_sortdb);
@@ -1267,7 +1273,7 @@ impl<'a> TestRPC<'a> {
                         peer_1.config.txindex,
                     );
 
-                    convo_1.chat(&mut node_state).unwrap();
+                    convo_1.chat(&mut node_state, &mut rate_limiter_1).unwrap();
 
                     peer_1.sortdb = Some(peer_1_sortdb);
                     peer_1.stacks_node = Some(peer_1_stacks_node);
@@ -49,7 +49,7 @@ use crate::net::api::postblock_proposal::{
 };
 use crate::net::api::*;
 use crate::net::connection::ConnectionOptions;
-use crate::net::http::HttpRequestContents;
+use crate::net::http::{HttpApiKey, HttpApiKeys, HttpRequestContents};
 use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
 use crate::net::relay::Relayer;
 use crate::net::test::{TestEventObserver, TestPeer};
@@ -78,7 +78,9 @@ fn test_try_parse_request() {
 
     let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
     let mut handler =
-        postblock_proposal::RPCBlockProposalRequestHandler::new(Some("password".into()));
+        postblock_proposal::RPCBlockProposalRequestHandler::new(HttpApiKeys::new(vec![
+            HttpApiKey::from_auth_token("password".into()),
+        ]));
 
     // missing authorization header
     let bad_request = http.handle_try_parse_request(
@@ -124,7 +126,7 @@ fn test_try_parse_request() {
     assert_eq!(&preamble, request.preamble());
 
     handler.restart();
-    assert!(handler.auth.is_some());
+    assert!(!handler.api_keys.is_empty());
     assert!(handler.block_proposal.is_none());
 }
 
@@ -22,7 +22,7 @@ use super::TestRPC;
 use crate::chainstate::stacks::test::make_codec_test_nakamoto_block;
 use crate::net::api::*;
 use crate::net::connection::ConnectionOptions;
-use crate::net::http::HttpRequestContents;
+use crate::net::http::{HttpApiKey, HttpApiKeyScope, HttpApiKeys, HttpRequestContents};
 use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
 use crate::net::test::TestEventObserver;
 use crate::net::ProtocolFamily;
@@ -38,7 +38,9 @@ fn parse_request() {
     let bytes = request.try_serialize().unwrap();
 
     let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
-    let mut handler = postblock_v3::RPCPostBlockRequestHandler::new(Some("12345".to_string()));
+    let mut handler = postblock_v3::RPCPostBlockRequestHandler::new(HttpApiKeys::new(vec![
+        HttpApiKey::from_auth_token("12345".to_string()),
+    ]));
     let mut parsed_request = http
         .handle_try_parse_request(
             &mut handler,
@@ -157,6 +159,72 @@ fn parse_request() {
     }
 }
 
+#[test]
+fn parse_request_scoped_keys() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let mut http = StacksHttp::new(addr.clone(), &ConnectionOptions::default());
+
+    let miner_sk = StacksPrivateKey::from_seed(&[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+    let block = make_codec_test_nakamoto_block(StacksEpochId::Epoch30, &miner_sk);
+    let mut handler = postblock_v3::RPCPostBlockRequestHandler::new(HttpApiKeys::new(vec![
+        HttpApiKey {
+            name: "signer".into(),
+            key: "signer-key".into(),
+            scopes: vec![HttpApiKeyScope::BlockProposal],
+        },
+        HttpApiKey {
+            name: "uploader".into(),
+            key: "uploader-key".into(),
+            scopes: vec![HttpApiKeyScope::BlockUpload],
+        },
+    ]));
+
+    // a key with the block-upload scope may broadcast
+    let request =
+        StacksHttpRequest::new_post_block_v3_broadcast(addr.into(), &block, "uploader-key");
+    let bytes = request.try_serialize().unwrap();
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    http.handle_try_parse_request(
+        &mut handler,
+        &parsed_preamble.expect_request(),
+        &bytes[offset..],
+    )
+    .unwrap();
+    assert_eq!(handler.broadcast, Some(true));
+    handler.restart();
+
+    // a key without it may upload, but not broadcast
+    let request = StacksHttpRequest::new_post_block_v3_broadcast(addr.into(), &block, "signer-key");
+    let bytes = request.try_serialize().unwrap();
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    match http.handle_try_parse_request(
+        &mut handler,
+        &parsed_preamble.expect_request(),
+        &bytes[offset..],
+    ) {
+        Err(crate::net::Error::Http(crate::net::http::Error::Http(err_code, _))) => {
+            assert_eq!(err_code, 403);
+        }
+        x => {
+            error!("Expected HTTP 403, got {:?}", &x);
+            panic!("expected error");
+        }
+    }
+    handler.restart();
+
+    let mut request = StacksHttpRequest::new_post_block_v3(addr.into(), &block);
+    request.add_header("authorization".into(), "signer-key".into());
+    let bytes = request.try_serialize().unwrap();
+    let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+    http.handle_try_parse_request(
+        &mut handler,
+        &parsed_preamble.expect_request(),
+        &bytes[offset..],
+    )
+    .unwrap();
+    assert_eq!(handler.broadcast, Some(false));
+}
+
 #[test]
 fn handle_req_accepted() {
     let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
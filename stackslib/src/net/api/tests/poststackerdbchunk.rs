@@ -24,6 +24,7 @@ use stacks_common::util::secp256k1::MessageSignature;
 use super::TestRPC;
 use crate::net::api::*;
 use crate::net::connection::ConnectionOptions;
+use crate::net::http::{HttpApiKey, HttpApiKeyScope, HttpApiKeys};
 use crate::net::httpcore::{RPCRequestHandler, StacksHttp, StacksHttpRequest};
 use crate::net::ProtocolFamily;
 
@@ -48,7 +49,7 @@ fn test_try_parse_request() {
     debug!("Request:\n{}\n", std::str::from_utf8(&bytes).unwrap());
 
     let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
-    let mut handler = poststackerdbchunk::RPCPostStackerDBChunkRequestHandler::new();
+    let mut handler = poststackerdbchunk::RPCPostStackerDBChunkRequestHandler::new(None);
     let mut parsed_request = http
         .handle_try_parse_request(
             &mut handler,
@@ -87,6 +88,64 @@ fn test_try_parse_request() {
     assert!(handler.chunk.is_none());
 }
 
+#[test]
+fn test_try_parse_request_requires_api_key() {
+    let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
+    let mut http = StacksHttp::new(addr.clone(), &ConnectionOptions::default());
+    let mut handler =
+        poststackerdbchunk::RPCPostStackerDBChunkRequestHandler::new(Some(HttpApiKeys::new(vec![
+            HttpApiKey {
+                name: "signer".into(),
+                key: "signer-key".into(),
+                scopes: vec![HttpApiKeyScope::StackerDBWrite],
+            },
+            HttpApiKey {
+                name: "proposer".into(),
+                key: "proposer-key".into(),
+                scopes: vec![HttpApiKeyScope::BlockProposal],
+            },
+        ])));
+
+    for (auth, expected_err) in [
+        (None, Some(401)),
+        (Some("proposer-key"), Some(403)),
+        (Some("signer-key"), None),
+    ] {
+        let mut request = StacksHttpRequest::new_post_stackerdb_chunk(
+            addr.into(),
+            QualifiedContractIdentifier::parse(
+                "ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R.hello-world-unconfirmed",
+            )
+            .unwrap(),
+            0,
+            1,
+            MessageSignature::empty(),
+            vec![0, 1, 2, 3, 4],
+        );
+        if let Some(auth) = auth {
+            request.add_header("authorization".into(), auth.into());
+        }
+        let bytes = request.try_serialize().unwrap();
+        let (parsed_preamble, offset) = http.read_preamble(&bytes).unwrap();
+        let result = http.handle_try_parse_request(
+            &mut handler,
+            &parsed_preamble.expect_request(),
+            &bytes[offset..],
+        );
+        match (result, expected_err) {
+            (Ok(_), None) => {}
+            (
+                Err(crate::net::Error::Http(crate::net::http::Error::Http(err_code, _))),
+                Some(expected_code),
+            ) => {
+                assert_eq!(err_code, expected_code);
+            }
+            (x, _) => panic!("Unexpected result with authorization {auth:?}: {x:?}"),
+        }
+        handler.restart();
+    }
+}
+
 #[test]
 fn test_try_make_response() {
     let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 33333);
@@ -20,8 +20,7 @@ use std::net::SocketAddr;
 use std::{cmp, mem};
 
 use clarity::vm::types::QualifiedContractIdentifier;
-use rand;
-use rand::{thread_rng, Rng};
+use rand::{self, thread_rng, Rng};
 use stacks_common::types::net::PeerAddress;
 use stacks_common::types::StacksPublicKeyBuffer;
 use stacks_common::util::hash::to_hex;
@@ -37,8 +36,10 @@ use crate::core::{EpochList, StacksEpoch, PEER_VERSION_EPOCH_2_2, PEER_VERSION_E
 use crate::monitoring;
 use crate::net::connection::{ConnectionOptions, ConnectionP2P, ReplyHandleP2P};
 use crate::net::db::{PeerDB, *};
+use crate::net::neighbors::reputation::{PeerReputationEvent, NACK_FLOOD_WINDOW};
 use crate::net::neighbors::MAX_NEIGHBOR_BLOCK_DELAY;
 use crate::net::p2p::PeerNetwork;
+use crate::net::session::PendingSession;
 use crate::net::{
     Error as net_error, GetBlocksInv, GetPoxInv, Neighbor, NeighborKey, StacksMessage, StacksP2P,
     GETPOXINV_MAX_BITLEN, *,
@@ -116,6 +117,8 @@ pub struct NeighborStats {
     /// (timestamp, num bytes)
     pub nakamoto_block_push_rx_counts: VecDeque<(u64, u64)>,
     pub relayed_messages: HashMap<NeighborAddress, RelayStats>,
+    /// when we received each NACK in the last `NACK_FLOOD_WINDOW` seconds
+    pub nack_rx_times: VecDeque<u64>,
 }
 
 impl NeighborStats {
@@ -141,6 +144,7 @@ impl NeighborStats {
             stackerdb_push_rx_counts: VecDeque::new(),
             nakamoto_block_push_rx_counts: VecDeque::new(),
             relayed_messages: HashMap::new(),
+            nack_rx_times: VecDeque::new(),
         }
     }
 
@@ -213,6 +217,25 @@ impl NeighborStats {
         }
     }
 
+    /// Record that we just received a NACK.
+    /// NACKs received more than `NACK_FLOOD_WINDOW` seconds ago are forgotten.
+    pub fn add_nack(&mut self) {
+        let now = get_epoch_time_secs();
+        self.nack_rx_times.push_back(now);
+        while self
+            .nack_rx_times
+            .front()
+            .is_some_and(|time| time + NACK_FLOOD_WINDOW < now)
+        {
+            self.nack_rx_times.pop_front();
+        }
+    }
+
+    /// How many NACKs has this peer sent us in the last `NACK_FLOOD_WINDOW` seconds?
+    pub fn get_recent_nack_count(&self) -> u64 {
+        self.nack_rx_times.len() as u64
+    }
+
     pub fn add_relayer(&mut self, addr: &NeighborAddress, num_bytes: u64) {
         if let Some(stats) = self.relayed_messages.get_mut(addr) {
             stats.num_messages += 1;
@@ -377,6 +400,13 @@ pub struct ConversationP2P {
     /// outbound replies
     pub reply_handles: VecDeque<ReplyHandleP2P>,
 
+    /// whether or not we have sent this peer a SessionInit (i.e. we encrypt what we send it)
+    pub sent_session_init: bool,
+    /// when we sent this peer a SessionInit
+    session_init_time: u64,
+    /// our half of the encrypted session, until we get the peer's SessionInit
+    pending_session: Option<PendingSession>,
+
     /// system epochs
     epochs: EpochList,
 }
@@ -581,6 +611,9 @@ impl ConversationP2P {
 
             stats: NeighborStats::new(outbound),
             reply_handles: VecDeque::new(),
+            sent_session_init: false,
+            session_init_time: 0,
+            pending_session: None,
 
             db_smart_contracts: vec![],
 
@@ -691,6 +724,12 @@ impl ConversationP2P {
         (peer_services & (ServiceFlags::STACKERDB as u16)) != 0
     }
 
+    /// Does the given services bitfield support encrypted sessions?  It will if it has the
+    /// ENCRYPTED bit set
+    pub fn supports_encryption(peer_services: u16) -> bool {
+        (peer_services & (ServiceFlags::ENCRYPTED as u16)) != 0
+    }
+
     /// Does this remote neighbor support a particular StackerDB?
     pub fn replicates_stackerdb(&self, db: &QualifiedContractIdentifier) -> bool {
         for cid in self.db_smart_contracts.iter() {
@@ -1375,6 +1414,103 @@ impl ConversationP2P {
         Ok(())
     }
 
+    /// Start encrypting everything we send to this peer, if we both advertised support for it
+    /// and we have not done so already.  Queues a signed SessionInit; the messages queued after it
+    /// are encrypted, and are held back until we have the peer's SessionInit.
+    /// Called once the handshake completes, so we know the peer's services and public key.
+    fn try_start_session(&mut self, network: &PeerNetwork) -> Result<(), net_error> {
+        if self.sent_session_init
+            || !ConversationP2P::supports_encryption(network.get_local_peer().services)
+            || !ConversationP2P::supports_encryption(self.peer_services)
+        {
+            return Ok(());
+        }
+        // the peer's key is needed to derive the session keys from its SessionInit
+        if self.connection.get_public_key().is_none() {
+            return Ok(());
+        }
+
+        let private_key = &network.get_local_peer().private_key;
+        let (pending_session, session_init) = PendingSession::new();
+        let mut msg = StacksMessage::from_chain_view(
+            self.version,
+            self.network_id,
+            network.get_chain_view(),
+            StacksMessageType::SessionInit(session_init),
+        );
+        msg.sign(self.next_seq(), private_key)?;
+
+        let mut handle = self.connection.make_session_handle(self.conn_id)?;
+        let buf = msg.serialize_to_vec();
+        handle.write_all(&buf).map_err(net_error::WriteError)?;
+
+        self.stats.msgs_tx += 1;
+        self.reply_handles.push_back(handle);
+        self.sent_session_init = true;
+        self.session_init_time = get_epoch_time_secs();
+        self.pending_session = Some(pending_session);
+
+        debug!(
+            "{:?}: Sent SessionInit; encrypting everything we send from here",
+            &self
+        );
+        Ok(())
+    }
+
+    /// Has this peer failed to send its SessionInit within `timeout` seconds of us sending ours?
+    /// If so, it should be disconnected, since we cannot send it anything else until it does.
+    pub fn is_session_overdue(&self, now: u64, timeout: u64) -> bool {
+        self.sent_session_init
+            && !self.connection.is_inbound_encrypted()
+            && self.session_init_time.saturating_add(timeout) < now
+    }
+
+    /// Handle an inbound, authenticated SessionInit: derive the keys for everything we send
+    /// after our SessionInit and everything the peer sends after its, and start encrypting and
+    /// decrypting.
+    /// Returns net_error::SessionError if we did not negotiate an encrypted session with this
+    /// peer, or if it already started one.  The conversation cannot continue in either case,
+    /// since we cannot read anything else the peer sends.
+    fn handle_session_init(
+        &mut self,
+        network: &PeerNetwork,
+        msg: &StacksMessage,
+        session_init: &SessionInitData,
+    ) -> Result<(), net_error> {
+        let local_peer = network.get_local_peer();
+        if !ConversationP2P::supports_encryption(local_peer.services)
+            || !ConversationP2P::supports_encryption(self.peer_services)
+        {
+            return Err(net_error::SessionError(
+                "Peer started an encrypted session we did not negotiate".to_string(),
+            ));
+        }
+        // the peer can get its SessionInit to us before we have started our half of the session
+        self.try_start_session(network)?;
+        let pending_session = self.pending_session.take().ok_or_else(|| {
+            net_error::SessionError("Peer already started an encrypted session".to_string())
+        })?;
+        let peer_pubkey = self
+            .connection
+            .get_public_key()
+            .ok_or_else(|| net_error::SessionError("No public key for peer".to_string()))?;
+
+        // the connection may not have known the peer's key when it parsed this message (e.g. if
+        // it arrived together with the HandshakeAccept), so check the signature here
+        msg.verify_secp256k1(&StacksPublicKeyBuffer::from_public_key(&peer_pubkey))?;
+
+        let (outbound_cipher, inbound_cipher) =
+            pending_session.finish(&local_peer.private_key, &peer_pubkey, session_init)?;
+        self.connection.set_outbound_session(outbound_cipher)?;
+        self.connection.set_inbound_session(inbound_cipher)?;
+
+        debug!(
+            "{:?}: Got SessionInit; decrypting everything we receive from here",
+            &self
+        );
+        Ok(())
+    }
+
     /// Reply to a ping with a pong.
     /// Called from the p2p network thread.
     fn handle_ping(
@@ -2578,6 +2714,11 @@ impl ConversationP2P {
                 debug!("{:?}: Got NatPunchReply({})", &self, _m.nonce);
                 Ok(None)
             }
+            StacksMessageType::SessionInit(ref data) => {
+                debug!("{:?}: Got SessionInit", &self);
+                consume = true;
+                self.handle_session_init(network, msg, data).map(|_| None)
+            }
             _ => {
                 debug!(
                     "{:?}: Got a data-plane message (type {})",
@@ -2690,6 +2831,14 @@ impl ConversationP2P {
                 // it's okay to forward this back (i.e. don't consume)
                 Ok(None)
             }
+            StacksMessageType::SessionInit(_) => {
+                // we can't derive the key without knowing who this is, and we can't read anything
+                // else the peer sends without the key.
+                debug!("{:?}: Got unauthenticated SessionInit", &self);
+                Err(net_error::SessionError(
+                    "Peer started an encrypted session before handshaking".to_string(),
+                ))
+            }
             _ => {
                 debug!(
                     "{:?}: Got unauthenticated message (type {}), will NACK",
@@ -2921,19 +3070,45 @@ impl ConversationP2P {
         debug!("{:?}: {} messages pending", &self, num_inbound);
 
         let mut unsolicited = vec![];
-        for _ in 0..num_inbound {
+        // NOTE: handling a SessionInit can add messages to the inbox (the ones the peer encrypted
+        // after it), so keep going until the inbox is empty.
+        while let Some(mut msg) = self.connection.next_inbox_message() {
             let update_stats; // whether or not this message can count towards this peer's liveness stats
-            let mut msg = match self.connection.next_inbox_message() {
-                None => {
-                    continue;
-                }
-                Some(m) => m,
-            };
 
             if !self.validate_inbound_message(&msg, network.get_chain_view())? {
+                if let StacksMessageType::SessionInit(_) = msg.payload {
+                    // can't skip this one -- everything after it is encrypted
+                    return Err(net_error::SessionError(
+                        "Peer sent an invalid SessionInit".to_string(),
+                    ));
+                }
                 continue;
             }
 
+            if self.sent_session_init
+                && !self.connection.is_inbound_encrypted()
+                && !matches!(
+                    msg.payload,
+                    StacksMessageType::Handshake(_)
+                        | StacksMessageType::HandshakeAccept(_)
+                        | StacksMessageType::StackerDBHandshakeAccept(..)
+                        | StacksMessageType::SessionInit(_)
+                )
+            {
+                // we negotiated an encrypted session, so the peer must not send us anything else
+                // in the clear
+                info!(
+                    "{:?}: Received {} in the clear after negotiating an encrypted session; dropping connection",
+                    &self,
+                    msg.payload.get_message_name()
+                );
+                self.stats.msgs_err += 1;
+                return Err(net_error::SessionError(format!(
+                    "Peer sent {} before starting its encrypted session",
+                    msg.payload.get_message_name()
+                )));
+            }
+
             let (mut reply_opt, consumed) = if self.connection.has_public_key() {
                 // we already have this remote peer's public key, so the message signature will
                 // have been verified by the underlying ConnectionP2P.
@@ -2948,6 +3123,20 @@ impl ConversationP2P {
                 self.handle_unauthenticated_control_message(network, &mut msg, ibd)?
             };
 
+            // did this message complete a handshake?  If so, we may start encrypting.
+            let handshake_completed = match msg.payload {
+                StacksMessageType::Handshake(_) => reply_opt.as_ref().is_some_and(|reply| {
+                    matches!(
+                        reply.payload,
+                        StacksMessageType::HandshakeAccept(_)
+                            | StacksMessageType::StackerDBHandshakeAccept(..)
+                    )
+                }),
+                StacksMessageType::HandshakeAccept(_)
+                | StacksMessageType::StackerDBHandshakeAccept(..) => true,
+                _ => false,
+            };
+
             if let Some(mut reply) = reply_opt.take() {
                 // handler generated a reply.
                 // send back this message to the remote peer.
@@ -2961,8 +3150,18 @@ impl ConversationP2P {
                 self.reply_handles.push_back(reply_handle);
             }
 
+            if handshake_completed {
+                self.try_start_session(network)?;
+            }
+
             self.update_stats(&msg, update_stats);
 
+            if self.connection.has_public_key() {
+                if let StacksMessageType::Nack(_) = msg.payload {
+                    self.check_nack_flood(network);
+                }
+            }
+
             let _msgtype = msg.payload.get_message_description().to_owned();
             let _relayers = format!("{:?}", &msg.relayers);
             let _seq = msg.request_id();
@@ -3012,6 +3211,28 @@ impl ConversationP2P {
         Ok(unsolicited)
     }
 
+    /// Count a NACK from the remote peer.  If it has sent more than
+    /// `reputation_nack_flood_threshold` of them lately, then penalize it and start counting
+    /// again.
+    fn check_nack_flood(&mut self, network: &mut PeerNetwork) {
+        let threshold = network
+            .get_connection_opts()
+            .reputation_nack_flood_threshold;
+        if threshold == 0 {
+            return;
+        }
+        self.stats.add_nack();
+        if self.stats.get_recent_nack_count() <= threshold {
+            return;
+        }
+        debug!(
+            "{:?}: Received more than {} NACKs in {}s",
+            &self, threshold, NACK_FLOOD_WINDOW
+        );
+        self.stats.nack_rx_times.clear();
+        network.penalize_neighbor(&self.to_neighbor_key(), PeerReputationEvent::NackFlood);
+    }
+
     /// Remove all timed-out messages, and ding the remote peer as unhealthy
     pub fn clear_timeouts(&mut self) {
         let num_drained = self.connection.drain_timeouts();
@@ -3061,6 +3282,7 @@ mod test {
     const STACKERDB_SERVICES: u16 = (ServiceFlags::RELAY as u16)
         | (ServiceFlags::RPC as u16)
         | (ServiceFlags::STACKERDB as u16);
+    const ENCRYPTED_SERVICES: u16 = DEFAULT_SERVICES | (ServiceFlags::ENCRYPTED as u16);
 
     fn make_test_chain_dbs(
         testname: &str,
@@ -4758,6 +4980,366 @@ mod test {
         }
     }
 
+    /// Two peers handshake and then exchange a ping and pong.  The traffic after the handshake is
+    /// encrypted if and only if both peers advertise `ServiceFlags::ENCRYPTED`.
+    fn inner_convo_encrypted_session(test_name: &str, services_1: u16, services_2: u16) {
+        let conn_opts = ConnectionOptions::default();
+        let socketaddr_1 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+        let socketaddr_2 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 8081);
+
+        let mut chain_view = BurnchainView {
+            burn_block_height: 12348,
+            burn_block_hash: BurnchainHeaderHash([0x11; 32]),
+            burn_stable_block_height: 12341,
+            burn_stable_block_hash: BurnchainHeaderHash([0x22; 32]),
+            last_burn_block_hashes: HashMap::new(),
+            rc_consensus_hash: ConsensusHash([0x33; 20]),
+        };
+        chain_view.make_test_data();
+
+        let test_name_1 = format!("{test_name}_1");
+        let test_name_2 = format!("{test_name}_2");
+
+        let burnchain_1 = testing_burnchain_config(&test_name_1);
+        let burnchain_2 = testing_burnchain_config(&test_name_2);
+
+        let (mut peerdb_1, mut sortdb_1, _stackerdbs_1, _pox_id_1, mut chainstate_1) =
+            make_test_chain_dbs(
+                &test_name_1,
+                &burnchain_1,
+                0x9abcdef0,
+                12350,
+                "http://peer1.com".into(),
+                &[],
+                &[],
+                services_1,
+            );
+        let (mut peerdb_2, mut sortdb_2, _stackerdbs_2, _pox_id_2, mut chainstate_2) =
+            make_test_chain_dbs(
+                &test_name_2,
+                &burnchain_2,
+                0x9abcdef0,
+                12351,
+                "http://peer2.com".into(),
+                &[],
+                &[],
+                services_2,
+            );
+
+        let mut net_1 = db_setup(
+            &test_name_1,
+            &burnchain_1,
+            0x9abcdef0,
+            &mut peerdb_1,
+            &mut sortdb_1,
+            &socketaddr_1,
+            &chain_view,
+        );
+        let mut net_2 = db_setup(
+            &test_name_2,
+            &burnchain_2,
+            0x9abcdef0,
+            &mut peerdb_2,
+            &mut sortdb_2,
+            &socketaddr_2,
+            &chain_view,
+        );
+
+        let local_peer_1 = PeerDB::get_local_peer(peerdb_1.conn()).unwrap();
+
+        let mut convo_1 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain_1,
+            &socketaddr_2,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+        let mut convo_2 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain_2,
+            &socketaddr_1,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+
+        // convo_1 handshakes with convo_2
+        let handshake_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Handshake(HandshakeData::from_local_peer(&local_peer_1)),
+            )
+            .unwrap();
+        let mut rh_handshake_1 = convo_1.send_signed_request(handshake_1, 1000000).unwrap();
+
+        convo_send_recv(&mut convo_1, vec![&mut rh_handshake_1], &mut convo_2);
+        convo_2
+            .chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false)
+            .unwrap();
+
+        convo_send_recv(&mut convo_2, vec![&mut rh_handshake_1], &mut convo_1);
+        convo_1
+            .chat(&mut net_1, &sortdb_1, &mut chainstate_1, &mut None, false)
+            .unwrap();
+
+        let reply_handshake_1 = rh_handshake_1.recv(0).unwrap();
+        assert!(matches!(
+            reply_handshake_1.payload,
+            StacksMessageType::HandshakeAccept(_)
+        ));
+
+        // convo_1 pings convo_2, and gets a pong back
+        let ping_data_1 = PingData::new();
+        let ping_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Ping(ping_data_1.clone()),
+            )
+            .unwrap();
+        let mut rh_ping_1 = convo_1.send_signed_request(ping_1, 1000000).unwrap();
+
+        convo_send_recv(&mut convo_1, vec![&mut rh_ping_1], &mut convo_2);
+        convo_2
+            .chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false)
+            .unwrap();
+
+        convo_send_recv(&mut convo_2, vec![&mut rh_ping_1], &mut convo_1);
+        convo_1
+            .chat(&mut net_1, &sortdb_1, &mut chainstate_1, &mut None, false)
+            .unwrap();
+
+        let reply_ping_1 = rh_ping_1.recv(0).unwrap();
+        let StacksMessageType::Pong(ref data) = reply_ping_1.payload else {
+            panic!("Unexpected payload message type");
+        };
+        assert_eq!(data.nonce, ping_data_1.nonce);
+
+        let encrypted = ConversationP2P::supports_encryption(services_1)
+            && ConversationP2P::supports_encryption(services_2);
+        for convo in [&convo_1, &convo_2] {
+            assert_eq!(convo.sent_session_init, encrypted);
+            assert_eq!(convo.connection.is_outbound_encrypted(), encrypted);
+            assert_eq!(convo.connection.is_inbound_encrypted(), encrypted);
+        }
+    }
+
+    #[test]
+    /// Two peers that both support encryption encrypt everything after the handshake
+    fn convo_encrypted_session() {
+        inner_convo_encrypted_session(
+            "convo_encrypted_session",
+            ENCRYPTED_SERVICES,
+            ENCRYPTED_SERVICES,
+        );
+    }
+
+    #[test]
+    /// A peer that supports encryption still talks in the clear to a peer that does not
+    fn convo_encrypted_session_legacy() {
+        inner_convo_encrypted_session(
+            "convo_encrypted_session_legacy",
+            ENCRYPTED_SERVICES,
+            DEFAULT_SERVICES,
+        );
+        inner_convo_encrypted_session(
+            "convo_encrypted_session_legacy_reverse",
+            DEFAULT_SERVICES,
+            ENCRYPTED_SERVICES,
+        );
+    }
+
+    #[test]
+    /// A peer that negotiates an encrypted session but never sends its SessionInit gets nothing
+    /// more from us, is disconnected for anything it sends in the clear, and is overdue once the
+    /// handshake timeout passes.
+    fn convo_encrypted_session_suppressed_session_init() {
+        let conn_opts = ConnectionOptions::default();
+        let socketaddr_1 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080);
+        let socketaddr_2 = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)), 8081);
+
+        let mut chain_view = BurnchainView {
+            burn_block_height: 12348,
+            burn_block_hash: BurnchainHeaderHash([0x11; 32]),
+            burn_stable_block_height: 12341,
+            burn_stable_block_hash: BurnchainHeaderHash([0x22; 32]),
+            last_burn_block_hashes: HashMap::new(),
+            rc_consensus_hash: ConsensusHash([0x33; 20]),
+        };
+        chain_view.make_test_data();
+
+        let test_name_1 = "convo_encrypted_session_suppressed_session_init_1";
+        let test_name_2 = "convo_encrypted_session_suppressed_session_init_2";
+
+        let burnchain_1 = testing_burnchain_config(test_name_1);
+        let burnchain_2 = testing_burnchain_config(test_name_2);
+
+        let (mut peerdb_1, mut sortdb_1, _stackerdbs_1, _pox_id_1, mut chainstate_1) =
+            make_test_chain_dbs(
+                test_name_1,
+                &burnchain_1,
+                0x9abcdef0,
+                12350,
+                "http://peer1.com".into(),
+                &[],
+                &[],
+                ENCRYPTED_SERVICES,
+            );
+        let (mut peerdb_2, mut sortdb_2, _stackerdbs_2, _pox_id_2, mut chainstate_2) =
+            make_test_chain_dbs(
+                test_name_2,
+                &burnchain_2,
+                0x9abcdef0,
+                12351,
+                "http://peer2.com".into(),
+                &[],
+                &[],
+                ENCRYPTED_SERVICES,
+            );
+
+        let mut net_1 = db_setup(
+            test_name_1,
+            &burnchain_1,
+            0x9abcdef0,
+            &mut peerdb_1,
+            &mut sortdb_1,
+            &socketaddr_1,
+            &chain_view,
+        );
+        let mut net_2 = db_setup(
+            test_name_2,
+            &burnchain_2,
+            0x9abcdef0,
+            &mut peerdb_2,
+            &mut sortdb_2,
+            &socketaddr_2,
+            &chain_view,
+        );
+
+        let local_peer_1 = PeerDB::get_local_peer(peerdb_1.conn()).unwrap();
+        let local_peer_2 = PeerDB::get_local_peer(peerdb_2.conn()).unwrap();
+
+        let mut convo_1 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain_1,
+            &socketaddr_2,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+        let mut convo_2 = ConversationP2P::new(
+            123,
+            456,
+            &burnchain_2,
+            &socketaddr_1,
+            &conn_opts,
+            true,
+            0,
+            StacksEpoch::unit_test_pre_2_05(0),
+        );
+
+        // convo_1 handshakes with convo_2
+        let handshake_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Handshake(HandshakeData::from_local_peer(&local_peer_1)),
+            )
+            .unwrap();
+        let mut rh_handshake_1 = convo_1.send_signed_request(handshake_1, 1000000).unwrap();
+
+        convo_send_recv(&mut convo_1, vec![&mut rh_handshake_1], &mut convo_2);
+        convo_2
+            .chat(&mut net_2, &sortdb_2, &mut chainstate_2, &mut None, false)
+            .unwrap();
+
+        // convo_2 replies with a HandshakeAccept and a SessionInit, but the SessionInit is lost
+        let mut wire = vec![];
+        for _ in 0..10 {
+            convo_2.send(&mut wire).unwrap();
+        }
+        let accept = StacksMessage::consensus_deserialize(&mut &wire[..]).unwrap();
+        assert!(matches!(
+            accept.payload,
+            StacksMessageType::HandshakeAccept(_)
+        ));
+        let accept_len = accept.serialize_to_vec().len();
+        let session_init = StacksMessage::consensus_deserialize(&mut &wire[accept_len..]).unwrap();
+        assert!(matches!(
+            session_init.payload,
+            StacksMessageType::SessionInit(_)
+        ));
+        wire.truncate(accept_len);
+
+        convo_1
+            .recv(&mut crate::net::test::NetCursor::new(wire.as_slice()))
+            .unwrap();
+        convo_1
+            .chat(&mut net_1, &sortdb_1, &mut chainstate_1, &mut None, false)
+            .unwrap();
+        let reply_handshake_1 = rh_handshake_1.recv(0).unwrap();
+        assert!(matches!(
+            reply_handshake_1.payload,
+            StacksMessageType::HandshakeAccept(_)
+        ));
+        assert!(convo_1.sent_session_init);
+
+        // convo_1 sends its own SessionInit, but holds back everything after it
+        let ping_1 = convo_1
+            .sign_message(
+                &chain_view,
+                &local_peer_1.private_key,
+                StacksMessageType::Ping(PingData::new()),
+            )
+            .unwrap();
+        let mut rh_ping_1 = convo_1.send_signed_request(ping_1, 1000000).unwrap();
+        rh_ping_1.try_flush().unwrap();
+
+        let mut wire = vec![];
+        for _ in 0..10 {
+            convo_1.send(&mut wire).unwrap();
+        }
+        let sent = StacksMessage::consensus_deserialize(&mut &wire[..]).unwrap();
+        assert!(matches!(sent.payload, StacksMessageType::SessionInit(_)));
+        assert_eq!(sent.serialize_to_vec().len(), wire.len());
+        assert_eq!(convo_1.connection.outbox_len(), 1);
+        assert!(!convo_1.connection.is_outbound_encrypted());
+        assert!(!convo_1.connection.is_inbound_encrypted());
+
+        // the session is overdue once the handshake timeout passes
+        let now = get_epoch_time_secs();
+        assert!(!convo_1.is_session_overdue(now, conn_opts.handshake_timeout));
+        assert!(convo_1.is_session_overdue(
+            now + conn_opts.handshake_timeout + 1,
+            conn_opts.handshake_timeout
+        ));
+
+        // anything convo_2 sends in the clear gets it disconnected
+        let ping_2 = convo_2
+            .sign_message(
+                &chain_view,
+                &local_peer_2.private_key,
+                StacksMessageType::Ping(PingData::new()),
+            )
+            .unwrap();
+        let wire = ping_2.serialize_to_vec();
+        convo_1
+            .recv(&mut crate::net::test::NetCursor::new(wire.as_slice()))
+            .unwrap();
+        assert!(matches!(
+            convo_1.chat(&mut net_1, &sortdb_1, &mut chainstate_1, &mut None, false),
+            Err(net_error::SessionError(..))
+        ));
+    }
+
     #[test]
     fn convo_handshake_ping_loop() {
         let conn_opts = ConnectionOptions::default();
@@ -774,6 +774,20 @@ impl StacksMessageCodec for NatPunchData {
     }
 }
 
+impl StacksMessageCodec for SessionInitData {
+    fn consensus_serialize<W: Write>(&self, fd: &mut W) -> Result<(), codec_error> {
+        write_next(fd, &self.ephemeral_public_key)?;
+        Ok(())
+    }
+
+    fn consensus_deserialize<R: Read>(fd: &mut R) -> Result<SessionInitData, codec_error> {
+        let ephemeral_public_key: StacksPublicKeyBuffer = read_next(fd)?;
+        Ok(SessionInitData {
+            ephemeral_public_key,
+        })
+    }
+}
+
 fn contract_id_consensus_serialize<W: Write>(
     fd: &mut W,
     cid: &QualifiedContractIdentifier,
@@ -966,6 +980,7 @@ impl StacksMessageType {
             StacksMessageType::GetNakamotoInv(ref _m) => StacksMessageID::GetNakamotoInv,
             StacksMessageType::NakamotoInv(ref _m) => StacksMessageID::NakamotoInv,
             StacksMessageType::NakamotoBlocks(ref _m) => StacksMessageID::NakamotoBlocks,
+            StacksMessageType::SessionInit(ref _m) => StacksMessageID::SessionInit,
         }
     }
 
@@ -1001,6 +1016,7 @@ impl StacksMessageType {
             StacksMessageType::GetNakamotoInv(ref _m) => "GetNakamotoInv",
             StacksMessageType::NakamotoInv(ref _m) => "NakamotoInv",
             StacksMessageType::NakamotoBlocks(ref _m) => "NakamotoBlocks",
+            StacksMessageType::SessionInit(ref _m) => "SessionInit",
         }
     }
 
@@ -1117,6 +1133,9 @@ impl StacksMessageType {
                         .collect::<Vec<_>>()
                 )
             }
+            StacksMessageType::SessionInit(ref m) => {
+                format!("SessionInit({})", to_hex(m.ephemeral_public_key.as_bytes()))
+            }
         }
     }
 }
@@ -1169,6 +1188,7 @@ impl StacksMessageCodec for StacksMessageID {
             x if x == StacksMessageID::GetNakamotoInv as u8 => StacksMessageID::GetNakamotoInv,
             x if x == StacksMessageID::NakamotoInv as u8 => StacksMessageID::NakamotoInv,
             x if x == StacksMessageID::NakamotoBlocks as u8 => StacksMessageID::NakamotoBlocks,
+            x if x == StacksMessageID::SessionInit as u8 => StacksMessageID::SessionInit,
             _ => {
                 return Err(codec_error::DeserializeError(
                     "Unknown message ID".to_string(),
@@ -1214,6 +1234,7 @@ impl StacksMessageCodec for StacksMessageType {
             StacksMessageType::GetNakamotoInv(ref m) => write_next(fd, m)?,
             StacksMessageType::NakamotoInv(ref m) => write_next(fd, m)?,
             StacksMessageType::NakamotoBlocks(ref m) => write_next(fd, m)?,
+            StacksMessageType::SessionInit(ref m) => write_next(fd, m)?,
         }
         Ok(())
     }
@@ -1328,6 +1349,10 @@ impl StacksMessageCodec for StacksMessageType {
                 let m: NakamotoBlocksData = read_next(fd)?;
                 StacksMessageType::NakamotoBlocks(m)
             }
+            StacksMessageID::SessionInit => {
+                let m: SessionInitData = read_next(fd)?;
+                StacksMessageType::SessionInit(m)
+            }
             StacksMessageID::Reserved => {
                 return Err(codec_error::DeserializeError(
                     "Unsupported message ID 'reserved'".to_string(),
@@ -1596,6 +1621,11 @@ impl ProtocolFamily for StacksP2P {
     ) -> Result<(), net_error> {
         message.consensus_serialize(fd).map_err(|e| e.into())
     }
+
+    /// Everything a peer sends after its SessionInit is encrypted
+    fn starts_encrypted_session(&self, message: &StacksMessage) -> bool {
+        matches!(message.payload, StacksMessageType::SessionInit(..))
+    }
 }
 
 #[cfg(test)]
@@ -2472,6 +2502,21 @@ pub mod test {
         let _ = NakamotoInvData::consensus_deserialize(&mut &nakamoto_inv_bytes[..]).unwrap_err();
     }
 
+    #[test]
+    fn codec_SessionInit() {
+        let data = SessionInitData {
+            ephemeral_public_key: StacksPublicKeyBuffer::from_bytes(
+                &hex_bytes("034e316be04870cef1795fba64d581cf64bad0c894b01a068fb9edf85321dcd9bb")
+                    .unwrap(),
+            )
+            .unwrap(),
+        };
+        let bytes = hex_bytes("034e316be04870cef1795fba64d581cf64bad0c894b01a068fb9edf85321dcd9bb")
+            .unwrap();
+
+        check_codec_and_corruption::<SessionInitData>(&data, &bytes);
+    }
+
     #[test]
     fn codec_StacksMessage() {
         let payloads: Vec<StacksMessageType> = vec![
@@ -2668,6 +2713,15 @@ pub mod test {
                     true, true, true, true, true, true, true, true].as_slice()
                 ).unwrap()
             }),
+            StacksMessageType::SessionInit(SessionInitData {
+                ephemeral_public_key: StacksPublicKeyBuffer::from_bytes(
+                    &hex_bytes(
+                        "034e316be04870cef1795fba64d581cf64bad0c894b01a068fb9edf85321dcd9bb",
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            }),
         ];
 
         let mut maximal_relayers: Vec<RelayData> = vec![];
@@ -34,6 +34,9 @@ use crate::chainstate::stacks::{StacksPrivateKey, StacksPublicKey};
 #[cfg(any(test, feature = "testing"))]
 use crate::core::NETWORK_P2P_PORT;
 use crate::net::asn::ASEntry4;
+use crate::net::neighbors::reputation::{
+    PeerReputation, PeerReputationEvent, MIN_REPUTATION_SCORE,
+};
 use crate::net::{Neighbor, NeighborAddress, NeighborKey, ServiceFlags};
 use crate::util_lib::db::{
     query_count, query_row, query_row_panic, query_rows, sqlite_open, tx_begin_immediate,
@@ -41,7 +44,7 @@ use crate::util_lib::db::{
 };
 use crate::util_lib::strings::UrlString;
 
-pub const PEERDB_VERSION: &str = "3";
+pub const PEERDB_VERSION: &str = "4";
 
 const NUM_SLOTS: usize = 8;
 
@@ -286,6 +289,32 @@ impl FromRow<Neighbor> for Neighbor {
     }
 }
 
+impl FromRow<PeerReputation> for PeerReputation {
+    fn from_row(row: &Row) -> Result<PeerReputation, db_error> {
+        let network_id: u32 = row.get_unwrap("network_id");
+        let addrbytes: PeerAddress = PeerAddress::from_column(row, "addrbytes")?;
+        let port: u16 = row.get_unwrap("port");
+        let score: f64 = row.get_unwrap("score");
+        let last_update = u64::from_column(row, "last_update")?;
+        let invalid_blocks = u64::from_column(row, "invalid_blocks")?;
+        let nack_floods = u64::from_column(row, "nack_floods")?;
+        let inventory_lies = u64::from_column(row, "inventory_lies")?;
+        let invalid_stackerdb_chunks = u64::from_column(row, "invalid_stackerdb_chunks")?;
+
+        Ok(PeerReputation {
+            network_id,
+            addrbytes,
+            port,
+            score,
+            last_update,
+            invalid_blocks,
+            nack_floods,
+            inventory_lies,
+            invalid_stackerdb_chunks,
+        })
+    }
+}
+
 // In what is likely an abuse of Sqlite, the peer database is structured such that the `frontier`
 // table stores peers keyed by a deterministically-chosen random "slot," instead of their IP/port.
 // (i.e. the slot is determined by a cryptographic the hash of the IP/port).  The reason for this
@@ -387,6 +416,24 @@ const PEERDB_SCHEMA_3: &[&str] = &[
     "UPDATE db_config SET version = 3;",
 ];
 
+const PEERDB_SCHEMA_4: &[&str] = &[
+    r#"
+    CREATE TABLE peer_reputation(
+        network_id INTEGER NOT NULL,
+        addrbytes TEXT NOT NULL,
+        port INTEGER NOT NULL,
+        score REAL NOT NULL,
+        last_update INTEGER NOT NULL,
+        invalid_blocks INTEGER NOT NULL,
+        nack_floods INTEGER NOT NULL,
+        inventory_lies INTEGER NOT NULL,
+        invalid_stackerdb_chunks INTEGER NOT NULL,
+        PRIMARY KEY(network_id,addrbytes,port)
+    );
+    "#,
+    "UPDATE db_config SET version = 4;",
+];
+
 #[derive(Debug)]
 pub struct PeerDB {
     pub conn: Connection,
@@ -533,6 +580,15 @@ impl PeerDB {
         Ok(())
     }
 
+    #[cfg_attr(test, mutants::skip)]
+    fn apply_schema_4(tx: &Transaction) -> Result<(), db_error> {
+        test_debug!("Apply schema 4 to peer DB");
+        for row_text in PEERDB_SCHEMA_4 {
+            tx.execute_batch(row_text).map_err(db_error::SqliteError)?;
+        }
+        Ok(())
+    }
+
     fn apply_schema_migrations(tx: &Transaction) -> Result<String, db_error> {
         test_debug!("Apply any schema migrations");
         let expected_version = PEERDB_VERSION.to_string();
@@ -547,6 +603,8 @@ impl PeerDB {
                         PeerDB::apply_schema_2(tx)?;
                     } else if version == "2" {
                         PeerDB::apply_schema_3(tx)?;
+                    } else if version == "3" {
+                        PeerDB::apply_schema_4(tx)?;
                     } else if version == expected_version {
                         return Ok(ret.expect("unreachable"));
                     } else {
@@ -1630,6 +1688,102 @@ impl PeerDB {
         Ok(())
     }
 
+    /// Get a peer's reputation, if it has ever misbehaved
+    pub fn get_peer_reputation(
+        conn: &DBConn,
+        network_id: u32,
+        peer_addr: &PeerAddress,
+        peer_port: u16,
+    ) -> Result<Option<PeerReputation>, db_error> {
+        let qry =
+            "SELECT * FROM peer_reputation WHERE network_id = ?1 AND addrbytes = ?2 AND port = ?3";
+        let args = params![network_id, peer_addr.to_bin(), peer_port];
+        query_row(conn, qry, args)
+    }
+
+    /// Get the reputations of all peers on this network that have ever misbehaved
+    pub fn get_all_peer_reputations(
+        conn: &DBConn,
+        network_id: u32,
+    ) -> Result<Vec<PeerReputation>, db_error> {
+        let qry = "SELECT * FROM peer_reputation WHERE network_id = ?1";
+        query_rows(conn, qry, params![network_id])
+    }
+
+    /// Store a peer's reputation, replacing any previous one
+    pub fn put_peer_reputation(
+        tx: &Transaction,
+        reputation: &PeerReputation,
+    ) -> Result<(), db_error> {
+        let args = params![
+            reputation.network_id,
+            reputation.addrbytes.to_bin(),
+            reputation.port,
+            reputation.score,
+            u64_to_sql(reputation.last_update)?,
+            u64_to_sql(reputation.invalid_blocks)?,
+            u64_to_sql(reputation.nack_floods)?,
+            u64_to_sql(reputation.inventory_lies)?,
+            u64_to_sql(reputation.invalid_stackerdb_chunks)?,
+        ];
+        tx.execute("INSERT OR REPLACE INTO peer_reputation (network_id, addrbytes, port, score, last_update, invalid_blocks, nack_floods, inventory_lies, invalid_stackerdb_chunks) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9)", args)
+            .map_err(db_error::SqliteError)?;
+        Ok(())
+    }
+
+    /// Add a misbehavior event that happened at time `now` to a peer's reputation.
+    /// Returns the peer's updated reputation.
+    pub fn record_peer_reputation_event(
+        tx: &Transaction,
+        neighbor: &NeighborKey,
+        event: PeerReputationEvent,
+        now: u64,
+        half_life_secs: u64,
+    ) -> Result<PeerReputation, db_error> {
+        let mut reputation = PeerDB::get_peer_reputation(
+            tx,
+            neighbor.network_id,
+            &neighbor.addrbytes,
+            neighbor.port,
+        )?
+        .unwrap_or_else(|| PeerReputation::new(neighbor));
+        reputation.record(event, now, half_life_secs);
+        PeerDB::put_peer_reputation(tx, &reputation)?;
+        Ok(reputation)
+    }
+
+    /// Forget the reputations of peers whose scores have decayed below `MIN_REPUTATION_SCORE`
+    /// by time `now`.  Returns the number of reputations removed.
+    pub fn prune_peer_reputations(
+        tx: &Transaction,
+        now: u64,
+        half_life_secs: u64,
+    ) -> Result<usize, db_error> {
+        if half_life_secs == 0 {
+            // scores never decay
+            return Ok(0);
+        }
+        let reputations: Vec<PeerReputation> =
+            query_rows(tx, "SELECT * FROM peer_reputation", NO_PARAMS)?;
+        let mut pruned = 0;
+        for reputation in reputations {
+            if reputation.score_at(now, half_life_secs) >= MIN_REPUTATION_SCORE {
+                continue;
+            }
+            tx.execute(
+                "DELETE FROM peer_reputation WHERE network_id = ?1 AND addrbytes = ?2 AND port = ?3",
+                params![
+                    reputation.network_id,
+                    reputation.addrbytes.to_bin(),
+                    reputation.port
+                ],
+            )
+            .map_err(db_error::SqliteError)?;
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+
     /// Get random neighbors, optionally always including allowed neighbors.
     /// Private IPs may be returned, if known.
     pub fn get_random_neighbors(
@@ -4188,4 +4342,93 @@ mod test {
             assert!(phase1_candidate_ports.contains(&port), "Peers for always_include_allowed=true with small count should come from Phase 1 candidates (epoch filtered)");
         }
     }
+
+    #[test]
+    fn test_peer_reputation() {
+        let mut db =
+            PeerDB::connect_memory(0x9abcdef0, 12345, 0, "http://foo.com".into(), &[], &[])
+                .unwrap();
+
+        let nk_1 = NeighborKey {
+            peer_version: 0x12345678,
+            network_id: 0x9abcdef0,
+            addrbytes: PeerAddress::from_ipv4(1, 2, 3, 4),
+            port: 12345,
+        };
+        let mut nk_2 = nk_1.clone();
+        nk_2.port = 12346;
+
+        assert!(PeerDB::get_peer_reputation(
+            db.conn(),
+            nk_1.network_id,
+            &nk_1.addrbytes,
+            nk_1.port
+        )
+        .unwrap()
+        .is_none());
+
+        let tx = db.tx_begin().unwrap();
+        let rep = PeerDB::record_peer_reputation_event(
+            &tx,
+            &nk_1,
+            PeerReputationEvent::InventoryLie,
+            1000,
+            100,
+        )
+        .unwrap();
+        assert_eq!(rep.score, 20.0);
+        assert_eq!(rep.inventory_lies, 1);
+
+        // one half-life later, the old score has halved before the new penalty is added
+        let rep = PeerDB::record_peer_reputation_event(
+            &tx,
+            &nk_1,
+            PeerReputationEvent::NackFlood,
+            1100,
+            100,
+        )
+        .unwrap();
+        assert_eq!(rep.score, 35.0);
+        assert_eq!(rep.last_update, 1100);
+        assert_eq!(rep.inventory_lies, 1);
+        assert_eq!(rep.nack_floods, 1);
+
+        PeerDB::record_peer_reputation_event(
+            &tx,
+            &nk_2,
+            PeerReputationEvent::InvalidBlock,
+            1100,
+            100,
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let rep =
+            PeerDB::get_peer_reputation(db.conn(), nk_1.network_id, &nk_1.addrbytes, nk_1.port)
+                .unwrap()
+                .unwrap();
+        assert_eq!(rep.score, 35.0);
+        assert_eq!(rep.nack_floods, 1);
+        assert_eq!(rep.invalid_blocks, 0);
+        assert_eq!(rep.score_at(1300, 100), 35.0 / 4.0);
+        assert_eq!(rep.score_at(1300, 0), 35.0);
+
+        let reps = PeerDB::get_all_peer_reputations(db.conn(), nk_1.network_id).unwrap();
+        assert_eq!(reps.len(), 2);
+        assert!(PeerDB::get_all_peer_reputations(db.conn(), 0x01020304)
+            .unwrap()
+            .is_empty());
+
+        // nk_1's score of 35 decays below 1 after six half-lives, and nk_2's score of 100 after
+        // seven
+        let tx = db.tx_begin().unwrap();
+        assert_eq!(PeerDB::prune_peer_reputations(&tx, 1600, 0).unwrap(), 0);
+        assert_eq!(PeerDB::prune_peer_reputations(&tx, 1600, 100).unwrap(), 0);
+        assert_eq!(PeerDB::prune_peer_reputations(&tx, 1700, 100).unwrap(), 1);
+        tx.commit().unwrap();
+
+        let reps = PeerDB::get_all_peer_reputations(db.conn(), nk_1.network_id).unwrap();
+        assert_eq!(reps.len(), 1);
+        assert_eq!(reps[0].port, nk_2.port);
+    }
 }
@@ -22,9 +22,11 @@ use stacks_common::util::get_epoch_time_secs;
 use crate::chainstate::nakamoto::NakamotoBlock;
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::net::download::nakamoto::{AvailableTenures, NakamotoTenureDownloader, TenureStartEnd};
+use crate::net::neighbors::comms::ToNeighborKey;
+use crate::net::neighbors::reputation::PeerReputationEvent;
 use crate::net::neighbors::rpc::NeighborRPC;
 use crate::net::p2p::{CurrentRewardSet, DropReason, DropSource, PeerNetwork};
-use crate::net::NeighborAddress;
+use crate::net::{Error as NetError, NeighborAddress};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct CompletedTenure {
@@ -615,6 +617,12 @@ impl NakamotoTenureDownloaderSet {
                         "Failed to handle response from {naddr} on tenure {}: {e}",
                         &downloader.tenure_id_consensus_hash,
                     );
+                    if matches!(e, NetError::NotFoundError | NetError::InvalidMessage) {
+                        // the peer's inventory said it had this tenure, but it either doesn't
+                        // or it served us bad blocks for it
+                        let nk = naddr.to_neighbor_key(network);
+                        network.penalize_neighbor(&nk, PeerReputationEvent::InventoryLie);
+                    }
                     Self::mark_failed_and_deprioritize_peer(
                         &mut self.attempt_failed_tenures,
                         &mut self.deprioritized_peers,
@@ -0,0 +1,314 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fan-out of node events to RPC clients subscribed to the event stream endpoint.
+//!
+//! The node's event dispatcher publishes events into an `EventStreamHub`, and each event stream
+//! client holds an `EventStreamSubscription` with its own bounded queue.  Publishing never
+//! blocks on a slow client: once a client's queue is full, its oldest events are dropped and
+//! the client is told how many it missed.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Kinds of events that can be streamed to RPC clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StreamEventKind {
+    /// A new Stacks chain tip was processed
+    NewBlock,
+    /// A new tenure started (i.e. a block with a `BlockFound` tenure change was processed)
+    NewTenure,
+    /// A new burnchain block was processed
+    NewBurnBlock,
+    /// A transaction was admitted to the mempool
+    NewMempoolTx,
+}
+
+impl StreamEventKind {
+    pub const ALL: [StreamEventKind; 4] = [
+        StreamEventKind::NewBlock,
+        StreamEventKind::NewTenure,
+        StreamEventKind::NewBurnBlock,
+        StreamEventKind::NewMempoolTx,
+    ];
+
+    /// The name of this event, as used in the SSE `event:` field
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            StreamEventKind::NewBlock => "new_block",
+            StreamEventKind::NewTenure => "new_tenure",
+            StreamEventKind::NewBurnBlock => "new_burn_block",
+            StreamEventKind::NewMempoolTx => "new_mempool_tx",
+        }
+    }
+
+    /// Decode an event filter key.  Keys that also exist in `EventKeyType` (`*`, `burn_blocks`,
+    /// `memtx`) have the same meaning here.
+    pub fn from_filter_key(key: &str) -> Option<Vec<StreamEventKind>> {
+        match key {
+            "*" => Some(Self::ALL.to_vec()),
+            "blocks" => Some(vec![StreamEventKind::NewBlock]),
+            "tenures" => Some(vec![StreamEventKind::NewTenure]),
+            "burn_blocks" => Some(vec![StreamEventKind::NewBurnBlock]),
+            "memtx" => Some(vec![StreamEventKind::NewMempoolTx]),
+            _ => None,
+        }
+    }
+}
+
+/// A single event to be streamed
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    pub kind: StreamEventKind,
+    pub data: serde_json::Value,
+}
+
+impl StreamEvent {
+    pub fn new(kind: StreamEventKind, data: serde_json::Value) -> Self {
+        Self { kind, data }
+    }
+}
+
+/// An item read from a subscription
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem {
+    /// The next event
+    Event(StreamEvent),
+    /// This many events were dropped because the subscriber did not keep up
+    Lagged(u64),
+}
+
+/// Per-subscriber state shared between the hub and the subscription
+struct SubscriberQueue {
+    events: VecDeque<StreamEvent>,
+    capacity: usize,
+    dropped: u64,
+    closed: bool,
+}
+
+impl SubscriberQueue {
+    fn push(&mut self, event: StreamEvent) {
+        while self.events.len() >= self.capacity.max(1) {
+            self.events.pop_front();
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.events.push_back(event);
+    }
+}
+
+/// A handle to an event stream subscription.
+/// The subscription is removed from the hub once this is dropped.
+pub struct EventStreamSubscription {
+    queue: Arc<Mutex<SubscriberQueue>>,
+}
+
+impl EventStreamSubscription {
+    /// Take the next item off of the queue, if there is one.
+    /// A lag notice is always reported ahead of the events that follow the dropped ones.
+    pub fn next_item(&self) -> Option<StreamItem> {
+        let mut queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        if queue.dropped > 0 {
+            let dropped = queue.dropped;
+            queue.dropped = 0;
+            return Some(StreamItem::Lagged(dropped));
+        }
+        queue.events.pop_front().map(StreamItem::Event)
+    }
+
+    /// Is there anything to read?
+    pub fn has_pending(&self) -> bool {
+        let queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.dropped > 0 || !queue.events.is_empty()
+    }
+
+    /// Has the hub stopped sending events to this subscription?
+    /// Whatever is still pending can be read, but nothing new will arrive.
+    pub fn is_closed(&self) -> bool {
+        let queue = self.queue.lock().unwrap_or_else(|e| e.into_inner());
+        queue.closed
+    }
+}
+
+struct Subscriber {
+    kinds: HashSet<StreamEventKind>,
+    queue: Weak<Mutex<SubscriberQueue>>,
+}
+
+/// Registry of event stream subscribers.
+/// It is shared between the thread(s) that produce events and the RPC server that consumes them.
+#[derive(Default)]
+pub struct EventStreamHub {
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl EventStreamHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_subscribers(&self) -> std::sync::MutexGuard<'_, Vec<Subscriber>> {
+        let mut subscribers = self.subscribers.lock().unwrap_or_else(|e| e.into_inner());
+        subscribers.retain(|sub| sub.queue.strong_count() > 0);
+        subscribers
+    }
+
+    /// How many live subscriptions are there?
+    pub fn num_subscribers(&self) -> usize {
+        self.lock_subscribers().len()
+    }
+
+    /// Is anyone subscribed to events of this kind?
+    /// Producers can use this to avoid building events that nobody will receive.
+    pub fn has_subscribers(&self, kind: StreamEventKind) -> bool {
+        self.lock_subscribers()
+            .iter()
+            .any(|sub| sub.kinds.contains(&kind))
+    }
+
+    /// Subscribe to the given kinds of events, buffering at most `capacity` of them.
+    /// Returns None if there are already `max_subscribers` live subscriptions.
+    pub fn subscribe(
+        &self,
+        kinds: HashSet<StreamEventKind>,
+        capacity: usize,
+        max_subscribers: usize,
+    ) -> Option<EventStreamSubscription> {
+        let mut subscribers = self.lock_subscribers();
+        if subscribers.len() >= max_subscribers {
+            return None;
+        }
+        let queue = Arc::new(Mutex::new(SubscriberQueue {
+            events: VecDeque::new(),
+            capacity,
+            dropped: 0,
+            closed: false,
+        }));
+        subscribers.push(Subscriber {
+            kinds,
+            queue: Arc::downgrade(&queue),
+        });
+        Some(EventStreamSubscription { queue })
+    }
+
+    /// Close every subscription.  This is used when the hub is replaced, so that its subscribers
+    /// can reconnect to the new one.
+    pub fn close(&self) {
+        let mut subscribers = self.lock_subscribers();
+        for sub in subscribers.drain(..) {
+            let Some(queue) = sub.queue.upgrade() else {
+                continue;
+            };
+            queue.lock().unwrap_or_else(|e| e.into_inner()).closed = true;
+        }
+    }
+
+    /// Send an event to every subscriber interested in it
+    pub fn publish(&self, event: StreamEvent) {
+        for sub in self.lock_subscribers().iter() {
+            if !sub.kinds.contains(&event.kind) {
+                continue;
+            }
+            let Some(queue) = sub.queue.upgrade() else {
+                continue;
+            };
+            queue
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(event.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_event_stream_hub_filters_and_limits() {
+        let hub = EventStreamHub::new();
+        let blocks = hub
+            .subscribe(HashSet::from([StreamEventKind::NewBlock]), 4, 2)
+            .unwrap();
+        let all = hub
+            .subscribe(StreamEventKind::ALL.into_iter().collect(), 4, 2)
+            .unwrap();
+        assert_eq!(hub.num_subscribers(), 2);
+
+        // over the limit
+        assert!(hub
+            .subscribe(HashSet::from([StreamEventKind::NewBlock]), 4, 2)
+            .is_none());
+
+        assert!(hub.has_subscribers(StreamEventKind::NewMempoolTx));
+        let block = StreamEvent::new(StreamEventKind::NewBlock, json!({"block_height": 1}));
+        let tx = StreamEvent::new(StreamEventKind::NewMempoolTx, json!({"txid": "00"}));
+        hub.publish(block.clone());
+        hub.publish(tx.clone());
+
+        assert_eq!(blocks.next_item(), Some(StreamItem::Event(block.clone())));
+        assert_eq!(blocks.next_item(), None);
+        assert_eq!(all.next_item(), Some(StreamItem::Event(block)));
+        assert_eq!(all.next_item(), Some(StreamItem::Event(tx)));
+        assert_eq!(all.next_item(), None);
+
+        // dropping a subscription frees up a slot
+        drop(all);
+        assert_eq!(hub.num_subscribers(), 1);
+        assert!(!hub.has_subscribers(StreamEventKind::NewMempoolTx));
+        assert!(hub
+            .subscribe(HashSet::from([StreamEventKind::NewMempoolTx]), 4, 2)
+            .is_some());
+    }
+
+    #[test]
+    fn test_event_stream_hub_lagged_subscriber() {
+        let hub = EventStreamHub::new();
+        let sub = hub
+            .subscribe(HashSet::from([StreamEventKind::NewBurnBlock]), 2, 1)
+            .unwrap();
+        for height in 0..5 {
+            hub.publish(StreamEvent::new(
+                StreamEventKind::NewBurnBlock,
+                json!({ "burn_block_height": height }),
+            ));
+        }
+
+        assert!(sub.has_pending());
+        assert_eq!(sub.next_item(), Some(StreamItem::Lagged(3)));
+        assert_eq!(
+            sub.next_item(),
+            Some(StreamItem::Event(StreamEvent::new(
+                StreamEventKind::NewBurnBlock,
+                json!({ "burn_block_height": 3 })
+            )))
+        );
+        assert_eq!(
+            sub.next_item(),
+            Some(StreamItem::Event(StreamEvent::new(
+                StreamEventKind::NewBurnBlock,
+                json!({ "burn_block_height": 4 })
+            )))
+        );
+        assert_eq!(sub.next_item(), None);
+        assert!(!sub.has_pending());
+
+        assert!(!sub.is_closed());
+        hub.close();
+        assert!(sub.is_closed());
+        assert_eq!(hub.num_subscribers(), 0);
+    }
+}
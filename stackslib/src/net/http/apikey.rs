@@ -0,0 +1,134 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Named API keys for privileged RPC endpoints.
+//!
+//! Each key is sent verbatim in a request's `authorization` header, and grants only the scopes it
+//! was configured with.  The legacy `auth_token` is treated as a key named `auth_token` with the
+//! `admin` scope, which grants everything.
+
+use crate::net::connection::ConnectionOptions;
+use crate::net::http::{Error, HttpRequestPreamble};
+
+define_named_enum!(
+    /// The privileged operations an API key can be allowed to perform
+    HttpApiKeyScope {
+        /// Submit Nakamoto blocks for validation via `/v3/block_proposal`
+        BlockProposal("block-proposal"),
+        /// Upload and broadcast Nakamoto blocks via `/v3/blocks/upload?broadcast=1`
+        BlockUpload("block-upload"),
+        /// Write StackerDB chunks, if the node is configured to require a key for that
+        StackerDBWrite("stackerdb-write"),
+        /// Make read-only calls without cost limits via `/v3/contracts/fast-call-read`
+        UnlimitedReadOnly("unlimited-read-only"),
+        /// Make requests without being subject to the RPC server's rate limits
+        RateLimitExempt("rate-limit-exempt"),
+        /// Every other scope
+        Admin("admin"),
+    }
+);
+
+/// Name given to the key made from the legacy `auth_token` option
+pub const LEGACY_AUTH_TOKEN_KEY_NAME: &str = "auth_token";
+
+/// A single named API key
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpApiKey {
+    /// Name of the key, used only for logging
+    pub name: String,
+    /// The secret itself, as sent in the `authorization` header
+    pub key: String,
+    /// What the key may be used for
+    pub scopes: Vec<HttpApiKeyScope>,
+}
+
+impl HttpApiKey {
+    /// The key made from the legacy `auth_token` option, which may do anything
+    pub fn from_auth_token(auth_token: String) -> HttpApiKey {
+        HttpApiKey {
+            name: LEGACY_AUTH_TOKEN_KEY_NAME.into(),
+            key: auth_token,
+            scopes: vec![HttpApiKeyScope::Admin],
+        }
+    }
+
+    /// Does this key grant `scope`?
+    pub fn has_scope(&self, scope: HttpApiKeyScope) -> bool {
+        self.scopes
+            .iter()
+            .any(|s| *s == scope || *s == HttpApiKeyScope::Admin)
+    }
+}
+
+/// The set of API keys the RPC server accepts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HttpApiKeys {
+    keys: Vec<HttpApiKey>,
+}
+
+impl HttpApiKeys {
+    pub fn new(keys: Vec<HttpApiKey>) -> HttpApiKeys {
+        HttpApiKeys { keys }
+    }
+
+    /// All of the keys configured in `conn_opts`, including the legacy `auth_token`
+    pub fn from_connection_options(conn_opts: &ConnectionOptions) -> HttpApiKeys {
+        let mut keys = conn_opts.api_keys.clone();
+        if let Some(auth_token) = conn_opts.auth_token.as_ref() {
+            keys.push(HttpApiKey::from_auth_token(auth_token.clone()));
+        }
+        HttpApiKeys { keys }
+    }
+
+    /// Are there no keys at all?
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Does any key grant `scope`?  If not, endpoints that require it are disabled.
+    pub fn any_with_scope(&self, scope: HttpApiKeyScope) -> bool {
+        self.keys.iter().any(|key| key.has_scope(scope))
+    }
+
+    /// Find the key sent in this request's `authorization` header, if any
+    pub fn lookup(&self, preamble: &HttpRequestPreamble) -> Option<&HttpApiKey> {
+        let auth_header = preamble.headers.get("authorization")?;
+        self.keys.iter().find(|key| &key.key == auth_header)
+    }
+
+    /// Check that this request carries a key that grants `scope`.
+    /// Returns the key on success.
+    /// Returns Error::Http(401, ..) if the request carries no known key, and
+    /// Error::Http(403, ..) if its key does not grant `scope`.
+    pub fn authorize(
+        &self,
+        preamble: &HttpRequestPreamble,
+        scope: HttpApiKeyScope,
+    ) -> Result<&HttpApiKey, Error> {
+        let Some(key) = self.lookup(preamble) else {
+            return Err(Error::Http(401, "Unauthorized".into()));
+        };
+        if !key.has_scope(scope) {
+            return Err(Error::Http(
+                403,
+                format!(
+                    "Forbidden: API key does not have the '{}' scope",
+                    scope.get_name_str()
+                ),
+            ));
+        }
+        Ok(key)
+    }
+}
@@ -14,11 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+pub mod apikey;
 pub mod common;
 pub mod error;
+pub mod ratelimit;
 pub mod request;
 pub mod response;
 pub mod stream;
+pub mod tls;
 
 #[cfg(test)]
 mod tests;
@@ -31,6 +34,7 @@ use std::{fmt, io};
 use serde_json;
 use stacks_common::codec::Error as CodecError;
 
+pub use crate::net::http::apikey::{HttpApiKey, HttpApiKeyScope, HttpApiKeys};
 pub use crate::net::http::common::{
     parse_bytes, parse_bytestream, parse_json, HttpReservedHeader, HttpVersion,
     HTTP_PREAMBLE_MAX_NUM_HEADERS,
@@ -38,8 +42,9 @@ pub use crate::net::http::common::{
 pub use crate::net::http::error::{
     http_error_from_code_and_text, http_reason, HttpBadRequest, HttpError, HttpErrorResponse,
     HttpForbidden, HttpNotFound, HttpNotImplemented, HttpPaymentRequired, HttpRequestTimeout,
-    HttpServerError, HttpServiceUnavailable, HttpUnauthorized,
+    HttpServerError, HttpServiceUnavailable, HttpTooManyRequests, HttpUnauthorized,
 };
+pub use crate::net::http::ratelimit::{HttpRateLimitClass, HttpRateLimiter};
 pub use crate::net::http::request::{
     HttpRequest, HttpRequestContents, HttpRequestPayload, HttpRequestPreamble,
 };
@@ -47,6 +52,7 @@ pub use crate::net::http::response::{
     HttpResponse, HttpResponseContents, HttpResponsePayload, HttpResponsePreamble,
 };
 pub use crate::net::http::stream::HttpChunkGenerator;
+pub use crate::net::http::tls::{HttpTlsServer, HttpTlsStream};
 
 #[derive(Debug)]
 pub enum Error {
@@ -152,6 +158,8 @@ pub enum HttpContentType {
     Bytes,
     Text,
     JSON,
+    /// `text/event-stream`, used for server-sent events
+    EventStream,
 }
 
 impl fmt::Display for HttpContentType {
@@ -166,6 +174,7 @@ impl HttpContentType {
             HttpContentType::Bytes => "application/octet-stream",
             HttpContentType::Text => "text/plain",
             HttpContentType::JSON => "application/json",
+            HttpContentType::EventStream => "text/event-stream",
         }
     }
 }
@@ -181,6 +190,8 @@ impl FromStr for HttpContentType {
             Ok(HttpContentType::Text)
         } else if s == "application/json" || s.starts_with("application/json;") {
             Ok(HttpContentType::JSON)
+        } else if s == "text/event-stream" || s.starts_with("text/event-stream;") {
+            Ok(HttpContentType::EventStream)
         } else {
             Err(CodecError::DeserializeError(format!(
                 "Unsupported HTTP content type: {header}"
@@ -0,0 +1,273 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-client request budgets for the RPC server.
+//!
+//! Clients are grouped by the CIDR prefix of their address (so, for example, every address in an
+//! IPv6 /64 can be made to share one budget), and each (prefix, endpoint class) pair gets a token
+//! bucket.  A bucket holds at most `limit` tokens, and refills at `limit` tokens per window.  Each
+//! request spends one token, and a request that finds its bucket empty is throttled.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+use stacks_common::types::net::PeerAddress;
+
+use crate::net::connection::ConnectionOptions;
+
+define_named_enum!(
+    /// The classes of RPC endpoints that get separate per-client budgets
+    HttpRateLimitClass {
+        /// Read-only Clarity function calls
+        ReadOnlyCall("read_only_call"),
+        /// Transaction broadcasts
+        TxBroadcast("tx_broadcast"),
+        /// Block and microblock downloads
+        BlockFetch("block_fetch"),
+        /// Everything else
+        Other("other"),
+    }
+);
+
+/// Number of leading bits of a `PeerAddress` that hold the IPv4-mapped prefix
+const IPV4_MAPPED_PREFIX_BITS: u32 = 96;
+
+/// Mask off all but the first `mask` bits of `addr`.
+fn mask_address(addr: &PeerAddress, mask: u32) -> PeerAddress {
+    if mask == 0 {
+        return PeerAddress([0u8; 16]);
+    }
+    let addr_mask = if mask >= 128 {
+        u128::MAX
+    } else {
+        !((1u128 << (128 - mask)) - 1)
+    };
+    let masked = u128::from_be_bytes(addr.as_bytes().to_owned()) & addr_mask;
+    PeerAddress(masked.to_be_bytes())
+}
+
+/// Does the CIDR prefix `prefix`/`mask` (where `mask` counts bits of the 128-bit `PeerAddress`)
+/// contain `addr`?
+pub fn cidr_contains(prefix: &PeerAddress, mask: u32, addr: &PeerAddress) -> bool {
+    mask_address(prefix, mask) == mask_address(addr, mask)
+}
+
+/// Parse a CIDR prefix like `10.0.0.0/8` or `2001:db8::/32` into a `PeerAddress` and a mask over
+/// its 128 bits, as stored in the peer DB's allow and deny lists.  A bare IP address is treated
+/// as a prefix that contains only itself.
+pub fn parse_cidr(cidr: &str) -> Result<(PeerAddress, u32), String> {
+    let (ip_str, mask_str) = match cidr.split_once('/') {
+        Some((ip_str, mask_str)) => (ip_str, Some(mask_str)),
+        None => (cidr, None),
+    };
+    let ip = ip_str
+        .trim()
+        .parse::<IpAddr>()
+        .map_err(|e| format!("Invalid IP address in CIDR prefix '{cidr}': {e}"))?;
+    let max_mask = if ip.is_ipv4() { 32 } else { 128 };
+    let mask = match mask_str {
+        Some(mask_str) => mask_str
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid prefix length in CIDR prefix '{cidr}': {e}"))?,
+        None => max_mask,
+    };
+    if mask > max_mask {
+        return Err(format!(
+            "Invalid prefix length in CIDR prefix '{cidr}': must be at most {max_mask}"
+        ));
+    }
+    let mask = if ip.is_ipv4() {
+        mask + IPV4_MAPPED_PREFIX_BITS
+    } else {
+        mask
+    };
+    Ok((PeerAddress::from_ip(&ip), mask))
+}
+
+/// A single client's budget for a single class of endpoints
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    /// tokens left to spend
+    tokens: f64,
+    /// when `tokens` was last brought up to date
+    last_refill: Instant,
+}
+
+/// The size and refill rate of every bucket in a class
+#[derive(Debug, Clone, PartialEq)]
+struct ClassBudget {
+    /// most tokens a bucket can hold
+    capacity: f64,
+    /// tokens added to a bucket per second
+    refill_per_sec: f64,
+}
+
+impl ClassBudget {
+    /// Bring `bucket` up to date as of `now`
+    fn refill(&self, bucket: &mut TokenBucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+    }
+}
+
+/// Token-bucket rate limiter for inbound RPC requests, shared by all HTTP conversations.
+#[derive(Debug)]
+pub struct HttpRateLimiter {
+    /// budget for each rate-limited class.  Classes missing from here are not rate-limited.
+    budgets: HashMap<HttpRateLimitClass, ClassBudget>,
+    /// IPv4 clients are grouped by prefixes of this many bits
+    ipv4_prefix_len: u32,
+    /// IPv6 clients are grouped by prefixes of this many bits
+    ipv6_prefix_len: u32,
+    /// CIDR prefixes of clients that are never rate-limited
+    exempt: Vec<(PeerAddress, u32)>,
+    /// buckets of every client prefix that has made a rate-limited request recently
+    buckets: HashMap<(PeerAddress, HttpRateLimitClass), TokenBucket>,
+    /// how often to forget about buckets that have refilled
+    prune_interval: Duration,
+    /// when we last forgot about buckets that have refilled
+    last_prune: Instant,
+}
+
+impl HttpRateLimiter {
+    pub fn new(conn_opts: &ConnectionOptions) -> HttpRateLimiter {
+        let window_secs = conn_opts.rpc_rate_limit_window_secs.max(1);
+        let mut budgets = HashMap::new();
+        for (class, limit) in [
+            (
+                HttpRateLimitClass::ReadOnlyCall,
+                conn_opts.rpc_rate_limit_read_only_calls,
+            ),
+            (
+                HttpRateLimitClass::TxBroadcast,
+                conn_opts.rpc_rate_limit_tx_broadcasts,
+            ),
+            (
+                HttpRateLimitClass::BlockFetch,
+                conn_opts.rpc_rate_limit_block_fetches,
+            ),
+            (HttpRateLimitClass::Other, conn_opts.rpc_rate_limit_other),
+        ] {
+            if limit == 0 {
+                continue;
+            }
+            budgets.insert(
+                class,
+                ClassBudget {
+                    capacity: limit as f64,
+                    refill_per_sec: limit as f64 / window_secs as f64,
+                },
+            );
+        }
+        HttpRateLimiter {
+            budgets,
+            ipv4_prefix_len: conn_opts.rpc_rate_limit_ipv4_prefix_len.min(32),
+            ipv6_prefix_len: conn_opts.rpc_rate_limit_ipv6_prefix_len.min(128),
+            exempt: conn_opts.rpc_rate_limit_exempt.clone(),
+            buckets: HashMap::new(),
+            prune_interval: Duration::from_secs(window_secs),
+            last_prune: Instant::now(),
+        }
+    }
+
+    /// Is any class of endpoints rate-limited?
+    pub fn is_enabled(&self) -> bool {
+        !self.budgets.is_empty()
+    }
+
+    /// Which prefix is the client at `addr` charged to?
+    fn client_prefix(&self, addr: &PeerAddress) -> PeerAddress {
+        if addr.is_ipv4() {
+            mask_address(addr, IPV4_MAPPED_PREFIX_BITS + self.ipv4_prefix_len)
+        } else {
+            mask_address(addr, self.ipv6_prefix_len)
+        }
+    }
+
+    /// Charge one request of the given class to the client at `addr`.
+    /// Returns Ok(()) if the request may proceed.
+    /// Returns Err(retry_after_secs) if the client has spent its budget, where `retry_after_secs`
+    /// is how long it must wait before its next request of this class will be allowed.
+    pub fn try_acquire(&mut self, addr: &SocketAddr, class: HttpRateLimitClass) -> Result<(), u64> {
+        self.try_acquire_at(addr, class, Instant::now())
+    }
+
+    /// `try_acquire()`, but as of the given time
+    pub(crate) fn try_acquire_at(
+        &mut self,
+        addr: &SocketAddr,
+        class: HttpRateLimitClass,
+        now: Instant,
+    ) -> Result<(), u64> {
+        let Some(budget) = self.budgets.get(&class) else {
+            return Ok(());
+        };
+        let addr = PeerAddress::from_socketaddr(addr);
+        if self
+            .exempt
+            .iter()
+            .any(|(prefix, mask)| cidr_contains(prefix, *mask, &addr))
+        {
+            return Ok(());
+        }
+
+        let bucket = self
+            .buckets
+            .entry((self.client_prefix(&addr), class))
+            .or_insert_with(|| TokenBucket {
+                tokens: budget.capacity,
+                last_refill: now,
+            });
+        budget.refill(bucket, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        let wait_secs = ((1.0 - bucket.tokens) / budget.refill_per_sec).ceil() as u64;
+        Err(wait_secs.max(1))
+    }
+
+    /// Forget about every bucket that has refilled, so that clients that have gone away do not
+    /// take up memory.  Does nothing if it was last done less than a window ago.
+    pub fn prune(&mut self) {
+        self.prune_at(Instant::now())
+    }
+
+    /// `prune()`, but as of the given time
+    pub(crate) fn prune_at(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_prune) < self.prune_interval {
+            return;
+        }
+        self.last_prune = now;
+        let budgets = &self.budgets;
+        self.buckets.retain(|(_, class), bucket| {
+            let Some(budget) = budgets.get(class) else {
+                return false;
+            };
+            budget.refill(bucket, now);
+            bucket.tokens < budget.capacity
+        });
+    }
+
+    /// How many client buckets are being tracked?
+    #[cfg(test)]
+    pub fn num_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+}
@@ -109,6 +109,14 @@ impl HttpResponseContents {
         }
     }
 
+    /// Is this a stream that is waiting for more data to become available?
+    pub fn is_waiting(&self) -> bool {
+        match self {
+            Self::Stream(inner_stream) => inner_stream.generator.is_waiting(),
+            Self::RAM(..) => false,
+        }
+    }
+
     /// Write data for this to a pipe writer, which buffers it up.
     /// Return Ok(Some(..)) if there is mroe data to send.
     /// Once all data is sent, return Ok(None)
@@ -26,6 +26,14 @@ pub trait HttpChunkGenerator: Send {
     fn generate_next_chunk(&mut self) -> Result<Vec<u8>, String>;
     fn hint_chunk_size(&self) -> usize;
 
+    /// Is this generator waiting for more data to become available?
+    /// A waiting generator has not finished its stream, but has nothing to send right now.  The
+    /// connection will be kept open, and the generator will be polled again later.
+    /// Generators for finite data (e.g. blocks) never wait.
+    fn is_waiting(&self) -> bool {
+        false
+    }
+
     /// Stream one chunk to the pipe writer.  This never blocks.
     /// Returns Ok(num-bytes > 0) if there are more chunks (i.e. the caller should call this again)
     /// Returns Ok(0) if there are no more chunks (i.e. the caller should not call this again)
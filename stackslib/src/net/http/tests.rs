@@ -15,15 +15,24 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::BTreeMap;
+use std::fs;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant, SystemTime};
 
 use regex;
 use stacks_common::codec::{Error as CodecError, StacksMessageCodec};
 use stacks_common::types::net::{PeerAddress, PeerHost};
 
+use crate::net::connection::ConnectionOptions;
 use crate::net::http::common::{HTTP_PREAMBLE_MAX_ENCODED_SIZE, HTTP_PREAMBLE_MAX_NUM_HEADERS};
+use crate::net::http::ratelimit::{cidr_contains, parse_cidr};
+use crate::net::http::tls::make_client_config;
 use crate::net::http::{
-    HttpContentType, HttpRequestPreamble, HttpReservedHeader, HttpResponsePreamble, HttpVersion,
+    Error, HttpApiKey, HttpApiKeyScope, HttpApiKeys, HttpContentType, HttpRateLimitClass,
+    HttpRateLimiter, HttpRequestPreamble, HttpReservedHeader, HttpResponsePreamble, HttpTlsServer,
+    HttpVersion,
 };
+use crate::net::tests::tls_test_file_path;
 
 #[test]
 fn test_parse_reserved_header() {
@@ -722,3 +731,327 @@ fn test_http_live_headers() {
         );
     }
 }
+
+#[test]
+fn test_parse_cidr() {
+    assert_eq!(
+        parse_cidr("10.1.0.0/16").unwrap(),
+        (PeerAddress::from_ipv4(10, 1, 0, 0), 112)
+    );
+    assert_eq!(
+        parse_cidr("192.168.1.1").unwrap(),
+        (PeerAddress::from_ipv4(192, 168, 1, 1), 128)
+    );
+    let (prefix, mask) = parse_cidr("2001:db8::/32").unwrap();
+    assert_eq!(mask, 32);
+    assert_eq!(prefix, PeerAddress::from_ip(&"2001:db8::".parse().unwrap()));
+
+    assert!(parse_cidr("10.0.0.0/33").is_err());
+    assert!(parse_cidr("2001:db8::/129").is_err());
+    assert!(parse_cidr("10.0.0.0/eight").is_err());
+    assert!(parse_cidr("localhost").is_err());
+
+    let (prefix, mask) = parse_cidr("10.1.0.0/16").unwrap();
+    assert!(cidr_contains(
+        &prefix,
+        mask,
+        &PeerAddress::from_ipv4(10, 1, 255, 3)
+    ));
+    assert!(!cidr_contains(
+        &prefix,
+        mask,
+        &PeerAddress::from_ipv4(10, 2, 0, 1)
+    ));
+}
+
+#[test]
+fn test_http_rate_limiter_budgets() {
+    let mut conn_opts = ConnectionOptions::default();
+    let limiter = HttpRateLimiter::new(&conn_opts);
+    assert!(!limiter.is_enabled());
+
+    conn_opts.rpc_rate_limit_read_only_calls = 4;
+    conn_opts.rpc_rate_limit_window_secs = 32;
+    let mut limiter = HttpRateLimiter::new(&conn_opts);
+    assert!(limiter.is_enabled());
+
+    let client: SocketAddr = "1.2.3.4:5678".parse().unwrap();
+    let other_client: SocketAddr = "1.2.3.5:5678".parse().unwrap();
+    let start = Instant::now();
+
+    // the whole budget can be spent at once
+    for _ in 0..4 {
+        limiter
+            .try_acquire_at(&client, HttpRateLimitClass::ReadOnlyCall, start)
+            .unwrap();
+    }
+    // one token is refilled every 8 seconds
+    assert_eq!(
+        limiter.try_acquire_at(&client, HttpRateLimitClass::ReadOnlyCall, start),
+        Err(8)
+    );
+    assert_eq!(
+        limiter.try_acquire_at(
+            &client,
+            HttpRateLimitClass::ReadOnlyCall,
+            start + Duration::from_secs(4)
+        ),
+        Err(4)
+    );
+
+    // classes without a budget, and other clients, are unaffected
+    for _ in 0..10 {
+        limiter
+            .try_acquire_at(&client, HttpRateLimitClass::TxBroadcast, start)
+            .unwrap();
+        limiter
+            .try_acquire_at(&client, HttpRateLimitClass::Other, start)
+            .unwrap();
+    }
+    limiter
+        .try_acquire_at(&other_client, HttpRateLimitClass::ReadOnlyCall, start)
+        .unwrap();
+
+    // budget refills over time, but never past its capacity
+    limiter
+        .try_acquire_at(
+            &client,
+            HttpRateLimitClass::ReadOnlyCall,
+            start + Duration::from_secs(8),
+        )
+        .unwrap();
+    let later = start + Duration::from_secs(3600);
+    for _ in 0..4 {
+        limiter
+            .try_acquire_at(&client, HttpRateLimitClass::ReadOnlyCall, later)
+            .unwrap();
+    }
+    assert!(limiter
+        .try_acquire_at(&client, HttpRateLimitClass::ReadOnlyCall, later)
+        .is_err());
+}
+
+#[test]
+fn test_http_rate_limiter_prefixes_and_exemptions() {
+    let mut conn_opts = ConnectionOptions::default();
+    conn_opts.rpc_rate_limit_other = 1;
+    conn_opts.rpc_rate_limit_ipv4_prefix_len = 24;
+    conn_opts.rpc_rate_limit_exempt = vec![parse_cidr("10.0.0.0/8").unwrap()];
+    let mut limiter = HttpRateLimiter::new(&conn_opts);
+    let now = Instant::now();
+
+    // addresses in the same /24 share a budget
+    let client: SocketAddr = "1.2.3.4:1000".parse().unwrap();
+    let neighbor: SocketAddr = "1.2.3.200:2000".parse().unwrap();
+    let stranger: SocketAddr = "1.2.4.4:1000".parse().unwrap();
+    limiter
+        .try_acquire_at(&client, HttpRateLimitClass::Other, now)
+        .unwrap();
+    assert!(limiter
+        .try_acquire_at(&neighbor, HttpRateLimitClass::Other, now)
+        .is_err());
+    limiter
+        .try_acquire_at(&stranger, HttpRateLimitClass::Other, now)
+        .unwrap();
+
+    // IPv6 clients share a budget per /64 by default
+    let v6_client: SocketAddr = "[2001:db8::1]:1000".parse().unwrap();
+    let v6_neighbor: SocketAddr = "[2001:db8::2]:1000".parse().unwrap();
+    limiter
+        .try_acquire_at(&v6_client, HttpRateLimitClass::Other, now)
+        .unwrap();
+    assert!(limiter
+        .try_acquire_at(&v6_neighbor, HttpRateLimitClass::Other, now)
+        .is_err());
+
+    // exempt clients are never limited
+    let exempt: SocketAddr = "10.20.30.40:1000".parse().unwrap();
+    for _ in 0..10 {
+        limiter
+            .try_acquire_at(&exempt, HttpRateLimitClass::Other, now)
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_http_rate_limiter_prune() {
+    let mut conn_opts = ConnectionOptions::default();
+    conn_opts.rpc_rate_limit_block_fetches = 2;
+    conn_opts.rpc_rate_limit_window_secs = 10;
+    let mut limiter = HttpRateLimiter::new(&conn_opts);
+    let start = Instant::now();
+
+    let client: SocketAddr = "1.2.3.4:1000".parse().unwrap();
+    let other_client: SocketAddr = "5.6.7.8:1000".parse().unwrap();
+    limiter
+        .try_acquire_at(&client, HttpRateLimitClass::BlockFetch, start)
+        .unwrap();
+    limiter
+        .try_acquire_at(&client, HttpRateLimitClass::BlockFetch, start)
+        .unwrap();
+    assert_eq!(limiter.num_buckets(), 1);
+
+    // not pruned until a window has passed
+    limiter.prune_at(start + Duration::from_secs(1));
+    assert_eq!(limiter.num_buckets(), 1);
+
+    // a bucket that is still refilling is kept
+    limiter
+        .try_acquire_at(
+            &other_client,
+            HttpRateLimitClass::BlockFetch,
+            start + Duration::from_secs(20),
+        )
+        .unwrap();
+    limiter.prune_at(start + Duration::from_secs(21));
+    assert_eq!(limiter.num_buckets(), 1);
+    assert!(limiter
+        .try_acquire_at(
+            &client,
+            HttpRateLimitClass::BlockFetch,
+            start + Duration::from_secs(21)
+        )
+        .is_ok());
+}
+
+#[test]
+fn test_http_api_keys() {
+    let mut conn_opts = ConnectionOptions::default();
+    conn_opts.api_keys = vec![HttpApiKey {
+        name: "signer".into(),
+        key: "signer-key".into(),
+        scopes: vec![
+            HttpApiKeyScope::BlockProposal,
+            HttpApiKeyScope::StackerDBWrite,
+        ],
+    }];
+
+    let api_keys = HttpApiKeys::from_connection_options(&conn_opts);
+    assert!(api_keys.any_with_scope(HttpApiKeyScope::BlockProposal));
+    assert!(api_keys.any_with_scope(HttpApiKeyScope::StackerDBWrite));
+    assert!(!api_keys.any_with_scope(HttpApiKeyScope::BlockUpload));
+    assert!(!api_keys.any_with_scope(HttpApiKeyScope::UnlimitedReadOnly));
+    assert!(!api_keys.any_with_scope(HttpApiKeyScope::Admin));
+
+    // the legacy auth token may do anything
+    conn_opts.auth_token = Some("password".into());
+    let api_keys = HttpApiKeys::from_connection_options(&conn_opts);
+    for scope in HttpApiKeyScope::ALL {
+        assert!(api_keys.any_with_scope(*scope));
+    }
+
+    let mut preamble = HttpRequestPreamble::new(
+        HttpVersion::Http11,
+        "POST".into(),
+        "/v3/block_proposal".into(),
+        "localhost".into(),
+        20443,
+        true,
+    );
+    assert!(api_keys.lookup(&preamble).is_none());
+    match api_keys.authorize(&preamble, HttpApiKeyScope::BlockProposal) {
+        Err(Error::Http(401, _)) => {}
+        x => panic!("Expected 401, got {x:?}"),
+    }
+
+    preamble.add_header("Authorization".into(), "wrong-key".into());
+    assert!(api_keys.lookup(&preamble).is_none());
+    match api_keys.authorize(&preamble, HttpApiKeyScope::BlockProposal) {
+        Err(Error::Http(401, _)) => {}
+        x => panic!("Expected 401, got {x:?}"),
+    }
+
+    preamble.add_header("Authorization".into(), "signer-key".into());
+    assert_eq!(api_keys.lookup(&preamble).unwrap().name, "signer");
+    assert_eq!(
+        api_keys
+            .authorize(&preamble, HttpApiKeyScope::BlockProposal)
+            .unwrap()
+            .name,
+        "signer"
+    );
+    match api_keys.authorize(&preamble, HttpApiKeyScope::BlockUpload) {
+        Err(Error::Http(403, _)) => {}
+        x => panic!("Expected 403, got {x:?}"),
+    }
+
+    preamble.add_header("Authorization".into(), "password".into());
+    assert_eq!(
+        api_keys
+            .authorize(&preamble, HttpApiKeyScope::BlockUpload)
+            .unwrap()
+            .name,
+        "auth_token"
+    );
+}
+
+/// Run a TLS handshake in memory between a new session on `server` and a client that trusts only
+/// the CA in `ca_file`
+fn tls_handshake(server: &HttpTlsServer, ca_file: &str) -> Result<(), rustls::Error> {
+    let client_config = make_client_config(Some(&tls_test_file_path(ca_file))).unwrap();
+    let mut client =
+        rustls::ClientConnection::new(client_config, "localhost".try_into().unwrap()).unwrap();
+    let mut server = server.new_session().unwrap();
+    for _ in 0..10 {
+        let mut records = vec![];
+        client.write_tls(&mut records).unwrap();
+        server.read_tls(&mut records.as_slice()).unwrap();
+        server.process_new_packets()?;
+
+        let mut records = vec![];
+        server.write_tls(&mut records).unwrap();
+        client.read_tls(&mut records.as_slice()).unwrap();
+        client.process_new_packets()?;
+
+        if !client.is_handshaking() && !server.is_handshaking() {
+            return Ok(());
+        }
+    }
+    panic!("TLS handshake did not finish");
+}
+
+/// Replace `dest` with a copy of `src`, and move its modification time forward by `secs` so
+/// that the change is noticed even on filesystems with coarse timestamps
+fn replace_tls_file(src: &str, dest: &std::path::Path, secs: u64) {
+    fs::copy(tls_test_file_path(src), dest).unwrap();
+    fs::File::options()
+        .write(true)
+        .open(dest)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(secs))
+        .unwrap();
+}
+
+#[test]
+fn test_http_tls_server_reload() {
+    let dir = tempfile::tempdir().unwrap();
+    let cert_path = dir.path().join("cert.pem");
+    let key_path = dir.path().join("key.pem");
+    replace_tls_file("server-1.pem", &cert_path, 0);
+    replace_tls_file("server-1.key", &key_path, 0);
+
+    let mut server =
+        HttpTlsServer::new(cert_path.to_str().unwrap(), key_path.to_str().unwrap()).unwrap();
+    assert_eq!(server.reload_if_changed(), Ok(false));
+    tls_handshake(&server, "ca-1.pem").unwrap();
+    tls_handshake(&server, "ca-2.pem").unwrap_err();
+
+    // renewed with a certificate from another CA
+    replace_tls_file("server-2.pem", &cert_path, 10);
+    replace_tls_file("server-2.key", &key_path, 10);
+    assert_eq!(server.reload_if_changed(), Ok(true));
+    assert_eq!(server.reload_if_changed(), Ok(false));
+    tls_handshake(&server, "ca-2.pem").unwrap();
+    tls_handshake(&server, "ca-1.pem").unwrap_err();
+
+    // a bad replacement is ignored
+    fs::write(&cert_path, "not a certificate").unwrap();
+    fs::File::options()
+        .write(true)
+        .open(&cert_path)
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(20))
+        .unwrap();
+    assert!(server.reload_if_changed().is_err());
+    tls_handshake(&server, "ca-2.pem").unwrap();
+}
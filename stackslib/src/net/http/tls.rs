@@ -0,0 +1,250 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! TLS for the HTTP stack.
+//!
+//! The RPC server can terminate TLS itself.  It tells TLS clients apart from plaintext ones by the
+//! first byte they send, so plaintext clients (such as other nodes' block downloaders) keep
+//! working on the same port, unless `rpc_tls_required` is set, in which case they are
+//! disconnected before anything they send is processed.  Its certificate and key are re-read
+//! whenever their files change.
+//!
+//! Outbound HTTPS requests verify the server against either the built-in web PKI roots, or a
+//! pinned set of CA certificates.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use rustls::{
+    Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore, ServerConfig,
+    ServerConnection,
+};
+
+/// The first byte of a TLS handshake record.  No HTTP request can begin with it.
+pub const TLS_HANDSHAKE_RECORD_TYPE: u8 = 0x16;
+
+/// How often the RPC server checks whether its certificate or key have changed on disk
+pub const TLS_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Load all of the PEM-encoded certificates in `path`
+pub fn load_certificates(path: &str) -> Result<Vec<Certificate>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{path}': {e}"))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .map_err(|e| format!("Failed to read certificates from '{path}': {e}"))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates found in '{path}'"));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Load the first PEM-encoded private key in `path`.  PKCS#8, PKCS#1 (RSA) and SEC1 (EC) keys
+/// are supported.
+pub fn load_private_key(path: &str) -> Result<PrivateKey, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open '{path}': {e}"))?;
+    let items = rustls_pemfile::read_all(&mut BufReader::new(file))
+        .map_err(|e| format!("Failed to read private key from '{path}': {e}"))?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| format!("No private key found in '{path}'"))
+}
+
+/// Build a TLS server configuration from a PEM certificate chain and private key
+pub fn make_server_config(cert_path: &str, key_path: &str) -> Result<Arc<ServerConfig>, String> {
+    let certs = load_certificates(cert_path)?;
+    let key = load_private_key(key_path)?;
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("Invalid certificate or key in '{cert_path}', '{key_path}': {e}"))?;
+    Ok(Arc::new(config))
+}
+
+/// Build a TLS client configuration.  If `ca_cert_path` is given, then servers must present a
+/// certificate issued by one of the CAs in that file.  Otherwise, the web PKI roots are trusted.
+pub fn make_client_config(ca_cert_path: Option<&str>) -> Result<Arc<ClientConfig>, String> {
+    let mut roots = RootCertStore::empty();
+    match ca_cert_path {
+        Some(path) => {
+            for cert in load_certificates(path)? {
+                roots
+                    .add(&cert)
+                    .map_err(|e| format!("Invalid CA certificate in '{path}': {e}"))?;
+            }
+        }
+        None => {
+            roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    ta.subject,
+                    ta.spki,
+                    ta.name_constraints,
+                )
+            }));
+        }
+    }
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+/// Modification time of `path`, if it can be determined
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|md| md.modified()).ok()
+}
+
+/// The RPC server's TLS configuration, which is rebuilt whenever the certificate or key file
+/// changes.  Sessions already in progress keep the configuration they started with.
+#[derive(Debug, Clone)]
+pub struct HttpTlsServer {
+    cert_path: String,
+    key_path: String,
+    config: Arc<ServerConfig>,
+    cert_modified: Option<SystemTime>,
+    key_modified: Option<SystemTime>,
+    last_reload_check: Instant,
+}
+
+impl HttpTlsServer {
+    pub fn new(cert_path: &str, key_path: &str) -> Result<HttpTlsServer, String> {
+        Ok(HttpTlsServer {
+            cert_path: cert_path.to_string(),
+            key_path: key_path.to_string(),
+            cert_modified: modified_time(cert_path),
+            key_modified: modified_time(key_path),
+            config: make_server_config(cert_path, key_path)?,
+            last_reload_check: Instant::now(),
+        })
+    }
+
+    /// Start a TLS session for a newly-connected client
+    pub fn new_session(&self) -> Result<ServerConnection, rustls::Error> {
+        ServerConnection::new(self.config.clone())
+    }
+
+    /// Reload the certificate and key if either file has changed since they were last loaded.
+    /// Returns Ok(true) if they were reloaded.
+    /// If they changed but could not be loaded, then the current configuration is kept.
+    pub fn reload_if_changed(&mut self) -> Result<bool, String> {
+        let cert_modified = modified_time(&self.cert_path);
+        let key_modified = modified_time(&self.key_path);
+        if cert_modified == self.cert_modified && key_modified == self.key_modified {
+            return Ok(false);
+        }
+        self.config = make_server_config(&self.cert_path, &self.key_path)?;
+        self.cert_modified = cert_modified;
+        self.key_modified = key_modified;
+        Ok(true)
+    }
+
+    /// Call `reload_if_changed()` at most once every `TLS_RELOAD_CHECK_INTERVAL`
+    pub fn try_reload(&mut self) {
+        if self.last_reload_check.elapsed() < TLS_RELOAD_CHECK_INTERVAL {
+            return;
+        }
+        self.last_reload_check = Instant::now();
+        match self.reload_if_changed() {
+            Ok(true) => {
+                info!("Reloaded RPC TLS certificate"; "cert_path" => &self.cert_path, "key_path" => &self.key_path);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                warn!("Failed to reload RPC TLS certificate; keeping the current one"; "error" => %e);
+            }
+        }
+    }
+}
+
+/// A non-blocking socket carrying a server-side TLS session.
+/// Reads yield decrypted data, and writes are encrypted before they are sent.
+/// Like the socket, this returns `io::ErrorKind::WouldBlock` whenever it cannot make progress.
+pub struct HttpTlsStream<'a, S: Read + Write> {
+    session: &'a mut ServerConnection,
+    sock: &'a mut S,
+}
+
+impl<'a, S: Read + Write> HttpTlsStream<'a, S> {
+    pub fn new(session: &'a mut ServerConnection, sock: &'a mut S) -> HttpTlsStream<'a, S> {
+        HttpTlsStream { session, sock }
+    }
+
+    /// Send as many buffered TLS records as the socket will take
+    fn write_tls(&mut self) -> io::Result<()> {
+        while self.session.wants_write() {
+            match self.session.write_tls(self.sock) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S: Read + Write + fmt::Debug> fmt::Debug for HttpTlsStream<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TLS({:?})", &self.sock)
+    }
+}
+
+impl<S: Read + Write> Read for HttpTlsStream<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            // The reader yields Ok(0) on a clean close, and WouldBlock if it needs more records
+            match self.session.reader().read(buf) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                res => return res,
+            }
+            // A WouldBlock from the socket is passed along
+            self.session.read_tls(self.sock)?;
+            if let Err(e) = self.session.process_new_packets() {
+                // try to tell the client why
+                let _ = self.write_tls();
+                return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+            // the handshake may need a reply
+            self.write_tls()?;
+        }
+    }
+}
+
+impl<S: Read + Write> Write for HttpTlsStream<'_, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_tls()?;
+        let num_written = self.session.writer().write(buf)?;
+        if num_written == 0 && !buf.is_empty() {
+            // outgoing records are backed up
+            return Err(io::Error::from(io::ErrorKind::WouldBlock));
+        }
+        self.write_tls()?;
+        Ok(num_written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.session.writer().flush()?;
+        self.write_tls()
+    }
+}
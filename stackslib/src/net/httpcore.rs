@@ -18,6 +18,7 @@
 use std::collections::BTreeMap;
 use std::io::{Read, Write};
 use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::{fmt, io, mem};
 
@@ -46,9 +47,10 @@ use crate::core::StacksEpoch;
 use crate::net::connection::{ConnectionOptions, NetworkConnection};
 use crate::net::http::common::{parse_raw_bytes, HTTP_PREAMBLE_MAX_ENCODED_SIZE};
 use crate::net::http::{
-    http_reason, parse_bytes, parse_json, Error as HttpError, HttpContentType, HttpErrorResponse,
-    HttpNotFound, HttpRequest, HttpRequestContents, HttpRequestPreamble, HttpResponse,
-    HttpResponseContents, HttpResponsePayload, HttpResponsePreamble, HttpServerError,
+    http_reason, parse_bytes, parse_json, Error as HttpError, HttpApiKey, HttpApiKeys,
+    HttpContentType, HttpErrorResponse, HttpNotFound, HttpRateLimitClass, HttpRequest,
+    HttpRequestContents, HttpRequestPreamble, HttpResponse, HttpResponseContents,
+    HttpResponsePayload, HttpResponsePreamble, HttpServerError,
 };
 use crate::net::p2p::PeerNetwork;
 use crate::net::server::HttpPeer;
@@ -376,6 +378,11 @@ pub trait RPCRequestHandler: HttpRequest + HttpResponse + RPCRequestHandlerClone
         state: &mut StacksNodeState,
     ) -> Result<(HttpResponsePreamble, HttpResponseContents), NetError>;
 
+    /// Which per-client rate limit applies to requests for this endpoint
+    fn rate_limit_class(&self) -> HttpRateLimitClass {
+        HttpRateLimitClass::Other
+    }
+
     /// Helper to get the canonical sortition tip
     fn get_canonical_burn_chain_tip(
         &self,
@@ -891,12 +898,12 @@ impl HttpResponse for RPCArbitraryResponseHandler {
                 let json = parse_json(preamble, body)?;
                 Ok(HttpResponsePayload::JSON(json))
             }
-            HttpContentType::Text => {
+            HttpContentType::Text | HttpContentType::EventStream => {
                 let text_bytes = parse_raw_bytes(
                     preamble,
                     body,
                     MAX_MESSAGE_LEN.into(),
-                    HttpContentType::Text,
+                    preamble.content_type,
                 )?;
                 let text = String::from_utf8_lossy(&text_bytes).to_string();
                 Ok(HttpResponsePayload::Text(text))
@@ -938,8 +945,10 @@ pub struct StacksHttp {
     pub maximum_call_argument_size: u32,
     /// Maximum execution budget of a read-only call
     pub read_only_call_limit: ExecutionCost,
-    /// The authorization token to enable access to privileged features, such as the block proposal RPC endpoint
-    pub auth_token: Option<String>,
+    /// The API keys that enable access to privileged features, such as the block proposal RPC endpoint
+    pub api_keys: HttpApiKeys,
+    /// Whether or not StackerDB chunk writes require an API key
+    pub stackerdb_write_requires_api_key: bool,
     /// Allow arbitrary responses to be handled in addition to request handlers
     allow_arbitrary_response: bool,
     /// Maximum execution time of a read-only call when in zero cost-tracking mode
@@ -961,7 +970,8 @@ impl StacksHttp {
             request_handlers: vec![],
             maximum_call_argument_size: conn_opts.maximum_call_argument_size,
             read_only_call_limit: conn_opts.read_only_call_limit.clone(),
-            auth_token: conn_opts.auth_token.clone(),
+            api_keys: HttpApiKeys::from_connection_options(conn_opts),
+            stackerdb_write_requires_api_key: conn_opts.stackerdb_write_requires_api_key,
             allow_arbitrary_response: false,
             read_only_max_execution_time: Duration::from_secs(
                 conn_opts.read_only_max_execution_time_secs,
@@ -985,7 +995,8 @@ impl StacksHttp {
             request_handlers: vec![],
             maximum_call_argument_size: conn_opts.maximum_call_argument_size,
             read_only_call_limit: conn_opts.read_only_call_limit.clone(),
-            auth_token: conn_opts.auth_token.clone(),
+            api_keys: HttpApiKeys::from_connection_options(conn_opts),
+            stackerdb_write_requires_api_key: conn_opts.stackerdb_write_requires_api_key,
             allow_arbitrary_response: true,
             read_only_max_execution_time: Duration::from_secs(
                 conn_opts.read_only_max_execution_time_secs,
@@ -1360,6 +1371,34 @@ impl StacksHttp {
         request_handler.metrics_identifier()
     }
 
+    /// Get the class of per-client rate limit that applies to a request.
+    /// Requests for unknown endpoints fall into `HttpRateLimitClass::Other`.
+    pub fn rate_limit_class(&self, req: &mut StacksHttpRequest) -> HttpRateLimitClass {
+        let Ok((decoded_path, _)) = decode_request_path(req.request_path()) else {
+            return HttpRateLimitClass::Other;
+        };
+
+        let Some(response_handler_index) = req
+            .response_handler_index
+            .or_else(|| self.find_response_handler(&req.preamble().verb, &decoded_path))
+        else {
+            return HttpRateLimitClass::Other;
+        };
+        req.response_handler_index = Some(response_handler_index);
+
+        let (_, _, request_handler) = self
+            .request_handlers
+            .get(response_handler_index)
+            .expect("FATAL: request points to a nonexistent handler");
+
+        request_handler.rate_limit_class()
+    }
+
+    /// Which API key, if any, does this request carry?
+    pub fn request_api_key(&self, req: &StacksHttpRequest) -> Option<&HttpApiKey> {
+        self.api_keys.lookup(req.preamble())
+    }
+
     /// Given a fully-formed single HTTP response, parse it (used by clients).
     #[cfg(test)]
     pub fn parse_response(
@@ -1805,6 +1844,37 @@ pub fn send_http_request(
     request: StacksHttpRequest,
     timeout: Duration,
 ) -> Result<StacksHttpResponse, io::Error> {
+    let (mut stream, addr) = connect_http_socket(host, port, timeout)?;
+    send_http_request_on(&mut stream, addr, request, timeout)
+}
+
+/// Send an HTTPS request to the given host:port.  Returns the decoded response.
+/// This behaves just like `send_http_request()`, except that the request and response are
+/// carried over a TLS session.  The server's certificate is verified against `tls_config`'s
+/// roots, and must be valid for `host`.
+pub fn send_https_request(
+    host: &str,
+    port: u16,
+    request: StacksHttpRequest,
+    timeout: Duration,
+    tls_config: Arc<rustls::ClientConfig>,
+) -> Result<StacksHttpResponse, io::Error> {
+    let server_name = rustls::ServerName::try_from(host)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let session =
+        rustls::ClientConnection::new(tls_config, server_name).map_err(io::Error::other)?;
+    let (stream, addr) = connect_http_socket(host, port, timeout)?;
+    let mut stream = rustls::StreamOwned::new(session, stream);
+    send_http_request_on(&mut stream, addr, request, timeout)
+}
+
+/// Connect a blocking socket to the given host:port, with read and write timeouts of `timeout`.
+/// Returns the socket and the address it connected to.
+fn connect_http_socket(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> Result<(TcpStream, SocketAddr), io::Error> {
     // Find the host:port that works.
     // This is sometimes necessary because `localhost` can resolve to both its ipv4 and ipv6
     // addresses, but usually, Stacks services like event observers are only bound to ipv4
@@ -1824,14 +1894,23 @@ pub fn send_http_request(
         }
     }
 
-    let Some((mut stream, addr)) = stream_and_addr else {
+    let Some((stream, addr)) = stream_and_addr else {
         return Err(last_err.unwrap_or(io::Error::other("Unable to connect to {host}:{port}")));
     };
 
     stream.set_read_timeout(Some(timeout))?;
     stream.set_write_timeout(Some(timeout))?;
     stream.set_nodelay(true)?;
+    Ok((stream, addr))
+}
 
+/// Send an HTTP request over `stream`, which is connected to `addr`, and decode the response.
+fn send_http_request_on<S: Read + Write>(
+    stream: &mut S,
+    addr: SocketAddr,
+    request: StacksHttpRequest,
+    timeout: Duration,
+) -> Result<StacksHttpResponse, io::Error> {
     let start = Instant::now();
     let request_path = request.request_path();
     debug!("send_request: Sending request"; "request" => request_path);
@@ -1897,7 +1976,7 @@ pub fn send_http_request(
 
         // send it out
         let num_sent = connection
-            .send_data(&mut stream)
+            .send_data(stream)
             .map_err(|e| handle_net_error(e, "Failed to send socket data"))?;
 
         debug!(
@@ -1923,6 +2002,9 @@ pub fn send_http_request(
         }
     }
 
+    // make sure nothing is left buffered (i.e. in a TLS session)
+    stream.flush()?;
+
     // Step 4: pull bytes from the socket back into the handle, and see if the connection decoded
     // and dispatched any new messages to the request handle.  If so, then extract the message and
     // check that it's a well-formed HTTP response.
@@ -1931,7 +2013,7 @@ pub fn send_http_request(
     let response = loop {
         // get back the reply
         debug!("send_request(receiving data): try to receive data");
-        match connection.recv_data(&mut stream) {
+        match connection.recv_data(stream) {
             Ok(nr) => {
                 debug!("send_request(receiving data): received {} bytes", nr);
             }
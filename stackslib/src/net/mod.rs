@@ -87,6 +87,7 @@ pub mod db;
 /// which serves as an API for `DNSResolver`.
 pub mod dns;
 pub mod download;
+pub mod eventstream;
 pub mod http;
 /// Links http crate to Stacks
 pub mod httpcore;
@@ -103,6 +104,9 @@ pub mod prune;
 pub mod relay;
 pub mod rpc;
 pub mod server;
+/// Implements the optional encrypted transport between peers that both advertise
+/// `ServiceFlags::ENCRYPTED`: session key derivation and frame sealing/opening.
+pub mod session;
 pub mod stackerdb;
 pub mod unsolicited;
 
@@ -254,6 +258,8 @@ pub enum Error {
     WaitingForDNS,
     /// No reward set for given reward cycle
     NoPoXRewardSet(u64),
+    /// Encrypted p2p session could not be established or a frame failed to authenticate
+    SessionError(String),
 }
 
 impl From<libstackerdb_error> for Error {
@@ -403,6 +409,7 @@ impl fmt::Display for Error {
             Error::InvalidState => write!(f, "Invalid state-machine state reached"),
             Error::WaitingForDNS => write!(f, "Waiting for DNS resolution"),
             Error::NoPoXRewardSet(rc) => write!(f, "No PoX reward set for cycle {}", rc),
+            Error::SessionError(s) => write!(f, "Encrypted session error: {}", s),
         }
     }
 }
@@ -477,6 +484,7 @@ impl error::Error for Error {
             Error::InvalidState => None,
             Error::WaitingForDNS => None,
             Error::NoPoXRewardSet(..) => None,
+            Error::SessionError(..) => None,
         }
     }
 }
@@ -996,6 +1004,16 @@ pub enum ServiceFlags {
     RELAY = 0x01,
     RPC = 0x02,
     STACKERDB = 0x04,
+    ENCRYPTED = 0x08,
+}
+
+/// Sent by a peer, right after the handshake, to announce that every byte it sends after this
+/// message is encrypted.  Only sent if both peers advertise `ServiceFlags::ENCRYPTED`.  See
+/// `net::session` for how the session key is derived.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionInitData {
+    /// the sender's per-session ephemeral public key
+    pub ephemeral_public_key: StacksPublicKeyBuffer,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1143,6 +1161,8 @@ pub enum StacksMessageType {
     GetNakamotoInv(GetNakamotoInvData),
     NakamotoInv(NakamotoInvData),
     NakamotoBlocks(NakamotoBlocksData),
+    // encrypted sessions
+    SessionInit(SessionInitData),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -1178,6 +1198,8 @@ pub enum StacksMessageID {
     GetNakamotoInv = 26,
     NakamotoInv = 27,
     NakamotoBlocks = 28,
+    // encrypted sessions
+    SessionInit = 29,
     // reserved
     Reserved = 255,
 }
@@ -1244,6 +1266,13 @@ pub trait ProtocolFamily {
     /// and writing out a Preamble for its Message.
     fn write_message<W: Write>(&mut self, fd: &mut W, message: &Self::Message)
         -> Result<(), Error>;
+
+    /// Does the remote peer switch to an encrypted byte stream right after sending this message?
+    /// If so, the connection stops parsing at this message and holds on to the remaining bytes
+    /// until the session key is installed.  Protocols without encrypted sessions never do.
+    fn starts_encrypted_session(&self, _message: &Self::Message) -> bool {
+        false
+    }
 }
 
 // these implement the ProtocolFamily trait
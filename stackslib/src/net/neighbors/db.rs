@@ -249,6 +249,9 @@ pub trait NeighborWalkDB {
                     && neighbor.addr.addrbytes.is_in_private_range()
                 {
                     None
+                } else if network.has_poor_reputation(&neighbor.addr) {
+                    // don't start a walk at a peer that has been misbehaving
+                    None
                 } else {
                     Some(neighbor)
                 }
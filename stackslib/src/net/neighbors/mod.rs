@@ -26,6 +26,7 @@ use crate::net::{Error as net_error, *};
 pub mod comms;
 pub mod db;
 pub mod neighbor;
+pub mod reputation;
 pub mod rpc;
 pub mod walk;
 
@@ -0,0 +1,124 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Peer reputation.
+//!
+//! Each peer accrues a score from concrete protocol violations, and the score halves every
+//! `reputation_half_life_secs` so that a peer which stops misbehaving is eventually forgiven.
+//! A peer whose score reaches `reputation_ban_threshold` is banned, and a peer whose score
+//! reaches `reputation_walk_threshold` is not used to start a neighbor walk.  Peers that send
+//! invalid blocks are still banned outright; the event is recorded here as well so that it shows
+//! up in the peer's history.
+//!
+//! Scores are stored in the peer DB, keyed by network ID, address, and port, so they survive
+//! restarts and apply to peers we are not currently connected to.  A peer is forgotten once its
+//! score has decayed below `MIN_REPUTATION_SCORE`.
+
+use stacks_common::types::net::PeerAddress;
+
+use crate::net::NeighborKey;
+
+/// A peer that sends us more than `reputation_nack_flood_threshold` NACKs within this many
+/// seconds is flooding us with them
+pub const NACK_FLOOD_WINDOW: u64 = 60;
+
+/// A peer whose score has decayed below this is forgotten
+pub const MIN_REPUTATION_SCORE: f64 = 1.0;
+
+define_named_enum!(
+    /// Misbehavior that counts against a peer's reputation
+    PeerReputationEvent {
+        /// The peer sent us a block or microblock stream that failed validation
+        InvalidBlock("invalid-block"),
+        /// The peer sent us too many NACKs within `NACK_FLOOD_WINDOW` seconds
+        NackFlood("nack-flood"),
+        /// The peer's Nakamoto inventory advertised a tenure that the peer then could not serve,
+        /// or served invalid blocks for
+        InventoryLie("inventory-lie"),
+        /// The peer sent us a StackerDB chunk that failed validation
+        InvalidStackerDBChunk("invalid-stackerdb-chunk"),
+    }
+);
+
+impl PeerReputationEvent {
+    /// How much this event adds to a peer's score.
+    /// An invalid block is as bad as it gets (and always gets the sender banned, whatever its
+    /// score).  The others can happen to an honest peer now and then (e.g. a tenure that it pruned after
+    /// advertising it), so it takes several of them in a short time to earn a ban.
+    pub fn penalty(&self) -> f64 {
+        match self {
+            PeerReputationEvent::InvalidBlock => 100.0,
+            PeerReputationEvent::NackFlood => 25.0,
+            PeerReputationEvent::InventoryLie => 20.0,
+            PeerReputationEvent::InvalidStackerDBChunk => 10.0,
+        }
+    }
+}
+
+/// A peer's reputation.  A higher score is worse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PeerReputation {
+    pub network_id: u32,
+    pub addrbytes: PeerAddress,
+    pub port: u16,
+    /// The score as of `last_update`.  Use `score_at()` to get the score at another time.
+    pub score: f64,
+    /// When the score was last changed, in seconds since the epoch
+    pub last_update: u64,
+    /// How many of each kind of event have been recorded for this peer
+    pub invalid_blocks: u64,
+    pub nack_floods: u64,
+    pub inventory_lies: u64,
+    pub invalid_stackerdb_chunks: u64,
+}
+
+impl PeerReputation {
+    /// A clean reputation for a peer
+    pub fn new(neighbor: &NeighborKey) -> PeerReputation {
+        PeerReputation {
+            network_id: neighbor.network_id,
+            addrbytes: neighbor.addrbytes.clone(),
+            port: neighbor.port,
+            score: 0.0,
+            last_update: 0,
+            invalid_blocks: 0,
+            nack_floods: 0,
+            inventory_lies: 0,
+            invalid_stackerdb_chunks: 0,
+        }
+    }
+
+    /// The score at time `now`, after it has decayed with the given half-life.
+    /// A half-life of 0 means that the score never decays.
+    pub fn score_at(&self, now: u64, half_life_secs: u64) -> f64 {
+        if half_life_secs == 0 {
+            return self.score;
+        }
+        let elapsed = now.saturating_sub(self.last_update) as f64;
+        self.score * 0.5_f64.powf(elapsed / half_life_secs as f64)
+    }
+
+    /// Add an event that happened at time `now` to the score
+    pub fn record(&mut self, event: PeerReputationEvent, now: u64, half_life_secs: u64) {
+        self.score = self.score_at(now, half_life_secs) + event.penalty();
+        self.last_update = now.max(self.last_update);
+        match event {
+            PeerReputationEvent::InvalidBlock => self.invalid_blocks += 1,
+            PeerReputationEvent::NackFlood => self.nack_floods += 1,
+            PeerReputationEvent::InventoryLie => self.inventory_lies += 1,
+            PeerReputationEvent::InvalidStackerDBChunk => self.invalid_stackerdb_chunks += 1,
+        }
+    }
+}
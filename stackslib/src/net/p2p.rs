@@ -18,6 +18,7 @@ use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::mem;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TryRecvError, TrySendError};
+use std::sync::Arc;
 use std::thread::JoinHandle;
 
 use clarity::vm::ast::ASTRules;
@@ -54,9 +55,11 @@ use crate::net::connection::{ConnectionOptions, ReplyHandleP2P};
 use crate::net::db::{LocalPeer, PeerDB};
 use crate::net::download::nakamoto::NakamotoDownloadStateMachine;
 use crate::net::download::BlockDownloader;
+use crate::net::eventstream::EventStreamHub;
 use crate::net::inv::inv2x::*;
 use crate::net::inv::nakamoto::{InvGenerator, NakamotoInvStateMachine};
 use crate::net::mempool::MempoolSync;
+use crate::net::neighbors::reputation::PeerReputationEvent;
 use crate::net::neighbors::*;
 use crate::net::poll::{NetworkPollState, NetworkState};
 use crate::net::relay::{RelayerStats, *};
@@ -69,6 +72,7 @@ use crate::util_lib::db::{DBConn, DBTx, Error as db_error};
 #[derive(Debug)]
 pub enum NetworkRequest {
     Ban(Vec<NeighborKey>),
+    Penalize(Vec<NeighborKey>, PeerReputationEvent),
     AdvertizeBlocks(BlocksAvailableMap, HashMap<ConsensusHash, StacksBlock>), // announce to all wanting neighbors that we have these blocks
     AdvertizeMicroblocks(
         BlocksAvailableMap,
@@ -121,6 +125,16 @@ impl NetworkHandle {
         self.send_request(req)
     }
 
+    /// Count a misbehavior event against each of these peers' reputations
+    pub fn penalize_peers(
+        &mut self,
+        neighbor_keys: Vec<NeighborKey>,
+        event: PeerReputationEvent,
+    ) -> Result<(), net_error> {
+        let req = NetworkRequest::Penalize(neighbor_keys, event);
+        self.send_request(req)
+    }
+
     /// Advertize blocks
     pub fn advertize_blocks(
         &mut self,
@@ -507,6 +521,8 @@ pub struct PeerNetwork {
     pub events: HashMap<NeighborKey, usize>,
     pub connecting: HashMap<usize, ConnectingPeer>,
     pub bans: HashSet<usize>,
+    /// misbehavior to count against neighbors' reputations when bans are next processed
+    pub pending_reputation_events: Vec<(NeighborKey, PeerReputationEvent)>,
 
     // ongoing messages the network is sending via the p2p interface
     pub relay_handles: HashMap<usize, VecDeque<ReplyHandleP2P>>,
@@ -638,6 +654,10 @@ pub struct PeerNetwork {
 
     /// Thread handle for the async block proposal endpoint.
     block_proposal_thread: Option<JoinHandle<()>>,
+
+    /// Subscribers to the event stream RPC endpoint.
+    /// The node's event dispatcher publishes into this.
+    pub event_stream: Arc<EventStreamHub>,
 }
 
 impl PeerNetwork {
@@ -718,6 +738,7 @@ impl PeerNetwork {
             events: HashMap::new(),
             connecting: HashMap::new(),
             bans: HashSet::new(),
+            pending_reputation_events: vec![],
 
             relay_handles: HashMap::new(),
             relayer_stats: RelayerStats::new(),
@@ -799,6 +820,8 @@ impl PeerNetwork {
             nakamoto_inv_generator: InvGenerator::new(),
 
             block_proposal_thread: None,
+
+            event_stream: Arc::new(EventStreamHub::new()),
         };
 
         network.init_block_downloader();
@@ -807,6 +830,15 @@ impl PeerNetwork {
         network
     }
 
+    /// Share an event stream hub with whatever produces the node's events.
+    /// Clients subscribed to the old hub are disconnected, so they can resubscribe to this one.
+    pub fn set_event_stream(&mut self, event_stream: Arc<EventStreamHub>) {
+        if !Arc::ptr_eq(&self.event_stream, &event_stream) {
+            self.event_stream.close();
+        }
+        self.event_stream = event_stream;
+    }
+
     pub fn set_proposal_thread(&mut self, thread: JoinHandle<()>) {
         self.block_proposal_thread = Some(thread);
     }
@@ -1575,6 +1607,12 @@ impl PeerNetwork {
                 }
                 Ok(())
             }
+            NetworkRequest::Penalize(neighbor_keys, event) => {
+                for neighbor_key in neighbor_keys.iter() {
+                    self.penalize_neighbor(neighbor_key, event);
+                }
+                Ok(())
+            }
             NetworkRequest::AdvertizeBlocks(blocks, block_data) => {
                 if !(cfg!(test) && self.connection_opts.disable_block_advertisement) {
                     self.advertize_blocks(blocks, block_data)?;
@@ -1698,8 +1736,92 @@ impl PeerNetwork {
         }
     }
 
+    /// Add pending misbehavior events to the neighbors' reputation scores in the peer DB, and
+    /// forget the neighbors whose scores have decayed away.
+    /// Connected neighbors whose scores reach the ban threshold are queued to be banned, and
+    /// unconnected ones are denied outright.
+    /// The events stay pending if they cannot be stored, and are retried on the next call.
+    fn process_reputation_events(&mut self) -> Result<(), net_error> {
+        if self.pending_reputation_events.is_empty() {
+            return Ok(());
+        }
+
+        let now = get_epoch_time_secs();
+        let half_life_secs = self.connection_opts.reputation_half_life_secs;
+        let ban_threshold = self.connection_opts.reputation_ban_threshold;
+        let bans_enabled =
+            ban_threshold > 0 && !(cfg!(test) && self.connection_opts.disable_network_bans);
+
+        let tx = self.peerdb.tx_begin()?;
+        for (neighbor_key, event) in self.pending_reputation_events.iter() {
+            let reputation = PeerDB::record_peer_reputation_event(
+                &tx,
+                neighbor_key,
+                *event,
+                now,
+                half_life_secs,
+            )?;
+            debug!(
+                "{:?}: Reputation score of {:?} is now {} after {}",
+                &self.local_peer,
+                &neighbor_key,
+                reputation.score,
+                event.get_name_str()
+            );
+
+            if !bans_enabled || reputation.score < ban_threshold as f64 {
+                continue;
+            }
+
+            info!("Peer reputation score reached the ban threshold";
+                  "neighbor" => ?neighbor_key,
+                  "score" => reputation.score,
+                  "threshold" => ban_threshold);
+
+            if let Some(event_id) = self.events.get(neighbor_key) {
+                self.bans.insert(*event_id);
+                continue;
+            }
+
+            // not connected, so deny it directly
+            let allowed = PeerDB::get_peer(
+                &tx,
+                neighbor_key.network_id,
+                &neighbor_key.addrbytes,
+                neighbor_key.port,
+            )?
+            .is_some_and(|neighbor| neighbor.is_allowed());
+            if allowed {
+                debug!(
+                    "Misbehaving neighbor {:?} is allowed; will not punish",
+                    &neighbor_key
+                );
+                continue;
+            }
+            PeerDB::set_deny_peer(
+                &tx,
+                neighbor_key.network_id,
+                &neighbor_key.addrbytes,
+                neighbor_key.port,
+                now + DENY_BAN_DURATION,
+            )?;
+        }
+        PeerDB::prune_peer_reputations(&tx, now, half_life_secs)?;
+        tx.commit()?;
+        self.pending_reputation_events.clear();
+        Ok(())
+    }
+
     /// Process ban requests.  Update the deny in the peer database.  Return the vec of event IDs to disconnect from.
     fn process_bans(&mut self) -> Result<Vec<DropPeer>, net_error> {
+        // misbehavior may add more bans, but the bans already queued are processed regardless
+        if let Err(e) = self.process_reputation_events() {
+            warn!(
+                "{:?}: Failed to process peer reputation events: {e:?}",
+                &self.local_peer
+            );
+        }
+
         if cfg!(test) && self.connection_opts.disable_network_bans {
             return Ok(vec![]);
         }
@@ -2167,6 +2289,42 @@ impl PeerNetwork {
         });
     }
 
+    /// Count a misbehavior event against a neighbor's reputation.  The neighbor will be banned the
+    /// next time bans are processed if this brings its score up to the ban threshold.
+    pub fn penalize_neighbor(&mut self, neighbor: &NeighborKey, event: PeerReputationEvent) {
+        debug!("Penalize {neighbor:?} for {}", event.get_name_str());
+        self.pending_reputation_events
+            .push((neighbor.clone(), event));
+    }
+
+    /// Is this neighbor's reputation score at or above the neighbor walk threshold?  If so, it
+    /// should not be used to start a neighbor walk.
+    pub fn has_poor_reputation(&self, neighbor: &NeighborKey) -> bool {
+        let threshold = self.connection_opts.reputation_walk_threshold;
+        if threshold == 0 {
+            return false;
+        }
+        match PeerDB::get_peer_reputation(
+            self.peerdb_conn(),
+            neighbor.network_id,
+            &neighbor.addrbytes,
+            neighbor.port,
+        ) {
+            Ok(Some(reputation)) => {
+                let score = reputation.score_at(
+                    get_epoch_time_secs(),
+                    self.connection_opts.reputation_half_life_secs,
+                );
+                score >= threshold as f64
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!("Failed to load reputation of {neighbor:?}: {e:?}");
+                false
+            }
+        }
+    }
+
     /// Deregister and ban a neighbor
     pub fn deregister_and_ban_neighbor(
         &mut self,
@@ -2639,6 +2797,23 @@ impl PeerNetwork {
                         },
                         source: DropSource::PeerNetworkDisconnectUnresponsive,
                     });
+                } else if convo.is_session_overdue(now, self.connection_opts.handshake_timeout) {
+                    // we negotiated an encrypted session, but the peer never started its half
+                    debug!(
+                        "{:?}: Disconnect authenticated peer {:?}: no SessionInit within {}s",
+                        &self.local_peer, &convo, self.connection_opts.handshake_timeout
+                    );
+
+                    to_remove.push(DropPeer {
+                        address: convo.peer_addrbytes,
+                        port: convo.peer_port,
+                        reason: DropReason::Unresponsive {
+                            timeout: self.connection_opts.handshake_timeout,
+                            last_seen: convo.stats.last_contact_time,
+                            status: PeerStatus::Authenticated,
+                        },
+                        source: DropSource::PeerNetworkDisconnectUnresponsive,
+                    });
                 }
             } else {
                 // have not handshaked with this remote peer
@@ -5991,4 +6166,87 @@ mod test {
         assert!(peer_1.network.is_connecting_neighbor(&nk));
         assert!(comms.is_neighbor_connecting(&peer_1.network, &nk));
     }
+
+    #[test]
+    fn test_reputation_bans_unconnected_peer() {
+        let peer_1_config = TestPeerConfig::new(function_name!(), 0, 0);
+        let mut peer_1 = TestPeer::new(peer_1_config);
+        let network_id = peer_1.network.get_local_peer().network_id;
+
+        let bad_nk = NeighborKey {
+            peer_version: 0,
+            network_id,
+            addrbytes: PeerAddress::from_ipv4(1, 2, 3, 4),
+            port: 20444,
+        };
+        let allowed_nk = NeighborKey {
+            port: 20445,
+            ..bad_nk.clone()
+        };
+
+        let tx = peer_1.network.peerdb.tx_begin().unwrap();
+        PeerDB::set_allow_peer(&tx, network_id, &allowed_nk.addrbytes, allowed_nk.port, -1)
+            .unwrap();
+        tx.commit().unwrap();
+
+        // a NACK flood alone isn't enough to stop walking to the peer or to ban it
+        peer_1
+            .network
+            .penalize_neighbor(&bad_nk, PeerReputationEvent::NackFlood);
+        peer_1.network.process_bans().unwrap();
+        assert!(peer_1.network.pending_reputation_events.is_empty());
+        assert!(!peer_1.network.has_poor_reputation(&bad_nk));
+        let neighbor = PeerDB::get_peer(
+            peer_1.network.peerdb_conn(),
+            network_id,
+            &bad_nk.addrbytes,
+            bad_nk.port,
+        )
+        .unwrap();
+        assert!(neighbor.is_none());
+
+        // but an invalid block is
+        peer_1
+            .network
+            .penalize_neighbor(&bad_nk, PeerReputationEvent::InvalidBlock);
+        peer_1
+            .network
+            .penalize_neighbor(&allowed_nk, PeerReputationEvent::InvalidBlock);
+        peer_1.network.process_bans().unwrap();
+        assert!(peer_1.network.has_poor_reputation(&bad_nk));
+        assert!(peer_1.network.has_poor_reputation(&allowed_nk));
+
+        let reputation = PeerDB::get_peer_reputation(
+            peer_1.network.peerdb_conn(),
+            network_id,
+            &bad_nk.addrbytes,
+            bad_nk.port,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(reputation.nack_floods, 1);
+        assert_eq!(reputation.invalid_blocks, 1);
+        assert!(reputation.score >= 100.0);
+
+        let neighbor = PeerDB::get_peer(
+            peer_1.network.peerdb_conn(),
+            network_id,
+            &bad_nk.addrbytes,
+            bad_nk.port,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(neighbor.denied > get_epoch_time_secs() as i64);
+
+        // allowed peers are never banned
+        let neighbor = PeerDB::get_peer(
+            peer_1.network.peerdb_conn(),
+            network_id,
+            &allowed_nk.addrbytes,
+            allowed_nk.port,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(neighbor.is_allowed());
+    }
 }
@@ -45,6 +45,7 @@ use crate::monitoring::update_stacks_tip_height;
 use crate::net::chat::*;
 use crate::net::connection::*;
 use crate::net::db::*;
+use crate::net::neighbors::reputation::PeerReputationEvent;
 use crate::net::p2p::*;
 use crate::net::stackerdb::{
     StackerDBConfig, StackerDBEventDispatcher, StackerDBSyncResult, StackerDBs,
@@ -2674,9 +2675,15 @@ impl Relayer {
                         &_local_peer,
                         bad_block_neighbors.len()
                     );
-                    if let Err(e) = self.p2p.ban_peers(bad_block_neighbors) {
+                    if let Err(e) = self.p2p.ban_peers(bad_block_neighbors.clone()) {
                         warn!("Failed to ban bad-block peers: {:?}", &e);
                     }
+                    if let Err(e) = self
+                        .p2p
+                        .penalize_peers(bad_block_neighbors, PeerReputationEvent::InvalidBlock)
+                    {
+                        warn!("Failed to penalize bad-block peers: {:?}", &e);
+                    }
                 }
 
                 // only relay if not ibd
@@ -2868,9 +2875,15 @@ impl Relayer {
         // punish bad peers
         if !bad_neighbors.is_empty() {
             debug!("{:?}: Ban {} peers", &local_peer, bad_neighbors.len());
-            if let Err(e) = self.p2p.ban_peers(bad_neighbors) {
+            if let Err(e) = self.p2p.ban_peers(bad_neighbors.clone()) {
                 warn!("Failed to ban bad-block peers: {:?}", &e);
             }
+            if let Err(e) = self
+                .p2p
+                .penalize_peers(bad_neighbors, PeerReputationEvent::InvalidBlock)
+            {
+                warn!("Failed to penalize bad-block peers: {:?}", &e);
+            }
         }
 
         // relay if not IBD
@@ -26,7 +26,9 @@ use stacks_common::util::get_epoch_time_secs;
 
 use crate::monitoring;
 use crate::net::connection::{ConnectionHttp, ConnectionOptions, ReplyHandleHttp};
-use crate::net::http::HttpResponseContents;
+use crate::net::http::{
+    HttpApiKeyScope, HttpRateLimitClass, HttpRateLimiter, HttpResponseContents, HttpTooManyRequests,
+};
 use crate::net::httpcore::{
     StacksHttp, StacksHttpMessage, StacksHttpRequest, StacksHttpResponse, HTTP_REQUEST_ID_RESERVED,
 };
@@ -239,6 +241,33 @@ impl ConversationHttp {
         Ok(relay_msg_opt)
     }
 
+    /// Reply to a request with HTTP 429, telling the client how many seconds to wait before trying
+    /// again.
+    fn reply_too_many_requests(
+        &mut self,
+        req: &StacksHttpRequest,
+        class: HttpRateLimitClass,
+        retry_after_secs: u64,
+    ) -> Result<(), net_error> {
+        let keep_alive = req.preamble().keep_alive;
+        let (mut response_preamble, response_body) = StacksHttpResponse::new_error(
+            req.preamble(),
+            &HttpTooManyRequests::new(format!(
+                "Too many {} requests; retry after {retry_after_secs} seconds",
+                class.get_name_str()
+            )),
+        )
+        .try_into_contents()?;
+        response_preamble.add_header("Retry-After".into(), retry_after_secs.to_string());
+
+        let mut reply = self.connection.make_relay_handle(self.conn_id)?;
+        response_preamble.content_length = response_body.content_length();
+        response_preamble.consensus_serialize(&mut reply)?;
+        self.reply_streams
+            .push_back((reply, response_body, keep_alive));
+        Ok(())
+    }
+
     /// Make progress on outbound requests.
     fn send_outbound_responses(&mut self) -> Result<(), net_error> {
         // send out streamed responses in the order they were requested
@@ -260,6 +289,11 @@ impl ConversationHttp {
             do_keep_alive = *keep_alive;
 
             while !drained_stream {
+                if http_response.is_waiting() {
+                    // the stream has nothing more to send right now, but it isn't finished (e.g.
+                    // it's an event stream).  Flush what we have, and try again later.
+                    break;
+                }
                 // write out the last-generated data into the write-end of the reply handle's pipe
                 if let Some(pipe_fd) = reply.inner_pipe_out() {
                     let num_written = http_response.pipe_out(pipe_fd)?;
@@ -449,6 +483,11 @@ impl ConversationHttp {
         self.total_request_count > 0 && self.total_reply_count > 0 && self.is_idle()
     }
 
+    /// Are we in the process of streaming a reply?
+    pub fn has_reply_streams(&self) -> bool {
+        !self.reply_streams.is_empty()
+    }
+
     /// Should the connection be kept alive even if drained?
     pub fn is_keep_alive(&self) -> bool {
         self.keep_alive
@@ -471,10 +510,13 @@ impl ConversationHttp {
     }
 
     /// Make progress on in-flight requests and replies.
+    /// Requests that exceed the client's budget in `rate_limiter` are answered with HTTP 429
+    /// instead of being handled.
     /// Returns the list of messages we'll need to forward to the peer network
     pub fn chat(
         &mut self,
         node: &mut StacksNodeState,
+        rate_limiter: &mut HttpRateLimiter,
     ) -> Result<Vec<StacksMessageType>, net_error> {
         // handle in-bound HTTP request(s)
         let num_inbound = self.connection.inbox_len();
@@ -487,10 +529,33 @@ impl ConversationHttp {
             };
 
             match msg {
-                StacksHttpMessage::Request(req) => {
+                StacksHttpMessage::Request(mut req) => {
                     // new request that we can handle
                     self.total_request_count += 1;
                     self.last_request_timestamp = get_epoch_time_secs();
+                    // requests that carry an API key are logged by key name, and are not
+                    // rate-limited if the key has the `rate-limit-exempt` scope
+                    let api_key = self.connection.protocol.request_api_key(&req);
+                    let rate_limit_exempt = api_key
+                        .is_some_and(|key| key.has_scope(HttpApiKeyScope::RateLimitExempt));
+                    let api_key_name = api_key.map(|key| key.name.clone());
+                    if rate_limiter.is_enabled() && !rate_limit_exempt {
+                        let class = self.connection.protocol.rate_limit_class(&mut req);
+                        if let Err(retry_after_secs) =
+                            rate_limiter.try_acquire(&self.peer_addr, class)
+                        {
+                            monitoring::increment_rpc_throttled_requests_counter(class);
+                            info!("Throttled StacksHTTPRequest";
+                                  "verb" => %req.verb(),
+                                  "path" => %req.request_path(),
+                                  "class" => class.get_name_str(),
+                                  "retry_after_secs" => retry_after_secs,
+                                  "conn_id" => self.conn_id,
+                                  "peer_addr" => &self.peer_addr);
+                            self.reply_too_many_requests(&req, class, retry_after_secs)?;
+                            continue;
+                        }
+                    }
                     let latency = req.duration_ms();
                     let start_time = Instant::now();
                     let verb = req.verb().to_string();
@@ -513,6 +578,7 @@ impl ConversationHttp {
                           "latency_ms" => latency,
                           "conn_id" => self.conn_id,
                           "peer_addr" => &self.peer_addr,
+                          "api_key" => api_key_name.as_deref().unwrap_or("None"),
                           "p2p_msg" => msg_opt_log);
 
                     if let Some(msg) = msg_opt {
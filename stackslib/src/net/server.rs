@@ -14,13 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{Read, Write};
 
 use mio::net as mio_net;
+use rustls::ServerConnection;
 use stacks_common::types::net::{PeerAddress, PeerHost};
 use stacks_common::util::get_epoch_time_secs;
 
 use crate::net::connection::*;
+use crate::net::http::tls::TLS_HANDSHAKE_RECORD_TYPE;
 use crate::net::http::*;
 use crate::net::httpcore::*;
 use crate::net::p2p::PeerNetwork;
@@ -53,6 +57,17 @@ pub struct HttpPeer {
 
     /// connection options
     pub connection_opts: ConnectionOptions,
+
+    /// per-client budgets for inbound requests
+    pub rate_limiter: HttpRateLimiter,
+
+    /// TLS configuration, if inbound clients may use TLS
+    pub tls: Option<HttpTlsServer>,
+    /// TLS sessions of inbound conversations whose clients chose TLS
+    pub tls_sessions: HashMap<usize, ServerConnection>,
+    /// inbound conversations that have not yet sent anything, so we don't know yet whether they
+    /// will use TLS
+    pub tls_undecided: HashSet<usize>,
 }
 
 impl HttpPeer {
@@ -69,10 +84,33 @@ impl HttpPeer {
             http_server_handle: server_handle,
             http_server_addr: server_addr,
 
+            rate_limiter: HttpRateLimiter::new(&conn_opts),
+            tls: HttpPeer::make_tls_server(&conn_opts),
+            tls_sessions: HashMap::new(),
+            tls_undecided: HashSet::new(),
             connection_opts: conn_opts,
         }
     }
 
+    /// Load the RPC TLS certificate and key, if configured.
+    /// If they cannot be loaded, then only plaintext clients will be served (or no clients at all,
+    /// if TLS is required).
+    fn make_tls_server(conn_opts: &ConnectionOptions) -> Option<HttpTlsServer> {
+        let (Some(cert_path), Some(key_path)) = (
+            conn_opts.rpc_tls_cert_path.as_ref(),
+            conn_opts.rpc_tls_key_path.as_ref(),
+        ) else {
+            return None;
+        };
+        match HttpTlsServer::new(cert_path, key_path) {
+            Ok(tls) => Some(tls),
+            Err(e) => {
+                error!("Failed to load RPC TLS certificate; TLS will not be available"; "error" => %e);
+                None
+            }
+        }
+    }
+
     pub fn set_server_handle(&mut self, h: usize, addr: SocketAddr) {
         self.http_server_handle = h;
         self.http_server_addr = addr;
@@ -271,6 +309,9 @@ impl HttpPeer {
             }
         }
 
+        if outbound_url.is_none() && (self.tls.is_some() || self.connection_opts.rpc_tls_required) {
+            self.tls_undecided.insert(event_id);
+        }
         self.sockets.insert(event_id, socket);
         self.peers.insert(event_id, new_convo);
         Ok(())
@@ -281,10 +322,17 @@ impl HttpPeer {
     pub fn deregister_http(&mut self, network_state: &mut NetworkState, event_id: usize) {
         test_debug!("Remove HTTP event {}", event_id);
         self.peers.remove(&event_id);
+        self.tls_undecided.remove(&event_id);
+        let tls_session = self.tls_sessions.remove(&event_id);
 
         match self.sockets.remove(&event_id) {
             None => {}
-            Some(sock) => {
+            Some(mut sock) => {
+                if let Some(mut session) = tls_session {
+                    // best-effort
+                    session.send_close_notify();
+                    let _ = session.write_tls(&mut sock);
+                }
                 let _ = network_state.deregister(event_id, &sock);
             }
         }
@@ -336,8 +384,8 @@ impl HttpPeer {
 
     /// Saturate a conversation's socket -- either sends the whole request, or fills the socket
     /// buffer.
-    pub fn saturate_http_socket(
-        client_sock: &mut mio::net::TcpStream,
+    pub fn saturate_http_socket<W: Write + fmt::Debug>(
+        client_sock: &mut W,
         convo: &mut ConversationHttp,
     ) -> Result<(), net_error> {
         // saturate the socket
@@ -356,9 +404,34 @@ impl HttpPeer {
             }
         }
 
+        // push out anything the socket is still holding back (i.e. buffered TLS records)
+        if let Err(e) = client_sock.flush() {
+            debug!("Failed to flush socket {:?}: {:?}", &client_sock, &e);
+            return Err(net_error::WriteError(e));
+        }
         Ok(())
     }
 
+    /// Whether a plaintext client at `peer_addr` may be served.  Loopback clients (such as this
+    /// node's own miner) are served even if TLS is required, since their traffic never leaves
+    /// the host.
+    fn allow_plaintext(tls_required: bool, peer_addr: Option<SocketAddr>) -> bool {
+        !tls_required || peer_addr.is_some_and(|addr| addr.ip().is_loopback())
+    }
+
+    /// Find out whether an inbound client is speaking TLS by peeking at the first byte it sent.
+    /// Returns Ok(None) if it has not sent anything yet.
+    fn sniff_tls(client_sock: &mio_net::TcpStream) -> Result<Option<bool>, net_error> {
+        let mut first_byte = [0u8; 1];
+        match client_sock.peek(&mut first_byte) {
+            // closed before sending anything; let the conversation find that out
+            Ok(0) => Ok(Some(false)),
+            Ok(_) => Ok(Some(first_byte[0] == TLS_HANDSHAKE_RECORD_TYPE)),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(net_error::ReadError(e)),
+        }
+    }
+
     /// Process new inbound HTTP connections we just accepted.
     /// Returns the event IDs of sockets we need to register
     fn process_new_sockets(
@@ -411,10 +484,11 @@ impl HttpPeer {
     /// Process network traffic on a HTTP conversation.
     /// Returns whether or not the convo is still alive, as well as any message(s) that need to be
     /// forwarded to the peer network.
-    fn process_http_conversation(
+    fn process_http_conversation<S: Read + Write + fmt::Debug>(
         node_state: &mut StacksNodeState,
+        rate_limiter: &mut HttpRateLimiter,
         event_id: usize,
-        client_sock: &mut mio_net::TcpStream,
+        client_sock: &mut S,
         convo: &mut ConversationHttp,
     ) -> Result<(bool, Vec<StacksMessageType>), net_error> {
         // get incoming bytes and update the state of this conversation.
@@ -471,7 +545,7 @@ impl HttpPeer {
         // react to inbound messages -- do we need to send something out, or fulfill requests
         // to other threads?  Try to chat even if the recv() failed, since we'll want to at
         // least drain the conversation inbox.
-        let msgs = match convo.chat(node_state) {
+        let msgs = match convo.chat(node_state, rate_limiter) {
             Ok(msgs) => msgs,
             Err(e) => {
                 debug!(
@@ -553,16 +627,74 @@ impl HttpPeer {
                 continue;
             };
 
+            if self.tls_undecided.contains(event_id) {
+                match HttpPeer::sniff_tls(client_sock) {
+                    Ok(None) => {
+                        // nothing to read yet
+                        continue;
+                    }
+                    Ok(Some(is_tls)) => {
+                        self.tls_undecided.remove(event_id);
+                        if is_tls {
+                            let session = match self.tls.as_ref().map(|tls| tls.new_session()) {
+                                Some(Ok(session)) => session,
+                                Some(Err(e)) => {
+                                    warn!(
+                                        "Failed to start TLS session on {:?}: {:?}",
+                                        &client_sock, &e
+                                    );
+                                    to_remove.push(*event_id);
+                                    continue;
+                                }
+                                None => {
+                                    warn!(
+                                        "TLS client {:?}, but TLS is not configured",
+                                        &client_sock
+                                    );
+                                    to_remove.push(*event_id);
+                                    continue;
+                                }
+                            };
+                            debug!("HTTP event {} uses TLS", event_id);
+                            self.tls_sessions.insert(*event_id, session);
+                        } else if !HttpPeer::allow_plaintext(
+                            self.connection_opts.rpc_tls_required,
+                            client_sock.peer_addr().ok(),
+                        ) {
+                            debug!("Plaintext client {:?}, but TLS is required", &client_sock);
+                            to_remove.push(*event_id);
+                            continue;
+                        }
+                    }
+                    Err(e) => {
+                        debug!("Failed to read from {:?}: {:?}", &client_sock, &e);
+                        to_remove.push(*event_id);
+                        continue;
+                    }
+                }
+            }
+
             match self.peers.get_mut(event_id) {
                 Some(ref mut convo) => {
                     // activity on a http socket
                     debug!("Process HTTP data from {:?}", convo);
-                    match HttpPeer::process_http_conversation(
-                        node_state,
-                        *event_id,
-                        client_sock,
-                        convo,
-                    ) {
+                    let process_res = match self.tls_sessions.get_mut(event_id) {
+                        Some(session) => HttpPeer::process_http_conversation(
+                            node_state,
+                            &mut self.rate_limiter,
+                            *event_id,
+                            &mut HttpTlsStream::new(session, client_sock),
+                            convo,
+                        ),
+                        None => HttpPeer::process_http_conversation(
+                            node_state,
+                            &mut self.rate_limiter,
+                            *event_id,
+                            client_sock,
+                            convo,
+                        ),
+                    };
+                    match process_res {
                         Ok((alive, mut new_msgs)) => {
                             if !alive {
                                 debug!("HTTP convo {:?} is no longer alive", &convo);
@@ -599,8 +731,32 @@ impl HttpPeer {
             if let Err(e) = convo.try_flush() {
                 info!("Broken HTTP connection {:?}: {:?}", convo, &e);
                 close.push(*event_id);
+                continue;
             }
-            if convo.is_drained() && !convo.is_keep_alive() {
+            if convo.has_reply_streams() {
+                // A long-lived stream (e.g. an event stream) may have produced new data since its
+                // socket last became writeable, and we won't get another edge trigger for it
+                // until we write something.  So, push it out now.
+                if let Some(client_sock) = self.sockets.get_mut(event_id) {
+                    let saturate_res = match self.tls_sessions.get_mut(event_id) {
+                        Some(session) => HttpPeer::saturate_http_socket(
+                            &mut HttpTlsStream::new(session, client_sock),
+                            convo,
+                        ),
+                        None => HttpPeer::saturate_http_socket(client_sock, convo),
+                    };
+                    if let Err(e) = saturate_res {
+                        info!("Broken HTTP connection {:?}: {:?}", convo, &e);
+                        close.push(*event_id);
+                        continue;
+                    }
+                }
+            }
+            let tls_pending = self
+                .tls_sessions
+                .get(event_id)
+                .is_some_and(|session| session.wants_write());
+            if convo.is_drained() && !convo.is_keep_alive() && !tls_pending {
                 // did some work, but nothing more to do and we're not keep-alive
                 debug!("Close drained HTTP connection {:?}", convo);
                 close.push(*event_id);
@@ -651,6 +807,14 @@ impl HttpPeer {
         // clear out slow or non-responsive peers
         self.disconnect_unresponsive(network_state);
 
+        // forget about clients that have not used their request budgets recently
+        self.rate_limiter.prune();
+
+        // pick up a renewed TLS certificate
+        if let Some(tls) = self.tls.as_mut() {
+            tls.try_reload();
+        }
+
         stacks_msgs
     }
 }
@@ -672,8 +836,18 @@ mod test {
     use crate::chainstate::stacks::db::StacksChainState;
     use crate::chainstate::stacks::test::*;
     use crate::chainstate::stacks::{StacksBlockHeader, *};
+    use crate::net::http::tls::make_client_config;
     use crate::net::test::*;
+    use crate::net::tests::tls_test_file_path;
+
+    /// A client's connection to the test server, with or without TLS
+    trait TestClientSocket: Read + Write + Send {}
+    impl<S: Read + Write + Send> TestClientSocket for S {}
 
+    /// Runs a test peer with `conn_opts`, and has `num_clients` clients each send it the request
+    /// from `make_request` and check the response with `check_result`.
+    /// If the peer offers TLS, then even-numbered clients use it (trusting `ca-1.pem`), and
+    /// odd-numbered clients don't.
     fn test_http_server<F, C>(
         test_name: &str,
         peer_p2p: u16,
@@ -688,6 +862,11 @@ mod test {
         F: FnMut(usize, &mut StacksChainState) -> Vec<u8>,
         C: Fn(usize, Result<Vec<u8>, net_error>) -> bool,
     {
+        let tls_config = conn_opts
+            .rpc_tls_cert_path
+            .as_ref()
+            .map(|_| make_client_config(Some(&tls_test_file_path("ca-1.pem"))).unwrap());
+
         let mut peer_config = TestPeerConfig::new(test_name, peer_p2p, peer_http);
         peer_config.connection_opts = conn_opts;
 
@@ -729,13 +908,25 @@ mod test {
 
         for (i, request) in client_requests.into_iter().enumerate() {
             let (client_sx, client_rx) = sync_channel(1);
+            let tls_config = tls_config.clone();
             let client = thread::spawn(move || {
-                let mut sock = TcpStream::connect(
+                let sock = TcpStream::connect(
                     &format!("127.0.0.1:{}", peer_http)
                         .parse::<SocketAddr>()
                         .unwrap(),
                 )
                 .unwrap();
+                let mut sock: Box<dyn TestClientSocket> = match tls_config {
+                    Some(tls_config) if i % 2 == 0 => {
+                        let session = rustls::ClientConnection::new(
+                            tls_config,
+                            "localhost".try_into().unwrap(),
+                        )
+                        .unwrap();
+                        Box::new(rustls::StreamOwned::new(session, sock))
+                    }
+                    _ => Box::new(sock),
+                };
 
                 if client_sleep > 0 {
                     sleep_ms(client_sleep * 1000);
@@ -1205,6 +1396,207 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_http_429() {
+        let mut conn_opts = ConnectionOptions::default();
+        conn_opts.rpc_rate_limit_other = 1;
+        conn_opts.rpc_rate_limit_window_secs = 3600;
+
+        let num_ok = RefCell::new(0);
+        let num_throttled = RefCell::new(0);
+        test_http_server(
+            function_name!(),
+            51074,
+            51075,
+            conn_opts,
+            3,
+            0,
+            |client_id, _| {
+                let mut request = StacksHttpRequest::new_for_peer(
+                    PeerHost::from_host_port("127.0.0.1".to_string(), 51075),
+                    "GET".to_string(),
+                    "/v2/info".to_string(),
+                    HttpRequestContents::new(),
+                )
+                .unwrap();
+                request.preamble_mut().keep_alive = false;
+                request.try_serialize().unwrap()
+            },
+            |client_id, http_response_bytes_res| {
+                // all clients are on 127.0.0.1, so only one of them gets a PeerInfo and the
+                // others are told to come back in an hour
+                let http_response_bytes = http_response_bytes_res.unwrap();
+                let http_response_str = String::from_utf8_lossy(&http_response_bytes);
+                eprintln!("HTTP response\n{}", http_response_str);
+                if http_response_str.contains("429 Too Many Requests") {
+                    let retry_after_secs: u64 = http_response_str
+                        .lines()
+                        .find_map(|line| {
+                            line.to_lowercase()
+                                .strip_prefix("retry-after: ")
+                                .map(|secs| secs.trim().to_string())
+                        })
+                        .expect("429 response has no Retry-After header")
+                        .parse()
+                        .unwrap();
+                    assert!(retry_after_secs > 3500 && retry_after_secs <= 3600);
+                    *num_throttled.borrow_mut() += 1;
+                } else {
+                    StacksHttp::parse_response("GET", "/v2/info", &http_response_bytes).unwrap();
+                    *num_ok.borrow_mut() += 1;
+                }
+                true
+            },
+        );
+        assert_eq!(*num_ok.borrow(), 1);
+        assert_eq!(*num_throttled.borrow(), 2);
+    }
+
+    #[test]
+    fn test_http_429_api_key_scopes() {
+        let mut conn_opts = ConnectionOptions::default();
+        conn_opts.rpc_rate_limit_other = 1;
+        conn_opts.rpc_rate_limit_window_secs = 3600;
+        conn_opts.api_keys = vec![
+            HttpApiKey {
+                name: "exempt".into(),
+                key: "exempt-key".into(),
+                scopes: vec![HttpApiKeyScope::RateLimitExempt],
+            },
+            HttpApiKey {
+                name: "proposer".into(),
+                key: "proposer-key".into(),
+                scopes: vec![HttpApiKeyScope::BlockProposal],
+            },
+        ];
+
+        let num_ok = RefCell::new(0);
+        let num_throttled = RefCell::new(0);
+        test_http_server(
+            function_name!(),
+            51084,
+            51085,
+            conn_opts,
+            4,
+            0,
+            |client_id, _| {
+                let mut request = StacksHttpRequest::new_for_peer(
+                    PeerHost::from_host_port("127.0.0.1".to_string(), 51085),
+                    "GET".to_string(),
+                    "/v2/info".to_string(),
+                    HttpRequestContents::new(),
+                )
+                .unwrap();
+                request.preamble_mut().keep_alive = false;
+                // clients 0 and 1 are exempt, but a key without the `rate-limit-exempt` scope
+                // is limited like no key at all
+                let key = if client_id < 2 {
+                    "exempt-key"
+                } else {
+                    "proposer-key"
+                };
+                request.add_header("authorization".into(), key.into());
+                request.try_serialize().unwrap()
+            },
+            |client_id, http_response_bytes_res| {
+                let http_response_bytes = http_response_bytes_res.unwrap();
+                let http_response_str = String::from_utf8_lossy(&http_response_bytes);
+                eprintln!("HTTP response\n{}", http_response_str);
+                if http_response_str.contains("429 Too Many Requests") {
+                    assert!(client_id >= 2);
+                    *num_throttled.borrow_mut() += 1;
+                } else {
+                    StacksHttp::parse_response("GET", "/v2/info", &http_response_bytes).unwrap();
+                    *num_ok.borrow_mut() += 1;
+                }
+                true
+            },
+        );
+        assert_eq!(*num_ok.borrow(), 3);
+        assert_eq!(*num_throttled.borrow(), 1);
+    }
+
+    #[test]
+    fn test_http_getinfo_tls() {
+        let mut conn_opts = ConnectionOptions::default();
+        conn_opts.rpc_tls_cert_path = Some(tls_test_file_path("server-1.pem"));
+        conn_opts.rpc_tls_key_path = Some(tls_test_file_path("server-1.key"));
+
+        // client 0 uses TLS and client 1 doesn't, but both talk to the same port
+        test_http_server(
+            function_name!(),
+            51076,
+            51077,
+            conn_opts,
+            2,
+            0,
+            |client_id, _| {
+                let mut request = StacksHttpRequest::new_for_peer(
+                    PeerHost::from_host_port("127.0.0.1".to_string(), 51077),
+                    "GET".to_string(),
+                    "/v2/info".to_string(),
+                    HttpRequestContents::new(),
+                )
+                .unwrap();
+                request.preamble_mut().keep_alive = false;
+                request.try_serialize().unwrap()
+            },
+            |client_id, http_response_bytes_res| {
+                let http_response_bytes = http_response_bytes_res.unwrap();
+                StacksHttp::parse_response("GET", "/v2/info", &http_response_bytes).unwrap();
+                true
+            },
+        );
+    }
+
+    #[test]
+    fn test_http_getinfo_tls_required() {
+        let mut conn_opts = ConnectionOptions::default();
+        conn_opts.rpc_tls_cert_path = Some(tls_test_file_path("server-1.pem"));
+        conn_opts.rpc_tls_key_path = Some(tls_test_file_path("server-1.key"));
+        conn_opts.rpc_tls_required = true;
+
+        // client 0 uses TLS; client 1 doesn't, but is served anyway since it's on loopback
+        test_http_server(
+            function_name!(),
+            51078,
+            51079,
+            conn_opts,
+            2,
+            0,
+            |client_id, _| {
+                let mut request = StacksHttpRequest::new_for_peer(
+                    PeerHost::from_host_port("127.0.0.1".to_string(), 51079),
+                    "GET".to_string(),
+                    "/v2/info".to_string(),
+                    HttpRequestContents::new(),
+                )
+                .unwrap();
+                request.preamble_mut().keep_alive = false;
+                request.try_serialize().unwrap()
+            },
+            |client_id, http_response_bytes_res| {
+                let http_response_bytes = http_response_bytes_res.unwrap();
+                StacksHttp::parse_response("GET", "/v2/info", &http_response_bytes).unwrap();
+                true
+            },
+        );
+    }
+
+    #[test]
+    fn test_http_allow_plaintext() {
+        let loopback: SocketAddr = "127.0.0.1:20443".parse().unwrap();
+        let loopback_v6: SocketAddr = "[::1]:20443".parse().unwrap();
+        let remote: SocketAddr = "192.0.2.1:20443".parse().unwrap();
+
+        assert!(HttpPeer::allow_plaintext(false, Some(remote)));
+        assert!(HttpPeer::allow_plaintext(false, None));
+        assert!(HttpPeer::allow_plaintext(true, Some(loopback)));
+        assert!(HttpPeer::allow_plaintext(true, Some(loopback_v6)));
+        assert!(!HttpPeer::allow_plaintext(true, Some(remote)));
+        assert!(!HttpPeer::allow_plaintext(true, None));
+    }
+
     #[test]
     fn test_http_no_connecting_event_id_leak() {
         let mut conn_opts = ConnectionOptions::default();
@@ -0,0 +1,439 @@
+// Copyright (C) 2025 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Encrypted p2p sessions.
+//!
+//! Two peers that both advertise `ServiceFlags::ENCRYPTED` in their handshakes each send a signed
+//! `SessionInit` message, carrying a fresh ephemeral public key, right after the handshake
+//! completes.  Every byte a peer sends after its `SessionInit` is encrypted with a key that only
+//! it and the remote peer can derive, so a peer sends nothing after its `SessionInit` until it has
+//! the remote peer's.
+//!
+//! Each direction of the connection has its own key, derived in the style of the Noise `KK`
+//! pattern from the sender's ephemeral and node keys `e` and `s`, and the receiver's ephemeral
+//! and node keys `f` and `r` (the node keys are exchanged in `HandshakeData`):
+//!
+//! ```text
+//! ikm  = ECDH(e, F) || ECDH(e, R) || ECDH(s, F) || ECDH(s, R)
+//! info = E || F || S || R
+//! key  = HKDF-SHA256(salt = SESSION_KDF_SALT, ikm, info)
+//! ```
+//!
+//! The receiver computes the same key as `ECDH(f, E) || ECDH(r, E) || ECDH(f, S) || ECDH(r, S)`.
+//! Only the holder of `s` can produce a key that the receiver accepts, and only the holder of `r`
+//! can read what is sent.  Since both ephemeral keys are mixed in, a session's keys cannot be
+//! recovered from the node keys alone once the ephemeral keys are gone, and a recorded session
+//! cannot be replayed in another connection.
+//!
+//! The encrypted byte stream is a sequence of frames.  Each frame is a 4-byte big-endian length
+//! followed by that many bytes of ChaCha20-Poly1305 ciphertext (including its tag).  The length
+//! prefix is authenticated as associated data, and the nonce is a per-direction frame counter, so
+//! dropped, reordered, replayed, or altered frames all fail to open and tear down the connection.
+
+use std::fmt;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use secp256k1::ecdh::SharedSecret;
+use sha2::Sha256;
+use stacks_common::types::StacksPublicKeyBuffer;
+use stacks_common::util::secp256k1::{Secp256k1PrivateKey, Secp256k1PublicKey};
+
+use crate::net::{Error as net_error, SessionInitData};
+
+/// Domain separator for session key derivation
+pub const SESSION_KDF_SALT: &[u8] = b"stacks-p2p-session-v1";
+/// Largest plaintext we will put into (or accept from) a single frame
+pub const MAX_SESSION_FRAME_LEN: usize = 65536;
+/// Length of the big-endian ciphertext length that begins each frame
+const FRAME_LENGTH_PREFIX_LEN: usize = 4;
+/// Length of the Poly1305 tag at the end of each frame's ciphertext
+const FRAME_TAG_LEN: usize = 16;
+
+/// One direction of an encrypted session.  Seals frames for the sender, or opens them for the
+/// receiver, depending on which side constructed it.
+pub struct SessionCipher {
+    cipher: ChaCha20Poly1305,
+    /// number of frames sealed or opened so far; doubles as the nonce
+    frame_count: u64,
+}
+
+impl fmt::Debug for SessionCipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // never log key material
+        write!(f, "SessionCipher(frames={})", self.frame_count)
+    }
+}
+
+/// Diffie-Hellman between a Stacks private key and a Stacks public key.
+fn ecdh(privkey: &Secp256k1PrivateKey, pubkey: &Secp256k1PublicKey) -> Result<[u8; 32], net_error> {
+    let secret_key = secp256k1::SecretKey::from_slice(privkey.as_slice())
+        .map_err(|e| net_error::SessionError(format!("Invalid private key: {e:?}")))?;
+    let public_key = secp256k1::PublicKey::from_slice(&pubkey.to_bytes_compressed())
+        .map_err(|e| net_error::SessionError(format!("Invalid public key: {e:?}")))?;
+    Ok(SharedSecret::new(&public_key, &secret_key).secret_bytes())
+}
+
+/// Our half of an encrypted session that we have started, but that the remote peer has not.
+/// Holds our ephemeral key until we have the remote peer's `SessionInit`.
+#[derive(Clone)]
+pub struct PendingSession {
+    ephemeral_private_key: Secp256k1PrivateKey,
+}
+
+impl fmt::Debug for PendingSession {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // never log key material
+        write!(f, "PendingSession")
+    }
+}
+
+impl PendingSession {
+    /// Start an encrypted session.  Returns our half of it, and the `SessionInit` payload to send
+    /// to the remote peer (in the clear).
+    pub fn new() -> (PendingSession, SessionInitData) {
+        let mut ephemeral_private_key = Secp256k1PrivateKey::random();
+        ephemeral_private_key.set_compress_public(true);
+        let session_init = SessionInitData {
+            ephemeral_public_key: StacksPublicKeyBuffer::from_public_key(
+                &Secp256k1PublicKey::from_private(&ephemeral_private_key),
+            ),
+        };
+        (
+            PendingSession {
+                ephemeral_private_key,
+            },
+            session_init,
+        )
+    }
+
+    /// Finish the session with the `SessionInit` that the peer with public key `peer_public_key`
+    /// sent us.  Returns the cipher that seals everything we send after our `SessionInit`, and
+    /// the cipher that opens everything the peer sends after its `SessionInit`.
+    pub fn finish(
+        self,
+        local_private_key: &Secp256k1PrivateKey,
+        peer_public_key: &Secp256k1PublicKey,
+        session_init: &SessionInitData,
+    ) -> Result<(SessionCipher, SessionCipher), net_error> {
+        let local_ephemeral_key = &self.ephemeral_private_key;
+        let peer_ephemeral_key = session_init
+            .ephemeral_public_key
+            .to_public_key()
+            .map_err(|e| net_error::SessionError(e.to_string()))?;
+        let local_ephemeral_public_key = Secp256k1PublicKey::from_private(local_ephemeral_key);
+        let local_public_key = Secp256k1PublicKey::from_private(local_private_key);
+
+        let outbound = SessionCipher::derive(
+            [
+                ecdh(local_ephemeral_key, &peer_ephemeral_key)?,
+                ecdh(local_ephemeral_key, peer_public_key)?,
+                ecdh(local_private_key, &peer_ephemeral_key)?,
+                ecdh(local_private_key, peer_public_key)?,
+            ],
+            [
+                &local_ephemeral_public_key,
+                &peer_ephemeral_key,
+                &local_public_key,
+                peer_public_key,
+            ],
+        )?;
+        let inbound = SessionCipher::derive(
+            [
+                ecdh(local_ephemeral_key, &peer_ephemeral_key)?,
+                ecdh(local_private_key, &peer_ephemeral_key)?,
+                ecdh(local_ephemeral_key, peer_public_key)?,
+                ecdh(local_private_key, peer_public_key)?,
+            ],
+            [
+                &peer_ephemeral_key,
+                &local_ephemeral_public_key,
+                peer_public_key,
+                &local_public_key,
+            ],
+        )?;
+        Ok((outbound, inbound))
+    }
+}
+
+impl SessionCipher {
+    /// Derive the key for one direction of a session from the four ECDH outputs and the four
+    /// public keys, both in the order given in the module docs.
+    fn derive(
+        shared_secrets: [[u8; 32]; 4],
+        public_keys: [&Secp256k1PublicKey; 4],
+    ) -> Result<SessionCipher, net_error> {
+        let ikm = shared_secrets.concat();
+        let info: Vec<u8> = public_keys
+            .iter()
+            .flat_map(|public_key| public_key.to_bytes_compressed())
+            .collect();
+
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(Some(SESSION_KDF_SALT), &ikm)
+            .expand(&info, &mut key)
+            .map_err(|e| net_error::SessionError(format!("Failed to derive session key: {e}")))?;
+
+        Ok(SessionCipher {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+            frame_count: 0,
+        })
+    }
+
+    /// Get the nonce for the next frame
+    fn next_nonce(&mut self) -> Result<[u8; 12], net_error> {
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&self.frame_count.to_be_bytes());
+        self.frame_count = self
+            .frame_count
+            .checked_add(1)
+            .ok_or_else(|| net_error::SessionError("Session nonce exhausted".to_string()))?;
+        Ok(nonce)
+    }
+
+    /// Encrypt `plaintext` as one or more frames, and append them to `out`.
+    pub fn seal(&mut self, plaintext: &[u8], out: &mut Vec<u8>) -> Result<(), net_error> {
+        for chunk in plaintext.chunks(MAX_SESSION_FRAME_LEN) {
+            let frame_len = u32::try_from(chunk.len() + FRAME_TAG_LEN)
+                .expect("FATAL: session frame length exceeds u32::MAX");
+            let length_prefix = frame_len.to_be_bytes();
+            let nonce = self.next_nonce()?;
+            let ciphertext = self
+                .cipher
+                .encrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: chunk,
+                        aad: &length_prefix,
+                    },
+                )
+                .map_err(|_| net_error::SessionError("Failed to seal frame".to_string()))?;
+            out.extend_from_slice(&length_prefix);
+            out.extend_from_slice(&ciphertext);
+        }
+        Ok(())
+    }
+
+    /// Decrypt every complete frame at the front of `frames`, and remove them from it.  Bytes of
+    /// a trailing partial frame are left in `frames` until the rest of it arrives.
+    /// Returns the concatenated plaintext of the frames opened.
+    /// Returns net_error::SessionError if a frame is malformed or fails to authenticate; the
+    /// connection cannot recover from this.
+    pub fn open(&mut self, frames: &mut Vec<u8>) -> Result<Vec<u8>, net_error> {
+        let mut plaintext = vec![];
+        let mut offset = 0;
+        while let Some(length_prefix) = frames.get(offset..offset + FRAME_LENGTH_PREFIX_LEN) {
+            let length_prefix: [u8; FRAME_LENGTH_PREFIX_LEN] = length_prefix
+                .try_into()
+                .expect("FATAL: bad frame length prefix slice");
+            let frame_len = u32::from_be_bytes(length_prefix) as usize;
+            if !(FRAME_TAG_LEN..=MAX_SESSION_FRAME_LEN + FRAME_TAG_LEN).contains(&frame_len) {
+                return Err(net_error::SessionError(format!(
+                    "Invalid frame length {frame_len}"
+                )));
+            }
+
+            let frame_start = offset + FRAME_LENGTH_PREFIX_LEN;
+            let Some(ciphertext) = frames.get(frame_start..frame_start + frame_len) else {
+                // need more bytes
+                break;
+            };
+            let nonce = self.next_nonce()?;
+            let frame_plaintext = self
+                .cipher
+                .decrypt(
+                    Nonce::from_slice(&nonce),
+                    Payload {
+                        msg: ciphertext,
+                        aad: &length_prefix,
+                    },
+                )
+                .map_err(|_| net_error::SessionError("Frame failed to authenticate".to_string()))?;
+            plaintext.extend_from_slice(&frame_plaintext);
+            offset = frame_start + frame_len;
+        }
+        frames.drain(..offset);
+        Ok(plaintext)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_keys() -> (Secp256k1PrivateKey, Secp256k1PrivateKey) {
+        let mut alice = Secp256k1PrivateKey::random();
+        alice.set_compress_public(true);
+        let mut bob = Secp256k1PrivateKey::random();
+        bob.set_compress_public(true);
+        (alice, bob)
+    }
+
+    /// Run a session between `alice` and `bob`.  Returns the cipher that seals what alice sends,
+    /// and the cipher that opens it for bob.
+    fn make_session(
+        alice: &Secp256k1PrivateKey,
+        bob: &Secp256k1PrivateKey,
+    ) -> (SessionCipher, SessionCipher) {
+        let alice_pubk = Secp256k1PublicKey::from_private(alice);
+        let bob_pubk = Secp256k1PublicKey::from_private(bob);
+        let (alice_session, alice_init) = PendingSession::new();
+        let (bob_session, bob_init) = PendingSession::new();
+        let (sealer, _) = alice_session.finish(alice, &bob_pubk, &bob_init).unwrap();
+        let (_, opener) = bob_session.finish(bob, &alice_pubk, &alice_init).unwrap();
+        (sealer, opener)
+    }
+
+    #[test]
+    fn test_session_seal_open() {
+        let (alice, bob) = make_keys();
+        let (mut sealer, mut opener) = make_session(&alice, &bob);
+
+        let mut frames = vec![];
+        sealer.seal(b"hello", &mut frames).unwrap();
+        sealer
+            .seal(&[0x42; 3 * MAX_SESSION_FRAME_LEN / 2], &mut frames)
+            .unwrap();
+        sealer.seal(b"world", &mut frames).unwrap();
+
+        let mut expected = b"hello".to_vec();
+        expected.extend_from_slice(&[0x42; 3 * MAX_SESSION_FRAME_LEN / 2]);
+        expected.extend_from_slice(b"world");
+
+        // feed the frames in byte-by-byte-ish pieces, so partial frames get buffered
+        let mut pending = vec![];
+        let mut opened = vec![];
+        for piece in frames.chunks(7919) {
+            pending.extend_from_slice(piece);
+            opened.extend_from_slice(&opener.open(&mut pending).unwrap());
+        }
+        assert!(pending.is_empty());
+        assert_eq!(opened, expected);
+
+        // the other direction has its own key
+        let alice_pubk = Secp256k1PublicKey::from_private(&alice);
+        let bob_pubk = Secp256k1PublicKey::from_private(&bob);
+        let (alice_session, alice_init) = PendingSession::new();
+        let (bob_session, bob_init) = PendingSession::new();
+        let (mut alice_sealer, mut alice_opener) =
+            alice_session.finish(&alice, &bob_pubk, &bob_init).unwrap();
+        let (mut bob_sealer, mut bob_opener) =
+            bob_session.finish(&bob, &alice_pubk, &alice_init).unwrap();
+
+        let mut to_bob = vec![];
+        alice_sealer.seal(b"hello bob", &mut to_bob).unwrap();
+        let mut to_alice = vec![];
+        bob_sealer.seal(b"hello alice", &mut to_alice).unwrap();
+        assert_ne!(to_bob[4..], to_alice[4..]);
+        assert_eq!(bob_opener.open(&mut to_bob).unwrap(), b"hello bob".to_vec());
+        assert_eq!(
+            alice_opener.open(&mut to_alice).unwrap(),
+            b"hello alice".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_session_rejects_tampering() {
+        let (alice, bob) = make_keys();
+        let alice_pubk = Secp256k1PublicKey::from_private(&alice);
+        let bob_pubk = Secp256k1PublicKey::from_private(&bob);
+
+        let (alice_session, alice_init) = PendingSession::new();
+        let (bob_session, bob_init) = PendingSession::new();
+        let (mut sealer, _) = alice_session.finish(&alice, &bob_pubk, &bob_init).unwrap();
+        let make_opener = |alice_init: &SessionInitData| {
+            let (_, opener) = bob_session
+                .clone()
+                .finish(&bob, &alice_pubk, alice_init)
+                .unwrap();
+            opener
+        };
+        let mut first = vec![];
+        sealer.seal(b"first", &mut first).unwrap();
+        let mut second = vec![];
+        sealer.seal(b"second", &mut second).unwrap();
+
+        // altered ciphertext
+        let mut opener = make_opener(&alice_init);
+        let mut altered = first.clone();
+        *altered.last_mut().unwrap() ^= 0x01;
+        assert!(matches!(
+            opener.open(&mut altered),
+            Err(net_error::SessionError(..))
+        ));
+
+        // reordered frames
+        let mut opener = make_opener(&alice_init);
+        let mut reordered = second.clone();
+        assert!(matches!(
+            opener.open(&mut reordered),
+            Err(net_error::SessionError(..))
+        ));
+
+        // replayed frame
+        let mut opener = make_opener(&alice_init);
+        let mut replayed = first.clone();
+        replayed.extend_from_slice(&first);
+        assert!(matches!(
+            opener.open(&mut replayed),
+            Err(net_error::SessionError(..))
+        ));
+
+        // oversized frame length
+        let mut opener = make_opener(&alice_init);
+        let mut oversized = ((MAX_SESSION_FRAME_LEN + FRAME_TAG_LEN + 1) as u32)
+            .to_be_bytes()
+            .to_vec();
+        assert!(matches!(
+            opener.open(&mut oversized),
+            Err(net_error::SessionError(..))
+        ));
+
+        // a third party who claims to be alice, but does not have alice's key, cannot produce
+        // frames that bob will open
+        let mut mallory = Secp256k1PrivateKey::random();
+        mallory.set_compress_public(true);
+        let (mallory_session, forged_init) = PendingSession::new();
+        let (mut forger, _) = mallory_session
+            .finish(&mallory, &bob_pubk, &bob_init)
+            .unwrap();
+        let mut forged = vec![];
+        forger.seal(b"first", &mut forged).unwrap();
+        let mut opener = make_opener(&forged_init);
+        assert!(matches!(
+            opener.open(&mut forged),
+            Err(net_error::SessionError(..))
+        ));
+
+        // a recorded session cannot be replayed to bob in another connection, since bob's new
+        // ephemeral key goes into the key
+        let (new_bob_session, _) = PendingSession::new();
+        let (_, mut opener) = new_bob_session
+            .finish(&bob, &alice_pubk, &alice_init)
+            .unwrap();
+        let mut replayed = first.clone();
+        assert!(matches!(
+            opener.open(&mut replayed),
+            Err(net_error::SessionError(..))
+        ));
+
+        // and the untampered stream still opens
+        let mut opener = make_opener(&alice_init);
+        let mut stream = first;
+        stream.extend_from_slice(&second);
+        assert_eq!(opener.open(&mut stream).unwrap(), b"firstsecond".to_vec());
+    }
+}
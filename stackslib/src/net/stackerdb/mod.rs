@@ -129,6 +129,7 @@ use crate::chainstate::nakamoto::NakamotoChainState;
 use crate::chainstate::stacks::boot::MINERS_NAME;
 use crate::chainstate::stacks::db::StacksChainState;
 use crate::net::connection::ConnectionOptions;
+use crate::net::neighbors::reputation::PeerReputationEvent;
 use crate::net::neighbors::NeighborComms;
 use crate::net::p2p::PeerNetwork;
 use crate::net::{
@@ -700,6 +701,19 @@ impl PeerNetwork {
                     &chunk_data.chunk_data,
                     &data.slot_versions,
                 )? {
+                    // a stale chunk can be an honest race with another writer, so only
+                    // penalize the sender for chunks that could never have been valid
+                    let is_stale = data
+                        .slot_versions
+                        .get(chunk_data.chunk_data.slot_id as usize)
+                        .is_some_and(|version| chunk_data.chunk_data.slot_version < *version);
+                    if let Some(nk) = self
+                        .get_p2p_convo(event_id)
+                        .filter(|_| !is_stale)
+                        .map(|convo| convo.to_neighbor_key())
+                    {
+                        self.penalize_neighbor(&nk, PeerReputationEvent::InvalidStackerDBChunk);
+                    }
                     return Ok((false, false));
                 }
 
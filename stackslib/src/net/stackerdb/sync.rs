@@ -25,6 +25,7 @@ use stacks_common::util::get_epoch_time_secs;
 
 use crate::net::db::PeerDB;
 use crate::net::neighbors::comms::ToNeighborKey;
+use crate::net::neighbors::reputation::PeerReputationEvent;
 use crate::net::neighbors::NeighborComms;
 use crate::net::p2p::PeerNetwork;
 use crate::net::stackerdb::{
@@ -1161,6 +1162,14 @@ impl<NC: NeighborComms> StackerDBSync<NC> {
                     data.slot_id
                 );
                 self.unpin_connected_replica(network, &naddr);
+                let is_stale = self
+                    .expected_versions
+                    .get(data.slot_id as usize)
+                    .is_some_and(|version| data.slot_version < *version);
+                if !is_stale {
+                    let nk = naddr.to_neighbor_key(network);
+                    network.penalize_neighbor(&nk, PeerReputationEvent::InvalidStackerDBChunk);
+                }
                 continue;
             }
 
@@ -35,15 +35,18 @@ use crate::chainstate::stacks::{
 };
 use crate::net::api::getneighbors::{RPCNeighbor, RPCNeighborsInfo};
 use crate::net::connection::ConnectionOptions;
+use crate::net::http::tls::make_client_config;
 use crate::net::http::{
-    http_error_from_code_and_text, http_reason, HttpContentType, HttpRequestContents,
-    HttpRequestPreamble, HttpResponsePayload, HttpResponsePreamble, HttpVersion,
+    http_error_from_code_and_text, http_reason, HttpContentType, HttpRateLimitClass,
+    HttpRequestContents, HttpRequestPreamble, HttpResponsePayload, HttpResponsePreamble,
+    HttpTlsServer, HttpVersion,
 };
 use crate::net::httpcore::{
-    send_http_request, HttpPreambleExtensions, HttpRequestContentsExtensions, StacksHttp,
-    StacksHttpMessage, StacksHttpPreamble, StacksHttpRequest, StacksHttpResponse,
+    send_http_request, send_https_request, HttpPreambleExtensions, HttpRequestContentsExtensions,
+    StacksHttp, StacksHttpMessage, StacksHttpPreamble, StacksHttpRequest, StacksHttpResponse,
 };
 use crate::net::rpc::ConversationHttp;
+use crate::net::tests::tls_test_file_path;
 use crate::net::{ProtocolFamily, TipRequest};
 
 #[test]
@@ -776,6 +779,66 @@ fn test_http_response_type_codec() {
     }
 }
 
+#[test]
+fn test_http_rate_limit_class() {
+    let mut conn_opts = ConnectionOptions::default();
+    conn_opts.auth_token = Some("password".into());
+    let http = StacksHttp::new("127.0.0.1:20443".parse().unwrap(), &conn_opts);
+    let peer_host = PeerHost::DNS("localhost".to_string(), 20443);
+
+    let tests = [
+        (
+            "POST",
+            "/v2/contracts/call-read/SP000000000000000000002Q6VF78/pox-4/get-pox-info",
+            HttpRateLimitClass::ReadOnlyCall,
+        ),
+        (
+            "POST",
+            "/v3/contracts/fast-call-read/SP000000000000000000002Q6VF78/pox-4/get-pox-info",
+            HttpRateLimitClass::ReadOnlyCall,
+        ),
+        ("POST", "/v2/transactions", HttpRateLimitClass::TxBroadcast),
+        (
+            "GET",
+            "/v3/blocks/1111111111111111111111111111111111111111111111111111111111111111",
+            HttpRateLimitClass::BlockFetch,
+        ),
+        (
+            "GET",
+            "/v2/blocks/1111111111111111111111111111111111111111111111111111111111111111",
+            HttpRateLimitClass::BlockFetch,
+        ),
+        (
+            "GET",
+            "/v3/blocks/height/123",
+            HttpRateLimitClass::BlockFetch,
+        ),
+        ("GET", "/v2/info", HttpRateLimitClass::Other),
+        ("GET", "/no/such/endpoint", HttpRateLimitClass::Other),
+    ];
+    for (verb, path, expected_class) in tests {
+        let mut request = StacksHttpRequest::new_for_peer(
+            peer_host.clone(),
+            verb.to_string(),
+            path.to_string(),
+            HttpRequestContents::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            http.rate_limit_class(&mut request),
+            expected_class,
+            "{verb} {path}"
+        );
+        assert!(http.request_api_key(&request).is_none());
+
+        request.add_header("authorization".into(), "password".into());
+        assert_eq!(
+            http.request_api_key(&request).map(|key| key.name.as_str()),
+            Some("auth_token")
+        );
+    }
+}
+
 #[test]
 fn test_http_response_type_codec_err() {
     let request_paths = vec![
@@ -1227,3 +1290,65 @@ fn test_send_request_success() {
         "Expected a successful request, but got {result:?}"
     );
 }
+
+/// Serve `response` over TLS to the next `num_connections` clients, using the certificate
+/// issued by `ca-1.pem`.  Returns the port the server listens on.
+fn start_mock_tls_server(response: String, num_connections: usize) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind server");
+    let port = listener.local_addr().unwrap().port();
+    let server = HttpTlsServer::new(
+        &tls_test_file_path("server-1.pem"),
+        &tls_test_file_path("server-1.key"),
+    )
+    .unwrap();
+
+    thread::spawn(move || {
+        for stream in listener.incoming().take(num_connections) {
+            let session = server.new_session().unwrap();
+            let mut stream = rustls::StreamOwned::new(session, stream.unwrap());
+
+            // this fails if the client rejects our certificate
+            let mut buffer = [0; 512];
+            if let Err(e) = stream.read(&mut buffer) {
+                debug!("Mock TLS server failed to read request: {e:?}");
+                continue;
+            }
+            stream
+                .write_all(response.as_bytes())
+                .expect("Failed to write response");
+            stream.conn.send_close_notify();
+            stream.flush().expect("Failed to flush stream");
+        }
+    });
+    port
+}
+
+#[test]
+fn test_send_https_request() {
+    let mock_response = "HTTP/1.1 200 OK\r\nContent-Length: 13\r\n\r\nHello, world!";
+    let port = start_mock_tls_server(mock_response.to_string(), 2);
+    let timeout_duration = Duration::from_secs(5);
+
+    // the server's certificate was issued by the pinned CA
+    let tls_config = make_client_config(Some(&tls_test_file_path("ca-1.pem"))).unwrap();
+    let response = send_https_request(
+        "127.0.0.1",
+        port,
+        json_body("127.0.0.1", port, "/", b"{}"),
+        timeout_duration,
+        tls_config,
+    )
+    .unwrap();
+    assert_eq!(parse_http_response(response), "Hello, world!");
+
+    // ...but not by this one
+    let tls_config = make_client_config(Some(&tls_test_file_path("ca-2.pem"))).unwrap();
+    let result = send_https_request(
+        "127.0.0.1",
+        port,
+        json_body("127.0.0.1", port, "/", b"{}"),
+        timeout_duration,
+        tls_config,
+    );
+    assert!(result.is_err(), "Expected a TLS error, but got {result:?}");
+}
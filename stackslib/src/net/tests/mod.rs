@@ -71,6 +71,13 @@ use crate::net::{
 use crate::util_lib::boot::boot_code_id;
 use crate::util_lib::signed_structured_data::pox4::make_pox_4_signer_key_signature;
 
+/// Path to a TLS test certificate or key in `tls/`.  `ca-N.pem` is a CA that issued
+/// `server-N.pem` (with key `server-N.key`), which is valid for `localhost` and `127.0.0.1`.
+/// All of them were generated with `openssl` and expire in 2126.
+pub fn tls_test_file_path(name: &str) -> String {
+    format!("{}/src/net/tests/tls/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
 /// One step of a simulated Nakamoto node's bootup procedure.
 #[derive(Debug, PartialEq, Clone)]
 pub enum NakamotoBootStep {